@@ -57,7 +57,8 @@ fn main() {
     }
 }
 
-/// Creates a zip archive `template.zip` of the `new` project template in `out_dir`.
+/// Creates a zip archive `template.zip` of the `new` project templates in `out_dir`, with
+/// one top level directory per template.
 fn zip_template(manifest_dir: &Path, out_dir: &Path) -> Result<()> {
     let template_dir = manifest_dir.join("templates").join("new");
     let template_dst_file = out_dir.join("template.zip");
@@ -152,6 +152,9 @@ pub enum Target {
     RiscV,
 }
 
+/// The magic bytes every Wasm module starts with.
+const WASM_MAGIC: [u8; 4] = *b"\0asm";
+
 impl Target {
     /// The target string to be passed to rustc in order to build for this target.
     pub fn llvm_target(&self) -> &'static str {
@@ -181,7 +184,24 @@ impl Target {
     pub fn dest_extension(&self) -> &'static str {
         match self {
             Self::Wasm => "wasm",
-            Self::RiscV => "riscv",
+            Self::RiscV => "polkavm",
+        }
+    }
+
+    /// Returns `true` if this is the default Wasm target.
+    pub fn is_wasm(&self) -> bool {
+        matches!(self, Self::Wasm)
+    }
+
+    /// Detects the target of the contract `code` from its magic bytes. Returns `None`
+    /// if the code is neither a Wasm module nor a PolkaVM program blob.
+    pub fn from_code(code: &[u8]) -> Option<Self> {
+        if code.starts_with(&WASM_MAGIC) {
+            Some(Self::Wasm)
+        } else if code.starts_with(&crate::polkavm::POLKAVM_MAGIC) {
+            Some(Self::RiscV)
+        } else {
+            None
         }
     }
 }
@@ -252,11 +272,23 @@ impl TryFrom<&UnstableOptions> for UnstableFlags {
 }
 
 /// Define the standard `cargo` features args to be passed through.
-#[derive(Default, Clone, Debug, Args)]
+#[derive(
+    Default, Clone, Debug, Eq, PartialEq, Args, serde::Serialize, serde::Deserialize,
+)]
 pub struct Features {
     /// Space or comma separated list of features to activate
     #[clap(long, value_delimiter = ',')]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     features: Vec<String>,
+    /// Do not activate the `default` features of the contract when generating the
+    /// metadata. The contract code is always built without its `default` features.
+    #[clap(long)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    no_default_features: bool,
+    /// Activate all features of the contract, except for `std`.
+    #[clap(long, conflicts_with = "features")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    all_features: bool,
 }
 
 impl Features {
@@ -265,6 +297,53 @@ impl Features {
         self.features.push(feature.to_owned())
     }
 
+    /// Returns `true` if no feature flags are set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns a copy with `--all-features` resolved to the list of the features
+    /// declared by the contract package, excluding `default` and `std`.
+    ///
+    /// The contract code is built for a `no_std` target, so it must never activate
+    /// `std` as `cargo build --all-features` would do.
+    pub fn resolve(&self, package: &cargo_metadata::Package) -> Self {
+        let mut features = self.clone();
+        if self.all_features {
+            features.all_features = false;
+            features.features = package
+                .features
+                .keys()
+                .filter(|feature| *feature != "default" && *feature != "std")
+                .cloned()
+                .collect();
+        }
+        features
+    }
+
+    /// Returns a suffix for the names of the contract artifacts if the features change
+    /// the build output, e.g. `mock-oracle` for `--features mock-oracle`.
+    pub fn artifact_suffix(&self) -> Option<String> {
+        let mut parts = self
+            .features
+            .iter()
+            .map(|feature| feature.replace('/', "_"))
+            .collect::<Vec<_>>();
+        parts.sort();
+        if self.all_features {
+            parts.push("all-features".to_owned());
+        }
+        if self.no_default_features {
+            parts.push("no-default-features".to_owned());
+        }
+        (!parts.is_empty()).then(|| parts.join("-"))
+    }
+
+    /// Returns whether the `default` features of the contract are disabled.
+    pub fn no_default_features(&self) -> bool {
+        self.no_default_features
+    }
+
     /// Appends the raw features args to pass through to the `cargo` invocation.
     pub fn append_to_args(&self, args: &mut Vec<String>) {
         if !self.features.is_empty() {
@@ -276,5 +355,8 @@ impl Features {
             };
             args.push(features);
         }
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
     }
 }
@@ -34,7 +34,10 @@ use serde_json::{
 };
 use std::{
     fs,
-    path::PathBuf,
+    path::{
+        Path,
+        PathBuf,
+    },
 };
 use toml::value;
 use url::Url;
@@ -53,6 +56,9 @@ pub struct CrateMetadata {
     pub homepage: Option<Url>,
     pub user: Option<Map<String, Value>>,
     pub target_directory: PathBuf,
+    /// The target directory of the `cargo` invocations, shared by all contracts of a
+    /// workspace so that their dependencies are only compiled once.
+    pub cargo_target_directory: PathBuf,
     pub target_file_path: PathBuf,
 }
 
@@ -69,7 +75,8 @@ impl CrateMetadata {
     /// Parses the contract manifest and returns relevant metadata.
     pub fn collect(manifest_path: &ManifestPath, target: Target) -> Result<Self> {
         let (metadata, root_package) = get_cargo_metadata(manifest_path)?;
-        let mut target_directory = metadata.target_directory.as_path().join("ink");
+        let cargo_target_directory = metadata.target_directory.as_path().join("ink");
+        let mut target_directory = cargo_target_directory.clone();
 
         // Normalize the final contract artifact name.
         let contract_artifact_name = root_package.name.replace('-', "_");
@@ -101,8 +108,8 @@ impl CrateMetadata {
             target_directory = target_directory.join(contract_artifact_name.clone());
         }
 
-        // {target_dir}/{target}/release/{contract_artifact_name}.{extension}
-        let mut original_code = target_directory.clone();
+        // {cargo_target_dir}/{target}/release/{contract_artifact_name}.{extension}
+        let mut original_code = cargo_target_directory.clone();
         original_code.push(target.llvm_target());
         original_code.push("release");
         original_code.push(root_package.name.clone());
@@ -132,6 +139,7 @@ impl CrateMetadata {
             documentation,
             homepage,
             user,
+            output_dir,
         } = get_cargo_toml_metadata(manifest_path)?;
 
         let crate_metadata = CrateMetadata {
@@ -147,8 +155,41 @@ impl CrateMetadata {
             user,
             target_file_path: target_directory.join(".target").into(),
             target_directory: target_directory.into(),
+            cargo_target_directory: cargo_target_directory.into(),
         };
-        Ok(crate_metadata)
+        crate_metadata.with_output_dir(output_dir.as_deref())
+    }
+
+    /// Appends the suffix to the names of the contract artifacts, e.g. for a build with
+    /// features which change the output.
+    pub fn with_artifact_suffix(self, suffix: Option<String>) -> Self {
+        let Some(suffix) = suffix else { return self };
+        let mut this = self;
+        this.contract_artifact_name =
+            format!("{}-{}", this.contract_artifact_name, suffix);
+        let extension = this.dest_code.extension().map(ToOwned::to_owned);
+        this.dest_code.set_file_name(&this.contract_artifact_name);
+        if let Some(extension) = extension {
+            this.dest_code.set_extension(extension);
+        }
+        this
+    }
+
+    /// Writes the contract artifacts to the given directory instead of the target
+    /// directory. A relative path is interpreted relative to the manifest directory.
+    ///
+    /// The intermediate build files stay in the `cargo` target directory.
+    pub fn with_output_dir(self, output_dir: Option<&Path>) -> Result<Self> {
+        let Some(output_dir) = output_dir else {
+            return Ok(self)
+        };
+        let mut this = self;
+        let output_dir = this.manifest_path.absolute_directory()?.join(output_dir);
+        if let Some(file_name) = this.dest_code.file_name() {
+            this.dest_code = output_dir.join(file_name);
+        }
+        this.target_directory = output_dir;
+        Ok(this)
     }
 
     /// Get the path of the contract metadata file
@@ -165,6 +206,57 @@ impl CrateMetadata {
     }
 }
 
+/// Returns whether the manifest is a virtual manifest, i.e. a workspace root without a
+/// `[package]` section.
+pub fn is_virtual_manifest(manifest_path: &ManifestPath) -> Result<bool> {
+    let toml = fs::read_to_string(manifest_path)?;
+    let toml: value::Table = toml::from_str(&toml)?;
+    Ok(!toml.contains_key("package"))
+}
+
+/// Returns the manifest paths of the ink! contracts among the members of the workspace
+/// of the given manifest, optionally restricted to the given `packages`.
+///
+/// A member is an ink! contract if it depends on `ink` or has a
+/// `[package.metadata.contract]` section.
+pub fn workspace_contracts(
+    manifest_path: &ManifestPath,
+    packages: &[String],
+) -> Result<Vec<ManifestPath>> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(manifest_path.as_ref())
+        .no_deps()
+        .exec()
+        .with_context(|| {
+            format!(
+                "Error invoking `cargo metadata` for {}",
+                manifest_path.as_ref().display()
+            )
+        })?;
+    let contracts = metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| {
+            package.dependencies.iter().any(|dep| dep.name == "ink")
+                || package.metadata.get("contract").is_some()
+        })
+        .collect::<Vec<_>>();
+    if let Some(unknown) = packages
+        .iter()
+        .find(|name| !contracts.iter().any(|package| &package.name == *name))
+    {
+        anyhow::bail!("Package `{unknown}` is not an ink! contract of the workspace")
+    }
+    if contracts.is_empty() {
+        anyhow::bail!("No ink! contracts found in the workspace")
+    }
+    contracts
+        .into_iter()
+        .filter(|package| packages.is_empty() || packages.contains(&package.name))
+        .map(|package| ManifestPath::new(&package.manifest_path))
+        .collect()
+}
+
 /// Get the result of `cargo metadata`, together with the root package id.
 fn get_cargo_metadata(manifest_path: &ManifestPath) -> Result<(CargoMetadata, Package)> {
     tracing::debug!(
@@ -203,6 +295,7 @@ struct ExtraMetadata {
     documentation: Option<Url>,
     homepage: Option<Url>,
     user: Option<Map<String, Value>>,
+    output_dir: Option<PathBuf>,
 }
 
 /// Read extra metadata not available via `cargo metadata` directly from `Cargo.toml`
@@ -236,9 +329,22 @@ fn get_cargo_toml_metadata(manifest_path: &ManifestPath) -> Result<ExtraMetadata
         })
         .transpose()?;
 
+    let output_dir = toml
+        .get("package")
+        .and_then(|v| v.get("metadata"))
+        .and_then(|v| v.get("contract"))
+        .and_then(|v| v.get("output-dir"))
+        .map(|v| {
+            v.as_str()
+                .map(PathBuf::from)
+                .context("output-dir should be a path")
+        })
+        .transpose()?;
+
     Ok(ExtraMetadata {
         documentation,
         homepage,
         user,
+        output_dir,
     })
 }
@@ -89,6 +89,7 @@ use crate::{
     BuildResult,
     CrateMetadata,
     ExecuteArgs,
+    GitInfo,
     Verbosity,
 };
 
@@ -128,6 +129,7 @@ pub fn docker_build(args: ExecuteArgs) -> Result<BuildResult> {
         output_type,
         target,
         image,
+        no_git_info,
         ..
     } = args;
     tokio::runtime::Builder::new_multi_thread()
@@ -166,7 +168,14 @@ pub fn docker_build(args: ExecuteArgs) -> Result<BuildResult> {
 
             update_build_result(&host_folder, &mut build_result)?;
 
-            update_metadata(&build_result, &verbosity, &image, &client).await?;
+            // the repository of the contract may not be available in the container
+            let git = if no_git_info {
+                None
+            } else {
+                GitInfo::collect(&crate_metadata.manifest_path.absolute_directory()?)
+            };
+
+            update_metadata(&build_result, &verbosity, &image, git, &client).await?;
 
             verbose_eprintln!(
                 verbosity,
@@ -225,6 +234,7 @@ async fn update_metadata(
     build_result: &BuildResult,
     verbosity: &Verbosity,
     build_image: &str,
+    git: Option<GitInfo>,
     client: &Docker,
 ) -> Result<()> {
     if let Some(metadata_artifacts) = &build_result.metadata_result {
@@ -245,6 +255,13 @@ async fn update_metadata(
 
         metadata.image = Some(image_tag);
 
+        if let (Some(build_info), Some(git)) = (metadata.source.build_info.as_mut(), git)
+        {
+            if !build_info.contains_key("git") {
+                build_info.insert("git".into(), serde_json::to_value(git)?);
+            }
+        }
+
         crate::metadata::write_metadata(metadata_artifacts, metadata, verbosity, true)?;
     }
     Ok(())
@@ -28,6 +28,8 @@ mod crate_metadata;
 mod docker;
 pub mod metadata;
 mod new;
+mod polkavm;
+mod size_report;
 #[cfg(test)]
 mod tests;
 pub mod util;
@@ -51,17 +53,37 @@ pub use self::{
         Verbosity,
         VerbosityFlags,
     },
-    crate_metadata::CrateMetadata,
+    crate_metadata::{
+        is_virtual_manifest,
+        CrateMetadata,
+    },
     metadata::{
         BuildInfo,
+        GitInfo,
         MetadataArtifacts,
         WasmOptSettings,
     },
-    new::new_contract_project,
+    new::{
+        new_contract_project,
+        new_contract_project_from_template,
+        Template,
+        TEMPLATES,
+    },
+    size_report::{
+        CrateSize,
+        FunctionSize,
+        SizeReport,
+    },
     util::DEFAULT_KEY_COL_WIDTH,
+    validate_wasm::{
+        FloatInstructions,
+        HostFunction,
+        WasmValidation,
+    },
     wasm_opt::{
         OptimizationPasses,
         OptimizationResult,
+        WasmOptConfig,
     },
     workspace::{
         Lto,
@@ -142,6 +164,16 @@ pub struct ExecuteArgs {
     pub target: Target,
     pub max_memory_pages: u32,
     pub image: ImageVariant,
+    /// The directory of the contract artifacts, if not the target directory.
+    pub output_dir: Option<PathBuf>,
+    /// Whether to report the size of the contract code by function.
+    pub size_report: bool,
+    /// The custom configuration of `wasm-opt`, overriding the one of the manifest.
+    pub wasm_opt_config: Option<WasmOptConfig>,
+    /// Do not embed the state of the git repository into the build info.
+    pub no_git_info: bool,
+    /// Post process the code and generate the metadata even if they are up to date.
+    pub force: bool,
 }
 
 impl Default for ExecuteArgs {
@@ -162,6 +194,11 @@ impl Default for ExecuteArgs {
             target: Default::default(),
             max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
             image: Default::default(),
+            output_dir: Default::default(),
+            size_report: Default::default(),
+            wasm_opt_config: Default::default(),
+            no_git_info: Default::default(),
+            force: Default::default(),
         }
     }
 }
@@ -185,11 +222,44 @@ pub struct BuildResult {
     pub verbosity: Verbosity,
     /// Image used for the verifiable build
     pub image: Option<String>,
+    /// The breakdown of the contract code size, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_report: Option<SizeReport>,
+    /// The hex encoded hash of the resulting Wasm code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<String>,
+    /// The versions of the tools the contract was built with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<BuildToolchain>,
+    /// The warnings reported while validating the contract.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// The findings of the validation of the contract Wasm, unless skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_validation: Option<WasmValidation>,
     /// The type of formatting to use for the build output.
     #[serde(skip_serializing, skip_deserializing)]
     pub output_type: OutputType,
 }
 
+/// The versions of the tools used to build a contract.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BuildToolchain {
+    /// The Rust toolchain used to build the contract.
+    pub rust_toolchain: String,
+    /// The version of `cargo-contract` used to build the contract.
+    pub cargo_contract_version: Version,
+}
+
+impl From<&BuildInfo> for BuildToolchain {
+    fn from(build_info: &BuildInfo) -> Self {
+        Self {
+            rust_toolchain: build_info.rust_toolchain.clone(),
+            cargo_contract_version: build_info.cargo_contract_version.clone(),
+        }
+    }
+}
+
 impl BuildResult {
     pub fn display(&self) -> String {
         let opt_size_diff = if let Some(ref opt_result) = self.optimization_result {
@@ -224,6 +294,9 @@ impl BuildResult {
                     .to_string()
                     .bold()
             );
+            if let Some(size_report) = self.size_report.as_ref() {
+                return format!("{out}\n\n{}", size_report.display())
+            }
             return out
         };
 
@@ -254,6 +327,10 @@ impl BuildResult {
             );
             out.push_str(&metadata);
         }
+        if let Some(size_report) = self.size_report.as_ref() {
+            out.push_str("\n\n");
+            out.push_str(&size_report.display());
+        }
         out
     }
 
@@ -263,6 +340,34 @@ impl BuildResult {
     }
 }
 
+/// Returns the `rustc` flags which remap the machine specific absolute paths of the
+/// sources, e.g. embedded in panic messages, so that the code does not depend on the
+/// location of the contract and of the `cargo` and `rustup` homes.
+fn remap_path_prefixes(crate_metadata: &CrateMetadata) -> Vec<String> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let home_dir = |var: &str, default: &str| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(default)))
+    };
+    let mut prefixes = Vec::new();
+    if let Some(rustup_home) = home_dir("RUSTUP_HOME", ".rustup") {
+        prefixes.push((rustup_home, "/rustup".to_owned()));
+    }
+    if let Some(cargo_home) = home_dir("CARGO_HOME", ".cargo") {
+        prefixes.push((cargo_home, "/cargo".to_owned()));
+    }
+    // the last matching prefix takes precedence, the workspace is the most specific one
+    prefixes.push((
+        crate_metadata.cargo_meta.workspace_root.clone().into(),
+        "/contract".to_owned(),
+    ));
+    prefixes
+        .into_iter()
+        .map(|(from, to)| format!("--remap-path-prefix={}={}", from.display(), to))
+        .collect()
+}
+
 /// Executes the supplied cargo command on the project in the specified directory,
 /// defaults to the current directory.
 ///
@@ -298,13 +403,13 @@ fn exec_cargo_for_onchain_target(
     let cargo_build = |manifest_path: &ManifestPath| {
         let target_dir = format!(
             "--target-dir={}",
-            crate_metadata.target_directory.to_string_lossy()
+            crate_metadata.cargo_target_directory.to_string_lossy()
         );
         let mut args = vec![target_dir, "--release".to_owned()];
         args.extend(onchain_cargo_options(target));
         network.append_to_args(&mut args);
 
-        let mut features = features.clone();
+        let mut features = features.resolve(&crate_metadata.root_package);
         if build_mode == &BuildMode::Debug {
             features.push("ink/ink-debug");
         } else {
@@ -325,11 +430,13 @@ fn exec_cargo_for_onchain_target(
         // to live with duplicated warnings. For the metadata build we can disable
         // warnings.
         let rustflags = {
-            let common_flags = "-Clinker-plugin-lto";
+            let mut common_flags = vec!["-Clinker-plugin-lto".to_owned()];
+            common_flags.extend(remap_path_prefixes(crate_metadata));
+            let common_flags = common_flags.join("\x1f");
             if let Some(target_flags) = target.rustflags() {
                 format!("{}\x1f{}", common_flags, target_flags)
             } else {
-                common_flags.to_string()
+                common_flags
             }
         };
 
@@ -560,7 +667,7 @@ fn exec_cargo_dylint(
     // macros, as it does for the release build.
     args.extend(onchain_cargo_options(target));
 
-    let target_dir = &crate_metadata.target_directory.to_string_lossy();
+    let target_dir = &crate_metadata.cargo_target_directory.to_string_lossy();
     let env = vec![
         // We need to set the `CARGO_TARGET_DIR` environment variable in
         // case `cargo dylint` is invoked.
@@ -770,6 +877,7 @@ fn post_process_wasm(
 
     if !skip_wasm_validation {
         validate_wasm::validate_import_section(&module)?;
+        WasmValidation::new(&module.clone().into_bytes()?)?.ensure_valid(false)?;
     } else {
         verbose_eprintln!(
             verbosity,
@@ -820,6 +928,10 @@ pub fn execute(args: ExecuteArgs) -> Result<BuildResult> {
         extra_lints,
         output_type,
         target,
+        output_dir,
+        size_report,
+        wasm_opt_config,
+        force,
         ..
     } = &args;
 
@@ -828,13 +940,13 @@ pub fn execute(args: ExecuteArgs) -> Result<BuildResult> {
         return docker_build(args)
     }
 
+    let mut manifest = Manifest::new(manifest_path.clone())?;
+
     // The CLI flag `optimization-passes` overwrites optimization passes which are
     // potentially defined in the `Cargo.toml` profile.
     let optimization_passes = match optimization_passes {
         Some(opt_passes) => *opt_passes,
         None => {
-            let mut manifest = Manifest::new(manifest_path.clone())?;
-
             match manifest.profile_optimization_passes() {
                 // if no setting is found, neither on the cli nor in the profile,
                 // then we use the default
@@ -844,54 +956,95 @@ pub fn execute(args: ExecuteArgs) -> Result<BuildResult> {
         }
     };
 
-    let crate_metadata = CrateMetadata::collect(manifest_path, *target)?;
+    // Likewise `--wasm-opt-args` overwrites the `wasm-opt` configuration of the profile.
+    let wasm_opt_config = match wasm_opt_config {
+        Some(config) => config.clone(),
+        None => manifest.profile_wasm_opt_config()?.unwrap_or_default(),
+    };
+    // fail before building if the passes are not supported
+    wasm_opt_config.validate()?;
+
+    let crate_metadata = CrateMetadata::collect(manifest_path, *target)?
+        .with_artifact_suffix(features.artifact_suffix())
+        .with_output_dir(output_dir.as_deref())?;
+
+    if *size_report && target != &Target::Wasm {
+        anyhow::bail!("The size report is only supported for the Wasm target")
+    }
+    // The settings of the profile are ignored for other targets, so that a contract can
+    // be built for any target without changing its manifest.
+    if target != &Target::Wasm
+        && (args.optimization_passes.is_some() || args.wasm_opt_config.is_some())
+    {
+        anyhow::bail!(
+            "`wasm-opt` is not run for the RISC-V target, remove the \
+            `--optimization-passes` and `--wasm-opt-args` options"
+        )
+    }
 
     if build_mode == &BuildMode::Debug {
         assert_debug_mode_supported(&crate_metadata.ink_version)?;
     }
 
+    let mut warnings = Vec::new();
     if let Err(e) = check_contract_ink_compatibility(&crate_metadata.ink_version, None) {
         eprintln!("{} {}", "warning:".yellow().bold(), e.to_string().bold());
+        warnings.push(e.to_string());
     }
 
     let clean_metadata = || {
         fs::remove_file(crate_metadata.metadata_path()).ok();
         fs::remove_file(crate_metadata.contract_bundle_path()).ok();
+        fs::remove_file(Fingerprint::metadata_path(&crate_metadata)).ok();
     };
 
-    let (opt_result, metadata_result, dest_wasm) = match build_artifact {
+    let (opt_result, metadata_result, dest_wasm, toolchain) = match build_artifact {
         BuildArtifacts::CheckOnly => {
             // Check basically means only running our linter without building.
             lint(*extra_lints, &crate_metadata, target, verbosity)?;
-            (None, None, None)
+            (None, None, None, None)
         }
         BuildArtifacts::CodeOnly => {
             // when building only the code metadata will become stale
             clean_metadata();
-            let (opt_result, _, dest_wasm) =
-                local_build(&crate_metadata, &optimization_passes, &args)?;
-            (opt_result, None, Some(dest_wasm))
+            let (opt_result, build_info, dest_wasm) = local_build(
+                &crate_metadata,
+                &optimization_passes,
+                &wasm_opt_config,
+                &args,
+            )?;
+            let toolchain = BuildToolchain::from(&build_info);
+            (opt_result, None, Some(dest_wasm), Some(toolchain))
         }
         BuildArtifacts::All => {
-            let (opt_result, build_info, dest_wasm) =
-                local_build(&crate_metadata, &optimization_passes, &args).map_err(
-                    |e| {
-                        // build error -> bundle is stale
-                        clean_metadata();
-                        e
-                    },
-                )?;
+            let (opt_result, build_info, dest_wasm) = local_build(
+                &crate_metadata,
+                &optimization_passes,
+                &wasm_opt_config,
+                &args,
+            )
+            .inspect_err(|_| {
+                // build error -> bundle is stale
+                clean_metadata();
+            })?;
 
             let metadata_result = MetadataArtifacts {
                 dest_metadata: crate_metadata.metadata_path(),
                 dest_bundle: crate_metadata.contract_bundle_path(),
             };
 
-            // skip metadata generation if contract unchanged and all metadata artifacts
-            // exist.
-            if opt_result.is_some()
+            let toolchain = BuildToolchain::from(&build_info);
+            let metadata_fingerprint = Fingerprint::of_metadata(&dest_wasm, &build_info)?;
+            let metadata_fingerprint_path = Fingerprint::metadata_path(&crate_metadata);
+
+            // skip metadata generation if contract unchanged, all metadata artifacts
+            // exist and were generated with the same build info, e.g. git state.
+            if *force
+                || opt_result.is_some()
                 || !metadata_result.dest_metadata.exists()
                 || !metadata_result.dest_bundle.exists()
+                || Fingerprint::load(&metadata_fingerprint_path).as_ref()
+                    != Some(&metadata_fingerprint)
             {
                 // if metadata build fails after a code build it might become stale
                 clean_metadata();
@@ -905,11 +1058,39 @@ pub fn execute(args: ExecuteArgs) -> Result<BuildResult> {
                     unstable_flags,
                     build_info,
                 )?;
+                metadata_fingerprint.store(&metadata_fingerprint_path)?;
+            } else {
+                verbose_eprintln!(
+                    verbosity,
+                    " {} {}",
+                    "[==]".bold(),
+                    "Generating metadata skipped (up to date)"
+                        .bright_green()
+                        .bold()
+                );
             }
-            (opt_result, Some(metadata_result), Some(dest_wasm))
+            (
+                opt_result,
+                Some(metadata_result),
+                Some(dest_wasm),
+                Some(toolchain),
+            )
         }
     };
 
+    let code = dest_wasm.as_ref().map(fs::read).transpose()?;
+    let size_report = match code.as_deref() {
+        Some(code) if *size_report => Some(SizeReport::new(code)?),
+        _ => None,
+    };
+    let wasm_validation = match code.as_deref() {
+        Some(code) if target == &Target::Wasm && !args.skip_wasm_validation => {
+            Some(WasmValidation::new(code)?)
+        }
+        _ => None,
+    };
+    let code_hash = code.map(|code| format!("0x{}", hex::encode(code_hash(&code))));
+
     Ok(BuildResult {
         dest_wasm,
         metadata_result,
@@ -919,14 +1100,48 @@ pub fn execute(args: ExecuteArgs) -> Result<BuildResult> {
         build_artifact: *build_artifact,
         verbosity: *verbosity,
         image: None,
+        size_report,
+        code_hash,
+        toolchain,
+        warnings,
+        wasm_validation,
         output_type: output_type.clone(),
     })
 }
 
+/// Executes the build of all ink! contracts of the workspace of `args.manifest_path`,
+/// or of the given `packages` of the workspace only.
+///
+/// The contracts share the `cargo` target directory, so that common dependencies are
+/// compiled only once, and their artifacts are written to `target/ink/<name>/`.
+pub fn execute_workspace(
+    args: ExecuteArgs,
+    packages: &[String],
+) -> Result<Vec<BuildResult>> {
+    crate_metadata::workspace_contracts(&args.manifest_path, packages)?
+        .into_iter()
+        .map(|manifest_path| {
+            verbose_eprintln!(
+                args.verbosity,
+                " {} {}",
+                "[==]".bold(),
+                format!("Building {}", manifest_path.as_ref().display())
+                    .bright_green()
+                    .bold()
+            );
+            execute(ExecuteArgs {
+                manifest_path,
+                ..args.clone()
+            })
+        })
+        .collect()
+}
+
 /// Build the contract on host locally
 fn local_build(
     crate_metadata: &CrateMetadata,
     optimization_passes: &OptimizationPasses,
+    wasm_opt_config: &WasmOptConfig,
     args: &ExecuteArgs,
 ) -> Result<(Option<OptimizationResult>, BuildInfo, PathBuf)> {
     let ExecuteArgs {
@@ -940,15 +1155,23 @@ fn local_build(
         skip_wasm_validation,
         target,
         max_memory_pages,
+        no_git_info,
+        force,
         ..
     } = args;
 
+    // The state of the repository is collected before building, since the build may
+    // add untracked files.
+    let git = if *no_git_info {
+        None
+    } else {
+        GitInfo::collect(&crate_metadata.manifest_path.absolute_directory()?)
+    };
+
     // We always want to lint first so we don't suppress any warnings when a build is
     // skipped because of a matching fingerprint.
     lint(*extra_lints, crate_metadata, target, verbosity)?;
 
-    let pre_fingerprint = Fingerprint::new(crate_metadata)?;
-
     verbose_eprintln!(
         verbosity,
         " {} {}",
@@ -967,6 +1190,13 @@ fn local_build(
         target,
     )?;
 
+    // The artifact directories are not created by `cargo` if they differ from its target
+    // directory, e.g. for a workspace member or an output directory.
+    fs::create_dir_all(&crate_metadata.target_directory)?;
+    if let Some(dir) = crate_metadata.target_file_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     // We persist the latest target we used so we trigger a rebuild when we switch
     fs::write(&crate_metadata.target_file_path, target.llvm_target())?;
 
@@ -986,30 +1216,36 @@ fn local_build(
         wasm_opt_settings: WasmOptSettings {
             optimization_passes: *optimization_passes,
             keep_debug_symbols: *keep_debug_symbols,
+            config: wasm_opt_config.clone(),
         },
+        features: features.clone(),
+        target: *target,
+        git,
     };
 
-    let post_fingerprint = Fingerprint::new(crate_metadata)?.ok_or_else(|| {
-        anyhow::anyhow!(
-            "Expected '{}' to be generated by build",
-            crate_metadata.original_code.display()
-        )
-    })?;
+    let fingerprint = Fingerprint::new(crate_metadata, &build_info, args)?;
+    let fingerprint_path = Fingerprint::path(crate_metadata);
+    let previous_fingerprint = Fingerprint::load(&fingerprint_path);
 
     tracing::debug!(
-        "Fingerprint before build: {:?}, after build: {:?}",
-        pre_fingerprint,
-        post_fingerprint
+        "Fingerprint of the previous build: {:?}, of this build: {:?}",
+        previous_fingerprint,
+        fingerprint
     );
 
     let dest_code_path = crate_metadata.dest_code.clone();
 
-    if pre_fingerprint == Some(post_fingerprint) && crate_metadata.dest_code.exists() {
-        tracing::info!(
-            "No changes in the original wasm at {}, fingerprint {:?}. \
-                Skipping Wasm optimization and metadata generation.",
-            crate_metadata.original_code.display(),
-            pre_fingerprint
+    if !*force
+        && previous_fingerprint.as_ref() == Some(&fingerprint)
+        && crate_metadata.dest_code.exists()
+    {
+        verbose_eprintln!(
+            verbosity,
+            " {} {}",
+            "[==]".bold(),
+            "Post processing code skipped (up to date)"
+                .bright_green()
+                .bold()
         );
         return Ok((None, build_info, dest_code_path))
     }
@@ -1022,6 +1258,7 @@ fn local_build(
     );
 
     // remove build artifacts so we don't have anything stale lingering around
+    fs::remove_file(&fingerprint_path).ok();
     for t in Target::iter() {
         fs::remove_file(crate_metadata.dest_code.with_extension(t.dest_extension())).ok();
     }
@@ -1031,7 +1268,11 @@ fn local_build(
 
     match target {
         Target::Wasm => {
-            let handler = WasmOptHandler::new(*optimization_passes, *keep_debug_symbols)?;
+            let handler = WasmOptHandler::new(
+                *optimization_passes,
+                *keep_debug_symbols,
+                wasm_opt_config.clone(),
+            )?;
             handler.optimize(&crate_metadata.original_code, &crate_metadata.dest_code)?;
             post_process_wasm(
                 &crate_metadata.dest_code,
@@ -1041,11 +1282,17 @@ fn local_build(
             )?;
         }
         Target::RiscV => {
-            fs::copy(&crate_metadata.original_code, &crate_metadata.dest_code)?;
+            polkavm::link(
+                &crate_metadata.original_code,
+                &crate_metadata.dest_code,
+                *keep_debug_symbols,
+            )?;
+            polkavm::validate(&fs::read(&crate_metadata.dest_code)?)?;
         }
     }
 
     let optimized_size = fs::metadata(&dest_code_path)?.len() as f64 / 1000.0;
+    fingerprint.store(&fingerprint_path)?;
 
     let optimization_result = OptimizationResult {
         original_size,
@@ -1059,37 +1306,75 @@ fn local_build(
     ))
 }
 
-/// Unique fingerprint for a file to detect whether it has changed.
+/// Unique fingerprint of the inputs of the post processing of the code, i.e. the code
+/// built by `cargo` and the settings of the build, to detect whether the artifacts are
+/// up to date.
 #[derive(Debug, Eq, PartialEq)]
 struct Fingerprint {
-    path: PathBuf,
     hash: [u8; 32],
-    modified: std::time::SystemTime,
-    target: String,
 }
 
 impl Fingerprint {
-    fn new(crate_metadata: &CrateMetadata) -> Result<Option<Fingerprint>> {
-        let code_path = &crate_metadata.original_code;
-        let target_path = &crate_metadata.target_file_path;
-        if code_path.exists() {
-            let modified = fs::metadata(code_path)?.modified()?;
-            let bytes = fs::read(code_path)?;
-            let hash = blake2_hash(&bytes);
-            Ok(Some(Self {
-                path: code_path.clone(),
-                hash,
-                modified,
-                target: fs::read_to_string(target_path).with_context(|| {
-                    format!(
-                        "Cannot read {}.\n A clean build will fix this.",
-                        target_path.display()
-                    )
-                })?,
-            }))
-        } else {
-            Ok(None)
-        }
+    fn new(
+        crate_metadata: &CrateMetadata,
+        build_info: &BuildInfo,
+        args: &ExecuteArgs,
+    ) -> Result<Self> {
+        let code = fs::read(&crate_metadata.original_code).with_context(|| {
+            format!(
+                "Expected '{}' to be generated by build",
+                crate_metadata.original_code.display()
+            )
+        })?;
+        let settings = serde_json::json!({
+            "target": args.target.llvm_target(),
+            "wasm_opt_settings": build_info.wasm_opt_settings,
+            "max_memory_pages": args.max_memory_pages,
+            "skip_wasm_validation": args.skip_wasm_validation,
+        });
+        let mut input = code;
+        input.extend(serde_json::to_vec(&settings)?);
+        Ok(Self {
+            hash: blake2_hash(&input),
+        })
+    }
+
+    /// Fingerprint of the inputs of the metadata generation, i.e. the post processed
+    /// `code` and the complete `build_info` embedded in the metadata, including the
+    /// state of the git repository and the features.
+    fn of_metadata(code: &Path, build_info: &BuildInfo) -> Result<Self> {
+        let mut input = fs::read(code)?;
+        input.extend(serde_json::to_vec(build_info)?);
+        Ok(Self {
+            hash: blake2_hash(&input),
+        })
+    }
+
+    /// The path of the fingerprint of the latest build, next to the persisted target.
+    fn path(crate_metadata: &CrateMetadata) -> PathBuf {
+        crate_metadata.target_file_path.with_file_name(format!(
+            ".{}.fingerprint",
+            crate_metadata.contract_artifact_name
+        ))
+    }
+
+    /// The path of the fingerprint of the metadata of the latest build.
+    fn metadata_path(crate_metadata: &CrateMetadata) -> PathBuf {
+        crate_metadata.target_file_path.with_file_name(format!(
+            ".{}.metadata.fingerprint",
+            crate_metadata.contract_artifact_name
+        ))
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let hash = fs::read_to_string(path).ok()?;
+        let hash = hex::decode(hash.trim()).ok()?.try_into().ok()?;
+        Some(Self { hash })
+    }
+
+    fn store(&self, path: &Path) -> Result<()> {
+        fs::write(path, hex::encode(self.hash))?;
+        Ok(())
     }
 }
 
@@ -1144,6 +1429,58 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn wasm_opt_config_from_args() {
+        let config: WasmOptConfig = "--converge -s 2 --dce,--no-default-passes"
+            .parse()
+            .expect("parsing must work");
+        assert_eq!(
+            config,
+            WasmOptConfig {
+                passes: vec!["dce".to_owned()],
+                no_default_passes: true,
+                shrink_level: Some(2),
+                converge: true,
+                debug_info: false,
+            }
+        );
+
+        let err = "--dce --not-a-pass"
+            .parse::<WasmOptConfig>()
+            .expect_err("unknown pass must fail");
+        assert!(err
+            .to_string()
+            .starts_with("Unsupported wasm-opt argument `--not-a-pass`"));
+
+        let config = WasmOptConfig {
+            passes: vec!["not-a-pass".to_owned()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn features_artifact_suffix() {
+        let mut features = Features::default();
+        assert!(features.is_empty());
+        assert_eq!(features.artifact_suffix(), None);
+        assert_eq!(
+            serde_json::to_value(&features).expect("serialization must work"),
+            serde_json::json!({})
+        );
+
+        features.push("mock-oracle");
+        features.push("ink-debug");
+        assert_eq!(
+            features.artifact_suffix(),
+            Some("ink-debug-mock-oracle".to_owned())
+        );
+        assert_eq!(
+            serde_json::to_value(&features).expect("serialization must work"),
+            serde_json::json!({ "features": ["mock-oracle", "ink-debug"] })
+        );
+    }
+
     #[test]
     fn build_result_seralization_sanity_check() {
         // given
@@ -1178,6 +1515,11 @@ mod unit_tests {
             build_mode: Default::default(),
             build_artifact: Default::default(),
             image: None,
+            size_report: None,
+            code_hash: None,
+            toolchain: None,
+            warnings: Vec::new(),
+            wasm_validation: None,
             verbosity: Verbosity::Quiet,
             output_type: OutputType::Json,
         };
@@ -29,8 +29,10 @@ use crate::{
     Network,
     OptimizationPasses,
     Profile,
+    Target,
     UnstableFlags,
     Verbosity,
+    WasmOptConfig,
 };
 
 use anyhow::Result;
@@ -86,6 +88,58 @@ pub struct BuildInfo {
     pub build_mode: BuildMode,
     /// Information about the `wasm-opt` optimization settings.
     pub wasm_opt_settings: WasmOptSettings,
+    /// The `cargo` features the contract was built with.
+    #[serde(default, skip_serializing_if = "Features::is_empty")]
+    pub features: Features,
+    /// The target the contract was built for.
+    #[serde(default, skip_serializing_if = "Target::is_wasm")]
+    pub target: Target,
+    /// The state of the git repository the contract was built from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
+}
+
+/// The state of the git repository a contract was built from.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GitInfo {
+    /// The hash of the checked out commit.
+    pub commit: String,
+    /// The tag pointing at the checked out commit, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+}
+
+impl GitInfo {
+    /// Collects the state of the git repository of the given directory.
+    ///
+    /// Returns `None` if the directory is not part of a git repository, or if `git` is
+    /// not installed.
+    pub fn collect(dir: &Path) -> Option<Self> {
+        let git =
+            |args: &[&str]| duct::cmd("git", args).dir(dir).stderr_null().read().ok();
+        let commit = git(&["rev-parse", "HEAD"])?;
+        let tag = git(&["describe", "--tags", "--exact-match"]).filter(|t| !t.is_empty());
+        let dirty = git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+        Some(Self { commit, tag, dirty })
+    }
+
+    /// Display the git information, e.g. `1a2b3c4 (v1.0.0, dirty)`.
+    pub fn display(&self) -> String {
+        let mut details = Vec::new();
+        if let Some(tag) = &self.tag {
+            details.push(tag.as_str());
+        }
+        if self.dirty {
+            details.push("dirty");
+        }
+        if details.is_empty() {
+            self.commit.clone()
+        } else {
+            format!("{} ({})", self.commit, details.join(", "))
+        }
+    }
 }
 
 impl TryFrom<BuildInfo> for serde_json::Map<String, serde_json::Value> {
@@ -104,6 +158,9 @@ pub struct WasmOptSettings {
     pub optimization_passes: OptimizationPasses,
     /// Whether or not the Wasm name section should be kept.
     pub keep_debug_symbols: bool,
+    /// The custom configuration of the `wasm-opt` run.
+    #[serde(default, skip_serializing_if = "WasmOptConfig::is_empty")]
+    pub config: WasmOptConfig,
 }
 
 /// Generates a file with metadata describing the ABI of the smart contract.
@@ -135,7 +192,7 @@ pub fn execute(
             "Generating metadata".bright_green().bold(),
         );
         let target_dir = crate_metadata
-            .target_directory
+            .cargo_target_directory
             .to_string_lossy()
             .to_string();
         let mut args = vec![
@@ -169,6 +226,7 @@ pub fn execute(
 
         let ink_meta: serde_json::Map<String, serde_json::Value> =
             serde_json::from_slice(&output.stdout)?;
+        let ink_meta = normalize_json_object(ink_meta);
         let metadata = ContractMetadata::new(source, contract, None, user, ink_meta);
 
         write_metadata(metadata_artifacts, metadata, &verbosity, false)?;
@@ -189,6 +247,9 @@ pub fn execute(
                     })?
                     .with_merged_workspace_dependencies(crate_metadata)?
                     .with_empty_workspace();
+                if features.no_default_features() {
+                    manifest.with_empty_default_features()?;
+                }
                 Ok(())
             })?
             .with_metadata_gen_package()?
@@ -245,7 +306,10 @@ fn extended_metadata(
     let contract_version = Version::parse(&contract_package.version.to_string())?;
     let contract_authors = contract_package.authors.clone();
     // optional
-    let description = contract_package.description.clone();
+    let description = contract_package
+        .description
+        .as_ref()
+        .map(|description| normalize_line_endings(description));
     let documentation = crate_metadata.documentation.clone();
     let repository = contract_package
         .repository
@@ -300,7 +364,10 @@ fn extended_metadata(
     })?;
 
     // user defined metadata
-    let user = crate_metadata.user.clone().map(User::new);
+    let user = crate_metadata
+        .user
+        .clone()
+        .map(|user| User::new(normalize_json_object(user)));
 
     Ok(ExtendedMetadataResult {
         source,
@@ -308,3 +375,33 @@ fn extended_metadata(
         user,
     })
 }
+
+/// Normalizes the JSON object so that its serialization does not depend on the platform
+/// or on the order the keys were inserted in: the keys are sorted, and the line endings
+/// of strings are `\n`.
+fn normalize_json_object(
+    object: serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut entries = object
+        .into_iter()
+        .map(|(key, value)| (key, normalize_json(value)))
+        .collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries.into_iter().collect()
+}
+
+fn normalize_json(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(string) => Value::String(normalize_line_endings(&string)),
+        Value::Array(array) => {
+            Value::Array(array.into_iter().map(normalize_json).collect())
+        }
+        Value::Object(object) => Value::Object(normalize_json_object(object)),
+        value => value,
+    }
+}
+
+fn normalize_line_endings(string: &str) -> String {
+    string.replace("\r\n", "\n")
+}
@@ -14,10 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use anyhow::Result;
+use anyhow::{
+    Context,
+    Result,
+};
 use heck::ToUpperCamelCase as _;
 use std::{
     env,
+    fmt,
     fs,
     io::{
         Cursor,
@@ -29,10 +33,97 @@ use std::{
         Path,
         PathBuf,
     },
+    str::FromStr,
 };
 
-/// Creates a new contract project from the template.
+/// The built-in templates for new contract projects, with their descriptions.
+pub const TEMPLATES: [(&str, &str); 4] = [
+    ("flipper", "A contract which flips a `bool` value (default)"),
+    ("erc20", "An ERC-20 style fungible token"),
+    (
+        "multisig",
+        "A wallet which executes calls once enough of its owners confirmed them",
+    ),
+    (
+        "cross-contract",
+        "A contract which instantiates and calls another contract, with E2E tests",
+    ),
+];
+
+/// The template a new contract project is created from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// One of the built-in [`TEMPLATES`].
+    BuiltIn(&'static str),
+    /// The URL of a git repository.
+    Git(String),
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self::BuiltIn(TEMPLATES[0].0)
+    }
+}
+
+impl FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        if let Some((name, _)) = TEMPLATES.iter().find(|(name, _)| *name == input) {
+            return Ok(Self::BuiltIn(name))
+        }
+        if input.contains("://") || input.starts_with("git@") || input.ends_with(".git") {
+            return Ok(Self::Git(input.to_string()))
+        }
+        let names = TEMPLATES
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!(
+            "Unknown template `{input}`, expected one of {names} or the URL of a git \
+            repository"
+        )
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BuiltIn(name) => write!(f, "{name}"),
+            Self::Git(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Creates a new contract project from the default template, without adding it to an
+/// enclosing cargo workspace.
 pub fn new_contract_project<P>(name: &str, dir: Option<P>) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    new_contract_project_from_template(name, dir, &Template::default(), false)?;
+    Ok(())
+}
+
+/// Creates a new contract project from the given template.
+///
+/// The `{{name}}` and `{{camel_name}}` placeholders of the template files are replaced
+/// by the name of the contract. For a git repository, the package name of its
+/// `Cargo.toml` is replaced as well.
+///
+/// If the project is created inside of a cargo workspace and `join_workspace` is set,
+/// it is added to the members of the workspace, and the path of the workspace manifest
+/// is returned. Otherwise the project is kept out of the workspace.
+///
+/// If the project directory is created but the template can't be copied into it, e.g.
+/// because cloning the git repository failed, the directory is removed again.
+pub fn new_contract_project_from_template<P>(
+    name: &str,
+    dir: Option<P>,
+    template: &Template,
+    join_workspace: bool,
+) -> Result<Option<PathBuf>>
 where
     P: AsRef<Path>,
 {
@@ -57,34 +148,309 @@ where
     if out_dir.join("Cargo.toml").exists() {
         anyhow::bail!("A Cargo package already exists in {}", name);
     }
-    if !out_dir.exists() {
+    let created_out_dir = !out_dir.exists();
+    if created_out_dir {
         fs::create_dir(&out_dir)?;
     }
 
-    let template = include_bytes!(concat!(env!("OUT_DIR"), "/template.zip"));
+    let copied = match template {
+        Template::BuiltIn(template) => {
+            let templates = include_bytes!(concat!(env!("OUT_DIR"), "/template.zip"));
+            unzip(templates, template, out_dir.clone(), Some(name))
+        }
+        Template::Git(url) => clone_template(url, out_dir.clone(), name),
+    };
+    if let Err(err) = copied {
+        if created_out_dir {
+            let _ = fs::remove_dir_all(&out_dir);
+        }
+        return Err(err)
+    }
+
+    let Some(workspace_manifest) = find_workspace_manifest(&out_dir)? else {
+        return Ok(None)
+    };
+    let manifest_path = out_dir.join("Cargo.toml");
+    let mut manifest = read_toml_document(&manifest_path)?;
+    if join_workspace {
+        let mut workspace = read_toml_document(&workspace_manifest)?;
+        let member = relative_member_path(&workspace_manifest, &out_dir)?;
+        add_workspace_member(&mut workspace, &member);
+        inherit_workspace_package(&workspace, &mut manifest);
+        manifest.remove("workspace");
+        fs::write(&workspace_manifest, workspace.to_string())?;
+        fs::write(&manifest_path, manifest.to_string())?;
+        Ok(Some(workspace_manifest))
+    } else {
+        // an empty `[workspace]` table makes the project the root of its own workspace
+        if !manifest.contains_key("workspace") {
+            manifest.insert("workspace", toml_edit::table());
+            fs::write(&manifest_path, manifest.to_string())?;
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the manifest of the closest workspace enclosing `dir`, if any.
+fn find_workspace_manifest(dir: &Path) -> Result<Option<PathBuf>> {
+    let dir = dir.canonicalize()?;
+    for ancestor in dir.ancestors().skip(1) {
+        let manifest = ancestor.join("Cargo.toml");
+        if manifest.exists() && read_toml_document(&manifest)?.contains_key("workspace") {
+            return Ok(Some(manifest))
+        }
+    }
+    Ok(None)
+}
+
+fn read_toml_document(path: &Path) -> Result<toml_edit::Document> {
+    fs::read_to_string(path)?
+        .parse()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Returns the path of the member `dir` relative to the workspace root, with forward
+/// slashes as used in the `members` of the workspace.
+fn relative_member_path(workspace_manifest: &Path, dir: &Path) -> Result<String> {
+    let root = workspace_manifest
+        .parent()
+        .context("Workspace manifest has no parent directory")?;
+    let member = dir.canonicalize()?;
+    let member = member.strip_prefix(root)?;
+    Ok(member
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Adds `member` to the `members` of the workspace, unless it is already matched by one
+/// of the `members` or `exclude` entries. The formatting of the list is preserved.
+fn add_workspace_member(workspace: &mut toml_edit::Document, member: &str) {
+    let is_listed = |key: &str, workspace: &toml_edit::Document| {
+        workspace
+            .get("workspace")
+            .and_then(|workspace| workspace.get(key))
+            .and_then(|entries| entries.as_array())
+            .is_some_and(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.as_str())
+                    .any(|pattern| matches_member_pattern(pattern, member))
+            })
+    };
+    if is_listed("members", workspace) || is_listed("exclude", workspace) {
+        return
+    }
+    let members = workspace["workspace"]["members"]
+        .or_insert(toml_edit::value(toml_edit::Array::new()));
+    let Some(members) = members.as_array_mut() else {
+        return
+    };
+    // a multi-line list is continued with the indentation of its last entry
+    let is_multi_line = |prefix: &toml_edit::RawString| {
+        prefix.as_str().is_some_and(|prefix| prefix.contains('\n'))
+    };
+    match members.iter().last() {
+        Some(last) if last.decor().prefix().is_some_and(is_multi_line) => {
+            let mut value = toml_edit::Value::from(member);
+            *value.decor_mut() = last.decor().clone();
+            members.push_formatted(value);
+        }
+        _ => members.push(member),
+    }
+}
+
+/// Whether `member` is matched by a `members` pattern, where `*` matches a single path
+/// segment.
+fn matches_member_pattern(pattern: &str, member: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/').split('/').collect::<Vec<_>>();
+    let member = member.split('/').collect::<Vec<_>>();
+    pattern.len() == member.len()
+        && pattern.iter().zip(&member).all(|(pattern, segment)| {
+            match pattern.split_once('*') {
+                Some((prefix, suffix)) => {
+                    segment.len() >= prefix.len() + suffix.len()
+                        && segment.starts_with(prefix)
+                        && segment.ends_with(suffix)
+                }
+                None => pattern == segment,
+            }
+        })
+}
+
+/// Inherits the `edition` and `rust-version` of the package from the workspace, if the
+/// workspace defines them in `[workspace.package]`.
+fn inherit_workspace_package(
+    workspace: &toml_edit::Document,
+    manifest: &mut toml_edit::Document,
+) {
+    let Some(package) = workspace
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.as_table_like())
+    else {
+        return
+    };
+    for key in ["edition", "rust-version"] {
+        if package.contains_key(key) {
+            let mut inherited = toml_edit::InlineTable::new();
+            inherited.insert("workspace", true.into());
+            manifest["package"][key] = toml_edit::value(inherited);
+        }
+    }
+}
+
+/// Creates the project at `out_dir` from the files of the git repository at `url`.
+fn clone_template(url: &str, out_dir: PathBuf, name: &str) -> Result<()> {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("cargo-contract_template_")
+        .tempdir()?;
+    duct::cmd("git", ["clone", "--depth", "1", url, "."])
+        .dir(tmp_dir.path())
+        .stdout_to_stderr()
+        .run()
+        .with_context(|| format!("Cloning the template repository {url} failed"))?;
 
-    unzip(template, out_dir, Some(name))?;
+    // The package name of a template which is a working contract itself.
+    let template_name = fs::read_to_string(tmp_dir.path().join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| toml::from_str::<toml::Table>(&manifest).ok())
+        .and_then(|manifest| {
+            manifest
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(ToString::to_string)
+        })
+        .filter(|template_name| !template_name.contains("{{"));
 
+    copy_template(tmp_dir.path(), &out_dir, name, template_name.as_deref())
+}
+
+/// Copies the files of the `template` directory to `out_dir`, except for the `.git`
+/// directory, replacing the placeholders in text files.
+fn copy_template(
+    template: &Path,
+    out_dir: &Path,
+    name: &str,
+    template_name: Option<&str>,
+) -> Result<()> {
+    for entry in fs::read_dir(template)? {
+        let entry = entry?;
+        let path = entry.path();
+        let outpath = out_dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                fs::create_dir_all(&outpath)?;
+                copy_template(&path, &outpath, name, template_name)?;
+            }
+            continue
+        }
+        let contents = fs::read(&path)?;
+        let contents = match String::from_utf8(contents) {
+            Ok(contents) => replace_placeholders(&contents, name, template_name).into(),
+            Err(err) => err.into_bytes(),
+        };
+        create_new_file(&outpath, &outpath.display().to_string())?
+            .write_all(&contents)?;
+    }
     Ok(())
 }
 
-// Unzips the file at `template` to `out_dir`.
+/// Replaces the `{{name}}` and `{{camel_name}}` placeholders by the name of the
+/// contract.
+///
+/// If the template is a contract named `template_name`, its crate name and ink! module
+/// name are replaced as whole words. Its upper camel case name is replaced at the start
+/// of identifiers, so that e.g. both `Flipper` and `FlipperRef` are renamed.
+fn replace_placeholders(
+    contents: &str,
+    name: &str,
+    template_name: Option<&str>,
+) -> String {
+    let camel_name = name.to_upper_camel_case();
+    let mut contents = contents
+        .replace("{{name}}", name)
+        .replace("{{camel_name}}", &camel_name);
+    if let Some(template_name) = template_name {
+        let module_name = template_name.replace('-', "_");
+        contents = replace_word(&contents, template_name, name, false);
+        contents = replace_word(&contents, &module_name, name, false);
+        contents = replace_word(
+            &contents,
+            &module_name.to_upper_camel_case(),
+            &camel_name,
+            true,
+        );
+    }
+    contents
+}
+
+/// Replaces the occurrences of `from` which are not part of a longer identifier, unless
+/// `prefix` is set, in which case `from` only has to start an identifier.
+fn replace_word(contents: &str, from: &str, to: &str, prefix: bool) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let mut out = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(index) = rest.find(from) {
+        let (before, after) = (&rest[..index], &rest[index + from.len()..]);
+        let bounded =
+            !before.ends_with(is_ident) && (prefix || !after.starts_with(is_ident));
+        out.push_str(before);
+        out.push_str(if bounded { to } else { from });
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Creates the file at `path`, failing if it already exists.
+fn create_new_file(path: &Path, display_name: &str) -> Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                anyhow::anyhow!("File {} already exists", display_name)
+            } else {
+                anyhow::anyhow!(e)
+            }
+        })
+}
+
+// Unzips the files of the `template` directory of the zip file at `templates` to
+// `out_dir`.
 //
 // In case `name` is set the zip file is treated as if it were a template for a new
 // contract. Replacements in `Cargo.toml` for `name`-placeholders are attempted in
 // that case.
-fn unzip(template: &[u8], out_dir: PathBuf, name: Option<&str>) -> Result<()> {
+fn unzip(
+    templates: &[u8],
+    template: &str,
+    out_dir: PathBuf,
+    name: Option<&str>,
+) -> Result<()> {
     let mut cursor = Cursor::new(Vec::new());
-    cursor.write_all(template)?;
+    cursor.write_all(templates)?;
     cursor.rewind()?;
 
     let mut archive = zip::ZipArchive::new(cursor)?;
+    let prefix = format!("{template}/");
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let outpath = out_dir.join(file.name());
+        let Some(file_name) = file.name().strip_prefix(&prefix) else {
+            continue
+        };
+        if file_name.is_empty() {
+            continue
+        }
+        let file_name = file_name.to_string();
+        let outpath = out_dir.join(&file_name);
 
-        if (*file.name()).ends_with('/') {
+        if file_name.ends_with('/') {
             fs::create_dir_all(&outpath)?;
         } else {
             if let Some(p) = outpath.parent() {
@@ -92,24 +458,12 @@ fn unzip(template: &[u8], out_dir: PathBuf, name: Option<&str>) -> Result<()> {
                     fs::create_dir_all(p)?;
                 }
             }
-            let mut outfile = fs::OpenOptions::new()
-                .write(true)
-                .create_new(true)
-                .open(outpath.clone())
-                .map_err(|e| {
-                    if e.kind() == std::io::ErrorKind::AlreadyExists {
-                        anyhow::anyhow!("File {} already exists", file.name(),)
-                    } else {
-                        anyhow::anyhow!(e)
-                    }
-                })?;
+            let mut outfile = create_new_file(&outpath, &file_name)?;
 
             if let Some(name) = name {
                 let mut contents = String::new();
                 file.read_to_string(&mut contents)?;
-                let contents = contents.replace("{{name}}", name);
-                let contents =
-                    contents.replace("{{camel_name}}", &name.to_upper_camel_case());
+                let contents = replace_placeholders(&contents, name, None);
                 outfile.write_all(contents.as_bytes())?;
             } else {
                 let mut v = Vec::new();
@@ -209,4 +563,176 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn creates_project_from_each_built_in_template() {
+        with_tmp_dir(|path| {
+            for (template, _) in TEMPLATES {
+                let name = format!("new_{}", template.replace('-', "_"));
+                let template = template.parse::<Template>()?;
+                new_contract_project_from_template(&name, Some(path), &template, true)?;
+
+                let dir = path.join(&name);
+                let manifest = fs::read_to_string(dir.join("Cargo.toml"))?;
+                assert!(
+                    manifest.contains(&format!("name = \"{name}\"")),
+                    "{manifest}"
+                );
+                let lib = fs::read_to_string(dir.join("lib.rs"))?;
+                assert!(lib.contains(&format!("mod {name} {{")), "{lib}");
+                assert!(!lib.contains("{{"), "{lib}");
+            }
+            assert!(path
+                .join("new_cross_contract/other_contract/Cargo.toml")
+                .exists());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn parses_templates() {
+        assert_eq!(
+            "erc20".parse::<Template>().unwrap(),
+            Template::BuiltIn("erc20")
+        );
+        assert_eq!(
+            "https://github.com/org/template"
+                .parse::<Template>()
+                .unwrap(),
+            Template::Git("https://github.com/org/template".to_string())
+        );
+        let err = "erc721".parse::<Template>().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Unknown template `erc721`, expected one of flipper, erc20, multisig, \
+             cross-contract or the URL of a git repository"
+        );
+    }
+
+    #[test]
+    fn replaces_template_crate_and_module_name() {
+        let manifest =
+            "[package]\nname = \"my-token\"\n\n[dependencies]\nmy-token-ext = \"1\"";
+        assert_eq!(
+            replace_placeholders(manifest, "erc20_clone", Some("my-token")),
+            "[package]\nname = \"erc20_clone\"\n\n[dependencies]\nmy-token-ext = \"1\""
+        );
+
+        let lib =
+            "mod my_token {\n    pub struct MyToken;\n    type R = MyTokenRef;\n    \
+                   fn my_token_supply() {}\n}";
+        assert_eq!(
+            replace_placeholders(lib, "erc20_clone", Some("my-token")),
+            "mod erc20_clone {\n    pub struct Erc20Clone;\n    type R = Erc20CloneRef;\n    \
+             fn my_token_supply() {}\n}"
+        );
+    }
+
+    #[test]
+    fn joins_enclosing_workspace() {
+        with_tmp_dir(|path| {
+            let workspace_manifest = path.join("Cargo.toml");
+            fs::write(
+                &workspace_manifest,
+                "[workspace]\n# members\nmembers = [\n    \"lib_a\",\n]\n\n\
+                 [workspace.package]\nedition = \"2021\"\n",
+            )?;
+            let contracts = path.join("contracts");
+            fs::create_dir(&contracts)?;
+
+            let workspace = new_contract_project_from_template(
+                "joined",
+                Some(&contracts),
+                &Template::default(),
+                true,
+            )?;
+
+            assert_eq!(workspace, Some(workspace_manifest.canonicalize()?));
+            assert_eq!(
+                fs::read_to_string(&workspace_manifest)?,
+                "[workspace]\n# members\nmembers = [\n    \"lib_a\",\n    \
+                 \"contracts/joined\",\n]\n\n[workspace.package]\nedition = \"2021\"\n"
+            );
+            let manifest = fs::read_to_string(contracts.join("joined/Cargo.toml"))?;
+            assert!(
+                manifest.contains("edition = { workspace = true }"),
+                "{manifest}"
+            );
+            assert!(!manifest.contains("rust-version"), "{manifest}");
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn no_workspace_keeps_project_standalone() {
+        with_tmp_dir(|path| {
+            let workspace_manifest = path.join("Cargo.toml");
+            fs::write(&workspace_manifest, "[workspace]\nmembers = [\"*\"]\n")?;
+
+            let workspace = new_contract_project_from_template(
+                "standalone",
+                Some(path),
+                &Template::default(),
+                false,
+            )?;
+
+            assert_eq!(workspace, None);
+            assert_eq!(
+                fs::read_to_string(&workspace_manifest)?,
+                "[workspace]\nmembers = [\"*\"]\n"
+            );
+            let manifest = fs::read_to_string(path.join("standalone/Cargo.toml"))?;
+            assert!(manifest.ends_with("\n[workspace]\n"), "{manifest}");
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn library_default_keeps_project_out_of_workspace() {
+        with_tmp_dir(|path| {
+            let workspace_manifest = path.join("Cargo.toml");
+            fs::write(&workspace_manifest, "[workspace]\nmembers = []\n")?;
+
+            new_contract_project("library_default", Some(path))?;
+
+            assert_eq!(
+                fs::read_to_string(&workspace_manifest)?,
+                "[workspace]\nmembers = []\n"
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn failed_clone_removes_project_dir() {
+        with_tmp_dir(|path| {
+            let url = format!("file://{}/missing.git", path.display());
+            let result = new_contract_project_from_template(
+                "cloned",
+                Some(path),
+                &Template::Git(url),
+                false,
+            );
+
+            let err = result.unwrap_err().to_string();
+            assert!(err.starts_with("Cloning the template repository"), "{err}");
+            assert!(!path.join("cloned").exists());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn matches_member_patterns() {
+        assert!(matches_member_pattern("contracts/*", "contracts/flipper"));
+        assert!(matches_member_pattern(
+            "contracts/flip*",
+            "contracts/flipper"
+        ));
+        assert!(matches_member_pattern("flipper/", "flipper"));
+        assert!(!matches_member_pattern("contracts/*", "flipper"));
+        assert!(!matches_member_pattern(
+            "contracts/*",
+            "contracts/a/flipper"
+        ));
+    }
 }
@@ -0,0 +1,96 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use std::path::Path;
+
+/// The magic bytes every PolkaVM program blob starts with.
+pub(crate) const POLKAVM_MAGIC: [u8; 4] = *b"PVM\0";
+
+/// The maximum size of a PolkaVM program blob accepted by `pallet-revive`.
+const MAX_POLKAVM_CODE_SIZE: usize = 256 * 1024;
+
+/// Links the RISC-V ELF binary built by `rustc` into a PolkaVM program blob, using the
+/// `polkatool` linker.
+///
+/// The symbols are stripped unless `keep_debug_symbols` is set.
+pub fn link(
+    original_code: &Path,
+    dest_code: &Path,
+    keep_debug_symbols: bool,
+) -> Result<()> {
+    let polkatool = which::which("polkatool").map_err(|_| {
+        anyhow::anyhow!("polkatool was not found!\n\
+            Make sure it is installed and the binary is in your PATH environment.\n\n\
+            You can install it by executing `cargo install polkatool`."
+            .to_string()
+            .bright_yellow())
+    })?;
+
+    let mut args = vec!["link".to_string()];
+    if !keep_debug_symbols {
+        args.push("--strip".to_string());
+    }
+    args.push("--output".to_string());
+    args.push(dest_code.display().to_string());
+    args.push(original_code.display().to_string());
+
+    tracing::debug!("Linking PolkaVM program: {:?} {:?}", polkatool, args);
+    duct::cmd(polkatool, args)
+        .stdout_to_stderr()
+        .run()
+        .context("Linking of the PolkaVM program failed")?;
+    Ok(())
+}
+
+/// Validates the linked PolkaVM program blob.
+pub fn validate(code: &[u8]) -> Result<()> {
+    if !code.starts_with(&POLKAVM_MAGIC) {
+        anyhow::bail!("The linked code is not a PolkaVM program blob")
+    }
+    if code.len() > MAX_POLKAVM_CODE_SIZE {
+        anyhow::bail!(
+            "The PolkaVM program blob is {} bytes large, which exceeds the maximum size of \
+            {} bytes accepted by pallet-revive",
+            code.len(),
+            MAX_POLKAVM_CODE_SIZE
+        )
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_polkavm_blob() {
+        let mut code = POLKAVM_MAGIC.to_vec();
+        code.extend([1, 2, 3]);
+        assert!(validate(&code).is_ok());
+
+        let err = validate(b"\0asm\x01\0\0\0").unwrap_err().to_string();
+        assert_eq!(err, "The linked code is not a PolkaVM program blob");
+
+        code.resize(MAX_POLKAVM_CODE_SIZE + 1, 0);
+        let err = validate(&code).unwrap_err().to_string();
+        assert!(err.contains("exceeds the maximum size"), "{err}");
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use parity_wasm::elements::{
+    ImportCountType,
+    Module,
+    Section,
+};
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fmt::Write,
+};
+
+/// The number of the largest functions listed in the report.
+const TOP_FUNCTIONS: usize = 20;
+
+/// Breakdown of the size of the contract code, see `cargo contract build --size-report`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SizeReport {
+    /// The size of the contract code in bytes.
+    pub total_size: usize,
+    /// The size of the code section in bytes.
+    pub code_size: usize,
+    /// The size of the data section in bytes.
+    pub data_size: usize,
+    /// Whether the functions could be attributed by the name section of the code.
+    pub has_names: bool,
+    /// The largest functions of the code section.
+    pub functions: Vec<FunctionSize>,
+    /// The size of the functions of the code section by their originating crate.
+    pub crates: Vec<CrateSize>,
+}
+
+/// The size of a function of the contract code.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FunctionSize {
+    /// The demangled name of the function, or its index if the name is unknown.
+    pub name: String,
+    /// The crate the function originates from, if known.
+    #[serde(rename = "crate")]
+    pub krate: Option<String>,
+    /// The size of the function body in bytes.
+    pub size: usize,
+}
+
+/// The total size of the functions originating from a crate.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CrateSize {
+    /// The name of the crate.
+    pub name: String,
+    /// The size of its function bodies in bytes.
+    pub size: usize,
+}
+
+impl SizeReport {
+    /// Creates the size report of the given Wasm code.
+    pub fn new(code: &[u8]) -> Result<Self> {
+        let module = parity_wasm::deserialize_buffer::<Module>(code)
+            .context("Loading of wasm module for the size report failed")?;
+        let module = module.parse_names().unwrap_or_else(|(_, module)| module);
+
+        let mut code_size = 0;
+        let mut data_size = 0;
+        for section in module.sections() {
+            match section {
+                Section::Code(_) => code_size += section_size(section)?,
+                Section::Data(_) => data_size += section_size(section)?,
+                _ => (),
+            }
+        }
+
+        let names = module
+            .names_section()
+            .and_then(|names| names.functions())
+            .map(|functions| functions.names());
+        let imported = module.import_count(ImportCountType::Function);
+        let mut functions = Vec::new();
+        for (i, body) in module
+            .code_section()
+            .map(|code| code.bodies())
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            let index = (imported + i) as u32;
+            let name = names
+                .and_then(|names| names.get(index))
+                .map(|name| demangle(name));
+            functions.push(FunctionSize {
+                krate: name.as_deref().and_then(crate_of),
+                name: name.unwrap_or_else(|| format!("func[{index}]")),
+                size: parity_wasm::serialize(body.clone())?.len(),
+            });
+        }
+
+        let mut crates = BTreeMap::<_, usize>::new();
+        for function in &functions {
+            if let Some(krate) = &function.krate {
+                *crates.entry(krate.clone()).or_default() += function.size;
+            }
+        }
+        let mut crates = crates
+            .into_iter()
+            .map(|(name, size)| CrateSize { name, size })
+            .collect::<Vec<_>>();
+        crates.sort_by_key(|krate| Reverse(krate.size));
+
+        functions.sort_by_key(|function| Reverse(function.size));
+        functions.truncate(TOP_FUNCTIONS);
+
+        Ok(Self {
+            total_size: code.len(),
+            code_size,
+            data_size,
+            has_names: names.is_some(),
+            functions,
+            crates,
+        })
+    }
+
+    /// Display the size report as a table of the largest functions.
+    pub fn display(&self) -> String {
+        let mut out = format!(
+            "Size report of the contract code ({}):\n  code: {}, data: {}, other: {}\n",
+            format_size(self.total_size).bold(),
+            format_size(self.code_size),
+            format_size(self.data_size),
+            format_size(
+                self.total_size
+                    .saturating_sub(self.code_size + self.data_size)
+            ),
+        );
+        if !self.crates.is_empty() {
+            out.push_str("\nBy crate:\n");
+            for krate in &self.crates {
+                let _ = writeln!(
+                    out,
+                    "  {:>8} {:>6}  {}",
+                    format_size(krate.size),
+                    self.share(krate.size),
+                    krate.name
+                );
+            }
+        }
+        let _ = writeln!(out, "\nLargest functions:");
+        for function in &self.functions {
+            let _ = writeln!(
+                out,
+                "  {:>8} {:>6}  {}",
+                format_size(function.size),
+                self.share(function.size),
+                function.name
+            );
+        }
+        if !self.has_names {
+            let _ = writeln!(
+                out,
+                "\n{} the name section is stripped, so the functions cannot be \
+                attributed to their crates. Build with `--keep-debug-symbols` to keep it.",
+                "note:".yellow().bold()
+            );
+        }
+        out.trim_end().to_string()
+    }
+
+    fn share(&self, size: usize) -> String {
+        let share = if self.total_size == 0 {
+            0.0
+        } else {
+            size as f64 * 100.0 / self.total_size as f64
+        };
+        format!("{share:.1}%")
+    }
+}
+
+fn section_size(section: &Section) -> Result<usize> {
+    Ok(parity_wasm::serialize(section.clone())?.len())
+}
+
+fn format_size(bytes: usize) -> String {
+    format!("{:.1}K", bytes as f64 / 1000.0)
+}
+
+/// Demangles the symbol name, without the hash suffix of legacy Rust symbols.
+pub(crate) fn demangle(name: &str) -> String {
+    match rustc_demangle::try_demangle(name) {
+        Ok(demangled) => format!("{demangled:#}"),
+        Err(_) => {
+            // the linker usually writes demangled names, including the hash
+            match name.rsplit_once("::h") {
+                Some((path, hash))
+                    if hash.len() == 16
+                        && hash.chars().all(|c| c.is_ascii_hexdigit()) =>
+                {
+                    path.to_string()
+                }
+                _ => name.to_string(),
+            }
+        }
+    }
+}
+
+/// Returns the crate of the demangled function path, e.g. `core` for
+/// `<core::fmt::Error as core::fmt::Debug>::fmt`.
+pub(crate) fn crate_of(path: &str) -> Option<String> {
+    let path = path.trim_start_matches(['<', '&', '*']);
+    let path = path.strip_prefix("mut ").unwrap_or(path);
+    let (krate, _) = path.split_once("::")?;
+    let is_ident =
+        !krate.is_empty() && krate.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_ident.then(|| krate.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demangles_and_attributes_symbols() {
+        let name = demangle("_ZN4core3fmt5write17h5a5b8d0e3d3c1f4aE");
+        assert_eq!(name, "core::fmt::write");
+        assert_eq!(crate_of(&name).as_deref(), Some("core"));
+
+        let name = demangle(
+            "<ink_env::types::AccountId as core::fmt::Debug>::fmt::h0123456789abcdef",
+        );
+        assert_eq!(name, "<ink_env::types::AccountId as core::fmt::Debug>::fmt");
+        assert_eq!(crate_of(&name).as_deref(), Some("ink_env"));
+
+        assert_eq!(crate_of("deploy"), None);
+    }
+}
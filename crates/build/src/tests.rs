@@ -66,6 +66,7 @@ macro_rules! build_tests {
 // dependencies to be build once across all tests.
 build_tests!(
     build_code_only,
+    build_code_only_with_output_dir,
     check_must_not_output_contract_artifacts_in_project_dir,
     optimization_passes_from_cli_must_take_precedence_over_profile,
     optimization_passes_from_profile_must_be_used,
@@ -79,7 +80,10 @@ build_tests!(
     missing_linting_toolchain_installation_must_be_detected,
     generates_metadata,
     unchanged_contract_skips_optimization_and_metadata_steps,
-    unchanged_contract_no_metadata_artifacts_generates_metadata
+    changed_optimization_passes_or_force_invalidate_fingerprint,
+    builds_in_different_directories_are_identical,
+    unchanged_contract_no_metadata_artifacts_generates_metadata,
+    changed_git_state_regenerates_metadata
 );
 
 fn build_code_only(manifest_path: &ManifestPath) -> Result<()> {
@@ -119,6 +123,29 @@ fn build_code_only(manifest_path: &ManifestPath) -> Result<()> {
     Ok(())
 }
 
+fn build_code_only_with_output_dir(manifest_path: &ManifestPath) -> Result<()> {
+    // given
+    let args = ExecuteArgs {
+        manifest_path: manifest_path.clone(),
+        build_mode: BuildMode::Release,
+        build_artifact: BuildArtifacts::CodeOnly,
+        extra_lints: false,
+        output_dir: Some(PathBuf::from("dist/contracts")),
+        ..Default::default()
+    };
+
+    // when
+    let res = super::execute(args).expect("build failed");
+
+    // then
+    let output_dir = manifest_path.absolute_directory()?.join("dist/contracts");
+    assert_eq!(res.target_directory, output_dir);
+    let dest_wasm = res.dest_wasm.expect("wasm must be built");
+    assert_eq!(dest_wasm.parent(), Some(output_dir.as_path()));
+    assert!(dest_wasm.exists(), "wasm must be written to the output dir");
+    Ok(())
+}
+
 fn check_must_not_output_contract_artifacts_in_project_dir(
     manifest_path: &ManifestPath,
 ) -> Result<()> {
@@ -582,6 +609,95 @@ fn unchanged_contract_skips_optimization_and_metadata_steps(
     Ok(())
 }
 
+fn changed_optimization_passes_or_force_invalidate_fingerprint(
+    manifest_path: &ManifestPath,
+) -> Result<()> {
+    // given
+    let args = ExecuteArgs {
+        manifest_path: manifest_path.clone(),
+        build_artifact: BuildArtifacts::CodeOnly,
+        optimization_passes: Some(OptimizationPasses::Z),
+        ..Default::default()
+    };
+    let res = super::execute(args.clone()).expect("build failed");
+    let dest_wasm = res.dest_wasm.expect("dest_wasm must be returned");
+
+    // when
+    let res = super::execute(ExecuteArgs {
+        optimization_passes: Some(OptimizationPasses::Zero),
+        ..args.clone()
+    })
+    .expect("build failed");
+
+    // then
+    assert!(
+        res.optimization_result.is_some(),
+        "Changed optimization passes must invalidate the fingerprint"
+    );
+
+    // when
+    let modified = file_last_modified(&dest_wasm);
+    let res = super::execute(ExecuteArgs {
+        optimization_passes: Some(OptimizationPasses::Zero),
+        force: true,
+        ..args
+    })
+    .expect("build failed");
+
+    // then
+    assert!(
+        res.optimization_result.is_some(),
+        "Forced build must post process the code"
+    );
+    assert_ne!(modified, file_last_modified(&dest_wasm));
+    Ok(())
+}
+
+fn builds_in_different_directories_are_identical(
+    manifest_path: &ManifestPath,
+) -> Result<()> {
+    // given
+    let project_dir = manifest_path.absolute_directory()?;
+    let project_name = project_dir
+        .file_name()
+        .expect("project dir must have a name");
+    let other_project_dir = project_dir
+        .parent()
+        .expect("project dir must have a parent")
+        .join("reproducible")
+        .join(project_name);
+    copy_dir_all(&project_dir, &other_project_dir)?;
+    fs::remove_dir_all(other_project_dir.join("target")).ok();
+    let other_manifest_path = ManifestPath::new(other_project_dir.join("Cargo.toml"))?;
+
+    // when
+    // the artifacts are read right away, since the builds may share the target dir
+    let build = |manifest_path: &ManifestPath| -> Result<(Vec<u8>, Vec<u8>)> {
+        let res = super::execute(ExecuteArgs {
+            manifest_path: manifest_path.clone(),
+            ..Default::default()
+        })
+        .expect("build failed")
+        .metadata_result
+        .expect("metadata must be generated");
+        Ok((fs::read(res.dest_metadata)?, fs::read(res.dest_bundle)?))
+    };
+    let (metadata1, bundle1) = build(manifest_path)?;
+    let (metadata2, bundle2) = build(&other_manifest_path)?;
+
+    // then
+    assert_eq!(
+        metadata1, metadata2,
+        "metadata must not depend on the directory of the build"
+    );
+    assert_eq!(
+        bundle1, bundle2,
+        "bundle must not depend on the directory of the build"
+    );
+    fs::remove_dir_all(other_project_dir.parent().expect("dir must have a parent"))?;
+    Ok(())
+}
+
 fn unchanged_contract_no_metadata_artifacts_generates_metadata(
     manifest_path: &ManifestPath,
 ) -> Result<()> {
@@ -625,6 +741,50 @@ fn unchanged_contract_no_metadata_artifacts_generates_metadata(
     Ok(())
 }
 
+fn changed_git_state_regenerates_metadata(manifest_path: &ManifestPath) -> Result<()> {
+    // given
+    let args = ExecuteArgs {
+        manifest_path: manifest_path.clone(),
+        ..Default::default()
+    };
+    let res1 = super::execute(args.clone()).expect("build failed");
+    let metadata_path = res1.metadata_result.unwrap().dest_metadata;
+    let metadata_modified = file_last_modified(&metadata_path);
+
+    // when
+    let project_dir = manifest_path.absolute_directory()?;
+    let git = |args: &[&str]| duct::cmd("git", args).dir(&project_dir).run();
+    git(&["init", "--quiet"])?;
+    git(&[
+        "-c",
+        "user.name=test",
+        "-c",
+        "user.email=test@example.com",
+        "commit",
+        "--quiet",
+        "--allow-empty",
+        "--message",
+        "initial",
+    ])?;
+    let res2 = super::execute(args);
+    fs::remove_dir_all(project_dir.join(".git"))?;
+    let res2 = res2.expect("build failed");
+
+    // then
+    assert!(
+        res2.optimization_result.is_none(),
+        "Unchanged code should not be post processed"
+    );
+    assert_ne!(
+        metadata_modified,
+        file_last_modified(&metadata_path),
+        "Changed git state must regenerate the metadata"
+    );
+    let metadata = fs::read_to_string(&metadata_path)?;
+    assert!(metadata.contains("\"git\""), "{metadata}");
+    Ok(())
+}
+
 /// Get the last modified date of the given file.
 /// Panics if the file does not exist.
 fn file_last_modified(path: &Path) -> SystemTime {
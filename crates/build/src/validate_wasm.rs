@@ -14,10 +14,26 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use anyhow::Result;
+use crate::size_report::{
+    crate_of,
+    demangle,
+};
+use anyhow::{
+    Context,
+    Result,
+};
 use colored::Colorize;
 use impl_serde::serialize as serde_hex;
-use parity_wasm::elements::Module;
+use parity_wasm::elements::{
+    External,
+    ImportCountType,
+    Module,
+};
+use std::fmt::{
+    self,
+    Display,
+    Write,
+};
 
 /// Marker inserted by the ink! codegen for an error which can't
 /// be checked at compile time.
@@ -123,6 +139,211 @@ pub fn validate_import_section(module: &Module) -> Result<()> {
     Ok(())
 }
 
+/// The findings of the validation of the contract Wasm.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WasmValidation {
+    /// The host functions imported by the contract.
+    pub imports: Vec<HostFunction>,
+    /// The functions containing floating point instructions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub float_instructions: Vec<FloatInstructions>,
+    /// The imported host functions which are not provided by the chain.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown_imports: Vec<HostFunction>,
+}
+
+/// A host function imported by the contract.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HostFunction {
+    /// The module of the import, e.g. `seal0`.
+    pub module: String,
+    /// The name of the host function.
+    pub name: String,
+}
+
+impl Display for HostFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.module, self.name)
+    }
+}
+
+/// The floating point instructions of a function of the contract code.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FloatInstructions {
+    /// The index of the function.
+    pub function: u32,
+    /// The demangled name of the function, if the name section was kept.
+    pub name: Option<String>,
+    /// The crate the function originates from, if known.
+    #[serde(rename = "crate")]
+    pub krate: Option<String>,
+    /// The distinct floating point instructions of the function, e.g. `F64Add`.
+    pub instructions: Vec<String>,
+}
+
+impl WasmValidation {
+    /// Collects the imported host functions and the floating point instructions of the
+    /// Wasm `code`.
+    ///
+    /// Functions are only attributed to their crates if the code contains a name
+    /// section, i.e. if it was built with `--keep-debug-symbols`.
+    pub fn new(code: &[u8]) -> Result<Self> {
+        let module = parity_wasm::deserialize_buffer::<Module>(code)
+            .context("Loading of wasm module for the validation failed")?;
+        let module = module.parse_names().unwrap_or_else(|(_, module)| module);
+
+        let imports: Vec<_> = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .filter(|entry| matches!(entry.external(), External::Function(_)))
+            .map(|entry| {
+                HostFunction {
+                    module: entry.module().to_string(),
+                    name: entry.field().to_string(),
+                }
+            })
+            .collect();
+        let unknown_imports = imports
+            .iter()
+            .filter(|import| check_import(&import.module, &import.name).is_err())
+            .cloned()
+            .collect();
+
+        let names = module
+            .names_section()
+            .and_then(|names| names.functions())
+            .map(|functions| functions.names());
+        let imported = module.import_count(ImportCountType::Function);
+        let mut float_instructions = Vec::new();
+        for (i, body) in module
+            .code_section()
+            .map(|code| code.bodies())
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            let mut instructions = Vec::new();
+            for instruction in body.code().elements() {
+                // All floating point instructions, and only those, carry the type of one
+                // of their operands or of their result in their name, e.g. `F32Add` or
+                // `I64TruncSF64`.
+                let name = format!("{instruction:?}");
+                let opcode = name.split('(').next().unwrap_or_default();
+                if (opcode.contains("F32") || opcode.contains("F64"))
+                    && !instructions.iter().any(|i| i == opcode)
+                {
+                    instructions.push(opcode.to_string())
+                }
+            }
+            if instructions.is_empty() {
+                continue
+            }
+            let function = (imported + i) as u32;
+            let name = names
+                .and_then(|names| names.get(function))
+                .map(|name| demangle(name));
+            float_instructions.push(FloatInstructions {
+                function,
+                krate: name.as_deref().and_then(crate_of),
+                name,
+                instructions,
+            });
+        }
+
+        Ok(Self {
+            imports,
+            float_instructions,
+            unknown_imports,
+        })
+    }
+
+    /// Flags the imported host functions which are not part of the `host_functions`
+    /// provided by the chain.
+    ///
+    /// The names of the host functions are compared without their legacy `seal_`
+    /// prefix.
+    pub fn check_host_functions(&mut self, host_functions: &[String]) {
+        let provided = |import: &HostFunction| {
+            let name = import.name.strip_prefix("seal_").unwrap_or(&import.name);
+            host_functions.iter().any(|host_function| {
+                host_function.strip_prefix("seal_").unwrap_or(host_function) == name
+            })
+        };
+        for import in &self.imports {
+            if !provided(import) && !self.unknown_imports.contains(import) {
+                self.unknown_imports.push(import.clone())
+            }
+        }
+    }
+
+    /// Returns an error describing the findings for which the contracts pallet would
+    /// reject the code.
+    ///
+    /// Floating point instructions are only accepted for code uploaded with relaxed
+    /// determinism, which can only be executed off-chain.
+    pub fn ensure_valid(&self, allow_float_instructions: bool) -> Result<()> {
+        let mut errs = Vec::new();
+        if !allow_float_instructions && !self.float_instructions.is_empty() {
+            let mut err = String::from(
+                "The contract Wasm contains floating point instructions, which are \
+                rejected by pallet-contracts since their results are not deterministic:\n",
+            );
+            for function in &self.float_instructions {
+                let name = function
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("func[{}]", function.function));
+                let krate = match &function.krate {
+                    Some(krate) => format!(" (crate `{krate}`)"),
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    err,
+                    "  {name}{krate}: {}",
+                    function.instructions.join(", ")
+                );
+            }
+            if self.float_instructions.iter().all(|f| f.name.is_none()) {
+                err.push_str(
+                    "Build the contract with `--keep-debug-symbols` to see the names of \
+                    the functions.\n",
+                );
+            }
+            err.push_str(
+                "If the code is only executed off-chain, upload it with \
+                `cargo contract upload --determinism relaxed`.",
+            );
+            errs.push(err);
+        }
+        if !self.unknown_imports.is_empty() {
+            let mut err = String::from(
+                "The contract Wasm imports host functions which are not provided by the \
+                chain:\n",
+            );
+            for import in &self.unknown_imports {
+                let _ = writeln!(err, "  {import}");
+            }
+            err.push_str(
+                "The contract may have been built with an ink! version which is not \
+                supported by the chain.",
+            );
+            errs.push(err);
+        }
+        if !errs.is_empty() {
+            anyhow::bail!(
+                "Validation of the Wasm failed.\n\n\n{}\n\nIgnore with `--skip-wasm-validation`",
+                errs.into_iter()
+                    .map(|err| format!("{} {}", "ERROR:".to_string().bold(), err))
+                    .collect::<Vec<String>>()
+                    .join("\n\n\n")
+            )
+        }
+        Ok(())
+    }
+}
+
 /// Returns `Ok` if the import is allowed.
 fn check_import(module: &str, field: &str) -> Result<(), String> {
     if module.starts_with("seal") || field.starts_with("memory") {
@@ -190,7 +411,11 @@ fn parse_linker_error(field: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::validate_import_section;
+    use super::{
+        validate_import_section,
+        HostFunction,
+        WasmValidation,
+    };
     use parity_wasm::elements::Module;
 
     fn create_module(contract: &str) -> Module {
@@ -315,4 +540,82 @@ mod tests {
         // then
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn must_catch_float_instructions() {
+        // given
+        let contract = r#"
+            (module
+                (func (;0;) (param f32) (result i32)
+                    local.get 0
+                    i32.trunc_f32_s)
+            )"#;
+        let wasm = wabt::wat2wasm(contract).expect("invalid wabt");
+
+        // when
+        let validation = WasmValidation::new(&wasm).unwrap();
+        let res = validation.ensure_valid(false);
+
+        // then
+        assert_eq!(validation.float_instructions.len(), 1);
+        assert_eq!(validation.float_instructions[0].function, 0);
+        assert_eq!(
+            validation.float_instructions[0].instructions,
+            vec!["I32TruncSF32".to_string()]
+        );
+        let err = res.unwrap_err().to_string();
+        assert!(err.contains("floating point instructions"), "{err}");
+        assert!(err.contains("func[0]: I32TruncSF32"), "{err}");
+        assert!(err.contains("--determinism relaxed"), "{err}");
+        assert!(validation.ensure_valid(true).is_ok());
+    }
+
+    #[test]
+    fn must_validate_determinism_successfully() {
+        // given
+        let contract = r#"
+            (module
+                (func (;0;) (param i32) (result i64)
+                    local.get 0
+                    i64.extend_i32_s)
+            )"#;
+        let wasm = wabt::wat2wasm(contract).expect("invalid wabt");
+
+        // when
+        let res = WasmValidation::new(&wasm).unwrap().ensure_valid(false);
+
+        // then
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn must_catch_host_functions_not_provided_by_the_chain() {
+        // given
+        let contract = r#"
+            (module
+                (type (;0;) (func (param i32 i32)))
+                (import "seal0" "seal_input" (func (;0;) (type 0)))
+                (import "seal1" "instantiation_nonce" (func (;1;) (type 0)))
+                (import "env" "memory" (memory (;0;) 2 16))
+            )"#;
+        let wasm = wabt::wat2wasm(contract).expect("invalid wabt");
+        let mut validation = WasmValidation::new(&wasm).unwrap();
+
+        // when
+        validation.check_host_functions(&["input".to_string(), "call".to_string()]);
+        let res = validation.ensure_valid(false);
+
+        // then
+        assert_eq!(validation.imports.len(), 2);
+        assert_eq!(
+            validation.unknown_imports,
+            vec![HostFunction {
+                module: "seal1".into(),
+                name: "instantiation_nonce".into()
+            }]
+        );
+        let err = res.unwrap_err().to_string();
+        assert!(err.contains("not provided by the chain"), "{err}");
+        assert!(err.contains("seal1::instantiation_nonce"), "{err}");
+    }
 }
@@ -15,10 +15,12 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use anyhow::Result;
+use strum::IntoEnumIterator as _;
 use wasm_opt::{
     Feature,
     OptimizationOptions,
     Pass,
+    ShrinkLevel,
 };
 
 use std::{
@@ -33,6 +35,8 @@ pub struct WasmOptHandler {
     optimization_level: OptimizationPasses,
     /// Whether or not to keep debugging information in the final Wasm binary.
     keep_debug_symbols: bool,
+    /// The custom configuration of the `wasm-opt` run.
+    config: WasmOptConfig,
 }
 
 impl WasmOptHandler {
@@ -43,10 +47,12 @@ impl WasmOptHandler {
     pub fn new(
         optimization_level: OptimizationPasses,
         keep_debug_symbols: bool,
+        config: WasmOptConfig,
     ) -> Result<Self> {
         Ok(Self {
             optimization_level,
             keep_debug_symbols,
+            config,
         })
     }
 
@@ -59,7 +65,10 @@ impl WasmOptHandler {
             self.optimization_level
         );
 
-        OptimizationOptions::from(self.optimization_level)
+        tracing::debug!("Custom wasm-opt configuration: {:?}", self.config);
+
+        let mut options = OptimizationOptions::from(self.optimization_level);
+        options
             .mvp_features_only()
             // Since rustc 1.70 `SignExt` can't be disabled anymore. Hence we have to allow it,
             // in order that the Wasm binary containing these instructions can be loaded.
@@ -72,8 +81,20 @@ impl WasmOptHandler {
             // the memory is initialized to zeroes, otherwise it won't run the
             // memory-packing pre-pass.
             .zero_filled_memory(true)
-            .debug_info(self.keep_debug_symbols)
-            .run(original_wasm, dest_wasm)?;
+            .debug_info(self.keep_debug_symbols || self.config.debug_info);
+        if self.config.no_default_passes {
+            options.add_default_passes(false);
+        }
+        if let Some(shrink_level) = self.config.shrink_level()? {
+            options.shrink_level(shrink_level);
+        }
+        if self.config.converge {
+            options.set_converge();
+        }
+        for pass in self.config.passes()? {
+            options.add_pass(pass);
+        }
+        options.run(original_wasm, dest_wasm)?;
 
         if !dest_wasm.exists() {
             return Err(anyhow::anyhow!(
@@ -158,6 +179,141 @@ impl From<OptimizationPasses> for OptimizationOptions {
     }
 }
 
+/// Custom configuration of `wasm-opt`, on top of the [`OptimizationPasses`].
+///
+/// It is defined in the `[package.metadata.contract.wasm-opt]` section of the
+/// `Cargo.toml`, or with `--wasm-opt-args`, which takes precedence.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WasmOptConfig {
+    /// The names of additional passes to run, e.g. `dce`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passes: Vec<String>,
+    /// Do not run the default passes of the optimization level, only the given ones.
+    #[serde(
+        default,
+        alias = "no-default-passes",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub no_default_passes: bool,
+    /// The shrink level from `0` to `2`, overriding the one of the optimization level.
+    #[serde(
+        default,
+        alias = "shrink-level",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shrink_level: Option<u8>,
+    /// Run the passes until the code does not shrink anymore.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub converge: bool,
+    /// Keep the debug information, like `--keep-debug-symbols`.
+    #[serde(
+        default,
+        alias = "debug-info",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub debug_info: bool,
+}
+
+impl WasmOptConfig {
+    /// Returns `true` if nothing is configured.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Checks that the passes and the shrink level are supported by `wasm-opt`.
+    pub fn validate(&self) -> Result<()> {
+        self.passes()?;
+        self.shrink_level()?;
+        Ok(())
+    }
+
+    fn passes(&self) -> Result<Vec<Pass>> {
+        self.passes
+            .iter()
+            .map(|name| {
+                Pass::iter()
+                    .find(|pass| pass.name() == name)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown wasm-opt pass `{}`, the supported passes are: {}",
+                            name,
+                            supported_passes().join(", ")
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn shrink_level(&self) -> Result<Option<ShrinkLevel>> {
+        self.shrink_level
+            .map(|level| {
+                match level {
+                    0 => Ok(ShrinkLevel::Level0),
+                    1 => Ok(ShrinkLevel::Level1),
+                    2 => Ok(ShrinkLevel::Level2),
+                    _ => {
+                        anyhow::bail!(
+                            "Unsupported wasm-opt shrink level {}, expected 0, 1 or 2",
+                            level
+                        )
+                    }
+                }
+            })
+            .transpose()
+    }
+}
+
+impl str::FromStr for WasmOptConfig {
+    type Err = anyhow::Error;
+
+    /// Parses `wasm-opt` style arguments separated by whitespace or commas, e.g.
+    /// `--converge --shrink-level 2 --dce`.
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let mut config = Self::default();
+        let mut args = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|arg| !arg.is_empty());
+        while let Some(arg) = args.next() {
+            match arg {
+                "--converge" | "-c" => config.converge = true,
+                "--debuginfo" | "-g" => config.debug_info = true,
+                "--no-default-passes" => config.no_default_passes = true,
+                "--shrink-level" | "-s" => {
+                    let level = args.next().ok_or_else(|| {
+                        anyhow::anyhow!("Missing the value of wasm-opt argument `{arg}`")
+                    })?;
+                    config.shrink_level = Some(level.parse()?);
+                }
+                _ => {
+                    match arg
+                        .strip_prefix("--")
+                        .filter(|name| supported_passes().contains(name))
+                    {
+                        Some(pass) => config.passes.push(pass.to_owned()),
+                        None => {
+                            anyhow::bail!(
+                                "Unsupported wasm-opt argument `{}`, the supported arguments \
+                                are `--converge`, `--shrink-level <0|1|2>`, `--debuginfo`, \
+                                `--no-default-passes` and `--<pass>` with the passes: {}",
+                                arg,
+                                supported_passes().join(", ")
+                            )
+                        }
+                    }
+                }
+            }
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// The names of the passes supported by `wasm-opt`.
+fn supported_passes() -> Vec<&'static str> {
+    Pass::iter().map(|pass| pass.name()).collect()
+}
+
 /// Result of the optimization process.
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct OptimizationResult {
@@ -26,6 +26,7 @@ use super::{
 use crate::{
     CrateMetadata,
     OptimizationPasses,
+    WasmOptConfig,
 };
 
 use std::{
@@ -211,6 +212,20 @@ impl Manifest {
         Ok(self)
     }
 
+    /// Clear the `default` entry of the `[features]` section, so that the default
+    /// features are not activated when the package is used as a dependency.
+    pub fn with_empty_default_features(&mut self) -> Result<&mut Self> {
+        if let Some(features) = self.toml.get_mut("features") {
+            let features = features
+                .as_table_mut()
+                .ok_or_else(|| anyhow::anyhow!("[features] section should be a table"))?;
+            if features.contains_key("default") {
+                features.insert("default".into(), value::Value::Array(Vec::new()));
+            }
+        }
+        Ok(self)
+    }
+
     /// Extract `optimization-passes` from `[package.metadata.contract]`
     pub fn profile_optimization_passes(&mut self) -> Option<OptimizationPasses> {
         self.toml
@@ -225,6 +240,22 @@ impl Manifest {
             .map(Into::into)
     }
 
+    /// Extract the `[package.metadata.contract.wasm-opt]` section
+    pub fn profile_wasm_opt_config(&self) -> Result<Option<WasmOptConfig>> {
+        self.toml
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("contract"))
+            .and_then(|contract| contract.get("wasm-opt"))
+            .map(|config| {
+                config
+                    .clone()
+                    .try_into()
+                    .context("Invalid `[package.metadata.contract.wasm-opt]` section")
+            })
+            .transpose()
+    }
+
     /// Set preferred defaults for the `[profile.release]` section
     ///
     /// # Note
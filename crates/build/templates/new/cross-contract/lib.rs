@@ -0,0 +1,88 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod {{name}} {
+    use other_contract::OtherContractRef;
+
+    /// A contract which instantiates and calls `OtherContract`.
+    #[ink(storage)]
+    pub struct {{camel_name}} {
+        /// The instance of the called contract.
+        other_contract: OtherContractRef,
+    }
+
+    impl {{camel_name}} {
+        /// Instantiates `OtherContract` from its uploaded code, which is identified by
+        /// `other_contract_code_hash`.
+        #[ink(constructor)]
+        pub fn new(other_contract_code_hash: Hash) -> Self {
+            let other_contract = OtherContractRef::new(true)
+                .code_hash(other_contract_code_hash)
+                .endowment(0)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .instantiate();
+            Self { other_contract }
+        }
+
+        /// Flips the value of `OtherContract` and returns its new value.
+        #[ink(message)]
+        pub fn flip_and_get(&mut self) -> bool {
+            self.other_contract.flip();
+            self.other_contract.get()
+        }
+    }
+
+    /// This is how you'd write end-to-end (E2E) or integration tests for ink! contracts.
+    ///
+    /// Cross-contract calls are not supported by the off-chain environment of unit
+    /// tests, hence this contract is only tested end-to-end.
+    ///
+    /// When running these you need to make sure that you:
+    /// - Compile the tests with the `e2e-tests` feature flag enabled (`--features e2e-tests`)
+    /// - Are running a Substrate node which contains `pallet-contracts` in the background
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// The trait providing the methods to instantiate and call contracts.
+        use ink_e2e::ContractsBackend;
+
+        /// The End-to-End test `Result` type.
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// We test that the contract calls the instance of `OtherContract` it created.
+        #[ink_e2e::test]
+        async fn flip_and_get_works<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // Given
+            let other_contract_code = client
+                .upload("other_contract", &ink_e2e::alice())
+                .submit()
+                .await
+                .expect("other_contract upload failed");
+            let mut constructor = {{camel_name}}Ref::new(other_contract_code.code_hash);
+            let contract = client
+                .instantiate("{{name}}", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<{{camel_name}}>();
+
+            // When
+            let flip_and_get = call_builder.flip_and_get();
+            let value = client
+                .call(&ink_e2e::alice(), &flip_and_get)
+                .submit()
+                .await
+                .expect("flip_and_get failed")
+                .return_value();
+
+            // Then
+            assert!(!value);
+
+            Ok(())
+        }
+    }
+}
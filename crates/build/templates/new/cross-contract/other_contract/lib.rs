@@ -0,0 +1,34 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// The contract which is called by the `{{name}}` contract.
+#[ink::contract]
+mod other_contract {
+
+    /// Stores a single `bool` value on the storage.
+    #[ink(storage)]
+    pub struct OtherContract {
+        value: bool,
+    }
+
+    impl OtherContract {
+        /// Constructor that initializes the `bool` value to the given `init_value`.
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {
+            Self { value: init_value }
+        }
+
+        /// Flips the value of the stored `bool` from `true` to `false` and vice versa.
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        /// Returns the current value of the stored `bool`.
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+}
+
+pub use self::other_contract::OtherContractRef;
@@ -0,0 +1,284 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod {{name}} {
+    use ink::storage::Mapping;
+
+    /// A simple ERC-20 style fungible token.
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct {{camel_name}} {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to number of owned tokens.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping of the token amount which an account is allowed to withdraw
+        /// from another account.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` tokens from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// The token error types.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// Returned if not enough balance to fulfill a request is available.
+        InsufficientBalance,
+        /// Returned if not enough allowance to fulfill a request is available.
+        InsufficientAllowance,
+    }
+
+    /// The token result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl {{camel_name}} {
+        /// Creates a new token contract with the specified initial supply, which is
+        /// owned by the caller.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            let caller = Self::env().caller();
+            balances.insert(caller, &total_supply);
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: total_supply,
+            });
+            Self {
+                total_supply,
+                balances,
+                allowances: Default::default(),
+            }
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        ///
+        /// Returns `0` if the account is non-existent.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// Returns `0` if no allowance has been set.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        ///
+        /// If this function is called again it overwrites the current allowance with
+        /// `value`.
+        ///
+        /// An `Approval` event is emitted.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
+        /// for the caller to withdraw from `from`.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance)
+            }
+            self.transfer_from_to(&from, &to, value)?;
+            self.allowances.insert((from, caller), &(allowance - value));
+            Ok(())
+        }
+
+        fn transfer_from_to(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let from_balance = self.balance_of(*from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(*to);
+            self.balances.insert(to, &(to_balance + value));
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+    }
+
+    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
+    /// module and test functions are marked with a `#[test]` attribute.
+    /// The below code is technically just normal Rust code.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        /// The initial supply is owned by the creator of the token.
+        #[ink::test]
+        fn new_works() {
+            let token = {{camel_name}}::new(100);
+            assert_eq!(token.total_supply(), 100);
+            assert_eq!(token.balance_of(accounts().alice), 100);
+            assert_eq!(token.balance_of(accounts().bob), 0);
+        }
+
+        /// Tokens can be transferred, but not more than the balance.
+        #[ink::test]
+        fn transfer_works() {
+            let mut token = {{camel_name}}::new(100);
+            assert_eq!(token.transfer(accounts().bob, 10), Ok(()));
+            assert_eq!(token.balance_of(accounts().alice), 90);
+            assert_eq!(token.balance_of(accounts().bob), 10);
+            assert_eq!(
+                token.transfer(accounts().bob, 91),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        /// Tokens can be transferred on behalf of another account up to the allowance.
+        #[ink::test]
+        fn transfer_from_works() {
+            let mut token = {{camel_name}}::new(100);
+            let accounts = accounts();
+            assert_eq!(token.approve(accounts.bob, 10), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.eve, 11),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(token.transfer_from(accounts.alice, accounts.eve, 10), Ok(()));
+            assert_eq!(token.balance_of(accounts.eve), 10);
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+        }
+    }
+
+    /// This is how you'd write end-to-end (E2E) or integration tests for ink! contracts.
+    ///
+    /// When running these you need to make sure that you:
+    /// - Compile the tests with the `e2e-tests` feature flag enabled (`--features e2e-tests`)
+    /// - Are running a Substrate node which contains `pallet-contracts` in the background
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// The trait providing the methods to instantiate and call contracts.
+        use ink_e2e::ContractsBackend;
+
+        /// The End-to-End test `Result` type.
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// We test that tokens can be transferred on chain.
+        #[ink_e2e::test]
+        async fn transfer_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            // Given
+            let mut constructor = {{camel_name}}Ref::new(100);
+            let contract = client
+                .instantiate("{{name}}", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<{{camel_name}}>();
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            // When
+            let transfer = call_builder.transfer(bob, 10);
+            client
+                .call(&ink_e2e::alice(), &transfer)
+                .submit()
+                .await
+                .expect("transfer failed");
+
+            // Then
+            let balance_of = call_builder.balance_of(bob);
+            let balance = client
+                .call(&ink_e2e::alice(), &balance_of)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(balance, 10);
+
+            Ok(())
+        }
+    }
+}
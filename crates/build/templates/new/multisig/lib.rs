@@ -0,0 +1,349 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod {{name}} {
+    use ink::{
+        env::{
+            call::{
+                build_call,
+                ExecutionInput,
+            },
+            CallFlags,
+        },
+        prelude::vec::Vec,
+        storage::Mapping,
+    };
+
+    /// The identifier of a transaction.
+    pub type TransactionId = u32;
+
+    /// A call to another contract, which is executed once it is confirmed by enough
+    /// owners.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Transaction {
+        /// The account of the contract which is called.
+        pub callee: AccountId,
+        /// The selector of the message which is called.
+        pub selector: [u8; 4],
+        /// The SCALE encoded arguments of the message.
+        pub input: Vec<u8>,
+        /// The amount of balance transferred to the callee.
+        pub transferred_value: Balance,
+        /// The maximum amount of computation time the call may use.
+        pub ref_time_limit: u64,
+    }
+
+    /// Event emitted when an owner submits a transaction.
+    #[ink(event)]
+    pub struct Submission {
+        #[ink(topic)]
+        transaction: TransactionId,
+    }
+
+    /// Event emitted when an owner confirms a transaction.
+    #[ink(event)]
+    pub struct Confirmation {
+        #[ink(topic)]
+        transaction: TransactionId,
+        #[ink(topic)]
+        from: AccountId,
+    }
+
+    /// Event emitted when a transaction is executed.
+    #[ink(event)]
+    pub struct Execution {
+        #[ink(topic)]
+        transaction: TransactionId,
+    }
+
+    /// The multisig error types.
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        /// The caller is not an owner of the wallet.
+        NotOwner,
+        /// The threshold is zero or larger than the number of owners.
+        InvalidThreshold,
+        /// No transaction with the given id exists.
+        UnknownTransaction,
+        /// The caller already confirmed the transaction.
+        AlreadyConfirmed,
+        /// The transaction does not have enough confirmations.
+        NotConfirmed,
+        /// The call of the transaction failed.
+        CallFailed,
+    }
+
+    /// The multisig result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A wallet which executes a transaction once `threshold` of its owners confirmed it.
+    #[ink(storage)]
+    pub struct {{camel_name}} {
+        /// The accounts which may submit and confirm transactions.
+        owners: Vec<AccountId>,
+        /// The number of confirmations required to execute a transaction.
+        threshold: u32,
+        /// The transactions which are not executed yet.
+        transactions: Mapping<TransactionId, Transaction>,
+        /// The owners which confirmed a transaction.
+        confirmations: Mapping<(TransactionId, AccountId), ()>,
+        /// The number of confirmations of a transaction.
+        confirmation_count: Mapping<TransactionId, u32>,
+        /// The id of the next submitted transaction.
+        next_id: TransactionId,
+    }
+
+    impl {{camel_name}} {
+        /// Creates a new wallet of the `owners`, which requires `threshold`
+        /// confirmations to execute a transaction.
+        #[ink(constructor)]
+        pub fn new(owners: Vec<AccountId>, threshold: u32) -> Result<Self> {
+            if threshold == 0 || threshold as usize > owners.len() {
+                return Err(Error::InvalidThreshold)
+            }
+            Ok(Self {
+                owners,
+                threshold,
+                transactions: Mapping::default(),
+                confirmations: Mapping::default(),
+                confirmation_count: Mapping::default(),
+                next_id: 0,
+            })
+        }
+
+        /// Returns the owners of the wallet.
+        #[ink(message)]
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.owners.clone()
+        }
+
+        /// Returns the number of confirmations required to execute a transaction.
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.threshold
+        }
+
+        /// Returns the number of confirmations of the transaction.
+        #[ink(message)]
+        pub fn confirmations(&self, id: TransactionId) -> u32 {
+            self.confirmation_count.get(id).unwrap_or_default()
+        }
+
+        /// Submits a transaction, which is confirmed by the caller.
+        #[ink(message)]
+        pub fn submit(&mut self, transaction: Transaction) -> Result<TransactionId> {
+            self.ensure_owner()?;
+            let id = self.next_id;
+            self.next_id = id.checked_add(1).expect("transaction ids are exhausted");
+            self.transactions.insert(id, &transaction);
+            self.env().emit_event(Submission { transaction: id });
+            self.confirm(id)?;
+            Ok(id)
+        }
+
+        /// Confirms the transaction.
+        #[ink(message)]
+        pub fn confirm(&mut self, id: TransactionId) -> Result<()> {
+            let caller = self.ensure_owner()?;
+            if !self.transactions.contains(id) {
+                return Err(Error::UnknownTransaction)
+            }
+            if self.confirmations.contains((id, caller)) {
+                return Err(Error::AlreadyConfirmed)
+            }
+            self.confirmations.insert((id, caller), &());
+            self.confirmation_count.insert(id, &(self.confirmations(id) + 1));
+            self.env().emit_event(Confirmation {
+                transaction: id,
+                from: caller,
+            });
+            Ok(())
+        }
+
+        /// Executes the transaction, once it has been confirmed by enough owners.
+        #[ink(message, payable)]
+        pub fn execute(&mut self, id: TransactionId) -> Result<()> {
+            self.ensure_owner()?;
+            let transaction = self
+                .transactions
+                .get(id)
+                .ok_or(Error::UnknownTransaction)?;
+            if self.confirmations(id) < self.threshold {
+                return Err(Error::NotConfirmed)
+            }
+            self.transactions.remove(id);
+            self.confirmation_count.remove(id);
+            for owner in &self.owners {
+                self.confirmations.remove((id, *owner));
+            }
+
+            build_call::<Environment>()
+                .call(transaction.callee)
+                .ref_time_limit(transaction.ref_time_limit)
+                .transferred_value(transaction.transferred_value)
+                .call_flags(CallFlags::ALLOW_REENTRY)
+                .exec_input(
+                    ExecutionInput::new(transaction.selector.into())
+                        .push_arg(CallInput(&transaction.input)),
+                )
+                .returns::<()>()
+                .try_invoke()
+                .map_err(|_| Error::CallFailed)?
+                .map_err(|_| Error::CallFailed)?;
+            self.env().emit_event(Execution { transaction: id });
+            Ok(())
+        }
+
+        /// Returns the caller if it is an owner of the wallet.
+        fn ensure_owner(&self) -> Result<AccountId> {
+            let caller = self.env().caller();
+            if !self.owners.contains(&caller) {
+                return Err(Error::NotOwner)
+            }
+            Ok(caller)
+        }
+    }
+
+    /// The already encoded arguments of a call, which are passed on as they are.
+    struct CallInput<'a>(&'a [u8]);
+
+    impl<'a> scale::Encode for CallInput<'a> {
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
+    /// module and test functions are marked with a `#[test]` attribute.
+    /// The below code is technically just normal Rust code.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn wallet() -> {{camel_name}} {
+            let accounts = accounts();
+            {{camel_name}}::new(vec![accounts.alice, accounts.bob, accounts.charlie], 2)
+                .expect("the threshold is valid")
+        }
+
+        fn transaction() -> Transaction {
+            Transaction {
+                callee: accounts().django,
+                selector: [0; 4],
+                input: Vec::new(),
+                transferred_value: 0,
+                ref_time_limit: 0,
+            }
+        }
+
+        /// The threshold must be reachable by the owners.
+        #[ink::test]
+        fn new_rejects_invalid_threshold() {
+            let owners = vec![accounts().alice];
+            assert!(matches!(
+                {{camel_name}}::new(owners.clone(), 0),
+                Err(Error::InvalidThreshold)
+            ));
+            assert!(matches!(
+                {{camel_name}}::new(owners, 2),
+                Err(Error::InvalidThreshold)
+            ));
+        }
+
+        /// A transaction is confirmed once by each owner.
+        #[ink::test]
+        fn confirm_works() {
+            let mut wallet = wallet();
+            let id = wallet.submit(transaction()).expect("alice is an owner");
+            assert_eq!(wallet.confirmations(id), 1);
+            assert_eq!(wallet.confirm(id), Err(Error::AlreadyConfirmed));
+
+            set_caller(accounts().bob);
+            assert_eq!(wallet.confirm(id), Ok(()));
+            assert_eq!(wallet.confirmations(id), 2);
+
+            set_caller(accounts().eve);
+            assert_eq!(wallet.confirm(id), Err(Error::NotOwner));
+        }
+
+        /// A transaction is not executed before it has enough confirmations.
+        #[ink::test]
+        fn execute_requires_threshold() {
+            let mut wallet = wallet();
+            let id = wallet.submit(transaction()).expect("alice is an owner");
+            assert_eq!(wallet.execute(id), Err(Error::NotConfirmed));
+            assert_eq!(wallet.execute(id + 1), Err(Error::UnknownTransaction));
+        }
+    }
+
+    /// This is how you'd write end-to-end (E2E) or integration tests for ink! contracts.
+    ///
+    /// When running these you need to make sure that you:
+    /// - Compile the tests with the `e2e-tests` feature flag enabled (`--features e2e-tests`)
+    /// - Are running a Substrate node which contains `pallet-contracts` in the background
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// The trait providing the methods to instantiate and call contracts.
+        use ink_e2e::ContractsBackend;
+
+        /// The End-to-End test `Result` type.
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// We test that a submitted transaction is confirmed by its submitter.
+        #[ink_e2e::test]
+        async fn submit_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            // Given
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let mut constructor = {{camel_name}}Ref::new(vec![alice, bob], 2);
+            let contract = client
+                .instantiate("{{name}}", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<{{camel_name}}>();
+
+            // When
+            let submit = call_builder.submit(Transaction {
+                callee: bob,
+                selector: [0; 4],
+                input: Vec::new(),
+                transferred_value: 0,
+                ref_time_limit: 0,
+            });
+            let id = client
+                .call(&ink_e2e::alice(), &submit)
+                .submit()
+                .await
+                .expect("submit failed")
+                .return_value()
+                .expect("alice is an owner");
+
+            // Then
+            let confirmations = call_builder.confirmations(id);
+            let confirmations = client
+                .call(&ink_e2e::alice(), &confirmations)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(confirmations, 1);
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Assertions on the result of a `call` dry-run with `--expect` and
+//! `--expect-reverted`, to check a deployed contract in CI pipelines without parsing
+//! the JSON output.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_build::{
+    name_value_println,
+    util::DEFAULT_KEY_COL_WIDTH,
+};
+use contract_extrinsics::{
+    AssertionFailed,
+    ContractMessageTranscoder,
+};
+use contract_transcode::Value;
+
+/// Expectations on the result of a dry-run, failing the command with exit code 2 if
+/// they are not met.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct ExpectOpts {
+    /// Assert that the dry-run returns the given value, in the syntax of the message
+    /// arguments, e.g. `42` or `Some(Alice)`. The `Ok` wrapping the return value of
+    /// ink! messages may be omitted.
+    #[clap(long, value_name = "VALUE", conflicts_with = "execute")]
+    expect: Option<String>,
+    /// Assert that the message reverts in the dry-run.
+    #[clap(long, conflicts_with = "execute")]
+    expect_reverted: bool,
+}
+
+impl ExpectOpts {
+    /// Returns the expectations of a step of a script run with `cargo contract run`.
+    pub fn new(expect: Option<String>, expect_reverted: bool) -> Self {
+        Self {
+            expect,
+            expect_reverted,
+        }
+    }
+
+    /// Parses the value expected with `--expect` as a return value of the `message`,
+    /// which requires the contract metadata.
+    pub fn expected_value(
+        &self,
+        transcoder: Option<&ContractMessageTranscoder>,
+        message: &str,
+    ) -> Result<Option<Value>> {
+        let Some(expect) = &self.expect else {
+            return Ok(None)
+        };
+        let transcoder = transcoder.ok_or_else(|| {
+            anyhow!(
+                "`--expect` requires the contract metadata to decode the return value"
+            )
+        })?;
+        transcoder.parse_message_return(message, expect).map(Some)
+    }
+
+    /// Checks the returned `value` and whether the message `reverted` against the
+    /// expectations, `None` if there are none.
+    pub fn check(
+        &self,
+        expected: Option<Value>,
+        value: &Value,
+        reverted: bool,
+    ) -> Option<Assertion> {
+        if expected.is_none() && !self.expect_reverted {
+            return None
+        }
+        let mut differences = Vec::new();
+        if let Some(expected) = &expected {
+            diff_values("", expected, value, &mut differences);
+        }
+        if self.expect_reverted && !reverted {
+            differences.push("expected the message to revert, it did not".to_string());
+        }
+        Some(Assertion {
+            passed: differences.is_empty(),
+            expected,
+            expect_reverted: self.expect_reverted,
+            differences,
+        })
+    }
+}
+
+/// The outcome of checking a dry-run result against the expectations.
+#[derive(Debug, serde::Serialize)]
+pub struct Assertion {
+    /// Whether the result met all expectations
+    pub passed: bool,
+    /// The return value expected with `--expect`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// Set if the message was expected to revert
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub expect_reverted: bool,
+    /// How the result differs from the expectations, one line per difference
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<String>,
+}
+
+impl Assertion {
+    pub fn print(&self) {
+        if let Some(expected) = &self.expected {
+            name_value_println!("Expected", expected.to_string(), DEFAULT_KEY_COL_WIDTH);
+        }
+        let outcome = if self.passed { "passed" } else { "failed" };
+        name_value_println!("Assertion", outcome, DEFAULT_KEY_COL_WIDTH);
+        for difference in &self.differences {
+            println!("{:>width$} {difference}", "", width = DEFAULT_KEY_COL_WIDTH);
+        }
+    }
+
+    /// Returns an error if the result did not meet the expectations.
+    pub fn into_result(self) -> Result<(), AssertionFailed> {
+        if self.passed {
+            return Ok(())
+        }
+        Err(AssertionFailed {
+            error: format!("Assertion failed: {}", self.differences.join("; ")),
+        })
+    }
+}
+
+/// Collects the differences between the `expected` and the `actual` value, naming the
+/// nested fields, tuple fields and sequence elements which differ by their `path`.
+fn diff_values(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Map(expected_map), Value::Map(actual_map))
+            if expected_map.ident() == actual_map.ident() =>
+        {
+            for (key, expected) in expected_map.iter() {
+                let path = field_path(path, &key_name(key));
+                match actual_map.iter().find(|(actual_key, _)| *actual_key == key) {
+                    Some((_, actual)) => diff_values(&path, expected, actual, out),
+                    None => out.push(format!("{path}: expected `{expected}`, missing")),
+                }
+            }
+            for (key, actual) in actual_map.iter() {
+                if !expected_map
+                    .iter()
+                    .any(|(expected_key, _)| expected_key == key)
+                {
+                    let path = field_path(path, &key_name(key));
+                    out.push(format!("{path}: unexpected `{actual}`"));
+                }
+            }
+        }
+        (Value::Tuple(expected_tuple), Value::Tuple(actual_tuple))
+            if expected_tuple.ident() == actual_tuple.ident()
+                && expected_tuple.values().count() == actual_tuple.values().count() =>
+        {
+            let ident = expected_tuple.ident().unwrap_or_default();
+            let path = field_path(path, &ident);
+            for (i, (expected, actual)) in expected_tuple
+                .values()
+                .zip(actual_tuple.values())
+                .enumerate()
+            {
+                diff_values(&field_path(&path, &i.to_string()), expected, actual, out);
+            }
+        }
+        (Value::Seq(expected_seq), Value::Seq(actual_seq))
+            if expected_seq.len() == actual_seq.len() =>
+        {
+            for (i, (expected, actual)) in expected_seq
+                .elems()
+                .iter()
+                .zip(actual_seq.elems())
+                .enumerate()
+            {
+                diff_values(&format!("{path}[{i}]"), expected, actual, out);
+            }
+        }
+        // maps are equal regardless of their identifiers, e.g. of enum variants
+        (Value::Map(_), Value::Map(_)) => {
+            out.push(mismatch(path, expected, actual));
+        }
+        _ if expected != actual => out.push(mismatch(path, expected, actual)),
+        _ => {}
+    }
+}
+
+fn mismatch(path: &str, expected: &Value, actual: &Value) -> String {
+    if path.is_empty() {
+        format!("expected `{expected}`, got `{actual}`")
+    } else {
+        format!("{path}: expected `{expected}`, got `{actual}`")
+    }
+}
+
+fn field_path(path: &str, field: &str) -> String {
+    match (path.is_empty(), field.is_empty()) {
+        (_, true) => path.to_string(),
+        (true, false) => field.to_string(),
+        (false, false) => format!("{path}.{field}"),
+    }
+}
+
+/// Returns the name of a map key: field names are strings, displayed without quotes.
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(name) => name.clone(),
+        key => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_extrinsics::ErrorVariant;
+    use contract_transcode::{
+        Map,
+        Tuple,
+    };
+
+    fn ok(value: Value) -> Value {
+        Value::Tuple(Tuple::new(Some("Ok"), vec![value]))
+    }
+
+    fn account(balance: u128, owner: &str) -> Value {
+        Value::Map(Map::new(
+            Some("Account"),
+            [
+                (Value::String("balance".into()), Value::UInt(balance)),
+                (Value::String("owner".into()), Value::Literal(owner.into())),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    fn expect(value: Option<&str>, reverted: bool) -> ExpectOpts {
+        ExpectOpts {
+            expect: value.map(Into::into),
+            expect_reverted: reverted,
+        }
+    }
+
+    #[test]
+    fn no_assertion_without_expectations() {
+        let opts = expect(None, false);
+        assert!(opts.check(None, &Value::UInt(42), false).is_none());
+    }
+
+    #[test]
+    fn matching_value_passes() {
+        let opts = expect(Some("42"), false);
+        let value = ok(Value::UInt(42));
+        let assertion = opts.check(Some(value.clone()), &value, false).unwrap();
+        assert!(assertion.passed);
+        assert!(assertion.differences.is_empty());
+        assert!(assertion.into_result().is_ok());
+    }
+
+    #[test]
+    fn differences_name_the_nested_fields() {
+        let opts = expect(Some("..."), false);
+        let expected = ok(account(42, "alice"));
+        let actual = ok(account(41, "alice"));
+        let assertion = opts.check(Some(expected), &actual, false).unwrap();
+        assert!(!assertion.passed);
+        assert_eq!(
+            assertion.differences,
+            vec!["Ok.0.balance: expected `42`, got `41`".to_string()]
+        );
+
+        let err = ErrorVariant::from(assertion.into_result().unwrap_err());
+        assert_eq!(err.exit_code(), 2);
+        assert_eq!(
+            err.to_string(),
+            "Assertion failed: Ok.0.balance: expected `42`, got `41`"
+        );
+    }
+
+    #[test]
+    fn differing_variants_are_reported_as_a_whole() {
+        let opts = expect(Some("..."), false);
+        let expected = ok(Value::UInt(1));
+        let actual = Value::Tuple(Tuple::new(Some("Err"), vec![Value::UInt(1)]));
+        let assertion = opts.check(Some(expected), &actual, false).unwrap();
+        assert_eq!(
+            assertion.differences,
+            vec!["expected `Ok(1)`, got `Err(1)`".to_string()]
+        );
+    }
+
+    #[test]
+    fn expect_reverted() {
+        let opts = expect(None, true);
+        let value = ok(Value::Unit);
+        assert!(opts.check(None, &value, true).unwrap().passed);
+
+        let assertion = opts.check(None, &value, false).unwrap();
+        assert!(!assertion.passed);
+        assert_eq!(
+            assertion.differences,
+            vec!["expected the message to revert, it did not".to_string()]
+        );
+    }
+
+    #[test]
+    fn expected_value_requires_metadata() {
+        let opts = expect(Some("42"), false);
+        let err = opts.expected_value(None, "get").unwrap_err();
+        assert!(err.to_string().contains("requires the contract metadata"));
+        assert!(expect(None, true)
+            .expected_value(None, "get")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -15,6 +15,7 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use anyhow::Result;
+use colored::Colorize;
 use contract_build::{
     BuildArtifacts,
     BuildMode,
@@ -31,9 +32,12 @@ use contract_build::{
     UnstableOptions,
     Verbosity,
     VerbosityFlags,
+    WasmOptConfig,
 };
 use std::{
     convert::TryFrom,
+    fmt::Write,
+    fs,
     path::PathBuf,
 };
 
@@ -132,10 +136,69 @@ pub struct BuildCommand {
     /// Specify a custom image for the verifiable build
     #[clap(long, default_value = None)]
     image: Option<String>,
+    /// Build all ink! contracts of the workspace. This is the default when the manifest
+    /// is a virtual workspace manifest.
+    #[clap(long, conflicts_with = "verifiable")]
+    workspace: bool,
+    /// Build only the given ink! contracts of the workspace.
+    #[clap(short, long, conflicts_with = "verifiable")]
+    package: Vec<String>,
+    /// Write the contract artifacts to the given directory instead of `target/ink`.
+    ///
+    /// A relative path is interpreted relative to the manifest directory. It is also
+    /// possible to define the directory in the `[package.metadata.contract]` of your
+    /// `Cargo.toml` as e.g. `output-dir = "dist/contracts"`, which is then honored by
+    /// `upload` and `instantiate` too.
+    #[clap(long, value_parser, conflicts_with = "verifiable")]
+    output_dir: Option<PathBuf>,
+    /// Report the size of the optimized contract code by function and crate.
+    ///
+    /// The functions are only attributed to their crates if the name section is kept
+    /// with `--keep-debug-symbols`.
+    #[clap(long)]
+    size_report: bool,
+    /// Custom `wasm-opt` arguments on top of the optimization passes, separated by
+    /// whitespace or commas, e.g. `--wasm-opt-args=--converge,--shrink-level,2,--dce`.
+    ///
+    /// - Supported are `--converge`, `--shrink-level <0|1|2>`, `--debuginfo`,
+    ///   `--no-default-passes` and the names of the `wasm-opt` passes as `--<pass>`.
+    ///
+    /// - It is possible to define the configuration in the
+    ///   `[package.metadata.contract.wasm-opt]` of your `Cargo.toml` as e.g. `passes =
+    ///   ["dce"]`, `no-default-passes`, `shrink-level`, `converge` and `debug-info`. The
+    ///   CLI argument always takes precedence over the profile value.
+    #[clap(long, allow_hyphen_values = true)]
+    wasm_opt_args: Option<WasmOptConfig>,
+    /// Do not embed the git commit, tag and dirty state of the repository of the
+    /// contract into the build info of the metadata.
+    #[clap(long)]
+    no_build_info: bool,
+    /// Post process the code and generate the metadata even if they are up to date.
+    #[clap(long)]
+    force: bool,
 }
 
 impl BuildCommand {
+    /// Returns whether the ink! contracts of a workspace are built, instead of a single
+    /// contract.
+    pub fn is_workspace_build(&self) -> Result<bool> {
+        if self.workspace || !self.package.is_empty() {
+            return Ok(true)
+        }
+        let manifest_path = ManifestPath::try_from(self.manifest_path.as_ref())?;
+        contract_build::is_virtual_manifest(&manifest_path)
+    }
+
     pub fn exec(&self) -> Result<BuildResult> {
+        contract_build::execute(self.execute_args()?)
+    }
+
+    /// Builds the ink! contracts of the workspace, see [`Self::is_workspace_build`].
+    pub fn exec_workspace(&self) -> Result<Vec<BuildResult>> {
+        contract_build::execute_workspace(self.execute_args()?, &self.package)
+    }
+
+    fn execute_args(&self) -> Result<ExecuteArgs> {
         let manifest_path = ManifestPath::try_from(self.manifest_path.as_ref())?;
         let unstable_flags: UnstableFlags =
             TryFrom::<&UnstableOptions>::try_from(&self.unstable_options)?;
@@ -185,9 +248,45 @@ impl BuildCommand {
             target: self.target,
             max_memory_pages: self.max_memory_pages,
             image,
+            output_dir: self.output_dir.clone(),
+            size_report: self.size_report,
+            wasm_opt_config: self.wasm_opt_args.clone(),
+            no_git_info: self.no_build_info,
+            force: self.force,
         };
-        contract_build::execute(args)
+        Ok(args)
+    }
+}
+
+/// Displays the artifacts of the contracts built by a workspace build, with their size
+/// and code hash.
+pub fn display_workspace_results(results: &[BuildResult]) -> Result<String> {
+    let mut out = String::new();
+    for result in results {
+        let Some(dest_wasm) = result.dest_wasm.as_ref() else {
+            continue
+        };
+        let code = fs::read(dest_wasm)?;
+        let artifact = result
+            .metadata_result
+            .as_ref()
+            .map_or(dest_wasm, |metadata| &metadata.dest_bundle);
+        let _ = writeln!(
+            out,
+            "  - {} ({:.1}K, code hash 0x{})",
+            artifact.display().to_string().bold(),
+            code.len() as f64 / 1000.0,
+            hex::encode(contract_build::code_hash(&code)),
+        );
+    }
+    if out.is_empty() {
+        return Ok(format!("Checked {} contracts.", results.len()))
     }
+    Ok(format!(
+        "Built {} contracts, the artifacts are ready:\n{}",
+        results.len(),
+        out.trim_end()
+    ))
 }
 
 #[derive(Debug, clap::Args)]
@@ -221,6 +320,11 @@ impl CheckCommand {
             target: Default::default(),
             max_memory_pages: 0,
             image: ImageVariant::Default,
+            output_dir: None,
+            size_report: false,
+            wasm_opt_config: None,
+            no_git_info: true,
+            force: false,
         };
 
         contract_build::execute(args)
@@ -16,58 +16,121 @@
 
 use crate::ErrorVariant;
 
+use colored::Colorize;
 use contract_build::util::DEFAULT_KEY_COL_WIDTH;
 use ink_env::{
     DefaultEnvironment,
     Environment,
 };
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+};
 
 use super::{
-    create_signer,
+    assertion::{
+        Assertion,
+        ExpectOpts,
+    },
+    config::{
+        with_chain_config,
+        ExtrinsicBalance,
+        ExtrinsicConfig,
+    },
+    denominate_balance,
     display_contract_exec_result,
     display_contract_exec_result_debug,
+    display_dry_run_events,
     display_dry_run_result_warning,
+    parse_node_url,
+    pre_submit_dry_run_error,
+    print_block_usage,
     print_dry_running_status,
+    print_fuzzy_match,
     print_gas_required_success,
     prompt_confirm_tx,
+    undecoded_note,
+    warn_block_usage,
+    AccountIdArg,
+    AdditionalMetadataOpts,
     CLIExtrinsicOpts,
+    ContractAddressArg,
+    DebugMessageOpts,
+    DisplayLimitOpts,
+    ExecutionOutcome,
+    ExternalSignerOpts,
+    PaidUnpayable,
+    TxCosts,
+    WeightArg,
     MAX_KEY_COL_WIDTH,
 };
 use anyhow::{
     anyhow,
-    Context,
     Result,
 };
-use contract_build::name_value_println;
+use contract_build::{
+    name_value_println,
+    util::decode_hex,
+};
 use contract_extrinsics::{
+    block_usage,
     pallet_contracts_primitives::StorageDeposit,
+    Address,
     BalanceVariant,
+    BlockUsage,
     CallCommandBuilder,
     CallExec,
+    Connection,
+    DebugMessage,
+    DisplayBalance,
     DisplayEvents,
     ExtrinsicOptsBuilder,
-    TokenMetadata,
+    MortalityParams,
+    Progress,
+    XcmFee,
 };
-use contract_transcode::Value;
-use sp_weights::Weight;
-use subxt::{
-    Config,
-    PolkadotConfig as DefaultConfig,
+use contract_transcode::{
+    Hex,
+    Value,
 };
+use sp_core::Bytes;
+use sp_weights::Weight;
+use subxt::config::ExtrinsicParams;
 use subxt_signer::sr25519::Keypair;
 #[derive(Debug, clap::Args)]
 #[clap(name = "call", about = "Call a contract")]
 pub struct CallCommand {
     /// The address of the the contract to call.
     #[clap(name = "contract", long, env = "CONTRACT")]
-    contract: <DefaultConfig as Config>::AccountId,
+    contract: ContractAddressArg,
     /// The name of the contract message to call.
-    #[clap(long, short)]
-    message: String,
-    /// The arguments of the contract message to call.
+    #[clap(long, short, required_unless_present = "selector")]
+    message: Option<String>,
+    /// The 4 byte selector of the contract message to call, as hex, instead of its name.
+    /// If both are given, the name must be the name of the message with the selector.
+    #[clap(long, value_parser = parse_selector)]
+    selector: Option<[u8; 4]>,
+    /// Call the message whose name matches `--message` case-insensitively, or starts
+    /// with it, if it is the only match.
+    #[clap(long)]
+    fuzzy_match: bool,
+    /// The arguments of the contract message to call. An argument `@path` is read from
+    /// the file at `path`, `@-` from stdin, and `@@` escapes a leading `@`.
     #[clap(long, num_args = 0..)]
     args: Vec<String>,
+    /// Read `@path` argument files of byte array arguments as hex if they start with
+    /// `0x`, instead of as raw bytes.
+    #[clap(long)]
+    args_hex_files: bool,
+    /// The SCALE encoded arguments of the message with `--selector`, as hex. The
+    /// contract is called without its metadata, so the returned data is displayed as
+    /// hex.
+    #[clap(long, requires = "selector", conflicts_with_all = ["message", "args"], value_parser = parse_hex_bytes)]
+    data: Option<Bytes>,
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
     /// Maximum amount of gas (execution time) to be used for this command.
@@ -80,12 +143,71 @@ pub struct CallCommand {
     /// the call.
     #[clap(long)]
     proof_size: Option<u64>,
-    /// The value to be transferred as part of the call.
+    /// Maximum weight for this call as `<ref_time>,<proof_size>`, combining `--gas`
+    /// and `--proof-size`. The reference time accepts the `ms` suffix and the proof size
+    /// the `KiB` and `MiB` suffixes, e.g. `2ms,256KiB`.
+    #[clap(long, conflicts_with_all = ["gas", "proof_size"])]
+    weight: Option<WeightArg>,
+    /// The value to be transferred as part of the call, or `max` to transfer the entire
+    /// free balance of the signer minus the fee and the existential deposit.
     #[clap(name = "value", long, default_value = "0")]
-    value: BalanceVariant<<DefaultEnvironment as Environment>::Balance>,
+    value: CallValue,
+    /// Dry-run with the given account as the caller instead of the signer. `--suri` is
+    /// not required with an origin, which can not be combined with `--execute`.
+    #[clap(long, conflicts_with = "execute")]
+    origin: Option<AccountIdArg>,
     /// Export the call output in JSON format.
-    #[clap(long, conflicts_with = "verbose")]
+    #[clap(long, conflicts_with_all = ["verbose", "quiet"])]
     output_json: bool,
+    /// Wrap the submitted call in `Sudo::sudo` to dispatch it with the root origin, e.g.
+    /// on development chains which only allow root to upload code. The signer must be
+    /// the sudo key of the chain.
+    #[clap(long, requires = "execute")]
+    sudo: bool,
+    /// Call the contract on the parachain with the given id from the chain of `--url`,
+    /// its relay chain or a sibling parachain, by sending the call as an XCM `Transact`.
+    /// The call is dispatched by the account the parachain derives from the signer and
+    /// the sending chain, which pays the `--xcm-fee`.
+    #[clap(
+        long,
+        requires_all = ["execute", "dest_url", "xcm_fee"],
+        conflicts_with_all = ["sudo", "sign_externally"]
+    )]
+    xcm_dest: Option<u32>,
+    /// The url of a node of the parachain of the contract called with `--xcm-dest`,
+    /// which the call is dry-run and weighed against.
+    #[clap(long, requires = "xcm_dest", value_parser = parse_node_url)]
+    dest_url: Option<url::Url>,
+    /// The fee buying the execution of the XCM message on the parachain, as
+    /// `<asset>:<amount>` in the smallest unit of the asset, where the asset is `parent`
+    /// for the token of the relay chain or `here` for the token of the parachain, e.g.
+    /// `parent:1000000000`.
+    #[clap(long, requires = "xcm_dest")]
+    xcm_fee: Option<XcmFee>,
+    #[clap(flatten)]
+    display_limit: DisplayLimitOpts,
+    #[clap(flatten)]
+    debug_message: DebugMessageOpts,
+    #[clap(flatten)]
+    additional_metadata: AdditionalMetadataOpts,
+    #[clap(flatten)]
+    expect: ExpectOpts,
+    #[clap(flatten)]
+    external_signer: ExternalSignerOpts,
+}
+
+/// Parse hex encoded bytes.
+fn parse_hex_bytes(input: &str) -> Result<Bytes> {
+    let bytes = decode_hex(input)?;
+    Ok(bytes.into())
+}
+
+/// Parse the 4 byte selector of a message.
+fn parse_selector(input: &str) -> Result<[u8; 4]> {
+    let bytes = decode_hex(input)?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow!("A selector must be 4 bytes long, got {} bytes", bytes.len())
+    })
 }
 
 impl CallCommand {
@@ -94,145 +216,597 @@ impl CallCommand {
         self.output_json
     }
 
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts(&self) -> &CLIExtrinsicOpts {
+        &self.extrinsic_cli_opts
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts_mut(&mut self) -> &mut CLIExtrinsicOpts {
+        &mut self.extrinsic_cli_opts
+    }
+
+    /// Returns the gas limit given by `--gas` or `--weight`.
+    fn gas_limit(&self) -> Option<u64> {
+        self.weight
+            .map(|weight| weight.ref_time())
+            .or(self.gas_limit)
+    }
+
+    /// Returns the proof size given by `--proof-size` or `--weight`.
+    fn proof_size(&self) -> Option<u64> {
+        self.weight
+            .map(|weight| weight.proof_size())
+            .or(self.proof_size)
+    }
+
     pub async fn handle(&self) -> Result<(), ErrorVariant> {
-        let token_metadata =
-            TokenMetadata::query::<DefaultConfig>(&self.extrinsic_cli_opts.url).await?;
+        if self.extrinsic_cli_opts.progress(self.output_json()) {
+            Progress::Connecting.report();
+        }
+        let connection = match &self.dest_url {
+            Some(dest_url) => self.extrinsic_cli_opts.connect_to(dest_url).await?,
+            None => self.extrinsic_cli_opts.connect().await?,
+        };
+        let chain_config = self.extrinsic_cli_opts.chain_config(&connection);
+        // the call via XCM is sent from the chain of `--url`, whose extrinsics are
+        // built with the configuration of the chain of the contract
+        let origin = match self.xcm_dest {
+            Some(_) => {
+                let origin = self.extrinsic_cli_opts.connect().await?;
+                if self.extrinsic_cli_opts.chain_config(&origin) != chain_config {
+                    return Err(anyhow!(
+                        "The chain of `--url` and the chain of `--dest-url` have \
+                         different configurations, specify one with `--chain-config`"
+                    )
+                    .into())
+                }
+                Some(origin)
+            }
+            None => None,
+        };
+        with_chain_config!(chain_config, |C, E| {
+            self.handle_with_config::<C, E>(
+                connection.with_config()?,
+                origin
+                    .as_ref()
+                    .map(|origin| origin.with_config())
+                    .transpose()?,
+            )
+            .await
+        })
+    }
 
-        let signer = create_signer(&self.extrinsic_cli_opts.suri)?;
+    async fn handle_with_config<C, E>(
+        &self,
+        connection: Connection<C>,
+        origin_connection: Option<Connection<C>>,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let token_metadata = self.extrinsic_cli_opts.token_metadata(&connection).await?;
+
+        let quiet = self.extrinsic_cli_opts.quiet()?;
+        if matches!(self.value, CallValue::Max) && self.extrinsic_cli_opts.skip_dry_run {
+            return Err(anyhow!(
+                "`--value max` cannot be combined with `--skip-dry-run`, the fee is \
+                 estimated with the gas required by a dry-run"
+            )
+            .into())
+        }
+        if matches!(self.value, CallValue::Max) && self.xcm_dest.is_some() {
+            return Err(anyhow!(
+                "`--value max` cannot be combined with `--xcm-dest`, the call is paid \
+                 by the account derived by the parachain"
+            )
+            .into())
+        }
+        let ss58_prefix = self.extrinsic_cli_opts.ss58_prefix(&connection).await?;
+        let address_book = self.extrinsic_cli_opts.address_book()?;
+        self.contract.note_ss58_prefix_mismatch(ss58_prefix);
+
+        if let Some(origin) = &self.origin {
+            origin.note_ss58_prefix_mismatch(ss58_prefix);
+        }
+        let (signer, origin) = self
+            .external_signer
+            .signer_and_origin(&self.extrinsic_cli_opts, self.origin.as_ref())?;
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
+            .origin(origin.clone())
             .file(self.extrinsic_cli_opts.file.clone())
+            .expected_hash(self.extrinsic_cli_opts.expected_hash.map(|hash| hash.0))
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
+            .require_signed_artifacts(self.extrinsic_cli_opts.require_signed_artifacts)
             .url(self.extrinsic_cli_opts.url.clone())
+            .ss58_prefix(ss58_prefix)
             .storage_deposit_limit(
                 self.extrinsic_cli_opts
                     .storage_deposit_limit
-                    .clone()
-                    .map(|bv| bv.denominate_balance(&token_metadata))
+                    .as_ref()
+                    .map(|bv| denominate_balance(bv, &token_metadata))
                     .transpose()?,
             )
-            .verbosity(self.extrinsic_cli_opts.verbosity()?)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .submit_retries(self.extrinsic_cli_opts.submit_retries)
+            .timeout(self.extrinsic_cli_opts.timeout())
+            .timings(self.extrinsic_cli_opts.recorder().clone())
+            .sudo(self.sudo)
+            .max_fee(self.extrinsic_cli_opts.max_fee(&token_metadata)?)
+            .progress(self.extrinsic_cli_opts.progress(self.output_json()))
+            .tx_status(self.extrinsic_cli_opts.tx_status(self.output_json())?)
+            .raw_values(self.extrinsic_cli_opts.raw_values)
+            .args_hex_files(self.args_hex_files)
+            .include_raw(self.extrinsic_cli_opts.include_raw)
+            .additional_metadata(
+                self.additional_metadata
+                    .load(ss58_prefix, self.extrinsic_cli_opts.raw_values)?,
+            )
+            .token_metadata(Some(token_metadata.clone()))
+            .verbosity(
+                self.extrinsic_cli_opts
+                    .extrinsic_verbosity(self.output_json())?,
+            )
             .done();
-        let call_exec =
-            CallCommandBuilder::new(self.contract.clone(), &self.message, extrinsic_opts)
-                .args(self.args.clone())
-                .gas_limit(self.gas_limit)
-                .proof_size(self.proof_size)
-                .value(self.value.denominate_balance(&token_metadata)?)
-                .done()
-                .await?;
+        let value = match &self.value {
+            CallValue::Balance(value) => denominate_balance(value, &token_metadata)?,
+            CallValue::Max => Default::default(),
+        };
+        let mut call_exec: CallExec<C, E, Keypair> = CallCommandBuilder::new(
+            self.contract.address(),
+            self.message.as_deref().unwrap_or_default(),
+            extrinsic_opts,
+        )
+        .selector(self.selector)
+        .fuzzy_match(self.fuzzy_match)
+        .args(self.args.clone())
+        .data(self.data.clone().map(|data| data.0))
+        .gas_limit(self.gas_limit())
+        .proof_size(self.proof_size())
+        .value(value)
+        .connection(Some(connection))
+        .done()
+        .await?;
+        if let Some(message) = &self.message {
+            print_fuzzy_match(
+                "message",
+                message,
+                call_exec.message(),
+                self.output_json(),
+            );
+        }
+        let paid_unpayable = call_exec.transcoder().and_then(|transcoder| {
+            PaidUnpayable::check(
+                transcoder,
+                "message",
+                call_exec.message(),
+                matches!(self.value, CallValue::Max)
+                    || *call_exec.value() != E::Balance::default(),
+            )
+        });
+        if let Some(paid_unpayable) = &paid_unpayable {
+            paid_unpayable
+                .confirm(self.output_json(), self.extrinsic_cli_opts.skip_confirm)?;
+        }
+        if matches!(self.value, CallValue::Max) {
+            call_exec.set_max_value().await?;
+        }
         let metadata = call_exec.client().metadata();
 
         if !self.extrinsic_cli_opts.execute {
+            if call_exec.opts().progress() {
+                Progress::DryRunning {
+                    entity: call_exec.message().to_string(),
+                }
+                .report();
+            }
+            let expected = self
+                .expect
+                .expected_value(call_exec.transcoder(), call_exec.message())?;
             let result = call_exec.call_dry_run().await?;
+            let debug_message_file = self.debug_message.write(&result.debug_message)?;
             match result.result {
                 Ok(ref ret_val) => {
-                    let value = call_exec
-                        .transcoder()
-                        .decode_message_return(
-                            call_exec.message(),
-                            &mut &ret_val.data[..],
-                        )
-                        .context(format!(
-                            "Failed to decode return value {:?}",
-                            &ret_val
-                        ))?;
+                    let decoded = match call_exec.transcoder() {
+                        Some(transcoder) => {
+                            transcoder.decode_message_return(
+                                call_exec.message(),
+                                &mut &ret_val.data[..],
+                            )
+                        }
+                        // without metadata the return type is unknown
+                        None => hex_value(&ret_val.data),
+                    };
+                    let (value, decode_error) = match decoded {
+                        Ok(value) => (value, None),
+                        // a reverted message may return data other than its declared
+                        // return type, which still explains the revert
+                        Err(err) if ret_val.did_revert() => {
+                            (hex_value(&ret_val.data)?, Some(format!("{err:#}")))
+                        }
+                        Err(err) => {
+                            return Err(err
+                                .context(format!(
+                                    "Failed to decode return value {:?}",
+                                    &ret_val
+                                ))
+                                .into())
+                        }
+                    };
+                    let debug_message = DebugMessage::from_bytes(&result.debug_message)
+                        .filter(|_| debug_message_file.is_none());
+                    let assertion =
+                        self.expect.check(expected, &value, ret_val.did_revert());
                     let dry_run_result = CallDryRunResult {
+                        origin: origin.as_ref().map(|origin| ss58_prefix.address(origin)),
+                        selector: self
+                            .selector
+                            .map(|_| selector_hex(call_exec.selector())),
                         reverted: ret_val.did_revert(),
+                        alias: address_book.alias(&value).map(ToString::to_string),
                         data: value,
+                        decode_error,
                         gas_consumed: result.gas_consumed,
                         gas_required: result.gas_required,
-                        storage_deposit: result.storage_deposit.clone(),
+                        storage_deposit: result
+                            .storage_deposit
+                            .display(Some(&token_metadata)),
+                        debug_message,
+                        debug_message_file: debug_message_file.map(Path::to_path_buf),
+                        block_usage: block_usage(call_exec.client(), result.gas_required),
+                        events: result
+                            .events
+                            .as_ref()
+                            .map(|records| {
+                                DisplayEvents::from_event_records::<C, E>(
+                                    records,
+                                    call_exec.transcoder(),
+                                    call_exec.opts().additional_metadata(),
+                                    &metadata,
+                                    ss58_prefix,
+                                    self.extrinsic_cli_opts.raw_values,
+                                    self.extrinsic_cli_opts.include_raw,
+                                    Some(&token_metadata),
+                                )
+                                .map(|events| events.with_aliases(&address_book))
+                            })
+                            .transpose()?,
+                        assertion,
                     };
                     if self.output_json() {
-                        println!("{}", dry_run_result.to_json()?);
+                        println!("{}", self.extrinsic_cli_opts.to_json(&dry_run_result)?);
+                    } else if quiet {
+                        if dry_run_result.reverted {
+                            eprintln!(
+                                "{} The message reverted",
+                                "Warning:".yellow().bold()
+                            );
+                        }
+                        if let Some(decode_error) = &dry_run_result.decode_error {
+                            eprintln!(
+                                "{} {}",
+                                "Warning:".yellow().bold(),
+                                undecoded_note(decode_error)
+                            );
+                        }
+                        warn_block_usage(dry_run_result.block_usage.as_ref());
+                        println!("{}", dry_run_result.data);
                     } else {
-                        dry_run_result.print();
-                        display_contract_exec_result_debug::<_, DEFAULT_KEY_COL_WIDTH>(
-                            &result,
+                        dry_run_result.print(&self.display_limit);
+                        display_contract_exec_result_debug::<
+                            _,
+                            _,
+                            _,
+                            DEFAULT_KEY_COL_WIDTH,
+                        >(&result, debug_message_file)?;
+                        display_dry_run_events::<E>(
+                            dry_run_result.events.as_ref(),
+                            self.extrinsic_cli_opts.verbosity()?,
+                            self.extrinsic_cli_opts.event_display(),
+                            &token_metadata,
                         )?;
                         display_dry_run_result_warning("message");
                     };
+                    if let Some(assertion) = dry_run_result.assertion {
+                        assertion.into_result()?;
+                    }
                 }
                 Err(ref err) => {
-                    let object = ErrorVariant::from_dispatch_error(err, &metadata)?;
-                    if self.output_json() {
+                    let mut object = ErrorVariant::from_dispatch_error(err, &metadata)?;
+                    if let Some(paid_unpayable) = &paid_unpayable {
+                        object = paid_unpayable.explain(object);
+                    }
+                    if self.output_json() || quiet {
                         return Err(object)
                     } else {
+                        if let Some(origin) = &origin {
+                            name_value_println!(
+                                "Origin",
+                                ss58_prefix.format_account(origin),
+                                MAX_KEY_COL_WIDTH
+                            );
+                        }
                         name_value_println!("Result", object, MAX_KEY_COL_WIDTH);
-                        display_contract_exec_result::<_, MAX_KEY_COL_WIDTH>(&result)?;
+                        display_contract_exec_result::<_, _, _, MAX_KEY_COL_WIDTH>(
+                            &result,
+                            debug_message_file,
+                            Some(&token_metadata),
+                        )?;
                     }
                 }
             }
         } else {
-            let gas_limit = pre_submit_dry_run_gas_estimate_call(
-                &call_exec,
+            let (gas_limit, storage_deposit) = pre_submit_dry_run_gas_estimate_call(
+                &mut call_exec,
                 self.output_json(),
+                quiet,
                 self.extrinsic_cli_opts.skip_dry_run,
+                self.extrinsic_cli_opts.storage_deposit_buffer,
+                &self.debug_message,
             )
-            .await?;
+            .await
+            .map_err(|err| {
+                match &paid_unpayable {
+                    Some(paid_unpayable) => paid_unpayable.explain(err),
+                    None => err,
+                }
+            })?;
+            let storage_deposit_limit = self
+                .extrinsic_cli_opts
+                .storage_deposit_buffer
+                .and(call_exec.opts().storage_deposit_limit());
             if !self.extrinsic_cli_opts.skip_confirm {
+                let costs = TxCosts::new(
+                    *call_exec.value(),
+                    call_exec.estimate_fee(gas_limit).await?,
+                    storage_deposit.as_ref(),
+                    &token_metadata,
+                );
+                let deposit_limit = storage_deposit_limit
+                    .map(|limit| DisplayBalance::new(limit, Some(&token_metadata)));
                 prompt_confirm_tx(|| {
                     name_value_println!(
                         "Message",
                         call_exec.message(),
                         DEFAULT_KEY_COL_WIDTH
                     );
+                    if self.selector.is_some() {
+                        name_value_println!(
+                            "Selector",
+                            selector_hex(call_exec.selector()),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    match &self.data {
+                        Some(data) => {
+                            name_value_println!(
+                                "Data",
+                                format!("0x{}", hex::encode(&data.0)),
+                                DEFAULT_KEY_COL_WIDTH
+                            );
+                        }
+                        None => {
+                            name_value_println!(
+                                "Args",
+                                call_exec.args().join(" "),
+                                DEFAULT_KEY_COL_WIDTH
+                            );
+                        }
+                    }
                     name_value_println!(
-                        "Args",
-                        call_exec.args().join(" "),
+                        "Gas limit",
+                        gas_limit.to_string(),
                         DEFAULT_KEY_COL_WIDTH
                     );
+                    if let Some(weight) = &self.weight {
+                        name_value_println!(
+                            "Weight",
+                            weight.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    costs.print();
+                    if let Some(limit) = &deposit_limit {
+                        name_value_println!(
+                            "Deposit limit",
+                            limit.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
                     name_value_println!(
-                        "Gas limit",
-                        gas_limit.to_string(),
+                        "Mortality",
+                        self.extrinsic_cli_opts.mortality.describe(),
                         DEFAULT_KEY_COL_WIDTH
                     );
+                    if let (Some(dest), Some(fee)) = (self.xcm_dest, &self.xcm_fee) {
+                        name_value_println!(
+                            "XCM dest",
+                            format!("parachain {dest}"),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                        name_value_println!(
+                            "XCM fee",
+                            fee.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
                 })?;
             }
-            let events = call_exec.call(Some(gas_limit)).await?;
-            let display_events = DisplayEvents::from_events::<
-                DefaultConfig,
-                DefaultEnvironment,
-            >(&events, None, &metadata)?;
+            if let (Some(dest), Some(fee), Some(origin_connection)) =
+                (self.xcm_dest, self.xcm_fee, &origin_connection)
+            {
+                let sent = call_exec
+                    .call_via_xcm(origin_connection, dest, fee, Some(gas_limit))
+                    .await?;
+                if quiet {
+                    return Ok(())
+                }
+                let result = XcmCallResult {
+                    dest,
+                    pallet: sent.pallet.name(),
+                    message_id: sent
+                        .message_id
+                        .map(|id| format!("0x{}", hex::encode(id))),
+                    extrinsic_hash: format!("{:?}", sent.events.extrinsic_hash()),
+                    block_hash: format!("{:?}", sent.events.block_hash()),
+                    fee,
+                };
+                if self.output_json() {
+                    println!("{}", self.extrinsic_cli_opts.to_json(&result)?);
+                } else {
+                    result.print();
+                }
+                return Ok(())
+            }
+            let events = match self.external_signer.account_id() {
+                Some(account_id) => {
+                    let unsigned = call_exec
+                        .build_partial_extrinsic(Some(gas_limit), account_id)
+                        .await?;
+                    let signed = ExternalSignerOpts::sign(&unsigned, self.output_json())?;
+                    call_exec.submit_signed(signed).await?
+                }
+                None => call_exec.call(Some(gas_limit)).await?,
+            };
+            if quiet {
+                return Ok(())
+            }
+            let display_events = DisplayEvents::from_events::<C, E>(
+                &events,
+                call_exec.transcoder(),
+                call_exec.opts().additional_metadata(),
+                &metadata,
+                ss58_prefix,
+                self.extrinsic_cli_opts.raw_values,
+                self.extrinsic_cli_opts.include_raw,
+                Some(&token_metadata),
+            )?
+            .with_aliases(&address_book);
+            let outcome = ExecutionOutcome::from_events::<C, E>(
+                &events,
+                &call_exec.opts().origin(),
+                &token_metadata,
+            )?;
+            let explorer_url = self
+                .extrinsic_cli_opts
+                .explorer_url(
+                    call_exec.client(),
+                    &events,
+                    Some(ss58_prefix.format_account(&self.contract.address())),
+                )
+                .await?;
 
             let output = if self.output_json() {
-                display_events.to_json()?
+                if storage_deposit_limit.is_none()
+                    && self.selector.is_none()
+                    && self.extrinsic_cli_opts.timings().is_none()
+                    && outcome.is_empty()
+                    && explorer_url.is_none()
+                {
+                    self.extrinsic_cli_opts
+                        .to_json_without_timings(&display_events)?
+                } else {
+                    let mut json_object = serde_json::json!({
+                        "events": display_events,
+                    });
+                    if let Some(limit) = storage_deposit_limit {
+                        json_object["storage_deposit_limit"] = serde_json::to_value(
+                            DisplayBalance::new(limit, Some(&token_metadata)),
+                        )?;
+                    }
+                    if self.selector.is_some() {
+                        json_object["selector"] =
+                            selector_hex(call_exec.selector()).into();
+                    }
+                    if let Some(weight_used) = outcome.weight_used {
+                        json_object["weight_used"] = serde_json::to_value(weight_used)?;
+                    }
+                    if let Some(refunded) = &outcome.deposit_refunded {
+                        json_object["deposit_refunded"] = serde_json::to_value(refunded)?;
+                    }
+                    if let Some(explorer_url) = &explorer_url {
+                        json_object["explorer_url"] = explorer_url.as_str().into();
+                    }
+                    self.extrinsic_cli_opts.to_json(&json_object)?
+                }
             } else {
-                display_events.display_events::<DefaultEnvironment>(
+                display_events.display_events::<E>(
                     self.extrinsic_cli_opts.verbosity().unwrap(),
+                    self.extrinsic_cli_opts.event_display(),
                     &token_metadata,
                 )?
             };
             println!("{output}");
+            if !self.output_json() {
+                outcome.print(gas_limit);
+                if let Some(explorer_url) = explorer_url {
+                    name_value_println!("Explorer", explorer_url, DEFAULT_KEY_COL_WIDTH);
+                }
+            }
         }
         Ok(())
     }
 }
 
-/// A helper function to estimate the gas required for a contract call.
-async fn pre_submit_dry_run_gas_estimate_call(
-    call_exec: &CallExec<DefaultConfig, DefaultEnvironment, Keypair>,
+/// A helper function to estimate the gas required for a contract call, returned along
+/// with the storage deposit of the dry-run unless it is skipped.
+///
+/// If a `storage_deposit_buffer` is given, the storage deposit charged in the dry-run,
+/// increased by the buffer percentage, is set as the storage deposit limit of the call.
+async fn pre_submit_dry_run_gas_estimate_call<C, E>(
+    call_exec: &mut CallExec<C, E, Keypair>,
     output_json: bool,
+    quiet: bool,
     skip_dry_run: bool,
-) -> Result<Weight> {
+    storage_deposit_buffer: Option<u64>,
+    debug_message: &DebugMessageOpts,
+) -> Result<(Weight, Option<StorageDeposit<E::Balance>>), ErrorVariant>
+where
+    C: ExtrinsicConfig,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams: Default + MortalityParams<C>,
+    E: Environment,
+    E::Balance: ExtrinsicBalance,
+{
     if skip_dry_run {
         return match (call_exec.gas_limit(), call_exec.proof_size()) {
-            (Some(ref_time), Some(proof_size)) => Ok(Weight::from_parts(ref_time, proof_size)),
+            (Some(ref_time), Some(proof_size)) => Ok((Weight::from_parts(ref_time, proof_size), None)),
             _ => {
                 Err(anyhow!(
-                "Weight args `--gas` and `--proof-size` required if `--skip-dry-run` specified"
-            ))
+                    "Weight args `--gas` and `--proof-size`, or `--weight`, required if `--skip-dry-run` specified"
+                )
+                .into())
             }
         };
     }
-    if !output_json {
+    if call_exec.opts().progress() {
+        Progress::DryRunning {
+            entity: call_exec.message().to_string(),
+        }
+        .report();
+    } else if !output_json && !quiet {
         print_dry_running_status(call_exec.message());
     }
     let call_result = call_exec.call_dry_run().await?;
     match call_result.result {
         Ok(_) => {
-            if !output_json {
-                print_gas_required_success(call_result.gas_required);
+            if call_exec.opts().progress() {
+                Progress::GasEstimated {
+                    gas_required: call_result.gas_required,
+                }
+                .report();
+            } else if !output_json && !quiet {
+                print_gas_required_success(
+                    call_result.gas_required,
+                    block_usage(call_exec.client(), call_result.gas_required),
+                );
+            }
+            if let Some(buffer) = storage_deposit_buffer {
+                let limit = call_result.storage_deposit.limit_with_buffer(buffer)?;
+                call_exec.set_storage_deposit_limit(Some(limit));
             }
             // use user specified values where provided, otherwise use the estimates
             let ref_time = call_exec
@@ -241,47 +815,344 @@ async fn pre_submit_dry_run_gas_estimate_call(
             let proof_size = call_exec
                 .proof_size()
                 .unwrap_or_else(|| call_result.gas_required.proof_size());
-            Ok(Weight::from_parts(ref_time, proof_size))
+            Ok((
+                Weight::from_parts(ref_time, proof_size),
+                Some(call_result.storage_deposit.clone()),
+            ))
         }
         Err(ref err) => {
             let object =
                 ErrorVariant::from_dispatch_error(err, &call_exec.client().metadata())?;
-            if output_json {
-                Err(anyhow!("{}", serde_json::to_string_pretty(&object)?))
-            } else {
-                name_value_println!("Result", object, MAX_KEY_COL_WIDTH);
-                display_contract_exec_result::<_, MAX_KEY_COL_WIDTH>(&call_result)?;
+            Err(pre_submit_dry_run_error(
+                object,
+                &call_result,
+                output_json,
+                quiet,
+                debug_message,
+                call_exec.opts().token_metadata(),
+            ))
+        }
+    }
+}
 
-                Err(anyhow!("Pre-submission dry-run failed. Use --skip-dry-run to skip this step."))
-            }
+/// The result of sending a call to a contract on a parachain via XCM.
+#[derive(serde::Serialize)]
+pub struct XcmCallResult {
+    /// The id of the parachain of the contract.
+    pub dest: u32,
+    /// The XCM pallet of the sending chain.
+    pub pallet: &'static str,
+    /// The id of the message, if reported by the XCM pallet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    pub extrinsic_hash: String,
+    pub block_hash: String,
+    pub fee: XcmFee,
+}
+
+impl XcmCallResult {
+    /// Prints the message, noting that the call is executed later on the parachain.
+    pub fn print(&self) {
+        name_value_println!(
+            "XCM dest",
+            format!("parachain {}", self.dest),
+            DEFAULT_KEY_COL_WIDTH
+        );
+        name_value_println!("XCM pallet", self.pallet, DEFAULT_KEY_COL_WIDTH);
+        if let Some(message_id) = &self.message_id {
+            name_value_println!("Message id", message_id, DEFAULT_KEY_COL_WIDTH);
+        }
+        name_value_println!("Extrinsic", self.extrinsic_hash, DEFAULT_KEY_COL_WIDTH);
+        name_value_println!("Block", self.block_hash, DEFAULT_KEY_COL_WIDTH);
+        eprintln!(
+            "{} The message was sent, the call is executed on the parachain \
+             asynchronously once the message is delivered. Check the events of the \
+             parachain for its outcome.",
+            "Note:".yellow().bold()
+        );
+    }
+}
+
+/// The value transferred as part of a call.
+#[derive(Debug, Clone)]
+pub enum CallValue {
+    /// The entire free balance of the signer minus the fee and the existential deposit.
+    Max,
+    /// The given balance.
+    Balance(BalanceVariant<<DefaultEnvironment as Environment>::Balance>),
+}
+
+impl FromStr for CallValue {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        match input {
+            "max" => Ok(Self::Max),
+            _ => BalanceVariant::from_str(input).map(Self::Balance),
         }
     }
 }
 
+/// Returns the raw return data of a message as a hex value.
+fn hex_value(data: &[u8]) -> Result<Value> {
+    Ok(Value::Hex(
+        Hex::from_str(&hex::encode(data)).map_err(|err| anyhow!("{err}"))?,
+    ))
+}
+
+/// Formats the selector of a message as hex.
+fn selector_hex(selector: &[u8]) -> String {
+    format!("0x{}", hex::encode(selector))
+}
+
 /// Result of the contract call
 #[derive(serde::Serialize)]
-pub struct CallDryRunResult {
+pub struct CallDryRunResult<Balance> {
+    /// The account the dry-run was performed with, if it is not the signer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Address>,
+    /// The selector of the called message, if it was called by `--selector`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
     /// Was the operation reverted
     pub reverted: bool,
     pub data: Value,
+    /// The alias of the returned account id in the address book
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Why the data returned by a reverted message could not be decoded, in which case
+    /// `data` is the raw data as hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
     pub gas_consumed: Weight,
     pub gas_required: Weight,
     /// Storage deposit after the operation
-    pub storage_deposit: StorageDeposit<<DefaultEnvironment as Environment>::Balance>,
+    pub storage_deposit: StorageDeposit<Balance>,
+    /// The debug message of the dry-run, absent if empty
+    #[serde(flatten)]
+    pub debug_message: Option<DebugMessage>,
+    /// The file the debug buffer was written to with `--debug-message-file`, instead
+    /// of the debug message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_message_file: Option<PathBuf>,
+    /// The share of the block weight limits taken up by the required gas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_usage: Option<BlockUsage>,
+    /// The events emitted during the dry-run, absent if the runtime doesn't return them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<DisplayEvents>,
+    /// The outcome of `--expect` and `--expect-reverted`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assertion: Option<Assertion>,
 }
 
-impl CallDryRunResult {
-    /// Returns a result in json format
-    pub fn to_json(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
-    }
-
-    pub fn print(&self) {
-        name_value_println!("Result", format!("{}", self.data), DEFAULT_KEY_COL_WIDTH);
+impl CallDryRunResult<DisplayBalance> {
+    pub fn print(&self, display_limit: &DisplayLimitOpts) {
+        if let Some(origin) = &self.origin {
+            name_value_println!("Origin", origin, DEFAULT_KEY_COL_WIDTH);
+        }
+        let mut data = display_limit.display_value(&self.data);
+        if let Some(alias) = &self.alias {
+            data = format!("{data} ({alias})");
+        }
+        name_value_println!("Result", data, DEFAULT_KEY_COL_WIDTH);
+        if let Some(decode_error) = &self.decode_error {
+            name_value_println!(
+                "Note",
+                undecoded_note(decode_error),
+                DEFAULT_KEY_COL_WIDTH
+            );
+        }
         name_value_println!(
             "Reverted",
             format!("{:?}", self.reverted),
             DEFAULT_KEY_COL_WIDTH
         );
+        name_value_println!(
+            "Storage deposit",
+            self.storage_deposit.to_string(),
+            DEFAULT_KEY_COL_WIDTH
+        );
+        if let Some(assertion) = &self.assertion {
+            assertion.print();
+        }
+        if let Some(block_usage) = &self.block_usage {
+            print_block_usage(block_usage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_call_value() {
+        assert!(matches!(
+            CallValue::from_str("max").unwrap(),
+            CallValue::Max
+        ));
+        assert!(matches!(
+            CallValue::from_str("100").unwrap(),
+            CallValue::Balance(BalanceVariant::Default(100))
+        ));
+        assert!(CallValue::from_str("maximum").is_err());
+    }
+
+    #[test]
+    fn parse_message_selector() {
+        assert_eq!(
+            parse_selector("0x633aa551").unwrap(),
+            [0x63, 0x3a, 0xa5, 0x51]
+        );
+        assert_eq!(
+            parse_selector("633aa551").unwrap(),
+            [0x63, 0x3a, 0xa5, 0x51]
+        );
+        assert!(parse_selector("0x633aa5").is_err());
+    }
+
+    #[test]
+    fn origin_conflicts_with_execute() {
+        #[derive(Debug, clap::Parser)]
+        struct CallCli {
+            #[clap(flatten)]
+            call: CallCommand,
+        }
+        let args = |extra: &[&'static str]| {
+            [
+                "cargo-contract",
+                "--contract",
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                "--message",
+                "get",
+                "--origin",
+                "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+            ]
+            .into_iter()
+            .chain(extra.iter().copied())
+            .collect::<Vec<_>>()
+        };
+
+        let cli = <CallCli as clap::Parser>::try_parse_from(args(&[])).unwrap();
+        assert!(cli.call.origin.is_some());
+
+        let err =
+            <CallCli as clap::Parser>::try_parse_from(args(&["--execute"])).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn raw_data_requires_selector_instead_of_message() {
+        #[derive(Debug, clap::Parser)]
+        struct CallCli {
+            #[clap(flatten)]
+            call: CallCommand,
+        }
+        let args = |extra: &[&'static str]| {
+            [
+                "cargo-contract",
+                "--contract",
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                "--data",
+                "0x2a000000",
+            ]
+            .into_iter()
+            .chain(extra.iter().copied())
+            .collect::<Vec<_>>()
+        };
+
+        let cli = <CallCli as clap::Parser>::try_parse_from(args(&[
+            "--selector",
+            "0xdeadbeef",
+        ]))
+        .unwrap();
+        assert_eq!(cli.call.selector, Some([0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(cli.call.data.unwrap().0, vec![0x2a, 0, 0, 0]);
+
+        let err = <CallCli as clap::Parser>::try_parse_from(args(&[])).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        let err = <CallCli as clap::Parser>::try_parse_from(args(&[
+            "--selector",
+            "0xdeadbeef",
+            "--message",
+            "get",
+        ]))
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+        let err = <CallCli as clap::Parser>::try_parse_from(args(&[
+            "--selector",
+            "0xdeadbeef",
+            "--args",
+            "42",
+        ]))
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn weight_sets_gas_limit_and_proof_size() {
+        #[derive(Debug, clap::Parser)]
+        struct CallCli {
+            #[clap(flatten)]
+            call: CallCommand,
+        }
+        let args = |extra: &[&'static str]| {
+            [
+                "cargo-contract",
+                "--contract",
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                "--message",
+                "get",
+                "--weight",
+                "2ms,256KiB",
+            ]
+            .into_iter()
+            .chain(extra.iter().copied())
+            .collect::<Vec<_>>()
+        };
+
+        let cli = <CallCli as clap::Parser>::try_parse_from(args(&[])).unwrap();
+        assert_eq!(cli.call.gas_limit(), Some(2_000_000_000));
+        assert_eq!(cli.call.proof_size(), Some(262_144));
+
+        for flag in [["--gas", "1"], ["--proof-size", "1"]] {
+            let err = <CallCli as clap::Parser>::try_parse_from(args(&flag)).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+    }
+
+    #[test]
+    fn sign_externally_requires_execute() {
+        #[derive(Debug, clap::Parser)]
+        struct CallCli {
+            #[clap(flatten)]
+            call: CallCommand,
+        }
+        const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let args = |extra: &[&'static str]| {
+            ["cargo-contract", "--contract", ALICE, "--message", "flip"]
+                .into_iter()
+                .chain(["--sign-externally", ALICE])
+                .chain(extra.iter().copied())
+                .collect::<Vec<_>>()
+        };
+
+        let cli =
+            <CallCli as clap::Parser>::try_parse_from(args(&["--execute"])).unwrap();
+        assert!(cli.call.external_signer.account_id().is_some());
+
+        let err = <CallCli as clap::Parser>::try_parse_from(args(&[])).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        let err = <CallCli as clap::Parser>::try_parse_from(args(&[
+            "--execute",
+            "--origin",
+            ALICE,
+        ]))
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
     }
 }
@@ -0,0 +1,212 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use clap_complete::Shell;
+use contract_extrinsics::ContractArtifacts;
+use std::{
+    io::Write,
+    path::PathBuf,
+};
+
+/// The name of the binary the completion scripts complete, `cargo contract` is invoked
+/// as a subcommand of `cargo`.
+const BIN_NAME: &str = "cargo";
+
+/// Generate a shell completion script for `cargo contract`, written to stdout.
+#[derive(Debug, clap::Args)]
+pub struct CompletionsCommand {
+    /// The shell to generate the completion script for.
+    #[clap(value_enum, required_unless_present = "list")]
+    shell: Option<Shell>,
+    /// List the names of the messages or constructors of the contract, one per line.
+    /// Invoked by the completion scripts to complete `--message` and `--constructor`.
+    #[clap(long, value_enum, hide = true)]
+    list: Option<Names>,
+    /// Path to the `Cargo.toml` of the contract to list the names of.
+    #[clap(long, hide = true, requires = "list")]
+    manifest_path: Option<PathBuf>,
+    /// Path to a contract build artifact file to list the names of.
+    #[clap(long, hide = true, requires = "list", conflicts_with = "manifest_path")]
+    file: Option<PathBuf>,
+}
+
+/// The names of a contract which are completed dynamically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Names {
+    Messages,
+    Constructors,
+}
+
+impl CompletionsCommand {
+    /// Writes the completion script for the `cmd` to stdout, or the names of the
+    /// contract if invoked by a completion script.
+    pub fn run(&self, cmd: clap::Command) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        match (self.list, self.shell) {
+            (Some(names), _) => {
+                // Completion must not fail loudly, e.g. if the contract is not built.
+                let names =
+                    list_names(names, self.manifest_path.as_ref(), self.file.as_ref())
+                        .unwrap_or_default();
+                for name in names {
+                    writeln!(stdout, "{name}")?;
+                }
+            }
+            (None, Some(shell)) => generate(shell, cmd, &mut stdout)?,
+            (None, None) => unreachable!("the shell is required by clap"),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the labels of the messages or constructors of the contract at the
+/// `manifest_path` or artifact `file`.
+pub fn list_names(
+    names: Names,
+    manifest_path: Option<&PathBuf>,
+    file: Option<&PathBuf>,
+) -> Result<Vec<String>> {
+    let artifacts = ContractArtifacts::from_manifest_or_file(manifest_path, file)?;
+    let metadata = artifacts.ink_project_metadata()?;
+    let spec = metadata.spec();
+    let labels = match names {
+        Names::Messages => {
+            spec.messages()
+                .iter()
+                .map(|message| message.label().to_string())
+                .collect()
+        }
+        Names::Constructors => {
+            spec.constructors()
+                .iter()
+                .map(|constructor| constructor.label().to_string())
+                .collect()
+        }
+    };
+    Ok(labels)
+}
+
+/// Writes the completion script for the `shell` to `out`.
+///
+/// The static completions generated by `clap_complete` are extended with the dynamic
+/// completion of `--message` and `--constructor` for the shells which support it.
+pub fn generate(
+    shell: Shell,
+    mut cmd: clap::Command,
+    out: &mut impl Write,
+) -> Result<()> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut cmd, BIN_NAME, &mut script);
+    let script = String::from_utf8(script)?;
+    match shell {
+        Shell::Bash => {
+            // Rename the generated function, so that the completion of other `cargo`
+            // subcommands can still be delegated to the completion of `cargo` itself.
+            // The registration of the generated function is replaced by the one of
+            // `_cargo_contract`.
+            let script = script.replace("_cargo()", "_cargo_contract_static()");
+            let script = match script.rfind("\nif [[ \"${BASH_VERSINFO[0]}\"") {
+                Some(registration) => &script[..registration],
+                None => &script,
+            };
+            write!(out, "{script}\n{BASH_DYNAMIC}")?;
+        }
+        Shell::Zsh => {
+            let script = script
+                .replace(":MESSAGE: '", ":MESSAGE:_cargo_contract_names messages'")
+                .replace(
+                    ":CONSTRUCTOR: '",
+                    ":CONSTRUCTOR:_cargo_contract_names constructors'",
+                );
+            // the functions must be defined before the `compdef` call at the end
+            let script =
+                script.replacen("_cargo() {", &format!("{ZSH_DYNAMIC}_cargo() {{"), 1);
+            write!(out, "{script}")?;
+        }
+        Shell::Fish => write!(out, "{script}{FISH_DYNAMIC}")?,
+        _ => write!(out, "{script}")?,
+    }
+    Ok(())
+}
+
+const BASH_DYNAMIC: &str = r#"
+_cargo_contract_names() {
+    local i args=()
+    for ((i = 2; i < COMP_CWORD; i++)); do
+        if [[ "${COMP_WORDS[i]}" == "--manifest-path" ]]; then
+            args+=(--manifest-path "${COMP_WORDS[i+1]}")
+        fi
+    done
+    cargo contract completions --list "$1" "${args[@]}" 2>/dev/null
+}
+
+_cargo_contract() {
+    if [[ "${COMP_WORDS[1]}" != "contract" ]]; then
+        if declare -F _cargo >/dev/null; then
+            _cargo "$@"
+        fi
+        return
+    fi
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "${prev}" in
+        --message|-m)
+            COMPREPLY=($(compgen -W "$(_cargo_contract_names messages)" -- "${cur}"))
+            ;;
+        --constructor)
+            COMPREPLY=($(compgen -W "$(_cargo_contract_names constructors)" -- "${cur}"))
+            ;;
+        *)
+            _cargo_contract_static "$@"
+            ;;
+    esac
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _cargo_contract -o nosort -o bashdefault -o default cargo
+else
+    complete -F _cargo_contract -o bashdefault -o default cargo
+fi
+"#;
+
+const ZSH_DYNAMIC: &str = r#"_cargo_contract_names() {
+    local -a names args
+    local i
+    for ((i = 2; i < CURRENT; i++)); do
+        if [[ "${words[i]}" == "--manifest-path" ]]; then
+            args+=(--manifest-path "${words[i+1]}")
+        fi
+    done
+    names=(${(f)"$(cargo contract completions --list $1 $args 2>/dev/null)"})
+    _describe "$1" names
+}
+
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+function __cargo_contract_names
+    set -l tokens (commandline -opc)
+    set -l args
+    set -l index (contains -i -- --manifest-path $tokens)
+    if test -n "$index"
+        set args --manifest-path $tokens[(math $index + 1)]
+    end
+    cargo contract completions --list $argv[1] $args 2>/dev/null
+end
+
+complete -c cargo -n "__fish_seen_subcommand_from call encode" -l message -s m -x -a "(__cargo_contract_names messages)"
+complete -c cargo -n "__fish_seen_subcommand_from instantiate" -l constructor -x -a "(__cargo_contract_names constructors)"
+"#;
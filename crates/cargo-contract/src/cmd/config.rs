@@ -0,0 +1,368 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Chain configurations the extrinsic commands can be instantiated with.
+//!
+//! To support another chain configuration, add a variant to [`ChainConfig`], describe how
+//! it is detected in [`ChainConfig::from_signed_extensions`] and map it to its types in
+//! [`with_chain_config`].
+
+use anyhow::Result;
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+};
+use scale::Encode;
+use scale_info::{
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use serde::Serialize;
+use std::fmt::{
+    Debug,
+    Display,
+};
+use subxt::{
+    client::OfflineClientT,
+    config::{
+        signed_extensions::{
+            self,
+            AnyOf,
+            SignedExtension,
+        },
+        ExtrinsicParams,
+        ExtrinsicParamsEncoder,
+        ExtrinsicParamsError,
+    },
+    ext::scale_decode::IntoVisitor,
+    utils::{
+        AccountId32,
+        MultiAddress,
+        MultiSignature,
+        H256,
+    },
+    Config,
+    Metadata,
+    PolkadotConfig,
+    SubstrateConfig,
+};
+
+/// The chain configurations the extrinsic commands can be instantiated with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChainConfig {
+    /// Polkadot and Substrate based chains with `u128` balances and the default set of
+    /// signed extensions, e.g. `substrate-contracts-node`.
+    Polkadot,
+    /// Chains with `u64` balances and the default set of signed extensions.
+    U64Balance,
+    /// Chains with the `CheckMetadataHash` signed extension.
+    MetadataHash,
+}
+
+impl ChainConfig {
+    /// Detect the chain configuration from the `metadata` of the chain.
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let signed_extensions = metadata
+            .extrinsic()
+            .signed_extensions()
+            .iter()
+            .map(|ext| (ext.identifier(), ext.extra_ty()));
+        Self::from_signed_extensions(signed_extensions, metadata.types())
+    }
+
+    /// Select the chain configuration from the identifiers and `extra` types of the
+    /// signed extensions of the chain.
+    fn from_signed_extensions<'a>(
+        signed_extensions: impl IntoIterator<Item = (&'a str, u32)>,
+        types: &PortableRegistry,
+    ) -> Self {
+        let mut balance = None;
+        for (identifier, extra_ty) in signed_extensions {
+            if <CheckMetadataHash as SignedExtension<MetadataHashConfig>>::matches(
+                identifier, extra_ty, types,
+            ) {
+                return Self::MetadataHash
+            }
+            if identifier == "ChargeTransactionPayment" {
+                balance = compact_balance_primitive(extra_ty, types);
+            }
+        }
+        match balance {
+            Some(TypeDefPrimitive::U64) => Self::U64Balance,
+            _ => Self::Polkadot,
+        }
+    }
+}
+
+/// Resolve the primitive balance type of a signed extension which consists of a single
+/// compact encoded balance, such as `ChargeTransactionPayment`.
+fn compact_balance_primitive(
+    ty: u32,
+    types: &PortableRegistry,
+) -> Option<TypeDefPrimitive> {
+    let field_ty = match &types.resolve(ty)?.type_def {
+        TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+            composite.fields[0].ty.id
+        }
+        _ => return None,
+    };
+    let balance_ty = match &types.resolve(field_ty)?.type_def {
+        TypeDef::Compact(compact) => compact.type_param.id,
+        _ => return None,
+    };
+    match &types.resolve(balance_ty)?.type_def {
+        TypeDef::Primitive(primitive) => Some(primitive.clone()),
+        _ => None,
+    }
+}
+
+/// Run `$body` with the type aliases `$config` and `$env` bound to the `subxt`
+/// configuration and the `ink!` environment of the given [`ChainConfig`].
+macro_rules! with_chain_config {
+    ($chain_config:expr, |$config:ident, $env:ident| $body:expr) => {
+        match $chain_config {
+            $crate::cmd::config::ChainConfig::Polkadot => {
+                type $config = ::subxt::PolkadotConfig;
+                type $env = ::ink_env::DefaultEnvironment;
+                $body
+            }
+            $crate::cmd::config::ChainConfig::U64Balance => {
+                type $config = ::subxt::PolkadotConfig;
+                type $env = $crate::cmd::config::U64BalanceEnvironment;
+                $body
+            }
+            $crate::cmd::config::ChainConfig::MetadataHash => {
+                type $config = $crate::cmd::config::MetadataHashConfig;
+                type $env = ::ink_env::DefaultEnvironment;
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_chain_config;
+
+/// A `subxt` configuration the extrinsic commands can be instantiated with.
+///
+/// The types of accounts and signatures must be compatible with the `sr25519` signer
+/// created from the `--suri` argument.
+pub trait ExtrinsicConfig:
+    Config<
+    Hash = H256,
+    AccountId = AccountId32,
+    Address = MultiAddress<AccountId32, ()>,
+    Signature = MultiSignature,
+>
+{
+}
+
+impl<T> ExtrinsicConfig for T where
+    T: Config<
+        Hash = H256,
+        AccountId = AccountId32,
+        Address = MultiAddress<AccountId32, ()>,
+        Signature = MultiSignature,
+    >
+{
+}
+
+/// The balance type of an `ink!` environment the extrinsic commands can be instantiated
+/// with.
+pub trait ExtrinsicBalance:
+    TryFrom<u128> + Into<u128> + Default + Debug + Display + Serialize + IntoVisitor
+{
+}
+
+impl<T> ExtrinsicBalance for T where
+    T: TryFrom<u128> + Into<u128> + Default + Debug + Display + Serialize + IntoVisitor
+{
+}
+
+/// The `ink!` environment of chains with `u64` balances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum U64BalanceEnvironment {}
+
+impl Environment for U64BalanceEnvironment {
+    const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <DefaultEnvironment as Environment>::AccountId;
+    type Balance = u64;
+    type Hash = <DefaultEnvironment as Environment>::Hash;
+    type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+    type ChainExtension = <DefaultEnvironment as Environment>::ChainExtension;
+}
+
+/// The `subxt` configuration of chains with the `CheckMetadataHash` signed extension.
+pub enum MetadataHashConfig {}
+
+impl Config for MetadataHashConfig {
+    type Hash = <SubstrateConfig as Config>::Hash;
+    type AccountId = <SubstrateConfig as Config>::AccountId;
+    type Address = <PolkadotConfig as Config>::Address;
+    type Signature = <SubstrateConfig as Config>::Signature;
+    type Hasher = <SubstrateConfig as Config>::Hasher;
+    type Header = <SubstrateConfig as Config>::Header;
+    type ExtrinsicParams = MetadataHashExtrinsicParams<Self>;
+    type AssetId = <PolkadotConfig as Config>::AssetId;
+}
+
+/// The default set of signed extensions, extended by [`CheckMetadataHash`].
+pub type MetadataHashExtrinsicParams<T> = AnyOf<
+    T,
+    (
+        signed_extensions::CheckSpecVersion,
+        signed_extensions::CheckTxVersion,
+        signed_extensions::CheckNonce,
+        signed_extensions::CheckGenesis<T>,
+        signed_extensions::CheckMortality<T>,
+        signed_extensions::ChargeAssetTxPayment<T>,
+        signed_extensions::ChargeTransactionPayment,
+        CheckMetadataHash,
+    ),
+>;
+
+/// The `CheckMetadataHash` signed extension, submitted with the metadata hash check
+/// disabled.
+pub struct CheckMetadataHash;
+
+impl CheckMetadataHash {
+    const IDENTIFIER: &'static str = "CheckMetadataHash";
+    /// The `Disabled` variant of the `Mode` of the signed extension.
+    const MODE_DISABLED: u8 = 0;
+}
+
+impl<T: Config> ExtrinsicParams<T> for CheckMetadataHash {
+    type OtherParams = ();
+
+    fn new<Client: OfflineClientT<T>>(
+        _nonce: u64,
+        _client: Client,
+        _other_params: Self::OtherParams,
+    ) -> Result<Self, ExtrinsicParamsError> {
+        Ok(CheckMetadataHash)
+    }
+}
+
+impl ExtrinsicParamsEncoder for CheckMetadataHash {
+    fn encode_extra_to(&self, v: &mut Vec<u8>) {
+        Self::MODE_DISABLED.encode_to(v);
+    }
+
+    fn encode_additional_to(&self, v: &mut Vec<u8>) {
+        // no metadata hash is included when the check is disabled
+        None::<[u8; 32]>.encode_to(v);
+    }
+}
+
+impl<T: Config> SignedExtension<T> for CheckMetadataHash {
+    type Decoded = u8;
+
+    fn matches(identifier: &str, _type_id: u32, _types: &PortableRegistry) -> bool {
+        identifier == Self::IDENTIFIER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_info::{
+        MetaType,
+        Registry,
+        TypeInfo,
+    };
+
+    /// `ChargeTransactionPayment` of a chain with `u128` balances.
+    #[derive(TypeInfo)]
+    #[allow(dead_code)]
+    struct ChargeTransactionPayment(#[codec(compact)] u128);
+
+    /// `ChargeTransactionPayment` of a chain with `u64` balances.
+    #[derive(TypeInfo)]
+    #[allow(dead_code)]
+    struct ChargeTransactionPaymentU64(#[codec(compact)] u64);
+
+    #[derive(TypeInfo)]
+    enum Mode {
+        #[allow(dead_code)]
+        Disabled,
+    }
+
+    /// Returns the registry and the type ids of the given `ChargeTransactionPayment` and
+    /// of `Mode`, the `extra` type of `CheckMetadataHash`.
+    fn signed_extension_types<ChargeTransactionPayment: TypeInfo + 'static>(
+    ) -> (PortableRegistry, u32, u32) {
+        let mut registry = Registry::new();
+        let charge_transaction_payment = registry
+            .register_type(&MetaType::new::<ChargeTransactionPayment>())
+            .id;
+        let mode = registry.register_type(&MetaType::new::<Mode>()).id;
+        (registry.into(), charge_transaction_payment, mode)
+    }
+
+    #[test]
+    fn detects_default_config() {
+        let (types, charge_transaction_payment, _) =
+            signed_extension_types::<ChargeTransactionPayment>();
+        let chain_config = ChainConfig::from_signed_extensions(
+            [
+                ("CheckNonce", 0),
+                ("ChargeTransactionPayment", charge_transaction_payment),
+            ],
+            &types,
+        );
+        assert_eq!(chain_config, ChainConfig::Polkadot);
+    }
+
+    #[test]
+    fn detects_u64_balance_config() {
+        let (types, charge_transaction_payment, _) =
+            signed_extension_types::<ChargeTransactionPaymentU64>();
+        let chain_config = ChainConfig::from_signed_extensions(
+            [
+                ("CheckNonce", 0),
+                ("ChargeTransactionPayment", charge_transaction_payment),
+            ],
+            &types,
+        );
+        assert_eq!(chain_config, ChainConfig::U64Balance);
+    }
+
+    #[test]
+    fn detects_metadata_hash_config() {
+        let (types, charge_transaction_payment, mode) =
+            signed_extension_types::<ChargeTransactionPayment>();
+        let chain_config = ChainConfig::from_signed_extensions(
+            [
+                ("ChargeTransactionPayment", charge_transaction_payment),
+                ("CheckMetadataHash", mode),
+            ],
+            &types,
+        );
+        assert_eq!(chain_config, ChainConfig::MetadataHash);
+    }
+
+    #[test]
+    fn check_metadata_hash_encodes_disabled_mode() {
+        let mut extra = Vec::new();
+        CheckMetadataHash.encode_extra_to(&mut extra);
+        let mut additional = Vec::new();
+        CheckMetadataHash.encode_additional_to(&mut additional);
+
+        assert_eq!(extra, vec![0]);
+        assert_eq!(additional, vec![0]);
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_transcode::convert::{
+    convert_metadata,
+    MetadataVersion,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    path::PathBuf,
+};
+
+/// Converts the metadata of a contract between ink! metadata versions.
+#[derive(Debug, clap::Args)]
+#[clap(name = "convert-metadata")]
+pub struct ConvertMetadataCommand {
+    /// The metadata file or contract bundle (`*.contract`) to convert.
+    #[clap(long, value_parser)]
+    file: PathBuf,
+    /// Write the converted metadata to this file instead of printing it to stdout.
+    #[clap(long, short, value_parser)]
+    output: Option<PathBuf>,
+    /// The ink! metadata version of the file, `v4` or `v5`. Detected from the file by
+    /// default.
+    #[clap(long)]
+    from: Option<MetadataVersion>,
+    /// The ink! metadata version to convert to, `v4` or `v5`.
+    #[clap(long)]
+    to: MetadataVersion,
+    /// Convert the metadata even if fields without a counterpart in the target version
+    /// are dropped.
+    #[clap(long)]
+    lossy: bool,
+}
+
+impl ConvertMetadataCommand {
+    pub fn run(&self) -> Result<()> {
+        let path = &self.file;
+        let file = File::open(path)
+            .context(format!("Failed to open metadata file {}", path.display()))?;
+        let metadata: Map<String, Value> = serde_json::from_reader(file).context(
+            format!("Failed to deserialize metadata file {}", path.display()),
+        )?;
+        if let Some(from) = self.from {
+            let version = MetadataVersion::of(&metadata)?;
+            anyhow::ensure!(
+                from == version,
+                "Expected ink! metadata {from}, but {} contains ink! metadata {version}",
+                path.display()
+            );
+        }
+
+        let conversion = convert_metadata(metadata, self.to)?;
+        let lost: Vec<_> = conversion
+            .unmapped
+            .iter()
+            .filter(|field| field.lost)
+            .map(|field| format!("`{}`", field.path))
+            .collect();
+        anyhow::ensure!(
+            self.lossy || lost.is_empty(),
+            "Converting to ink! metadata {} drops the fields {}. Pass `--lossy` to convert \
+             the metadata anyway.",
+            self.to,
+            lost.join(", ")
+        );
+        for field in &conversion.unmapped {
+            let label = if field.lost {
+                "Warning:".yellow().bold()
+            } else {
+                "Note:".cyan().bold()
+            };
+            eprintln!("{label} `{}` {}", field.path, field.note);
+        }
+
+        let contents = serde_json::to_string_pretty(&conversion.metadata)?;
+        match &self.output {
+            Some(output) => {
+                fs::write(output, contents).context(format!(
+                    "Failed to write metadata file {}",
+                    output.display()
+                ))?
+            }
+            None => println!("{contents}"),
+        }
+        Ok(())
+    }
+}
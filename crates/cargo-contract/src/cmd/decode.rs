@@ -28,7 +28,10 @@ use contract_build::{
     util,
     CrateMetadata,
 };
-use contract_transcode::ContractMessageTranscoder;
+use contract_transcode::{
+    mark_indexed_fields,
+    ContractMessageTranscoder,
+};
 
 #[derive(Debug, Args)]
 pub struct DecodeCommand {
@@ -65,9 +68,13 @@ pub struct DecodeConstructor {
 #[derive(Debug, Clone, Args)]
 pub struct DecodeEvent {
     /// The signature topic of the event to be decoded; this has to be a hex value
-    /// starting with `0x`.
+    /// starting with `0x`. Omitted for anonymous events.
     #[clap(short, long)]
-    signature_topic: String,
+    signature_topic: Option<String>,
+    /// The topic of an indexed field of the event; this has to be a hex value starting
+    /// with `0x`. Anonymous events are matched by the number of these topics.
+    #[clap(long = "topic", value_name = "TOPIC")]
+    topics: Vec<String>,
     /// The data to decode; this has to be a hex value starting with `0x`.
     #[clap(short, long)]
     data: String,
@@ -82,14 +89,24 @@ impl DecodeCommand {
         const ERR_MSG: &str = "Failed to decode specified data as a hex value";
         let decoded_data = match &self.commands {
             DecodeCommands::Event(event) => {
-                let signature_topic_data =
-                    util::decode_hex(&event.signature_topic).context(ERR_MSG)?;
-                let signature_topic =
-                    primitive_types::H256::from_slice(&signature_topic_data);
-                transcoder.decode_contract_event(
-                    &signature_topic,
+                let topics = event
+                    .signature_topic
+                    .iter()
+                    .chain(&event.topics)
+                    .map(|topic| {
+                        let topic = util::decode_hex(topic).context(ERR_MSG)?;
+                        anyhow::ensure!(
+                            topic.len() == 32,
+                            "A topic should be 32 bytes in length"
+                        );
+                        Ok(primitive_types::H256::from_slice(&topic))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let decoded = transcoder.decode_contract_event_with_topics(
+                    &topics,
                     &mut &util::decode_hex(&event.data).context(ERR_MSG)?[..],
-                )?
+                )?;
+                mark_indexed_fields(&decoded.value, &decoded.indexed)
             }
             DecodeCommands::Message(message) => {
                 transcoder.decode_contract_message(
@@ -0,0 +1,626 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A smoke test of a contract on a dev node: the contract is built, uploaded and
+//! instantiated, and the messages of a scenario file are called with their results
+//! checked against the expected ones.
+
+use super::{
+    create_signer,
+    node::DevNode,
+};
+use crate::ErrorVariant;
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_build::{
+    ExecuteArgs,
+    ManifestPath,
+};
+use contract_extrinsics::{
+    CallCommandBuilder,
+    Connection,
+    ContractAddress,
+    DebugMessage,
+    ExtrinsicOptsBuilder,
+    InstantiateCommandBuilder,
+};
+use contract_transcode::Value;
+use ink_env::DefaultEnvironment;
+use serde::Deserialize;
+use sp_core::Bytes;
+use sp_weights::Weight;
+use std::{
+    fmt::Display,
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+use subxt::{
+    Config,
+    PolkadotConfig as DefaultConfig,
+};
+use subxt_signer::sr25519::Keypair;
+
+/// The scenario file used if neither `--scenario` nor the manifest specify one.
+const DEFAULT_SCENARIO: &str = "e2e.toml";
+
+/// Test the contract.
+#[derive(Debug, clap::Args)]
+#[clap(name = "test")]
+pub struct TestCommand {
+    /// Build the contract, deploy it on a dev node and call the messages of the
+    /// scenario file, checking their results.
+    #[clap(long, required = true)]
+    e2e: bool,
+    /// Path to the `Cargo.toml` of the contract.
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+    /// The scenario file in TOML or JSON format. Defaults to the `scenario` of
+    /// `[package.metadata.contract.e2e]`, or `e2e.toml` next to the manifest.
+    #[clap(long, value_parser)]
+    scenario: Option<PathBuf>,
+    /// Websocket endpoint of the node to test against. Defaults to the node of
+    /// `cargo contract node`, which is started for the test run if it is not running.
+    #[clap(long, value_parser)]
+    url: Option<url::Url>,
+    /// Secret key URI of the account deploying and calling the contract.
+    #[clap(short, long, default_value = "//Alice")]
+    suri: String,
+    /// The constructor to instantiate the contract with, overriding the scenario.
+    #[clap(long)]
+    constructor: Option<String>,
+    /// The constructor arguments, overriding the scenario.
+    #[clap(long, num_args = 0..)]
+    args: Option<Vec<String>>,
+    /// Use the contract artifacts of the last build instead of building the contract.
+    #[clap(long)]
+    skip_build: bool,
+}
+
+/// The settings of `[package.metadata.contract.e2e]` in the manifest of the contract.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct E2eMetadata {
+    constructor: Option<String>,
+    args: Option<Vec<String>>,
+    scenario: Option<PathBuf>,
+}
+
+/// The steps of a test run, read from a TOML or JSON file.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Scenario {
+    /// The constructor to instantiate the contract with.
+    constructor: Option<String>,
+    /// The constructor arguments.
+    args: Option<Vec<String>>,
+    /// The messages called on the instantiated contract, in order.
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+/// A message call of a [`Scenario`].
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Step {
+    /// The name of the message.
+    message: String,
+    /// The message arguments.
+    #[serde(default)]
+    args: Vec<String>,
+    /// The expected return value, e.g. `true` or `Ok(true)`.
+    expect: Option<String>,
+    /// Whether the message is expected to revert.
+    #[serde(default)]
+    reverts: bool,
+    /// Whether to submit the call after the dry-run, defaults to whether the message
+    /// mutates the storage of the contract.
+    execute: Option<bool>,
+}
+
+impl Scenario {
+    /// Reads the scenario, in JSON format if the file has the `.json` extension and in
+    /// TOML format otherwise.
+    fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let scenario = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            toml::from_str(&contents).map_err(anyhow::Error::from)
+        };
+        scenario.with_context(|| format!("Invalid scenario {}", path.display()))
+    }
+}
+
+/// The outcome of a step of the test run.
+struct StepReport {
+    name: String,
+    /// The gas consumed and required by the dry-run.
+    gas: Option<(Weight, Weight)>,
+    /// The reason the step failed, `None` if it passed.
+    failure: Option<String>,
+}
+
+impl StepReport {
+    /// The report of the step `name` which failed with an `error` before it was run,
+    /// e.g. because its arguments could not be encoded.
+    fn error(name: String, error: anyhow::Error) -> Self {
+        Self {
+            name,
+            gas: None,
+            failure: Some(format!("{error:#}")),
+        }
+    }
+
+    fn print(&self) {
+        let status = match self.failure {
+            None => "PASS".green().bold(),
+            Some(_) => "FAIL".red().bold(),
+        };
+        match self.gas {
+            Some((consumed, required)) => {
+                println!(
+                    "{status} {} (gas consumed: {consumed}, gas required: {required})",
+                    self.name
+                )
+            }
+            None => println!("{status} {}", self.name),
+        }
+        if let Some(failure) = &self.failure {
+            for line in failure.lines() {
+                println!("     {line}");
+            }
+        }
+    }
+}
+
+impl TestCommand {
+    pub async fn run(&self) -> Result<(), ErrorVariant> {
+        let manifest_path = ManifestPath::try_from(self.manifest_path.as_ref())?;
+        let metadata = e2e_metadata(&manifest_path)?;
+        let scenario_path = self
+            .scenario
+            .clone()
+            .or_else(|| {
+                metadata
+                    .scenario
+                    .as_ref()
+                    .map(|path| manifest_dir(&manifest_path).join(path))
+            })
+            .or_else(|| {
+                Some(manifest_dir(&manifest_path).join(DEFAULT_SCENARIO))
+                    .filter(|path| path.exists())
+            });
+        let scenario = scenario_path
+            .as_deref()
+            .map(Scenario::load)
+            .transpose()?
+            .unwrap_or_default();
+        let constructor = self
+            .constructor
+            .clone()
+            .or(scenario.constructor.clone())
+            .or(metadata.constructor)
+            .unwrap_or_else(|| String::from("new"));
+        let args = self
+            .args
+            .clone()
+            .or(scenario.args.clone())
+            .or(metadata.args)
+            .unwrap_or_default();
+
+        if !self.skip_build {
+            contract_build::execute(ExecuteArgs {
+                manifest_path: manifest_path.clone(),
+                ..Default::default()
+            })?;
+        }
+
+        let node = match &self.url {
+            Some(_) => None,
+            None => Some(DevNode::connect_or_start().await?),
+        };
+        let url = match (&self.url, &node) {
+            (Some(url), _) => url.clone(),
+            (None, Some(node)) => {
+                url::Url::parse(node.url()).map_err(anyhow::Error::from)?
+            }
+            (None, None) => unreachable!("a node is started without `--url`"),
+        };
+        let reports = self
+            .run_steps(&manifest_path, &url, &constructor, &args, &scenario.steps)
+            .await;
+        if let Some(node) = node {
+            node.stop()?;
+        }
+        let reports = reports?;
+
+        let failed = reports
+            .iter()
+            .filter(|report| report.failure.is_some())
+            .count();
+        println!("\n{} passed, {} failed", reports.len() - failed, failed);
+        if failed > 0 {
+            return Err(anyhow!("{failed} of {} steps failed", reports.len()).into())
+        }
+        Ok(())
+    }
+
+    /// Instantiates the contract and calls the messages of the `steps`, stopping at the
+    /// first failure since the following steps depend on the state it left behind.
+    ///
+    /// An error of a step, e.g. an argument which can't be encoded, fails the step, so
+    /// that the report of the steps run so far is printed.
+    async fn run_steps(
+        &self,
+        manifest_path: &ManifestPath,
+        url: &url::Url,
+        constructor: &str,
+        args: &[String],
+        steps: &[Step],
+    ) -> Result<Vec<StepReport>> {
+        let chain = TestChain {
+            connection: Connection::connect(url.as_str()).await?,
+            signer: create_signer(&self.suri)?,
+            manifest_path: manifest_path.as_ref().to_path_buf(),
+            url: url.clone(),
+        };
+
+        let mut reports = Vec::new();
+        let (contract, report) = chain
+            .instantiate(constructor, args)
+            .await
+            .unwrap_or_else(|error| {
+                let name = format!("instantiate {}", signature(constructor, args));
+                (None, StepReport::error(name, error))
+            });
+        report.print();
+        reports.push(report);
+        let Some(contract) = contract else {
+            return Ok(reports)
+        };
+        for step in steps {
+            let report = chain.call(&contract, step).await.unwrap_or_else(|error| {
+                StepReport::error(signature(&step.message, &step.args), error)
+            });
+            report.print();
+            let failed = report.failure.is_some();
+            reports.push(report);
+            if failed {
+                break
+            }
+        }
+        Ok(reports)
+    }
+}
+
+/// The chain and the account the contract is tested with.
+struct TestChain {
+    connection: Connection<DefaultConfig>,
+    signer: Keypair,
+    manifest_path: PathBuf,
+    url: url::Url,
+}
+
+impl TestChain {
+    fn extrinsic_opts(
+        &self,
+    ) -> ExtrinsicOptsBuilder<DefaultConfig, DefaultEnvironment, Keypair> {
+        ExtrinsicOptsBuilder::new(self.signer.clone())
+            .manifest_path(Some(self.manifest_path.clone()))
+            .url(self.url.clone())
+    }
+
+    /// Uploads and instantiates the contract, returning its address if it succeeded.
+    async fn instantiate(
+        &self,
+        constructor: &str,
+        args: &[String],
+    ) -> Result<(
+        Option<ContractAddress<<DefaultConfig as Config>::AccountId>>,
+        StepReport,
+    )> {
+        let instantiate_exec =
+            InstantiateCommandBuilder::new(self.extrinsic_opts().done())
+                .constructor(constructor)
+                .args(args.to_vec())
+                .salt(Some(unique_salt()))
+                .connection(Some(self.connection.clone()))
+                .done()
+                .await?;
+        let mut report = StepReport {
+            name: format!("instantiate {}", signature(constructor, args)),
+            gas: None,
+            failure: None,
+        };
+        let result = instantiate_exec.instantiate_dry_run().await?;
+        report.gas = Some((result.gas_consumed, result.gas_required));
+        let debug_message = DebugMessage::from_bytes(&result.debug_message);
+        let dry_run = match instantiate_exec
+            .decode_instantiate_dry_run(&result, None)
+            .await
+        {
+            Ok(dry_run) => dry_run,
+            Err(error) => {
+                report.failure = Some(failure(error, debug_message.as_ref()));
+                return Ok((None, report))
+            }
+        };
+        if dry_run.reverted {
+            let error = dry_run.error.unwrap_or(dry_run.result);
+            report.failure = Some(failure(
+                format!("The constructor reverted: {error}"),
+                debug_message.as_ref(),
+            ));
+            return Ok((None, report))
+        }
+        match instantiate_exec
+            .instantiate(Some(result.gas_required))
+            .await
+        {
+            Ok(instantiated) => Ok((Some(instantiated.contract_address), report)),
+            Err(error) => {
+                report.failure = Some(failure(error, None));
+                Ok((None, report))
+            }
+        }
+    }
+
+    /// Dry-runs the message of the `step`, submitting it if it is executed, and checks
+    /// the result against the expected one.
+    async fn call(
+        &self,
+        contract: &ContractAddress<<DefaultConfig as Config>::AccountId>,
+        step: &Step,
+    ) -> Result<StepReport> {
+        let call_exec = CallCommandBuilder::<_, DefaultEnvironment, _>::new(
+            contract.clone(),
+            &step.message,
+            self.extrinsic_opts().done(),
+        )
+        .args(step.args.clone())
+        .connection(Some(self.connection.clone()))
+        .done()
+        .await?;
+        let mut report = StepReport {
+            name: signature(&step.message, &step.args),
+            gas: None,
+            failure: None,
+        };
+        let result = call_exec.call_dry_run().await?;
+        report.gas = Some((result.gas_consumed, result.gas_required));
+        let debug_message = DebugMessage::from_bytes(&result.debug_message);
+        let ret_val = match &result.result {
+            Ok(ret_val) => ret_val,
+            Err(err) => {
+                let metadata = call_exec.client().metadata();
+                let error = ErrorVariant::from_dispatch_error(err, &metadata)?;
+                report.failure = Some(failure(error, debug_message.as_ref()));
+                return Ok(report)
+            }
+        };
+        let transcoder = call_exec
+            .transcoder()
+            .expect("the message is called with the contract metadata");
+        let value = transcoder
+            .decode_message_return(call_exec.message(), &mut &ret_val.data[..])
+            .with_context(|| format!("Failed to decode return value {ret_val:?}"))?;
+        let mismatch = match (ret_val.did_revert(), step.reverts) {
+            (true, false) => Some(format!("The message reverted: {value}")),
+            (false, true) => {
+                Some(format!("Expected the message to revert, got `{value}`"))
+            }
+            _ => {
+                step.expect
+                    .as_ref()
+                    .filter(|expect| !matches_expectation(&value, expect))
+                    .map(|expect| format!("Expected `{expect}`, got `{value}`"))
+            }
+        };
+        if let Some(mismatch) = mismatch {
+            report.failure = Some(failure(mismatch, debug_message.as_ref()));
+            return Ok(report)
+        }
+
+        let mutates = transcoder
+            .metadata()
+            .spec()
+            .messages()
+            .iter()
+            .find(|message| message.label() == call_exec.message())
+            .is_some_and(|message| message.mutates());
+        if step.execute.unwrap_or(mutates) && !step.reverts {
+            if let Err(error) = call_exec.call(Some(result.gas_required)).await {
+                report.failure = Some(failure(error, None));
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Reads `[package.metadata.contract.e2e]` from the manifest.
+fn e2e_metadata(manifest_path: &ManifestPath) -> Result<E2eMetadata> {
+    let manifest = fs::read_to_string(manifest_path)?;
+    let manifest: toml::Value = toml::from_str(&manifest)?;
+    let e2e = ["package", "metadata", "contract", "e2e"]
+        .iter()
+        .try_fold(&manifest, |value, key| value.get(key));
+    match e2e {
+        Some(e2e) => {
+            e2e.clone()
+                .try_into()
+                .context("Invalid `[package.metadata.contract.e2e]` in the manifest")
+        }
+        None => Ok(E2eMetadata::default()),
+    }
+}
+
+/// Returns the directory containing the manifest.
+fn manifest_dir(manifest_path: &ManifestPath) -> PathBuf {
+    manifest_path
+        .directory()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Returns whether the decoded return `value` is the expected one, ignoring whitespace.
+/// The expectation may omit the `Ok` wrapping the return value of ink! messages.
+fn matches_expectation(value: &Value, expect: &str) -> bool {
+    let normalize = |s: &str| s.split_whitespace().collect::<String>();
+    let value = normalize(&value.to_string());
+    let expect = normalize(expect);
+    value == expect || value == format!("Ok({expect})")
+}
+
+/// Formats the failure of a step, followed by the debug message of the dry-run.
+fn failure(error: impl Display, debug_message: Option<&DebugMessage>) -> String {
+    match debug_message {
+        Some(debug_message) => {
+            format!(
+                "{error}\nDebug message: {}",
+                debug_message.message.trim_end()
+            )
+        }
+        None => error.to_string(),
+    }
+}
+
+/// Formats the call of the constructor or message `name` with the `args`.
+fn signature(name: &str, args: &[String]) -> String {
+    format!("{name}({})", args.join(", "))
+}
+
+/// Returns a salt which is unique to the test run, so that repeated runs against the
+/// same chain don't instantiate the same contract address.
+fn unique_salt() -> Bytes {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    Bytes(nanos.to_le_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenario_in_toml_and_json() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let toml_path = tmp_dir.path().join("e2e.toml");
+        fs::write(
+            &toml_path,
+            r#"
+            constructor = "new"
+            args = ["false"]
+
+            [[steps]]
+            message = "flip"
+
+            [[steps]]
+            message = "get"
+            expect = "true"
+            "#,
+        )
+        .unwrap();
+        let json_path = tmp_dir.path().join("e2e.json");
+        fs::write(
+            &json_path,
+            r#"{
+                "constructor": "new",
+                "args": ["false"],
+                "steps": [
+                    { "message": "flip" },
+                    { "message": "get", "expect": "true" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let scenario = Scenario::load(&toml_path).unwrap();
+        assert_eq!(scenario, Scenario::load(&json_path).unwrap());
+        assert_eq!(scenario.constructor.as_deref(), Some("new"));
+        assert_eq!(
+            scenario.steps[1],
+            Step {
+                message: "get".into(),
+                args: Vec::new(),
+                expect: Some("true".into()),
+                reverts: false,
+                execute: None,
+            }
+        );
+
+        fs::write(&toml_path, "[[steps]]\nmesage = \"get\"").unwrap();
+        let err = format!("{:#}", Scenario::load(&toml_path).unwrap_err());
+        assert!(err.contains("Invalid scenario"), "{err}");
+    }
+
+    #[test]
+    fn e2e_metadata_is_read_from_the_manifest() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let manifest = tmp_dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            r#"
+            [package]
+            name = "flipper"
+
+            [package.metadata.contract.e2e]
+            constructor = "default"
+            scenario = "tests/smoke.json"
+            "#,
+        )
+        .unwrap();
+        let metadata = e2e_metadata(&ManifestPath::new(&manifest).unwrap()).unwrap();
+        assert_eq!(
+            metadata,
+            E2eMetadata {
+                constructor: Some("default".into()),
+                args: None,
+                scenario: Some("tests/smoke.json".into()),
+            }
+        );
+
+        fs::write(&manifest, "[package]\nname = \"flipper\"").unwrap();
+        let metadata = e2e_metadata(&ManifestPath::new(&manifest).unwrap()).unwrap();
+        assert_eq!(metadata, E2eMetadata::default());
+    }
+
+    #[test]
+    fn expectations_match_the_decoded_value() {
+        let ok_true = Value::Tuple(contract_transcode::Tuple::new(
+            Some("Ok"),
+            vec![Value::Bool(true)],
+        ));
+        assert!(matches_expectation(&ok_true, "true"));
+        assert!(matches_expectation(&ok_true, "Ok(true)"));
+        assert!(matches_expectation(&ok_true, " Ok( true ) "));
+        assert!(!matches_expectation(&ok_true, "false"));
+        assert!(matches_expectation(&Value::UInt(42), "42"));
+    }
+}
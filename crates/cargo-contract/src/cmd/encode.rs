@@ -37,9 +37,14 @@ pub struct EncodeCommand {
     /// The name of the contract message to encode.
     #[clap(long, short)]
     message: String,
-    /// The arguments to encode
+    /// The arguments to encode. An argument `@path` is read from the file at `path`,
+    /// `@-` from stdin, and `@@` escapes a leading `@`.
     #[clap(long, num_args = 0..)]
     args: Vec<String>,
+    /// Read `@path` argument files of byte array arguments as hex if they start with
+    /// `0x`, instead of as raw bytes.
+    #[clap(long)]
+    args_hex_files: bool,
 }
 
 impl EncodeCommand {
@@ -48,7 +53,12 @@ impl EncodeCommand {
             self.manifest_path.as_ref(),
             self.file.as_ref(),
         )?;
-        let transcoder = artifacts.contract_transcoder()?;
+        for notice in artifacts.notices() {
+            eprintln!("{notice}");
+        }
+        let transcoder = artifacts
+            .contract_transcoder()?
+            .with_hex_file_args(self.args_hex_files);
 
         let call_data = transcoder.encode(&self.message, &self.args)?;
         let call_data_encoded = hex::encode_upper(call_data);
@@ -0,0 +1,161 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use std::{
+    fmt,
+    str::FromStr,
+};
+use subxt::{
+    blocks::ExtrinsicEvents,
+    Config,
+    OnlineClient,
+};
+
+/// The placeholders an explorer url template may contain.
+const PLACEHOLDERS: [&str; 4] =
+    ["extrinsic_hash", "block_hash", "block_number", "contract"];
+
+/// A template of the url of a submitted extrinsic in a block explorer, e.g.
+/// `https://xyz.subscan.io/extrinsic/{extrinsic_hash}`, with the placeholders
+/// `{extrinsic_hash}`, `{block_hash}`, `{block_number}` and `{contract}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplorerUrlTemplate(String);
+
+impl FromStr for ExplorerUrlTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(template: &str) -> Result<Self> {
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}').ok_or_else(|| {
+                anyhow!("Unclosed placeholder in explorer url template `{template}`")
+            })?;
+            let placeholder = &rest[start + 1..start + end];
+            if !PLACEHOLDERS.contains(&placeholder) {
+                return Err(anyhow!(
+                    "Unknown placeholder `{{{placeholder}}}` in explorer url template \
+                     `{template}`, expected one of {}",
+                    PLACEHOLDERS
+                        .iter()
+                        .map(|placeholder| format!("`{{{placeholder}}}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            }
+            rest = &rest[start + end + 1..];
+        }
+        Ok(Self(template.to_string()))
+    }
+}
+
+impl fmt::Display for ExplorerUrlTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ExplorerUrlTemplate {
+    /// Returns the url of the extrinsic which emitted the `events`, created by the
+    /// `contract` if any.
+    ///
+    /// The number of the block is only queried through the `client` if the template
+    /// contains `{block_number}`. Returns `None` if the template contains `{contract}`
+    /// but there is no contract, e.g. for an upload.
+    pub async fn url<C: Config>(
+        &self,
+        client: &OnlineClient<C>,
+        events: &ExtrinsicEvents<C>,
+        contract: Option<String>,
+    ) -> Result<Option<String>> {
+        let block_number = if self.0.contains("{block_number}") {
+            let block = client.blocks().at(events.block_hash()).await?;
+            let block_number: u64 = block.number().into();
+            Some(block_number.to_string())
+        } else {
+            None
+        };
+        let extrinsic_hash = format!("{:?}", events.extrinsic_hash());
+        let block_hash = format!("{:?}", events.block_hash());
+        Ok(self.fill(|placeholder| {
+            match placeholder {
+                "extrinsic_hash" => Some(extrinsic_hash.clone()),
+                "block_hash" => Some(block_hash.clone()),
+                "block_number" => block_number.clone(),
+                "contract" => contract.clone(),
+                _ => None,
+            }
+        }))
+    }
+
+    /// Replaces the placeholders of the template with their `value`, or returns `None`
+    /// if a placeholder has no value.
+    fn fill(&self, value: impl Fn(&str) -> Option<String>) -> Option<String> {
+        let mut url = self.0.clone();
+        for placeholder in PLACEHOLDERS {
+            let pattern = format!("{{{placeholder}}}");
+            if url.contains(&pattern) {
+                url = url.replace(&pattern, &value(placeholder)?);
+            }
+        }
+        Some(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders() {
+        let template = ExplorerUrlTemplate::from_str(
+            "https://xyz.subscan.io/block/{block_number}?contract={contract}",
+        )
+        .unwrap();
+        let value = |placeholder: &str| {
+            match placeholder {
+                "block_number" => Some("42".to_string()),
+                "contract" => Some("5Grw".to_string()),
+                _ => None,
+            }
+        };
+        assert_eq!(
+            template.fill(value).as_deref(),
+            Some("https://xyz.subscan.io/block/42?contract=5Grw")
+        );
+        assert_eq!(template.fill(|_| None), None);
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        assert!(ExplorerUrlTemplate::from_str(
+            "https://xyz.subscan.io/extrinsic/{extrinsic_hash}"
+        )
+        .is_ok());
+        let err =
+            ExplorerUrlTemplate::from_str("https://xyz.subscan.io/tx/{tx_hash}")
+                .unwrap_err()
+                .to_string();
+        assert!(err.contains("Unknown placeholder `{tx_hash}`"), "{err}");
+        assert!(
+            ExplorerUrlTemplate::from_str("https://xyz.subscan.io/{block_hash")
+                .is_err()
+        );
+    }
+}
@@ -17,17 +17,25 @@
 use super::{
     basic_display_format_extended_contract_info,
     display_all_contracts,
+    parse_node_url,
+    resolve_ss58_prefix,
+    ContractAddressArg,
     DefaultConfig,
 };
 use anyhow::Result;
 use contract_analyze::determine_language;
+use contract_build::name_value_println;
 use contract_extrinsics::{
     fetch_all_contracts,
     fetch_contract_info,
     fetch_wasm_code,
+    rpc_client,
     url_to_string,
+    ChainLimits,
     ContractInfo,
+    ContractsPallet,
     ErrorVariant,
+    Ss58Prefix,
     TrieId,
 };
 use ink_env::{
@@ -39,10 +47,7 @@ use std::{
     io::Write,
 };
 use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
-    },
+    backend::legacy::LegacyRpcMethods,
     Config,
     OnlineClient,
 };
@@ -55,14 +60,14 @@ pub struct InfoCommand {
         name = "contract",
         long,
         env = "CONTRACT",
-        required_unless_present = "all"
+        required_unless_present_any = ["all", "limits"]
     )]
-    contract: Option<<DefaultConfig as Config>::AccountId>,
-    /// Websockets url of a substrate node.
+    contract: Option<ContractAddressArg>,
+    /// Websockets or HTTP url of a substrate node.
     #[clap(
         name = "url",
         long,
-        value_parser,
+        value_parser = parse_node_url,
         default_value = "ws://localhost:9944"
     )]
     url: url::Url,
@@ -75,26 +80,52 @@ pub struct InfoCommand {
     /// Display all contracts addresses
     #[clap(name = "all", long)]
     all: bool,
+    /// Display the limits of the contracts pallet of the chain, such as the maximum
+    /// code size, instead of the infos of a contract.
+    #[clap(name = "limits", long, conflicts_with_all = ["contract", "all", "binary"])]
+    limits: bool,
+    /// The SS58 address prefix used to display account ids. If not specified, the
+    /// prefix reported by the chain is used.
+    #[clap(long)]
+    ss58_prefix: Option<Ss58Prefix>,
 }
 
 impl InfoCommand {
     pub async fn run(&self) -> Result<(), ErrorVariant> {
-        let rpc_cli = RpcClient::from_url(url_to_string(&self.url)).await?;
+        let rpc_cli = rpc_client(&url_to_string(&self.url)).await?;
         let client =
             OnlineClient::<DefaultConfig>::from_rpc_client(rpc_cli.clone()).await?;
         let rpc = LegacyRpcMethods::<DefaultConfig>::new(rpc_cli.clone());
 
+        if self.limits {
+            let metadata = client.metadata();
+            let pallet = ContractsPallet::detect(&metadata)?;
+            let limits = ChainLimits::from_metadata(&metadata, pallet);
+            if self.output_json {
+                println!("{}", serde_json::to_string_pretty(&limits)?);
+            } else {
+                display_chain_limits(pallet, &limits);
+            }
+            return Ok(())
+        }
+
+        let ss58_prefix = resolve_ss58_prefix(self.ss58_prefix, &self.url).await?;
+
         // All flag applied
         if self.all {
             let contracts = fetch_all_contracts(&client, &rpc).await?;
 
             if self.output_json {
+                let contracts: Vec<_> = contracts
+                    .iter()
+                    .map(|contract| ss58_prefix.address(contract))
+                    .collect();
                 let contracts_json = serde_json::json!({
                     "contracts": contracts
                 });
                 println!("{}", serde_json::to_string_pretty(&contracts_json)?);
             } else {
-                display_all_contracts(&contracts)
+                display_all_contracts(&contracts, ss58_prefix)
             }
             Ok(())
         } else {
@@ -104,9 +135,12 @@ impl InfoCommand {
                 .contract
                 .as_ref()
                 .expect("Contract argument was not provided");
+            contract.note_ss58_prefix_mismatch(ss58_prefix);
 
             let info_to_json = fetch_contract_info::<DefaultConfig, DefaultEnvironment>(
-                contract, &rpc, &client,
+                &contract.address(),
+                &rpc,
+                &client,
             )
             .await?;
 
@@ -147,6 +181,18 @@ impl InfoCommand {
     }
 }
 
+/// Displays the maximum sizes allowed by the contracts `pallet`, or that the chain
+/// doesn't expose one.
+fn display_chain_limits(pallet: ContractsPallet, limits: &ChainLimits) {
+    name_value_println!("Pallet", pallet.to_string());
+    let bytes = |limit: Option<u32>| {
+        limit.map_or_else(|| "not exposed".to_string(), |limit| format!("{limit} bytes"))
+    };
+    name_value_println!("Code", bytes(limits.max_code_len));
+    name_value_println!("Storage key", bytes(limits.max_storage_key_len));
+    name_value_println!("Debug buffer", bytes(limits.max_debug_buffer_len));
+}
+
 #[derive(serde::Serialize)]
 pub struct ExtendedContractInfo<Hash, Balance> {
     pub trie_id: TrieId,
@@ -15,14 +15,35 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
-    create_signer,
+    config::{
+        with_chain_config,
+        ExtrinsicBalance,
+        ExtrinsicConfig,
+    },
+    denominate_balance,
     display_contract_exec_result,
     display_contract_exec_result_debug,
+    display_dry_run_events,
     display_dry_run_result_warning,
+    parse_code_hash,
+    pre_submit_dry_run_error,
+    print_block_usage,
     print_dry_running_status,
+    print_fuzzy_match,
     print_gas_required_success,
     prompt_confirm_tx,
+    to_json,
+    undecoded_note,
+    warn_block_usage,
+    AccountIdArg,
+    AdditionalMetadataOpts,
     CLIExtrinsicOpts,
+    DebugMessageOpts,
+    ExecutionOutcome,
+    ExternalSignerOpts,
+    PaidUnpayable,
+    TxCosts,
+    WeightArg,
     MAX_KEY_COL_WIDTH,
 };
 use crate::{
@@ -32,6 +53,7 @@ use crate::{
     Weight,
 };
 use anyhow::Result;
+use colored::Colorize;
 use contract_build::{
     name_value_println,
     util::{
@@ -39,15 +61,26 @@ use contract_build::{
         DEFAULT_KEY_COL_WIDTH,
     },
     Verbosity,
+    WasmValidation,
 };
 use contract_extrinsics::{
+    block_usage,
+    pallet_contracts_primitives::StorageDeposit,
+    Address,
+    AddressBook,
     BalanceVariant,
     Code,
+    Connection,
+    DisplayBalance,
     DisplayEvents,
+    EventDisplay,
     ExtrinsicOptsBuilder,
     InstantiateCommandBuilder,
     InstantiateDryRunResult,
     InstantiateExecResult,
+    MortalityParams,
+    Progress,
+    TimingsReport,
     TokenMetadata,
 };
 use ink_env::{
@@ -55,8 +88,15 @@ use ink_env::{
     Environment,
 };
 use sp_core::Bytes;
-use std::fmt::Debug;
-use subxt::PolkadotConfig as DefaultConfig;
+use std::{
+    fmt::Debug,
+    path::PathBuf,
+};
+use subxt::{
+    config::ExtrinsicParams,
+    Config,
+    PolkadotConfig as DefaultConfig,
+};
 use subxt_signer::sr25519::Keypair;
 
 #[derive(Debug, clap::Args)]
@@ -64,9 +104,18 @@ pub struct InstantiateCommand {
     /// The name of the contract constructor to call
     #[clap(name = "constructor", long, default_value = "new")]
     constructor: String,
-    /// The constructor arguments, encoded as strings
+    /// Call the constructor whose name matches `--constructor` case-insensitively, or
+    /// starts with it, if it is the only match.
+    #[clap(long)]
+    fuzzy_match: bool,
+    /// The constructor arguments, encoded as strings. An argument `@path` is read from
+    /// the file at `path`, `@-` from stdin, and `@@` escapes a leading `@`.
     #[clap(long, num_args = 0..)]
     args: Vec<String>,
+    /// Read `@path` argument files of byte array arguments as hex if they start with
+    /// `0x`, instead of as raw bytes.
+    #[clap(long)]
+    args_hex_files: bool,
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
     /// Transfers an initial balance to the instantiated contract
@@ -81,13 +130,47 @@ pub struct InstantiateCommand {
     /// If not specified will perform a dry-run to estimate the proof size required.
     #[clap(long)]
     proof_size: Option<u64>,
+    /// Maximum weight for this instantiation as `<ref_time>,<proof_size>`, combining
+    /// `--gas` and `--proof-size`. The reference time accepts the `ms` suffix and
+    /// the proof size the `KiB` and `MiB` suffixes, e.g. `2ms,256KiB`.
+    #[clap(long, conflicts_with_all = ["gas", "proof_size"])]
+    weight: Option<WeightArg>,
     /// A salt used in the address derivation of the new contract. Use to create multiple
     /// instances of the same contract code from the same account.
     #[clap(long, value_parser = parse_hex_bytes)]
     salt: Option<Bytes>,
+    /// The hash of code already stored on chain to instantiate, instead of the code of
+    /// the contract artifact. The constructor arguments are encoded with the metadata
+    /// given by `--metadata`, or of the contract at `--manifest-path`.
+    #[clap(long, value_parser = parse_code_hash, conflicts_with = "file")]
+    code_hash: Option<<DefaultConfig as Config>::Hash>,
+    /// Path to the `.json` metadata file of the contract instantiated with
+    /// `--code-hash`, or of the raw `.wasm` or `.polkavm` code file given with
+    /// `--file`.
+    #[clap(long, value_name = "PATH", conflicts_with = "manifest_path")]
+    metadata: Option<PathBuf>,
+    /// Dry-run with the given account as the deployer instead of the signer. `--suri` is
+    /// not required with an origin, which can not be combined with `--execute`.
+    #[clap(long, conflicts_with = "execute")]
+    origin: Option<AccountIdArg>,
     /// Export the instantiate output in JSON format.
-    #[clap(long, conflicts_with = "verbose")]
+    #[clap(long, conflicts_with_all = ["verbose", "quiet"])]
     output_json: bool,
+    /// Wrap the submitted call in `Sudo::sudo` to dispatch it with the root origin, e.g.
+    /// on development chains which only allow root to upload code. The signer must be
+    /// the sudo key of the chain.
+    #[clap(long, requires = "execute")]
+    sudo: bool,
+    /// Succeed with the address of the existing contract if the signer already
+    /// instantiated the same code with the same constructor input and salt.
+    #[clap(long)]
+    idempotent: bool,
+    #[clap(flatten)]
+    debug_message: DebugMessageOpts,
+    #[clap(flatten)]
+    additional_metadata: AdditionalMetadataOpts,
+    #[clap(flatten)]
+    external_signer: ExternalSignerOpts,
 }
 
 /// Parse hex encoded bytes.
@@ -102,73 +185,294 @@ impl InstantiateCommand {
         self.output_json
     }
 
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts(&self) -> &CLIExtrinsicOpts {
+        &self.extrinsic_cli_opts
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts_mut(&mut self) -> &mut CLIExtrinsicOpts {
+        &mut self.extrinsic_cli_opts
+    }
+
+    /// Returns the gas limit given by `--gas` or `--weight`.
+    fn gas_limit(&self) -> Option<u64> {
+        self.weight
+            .map(|weight| weight.ref_time())
+            .or(self.gas_limit)
+    }
+
+    /// Returns the proof size given by `--proof-size` or `--weight`.
+    fn proof_size(&self) -> Option<u64> {
+        self.weight
+            .map(|weight| weight.proof_size())
+            .or(self.proof_size)
+    }
+
     pub async fn handle(&self) -> Result<(), ErrorVariant> {
-        let token_metadata =
-            TokenMetadata::query::<DefaultConfig>(&self.extrinsic_cli_opts.url).await?;
+        if self.extrinsic_cli_opts.progress(self.output_json()) {
+            Progress::Connecting.report();
+        }
+        let connection = self.extrinsic_cli_opts.connect().await?;
+        let chain_config = self.extrinsic_cli_opts.chain_config(&connection);
+        with_chain_config!(chain_config, |C, E| {
+            self.handle_with_config::<C, E>(connection.with_config()?)
+                .await
+        })
+    }
+
+    async fn handle_with_config<C, E>(
+        &self,
+        connection: Connection<C>,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let token_metadata = self.extrinsic_cli_opts.token_metadata(&connection).await?;
 
-        let signer = create_signer(&self.extrinsic_cli_opts.suri)?;
+        let quiet = self.extrinsic_cli_opts.quiet()?;
+        let ss58_prefix = self.extrinsic_cli_opts.ss58_prefix(&connection).await?;
+        let address_book = self.extrinsic_cli_opts.address_book()?;
+
+        if let Some(origin) = &self.origin {
+            origin.note_ss58_prefix_mismatch(ss58_prefix);
+        }
+        let (signer, origin) = self
+            .external_signer
+            .signer_and_origin(&self.extrinsic_cli_opts, self.origin.as_ref())?;
+        // The metadata file is either the only artifact of the code instantiated by its
+        // hash, or complements a raw code file.
+        let (file, metadata) = match (&self.extrinsic_cli_opts.file, &self.metadata) {
+            (Some(file), metadata) => (Some(file.clone()), metadata.clone()),
+            (None, Some(metadata)) if self.code_hash.is_some() => {
+                (Some(metadata.clone()), None)
+            }
+            (None, Some(_)) => {
+                return Err(anyhow!(
+                    "`--metadata` requires `--code-hash` or a raw code file given with \
+                     `--file`"
+                )
+                .into())
+            }
+            (None, None) => (None, None),
+        };
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
-            .file(self.extrinsic_cli_opts.file.clone())
+            .origin(origin.clone())
+            .file(file)
+            .metadata(metadata)
+            .expected_hash(self.extrinsic_cli_opts.expected_hash.map(|hash| hash.0))
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
+            .require_signed_artifacts(self.extrinsic_cli_opts.require_signed_artifacts)
             .url(self.extrinsic_cli_opts.url.clone())
+            .ss58_prefix(ss58_prefix)
+            .verbosity(
+                self.extrinsic_cli_opts
+                    .extrinsic_verbosity(self.output_json())?,
+            )
             .storage_deposit_limit(
                 self.extrinsic_cli_opts
                     .storage_deposit_limit
-                    .clone()
-                    .map(|bv| bv.denominate_balance(&token_metadata))
+                    .as_ref()
+                    .map(|bv| denominate_balance(bv, &token_metadata))
                     .transpose()?,
             )
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .submit_retries(self.extrinsic_cli_opts.submit_retries)
+            .timeout(self.extrinsic_cli_opts.timeout())
+            .timings(self.extrinsic_cli_opts.recorder().clone())
+            .sudo(self.sudo)
+            .max_fee(self.extrinsic_cli_opts.max_fee(&token_metadata)?)
+            .progress(self.extrinsic_cli_opts.progress(self.output_json()))
+            .tx_status(self.extrinsic_cli_opts.tx_status(self.output_json())?)
+            .raw_values(self.extrinsic_cli_opts.raw_values)
+            .args_hex_files(self.args_hex_files)
+            .include_raw(self.extrinsic_cli_opts.include_raw)
+            .additional_metadata(
+                self.additional_metadata
+                    .load(ss58_prefix, self.extrinsic_cli_opts.raw_values)?,
+            )
+            .skip_compat_check(self.extrinsic_cli_opts.skip_compat_check)
+            .skip_wasm_validation(self.extrinsic_cli_opts.skip_wasm_validation)
+            .token_metadata(Some(token_metadata.clone()))
             .done();
-        let instantiate_exec: InstantiateExec<
-            DefaultConfig,
-            DefaultEnvironment,
-            Keypair,
-        > = InstantiateCommandBuilder::new(extrinsic_opts)
-            .constructor(self.constructor.clone())
-            .args(self.args.clone())
-            .value(self.value.denominate_balance(&token_metadata)?)
-            .gas_limit(self.gas_limit)
-            .proof_size(self.proof_size)
-            .salt(self.salt.clone())
-            .done()
-            .await?;
+        let mut instantiate_exec: InstantiateExec<C, E, Keypair> =
+            InstantiateCommandBuilder::new(extrinsic_opts)
+                .constructor(self.constructor.clone())
+                .fuzzy_match(self.fuzzy_match)
+                .args(self.args.clone())
+                .value(denominate_balance(&self.value, &token_metadata)?)
+                .gas_limit(self.gas_limit())
+                .proof_size(self.proof_size())
+                .salt(self.salt.clone())
+                .code_hash(self.code_hash)
+                .connection(Some(connection))
+                .done()
+                .await?;
+        print_fuzzy_match(
+            "constructor",
+            &self.constructor,
+            instantiate_exec.args().constructor(),
+            self.output_json(),
+        );
+        let paid_unpayable = PaidUnpayable::check(
+            instantiate_exec.transcoder(),
+            "constructor",
+            instantiate_exec.args().constructor(),
+            instantiate_exec.args().value() != E::Balance::default(),
+        );
+        if let Some(paid_unpayable) = &paid_unpayable {
+            paid_unpayable
+                .confirm(self.output_json(), self.extrinsic_cli_opts.skip_confirm)?;
+        }
+        if self.idempotent {
+            if let Some(contract) = instantiate_exec.existing_contract().await? {
+                let contract = ss58_prefix.address(&contract);
+                display_existing_contract(
+                    contract,
+                    self.output_json(),
+                    quiet,
+                    self.extrinsic_cli_opts.timings(),
+                    self.extrinsic_cli_opts.compact_json(),
+                )?;
+                return Ok(())
+            }
+        }
 
         if !self.extrinsic_cli_opts.execute {
+            if instantiate_exec.opts().progress() {
+                Progress::DryRunning {
+                    entity: instantiate_exec.args().constructor().to_string(),
+                }
+                .report();
+            }
             let result = instantiate_exec.instantiate_dry_run().await?;
-            match instantiate_exec.decode_instantiate_dry_run(&result).await {
-                Ok(dry_run_result) => {
+            let debug_message_file = self.debug_message.write(&result.debug_message)?;
+            match instantiate_exec
+                .decode_instantiate_dry_run(&result, Some(&token_metadata))
+                .await
+            {
+                Ok(mut dry_run_result) => {
+                    dry_run_result.events = dry_run_result
+                        .events
+                        .map(|events| events.with_aliases(&address_book));
+                    if let Some(file) = debug_message_file {
+                        dry_run_result.debug_message = None;
+                        dry_run_result.debug_message_file = Some(file.to_path_buf());
+                    }
                     if self.output_json() {
-                        println!("{}", dry_run_result.to_json()?);
+                        println!("{}", self.extrinsic_cli_opts.to_json(&dry_run_result)?);
+                    } else if quiet {
+                        if let Some(error) = &dry_run_result.error {
+                            eprintln!(
+                                "{} The constructor reverted: {error}",
+                                "Warning:".yellow().bold()
+                            );
+                        }
+                        warn_block_usage(dry_run_result.block_usage.as_ref());
+                        println!("{}", dry_run_result.contract);
                     } else {
-                        print_instantiate_dry_run_result(&dry_run_result);
-                        display_contract_exec_result_debug::<_, DEFAULT_KEY_COL_WIDTH>(
-                            &result,
+                        print_instantiate_dry_run_result(
+                            &dry_run_result,
+                            self.extrinsic_cli_opts.verbosity()?,
+                        );
+                        display_contract_exec_result_debug::<
+                            _,
+                            _,
+                            _,
+                            DEFAULT_KEY_COL_WIDTH,
+                        >(&result, debug_message_file)?;
+                        display_dry_run_events::<E>(
+                            dry_run_result.events.as_ref(),
+                            self.extrinsic_cli_opts.verbosity()?,
+                            self.extrinsic_cli_opts.event_display(),
+                            &token_metadata,
                         )?;
                         display_dry_run_result_warning("instantiate");
                     }
                     Ok(())
                 }
                 Err(object) => {
-                    if self.output_json() {
+                    let object = match &paid_unpayable {
+                        Some(paid_unpayable) => paid_unpayable.explain(object),
+                        None => object,
+                    };
+                    if self.output_json() || quiet {
                         return Err(object)
                     } else {
+                        if let Some(origin) = &origin {
+                            name_value_println!(
+                                "Origin",
+                                ss58_prefix.format_account(origin),
+                                MAX_KEY_COL_WIDTH
+                            );
+                        }
                         name_value_println!("Result", object, MAX_KEY_COL_WIDTH);
-                        display_contract_exec_result::<_, MAX_KEY_COL_WIDTH>(&result)?;
+                        display_contract_exec_result::<_, _, _, MAX_KEY_COL_WIDTH>(
+                            &result,
+                            debug_message_file,
+                            Some(&token_metadata),
+                        )?;
                     }
                     Err(object)
                 }
             }
         } else {
             tracing::debug!("instantiate data {:?}", instantiate_exec.args().data());
-            let gas_limit = pre_submit_dry_run_gas_estimate_instantiate(
-                &instantiate_exec,
-                self.output_json(),
-                self.extrinsic_cli_opts.skip_dry_run,
-            )
-            .await?;
+            let (gas_limit, storage_deposit) =
+                pre_submit_dry_run_gas_estimate_instantiate(
+                    &mut instantiate_exec,
+                    self.output_json(),
+                    quiet,
+                    self.extrinsic_cli_opts.skip_dry_run,
+                    self.extrinsic_cli_opts.storage_deposit_buffer,
+                    &self.debug_message,
+                )
+                .await
+                .map_err(|err| {
+                    match &paid_unpayable {
+                        Some(paid_unpayable) => paid_unpayable.explain(err),
+                        None => err,
+                    }
+                })?;
+            let storage_deposit_limit = self
+                .extrinsic_cli_opts
+                .storage_deposit_buffer
+                .and(instantiate_exec.args().storage_deposit_limit());
             if !self.extrinsic_cli_opts.skip_confirm {
+                let verbosity = self.extrinsic_cli_opts.verbosity()?;
+                let costs = TxCosts::new(
+                    instantiate_exec.args().value(),
+                    instantiate_exec.estimate_fee(gas_limit).await?,
+                    storage_deposit.as_ref(),
+                    &token_metadata,
+                );
+                let deposit_limit = storage_deposit_limit
+                    .map(|limit| DisplayBalance::new(limit, Some(&token_metadata)));
                 prompt_confirm_tx(|| {
-                    print_default_instantiate_preview(&instantiate_exec, gas_limit);
+                    print_default_instantiate_preview(
+                        &instantiate_exec,
+                        gas_limit,
+                        verbosity,
+                    );
+                    if let Some(weight) = &self.weight {
+                        name_value_println!(
+                            "Weight",
+                            weight.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    costs.print();
+                    if let Some(limit) = &deposit_limit {
+                        name_value_println!(
+                            "Deposit limit",
+                            limit.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
                     if let Code::Existing(code_hash) =
                         instantiate_exec.args().code().clone()
                     {
@@ -178,16 +482,48 @@ impl InstantiateCommand {
                             DEFAULT_KEY_COL_WIDTH
                         );
                     }
+                    name_value_println!(
+                        "Mortality",
+                        self.extrinsic_cli_opts.mortality.describe(),
+                        DEFAULT_KEY_COL_WIDTH
+                    );
                 })?;
             }
-            let instantiate_result =
-                instantiate_exec.instantiate(Some(gas_limit)).await?;
+            let instantiate_result = match self.external_signer.account_id() {
+                Some(account_id) => {
+                    let unsigned = instantiate_exec
+                        .build_partial_extrinsic(Some(gas_limit), account_id)
+                        .await?;
+                    let signed = ExternalSignerOpts::sign(&unsigned, self.output_json())?;
+                    instantiate_exec.submit_signed(signed).await?
+                }
+                None => instantiate_exec.instantiate(Some(gas_limit)).await?,
+            };
+            let contract = instantiate_exec
+                .opts()
+                .ss58_prefix()
+                .format_account(&instantiate_result.contract_address);
+            let explorer_url = self
+                .extrinsic_cli_opts
+                .explorer_url(
+                    instantiate_exec.client(),
+                    &instantiate_result.events,
+                    Some(contract),
+                )
+                .await?;
             display_result(
                 &instantiate_exec,
                 instantiate_result,
+                gas_limit,
+                storage_deposit_limit,
                 &token_metadata,
+                &address_book,
                 self.output_json(),
                 self.extrinsic_cli_opts.verbosity().unwrap(),
+                self.extrinsic_cli_opts.event_display(),
+                explorer_url,
+                self.extrinsic_cli_opts.timings(),
+                self.extrinsic_cli_opts.compact_json(),
             )
             .await?;
             Ok(())
@@ -195,30 +531,67 @@ impl InstantiateCommand {
     }
 }
 
-/// A helper function to estimate the gas required for a contract instantiation.
-async fn pre_submit_dry_run_gas_estimate_instantiate(
-    instantiate_exec: &InstantiateExec<DefaultConfig, DefaultEnvironment, Keypair>,
+/// A helper function to estimate the gas required for a contract instantiation,
+/// returned along with the storage deposit of the dry-run unless it is skipped.
+///
+/// If a `storage_deposit_buffer` is given, the storage deposit charged in the dry-run,
+/// increased by the buffer percentage, is set as the storage deposit limit of the
+/// instantiation.
+async fn pre_submit_dry_run_gas_estimate_instantiate<C, E>(
+    instantiate_exec: &mut InstantiateExec<C, E, Keypair>,
     output_json: bool,
+    quiet: bool,
     skip_dry_run: bool,
-) -> Result<Weight> {
+    storage_deposit_buffer: Option<u64>,
+    debug_message: &DebugMessageOpts,
+) -> Result<(Weight, Option<StorageDeposit<E::Balance>>), ErrorVariant>
+where
+    C: ExtrinsicConfig,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams: Default + MortalityParams<C>,
+    E: Environment,
+    E::Balance: ExtrinsicBalance,
+{
     if skip_dry_run {
         return match (instantiate_exec.args().gas_limit(), instantiate_exec.args().proof_size()) {
-                (Some(ref_time), Some(proof_size)) => Ok(Weight::from_parts(ref_time, proof_size)),
+                (Some(ref_time), Some(proof_size)) => Ok((Weight::from_parts(ref_time, proof_size), None)),
                 _ => {
                     Err(anyhow!(
-                        "Weight args `--gas` and `--proof-size` required if `--skip-dry-run` specified"
-                    ))
+                    "Weight args `--gas` and `--proof-size`, or `--weight`, required if `--skip-dry-run` specified"
+                )
+                .into())
                 }
             };
     }
-    if !output_json {
+    if instantiate_exec.opts().progress() {
+        Progress::DryRunning {
+            entity: instantiate_exec.args().constructor().to_string(),
+        }
+        .report();
+    } else if !output_json && !quiet {
         print_dry_running_status(instantiate_exec.args().constructor());
     }
     let instantiate_result = instantiate_exec.instantiate_dry_run().await?;
     match instantiate_result.result {
         Ok(_) => {
-            if !output_json {
-                print_gas_required_success(instantiate_result.gas_required);
+            if instantiate_exec.opts().progress() {
+                Progress::GasEstimated {
+                    gas_required: instantiate_result.gas_required,
+                }
+                .report();
+            } else if !output_json && !quiet {
+                print_gas_required_success(
+                    instantiate_result.gas_required,
+                    block_usage(
+                        instantiate_exec.client(),
+                        instantiate_result.gas_required,
+                    ),
+                );
+            }
+            if let Some(buffer) = storage_deposit_buffer {
+                let limit = instantiate_result
+                    .storage_deposit
+                    .limit_with_buffer(buffer)?;
+                instantiate_exec.set_storage_deposit_limit(Some(limit));
             }
             // use user specified values where provided, otherwise use the estimates
             let ref_time = instantiate_exec
@@ -229,68 +602,157 @@ async fn pre_submit_dry_run_gas_estimate_instantiate(
                 .args()
                 .proof_size()
                 .unwrap_or_else(|| instantiate_result.gas_required.proof_size());
-            Ok(Weight::from_parts(ref_time, proof_size))
+            Ok((
+                Weight::from_parts(ref_time, proof_size),
+                Some(instantiate_result.storage_deposit.clone()),
+            ))
         }
         Err(ref err) => {
             let object = ErrorVariant::from_dispatch_error(
                 err,
                 &instantiate_exec.client().metadata(),
             )?;
-            if output_json {
-                Err(anyhow!("{}", serde_json::to_string_pretty(&object)?))
-            } else {
-                name_value_println!("Result", object, MAX_KEY_COL_WIDTH);
-                display_contract_exec_result::<_, MAX_KEY_COL_WIDTH>(
-                    &instantiate_result,
-                )?;
-
-                Err(anyhow!("Pre-submission dry-run failed. Use --skip-dry-run to skip this step."))
-            }
+            let object = instantiate_exec.explain_duplicate(object).await;
+            Err(pre_submit_dry_run_error(
+                object,
+                &instantiate_result,
+                output_json,
+                quiet,
+                debug_message,
+                instantiate_exec.opts().token_metadata(),
+            ))
         }
     }
 }
 
 /// Displays the results of contract instantiation, including contract address,
-/// events, and optional code hash.
-pub async fn display_result(
-    instantiate_exec: &InstantiateExec<DefaultConfig, DefaultEnvironment, Keypair>,
-    instantiate_exec_result: InstantiateExecResult<DefaultConfig>,
+/// events, optional code hash, and the weight used and deposit refunded.
+#[allow(clippy::too_many_arguments)]
+pub async fn display_result<C, E>(
+    instantiate_exec: &InstantiateExec<C, E, Keypair>,
+    instantiate_exec_result: InstantiateExecResult<C>,
+    gas_limit: Weight,
+    storage_deposit_limit: Option<E::Balance>,
     token_metadata: &TokenMetadata,
+    address_book: &AddressBook,
     output_json: bool,
     verbosity: Verbosity,
-) -> Result<(), ErrorVariant> {
-    let events = DisplayEvents::from_events::<DefaultConfig, DefaultEnvironment>(
+    event_display: EventDisplay,
+    explorer_url: Option<String>,
+    timings: Option<TimingsReport>,
+    compact_json: bool,
+) -> Result<(), ErrorVariant>
+where
+    C: ExtrinsicConfig,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams: Default + MortalityParams<C>,
+    E: Environment,
+    E::Balance: ExtrinsicBalance,
+{
+    let events = DisplayEvents::from_events::<C, E>(
         &instantiate_exec_result.events,
         Some(instantiate_exec.transcoder()),
+        instantiate_exec.opts().additional_metadata(),
         &instantiate_exec.client().metadata(),
+        instantiate_exec.opts().ss58_prefix(),
+        instantiate_exec.opts().raw_values(),
+        instantiate_exec.opts().include_raw(),
+        Some(token_metadata),
+    )?
+    .with_aliases(address_book);
+    let outcome = ExecutionOutcome::from_events::<C, E>(
+        &instantiate_exec_result.events,
+        &instantiate_exec.opts().origin(),
+        token_metadata,
     )?;
-    let contract_address = instantiate_exec_result.contract_address.to_string();
+    let contract_address = instantiate_exec
+        .opts()
+        .ss58_prefix()
+        .address(&instantiate_exec_result.contract_address);
     if output_json {
         let display_instantiate_result = InstantiateResult {
             code_hash: instantiate_exec_result
                 .code_hash
                 .map(|ch| format!("{ch:?}")),
             contract: Some(contract_address),
+            storage_deposit_limit: storage_deposit_limit
+                .map(|limit| DisplayBalance::new(limit, Some(token_metadata))),
             events,
+            existing: false,
+            wasm_validation: instantiate_exec.wasm_validation().cloned(),
+            gas_limit_submitted: Some(gas_limit),
+            weight_consumed: outcome.weight_used,
+            explorer_url,
+            outcome,
         };
-        println!("{}", display_instantiate_result.to_json()?)
+        println!(
+            "{}",
+            to_json(&display_instantiate_result, timings, compact_json)?
+        )
+    } else if verbosity == Verbosity::Quiet {
+        println!("{contract_address}");
     } else {
         println!(
             "{}",
-            events.display_events::<DefaultEnvironment>(verbosity, token_metadata)?
+            events.display_events::<E>(verbosity, event_display, token_metadata)?
         );
         if let Some(code_hash) = instantiate_exec_result.code_hash {
             name_value_println!("Code hash", format!("{code_hash:?}"));
         }
         name_value_println!("Contract", contract_address);
+        name_value_println!("Gas limit", gas_limit.to_string());
+        outcome.print(gas_limit);
+        if let Some(explorer_url) = explorer_url {
+            name_value_println!("Explorer", explorer_url);
+        }
     };
     Ok(())
 }
 
-pub fn print_default_instantiate_preview(
-    instantiate_exec: &InstantiateExec<DefaultConfig, DefaultEnvironment, Keypair>,
+/// Displays the address of a contract which already exists, instead of instantiating
+/// it again.
+fn display_existing_contract(
+    contract: Address,
+    output_json: bool,
+    quiet: bool,
+    timings: Option<TimingsReport>,
+    compact_json: bool,
+) -> Result<()> {
+    if output_json {
+        let result = InstantiateResult {
+            contract: Some(contract),
+            code_hash: None,
+            storage_deposit_limit: None,
+            events: Vec::new().into(),
+            existing: true,
+            wasm_validation: None,
+            gas_limit_submitted: None,
+            weight_consumed: None,
+            explorer_url: None,
+            outcome: Default::default(),
+        };
+        println!("{}", to_json(&result, timings, compact_json)?);
+    } else if quiet {
+        println!("{contract}");
+    } else {
+        name_value_println!("Contract", contract);
+        println!(
+            "The contract already exists, no new instance was created. It was \
+             instantiated before with the same code, constructor input and salt."
+        );
+    }
+    Ok(())
+}
+
+pub fn print_default_instantiate_preview<C, E>(
+    instantiate_exec: &InstantiateExec<C, E, Keypair>,
     gas_limit: Weight,
-) {
+    verbosity: Verbosity,
+) where
+    C: ExtrinsicConfig,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams: Default + MortalityParams<C>,
+    E: Environment,
+    E::Balance: ExtrinsicBalance,
+{
     name_value_println!(
         "Constructor",
         instantiate_exec.args().constructor(),
@@ -301,6 +763,18 @@ pub fn print_default_instantiate_preview(
         instantiate_exec.args().raw_args().join(" "),
         DEFAULT_KEY_COL_WIDTH
     );
+    if verbosity.is_verbose() {
+        name_value_println!(
+            "Data",
+            format!("0x{}", hex::encode(instantiate_exec.args().data())),
+            DEFAULT_KEY_COL_WIDTH
+        );
+        name_value_println!(
+            "Salt",
+            format!("0x{}", hex::encode(instantiate_exec.args().salt())),
+            DEFAULT_KEY_COL_WIDTH
+        );
+    }
     name_value_println!("Gas limit", gas_limit.to_string(), DEFAULT_KEY_COL_WIDTH);
 }
 
@@ -309,37 +783,83 @@ pub fn print_default_instantiate_preview(
 pub struct InstantiateResult {
     /// Instantiated contract hash
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub contract: Option<String>,
+    pub contract: Option<Address>,
     /// Instantiated code hash
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code_hash: Option<String>,
+    /// The storage deposit limit derived from the pre-submission dry-run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_deposit_limit: Option<DisplayBalance>,
     /// The events emitted from the instantiate extrinsic invocation.
     pub events: DisplayEvents,
-}
-
-impl InstantiateResult {
-    pub fn to_json(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
-    }
+    /// Set if no contract was instantiated because it already exists, see
+    /// `--idempotent`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub existing: bool,
+    /// The findings of the validation of the uploaded code, absent if skipped or if
+    /// existing code was instantiated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_validation: Option<WasmValidation>,
+    /// The gas limit the instantiation was submitted with, absent if no contract was
+    /// instantiated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit_submitted: Option<Weight>,
+    /// The weight the instantiation actually consumed, read from the dispatch info of
+    /// the `System::ExtrinsicSuccess` event, absent if the chain doesn't report it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight_consumed: Option<Weight>,
+    /// The url of the instantiation in a block explorer, if an explorer url template is
+    /// configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// The weight used and the storage deposit refunded by the instantiation
+    #[serde(flatten)]
+    pub outcome: ExecutionOutcome,
 }
 
 pub fn print_instantiate_dry_run_result(
-    result: &InstantiateDryRunResult<<DefaultEnvironment as Environment>::Balance>,
+    result: &InstantiateDryRunResult<DisplayBalance>,
+    verbosity: Verbosity,
 ) {
+    if let Some(origin) = &result.origin {
+        name_value_println!("Origin", origin, DEFAULT_KEY_COL_WIDTH);
+    }
     name_value_println!(
         "Result",
         format!("{}", result.result),
         DEFAULT_KEY_COL_WIDTH
     );
+    if let Some(decode_error) = &result.decode_error {
+        name_value_println!("Note", undecoded_note(decode_error), DEFAULT_KEY_COL_WIDTH);
+    }
+    if let Some(error) = &result.error {
+        name_value_println!(
+            "Error",
+            format!("{error}").bright_red().bold(),
+            DEFAULT_KEY_COL_WIDTH
+        );
+    }
     name_value_println!(
         "Reverted",
         format!("{:?}", result.reverted),
         DEFAULT_KEY_COL_WIDTH
     );
     name_value_println!("Contract", result.contract, DEFAULT_KEY_COL_WIDTH);
+    if verbosity.is_verbose() {
+        name_value_println!("Data", result.data, DEFAULT_KEY_COL_WIDTH);
+        name_value_println!("Salt", result.salt, DEFAULT_KEY_COL_WIDTH);
+    }
     name_value_println!(
         "Gas consumed",
         result.gas_consumed.to_string(),
         DEFAULT_KEY_COL_WIDTH
     );
+    name_value_println!(
+        "Storage deposit",
+        result.storage_deposit.to_string(),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    if let Some(block_usage) = &result.block_usage {
+        print_block_usage(block_usage);
+    }
 }
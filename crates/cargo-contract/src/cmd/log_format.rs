@@ -0,0 +1,128 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The format of the log output, see `--log-format`.
+
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+use tracing_subscriber::EnvFilter;
+
+/// Set once the log output is JSON.
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// The format of the log output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human readable, colored output.
+    #[default]
+    Plain,
+    /// One JSON object per line on stderr, for log aggregators. The status lines of
+    /// the commands are logged as `info` events and only the final result is printed
+    /// to stdout, without colors.
+    Json,
+}
+
+impl LogFormat {
+    /// Initializes the tracing subscriber for the log format.
+    ///
+    /// The level is taken from `RUST_LOG`. Without it, the `info` events of
+    /// `cargo-contract` are logged in JSON format, only errors otherwise.
+    pub fn init(self) {
+        match self {
+            Self::Plain => tracing_subscriber::fmt::init(),
+            Self::Json => {
+                JSON.store(true, Ordering::Relaxed);
+                colored::control::set_override(false);
+                let filter = if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+                    EnvFilter::from_default_env()
+                } else {
+                    EnvFilter::new("error,cargo_contract=info")
+                };
+                tracing_subscriber::fmt()
+                    .json()
+                    .with_ansi(false)
+                    .with_env_filter(filter)
+                    .with_writer(std::io::stderr)
+                    .init()
+            }
+        }
+    }
+}
+
+/// Returns whether the log output is JSON, in which case the status lines of the
+/// commands are logged as tracing events instead of being printed.
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::print_gas_required_success;
+    use sp_weights::Weight;
+    use std::{
+        io,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    /// A writer collecting the log output.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn status_lines_are_logged_as_json_events() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        JSON.store(true, Ordering::Relaxed);
+        tracing::subscriber::with_default(subscriber, || {
+            print_gas_required_success(Weight::from_parts(1_000, 10), None)
+        });
+        JSON.store(false, Ordering::Relaxed);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let event: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(
+            event["fields"],
+            serde_json::json!({
+                "message": "Gas required estimated",
+                "ref_time": 1_000,
+                "proof_size": 10,
+            })
+        );
+    }
+}
@@ -14,40 +14,68 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+pub mod assertion;
 pub mod build;
 pub mod call;
+pub mod completions;
+pub mod config;
+pub mod convert_metadata;
 pub mod decode;
+pub mod e2e;
 pub mod encode;
+pub mod explorer;
 pub mod info;
 pub mod instantiate;
+pub mod log_format;
+pub mod node;
+pub mod output_schema;
+pub mod profile;
 pub mod remove;
 pub mod rpc;
+pub mod run;
 pub mod schema;
+pub mod sign_bundle;
 pub mod storage;
+pub mod transfer;
 pub mod upload;
 pub mod verify;
+pub mod verify_bundle;
+pub mod watch;
 
 pub(crate) use self::{
     build::{
+        display_workspace_results,
         BuildCommand,
         CheckCommand,
     },
     call::CallCommand,
+    completions::CompletionsCommand,
+    convert_metadata::ConvertMetadataCommand,
     decode::DecodeCommand,
+    e2e::TestCommand,
     info::{
         ExtendedContractInfo,
         InfoCommand,
     },
     instantiate::InstantiateCommand,
+    log_format::LogFormat,
+    node::NodeCommand,
+    output_schema::OutputSchemaCommand,
+    profile::ConfigCommand,
     remove::RemoveCommand,
     rpc::RpcCommand,
+    run::RunCommand,
     schema::{
         GenerateSchemaCommand,
         VerifySchemaCommand,
     },
+    sign_bundle::SignBundleCommand,
     storage::StorageCommand,
+    transfer::TransferCommand,
     upload::UploadCommand,
     verify::VerifyCommand,
+    verify_bundle::VerifyBundleCommand,
+    watch::WatchCommand,
 };
 
 use crate::{
@@ -59,7 +87,16 @@ use anyhow::{
     Context,
     Result,
 };
+use clap::{
+    parser::ValueSource,
+    ArgMatches,
+};
 use colored::Colorize;
+use config::{
+    ChainConfig,
+    ExtrinsicBalance,
+    ExtrinsicConfig,
+};
 use contract_build::{
     name_value_println,
     Verbosity,
@@ -68,17 +105,69 @@ use contract_build::{
 };
 pub(crate) use contract_extrinsics::ErrorVariant;
 use contract_extrinsics::{
-    pallet_contracts_primitives::ContractResult,
+    deposit_refunded,
+    pallet_contracts_primitives::{
+        ContractResult,
+        StorageDeposit,
+    },
+    url_to_string,
+    weight_used,
+    AdditionalMetadata,
+    AddressBook,
     BalanceVariant,
+    BalanceWidth,
+    BlockUsage,
+    Connection,
+    ContractAddress,
+    ContractArtifacts,
+    DisplayBalance,
+    DisplayEvents,
+    EventDisplay,
+    Mortality,
+    Phase,
+    Ss58Prefix,
+    Timings,
+    TimingsReport,
+    TokenMetadata,
+    UnsignedExtrinsic,
+    DEFAULT_SUBMIT_RETRIES,
+};
+use contract_transcode::{
+    AccountId32,
+    ContractMessageTranscoder,
+    Value,
 };
 use core::fmt;
+use explorer::ExplorerUrlTemplate;
 use ink_env::{
     DefaultEnvironment,
     Environment,
 };
-use std::io::{
-    self,
-    Write,
+use profile::Profile;
+use scale::Decode;
+use serde::Serialize;
+use sp_weights::constants::{
+    WEIGHT_PROOF_SIZE_PER_KB,
+    WEIGHT_PROOF_SIZE_PER_MB,
+    WEIGHT_REF_TIME_PER_MILLIS,
+};
+use std::{
+    io::{
+        self,
+        IsTerminal,
+        Write,
+    },
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+use subxt::{
+    blocks::ExtrinsicEvents,
+    utils::{
+        MultiSignature,
+        H160,
+    },
+    OnlineClient,
 };
 pub use subxt::{
     Config,
@@ -89,31 +178,52 @@ use subxt_signer::{
     SecretUri,
 };
 
+/// The secret key URI of the development account which performs dry-runs if no
+/// `--suri` is given.
+const DRY_RUN_DEV_ACCOUNT: &str = "//Alice";
+
 /// Arguments required for creating and sending an extrinsic to a substrate node.
 #[derive(Clone, Debug, clap::Args)]
 pub struct CLIExtrinsicOpts {
-    /// Path to a contract build artifact file: a raw `.wasm` file, a `.contract` bundle,
-    /// or a `.json` metadata file.
+    /// Path to a contract build artifact file: a raw `.wasm` or `.polkavm` file, a
+    /// `.contract` bundle, or a `.json` metadata file. A `.contract` bundle can also be
+    /// downloaded from an `https://` URL, or read from stdin with `-`.
     #[clap(value_parser, conflicts_with = "manifest_path")]
     file: Option<PathBuf>,
+    /// The code hash the contract artifact is expected to have, as hex. Aborts if the
+    /// hash of the contract code differs, e.g. for a downloaded bundle.
+    #[clap(long, requires = "file", value_parser = parse_code_hash)]
+    expected_hash: Option<<DefaultConfig as Config>::Hash>,
     /// Path to the `Cargo.toml` of the contract.
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
-    /// Websockets url of a substrate node.
+    /// Websockets or HTTP url of a substrate node.
     #[clap(
         name = "url",
         long,
-        value_parser,
+        env = "CARGO_CONTRACT_URL",
+        value_parser = parse_node_url,
         default_value = "ws://localhost:9944"
     )]
     url: url::Url,
-    /// Secret key URI for the account deploying the contract.
+    /// Where the url of the node is taken from.
+    #[clap(skip)]
+    url_source: UrlSource,
+    /// Secret key URI for the account deploying the contract. Required unless the
+    /// selected profile has a `suri-file`, except for dry-runs of `call` and
+    /// `instantiate`, which are performed with the `//Alice` development account.
     ///
     /// e.g.
     /// - for a dev account "//Alice"
     /// - with a password "//Alice///SECRET_PASSWORD"
-    #[clap(name = "suri", long, short)]
-    suri: String,
+    #[clap(
+        name = "suri",
+        long,
+        short,
+        env = "CARGO_CONTRACT_SURI",
+        hide_env_values = true
+    )]
+    suri: Option<String>,
     #[clap(flatten)]
     verbosity: VerbosityFlags,
     /// Submit the extrinsic for on-chain execution.
@@ -124,12 +234,105 @@ pub struct CLIExtrinsicOpts {
     #[clap(long)]
     storage_deposit_limit:
         Option<BalanceVariant<<DefaultEnvironment as Environment>::Balance>>,
+    /// Use the storage deposit charged in the pre-submission dry-run, increased by the
+    /// given percentage, as the storage deposit limit of an executed call or
+    /// instantiation. A dry-run which refunds storage deposit results in a zero limit.
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        conflicts_with_all = ["storage_deposit_limit", "skip_dry_run"]
+    )]
+    storage_deposit_buffer: Option<u64>,
     /// Before submitting a transaction, do not dry-run it via RPC first.
     #[clap(long)]
     skip_dry_run: bool,
     /// Before submitting a transaction, do not ask the user for confirmation.
     #[clap(short('y'), long)]
     skip_confirm: bool,
+    /// The SS58 address prefix used to display account ids. If not specified, the
+    /// prefix reported by the chain is used.
+    #[clap(long)]
+    ss58_prefix: Option<Ss58Prefix>,
+    /// The configuration of the target chain. If not specified, it is detected from the
+    /// signed extensions in the metadata of the chain.
+    #[clap(long, value_enum)]
+    chain_config: Option<ChainConfig>,
+    /// The number of blocks the transaction is valid for, rounded to a power of two, or
+    /// `immortal` for a transaction which never expires.
+    #[clap(long, default_value_t)]
+    mortality: Mortality,
+    /// The number of times the transaction is resubmitted with a refreshed nonce if the
+    /// transaction pool rejects it because its nonce is outdated.
+    #[clap(long, default_value_t = DEFAULT_SUBMIT_RETRIES)]
+    submit_retries: u32,
+    /// Stop waiting for the transaction to be included in a block after this many
+    /// seconds, reporting its hash and last status. Defaults to waiting indefinitely,
+    /// or 300 seconds for nodes connected to via HTTP.
+    #[clap(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+    /// The maximum fee to pay for submitting the extrinsic. The extrinsic is not
+    /// submitted if its estimated fee exceeds it, even with `--skip-confirm`.
+    #[clap(long)]
+    max_fee: Option<BalanceVariant<<DefaultEnvironment as Environment>::Balance>>,
+    /// Do not print the status of submitted extrinsics to stderr, or progress messages
+    /// as JSON lines when exporting the output in JSON format.
+    #[clap(long)]
+    no_progress: bool,
+    /// Display account ids, hashes and weights in decoded results and events in their
+    /// structural representation instead of as SS58 addresses, hex and
+    /// `ref_time/proof_size`.
+    #[clap(long)]
+    raw_values: bool,
+    /// Include the SCALE encoded bytes of events as hex in the `--output-json` output,
+    /// and keep the events which can't be decoded instead of failing.
+    #[clap(long)]
+    include_raw: bool,
+    /// How to display events: `compact` prints one line per event with long values
+    /// elided, `full` prints one line per event field.
+    #[clap(long, value_enum, default_value_t)]
+    event_display: EventDisplayMode,
+    /// The number of levels of nested structures of event fields to expand with
+    /// `--event-display full`, deeper structures are displayed as `…`.
+    #[clap(long, value_name = "N")]
+    event_depth: Option<usize>,
+    /// Do not display the aliases of known account ids from the address book
+    /// `~/.config/cargo-contract/addressbook.toml` next to the addresses.
+    #[clap(long)]
+    no_aliases: bool,
+    /// A template of the url of the submitted extrinsic in a block explorer, printed
+    /// after a successful `--execute`, e.g.
+    /// `https://xyz.subscan.io/extrinsic/{extrinsic_hash}`. The placeholders
+    /// `{extrinsic_hash}`, `{block_hash}`, `{block_number}` and `{contract}` are
+    /// replaced.
+    #[clap(long, value_name = "TEMPLATE")]
+    explorer_url_template: Option<ExplorerUrlTemplate>,
+    /// Do not check whether the ink! version of the contract is supported by the
+    /// `pallet-contracts` version of the chain before uploading the contract code.
+    #[clap(long)]
+    skip_compat_check: bool,
+    /// Do not check the contract Wasm for floating point instructions and for imports
+    /// of host functions which the chain does not provide before uploading it.
+    #[clap(long)]
+    skip_wasm_validation: bool,
+    /// Refuse contract artifacts which are not a `.contract` bundle signed with
+    /// `cargo contract sign-bundle`, or whose signature is invalid. Without it, only an
+    /// invalid signature is reported as a warning.
+    #[clap(long)]
+    require_signed_artifacts: bool,
+    /// Report the duration of each phase of the command: connecting, querying the token
+    /// metadata, transcoding the arguments, dry-runs, signing, and waiting for the
+    /// transaction to be included in a block, or finalized if the node reports that
+    /// first. Printed as a table at the end, or as a `timings` object in the
+    /// `--output-json` output.
+    #[clap(long)]
+    timings: bool,
+    /// Print the `--output-json` output on a single line instead of pretty-printed, e.g.
+    /// for log lines. Has no effect without `--output-json`.
+    #[clap(long)]
+    compact_json: bool,
+    /// Records the durations of the phases, shared with the extrinsic options.
+    #[clap(skip)]
+    recorder: Timings,
 }
 
 impl CLIExtrinsicOpts {
@@ -137,54 +340,469 @@ impl CLIExtrinsicOpts {
     pub fn verbosity(&self) -> Result<Verbosity> {
         TryFrom::try_from(&self.verbosity)
     }
+
+    /// Returns the verbosity of the extrinsic options. Notices of the extrinsic options
+    /// are not printed at the default verbosity if the output is JSON, to keep stderr
+    /// machine readable.
+    pub fn extrinsic_verbosity(&self, output_json: bool) -> Result<Verbosity> {
+        let verbosity = self.verbosity()?;
+        if verbosity == Verbosity::Default && (output_json || log_format::is_json()) {
+            return Ok(Verbosity::Quiet)
+        }
+        Ok(verbosity)
+    }
+
+    /// Returns whether only the essential result value should be printed.
+    ///
+    /// Submitting an extrinsic in quiet mode requires `--skip-confirm`, since the
+    /// confirmation prompt would otherwise block scripts waiting for the result.
+    pub fn quiet(&self) -> Result<bool> {
+        let quiet = self.verbosity()? == Verbosity::Quiet;
+        if quiet && self.execute && !self.skip_confirm {
+            return Err(anyhow!(
+                "Cannot prompt for confirmation with --quiet, pass --skip-confirm to submit the transaction"
+            ))
+        }
+        Ok(quiet)
+    }
+
+    /// Returns whether to report progress as JSON lines on stderr, which is done only
+    /// when exporting the output in JSON format.
+    pub fn progress(&self, output_json: bool) -> bool {
+        output_json && !self.no_progress
+    }
+
+    /// Returns whether to print the status of submitted extrinsics as lines on stderr,
+    /// which is done at normal verbosity unless the output is JSON.
+    pub fn tx_status(&self, output_json: bool) -> Result<bool> {
+        Ok(!output_json
+            && !self.no_progress
+            && !log_format::is_json()
+            && self.verbosity()? != Verbosity::Quiet)
+    }
+
+    /// Returns the address book to resolve account ids to aliases with, which is empty
+    /// with `--no-aliases`.
+    pub fn address_book(&self) -> Result<AddressBook> {
+        if self.no_aliases {
+            return Ok(AddressBook::default());
+        }
+        profile::load_address_book()
+    }
+
+    /// Returns how to display the events of the extrinsic in human readable output.
+    pub fn event_display(&self) -> EventDisplay {
+        match self.event_display {
+            EventDisplayMode::Compact => EventDisplay::Compact,
+            EventDisplayMode::Full => EventDisplay::Full(self.event_depth),
+        }
+    }
+
+    /// Returns the url of the submitted extrinsic which emitted the `events` in a block
+    /// explorer, if an explorer url template is configured.
+    pub async fn explorer_url<C: Config>(
+        &self,
+        client: &OnlineClient<C>,
+        events: &ExtrinsicEvents<C>,
+        contract: Option<String>,
+    ) -> Result<Option<String>> {
+        match &self.explorer_url_template {
+            Some(template) => template.url(client, events, contract).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Returns how long to wait for the transaction to be included in a block.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// Returns the maximum fee to pay for submitting the extrinsic, denominated with the
+    /// `token_metadata`.
+    pub fn max_fee(&self, token_metadata: &TokenMetadata) -> Result<Option<u128>> {
+        self.max_fee
+            .as_ref()
+            .map(|max_fee| denominate_balance(max_fee, token_metadata))
+            .transpose()
+    }
+
+    /// Returns the secret key URI of the signer.
+    pub fn suri(&self) -> Result<&str> {
+        self.suri.as_deref().ok_or_else(|| {
+            anyhow!(
+                "No secret key URI given, pass --suri or select a profile with a `suri-file`"
+            )
+        })
+    }
+
+    /// Returns the signer of the command and the origin of its dry-runs, if it is not
+    /// the signer. Dry-runs are never signed, so the secret key URI is only required to
+    /// `--execute`: without it, the dry-runs are performed with the given `origin` or
+    /// else with a development account, which also stands in for the signer.
+    pub fn signer_and_origin(
+        &self,
+        origin: Option<&AccountIdArg>,
+    ) -> Result<(Keypair, Option<<DefaultConfig as Config>::AccountId>)> {
+        let origin = origin.map(|origin| origin.account_id().clone());
+        match &self.suri {
+            None if !self.execute => {
+                let signer = create_signer(DRY_RUN_DEV_ACCOUNT)?;
+                let origin =
+                    origin.unwrap_or_else(|| signer.public_key().to_account_id());
+                Ok((signer, Some(origin)))
+            }
+            _ => Ok((create_signer(self.suri()?)?, origin)),
+        }
+    }
+
+    /// Applies the settings of the `profile` to the options which were not given
+    /// explicitly on the command line or via an environment variable.
+    pub fn apply_profile(
+        &mut self,
+        profile: &Profile,
+        matches: &ArgMatches,
+    ) -> Result<()> {
+        let is_explicit = |id: &str| {
+            matches!(
+                matches.value_source(id),
+                Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+            )
+        };
+        if let Some(url) = profile.url.as_ref().filter(|_| !is_explicit("url")) {
+            check_url_scheme(&url.value)
+                .with_context(|| format!("Invalid url in {}", url.source.display()))?;
+            self.url = url.value.clone();
+            self.url_source = UrlSource::Config(url.source.clone());
+        }
+        if let Some(chain_config) = &profile.chain_config {
+            self.chain_config = self.chain_config.or(Some(chain_config.value));
+        }
+        if self.suri.is_none() {
+            self.suri = profile.read_suri()?;
+        }
+        if let Some(limit) = &profile.storage_deposit_limit {
+            if self.storage_deposit_limit.is_none()
+                && self.storage_deposit_buffer.is_none()
+            {
+                self.storage_deposit_limit =
+                    Some(profile::parse_storage_deposit_limit(&limit.value)?);
+            }
+        }
+        if let Some(skip_confirm) = &profile.skip_confirm {
+            self.skip_confirm |= skip_confirm.value;
+        }
+        if let Some(raw_values) = &profile.raw_values {
+            self.raw_values |= raw_values.value;
+        }
+        if let Some(ss58_prefix) = &profile.ss58_prefix {
+            self.ss58_prefix = self.ss58_prefix.or(Some(ss58_prefix.value));
+        }
+        if self.explorer_url_template.is_none() {
+            self.explorer_url_template = profile
+                .explorer_url_template
+                .as_ref()
+                .map(|template| template.value.clone());
+        }
+        Ok(())
+    }
+
+    /// Records whether the url of the node was given on the command line or via an
+    /// environment variable, as opposed to its default value.
+    pub fn set_url_source(&mut self, matches: &ArgMatches) {
+        self.url_source = match matches.value_source("url") {
+            Some(ValueSource::CommandLine) => UrlSource::Flag,
+            Some(ValueSource::EnvVariable) => UrlSource::Env,
+            _ => UrlSource::Default,
+        };
+    }
+
+    /// Prints the url of the node and where it is taken from in verbose mode.
+    pub fn report_url(&self) -> Result<()> {
+        if log_format::is_json() {
+            tracing::info!(
+                url = %profile::mask_password(&self.url),
+                source = %self.url_source,
+                "Using node"
+            );
+        } else if self.verbosity()?.is_verbose() {
+            eprintln!(
+                "{} Using node {} (from {})",
+                "Info:".cyan().bold(),
+                profile::mask_password(&self.url),
+                self.url_source
+            );
+        }
+        Ok(())
+    }
+
+    /// Connects to the node, the connection is shared by all queries, dry-runs and
+    /// submissions of the command.
+    pub async fn connect(&self) -> Result<Connection<DefaultConfig>> {
+        self.connect_to(&self.url).await
+    }
+
+    /// Connects to the node at the `url` instead of the node of the command, e.g. to
+    /// the chain of the contract of a call sent via XCM.
+    pub async fn connect_to(&self, url: &url::Url) -> Result<Connection<DefaultConfig>> {
+        let url = url_to_string(url);
+        let connect = Connection::connect(&url);
+        self.recorder.measure(Phase::Connect, connect).await
+    }
+
+    /// Queries the token metadata of the chain through the `connection`.
+    pub async fn token_metadata<C: Config>(
+        &self,
+        connection: &Connection<C>,
+    ) -> Result<TokenMetadata> {
+        let query = TokenMetadata::query_with(connection.rpc());
+        self.recorder.measure(Phase::TokenMetadata, query).await
+    }
+
+    /// Returns the recorder of the durations of the phases of the command, to be passed
+    /// to the extrinsic options.
+    pub fn recorder(&self) -> &Timings {
+        &self.recorder
+    }
+
+    /// Returns the durations of the phases of the command with `--timings`.
+    pub fn timings(&self) -> Option<TimingsReport> {
+        self.timings.then(|| self.recorder.report())
+    }
+
+    /// Prints the durations of the phases of the command with `--timings`, unless they
+    /// are part of the JSON output.
+    pub fn print_timings(&self, output_json: bool) {
+        if let Some(timings) = self.timings().filter(|_| !output_json) {
+            print_timings(&timings);
+        }
+    }
+
+    /// Serializes the `--output-json` output of the command, see [`to_json`].
+    pub fn to_json<T: Serialize>(&self, output: &T) -> Result<String> {
+        to_json(output, self.timings(), self.compact_json)
+    }
+
+    /// Serializes the `--output-json` output of the command without the `timings`, for
+    /// outputs which are not an object, see [`serialize_json`].
+    pub fn to_json_without_timings<T: Serialize>(&self, output: &T) -> Result<String> {
+        serialize_json(output, self.compact_json)
+    }
+
+    /// Whether `--output-json` is printed on a single line.
+    pub fn compact_json(&self) -> bool {
+        self.compact_json
+    }
+
+    /// Returns the SS58 address prefix to display account ids with, querying it through
+    /// the `connection` if not specified.
+    pub async fn ss58_prefix<C: Config>(
+        &self,
+        connection: &Connection<C>,
+    ) -> Result<Ss58Prefix> {
+        match self.ss58_prefix {
+            Some(ss58_prefix) => Ok(ss58_prefix),
+            None => Ss58Prefix::query_with(connection.rpc()).await,
+        }
+    }
+
+    /// Returns the configuration of the target chain, detecting it from the metadata of
+    /// the `connection` if not specified.
+    pub fn chain_config(&self, connection: &Connection<DefaultConfig>) -> ChainConfig {
+        self.chain_config.unwrap_or_else(|| {
+            ChainConfig::from_metadata(&connection.client().metadata())
+        })
+    }
+}
+
+/// How events are displayed in human readable output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EventDisplayMode {
+    /// One line per event, with long values elided.
+    Compact,
+    /// One line per event field.
+    #[default]
+    Full,
+}
+
+/// Where the url of the node is taken from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum UrlSource {
+    /// The `--url` flag.
+    Flag,
+    /// The `CARGO_CONTRACT_URL` environment variable.
+    Env,
+    /// The selected profile of the given config file.
+    Config(PathBuf),
+    /// The default url.
+    #[default]
+    Default,
+}
+
+impl fmt::Display for UrlSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "--url"),
+            Self::Env => write!(f, "CARGO_CONTRACT_URL"),
+            Self::Config(path) => write!(f, "{}", path.display()),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// Parses the url of a node, which must use the `ws`, `wss`, `http` or `https` scheme.
+pub fn parse_node_url(input: &str) -> Result<url::Url> {
+    let url = url::Url::parse(input)?;
+    check_url_scheme(&url)?;
+    Ok(url)
+}
+
+/// Returns an error if the `url` of a node doesn't use the `ws`, `wss`, `http` or
+/// `https` scheme.
+fn check_url_scheme(url: &url::Url) -> Result<()> {
+    match url.scheme() {
+        "ws" | "wss" | "http" | "https" => Ok(()),
+        scheme => {
+            Err(anyhow!(
+            "Unsupported url scheme `{scheme}`, expected `ws`, `wss`, `http` or `https`"
+        ))
+        }
+    }
+}
+
+/// Returns the SS58 address prefix specified by the user, otherwise queries the prefix
+/// of the chain.
+pub async fn resolve_ss58_prefix(
+    ss58_prefix: Option<Ss58Prefix>,
+    url: &url::Url,
+) -> Result<Ss58Prefix> {
+    match ss58_prefix {
+        Some(ss58_prefix) => Ok(ss58_prefix),
+        None => Ss58Prefix::query::<DefaultConfig>(url).await,
+    }
 }
 
 const STORAGE_DEPOSIT_KEY: &str = "Storage Total Deposit";
 pub const MAX_KEY_COL_WIDTH: usize = STORAGE_DEPOSIT_KEY.len() + 1;
 
-/// Print to stdout the fields of the result of a `instantiate` or `call` dry-run via RPC.
-pub fn display_contract_exec_result<R, const WIDTH: usize>(
-    result: &ContractResult<R, <DefaultEnvironment as Environment>::Balance, ()>,
+/// Print to stdout the fields of the result of a `instantiate` or `call` dry-run via RPC,
+/// with the storage deposit denominated with the `token_metadata` if given.
+pub fn display_contract_exec_result<
+    R,
+    Balance: Copy + Into<u128>,
+    EventRecord,
+    const WIDTH: usize,
+>(
+    result: &ContractResult<R, Balance, EventRecord>,
+    debug_message_file: Option<&Path>,
+    token_metadata: Option<&TokenMetadata>,
 ) -> Result<()> {
-    let mut debug_message_lines = std::str::from_utf8(&result.debug_message)
-        .context("Error decoding UTF8 debug message bytes")?
-        .lines();
     name_value_println!("Gas Consumed", format!("{:?}", result.gas_consumed), WIDTH);
     name_value_println!("Gas Required", format!("{:?}", result.gas_required), WIDTH);
     name_value_println!(
         STORAGE_DEPOSIT_KEY,
-        format!("{:?}", result.storage_deposit),
+        result.storage_deposit.display(token_metadata).to_string(),
         WIDTH
     );
+    display_contract_exec_result_debug::<_, _, _, WIDTH>(result, debug_message_file)
+}
+
+/// Print to stdout the debug buffer of a dry-run, only a preview of it if it was written
+/// to the `debug_message_file`.
+pub fn display_contract_exec_result_debug<R, Balance, EventRecord, const WIDTH: usize>(
+    result: &ContractResult<R, Balance, EventRecord>,
+    debug_message_file: Option<&Path>,
+) -> Result<()> {
+    let preview = match debug_message_file {
+        Some(_) => DebugMessageOpts::preview(&result.debug_message),
+        None => String::from_utf8_lossy(&result.debug_message).into_owned(),
+    };
 
     // print debug messages aligned, only first line has key
+    let mut debug_message_lines = preview.lines();
     if let Some(debug_message) = debug_message_lines.next() {
         name_value_println!("Debug Message", format!("{debug_message}"), WIDTH);
     }
-
     for debug_message in debug_message_lines {
         name_value_println!("", format!("{debug_message}"), WIDTH);
     }
+    if let Some(file) = debug_message_file {
+        name_value_println!(
+            "",
+            format!(
+                "(the full debug message of {} bytes is in {})",
+                result.debug_message.len(),
+                file.display()
+            ),
+            WIDTH
+        );
+    }
     Ok(())
 }
 
-pub fn display_contract_exec_result_debug<R, const WIDTH: usize>(
-    result: &ContractResult<R, <DefaultEnvironment as Environment>::Balance, ()>,
-) -> Result<()> {
-    let mut debug_message_lines = std::str::from_utf8(&result.debug_message)
-        .context("Error decoding UTF8 debug message bytes")?
-        .lines();
-    if let Some(debug_message) = debug_message_lines.next() {
-        name_value_println!("Debug Message", format!("{debug_message}"), WIDTH);
+/// Print to stdout the events emitted during a dry-run, if the runtime returned any.
+pub fn display_dry_run_events<E: Environment>(
+    events: Option<&DisplayEvents>,
+    verbosity: Verbosity,
+    event_display: EventDisplay,
+    token_metadata: &TokenMetadata,
+) -> Result<()>
+where
+    E::Balance: fmt::Display + TryFrom<u128>,
+{
+    if let Some(events) = events.filter(|events| !events.is_empty()) {
+        print!(
+            "{}",
+            events.display_events::<E>(verbosity, event_display, token_metadata)?
+        );
     }
+    Ok(())
+}
 
-    for debug_message in debug_message_lines {
-        name_value_println!("", format!("{debug_message}"), WIDTH);
+/// Returns the error of a failed pre-submission dry-run, after displaying the result of
+/// the dry-run unless the output is JSON or quiet.
+///
+/// The error is returned as it is in all output modes, so that it is reported in the
+/// same shape by all extrinsic commands.
+pub fn pre_submit_dry_run_error<R, Balance: Copy + Into<u128>, EventRecord>(
+    error: ErrorVariant,
+    result: &ContractResult<R, Balance, EventRecord>,
+    output_json: bool,
+    quiet: bool,
+    debug_message: &DebugMessageOpts,
+    token_metadata: Option<&TokenMetadata>,
+) -> ErrorVariant {
+    let debug_message_file = match debug_message.write(&result.debug_message) {
+        Ok(file) => file,
+        Err(err) => return err.into(),
+    };
+    if output_json || quiet {
+        return error
     }
-    Ok(())
+    name_value_println!("Result", error, MAX_KEY_COL_WIDTH);
+    if let Err(err) = display_contract_exec_result::<_, _, _, MAX_KEY_COL_WIDTH>(
+        result,
+        debug_message_file,
+        token_metadata,
+    ) {
+        return err.into()
+    }
+    println!("Pre-submission dry-run failed. Use --skip-dry-run to skip this step.");
+    error
+}
+
+/// Explains that the return data is shown as hex since it could not be decoded.
+pub fn undecoded_note(decode_error: &str) -> String {
+    format!("Failed to decode the returned data, showing it as hex: {decode_error}")
 }
 
 pub fn display_dry_run_result_warning(command: &str) {
+    if log_format::is_json() {
+        tracing::info!(
+            command,
+            "The {command} call has not been executed, add -x/--execute to submit it"
+        );
+        return
+    }
     println!("Your {} call {} been executed.", command, "has not".bold());
     println!(
             "To submit the transaction and execute the call on chain, add {} flag to the command.",
@@ -192,6 +810,61 @@ pub fn display_dry_run_result_warning(command: &str) {
         );
 }
 
+/// Serializes the `--output-json` output of an extrinsic command, adding the `timings`
+/// of its phases as an object next to the fields of the `output`.
+pub fn to_json<T: Serialize>(
+    output: &T,
+    timings: Option<TimingsReport>,
+    compact: bool,
+) -> Result<String> {
+    #[derive(Serialize)]
+    struct WithTimings<'a, T> {
+        #[serde(flatten)]
+        output: &'a T,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timings: Option<TimingsReport>,
+    }
+    serialize_json(&WithTimings { output, timings }, compact)
+}
+
+/// Serializes the `output` as pretty-printed JSON, or on a single line if `compact`.
+///
+/// The fields of objects are serialized in the order of their declaration, which is
+/// listed by `x-field-order` in the `schema` of the output.
+pub fn serialize_json<T: Serialize>(output: &T, compact: bool) -> Result<String> {
+    let json = if compact {
+        serde_json::to_string(output)?
+    } else {
+        serde_json::to_string_pretty(output)?
+    };
+    Ok(json)
+}
+
+/// Prints a notice that the constructor or message `name` was matched fuzzily to the
+/// one with the `label`, unless the output is JSON.
+pub fn print_fuzzy_match(entry_point: &str, name: &str, label: &str, output_json: bool) {
+    if name != label && !output_json && !log_format::is_json() {
+        eprintln!(
+            "{} Using the {entry_point} `{label}` for `{name}`",
+            "Note:".cyan().bold(),
+        );
+    }
+}
+
+/// Prints the durations of the phases of a command as a table.
+pub fn print_timings(timings: &TimingsReport) {
+    name_value_println!("Timings", "", DEFAULT_KEY_COL_WIDTH);
+    for (phase, duration) in &timings.phases {
+        let label = phase.label();
+        name_value_println!(label, format!("{duration}ms"), DEFAULT_KEY_COL_WIDTH);
+    }
+    name_value_println!(
+        "Total",
+        format!("{}ms", timings.total),
+        DEFAULT_KEY_COL_WIDTH
+    );
+}
+
 /// Prompt the user to confirm transaction submission.
 pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F) -> Result<()> {
     println!(
@@ -216,7 +889,189 @@ pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F) -> Result<()> {
     }
 }
 
+/// The costs of submitting an extrinsic, displayed in the confirmation prompt.
+pub struct TxCosts {
+    value: DisplayBalance,
+    fee: DisplayBalance,
+    storage_deposit: Option<StorageDeposit<DisplayBalance>>,
+}
+
+impl TxCosts {
+    /// Denominates the transferred `value`, the estimated `fee` and the storage deposit
+    /// of the pre-submission dry-run, absent if it was skipped, with the
+    /// `token_metadata`.
+    pub fn new<Balance: Copy + Into<u128>>(
+        value: Balance,
+        fee: u128,
+        storage_deposit: Option<&StorageDeposit<Balance>>,
+        token_metadata: &TokenMetadata,
+    ) -> Self {
+        Self {
+            value: DisplayBalance::new(value, Some(token_metadata)),
+            fee: DisplayBalance::new(fee, Some(token_metadata)),
+            storage_deposit: storage_deposit
+                .map(|deposit| deposit.display(Some(token_metadata))),
+        }
+    }
+
+    pub fn print(&self) {
+        name_value_println!("Value", self.value.to_string(), DEFAULT_KEY_COL_WIDTH);
+        name_value_println!("Fee estimate", self.fee.to_string(), DEFAULT_KEY_COL_WIDTH);
+        match &self.storage_deposit {
+            Some(StorageDeposit::Charge(amount)) => {
+                name_value_println!("Deposit", amount.to_string(), DEFAULT_KEY_COL_WIDTH);
+            }
+            Some(StorageDeposit::Refund(amount)) => {
+                name_value_println!("Refund", amount.to_string(), DEFAULT_KEY_COL_WIDTH);
+            }
+            None => {}
+        }
+    }
+}
+
+/// A non-payable message or constructor to which value is transferred.
+///
+/// The contract rejects the transfer, so the call is bound to fail.
+pub struct PaidUnpayable {
+    entity: &'static str,
+    name: String,
+}
+
+impl PaidUnpayable {
+    /// Returns the `entity`, a message or constructor, called `name` if it is not
+    /// payable and `transfers_value` is set.
+    pub fn check(
+        transcoder: &ContractMessageTranscoder,
+        entity: &'static str,
+        name: &str,
+        transfers_value: bool,
+    ) -> Option<Self> {
+        let payable = transcoder.is_payable(name).unwrap_or(true);
+        (transfers_value && !payable).then(|| {
+            Self {
+                entity,
+                name: name.to_string(),
+            }
+        })
+    }
+
+    /// Warns that the call will fail and asks the user to continue anyway.
+    ///
+    /// Without a terminal to ask on, or with `output_json`, it fails unless the
+    /// confirmation is skipped.
+    pub fn confirm(&self, output_json: bool, skip_confirm: bool) -> Result<()> {
+        if skip_confirm {
+            eprintln!("{} {}", "Warning:".yellow().bold(), self.hint());
+            return Ok(())
+        }
+        if output_json || !io::stdin().is_terminal() {
+            return Err(anyhow!(
+                "{} Pass `--value 0`, or `--skip-confirm` to transfer the value anyway.",
+                self.hint()
+            ))
+        }
+        eprintln!("{} {}", "Warning:".yellow().bold(), self.hint());
+        print!(
+            "{} (y/{}): ",
+            "Continue anyway?".bright_white().bold(),
+            "N".bright_white().bold()
+        );
+        let mut buf = String::new();
+        io::stdout().flush()?;
+        io::stdin().read_line(&mut buf)?;
+        match buf.trim().to_lowercase().as_str() {
+            "y" => Ok(()),
+            // default is 'n'
+            "n" | "" => Err(anyhow!("Transaction not submitted")),
+            c => Err(anyhow!("Expected either 'y' or 'n', got '{}'", c)),
+        }
+    }
+
+    /// Adds the hint to the error of a dry-run which trapped because of the transfer.
+    pub fn explain(&self, error: ErrorVariant) -> ErrorVariant {
+        match &error {
+            ErrorVariant::Module(err) if err.error == "ContractTrapped" => {
+                error.with_hint(self.hint())
+            }
+            _ => error,
+        }
+    }
+
+    fn hint(&self) -> String {
+        format!(
+            "The {} `{}` is not payable, the contract rejects the transferred value.",
+            self.entity, self.name
+        )
+    }
+}
+
+/// The weight an executed extrinsic consumed and the storage deposit refunded to its
+/// signer, both absent if the chain doesn't report them in the events.
+#[derive(Debug, Default, Serialize)]
+pub struct ExecutionOutcome {
+    /// The weight consumed, including the base weight of the extrinsic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight_used: Option<Weight>,
+    /// The storage deposit refunded to the signer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_refunded: Option<DisplayBalance>,
+}
+
+impl ExecutionOutcome {
+    /// Reads the outcome from the `events` of an extrinsic signed by `signer`,
+    /// denominating the refunded deposit with the `token_metadata`.
+    pub fn from_events<C: ExtrinsicConfig, E: Environment>(
+        events: &ExtrinsicEvents<C>,
+        signer: &C::AccountId,
+        token_metadata: &TokenMetadata,
+    ) -> Result<Self>
+    where
+        E::Balance: ExtrinsicBalance,
+    {
+        Ok(Self {
+            weight_used: weight_used(events),
+            deposit_refunded: deposit_refunded::<C, E>(events, signer)?
+                .map(|refunded| DisplayBalance::new(refunded, Some(token_metadata))),
+        })
+    }
+
+    /// Returns `true` if the chain reported neither the weight nor a refund.
+    pub fn is_empty(&self) -> bool {
+        self.weight_used.is_none() && self.deposit_refunded.is_none()
+    }
+
+    /// Prints the weight used along with its share of the `gas_limit` the extrinsic
+    /// was submitted with, and the refunded deposit.
+    pub fn print(&self, gas_limit: Weight) {
+        if let Some(weight_used) = self.weight_used {
+            let percent = |used: u64, limit: u64| {
+                if limit == 0 {
+                    0.0
+                } else {
+                    (used as f64 * 10_000.0 / limit as f64).round() / 100.0
+                }
+            };
+            name_value_println!(
+                "Weight used",
+                format!(
+                    "{weight_used} (ref_time {}%, proof_size {}% of the limit)",
+                    percent(weight_used.ref_time(), gas_limit.ref_time()),
+                    percent(weight_used.proof_size(), gas_limit.proof_size())
+                ),
+                DEFAULT_KEY_COL_WIDTH
+            );
+        }
+        if let Some(refunded) = &self.deposit_refunded {
+            name_value_println!("Refunded", refunded.to_string(), DEFAULT_KEY_COL_WIDTH);
+        }
+    }
+}
+
 pub fn print_dry_running_status(msg: &str) {
+    if log_format::is_json() {
+        tracing::info!(entity = msg, "Dry-running");
+        return
+    }
     println!(
         "{:>width$} {} (skip with --skip-dry-run)",
         "Dry-running".green().bold(),
@@ -225,13 +1080,360 @@ pub fn print_dry_running_status(msg: &str) {
     );
 }
 
-pub fn print_gas_required_success(gas: Weight) {
+pub fn print_gas_required_success(gas: Weight, block_usage: Option<BlockUsage>) {
+    if log_format::is_json() {
+        tracing::info!(
+            ref_time = gas.ref_time(),
+            proof_size = gas.proof_size(),
+            "Gas required estimated"
+        );
+        if let Some(block_usage) = block_usage {
+            print_block_usage(&block_usage);
+        }
+        return
+    }
     println!(
         "{:>width$} Gas required estimated at {}",
         "Success!".green().bold(),
         gas.to_string().bright_white(),
         width = DEFAULT_KEY_COL_WIDTH
     );
+    if let Some(block_usage) = block_usage {
+        print_block_usage(&block_usage);
+    }
+}
+
+/// Print the share of the block and extrinsic weight limits taken up by the required
+/// gas.
+pub fn print_block_usage(block_usage: &BlockUsage) {
+    if log_format::is_json() {
+        tracing::info!(
+            ref_time_block_percent = block_usage.ref_time_block_percent,
+            proof_size_block_percent = block_usage.proof_size_block_percent,
+            ref_time_extrinsic_percent = block_usage.ref_time_extrinsic_percent,
+            proof_size_extrinsic_percent = block_usage.proof_size_extrinsic_percent,
+            "Block usage"
+        );
+        warn_block_usage(Some(block_usage));
+        return
+    }
+    name_value_println!(
+        "Block usage",
+        format!(
+            "ref_time {}%, proof_size {}%",
+            block_usage.ref_time_block_percent, block_usage.proof_size_block_percent
+        ),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Extrinsic usage",
+        format!(
+            "ref_time {}%, proof_size {}%",
+            block_usage.ref_time_extrinsic_percent,
+            block_usage.proof_size_extrinsic_percent
+        ),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    warn_block_usage(Some(block_usage));
+}
+
+/// Warn on stderr if the required gas exceeds the weight limit of an extrinsic.
+pub fn warn_block_usage(block_usage: Option<&BlockUsage>) {
+    if block_usage.is_some_and(|usage| usage.exceeds_extrinsic_limit) {
+        if log_format::is_json() {
+            tracing::warn!(
+                "The gas required exceeds the maximum weight of an extrinsic, \
+                 submitting the transaction is guaranteed to fail"
+            );
+            return
+        }
+        eprintln!(
+            "{} The gas required exceeds the maximum weight of an extrinsic, submitting \
+             the transaction is guaranteed to fail",
+            "Warning:".yellow().bold()
+        );
+    }
+}
+
+/// The number of elements of a decoded collection displayed by default.
+const DEFAULT_MAX_ITEMS: usize = 100;
+/// The size of a decoded value displayed by default, in bytes.
+const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+/// Limits the size of decoded values in human readable output. JSON output is never
+/// truncated.
+#[derive(Clone, Copy, Debug, clap::Args)]
+pub struct DisplayLimitOpts {
+    /// Display decoded values in full instead of truncating large collections.
+    #[clap(long)]
+    full_output: bool,
+    /// The maximum number of elements of a decoded collection to display.
+    #[clap(long, value_name = "N", default_value_t = DEFAULT_MAX_ITEMS)]
+    max_items: usize,
+}
+
+impl DisplayLimitOpts {
+    /// Format the value for display, truncating large collections and output
+    /// exceeding 16 KiB unless `--full-output` is given.
+    pub fn display_value(&self, value: &Value) -> String {
+        if self.full_output {
+            return value.to_string()
+        }
+        let output = value.display_truncated(self.max_items).to_string();
+        let truncated = value.exceeds_items(self.max_items);
+        self.truncate(output, truncated)
+    }
+
+    /// Truncate already formatted output exceeding 16 KiB unless `--full-output` is
+    /// given.
+    pub fn display_str(&self, output: &str) -> String {
+        if self.full_output {
+            return output.to_string()
+        }
+        self.truncate(output.to_string(), false)
+    }
+
+    /// The maximum number of elements of a collection to display, if limited.
+    pub fn max_items(&self) -> Option<usize> {
+        (!self.full_output).then_some(self.max_items)
+    }
+
+    fn truncate(&self, mut output: String, mut truncated: bool) -> String {
+        if output.len() > MAX_OUTPUT_BYTES {
+            let mut end = MAX_OUTPUT_BYTES;
+            while !output.is_char_boundary(end) {
+                end -= 1;
+            }
+            let omitted = output.len() - end;
+            output.truncate(end);
+            output.push_str(&format!("… {omitted} more bytes"));
+            truncated = true;
+        }
+        if truncated {
+            output.push_str(" (use --full-output to show all)");
+        }
+        output
+    }
+}
+
+/// The number of bytes of the debug buffer shown with `--debug-message-file`.
+const DEBUG_MESSAGE_PREVIEW_BYTES: usize = 1024;
+/// The number of lines of the debug buffer shown with `--debug-message-file`.
+const DEBUG_MESSAGE_PREVIEW_LINES: usize = 10;
+
+/// Writes the debug buffer of a dry-run to a file instead of displaying it in full.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct DebugMessageOpts {
+    /// Write the raw debug buffer of the dry-run to a file, only a preview of it is
+    /// displayed and the JSON output contains the path of the file instead.
+    #[clap(long, value_name = "PATH")]
+    debug_message_file: Option<PathBuf>,
+}
+
+impl DebugMessageOpts {
+    /// Writes the raw debug buffer to the file of `--debug-message-file`, returning its
+    /// path. Nothing is written if the buffer is empty.
+    pub fn write(&self, debug_message: &[u8]) -> Result<Option<&Path>> {
+        let Some(file) = &self.debug_message_file else {
+            return Ok(None)
+        };
+        if debug_message.is_empty() {
+            return Ok(None)
+        }
+        std::fs::write(file, debug_message).with_context(|| {
+            format!("Failed to write the debug message to {}", file.display())
+        })?;
+        Ok(Some(file))
+    }
+
+    /// Returns the start of the debug buffer, decoded as UTF-8 with invalid sequences
+    /// replaced.
+    fn preview(debug_message: &[u8]) -> String {
+        let end = debug_message.len().min(DEBUG_MESSAGE_PREVIEW_BYTES);
+        let decoded = String::from_utf8_lossy(&debug_message[..end]);
+        let mut lines = decoded.lines();
+        let mut preview = lines
+            .by_ref()
+            .take(DEBUG_MESSAGE_PREVIEW_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if lines.next().is_some() || end < debug_message.len() {
+            preview.push_str("\n…");
+        }
+        preview
+    }
+}
+
+/// Signs the extrinsic of an executed call or instantiation outside of `cargo-contract`.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct ExternalSignerOpts {
+    /// Sign the extrinsic outside of cargo-contract with the given account, e.g. with a
+    /// wallet or on an air-gapped machine, instead of with `--suri`. The payload to sign
+    /// is printed and the signature is read from stdin.
+    #[clap(
+        long,
+        value_name = "ACCOUNT",
+        requires = "execute",
+        conflicts_with = "origin"
+    )]
+    sign_externally: Option<AccountIdArg>,
+}
+
+impl ExternalSignerOpts {
+    /// Returns the account signing externally, if any.
+    pub fn account_id(&self) -> Option<&<DefaultConfig as Config>::AccountId> {
+        self.sign_externally.as_ref().map(AccountIdArg::account_id)
+    }
+
+    /// Returns the signer and the origin for the extrinsic options. If signing
+    /// externally, the development account signs the fee estimate and the external
+    /// account is the origin of the dry-runs.
+    pub fn signer_and_origin(
+        &self,
+        opts: &CLIExtrinsicOpts,
+        origin: Option<&AccountIdArg>,
+    ) -> Result<(Keypair, Option<<DefaultConfig as Config>::AccountId>)> {
+        match self.account_id() {
+            Some(account_id) => {
+                Ok((
+                    create_signer(DRY_RUN_DEV_ACCOUNT)?,
+                    Some(account_id.clone()),
+                ))
+            }
+            None => opts.signer_and_origin(origin),
+        }
+    }
+
+    /// Prints the payload of the `unsigned` extrinsic and reads its signature from stdin,
+    /// returning the signed extrinsic.
+    ///
+    /// The signature is read as hex, 64 bytes are an sr25519 signature and longer ones a
+    /// SCALE encoded `MultiSignature`.
+    pub fn sign<C: ExtrinsicConfig>(
+        unsigned: &UnsignedExtrinsic<C>,
+        output_json: bool,
+    ) -> Result<Vec<u8>> {
+        let payload = format!("0x{}", hex::encode(unsigned.signer_payload()));
+        if output_json {
+            let payload = serde_json::json!({
+                "account": unsigned.account_id().to_string(),
+                "call_data": format!("0x{}", hex::encode(unsigned.call_data())),
+                "signer_payload": payload,
+            });
+            eprintln!("{payload}");
+        } else {
+            eprintln!(
+                "{:>width$} {payload}",
+                "Sign".bright_purple().bold(),
+                width = DEFAULT_KEY_COL_WIDTH
+            );
+            eprintln!(
+                "{:>width$} {}",
+                "with".bright_purple().bold(),
+                unsigned.account_id(),
+                width = DEFAULT_KEY_COL_WIDTH
+            );
+            eprint!("{} ", "Signature (hex):".bright_white().bold());
+            io::stderr().flush()?;
+        }
+        let mut signature = String::new();
+        io::stdin().read_line(&mut signature)?;
+        let signature = parse_signature(signature.trim())?;
+        Ok(unsigned.signed(&signature))
+    }
+}
+
+/// Parses a hex encoded signature, see [`ExternalSignerOpts::sign`].
+fn parse_signature(input: &str) -> Result<MultiSignature> {
+    let bytes = contract_build::util::decode_hex(input)
+        .context("The signature must be hex encoded")?;
+    match <[u8; 64]>::try_from(bytes.as_slice()) {
+        Ok(signature) => Ok(MultiSignature::Sr25519(signature)),
+        Err(_) => {
+            MultiSignature::decode(&mut &bytes[..]).map_err(|err| {
+                anyhow!(
+                    "Expected a 64 byte sr25519 signature or a SCALE encoded \
+                     MultiSignature, got {} bytes: {err}",
+                    bytes.len()
+                )
+            })
+        }
+    }
+}
+
+/// Metadata of other contracts to decode the events of contracts called by the contract.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct AdditionalMetadataOpts {
+    /// A metadata file of another contract to decode the events of contracts called by
+    /// the contract, e.g. a `.contract` bundle or `.json` metadata file. Bound to the
+    /// contract with the given address, the metadata is only used for and preferred for
+    /// the events emitted by that contract. Can be repeated.
+    #[clap(long = "additional-metadata", value_name = "PATH[:ADDRESS]")]
+    additional_metadata: Vec<AdditionalMetadataArg>,
+}
+
+impl AdditionalMetadataOpts {
+    /// Loads the metadata files, displaying account ids with the `ss58_prefix` and
+    /// values in their structural representation if `raw_values` is set.
+    pub fn load(
+        &self,
+        ss58_prefix: Ss58Prefix,
+        raw_values: bool,
+    ) -> Result<Vec<AdditionalMetadata>> {
+        self.additional_metadata
+            .iter()
+            .map(|arg| {
+                let transcoder =
+                    ContractArtifacts::from_manifest_or_file(None, Some(&arg.path))
+                        .and_then(|artifacts| artifacts.contract_transcoder())
+                        .with_context(|| {
+                            format!(
+                                "Failed to load the additional metadata {}",
+                                arg.path.display()
+                            )
+                        })?
+                        .with_ss58_prefix(ss58_prefix.value())
+                        .with_raw_values(raw_values);
+                Ok(AdditionalMetadata::new(
+                    arg.path.clone(),
+                    transcoder,
+                    arg.address
+                        .as_ref()
+                        .map(ContractAddressArg::address)
+                        .as_ref(),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// A metadata file given as `<path>[:<address>]`, optionally bound to the contract with
+/// the address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdditionalMetadataArg {
+    path: PathBuf,
+    address: Option<ContractAddressArg>,
+}
+
+impl FromStr for AdditionalMetadataArg {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        // a path may contain colons itself, e.g. a URL or a Windows drive, hence only a
+        // suffix which is an address binds the metadata
+        if let Some((path, address)) = input.rsplit_once(':') {
+            if let Ok(address) = ContractAddressArg::from_str(address) {
+                return Ok(Self {
+                    path: path.into(),
+                    address: Some(address),
+                })
+            }
+        }
+        Ok(Self {
+            path: input.into(),
+            address: None,
+        })
+    }
 }
 
 /// Display contract information in a formatted way
@@ -269,10 +1471,32 @@ pub fn basic_display_format_extended_contract_info<Hash>(
 }
 
 /// Display all contracts addresses in a formatted way
-pub fn display_all_contracts(contracts: &[<DefaultConfig as Config>::AccountId]) {
+pub fn display_all_contracts(
+    contracts: &[ContractAddress<<DefaultConfig as Config>::AccountId>],
+    ss58_prefix: Ss58Prefix,
+) {
     contracts
         .iter()
-        .for_each(|e: &<DefaultConfig as Config>::AccountId| println!("{}", e))
+        .for_each(|e| println!("{}", ss58_prefix.format_account(e)))
+}
+
+/// Denominate a balance given on the command line into the balance type of the chain.
+pub fn denominate_balance<Balance: TryFrom<u128>>(
+    balance: &BalanceVariant<<DefaultEnvironment as Environment>::Balance>,
+    token_metadata: &TokenMetadata,
+) -> Result<Balance> {
+    let balance = balance.denominate_balance(token_metadata)?;
+    Balance::try_from(balance).map_err(|_| {
+        match BalanceWidth::of::<Balance>() {
+            Ok(width) => {
+                anyhow!(
+                    "Balance {balance} is out of range for the {width} Balance of the \
+                     target chain"
+                )
+            }
+            Err(_) => anyhow!("Balance {balance} is out of range for the target chain"),
+        }
+    })
 }
 
 /// Create a Signer from a secret URI.
@@ -293,9 +1517,431 @@ pub fn parse_code_hash(input: &str) -> Result<<DefaultConfig as Config>::Hash> {
     Ok(arr.into())
 }
 
+/// An account id provided on the command line.
+///
+/// Accepts an SS58 address encoded with any valid prefix, or the 32 byte account id as
+/// `0x` prefixed hex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountIdArg {
+    account_id: <DefaultConfig as Config>::AccountId,
+    /// The SS58 prefix of the provided address, `None` if it was provided as hex.
+    ss58_prefix: Option<u16>,
+}
+
+impl AccountIdArg {
+    /// Returns the account id.
+    pub fn account_id(&self) -> &<DefaultConfig as Config>::AccountId {
+        &self.account_id
+    }
+
+    /// Print an informational note with the re-encoded address if the address was
+    /// provided with a different SS58 prefix than the one of the target chain.
+    pub fn note_ss58_prefix_mismatch(&self, chain_prefix: Ss58Prefix) {
+        match self.ss58_prefix {
+            Some(prefix) if prefix != chain_prefix.value() => {
+                eprintln!(
+                    "{} Address uses SS58 prefix {prefix}, the target chain uses prefix \
+                     {chain_prefix}: {}",
+                    "Info:".cyan().bold(),
+                    chain_prefix.format_account(&self.account_id)
+                );
+            }
+            _ => (),
+        }
+    }
+}
+
+impl FromStr for AccountIdArg {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        if input.starts_with("0x") {
+            let bytes = contract_build::util::decode_hex(input)?;
+            let account_id: [u8; 32] = bytes.try_into().map_err(|_| {
+                anyhow!("Account id given as hex should be 32 bytes in length")
+            })?;
+            return Ok(Self {
+                account_id: account_id.into(),
+                ss58_prefix: None,
+            })
+        }
+        let (account_id, ss58_prefix) = AccountId32::from_ss58check_with_prefix(input)
+            .map_err(|e| anyhow!("Invalid SS58 address '{input}': {e}"))?;
+        Ok(Self {
+            account_id: account_id.0.into(),
+            ss58_prefix: Some(ss58_prefix),
+        })
+    }
+}
+
+/// A contract address provided on the command line.
+///
+/// Accepts any account id accepted by [`AccountIdArg`] for chains with
+/// `pallet-contracts`, or a 20 byte H160 address as `0x` prefixed hex for chains with
+/// `pallet-revive`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContractAddressArg {
+    AccountId(AccountIdArg),
+    H160(H160),
+}
+
+impl ContractAddressArg {
+    /// Returns the contract address.
+    pub fn address(&self) -> ContractAddress<<DefaultConfig as Config>::AccountId> {
+        match self {
+            Self::AccountId(arg) => ContractAddress::AccountId(arg.account_id().clone()),
+            Self::H160(address) => ContractAddress::H160(*address),
+        }
+    }
+
+    /// Print an informational note with the re-encoded address if the address was
+    /// provided with a different SS58 prefix than the one of the target chain.
+    pub fn note_ss58_prefix_mismatch(&self, chain_prefix: Ss58Prefix) {
+        if let Self::AccountId(arg) = self {
+            arg.note_ss58_prefix_mismatch(chain_prefix)
+        }
+    }
+}
+
+impl FromStr for ContractAddressArg {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        if input.starts_with("0x") {
+            let bytes = contract_build::util::decode_hex(input)?;
+            if bytes.len() == H160::len_bytes() {
+                return Ok(Self::H160(H160::from_slice(&bytes)))
+            }
+        }
+        AccountIdArg::from_str(input).map(Self::AccountId)
+    }
+}
+
+/// A weight provided on the command line as `<ref_time>,<proof_size>`.
+///
+/// The reference time accepts the `ms` suffix for milliseconds and the proof size the
+/// `KiB` and `MiB` suffixes, e.g. `2ms,256KiB`. Values without a suffix are raw weight
+/// units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeightArg {
+    ref_time: u64,
+    proof_size: u64,
+}
+
+impl WeightArg {
+    /// Returns the reference time, the gas limit.
+    pub fn ref_time(&self) -> u64 {
+        self.ref_time
+    }
+
+    /// Returns the proof size.
+    pub fn proof_size(&self) -> u64 {
+        self.proof_size
+    }
+
+    /// Parses a value with one of the unit `suffixes`, each with the raw units it is
+    /// multiplied by.
+    fn parse_value(input: &str, name: &str, suffixes: &[(&str, u64)]) -> Result<u64> {
+        let (digits, multiplier) = suffixes
+            .iter()
+            .find_map(|(suffix, multiplier)| {
+                input
+                    .strip_suffix(suffix)
+                    .map(|digits| (digits, *multiplier))
+            })
+            .unwrap_or((input, 1));
+        let value: u64 = digits
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Invalid {name} `{input}`"))?;
+        value
+            .checked_mul(multiplier)
+            .ok_or_else(|| anyhow!("The {name} `{input}` overflows"))
+    }
+}
+
+impl FromStr for WeightArg {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let (ref_time, proof_size) = input.split_once(',').ok_or_else(|| {
+            anyhow!(
+                "A weight must be given as `<ref_time>,<proof_size>`, e.g. `2ms,256KiB`"
+            )
+        })?;
+        Ok(Self {
+            ref_time: Self::parse_value(
+                ref_time,
+                "reference time",
+                &[("ms", WEIGHT_REF_TIME_PER_MILLIS)],
+            )?,
+            proof_size: Self::parse_value(
+                proof_size,
+                "proof size",
+                &[
+                    ("KiB", WEIGHT_PROOF_SIZE_PER_KB),
+                    ("MiB", WEIGHT_PROOF_SIZE_PER_MB),
+                ],
+            )?,
+        })
+    }
+}
+
+impl fmt::Display for WeightArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.ref_time, self.proof_size)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::{
+        CommandFactory,
+        FromArgMatches,
+    };
+    use profile::Setting;
+
+    #[derive(Debug, clap::Parser)]
+    struct ExtrinsicCli {
+        #[clap(flatten)]
+        opts: CLIExtrinsicOpts,
+    }
+
+    fn apply_profile(profile: &Profile, args: &[&str]) -> CLIExtrinsicOpts {
+        let matches = ExtrinsicCli::command().get_matches_from(
+            std::iter::once("cargo-contract").chain(args.iter().copied()),
+        );
+        let mut opts = ExtrinsicCli::from_arg_matches(&matches).unwrap().opts;
+        opts.apply_profile(profile, &matches).unwrap();
+        opts
+    }
+
+    #[test]
+    fn denominate_balance_into_the_balance_type_of_the_chain() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".to_string(),
+            fallback: false,
+        };
+        let balance: BalanceVariant<u128> = "1UNIT".parse().unwrap();
+        assert_eq!(
+            denominate_balance::<u64>(&balance, &token_metadata).unwrap(),
+            1_000_000_000_000
+        );
+
+        let balance: BalanceVariant<u128> = "100000000UNIT".parse().unwrap();
+        assert_eq!(
+            denominate_balance::<u128>(&balance, &token_metadata).unwrap(),
+            100_000_000_000_000_000_000
+        );
+        let err = denominate_balance::<u64>(&balance, &token_metadata).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Balance 100000000000000000000 is out of range for the 64-bit Balance of \
+             the target chain"
+        );
+    }
+
+    #[test]
+    fn paid_unpayable_message_is_rejected_and_explained() {
+        let transcoder = ContractMessageTranscoder::load(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/flipper.json"),
+        )
+        .unwrap();
+        assert!(PaidUnpayable::check(&transcoder, "message", "flip", false).is_none());
+        let paid_unpayable =
+            PaidUnpayable::check(&transcoder, "message", "flip", true).unwrap();
+
+        let err = paid_unpayable.confirm(true, false).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("The message `flip` is not payable"),
+            "{err}"
+        );
+        assert!(paid_unpayable.confirm(true, true).is_ok());
+
+        let trapped = ErrorVariant::Module(contract_extrinsics::ModuleError {
+            pallet: "Contracts".into(),
+            error: "ContractTrapped".into(),
+            docs: vec!["Contract trapped during execution.".into()],
+            hint: None,
+            existing_contract: None,
+        });
+        let explained = paid_unpayable.explain(trapped).to_string();
+        assert!(
+            explained.ends_with("Hint: The message `flip` is not payable, the contract rejects the transferred value."),
+            "{explained}"
+        );
+        let generic =
+            paid_unpayable.explain(ErrorVariant::from("Transaction not submitted"));
+        assert_eq!(generic.to_string(), "Transaction not submitted");
+    }
+
+    #[test]
+    fn explicit_options_override_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("suri"), "//Bob\n").unwrap();
+        let setting = |value| {
+            Setting {
+                value,
+                source: dir.path().join(profile::PROJECT_CONFIG_FILE),
+            }
+        };
+        let profile = Profile {
+            name: "local".to_string(),
+            url: Some(setting("wss://example.com".parse().unwrap())),
+            suri_file: Some(Setting {
+                value: dir.path().join("suri"),
+                source: dir.path().join(profile::PROJECT_CONFIG_FILE),
+            }),
+            skip_confirm: Some(Setting {
+                value: true,
+                source: dir.path().join(profile::PROJECT_CONFIG_FILE),
+            }),
+            ..Default::default()
+        };
+
+        let opts = apply_profile(&profile, &[]);
+        assert_eq!(opts.url.as_str(), "wss://example.com/");
+        assert_eq!(opts.suri().unwrap(), "//Bob");
+        assert!(opts.skip_confirm);
+
+        let opts = apply_profile(
+            &profile,
+            &["--url", "ws://localhost:9944", "--suri", "//Alice"],
+        );
+        assert_eq!(opts.url.as_str(), "ws://localhost:9944/");
+        assert_eq!(opts.suri().unwrap(), "//Alice");
+    }
+
+    #[test]
+    fn url_scheme_is_validated() {
+        assert!(parse_node_url("ws://localhost:9944").is_ok());
+        assert!(parse_node_url("wss://rpc.example.com").is_ok());
+        assert!(parse_node_url("https://rpc.example.com").is_ok());
+        let err = parse_node_url("tcp://localhost:9944").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported url scheme `tcp`, expected `ws`, `wss`, `http` or `https`"
+        );
+        assert!(parse_node_url("localhost:9944").is_err());
+    }
+
+    #[test]
+    fn url_source_is_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join(profile::PROJECT_CONFIG_FILE);
+        let profile = Profile {
+            name: "local".to_string(),
+            url: Some(Setting {
+                value: "wss://example.com".parse().unwrap(),
+                source: source.clone(),
+            }),
+            ..Default::default()
+        };
+        let url_source = |profile: &Profile, args: &[&str]| {
+            let matches = ExtrinsicCli::command().get_matches_from(
+                std::iter::once("cargo-contract").chain(args.iter().copied()),
+            );
+            let mut opts = ExtrinsicCli::from_arg_matches(&matches).unwrap().opts;
+            opts.set_url_source(&matches);
+            opts.apply_profile(profile, &matches).unwrap();
+            opts.url_source
+        };
+
+        assert_eq!(url_source(&Profile::default(), &[]), UrlSource::Default);
+        assert_eq!(url_source(&profile, &[]), UrlSource::Config(source));
+        assert_eq!(
+            url_source(&profile, &["--url", "ws://localhost:9944"]),
+            UrlSource::Flag
+        );
+
+        let invalid = Profile {
+            url: Some(Setting {
+                value: "tcp://localhost:9944".parse().unwrap(),
+                source: dir.path().join(profile::PROJECT_CONFIG_FILE),
+            }),
+            ..Default::default()
+        };
+        let matches = ExtrinsicCli::command().get_matches_from(["cargo-contract"]);
+        let mut opts = ExtrinsicCli::from_arg_matches(&matches).unwrap().opts;
+        assert!(opts.apply_profile(&invalid, &matches).is_err());
+    }
+
+    #[test]
+    fn suri_is_required_without_profile() {
+        let opts = apply_profile(&Profile::default(), &[]);
+        assert!(opts.suri().is_err());
+    }
+
+    #[test]
+    fn suri_is_only_required_to_execute() {
+        let dev_account = create_signer(DRY_RUN_DEV_ACCOUNT)
+            .unwrap()
+            .public_key()
+            .to_account_id();
+        let origin: AccountIdArg = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
+            .parse()
+            .unwrap();
+
+        let opts = apply_profile(&Profile::default(), &[]);
+        let (_, dry_run_origin) = opts.signer_and_origin(None).unwrap();
+        assert_eq!(dry_run_origin, Some(dev_account));
+        let (_, dry_run_origin) = opts.signer_and_origin(Some(&origin)).unwrap();
+        assert_eq!(dry_run_origin.as_ref(), Some(origin.account_id()));
+
+        let opts = apply_profile(&Profile::default(), &["--suri", "//Bob"]);
+        let (signer, dry_run_origin) = opts.signer_and_origin(None).unwrap();
+        assert_eq!(dry_run_origin, None);
+        assert_eq!(
+            signer.public_key().to_account_id(),
+            create_signer("//Bob").unwrap().public_key().to_account_id()
+        );
+
+        let opts = apply_profile(&Profile::default(), &["--execute"]);
+        let err = opts.signer_and_origin(None).unwrap_err();
+        assert!(
+            err.to_string().starts_with("No secret key URI given"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn display_value_truncates_large_collections() {
+        let display_limit = DisplayLimitOpts {
+            full_output: false,
+            max_items: 2,
+        };
+        let seq = Value::Seq((0..5).map(Value::UInt).collect::<Vec<_>>().into());
+        assert_eq!(
+            display_limit.display_value(&seq),
+            "[0, 1, … 3 more items] (use --full-output to show all)"
+        );
+
+        let full_output = DisplayLimitOpts {
+            full_output: true,
+            max_items: 2,
+        };
+        assert_eq!(full_output.display_value(&seq), "[0, 1, 2, 3, 4]");
+    }
+
+    #[test]
+    fn display_str_truncates_long_output() {
+        let display_limit = DisplayLimitOpts {
+            full_output: false,
+            max_items: 2,
+        };
+        let output = "é".repeat(MAX_OUTPUT_BYTES);
+        let truncated = display_limit.display_str(&output);
+        assert!(truncated.starts_with(&"é".repeat(MAX_OUTPUT_BYTES / 2)));
+        assert!(truncated.ends_with(&format!(
+            "… {} more bytes (use --full-output to show all)",
+            MAX_OUTPUT_BYTES
+        )));
+        assert_eq!(display_limit.display_str("short"), "short");
+    }
 
     #[test]
     fn parse_code_hash_works() {
@@ -311,6 +1957,36 @@ mod tests {
         .is_ok())
     }
 
+    #[test]
+    fn parse_additional_metadata() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let arg =
+            AdditionalMetadataArg::from_str(&format!("callee.json:{alice}")).unwrap();
+        assert_eq!(arg.path, PathBuf::from("callee.json"));
+        assert_eq!(
+            arg.address,
+            Some(ContractAddressArg::from_str(alice).unwrap())
+        );
+
+        let h160 = format!("0x{}", "01".repeat(20));
+        let arg =
+            AdditionalMetadataArg::from_str(&format!("C:\\callee.json:{h160}")).unwrap();
+        assert_eq!(arg.path, PathBuf::from("C:\\callee.json"));
+        assert_eq!(
+            arg.address,
+            Some(ContractAddressArg::from_str(&h160).unwrap())
+        );
+
+        // colons of the path itself don't bind the metadata
+        let arg = AdditionalMetadataArg::from_str("https://example.com/callee.contract")
+            .unwrap();
+        assert_eq!(
+            arg.path,
+            PathBuf::from("https://example.com/callee.contract")
+        );
+        assert_eq!(arg.address, None);
+    }
+
     #[test]
     fn parse_incorrect_len_code_hash_fails() {
         // with len not equal to 32
@@ -328,4 +2004,140 @@ mod tests {
         )
         .is_err())
     }
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_HEX: &str =
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn parse_weight_arg_with_units() {
+        let weight = WeightArg::from_str("2ms,256KiB").unwrap();
+        assert_eq!(weight.ref_time(), 2_000_000_000);
+        assert_eq!(weight.proof_size(), 262_144);
+        assert_eq!(weight.to_string(), "2000000000,262144");
+
+        let weight = WeightArg::from_str("9375000000,1MiB").unwrap();
+        assert_eq!(weight.ref_time(), 9_375_000_000);
+        assert_eq!(weight.proof_size(), 1_048_576);
+        assert_eq!(WeightArg::from_str(&weight.to_string()).unwrap(), weight);
+    }
+
+    #[test]
+    fn parse_invalid_weight_arg_fails() {
+        assert!(WeightArg::from_str("2ms").is_err());
+        assert!(WeightArg::from_str("2s,256KiB").is_err());
+        assert!(WeightArg::from_str("2ms,256KB").is_err());
+        let err = WeightArg::from_str("18446744073709551615ms,0").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The reference time `18446744073709551615ms` overflows"
+        );
+    }
+
+    #[test]
+    fn parse_account_id_arg_with_any_ss58_prefix() {
+        let alice: <DefaultConfig as Config>::AccountId = ALICE.parse().unwrap();
+        // default substrate, polkadot, kusama and astar prefixes
+        for (address, prefix) in [
+            (ALICE, 42),
+            ("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", 0),
+            ("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F", 2),
+            ("ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8", 5),
+        ] {
+            let arg = AccountIdArg::from_str(address).unwrap();
+            assert_eq!(arg.account_id(), &alice);
+            assert_eq!(arg.ss58_prefix, Some(prefix));
+        }
+    }
+
+    #[test]
+    fn parse_account_id_arg_as_hex() {
+        let arg = AccountIdArg::from_str(ALICE_HEX).unwrap();
+        assert_eq!(arg.account_id(), &ALICE.parse().unwrap());
+        assert_eq!(arg.ss58_prefix, None);
+    }
+
+    #[test]
+    fn parse_invalid_account_id_arg_fails() {
+        // hex with len not equal to 32
+        assert!(AccountIdArg::from_str(&ALICE_HEX[..64]).is_err());
+        // bad checksum
+        assert!(AccountIdArg::from_str(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_contract_address_arg() {
+        let h160 = format!("0x{}", "ab".repeat(20));
+        assert_eq!(
+            ContractAddressArg::from_str(&h160).unwrap().address(),
+            ContractAddress::H160(H160::repeat_byte(0xab))
+        );
+        assert_eq!(
+            ContractAddressArg::from_str(ALICE).unwrap().address(),
+            ContractAddress::AccountId(ALICE.parse().unwrap())
+        );
+        assert_eq!(
+            ContractAddressArg::from_str(ALICE_HEX).unwrap().address(),
+            ContractAddress::AccountId(ALICE.parse().unwrap())
+        );
+        // hex neither 20 nor 32 bytes in length
+        assert!(ContractAddressArg::from_str(&h160[..40]).is_err());
+    }
+
+    #[test]
+    fn debug_message_file_keeps_raw_bytes() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("debug.bin");
+        let opts = DebugMessageOpts {
+            debug_message_file: Some(path.clone()),
+        };
+
+        assert_eq!(opts.write(b"").unwrap(), None);
+        assert!(!path.exists());
+
+        let bytes = [b'h', b'i', 0xff, b'\n', 0x00];
+        assert_eq!(opts.write(&bytes).unwrap(), Some(path.as_path()));
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+        assert_eq!(DebugMessageOpts::default().write(&bytes).unwrap(), None);
+    }
+
+    #[test]
+    fn debug_message_preview_is_truncated() {
+        assert_eq!(
+            DebugMessageOpts::preview(b"hi\xff\nthere"),
+            "hi\u{fffd}\nthere"
+        );
+
+        let lines = "line\n".repeat(DEBUG_MESSAGE_PREVIEW_LINES + 1);
+        let preview = DebugMessageOpts::preview(lines.as_bytes());
+        assert_eq!(preview.lines().count(), DEBUG_MESSAGE_PREVIEW_LINES + 1);
+        assert!(preview.ends_with("\n…"));
+
+        let long = "x".repeat(DEBUG_MESSAGE_PREVIEW_BYTES * 2);
+        let preview = DebugMessageOpts::preview(long.as_bytes());
+        assert_eq!(
+            preview,
+            format!("{}\n…", &long[..DEBUG_MESSAGE_PREVIEW_BYTES])
+        );
+    }
+
+    #[test]
+    fn parse_external_signatures() {
+        let sr25519 = format!("0x{}", "ab".repeat(64));
+        assert_eq!(
+            parse_signature(&sr25519).unwrap(),
+            MultiSignature::Sr25519([0xab; 64])
+        );
+        let ed25519 = format!("0x00{}", "cd".repeat(64));
+        assert_eq!(
+            parse_signature(&ed25519).unwrap(),
+            MultiSignature::Ed25519([0xcd; 64])
+        );
+        assert!(parse_signature("0x1234").is_err());
+        assert!(parse_signature("not hex").is_err());
+    }
 }
@@ -0,0 +1,725 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Management of a local `substrate-contracts-node` for development.
+//!
+//! The node binary is downloaded from the releases of `substrate-contracts-node` into
+//! the user's cache directory, and runs a dev chain with its state in a temporary
+//! directory, which is kept between runs unless `--fresh` is passed.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_build::name_value_println;
+use contract_extrinsics::{
+    RawParams,
+    RpcRequest,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::{
+        Child,
+        Command,
+        Stdio,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// The release of `substrate-contracts-node` started by default.
+const DEFAULT_NODE_VERSION: &str = "v0.41.0";
+/// The url the releases of `substrate-contracts-node` are downloaded from.
+const RELEASES_URL: &str =
+    "https://github.com/paritytech/substrate-contracts-node/releases/download";
+/// The SHA-256 of the release archives of `substrate-contracts-node` by version and
+/// asset name. The archives of other releases are only downloaded with `--sha256`.
+const RELEASE_SHA256: &[(&str, &str, &str)] = &[];
+/// The name of the node binary.
+const NODE_BINARY: &str = "substrate-contracts-node";
+/// How long to wait for the RPC endpoint of a started node to answer.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Manage a local `substrate-contracts-node` for development.
+#[derive(Debug, clap::Args)]
+#[clap(name = "node")]
+pub struct NodeCommand {
+    #[clap(subcommand)]
+    action: NodeAction,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum NodeAction {
+    /// Download the node if necessary, start a dev chain and print its url.
+    Start(StartCommand),
+    /// Stop the node started with `--detach`.
+    Stop,
+    /// Show whether the node is running and its url.
+    Status,
+}
+
+#[derive(Debug, clap::Args)]
+struct StartCommand {
+    /// The release of `substrate-contracts-node` to run, e.g. `v0.41.0`.
+    #[clap(long, default_value = DEFAULT_NODE_VERSION)]
+    version: String,
+    /// The port of the RPC endpoint of the node.
+    #[clap(long, default_value_t = 9944)]
+    port: u16,
+    /// Run the node in the background, stop it with `cargo contract node stop`.
+    #[clap(long)]
+    detach: bool,
+    /// Wipe the state of the chain of previous runs.
+    #[clap(long)]
+    fresh: bool,
+    /// The SHA-256 of the release archive, as hex, required to download a release
+    /// whose SHA-256 is not pinned by cargo-contract.
+    #[clap(long, value_name = "HEX")]
+    sha256: Option<String>,
+}
+
+impl NodeCommand {
+    pub async fn run(&self) -> Result<()> {
+        let dir = NodeDir::new();
+        match &self.action {
+            NodeAction::Start(start) => start.run(&dir).await,
+            NodeAction::Stop => stop(&dir),
+            NodeAction::Status => status(&dir),
+        }
+    }
+}
+
+impl StartCommand {
+    async fn run(&self, dir: &NodeDir) -> Result<()> {
+        let (mut child, state) = self.spawn(dir).await?;
+        if self.detach {
+            name_value_println!("Url", state.url());
+            name_value_println!("Pid", state.pid.to_string());
+            name_value_println!("Log", dir.log().display().to_string());
+            println!(
+                "The node runs in the background, stop it with `cargo contract node stop`"
+            );
+        } else {
+            eprintln!(
+                "{} The node is ready at {}",
+                "Info:".green().bold(),
+                state.url().bold()
+            );
+            println!("{}", state.url());
+            let exit = child.wait();
+            dir.remove();
+            exit?;
+        }
+        Ok(())
+    }
+
+    /// Starts the node and waits until its RPC endpoint answers.
+    async fn spawn(&self, dir: &NodeDir) -> Result<(Child, NodeState)> {
+        if let Some(state) = dir.running_node()? {
+            anyhow::bail!(
+                "A node is already running at {} (pid {}), stop it with \
+                `cargo contract node stop`",
+                state.url(),
+                state.pid
+            )
+        }
+        if std::net::TcpListener::bind(("127.0.0.1", self.port)).is_err() {
+            anyhow::bail!(
+                "The port {} is already in use, choose another one with `--port`",
+                self.port
+            )
+        }
+        let binary = node_binary(&self.version, self.sha256.as_deref())?;
+
+        let chain_dir = dir.chain();
+        if self.fresh && chain_dir.exists() {
+            fs::remove_dir_all(&chain_dir).with_context(|| {
+                format!("Failed to wipe the chain state in {}", chain_dir.display())
+            })?;
+        }
+        fs::create_dir_all(&dir.root)?;
+
+        let mut command = Command::new(&binary);
+        command
+            .arg("--dev")
+            .arg("--base-path")
+            .arg(&chain_dir)
+            .arg("--rpc-port")
+            .arg(self.port.to_string())
+            .stdin(Stdio::null());
+        if self.detach {
+            let log = fs::File::create(dir.log())?;
+            command.stdout(log.try_clone()?).stderr(log);
+            // the node must not receive the signals sent to the terminal of this process
+            #[cfg(unix)]
+            std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        }
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to start {}", binary.display()))?;
+
+        let state = NodeState {
+            pid: child.id(),
+            process: ProcessIdentity::of(child.id()),
+            port: self.port,
+            version: self.version.clone(),
+            base_path: chain_dir,
+        };
+        dir.store(&state)?;
+        if let Err(err) = wait_until_ready(&mut child, &state, self.detach, dir).await {
+            let _ = child.kill();
+            dir.remove();
+            return Err(err)
+        }
+        Ok((child, state))
+    }
+}
+
+/// A dev node to run the contract against: the node started with
+/// `cargo contract node start` if it is running, otherwise a node started in the
+/// background until [`DevNode::stop`].
+pub struct DevNode {
+    url: String,
+    started: Option<Child>,
+}
+
+impl DevNode {
+    /// Connects to the running node, or starts the default release of the node.
+    pub async fn connect_or_start() -> Result<Self> {
+        let dir = NodeDir::new();
+        if let Some(state) = dir.running_node()? {
+            return Ok(Self {
+                url: state.url(),
+                started: None,
+            })
+        }
+        let start = StartCommand {
+            version: DEFAULT_NODE_VERSION.into(),
+            port: 9944,
+            detach: true,
+            fresh: false,
+            sha256: None,
+        };
+        eprintln!(
+            "{} {}",
+            "[==]".bold(),
+            format!("Starting {NODE_BINARY} {DEFAULT_NODE_VERSION}")
+                .bright_green()
+                .bold()
+        );
+        let (child, state) = start.spawn(&dir).await?;
+        Ok(Self {
+            url: state.url(),
+            started: Some(child),
+        })
+    }
+
+    /// The websocket url of the RPC endpoint of the node.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Stops the node if it was started by [`DevNode::connect_or_start`].
+    pub fn stop(self) -> Result<()> {
+        if let Some(mut child) = self.started {
+            terminate(child.id())?;
+            child.wait()?;
+            NodeDir::new().remove();
+        }
+        Ok(())
+    }
+}
+
+/// Stops the node started with `--detach`.
+fn stop(dir: &NodeDir) -> Result<()> {
+    let Some(state) = dir.running_node()? else {
+        println!("No node is running");
+        return Ok(())
+    };
+    terminate(state.pid)?;
+    let start = Instant::now();
+    while state.is_running() {
+        if start.elapsed() > Duration::from_secs(30) {
+            anyhow::bail!(
+                "The node (pid {}) did not stop within 30 seconds",
+                state.pid
+            )
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    dir.remove();
+    println!("Stopped the node (pid {})", state.pid);
+    Ok(())
+}
+
+/// Prints whether the node is running, and its url.
+fn status(dir: &NodeDir) -> Result<()> {
+    match dir.running_node()? {
+        Some(state) => {
+            name_value_println!("Status", "running".green().bold().to_string());
+            name_value_println!("Url", state.url());
+            name_value_println!("Pid", state.pid.to_string());
+            name_value_println!("Version", state.version);
+            name_value_println!("Base path", state.base_path.display().to_string());
+        }
+        None => name_value_println!("Status", "stopped".yellow().bold().to_string()),
+    }
+    Ok(())
+}
+
+/// Waits until the RPC endpoint of the started node answers.
+async fn wait_until_ready(
+    child: &mut Child,
+    state: &NodeState,
+    detached: bool,
+    dir: &NodeDir,
+) -> Result<()> {
+    let url = url::Url::parse(&state.url())?;
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let hint = if detached {
+                format!(", see the log in {}", dir.log().display())
+            } else {
+                String::new()
+            };
+            anyhow::bail!("The node exited with {status} before it was ready{hint}")
+        }
+        if let Ok(request) = RpcRequest::new(&url).await {
+            if request
+                .raw_call("system_health", RawParams::new(&[])?)
+                .await
+                .is_ok()
+            {
+                return Ok(())
+            }
+        }
+        if start.elapsed() > STARTUP_TIMEOUT {
+            anyhow::bail!(
+                "The RPC endpoint of the node did not answer at {url} within {} seconds",
+                STARTUP_TIMEOUT.as_secs()
+            )
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+/// The node started by `cargo contract node start`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct NodeState {
+    pid: u32,
+    /// The identity of the node process, `None` if it could not be determined.
+    #[serde(default)]
+    process: Option<ProcessIdentity>,
+    port: u16,
+    version: String,
+    base_path: PathBuf,
+}
+
+impl NodeState {
+    /// The websocket url of the RPC endpoint of the node.
+    fn url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.port)
+    }
+
+    /// Returns whether the node is still running, i.e. the process with its pid is the
+    /// process which was started as the node, rather than a process which was assigned
+    /// the pid after the node exited.
+    fn is_running(&self) -> bool {
+        self.process.as_ref().is_some_and(|process| {
+            ProcessIdentity::of(self.pid).as_ref() == Some(process)
+        })
+    }
+}
+
+/// The executable and start time of a process, which tell it apart from processes
+/// assigned the same pid later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ProcessIdentity {
+    exe: PathBuf,
+    start_time: String,
+}
+
+impl ProcessIdentity {
+    /// Returns the identity of the running process with the `pid`.
+    #[cfg(target_os = "linux")]
+    fn of(pid: u32) -> Option<Self> {
+        let exe = fs::read_link(format!("/proc/{pid}/exe")).ok()?;
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // the start time is the 22nd field, the 20th after the parenthesized name
+        let (_, fields) = stat.rsplit_once(')')?;
+        let start_time = fields.split_whitespace().nth(19)?.to_string();
+        Some(Self { exe, start_time })
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    fn of(pid: u32) -> Option<Self> {
+        let ps = |field: &str| {
+            let output = Command::new("ps")
+                .args(["-o", field, "-p", &pid.to_string()])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())?;
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Some(value).filter(|value| !value.is_empty())
+        };
+        Some(Self {
+            exe: ps("comm=")?.into(),
+            start_time: ps("lstart=")?,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn of(_pid: u32) -> Option<Self> {
+        None
+    }
+}
+
+/// The temporary directory containing the chain state, the log and the pid of the node.
+struct NodeDir {
+    root: PathBuf,
+}
+
+impl NodeDir {
+    fn new() -> Self {
+        Self {
+            root: std::env::temp_dir().join("cargo-contract-node"),
+        }
+    }
+
+    fn chain(&self) -> PathBuf {
+        self.root.join("chain")
+    }
+
+    fn log(&self) -> PathBuf {
+        self.root.join("node.log")
+    }
+
+    fn state(&self) -> PathBuf {
+        self.root.join("node.json")
+    }
+
+    fn store(&self, state: &NodeState) -> Result<()> {
+        fs::write(self.state(), serde_json::to_string_pretty(state)?)?;
+        Ok(())
+    }
+
+    fn remove(&self) {
+        let _ = fs::remove_file(self.state());
+    }
+
+    /// Returns the recorded node if it is still running, removing a stale record.
+    ///
+    /// A record without the identity of the node process is stale, since the process
+    /// with its pid can't be verified to be the node.
+    fn running_node(&self) -> Result<Option<NodeState>> {
+        let Ok(json) = fs::read_to_string(self.state()) else {
+            return Ok(None)
+        };
+        let state: NodeState = serde_json::from_str(&json).with_context(|| {
+            format!("Failed to read the node state {}", self.state().display())
+        })?;
+        if state.is_running() {
+            Ok(Some(state))
+        } else {
+            self.remove();
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the path of the node binary of the `version`, downloading it into the cache
+/// directory first if necessary. The downloaded archive must match the pinned SHA-256
+/// of the release, or the `sha256` given for it.
+fn node_binary(version: &str, sha256: Option<&str>) -> Result<PathBuf> {
+    let cache_dir = cache_dir()
+        .context("Failed to determine the cache directory, set `XDG_CACHE_HOME`")?
+        .join("cargo-contract")
+        .join("node")
+        .join(version);
+    let binary = cache_dir.join(NODE_BINARY);
+    if binary.exists() {
+        return Ok(binary)
+    }
+
+    let asset = release_asset()?;
+    let url = download_url(version, asset);
+    let sha256 = sha256
+        .map(|sha256| sha256.trim_start_matches("0x").to_ascii_lowercase())
+        .or_else(|| pinned_sha256(version, asset).map(String::from))
+        .with_context(|| {
+            format!(
+                "No SHA-256 is pinned for {asset} of {NODE_BINARY} {version}, pass the \
+                SHA-256 published for the release with `--sha256`"
+            )
+        })?;
+    eprintln!(
+        "{} {} {url}",
+        "[==]".bold(),
+        format!("Downloading {NODE_BINARY} {version} from")
+            .bright_green()
+            .bold()
+    );
+    let download_dir = cache_dir.join("download");
+    let downloaded = download(&url, &sha256, &download_dir).and_then(|extracted| {
+        fs::rename(extracted, &binary)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&binary, fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(())
+    });
+    if downloaded.is_err() {
+        fs::remove_dir_all(&cache_dir).ok();
+    }
+    fs::remove_dir_all(&download_dir).ok();
+    downloaded?;
+    Ok(binary)
+}
+
+/// Downloads the release archive at the `url` into the `download_dir` and extracts it
+/// if its SHA-256 matches the `sha256`, returning the path of the extracted node
+/// binary.
+fn download(url: &str, sha256: &str, download_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(download_dir)?;
+    let archive = download_dir.join("node.tar.gz");
+    run_tool(
+        Command::new("curl")
+            .args([
+                "--fail",
+                "--location",
+                "--silent",
+                "--show-error",
+                "--output",
+            ])
+            .arg(&archive)
+            .arg(url),
+    )
+    .with_context(|| format!("Failed to download {url}"))?;
+    let actual = hex::encode(Sha256::digest(fs::read(&archive)?));
+    anyhow::ensure!(
+        actual == sha256,
+        "The SHA-256 {actual} of {url} does not match the expected SHA-256 {sha256}"
+    );
+    run_tool(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(download_dir),
+    )
+    .with_context(|| format!("Failed to extract {}", archive.display()))?;
+
+    find_file(download_dir, NODE_BINARY)?.with_context(|| {
+        format!("The release archive {url} does not contain `{NODE_BINARY}`")
+    })
+}
+
+/// Runs the external tool of the `command`, failing if it can't be found or fails.
+fn run_tool(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().to_string();
+    which::which(&program)
+        .with_context(|| format!("`{program}` is required to download the node"))?;
+    let status = command.status()?;
+    if !status.success() {
+        anyhow::bail!("`{program}` exited with {status}")
+    }
+    Ok(())
+}
+
+/// Returns the path of the first file with the `name` in `dir` or its subdirectories.
+fn find_file(dir: &Path, name: &str) -> Result<Option<PathBuf>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Some(file) = find_file(&path, name)? {
+                return Ok(Some(file))
+            }
+        } else if path.file_name().is_some_and(|file| file == name) {
+            return Ok(Some(path))
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the user's cache directory.
+fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+}
+
+/// Returns the name of the release archive of the node for the host platform.
+fn release_asset() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Ok("substrate-contracts-node-linux.tar.gz"),
+        ("macos", _) => Ok("substrate-contracts-node-mac-universal.tar.gz"),
+        (os, arch) => {
+            anyhow::bail!(
+                "There is no release of {NODE_BINARY} for {os} {arch}, build it from \
+                https://github.com/paritytech/substrate-contracts-node instead"
+            )
+        }
+    }
+}
+
+fn download_url(version: &str, asset: &str) -> String {
+    format!("{RELEASES_URL}/{version}/{asset}")
+}
+
+/// Returns the pinned SHA-256 of the release archive `asset` of the `version`.
+fn pinned_sha256(version: &str, asset: &str) -> Option<&'static str> {
+    RELEASE_SHA256
+        .iter()
+        .find(|(v, a, _)| *v == version && *a == asset)
+        .map(|(_, _, sha256)| *sha256)
+}
+
+/// Asks the process with the `pid` to terminate.
+#[cfg(unix)]
+fn terminate(pid: u32) -> Result<()> {
+    let pid = libc::pid_t::try_from(pid)?;
+    // SAFETY: sends `SIGTERM` to a single process, `pid` is positive
+    if pid <= 0 || unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        anyhow::bail!(
+            "Failed to stop the node (pid {pid}): {}",
+            std::io::Error::last_os_error()
+        )
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn terminate(pid: u32) -> Result<()> {
+    anyhow::bail!("Stopping the node (pid {pid}) is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_url_of_release() {
+        assert_eq!(
+            download_url("v0.41.0", "substrate-contracts-node-linux.tar.gz"),
+            "https://github.com/paritytech/substrate-contracts-node/releases/download/\
+            v0.41.0/substrate-contracts-node-linux.tar.gz"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stale_node_state_is_removed() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = NodeDir {
+            root: tmp_dir.path().to_path_buf(),
+        };
+        assert_eq!(dir.running_node().unwrap(), None);
+
+        let mut exited = Command::new("true").spawn().unwrap();
+        let process = ProcessIdentity::of(exited.id());
+        exited.wait().unwrap();
+        let state = NodeState {
+            pid: exited.id(),
+            process,
+            port: 9944,
+            version: DEFAULT_NODE_VERSION.into(),
+            base_path: dir.chain(),
+        };
+        dir.store(&state).unwrap();
+        assert_eq!(dir.running_node().unwrap(), None);
+        assert!(!dir.state().exists());
+
+        let pid = std::process::id();
+        let state = NodeState {
+            pid,
+            process: ProcessIdentity::of(pid),
+            ..state
+        };
+        dir.store(&state).unwrap();
+        assert_eq!(dir.running_node().unwrap(), Some(state.clone()));
+
+        // a process which was assigned the pid of the node after it exited
+        let state = NodeState {
+            process: state.process.map(|process| {
+                ProcessIdentity {
+                    start_time: "0".into(),
+                    ..process
+                }
+            }),
+            ..state
+        };
+        dir.store(&state).unwrap();
+        assert_eq!(dir.running_node().unwrap(), None);
+        assert!(!dir.state().exists());
+    }
+
+    #[test]
+    fn node_state_without_process_identity_is_stale() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = NodeDir {
+            root: tmp_dir.path().to_path_buf(),
+        };
+        let json = serde_json::json!({
+            "pid": std::process::id(),
+            "port": 9944,
+            "version": DEFAULT_NODE_VERSION,
+            "base_path": dir.chain(),
+        });
+        fs::write(dir.state(), json.to_string()).unwrap();
+        assert_eq!(dir.running_node().unwrap(), None);
+        assert!(!dir.state().exists());
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn download_requires_a_sha256() {
+        let err = node_binary("v0.0.0-unpinned", None).unwrap_err();
+        assert!(err.to_string().contains("pass the SHA-256"), "{err}");
+    }
+
+    #[test]
+    fn finds_the_binary_in_the_extracted_archive() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let nested = tmp_dir.path().join("artifacts").join("node-linux");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join(NODE_BINARY), b"").unwrap();
+        fs::write(tmp_dir.path().join("README.md"), b"").unwrap();
+
+        assert_eq!(
+            find_file(tmp_dir.path(), NODE_BINARY).unwrap(),
+            Some(nested.join(NODE_BINARY))
+        );
+        assert_eq!(find_file(tmp_dir.path(), "missing").unwrap(), None);
+    }
+}
@@ -0,0 +1,1158 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! JSON Schemas of the `--output-json` output of the extrinsic commands and of `build`.
+//!
+//! The schemas are maintained by hand, the tests validate serialized instances of the
+//! output types against them to make sure they don't drift apart.
+
+use anyhow::Result;
+use serde_json::{
+    json,
+    Value,
+};
+
+/// The commands whose `--output-json` output is described by a schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaTarget {
+    Call,
+    Instantiate,
+    Upload,
+    Remove,
+    Build,
+}
+
+impl SchemaTarget {
+    /// The names of the definitions of all outputs of the command.
+    fn outputs(&self) -> &'static [&'static str] {
+        match self {
+            Self::Call => {
+                &[
+                    "CallDryRunResult",
+                    "Events",
+                    "CallResult",
+                    "XcmCallResult",
+                    "ErrorVariant",
+                ]
+            }
+            Self::Instantiate => {
+                &[
+                    "InstantiateDryRunResult",
+                    "InstantiateResult",
+                    "ErrorVariant",
+                ]
+            }
+            Self::Upload => &["UploadDryRunResult", "UploadResult", "ErrorVariant"],
+            Self::Remove => {
+                &["RemoveResult", "RemovedCodes", "OwnedCodes", "ErrorVariant"]
+            }
+            Self::Build => &["BuildResult", "BuildResults"],
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::Instantiate => "instantiate",
+            Self::Upload => "upload",
+            Self::Remove => "remove",
+            Self::Build => "build",
+        }
+    }
+}
+
+/// Emits the JSON Schema of the `--output-json` output of the extrinsic commands and of
+/// `build`.
+#[derive(Debug, clap::Args)]
+#[clap(name = "schema")]
+pub struct OutputSchemaCommand {
+    /// Only describe the output of the given command.
+    #[clap(long, value_enum)]
+    command: Option<SchemaTarget>,
+}
+
+impl OutputSchemaCommand {
+    pub fn run(&self) -> Result<String> {
+        let schema = output_schema(self.command);
+        Ok(serde_json::to_string_pretty(&schema)?)
+    }
+}
+
+/// Returns the JSON Schema of the outputs of `command`, or of all commands.
+pub fn output_schema(command: Option<SchemaTarget>) -> Value {
+    let (title, outputs) = match command {
+        Some(command) => {
+            (
+                format!("cargo contract {} --output-json", command.name()),
+                command.outputs().to_vec(),
+            )
+        }
+        None => {
+            let mut outputs = Vec::new();
+            for command in [
+                SchemaTarget::Call,
+                SchemaTarget::Instantiate,
+                SchemaTarget::Upload,
+                SchemaTarget::Remove,
+                SchemaTarget::Build,
+            ] {
+                for output in command.outputs() {
+                    if !outputs.contains(output) {
+                        outputs.push(*output)
+                    }
+                }
+            }
+            ("cargo contract --output-json".to_string(), outputs)
+        }
+    };
+    let any_of: Vec<_> = outputs
+        .iter()
+        .map(|output| json!({ "$ref": format!("#/definitions/{output}") }))
+        .collect();
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": title,
+        "anyOf": any_of,
+        "definitions": definitions(),
+    })
+}
+
+/// An object with the given properties, all of which except `optional` are required.
+fn object(properties: Value, optional: &[&str]) -> Value {
+    let required: Vec<_> = properties
+        .as_object()
+        .expect("properties are an object")
+        .keys()
+        .filter(|key| !optional.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// An object like [`object`] whose properties are serialized in the given `order`,
+/// which is documented with the non-standard `x-field-order` keyword.
+fn ordered_object(properties: Value, optional: &[&str], order: &[&str]) -> Value {
+    let mut object = object(properties, optional);
+    let properties: Vec<_> = object["properties"]
+        .as_object()
+        .expect("properties are an object")
+        .keys()
+        .cloned()
+        .collect();
+    let mut sorted_order = order.to_vec();
+    sorted_order.sort_unstable();
+    assert_eq!(
+        properties, sorted_order,
+        "the order must list all properties"
+    );
+    object["x-field-order"] = json!(order);
+    object
+}
+
+/// An externally tagged enum variant, serialized as an object with a single property.
+fn variant(name: &str, schema: Value) -> Value {
+    object(json!({ name: schema }), &[])
+}
+
+fn definitions() -> Value {
+    let value = json!({ "$ref": "#/definitions/Value" });
+    let weight = json!({ "$ref": "#/definitions/Weight" });
+    let balance = json!({ "$ref": "#/definitions/Balance" });
+    let storage_deposit = json!({ "$ref": "#/definitions/StorageDeposit" });
+    let block_usage = json!({ "$ref": "#/definitions/BlockUsage" });
+    let events = json!({ "$ref": "#/definitions/Events" });
+    let wasm_validation = json!({ "$ref": "#/definitions/WasmValidation" });
+    let host_function = json!({ "$ref": "#/definitions/HostFunction" });
+    let address = json!({ "$ref": "#/definitions/Address" });
+    let timings = json!({ "$ref": "#/definitions/Timings" });
+    let string = json!({ "type": "string" });
+    let boolean = json!({ "type": "boolean" });
+    let determinism = json!({ "enum": ["enforced", "relaxed"] });
+    let size = json!({ "type": "integer", "minimum": 0 });
+    let path = json!({ "type": ["string", "null"] });
+    let size_report = object(
+        json!({
+            "total_size": size.clone(),
+            "code_size": size.clone(),
+            "data_size": size.clone(),
+            "has_names": boolean.clone(),
+            "functions": {
+                "type": "array",
+                "items": object(json!({
+                    "name": string.clone(),
+                    "crate": path.clone(),
+                    "size": size.clone(),
+                }), &[]),
+            },
+            "crates": {
+                "type": "array",
+                "items": object(json!({
+                    "name": string.clone(),
+                    "size": size.clone(),
+                }), &[]),
+            },
+        }),
+        &[],
+    );
+
+    json!({
+        "Balance": object(json!({
+            "raw": { "type": "string", "pattern": "^[0-9]+$" },
+            "denominated": string.clone(),
+        }), &["denominated"]),
+        "Address": object(json!({
+            "ss58": string.clone(),
+            "hex": string.clone(),
+        }), &["ss58"]),
+        "Weight": object(json!({
+            "ref_time": { "type": "integer", "minimum": 0 },
+            "proof_size": { "type": "integer", "minimum": 0 },
+        }), &[]),
+        "BlockUsage": object(json!({
+            "ref_time_block_percent": { "type": "number", "minimum": 0 },
+            "proof_size_block_percent": { "type": "number", "minimum": 0 },
+            "ref_time_extrinsic_percent": { "type": "number", "minimum": 0 },
+            "proof_size_extrinsic_percent": { "type": "number", "minimum": 0 },
+            "exceeds_extrinsic_limit": boolean.clone(),
+        }), &[]),
+        "StorageDeposit": {
+            "oneOf": [
+                variant("Charge", balance.clone()),
+                variant("Refund", balance.clone()),
+            ]
+        },
+        "Value": {
+            "description": "A value decoded with the contract metadata",
+            "oneOf": [
+                { "const": "Unit" },
+                variant("Bool", boolean.clone()),
+                variant("Char", string.clone()),
+                variant("UInt", json!({ "type": "integer", "minimum": 0 })),
+                variant("Int", json!({ "type": "integer" })),
+                variant("String", string.clone()),
+                variant("Literal", string.clone()),
+                variant("Map", json!({
+                    "type": "object",
+                    "additionalProperties": value.clone(),
+                })),
+                variant("Tuple", object(json!({
+                    "ident": { "type": ["string", "null"] },
+                    "values": { "type": "array", "items": value.clone() },
+                }), &[])),
+                variant("Seq", object(json!({
+                    "elems": { "type": "array", "items": value.clone() },
+                }), &[])),
+                variant("Hex", object(json!({
+                    "s": { "type": "string", "pattern": "^[0-9a-fA-F]*$" },
+                }), &[])),
+            ]
+        },
+        "Events": {
+            "description": "The events emitted by the extrinsic",
+            "type": "array",
+            "items": ordered_object(json!({
+                "pallet": string.clone(),
+                "name": string.clone(),
+                "fields": {
+                    "type": "array",
+                    "items": ordered_object(json!({
+                        "name": string.clone(),
+                        "value": value.clone(),
+                        "denominated": string.clone(),
+                        "indexed": { "type": "array", "items": string.clone() },
+                        "alias": string.clone(),
+                    }), &["denominated", "indexed", "alias"], &["name", "value", "denominated", "indexed", "alias"]),
+                },
+                "topics": { "type": "array", "items": string.clone() },
+                "raw": string.clone(),
+                "raw_data": string.clone(),
+                "decode_error": string.clone(),
+                "metadata": string.clone(),
+            }), &["topics", "raw", "raw_data", "decode_error", "metadata"], &["pallet", "name", "fields", "topics", "raw", "raw_data", "decode_error", "metadata"]),
+        },
+        "WasmValidation": {
+            "description": "The findings of the validation of the contract Wasm",
+            "type": "object",
+            "properties": {
+                "imports": { "type": "array", "items": host_function.clone() },
+                "float_instructions": {
+                    "type": "array",
+                    "items": object(json!({
+                        "function": { "type": "integer", "minimum": 0 },
+                        "name": { "type": ["string", "null"] },
+                        "crate": { "type": ["string", "null"] },
+                        "instructions": { "type": "array", "items": string.clone() },
+                    }), &[]),
+                },
+                "unknown_imports": { "type": "array", "items": host_function.clone() },
+            },
+            "required": ["imports"],
+            "additionalProperties": false,
+        },
+        "HostFunction": object(json!({
+            "module": string.clone(),
+            "name": string.clone(),
+        }), &[]),
+        "Timings": object(json!({
+                "connect": size.clone(),
+                "token_metadata": size.clone(),
+                "transcode": size.clone(),
+                "dry_run": size.clone(),
+                "sign": size.clone(),
+                "in_block": size.clone(),
+                "finalized": size.clone(),
+                "total": size.clone(),
+        }), &["connect", "token_metadata", "transcode", "dry_run", "sign", "in_block", "finalized"]),
+        "ErrorVariant": {
+            "description": "The error of a failed command, printed to stderr",
+            "oneOf": [
+                variant("module_error", object(json!({
+                    "pallet": string.clone(),
+                    "error": string.clone(),
+                    "docs": { "type": "array", "items": string.clone() },
+                    "hint": string.clone(),
+                    "existing_contract": address.clone(),
+                }), &["hint", "existing_contract"])),
+                variant("generic_error", object(json!({
+                    "error": string.clone(),
+                }), &[])),
+                variant("max_fee_exceeded_error", object(json!({
+                    "estimated_fee": balance.clone(),
+                    "max_fee": balance.clone(),
+                }), &[])),
+                variant("assertion_failed_error", object(json!({
+                    "error": string.clone(),
+                }), &[])),
+                variant("timeout_error", object(json!({
+                    "status": { "const": "timeout" },
+                    "tx_hash": string.clone(),
+                    "last_status": string.clone(),
+                    "from_block": size.clone(),
+                    "timeout_secs": size.clone(),
+                }), &[])),
+            ]
+        },
+        "CallDryRunResult": ordered_object(json!({
+            "origin": address.clone(),
+            "selector": string.clone(),
+            "reverted": boolean.clone(),
+            "data": value.clone(),
+            "alias": string.clone(),
+            "decode_error": string.clone(),
+            "gas_consumed": weight.clone(),
+            "gas_required": weight.clone(),
+            "storage_deposit": storage_deposit.clone(),
+            "debug_message": string.clone(),
+            "debug_message_hex": string.clone(),
+            "debug_message_file": string.clone(),
+            "block_usage": block_usage.clone(),
+            "events": events.clone(),
+            "assertion": object(json!({
+                "passed": boolean.clone(),
+                "expected": value.clone(),
+                "expect_reverted": boolean.clone(),
+                "differences": { "type": "array", "items": string.clone() },
+            }), &["expected", "expect_reverted", "differences"]),
+            "timings": timings.clone(),
+        }), &["origin", "selector", "alias", "decode_error", "debug_message", "debug_message_hex", "debug_message_file", "block_usage", "events", "assertion", "timings"],
+        &["origin", "selector", "reverted", "data", "alias", "decode_error", "gas_consumed", "gas_required", "storage_deposit", "debug_message", "debug_message_hex", "debug_message_file", "block_usage", "events", "assertion", "timings"]),
+        "InstantiateDryRunResult": ordered_object(json!({
+            "origin": address.clone(),
+            "result": value.clone(),
+            "decode_error": string.clone(),
+            "error": value.clone(),
+            "contract": address.clone(),
+            "data": string.clone(),
+            "salt": string.clone(),
+            "reverted": boolean.clone(),
+            "gas_consumed": weight.clone(),
+            "gas_required": weight.clone(),
+            "storage_deposit": storage_deposit.clone(),
+            "debug_message": string.clone(),
+            "debug_message_hex": string.clone(),
+            "debug_message_file": string.clone(),
+            "block_usage": block_usage.clone(),
+            "events": events.clone(),
+            "timings": timings.clone(),
+        }), &["origin", "decode_error", "error", "debug_message", "debug_message_hex", "debug_message_file", "block_usage", "events", "timings"],
+        &["origin", "result", "decode_error", "error", "contract", "data", "salt", "reverted", "gas_consumed", "gas_required", "storage_deposit", "debug_message", "debug_message_hex", "debug_message_file", "block_usage", "events", "timings"]),
+        "CallResult": object(json!({
+            "events": events.clone(),
+            "storage_deposit_limit": balance.clone(),
+            "selector": string.clone(),
+            "weight_used": weight.clone(),
+            "deposit_refunded": balance.clone(),
+            "explorer_url": string.clone(),
+            "timings": timings.clone(),
+        }), &["storage_deposit_limit", "selector", "weight_used", "deposit_refunded", "explorer_url", "timings"]),
+        "XcmCallResult": object(json!({
+            "dest": { "type": "integer", "minimum": 0 },
+            "pallet": { "enum": ["XcmPallet", "PolkadotXcm"] },
+            "message_id": string.clone(),
+            "extrinsic_hash": string.clone(),
+            "block_hash": string.clone(),
+            "fee": object(json!({
+                "asset": { "enum": ["parent", "here"] },
+                "amount": { "type": "integer", "minimum": 0 },
+            }), &[]),
+            "timings": timings.clone(),
+        }), &["message_id", "timings"]),
+        "InstantiateResult": ordered_object(json!({
+            "contract": address.clone(),
+            "code_hash": string.clone(),
+            "storage_deposit_limit": balance.clone(),
+            "events": events.clone(),
+            "existing": boolean.clone(),
+            "wasm_validation": wasm_validation.clone(),
+            "gas_limit_submitted": weight.clone(),
+            "weight_consumed": weight.clone(),
+            "explorer_url": string.clone(),
+            "weight_used": weight.clone(),
+            "deposit_refunded": balance.clone(),
+            "timings": timings.clone(),
+        }), &["contract", "code_hash", "storage_deposit_limit", "existing", "wasm_validation", "gas_limit_submitted", "weight_consumed", "explorer_url", "weight_used", "deposit_refunded", "timings"],
+        &["contract", "code_hash", "storage_deposit_limit", "events", "existing", "wasm_validation", "gas_limit_submitted", "weight_consumed", "explorer_url", "weight_used", "deposit_refunded", "timings"]),
+        "UploadDryRunResult": object(json!({
+            "result": string.clone(),
+            "code_hash": string.clone(),
+            "code_size": { "type": "integer", "minimum": 0 },
+            "deposit": balance.clone(),
+            "determinism": determinism.clone(),
+            "wasm_validation": wasm_validation.clone(),
+            "timings": timings.clone(),
+        }), &["wasm_validation", "timings"]),
+        "UploadResult": object(json!({
+            "code_hash": string.clone(),
+            "code_size": { "type": "integer", "minimum": 0 },
+            "deposit": balance.clone(),
+            "block_hash": string.clone(),
+            "newly_uploaded": boolean.clone(),
+            "determinism": determinism.clone(),
+            "wasm_validation": wasm_validation.clone(),
+            "events": events.clone(),
+            "explorer_url": string.clone(),
+            "timings": timings.clone(),
+        }), &["deposit", "wasm_validation", "explorer_url", "timings"]),
+        "RemoveResult": object(json!({
+            "code_hash": string.clone(),
+            "refunded_deposit": balance.clone(),
+            "events": events.clone(),
+            "timings": timings.clone(),
+        }), &["timings"]),
+        "RemovedCodes": {
+            "description": "The code removed with `--remove-all-unused`, wrapped in an object with `--timings`",
+            "oneOf": [
+                { "type": "array", "items": { "$ref": "#/definitions/RemoveResult" } },
+                object(json!({
+                    "removed": { "type": "array", "items": { "$ref": "#/definitions/RemoveResult" } },
+                    "timings": timings.clone(),
+                }), &[]),
+            ]
+        },
+        "OwnedCodes": object(json!({
+            "owner": address.clone(),
+            "codes": {
+                "type": "array",
+                "items": object(json!({
+                    "code_hash": string.clone(),
+                    "deposit": balance.clone(),
+                    "refcount": { "type": "integer", "minimum": 0 },
+                    "code_len": { "type": "integer", "minimum": 0 },
+                    "removable": boolean.clone(),
+                }), &[]),
+            },
+            "timings": timings.clone(),
+        }), &["timings"]),
+        "BuildResult": object(json!({
+            "dest_wasm": path.clone(),
+            "metadata_result": {
+                "oneOf": [
+                    { "type": "null" },
+                    object(json!({
+                        "dest_metadata": string.clone(),
+                        "dest_bundle": string.clone(),
+                    }), &[]),
+                ]
+            },
+            "target_directory": string.clone(),
+            "optimization_result": {
+                "oneOf": [
+                    { "type": "null" },
+                    object(json!({
+                        "original_size": { "type": "number", "minimum": 0 },
+                        "optimized_size": { "type": "number", "minimum": 0 },
+                    }), &[]),
+                ]
+            },
+            "build_mode": { "enum": ["Debug", "Release", "Verifiable"] },
+            "build_artifact": { "enum": ["All", "CodeOnly", "CheckOnly"] },
+            "verbosity": { "enum": ["Default", "Quiet", "Verbose"] },
+            "image": path.clone(),
+            "size_report": size_report,
+            "code_hash": string.clone(),
+            "toolchain": object(json!({
+                "rust_toolchain": string.clone(),
+                "cargo_contract_version": string.clone(),
+            }), &[]),
+            "warnings": { "type": "array", "items": string.clone() },
+            "wasm_validation": wasm_validation.clone(),
+        }), &["size_report", "code_hash", "toolchain", "warnings", "wasm_validation"]),
+        "BuildResults": {
+            "description": "The contracts built with `--workspace`",
+            "type": "array",
+            "items": { "$ref": "#/definitions/BuildResult" },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::{
+        assertion::Assertion,
+        call::{
+            CallDryRunResult,
+            XcmCallResult,
+        },
+        instantiate::InstantiateResult,
+        remove::{
+            OwnedCode,
+            OwnedCodes,
+            RemoveResult,
+        },
+        to_json,
+        upload::{
+            UploadDryRunResult,
+            UploadResult,
+        },
+        ExecutionOutcome,
+    };
+    use contract_build::{
+        BuildArtifacts,
+        BuildMode,
+        BuildResult,
+        BuildToolchain,
+        FloatInstructions,
+        HostFunction,
+        MetadataArtifacts,
+        OptimizationResult,
+        OutputType,
+        SizeReport,
+        Verbosity,
+        WasmValidation,
+    };
+    use contract_extrinsics::{
+        pallet_contracts_primitives::StorageDeposit,
+        Address,
+        AssertionFailed,
+        BlockUsage,
+        DebugMessage,
+        Determinism,
+        DisplayBalance,
+        DisplayEvents,
+        ErrorVariant,
+        Event,
+        Field,
+        InstantiateDryRunResult,
+        MaxFeeExceeded,
+        ModuleError,
+        Phase,
+        Ss58Prefix,
+        TimingsReport,
+        TokenMetadata,
+        TransactionTimeout,
+    };
+    use contract_transcode::{
+        Hex,
+        Map,
+        Tuple,
+        Value as ScaleValue,
+    };
+    use jsonschema::JSONSchema;
+    use serde::{
+        de::{
+            IgnoredAny,
+            MapAccess,
+            Visitor,
+        },
+        Deserialize,
+        Deserializer,
+        Serialize,
+    };
+    use sp_weights::Weight;
+    use std::str::FromStr;
+    use subxt::utils::AccountId32;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    fn address(ss58: &str) -> Address {
+        Ss58Prefix::default().address(&ss58.parse::<AccountId32>().unwrap())
+    }
+
+    fn balance(raw: u128) -> DisplayBalance {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".into(),
+            fallback: false,
+        };
+        DisplayBalance::new(raw, Some(&token_metadata))
+    }
+
+    fn assert_valid<T: Serialize>(command: SchemaTarget, instance: &T) {
+        let schema = output_schema(Some(command));
+        let validator = JSONSchema::compile(&schema).expect("schema must compile");
+        let instance = serde_json::to_value(instance).unwrap();
+        if let Err(errors) = validator.validate(&instance) {
+            let errors: Vec<_> = errors.map(|e| e.to_string()).collect();
+            panic!("{instance} does not match the {command:?} schema: {errors:?}");
+        };
+    }
+
+    /// The keys of a JSON object, in the order they are serialized in.
+    struct Keys(Vec<String>);
+
+    impl<'de> Deserialize<'de> for Keys {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct KeysVisitor;
+
+            impl<'de> Visitor<'de> for KeysVisitor {
+                type Value = Keys;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("an object")
+                }
+
+                fn visit_map<A: MapAccess<'de>>(
+                    self,
+                    mut map: A,
+                ) -> Result<Keys, A::Error> {
+                    let mut keys = Vec::new();
+                    while let Some(key) = map.next_key()? {
+                        map.next_value::<IgnoredAny>()?;
+                        keys.push(key);
+                    }
+                    Ok(Keys(keys))
+                }
+            }
+
+            deserializer.deserialize_map(KeysVisitor)
+        }
+    }
+
+    /// Asserts that the fields of the serialized `instance` are in the `x-field-order` of
+    /// the object `schema`.
+    fn assert_field_order<T: Serialize>(schema: &serde_json::Value, instance: &T) {
+        let Keys(keys) = serde_json::from_str(&serde_json::to_string(instance).unwrap())
+            .expect("instance is an object");
+        let expected: Vec<_> = schema["x-field-order"]
+            .as_array()
+            .expect("schema has a field order")
+            .iter()
+            .map(|field| field.as_str().unwrap())
+            .filter(|field| keys.iter().any(|key| key == field))
+            .collect();
+        assert_eq!(keys, expected, "fields are not in the documented order");
+    }
+
+    fn definition(name: &str) -> serde_json::Value {
+        output_schema(None)["definitions"][name].clone()
+    }
+
+    fn wasm_validation() -> WasmValidation {
+        let input = HostFunction {
+            module: "seal0".into(),
+            name: "input".into(),
+        };
+        WasmValidation {
+            imports: vec![input.clone()],
+            float_instructions: vec![FloatInstructions {
+                function: 12,
+                name: Some("core::fmt::float::float_to_decimal_display".into()),
+                krate: Some("core".into()),
+                instructions: vec!["F64Mul".into()],
+            }],
+            unknown_imports: vec![input],
+        }
+    }
+
+    fn ok(value: ScaleValue) -> ScaleValue {
+        ScaleValue::Tuple(Tuple::new(Some("Ok"), vec![value]))
+    }
+
+    fn events() -> DisplayEvents {
+        event_list().into()
+    }
+
+    fn event_list() -> Vec<Event> {
+        let data = [(ScaleValue::String("value".into()), ScaleValue::Bool(true))];
+        vec![
+            Event {
+                pallet: "Contracts".into(),
+                name: "ContractEmitted".into(),
+                fields: vec![
+                    Field {
+                        alias: Some("alice-treasury".into()),
+                        ..Field::new(
+                            "contract".into(),
+                            ScaleValue::Literal(ALICE.into()),
+                            None,
+                        )
+                    },
+                    Field {
+                        indexed: vec!["value".into()],
+                        ..Field::new(
+                            "data".into(),
+                            ScaleValue::Map(Map::from_iter(data)),
+                            Some("Vec<u8>".into()),
+                        )
+                    },
+                ],
+                topics: vec![format!("0x{}", "01".repeat(32))],
+                raw: Some(format!("0x{}0801", "d4".repeat(32))),
+                raw_data: Some("0x01".into()),
+                decode_error: None,
+                metadata: Some("callee.contract".into()),
+            },
+            Event {
+                pallet: "System".into(),
+                name: "ExtrinsicSuccess".into(),
+                fields: vec![Field::new(
+                    "dispatch_info".into(),
+                    ScaleValue::Seq(
+                        vec![ScaleValue::UInt(1), ScaleValue::Int(-1), ScaleValue::Unit]
+                            .into(),
+                    ),
+                    None,
+                )],
+                topics: vec![],
+                raw: None,
+                raw_data: None,
+                decode_error: None,
+                metadata: None,
+            },
+            Event {
+                pallet: "Balances".into(),
+                name: "Withdraw".into(),
+                fields: vec![Field {
+                    denominated: Some("1UNIT".into()),
+                    is_balance: true,
+                    ..Field::new(
+                        "amount".into(),
+                        ScaleValue::UInt(1_000_000_000_000),
+                        Some("T::Balance".into()),
+                    )
+                }],
+                topics: vec![],
+                raw: None,
+                raw_data: None,
+                decode_error: None,
+                metadata: None,
+            },
+            Event {
+                pallet: "Contracts".into(),
+                name: "Called".into(),
+                fields: vec![],
+                topics: vec![],
+                raw: Some("0xff".into()),
+                raw_data: None,
+                decode_error: Some("Not enough data to decode".into()),
+                metadata: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn events_keep_documented_field_order() {
+        let event_schema = &definition("Events")["items"];
+        let field_schema = &event_schema["properties"]["fields"]["items"];
+        for event in event_list() {
+            assert_field_order(event_schema, &event);
+            for field in &event.fields {
+                assert_field_order(field_schema, field);
+            }
+        }
+    }
+
+    #[test]
+    fn call_outputs_match_schema() {
+        let mut dry_run = CallDryRunResult::<DisplayBalance> {
+            origin: None,
+            selector: None,
+            reverted: false,
+            data: ok(ScaleValue::Char('x')),
+            alias: None,
+            decode_error: None,
+            gas_consumed: Weight::from_parts(1_000, 10),
+            gas_required: Weight::from_parts(2_000, 20),
+            storage_deposit: StorageDeposit::Charge(balance(100)),
+            debug_message: None,
+            debug_message_file: None,
+            block_usage: None,
+            events: None,
+            assertion: None,
+        };
+        assert_valid(SchemaTarget::Call, &dry_run);
+
+        dry_run.debug_message = DebugMessage::from_bytes(&[0x68, 0x69, 0xff]);
+        dry_run.block_usage = Some(BlockUsage {
+            ref_time_block_percent: 0.2,
+            proof_size_block_percent: 12.01,
+            ref_time_extrinsic_percent: 0.26,
+            proof_size_extrinsic_percent: 15.01,
+            exceeds_extrinsic_limit: false,
+        });
+        dry_run.events = Some(events());
+        dry_run.selector = Some("0x633aa551".into());
+        dry_run.reverted = true;
+        dry_run.data = ScaleValue::Hex(Hex::from_str("0x0102").unwrap());
+        dry_run.decode_error = Some("Expected a 0 or 1 index for Result".into());
+        dry_run.alias = Some("alice-treasury".into());
+        dry_run.origin = Some(address(BOB));
+        dry_run.assertion = Some(Assertion {
+            passed: false,
+            expected: Some(ok(ScaleValue::UInt(42))),
+            expect_reverted: true,
+            differences: vec!["expected `Ok(42)`, got `0x0102`".into()],
+        });
+        assert_valid(SchemaTarget::Call, &dry_run);
+        assert_field_order(&definition("CallDryRunResult"), &dry_run);
+        assert_valid(SchemaTarget::Call, &events());
+        assert_valid(
+            SchemaTarget::Call,
+            &json!({ "events": events(), "storage_deposit_limit": balance(1150) }),
+        );
+        assert_valid(
+            SchemaTarget::Call,
+            &json!({ "events": events(), "selector": "0x633aa551" }),
+        );
+        assert_valid(
+            SchemaTarget::Call,
+            &json!({
+                "events": events(),
+                "weight_used": { "ref_time": 1_000, "proof_size": 10 },
+                "deposit_refunded": balance(7),
+                "explorer_url": format!("https://xyz.subscan.io/extrinsic/0x{}", "01".repeat(32)),
+            }),
+        );
+        assert_valid(
+            SchemaTarget::Call,
+            &XcmCallResult {
+                dest: 1000,
+                pallet: "XcmPallet",
+                message_id: Some(format!("0x{}", "ab".repeat(32))),
+                extrinsic_hash: format!("0x{}", "01".repeat(32)),
+                block_hash: format!("0x{}", "02".repeat(32)),
+                fee: "parent:1000000000".parse().unwrap(),
+            },
+        );
+    }
+
+    #[test]
+    fn instantiate_outputs_match_schema() {
+        let mut dry_run = InstantiateDryRunResult::<DisplayBalance> {
+            origin: None,
+            result: ok(ScaleValue::Unit),
+            decode_error: None,
+            error: None,
+            contract: address(ALICE),
+            data: "0x9bae9d5e".into(),
+            salt: "0x".into(),
+            reverted: false,
+            gas_consumed: Weight::from_parts(1_000, 10),
+            gas_required: Weight::from_parts(2_000, 20),
+            storage_deposit: StorageDeposit::Refund(balance(7)),
+            debug_message: None,
+            debug_message_file: None,
+            block_usage: None,
+            events: None,
+        };
+        assert_valid(SchemaTarget::Instantiate, &dry_run);
+
+        dry_run.error = Some(ScaleValue::Hex(Hex::from_str("0xdead").unwrap()));
+        dry_run.decode_error = Some("Expected a 0 or 1 index for Result".into());
+        dry_run.reverted = true;
+        dry_run.debug_message = None;
+        dry_run.debug_message_file = Some("debug.bin".into());
+        dry_run.origin = Some(address(BOB));
+        dry_run.events = Some(events());
+        assert_valid(SchemaTarget::Instantiate, &dry_run);
+
+        // `pallet-revive` contract addresses have no SS58 encoding
+        dry_run.contract = Ss58Prefix::default().address(&[0xab_u8; 20]);
+        assert_valid(SchemaTarget::Instantiate, &dry_run);
+        assert_field_order(&definition("InstantiateDryRunResult"), &dry_run);
+
+        let result = InstantiateResult {
+            contract: Some(address(ALICE)),
+            code_hash: None,
+            storage_deposit_limit: Some(balance(1150)),
+            events: events(),
+            existing: false,
+            wasm_validation: Some(wasm_validation()),
+            gas_limit_submitted: Some(Weight::from_parts(2_000, 20)),
+            weight_consumed: Some(Weight::from_parts(1_000, 10)),
+            explorer_url: Some(format!(
+                "https://xyz.subscan.io/extrinsic/0x{}",
+                "01".repeat(32)
+            )),
+            outcome: ExecutionOutcome {
+                weight_used: Some(Weight::from_parts(1_000, 10)),
+                deposit_refunded: Some(DisplayBalance::new(7u128, None)),
+            },
+        };
+        assert_valid(SchemaTarget::Instantiate, &result);
+        assert_field_order(&definition("InstantiateResult"), &result);
+        let existing = InstantiateResult {
+            contract: Some(address(ALICE)),
+            code_hash: None,
+            storage_deposit_limit: None,
+            events: Vec::new().into(),
+            existing: true,
+            wasm_validation: None,
+            gas_limit_submitted: None,
+            weight_consumed: None,
+            explorer_url: None,
+            outcome: Default::default(),
+        };
+        assert_valid(SchemaTarget::Instantiate, &existing);
+    }
+
+    #[test]
+    fn upload_dry_run_matches_schema() {
+        let dry_run = UploadDryRunResult::<DisplayBalance> {
+            result: "Success!".into(),
+            code_hash: format!("0x{}", "ab".repeat(32)),
+            code_size: 1024,
+            deposit: balance(42),
+            determinism: Determinism::Relaxed,
+            wasm_validation: Some(wasm_validation()),
+        };
+        assert_valid(SchemaTarget::Upload, &dry_run);
+        let mut result = UploadResult::<DisplayBalance> {
+            code_hash: format!("0x{}", "ab".repeat(32)),
+            code_size: 1024,
+            deposit: Some(balance(42)),
+            block_hash: format!("0x{}", "cd".repeat(32)),
+            newly_uploaded: true,
+            determinism: Determinism::Enforced,
+            wasm_validation: None,
+            events: events(),
+            explorer_url: Some("https://xyz.subscan.io/block/42".to_string()),
+        };
+        assert_valid(SchemaTarget::Upload, &result);
+        result.deposit = None;
+        result.newly_uploaded = false;
+        assert_valid(SchemaTarget::Upload, &result);
+    }
+
+    #[test]
+    fn timings_match_schema() {
+        let timings = TimingsReport {
+            phases: vec![
+                (Phase::Connect, 412),
+                (Phase::TokenMetadata, 35),
+                (Phase::DryRun, 120),
+                (Phase::Sign, 8),
+                (Phase::InBlock, 6012),
+            ],
+            total: 6630,
+        };
+        let removed = RemoveResult::<DisplayBalance> {
+            code_hash: format!("0x{}", "ab".repeat(32)),
+            refunded_deposit: balance(42),
+            events: events(),
+        };
+        let json = to_json(&removed, Some(timings), false).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["timings"]["in_block"], 6012);
+        assert_eq!(json["timings"]["total"], 6630);
+        assert_valid(SchemaTarget::Remove, &json);
+
+        let removed_codes = json!({ "removed": [removed] });
+        let json = to_json(
+            &removed_codes,
+            Some(TimingsReport {
+                phases: Vec::new(),
+                total: 3,
+            }),
+            true,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_valid(SchemaTarget::Remove, &json);
+    }
+
+    #[test]
+    fn remove_outputs_match_schema() {
+        let code_hash = format!("0x{}", "ab".repeat(32));
+        let removed = RemoveResult::<DisplayBalance> {
+            code_hash: code_hash.clone(),
+            refunded_deposit: balance(42),
+            events: events(),
+        };
+        assert_valid(SchemaTarget::Remove, &removed);
+        assert_valid(SchemaTarget::Remove, &[removed]);
+        let owned = OwnedCodes::<DisplayBalance> {
+            owner: address(ALICE),
+            codes: vec![OwnedCode {
+                code_hash,
+                deposit: balance(42),
+                refcount: 0,
+                code_len: 1024,
+                removable: true,
+            }],
+        };
+        assert_valid(SchemaTarget::Remove, &owned);
+    }
+
+    #[test]
+    fn build_outputs_match_schema() {
+        let result = BuildResult {
+            dest_wasm: Some("/path/to/contract.wasm".into()),
+            metadata_result: Some(MetadataArtifacts {
+                dest_metadata: "/path/to/contract.json".into(),
+                dest_bundle: "/path/to/contract.contract".into(),
+            }),
+            target_directory: "/path/to/target".into(),
+            optimization_result: Some(OptimizationResult {
+                original_size: 64.0,
+                optimized_size: 32.0,
+            }),
+            build_mode: BuildMode::Release,
+            build_artifact: BuildArtifacts::All,
+            verbosity: Verbosity::Default,
+            image: None,
+            size_report: Some(SizeReport::new(b"\0asm\x01\0\0\0").unwrap()),
+            code_hash: Some(format!("0x{}", "01".repeat(32))),
+            toolchain: Some(BuildToolchain {
+                rust_toolchain: "stable-x86_64-unknown-linux-gnu".into(),
+                cargo_contract_version: "4.0.0".parse().unwrap(),
+            }),
+            warnings: vec!["The ink! version is not supported.".into()],
+            wasm_validation: Some(wasm_validation()),
+            output_type: OutputType::Json,
+        };
+        assert_valid(SchemaTarget::Build, &result);
+        assert_valid(SchemaTarget::Build, &[result]);
+
+        let check = BuildResult {
+            dest_wasm: None,
+            metadata_result: None,
+            target_directory: "/path/to/target".into(),
+            optimization_result: None,
+            build_mode: BuildMode::Debug,
+            build_artifact: BuildArtifacts::CheckOnly,
+            verbosity: Verbosity::Quiet,
+            image: None,
+            size_report: None,
+            code_hash: None,
+            toolchain: None,
+            warnings: Vec::new(),
+            wasm_validation: None,
+            output_type: OutputType::Json,
+        };
+        assert_valid(SchemaTarget::Build, &check);
+    }
+
+    #[test]
+    fn errors_match_schema() {
+        let module_error = ErrorVariant::Module(ModuleError {
+            pallet: "Contracts".into(),
+            error: "CodeNotFound".into(),
+            docs: vec!["No code could be found at the supplied code hash.".into()],
+            hint: None,
+            existing_contract: None,
+        });
+        let generic_error = ErrorVariant::from("Transaction not submitted");
+        let hinted_error = ErrorVariant::Module(ModuleError {
+            pallet: "Contracts".into(),
+            error: "ContractTrapped".into(),
+            docs: vec!["Contract trapped during execution.".into()],
+            hint: None,
+            existing_contract: None,
+        })
+        .with_hint("The message `flip` is not payable.".into());
+        let duplicate_error = ErrorVariant::Module(ModuleError {
+            pallet: "Contracts".into(),
+            error: "DuplicateContract".into(),
+            docs: vec!["A contract with the same AccountId already exists.".into()],
+            hint: None,
+            existing_contract: None,
+        })
+        .with_existing_contract(address(ALICE));
+        let max_fee_error = ErrorVariant::MaxFeeExceeded(MaxFeeExceeded {
+            estimated_fee: balance(1_500_000_000_000),
+            max_fee: DisplayBalance::new(1_000_000_000_000u128, None),
+        });
+        let assertion_error = ErrorVariant::AssertionFailed(AssertionFailed {
+            error: "Assertion failed: expected `Ok(42)`, got `Ok(41)`".into(),
+        });
+        let timeout_error = ErrorVariant::Timeout(TransactionTimeout::new(
+            "0x0a1b".into(),
+            "broadcasted".into(),
+            42,
+            60,
+        ));
+        for command in [
+            SchemaTarget::Call,
+            SchemaTarget::Instantiate,
+            SchemaTarget::Upload,
+            SchemaTarget::Remove,
+        ] {
+            assert_valid(command, &module_error);
+            assert_valid(command, &generic_error);
+            assert_valid(command, &hinted_error);
+            assert_valid(command, &duplicate_error);
+            assert_valid(command, &max_fee_error);
+            assert_valid(command, &timeout_error);
+        }
+        assert_valid(SchemaTarget::Call, &assertion_error);
+    }
+
+    #[test]
+    fn schema_rejects_unknown_fields() {
+        let schema = output_schema(Some(SchemaTarget::Upload));
+        let validator = JSONSchema::compile(&schema).unwrap();
+        let renamed = json!({ "result": "Success!", "hash": "0x00", "deposit": 1 });
+        assert!(!validator.is_valid(&renamed));
+
+        // balances are strings, so that consumers parsing numbers as floats don't lose
+        // precision
+        let schema = output_schema(Some(SchemaTarget::Remove));
+        let validator = JSONSchema::compile(&schema).unwrap();
+        let mut removed = json!({
+            "code_hash": "0x00",
+            "refunded_deposit": balance(u128::MAX),
+            "events": [],
+        });
+        assert!(validator.is_valid(&removed));
+        removed["refunded_deposit"] = json!(42);
+        assert!(!validator.is_valid(&removed));
+    }
+
+    #[test]
+    fn schema_of_all_commands_compiles() {
+        let schema = output_schema(None);
+        assert!(JSONSchema::compile(&schema).is_ok());
+        assert_eq!(schema["anyOf"].as_array().unwrap().len(), 14);
+    }
+}
@@ -18,21 +18,40 @@ use crate::ErrorVariant;
 use std::fmt::Debug;
 
 use super::{
+    config::{
+        with_chain_config,
+        ExtrinsicBalance,
+        ExtrinsicConfig,
+    },
     create_signer,
+    denominate_balance,
     parse_code_hash,
+    prompt_confirm_tx,
+    AccountIdArg,
     CLIExtrinsicOpts,
 };
 use anyhow::Result;
-use contract_build::name_value_println;
+use contract_build::{
+    name_value_println,
+    util::DEFAULT_KEY_COL_WIDTH,
+};
 use contract_extrinsics::{
+    fetch_all_code_info,
+    Address,
+    Connection,
+    DisplayBalance,
     DisplayEvents,
     ExtrinsicOptsBuilder,
+    MortalityParams,
+    Progress,
     RemoveCommandBuilder,
     RemoveExec,
+    Ss58Prefix,
     TokenMetadata,
 };
-use ink_env::DefaultEnvironment;
+use ink_env::Environment;
 use subxt::{
+    config::ExtrinsicParams,
     Config,
     PolkadotConfig as DefaultConfig,
 };
@@ -42,12 +61,22 @@ use subxt_signer::sr25519::Keypair;
 #[clap(name = "remove", about = "Remove a contract's code")]
 pub struct RemoveCommand {
     /// The hash of the smart contract code already uploaded to the chain.
-    #[clap(long, value_parser = parse_code_hash)]
+    #[clap(long, value_parser = parse_code_hash, conflicts_with_all = ["list", "remove_all_unused"])]
     code_hash: Option<<DefaultConfig as Config>::Hash>,
+    /// List the code uploaded by the signer, or by the `--owner`, with the deposit held
+    /// for it, instead of removing code.
+    #[clap(long)]
+    list: bool,
+    /// The account to list the uploaded code of, instead of the signer.
+    #[clap(long, requires = "list")]
+    owner: Option<AccountIdArg>,
+    /// Remove all code uploaded by the signer which is not used by any contract.
+    #[clap(long, conflicts_with = "list")]
+    remove_all_unused: bool,
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
-    /// Export the call output as JSON.
-    #[clap(long, conflicts_with = "verbose")]
+    /// Export the remove output in JSON format.
+    #[clap(long, conflicts_with_all = ["verbose", "quiet"])]
     output_json: bool,
 }
 
@@ -57,59 +86,182 @@ impl RemoveCommand {
         self.output_json
     }
 
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts(&self) -> &CLIExtrinsicOpts {
+        &self.extrinsic_cli_opts
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts_mut(&mut self) -> &mut CLIExtrinsicOpts {
+        &mut self.extrinsic_cli_opts
+    }
+
     pub async fn handle(&self) -> Result<(), ErrorVariant> {
-        let token_metadata =
-            TokenMetadata::query::<DefaultConfig>(&self.extrinsic_cli_opts.url).await?;
+        if self.extrinsic_cli_opts.progress(self.output_json()) {
+            Progress::Connecting.report();
+        }
+        let connection = self.extrinsic_cli_opts.connect().await?;
+        let chain_config = self.extrinsic_cli_opts.chain_config(&connection);
+        with_chain_config!(chain_config, |C, E| {
+            self.handle_with_config::<C, E>(connection.with_config()?)
+                .await
+        })
+    }
+
+    async fn handle_with_config<C, E>(
+        &self,
+        connection: Connection<C>,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let token_metadata = self.extrinsic_cli_opts.token_metadata(&connection).await?;
+
+        let ss58_prefix = self.extrinsic_cli_opts.ss58_prefix(&connection).await?;
+
+        if self.list {
+            let owner = match &self.owner {
+                Some(owner) => {
+                    owner.note_ss58_prefix_mismatch(ss58_prefix);
+                    owner.account_id().clone()
+                }
+                None => {
+                    create_signer(self.extrinsic_cli_opts.suri()?)?
+                        .public_key()
+                        .to_account_id()
+                }
+            };
+            let owned_codes = self
+                .owned_codes::<C, E>(&connection, &owner, &token_metadata)
+                .await?;
+            let owned_codes = OwnedCodes {
+                owner: ss58_prefix.address(&owner),
+                codes: owned_codes,
+            };
+            if self.output_json() {
+                println!("{}", self.extrinsic_cli_opts.to_json(&owned_codes)?);
+            } else {
+                owned_codes.print();
+            }
+            return Ok(())
+        }
 
-        let signer: Keypair = create_signer(&self.extrinsic_cli_opts.suri)?;
+        let signer: Keypair = create_signer(self.extrinsic_cli_opts.suri()?)?;
+        if self.remove_all_unused {
+            return self
+                .remove_all_unused::<C, E>(
+                    &connection,
+                    signer,
+                    ss58_prefix,
+                    &token_metadata,
+                )
+                .await
+        }
+
+        let removed = self
+            .remove_code::<C, E>(
+                &connection,
+                signer,
+                self.code_hash,
+                ss58_prefix,
+                &token_metadata,
+            )
+            .await?;
+        if self.output_json() {
+            println!("{}", self.extrinsic_cli_opts.to_json(&removed)?);
+        }
+        Ok(())
+    }
+
+    /// Submits the removal of the code, printing the result unless exporting JSON.
+    async fn remove_code<C, E>(
+        &self,
+        connection: &Connection<C>,
+        signer: Keypair,
+        code_hash: Option<C::Hash>,
+        ss58_prefix: Ss58Prefix,
+        token_metadata: &TokenMetadata,
+    ) -> Result<RemoveResult<DisplayBalance>, ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
             .file(self.extrinsic_cli_opts.file.clone())
+            .expected_hash(self.extrinsic_cli_opts.expected_hash.map(|hash| hash.0))
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
+            .require_signed_artifacts(self.extrinsic_cli_opts.require_signed_artifacts)
             .url(self.extrinsic_cli_opts.url.clone())
+            .ss58_prefix(ss58_prefix)
             .storage_deposit_limit(
                 self.extrinsic_cli_opts
                     .storage_deposit_limit
-                    .clone()
-                    .map(|bv| bv.denominate_balance(&token_metadata))
+                    .as_ref()
+                    .map(|bv| denominate_balance(bv, token_metadata))
                     .transpose()?,
             )
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .submit_retries(self.extrinsic_cli_opts.submit_retries)
+            .timeout(self.extrinsic_cli_opts.timeout())
+            .timings(self.extrinsic_cli_opts.recorder().clone())
+            .max_fee(self.extrinsic_cli_opts.max_fee(token_metadata)?)
+            .progress(self.extrinsic_cli_opts.progress(self.output_json()))
+            .tx_status(self.extrinsic_cli_opts.tx_status(self.output_json())?)
+            .verbosity(
+                self.extrinsic_cli_opts
+                    .extrinsic_verbosity(self.output_json())?,
+            )
+            .raw_values(self.extrinsic_cli_opts.raw_values)
+            .include_raw(self.extrinsic_cli_opts.include_raw)
+            .token_metadata(Some(token_metadata.clone()))
             .done();
-        let remove_exec: RemoveExec<DefaultConfig, DefaultEnvironment, Keypair> =
+        let remove_exec: RemoveExec<C, E, Keypair> =
             RemoveCommandBuilder::new(extrinsic_opts)
-                .code_hash(self.code_hash)
+                .code_hash(code_hash)
+                .connection(Some(connection.clone()))
                 .done()
                 .await?;
         let remove_result = remove_exec.remove_code().await?;
-        let display_events =
-            DisplayEvents::from_events::<DefaultConfig, DefaultEnvironment>(
-                &remove_result.events,
-                Some(remove_exec.transcoder()),
-                &remove_exec.client().metadata(),
-            )?;
-        let output_events = if self.output_json() {
-            display_events.to_json()?
-        } else {
-            display_events.display_events::<DefaultEnvironment>(
-                self.extrinsic_cli_opts.verbosity().unwrap(),
-                &token_metadata,
-            )?
-        };
+        let address_book = self.extrinsic_cli_opts.address_book()?;
+        let display_events = DisplayEvents::from_events::<C, E>(
+            &remove_result.events,
+            Some(remove_exec.transcoder()),
+            &[],
+            &remove_exec.client().metadata(),
+            ss58_prefix,
+            self.extrinsic_cli_opts.raw_values,
+            self.extrinsic_cli_opts.include_raw,
+            Some(token_metadata),
+        )?
+        .with_aliases(&address_book);
         if let Some(code_removed) = remove_result.code_removed {
-            let remove_result: <DefaultConfig as Config>::Hash = code_removed.code_hash;
-
-            if self.output_json() {
-                // Create a JSON object with the events and the removed code hash.
-                let json_object = serde_json::json!({
-                    "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
-                    "code_hash": remove_result,
-                });
-                let json_object = serde_json::to_string_pretty(&json_object)?;
-                println!("{}", json_object);
-            } else {
-                println!("{}", output_events);
-                name_value_println!("Code hash", format!("{remove_result:?}"));
+            let remove_result = RemoveResult {
+                code_hash: format!("{:?}", code_removed.code_hash),
+                refunded_deposit: DisplayBalance::new(
+                    code_removed.deposit_released,
+                    Some(token_metadata),
+                ),
+                events: display_events,
+            };
+            if !self.output_json() {
+                println!(
+                    "{}",
+                    remove_result.events.display_events::<E>(
+                        self.extrinsic_cli_opts.verbosity()?,
+                        self.extrinsic_cli_opts.event_display(),
+                        token_metadata,
+                    )?
+                );
+                remove_result.print();
             }
-            Result::<(), ErrorVariant>::Ok(())
+            Ok(remove_result)
         } else {
             let error_code_hash = hex::encode(remove_exec.final_code_hash());
             Err(anyhow::anyhow!(
@@ -119,4 +271,170 @@ impl RemoveCommand {
             .into())
         }
     }
+
+    /// Removes all code owned by the signer which is not used by any contract, after a
+    /// single confirmation.
+    async fn remove_all_unused<C, E>(
+        &self,
+        connection: &Connection<C>,
+        signer: Keypair,
+        ss58_prefix: Ss58Prefix,
+        token_metadata: &TokenMetadata,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let owner = signer.public_key().to_account_id();
+        let unused: Vec<_> = self
+            .owned_codes::<C, E>(connection, &owner, token_metadata)
+            .await?
+            .into_iter()
+            .filter(|code| code.removable)
+            .collect();
+        if unused.is_empty() {
+            if self.output_json() {
+                println!("[]");
+            } else {
+                println!(
+                    "No unused code is owned by {}",
+                    ss58_prefix.format_account(&owner)
+                );
+            }
+            return Ok(())
+        }
+
+        if !self.extrinsic_cli_opts.skip_confirm {
+            prompt_confirm_tx(|| {
+                for code in &unused {
+                    name_value_println!(
+                        "Code hash",
+                        format!("{} (deposit {})", code.code_hash, code.deposit),
+                        DEFAULT_KEY_COL_WIDTH
+                    );
+                }
+                name_value_println!(
+                    "Mortality",
+                    self.extrinsic_cli_opts.mortality.describe(),
+                    DEFAULT_KEY_COL_WIDTH
+                );
+            })?;
+        }
+
+        let mut removed = Vec::new();
+        for code in &unused {
+            let code_hash = parse_code_hash(&code.code_hash)?;
+            removed.push(
+                self.remove_code::<C, E>(
+                    connection,
+                    signer.clone(),
+                    Some(code_hash),
+                    ss58_prefix,
+                    token_metadata,
+                )
+                .await?,
+            );
+        }
+        if self.output_json() {
+            let output = match self.extrinsic_cli_opts.timings() {
+                Some(_) => {
+                    let removed = serde_json::json!({ "removed": removed });
+                    self.extrinsic_cli_opts.to_json(&removed)?
+                }
+                None => self.extrinsic_cli_opts.to_json_without_timings(&removed)?,
+            };
+            println!("{output}");
+        }
+        Ok(())
+    }
+
+    /// Returns the code uploaded by the `owner`, with the deposits denominated with the
+    /// `token_metadata`.
+    async fn owned_codes<C, E>(
+        &self,
+        connection: &Connection<C>,
+        owner: &C::AccountId,
+        token_metadata: &TokenMetadata,
+    ) -> Result<Vec<OwnedCode<DisplayBalance>>>
+    where
+        C: ExtrinsicConfig,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let codes = fetch_all_code_info::<C, E>(connection.client(), connection.rpc())
+            .await?
+            .into_iter()
+            .filter(|code| code.owner() == owner)
+            .map(|code| {
+                OwnedCode {
+                    code_hash: format!("{:?}", code.code_hash()),
+                    deposit: DisplayBalance::new(*code.deposit(), Some(token_metadata)),
+                    refcount: code.refcount(),
+                    code_len: code.code_len(),
+                    removable: code.is_removable(),
+                }
+            })
+            .collect();
+        Ok(codes)
+    }
+}
+
+/// The code uploaded by an account.
+#[derive(serde::Serialize)]
+pub struct OwnedCodes<Balance> {
+    pub owner: Address,
+    pub codes: Vec<OwnedCode<Balance>>,
+}
+
+/// Result of a submitted code removal.
+#[derive(serde::Serialize)]
+pub struct RemoveResult<Balance> {
+    /// The hash of the removed code
+    pub code_hash: String,
+    /// The storage deposit refunded to the owner of the code
+    pub refunded_deposit: Balance,
+    /// The events emitted from the remove extrinsic invocation
+    pub events: DisplayEvents,
+}
+
+impl RemoveResult<DisplayBalance> {
+    pub fn print(&self) {
+        name_value_println!("Code hash", self.code_hash);
+        name_value_println!("Refunded", self.refunded_deposit.to_string());
+    }
+}
+
+/// Code uploaded by an account, with the deposit held for it.
+#[derive(serde::Serialize)]
+pub struct OwnedCode<Balance> {
+    pub code_hash: String,
+    pub deposit: Balance,
+    /// The number of contracts using the code
+    pub refcount: u64,
+    /// The length of the code in bytes
+    pub code_len: u32,
+    /// Whether no contract uses the code, so that it can be removed
+    pub removable: bool,
+}
+
+impl OwnedCodes<DisplayBalance> {
+    pub fn print(&self) {
+        if self.codes.is_empty() {
+            println!("No code is owned by {}", self.owner);
+            return
+        }
+        for code in &self.codes {
+            name_value_println!("Code hash", code.code_hash);
+            name_value_println!("Deposit", code.deposit.to_string());
+            name_value_println!("Refcount", code.refcount.to_string());
+            name_value_println!("Code size", format!("{} bytes", code.code_len));
+            if code.removable {
+                name_value_println!("Removable", "yes".green().bold().to_string());
+            }
+            println!();
+        }
+    }
 }
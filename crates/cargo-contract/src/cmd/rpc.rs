@@ -22,7 +22,10 @@ use contract_extrinsics::{
 };
 use subxt::ext::scale_value;
 
-use super::MAX_KEY_COL_WIDTH;
+use super::{
+    parse_node_url,
+    MAX_KEY_COL_WIDTH,
+};
 
 #[derive(Debug, clap::Args)]
 #[clap(name = "rpc", about = "Make a raw RPC call")]
@@ -32,11 +35,11 @@ pub struct RpcCommand {
     /// The arguments of the method to call.
     #[clap(num_args = 0..)]
     params: Vec<String>,
-    /// Websockets url of a substrate node.
+    /// Websockets or HTTP url of a substrate node.
     #[clap(
         name = "url",
         long,
-        value_parser,
+        value_parser = parse_node_url,
         default_value = "ws://localhost:9944"
     )]
     url: url::Url,
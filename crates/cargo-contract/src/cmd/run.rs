@@ -0,0 +1,805 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scripted deployments: the upload, instantiate and call steps of a YAML script are
+//! executed in order by one signer over one connection. The outputs of a step, e.g.
+//! the address of an instantiated contract, are used in the following steps as
+//! `{{steps.<id>.<output>}}`.
+//!
+//! If a step fails, the outputs of the steps before it are written to a state file, so
+//! that the run can continue from the failed step with `--resume`.
+
+use super::{
+    assertion::ExpectOpts,
+    create_signer,
+    denominate_balance,
+    parse_node_url,
+    ContractAddressArg,
+};
+use crate::ErrorVariant;
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_build::util::decode_hex;
+use contract_extrinsics::{
+    BalanceVariant,
+    CallCommandBuilder,
+    Connection,
+    ExtrinsicOptsBuilder,
+    InstantiateCommandBuilder,
+    TokenMetadata,
+    UploadCommandBuilder,
+};
+use ink_env::DefaultEnvironment;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sp_core::Bytes;
+use sp_weights::Weight;
+use std::{
+    collections::{
+        BTreeMap,
+        HashSet,
+    },
+    fmt::Display,
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+};
+use subxt::PolkadotConfig as DefaultConfig;
+use subxt_signer::sr25519::Keypair;
+
+/// The outputs of a step by their names.
+type StepOutputs = BTreeMap<String, String>;
+
+/// The outputs of the steps which ran, by the ids of the steps.
+type Outputs = BTreeMap<String, StepOutputs>;
+
+/// Run a script of upload, instantiate and call steps.
+#[derive(Debug, clap::Args)]
+#[clap(name = "run")]
+pub struct RunCommand {
+    /// The script in YAML format.
+    #[clap(value_parser)]
+    script: PathBuf,
+    /// Websocket endpoint of the node to run the script against.
+    #[clap(
+        long,
+        value_parser = parse_node_url,
+        default_value = "ws://localhost:9944"
+    )]
+    url: url::Url,
+    /// Secret key URI of the account signing all steps.
+    #[clap(short, long, env = "CARGO_CONTRACT_SURI", hide_env_values = true)]
+    suri: String,
+    /// Continue from the step which failed in the previous run, with the outputs of the
+    /// steps before it read from the state file.
+    #[clap(long)]
+    resume: bool,
+    /// The state file written if a step fails. Defaults to the path of the script with
+    /// the `.state.json` extension.
+    #[clap(long, value_parser)]
+    state_file: Option<PathBuf>,
+}
+
+/// The steps of a run, read from a YAML file.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Script {
+    steps: Vec<Step>,
+}
+
+/// What a [`Step`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Action {
+    Upload,
+    Instantiate,
+    Call,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Upload => f.write_str("upload"),
+            Self::Instantiate => f.write_str("instantiate"),
+            Self::Call => f.write_str("call"),
+        }
+    }
+}
+
+/// A step of a [`Script`]. The string fields may refer to the outputs of the previous
+/// steps.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Step {
+    /// The id the outputs of the step are referred to with.
+    id: String,
+    action: Option<Action>,
+    /// The contract artifact relative to the script, defaults to the contract of the
+    /// manifest in the current directory.
+    file: Option<PathBuf>,
+    /// The address of the contract to call.
+    contract: Option<String>,
+    /// The constructor to instantiate the contract with, `new` by default.
+    constructor: Option<String>,
+    /// The message to call.
+    message: Option<String>,
+    /// The arguments of the constructor or message.
+    #[serde(default)]
+    args: Vec<String>,
+    /// The value transferred to the contract.
+    value: Option<String>,
+    /// The gas limit, estimated with a dry-run if not given.
+    gas: Option<u64>,
+    /// The proof size, estimated with a dry-run if not given.
+    proof_size: Option<u64>,
+    /// The salt of the instantiated contract as hex.
+    salt: Option<String>,
+    /// Only dry-run the step.
+    #[serde(default)]
+    dry_run: bool,
+    /// The value the dry-run of a call is expected to return.
+    expect: Option<String>,
+    /// Whether the dry-run of a call is expected to revert.
+    #[serde(default)]
+    expect_reverted: bool,
+}
+
+impl Script {
+    /// Reads the script and checks its steps.
+    fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let script: Self = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Invalid script {}", path.display()))?;
+        let mut ids = HashSet::new();
+        for step in &script.steps {
+            step.check()
+                .with_context(|| format!("Invalid script {}", path.display()))?;
+            if !ids.insert(&step.id) {
+                return Err(anyhow!(
+                    "Invalid script {}: the id `{}` is used by more than one step",
+                    path.display(),
+                    step.id
+                ))
+            }
+        }
+        Ok(script)
+    }
+}
+
+impl Step {
+    fn action(&self) -> Action {
+        self.action.expect("the action is checked when the script is loaded")
+    }
+
+    /// Returns an error if the step misses a field its action requires, or sets one
+    /// which doesn't apply to it.
+    fn check(&self) -> Result<()> {
+        if self.id.is_empty()
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(anyhow!(
+                "The step id `{}` must consist of letters, digits, `_` and `-`",
+                self.id
+            ))
+        }
+        let action = self.action.ok_or_else(|| {
+            anyhow!(
+                "The step `{}` requires an `action`, one of `upload`, `instantiate` \
+                 or `call`",
+                self.id
+            )
+        })?;
+        let unsupported = match action {
+            Action::Upload => {
+                vec![
+                    ("contract", self.contract.is_some()),
+                    ("constructor", self.constructor.is_some()),
+                    ("message", self.message.is_some()),
+                    ("args", !self.args.is_empty()),
+                    ("value", self.value.is_some()),
+                    ("gas", self.gas.is_some()),
+                    ("proof_size", self.proof_size.is_some()),
+                    ("salt", self.salt.is_some()),
+                    ("expect", self.expect.is_some()),
+                    ("expect_reverted", self.expect_reverted),
+                ]
+            }
+            Action::Instantiate => {
+                vec![
+                    ("contract", self.contract.is_some()),
+                    ("message", self.message.is_some()),
+                    ("expect", self.expect.is_some()),
+                    ("expect_reverted", self.expect_reverted),
+                ]
+            }
+            Action::Call => {
+                vec![
+                    ("constructor", self.constructor.is_some()),
+                    ("salt", self.salt.is_some()),
+                ]
+            }
+        };
+        if let Some((field, _)) = unsupported.iter().find(|(_, set)| *set) {
+            return Err(anyhow!(
+                "The `{action}` step `{}` does not support `{field}`",
+                self.id
+            ))
+        }
+        if action == Action::Call {
+            for (field, set) in [
+                ("contract", self.contract.is_some()),
+                ("message", self.message.is_some()),
+            ] {
+                if !set {
+                    return Err(anyhow!(
+                        "The `call` step `{}` requires `{field}`",
+                        self.id
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Describes the step for the progress output.
+    fn describe(&self) -> String {
+        let target = match self.action() {
+            Action::Upload => String::new(),
+            Action::Instantiate => {
+                format!(
+                    " {}({})",
+                    self.constructor.as_deref().unwrap_or("new"),
+                    self.args.join(", ")
+                )
+            }
+            Action::Call => {
+                format!(
+                    " {}({})",
+                    self.message.as_deref().unwrap_or_default(),
+                    self.args.join(", ")
+                )
+            }
+        };
+        let dry_run = if self.dry_run { " (dry-run)" } else { "" };
+        format!("{}: {}{target}{dry_run}", self.id, self.action())
+    }
+}
+
+/// Replaces the references `{{steps.<id>.<output>}}` in the `input` with the outputs of
+/// the previous steps.
+fn substitute(input: &str, outputs: &Outputs) -> Result<String> {
+    let mut substituted = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        substituted.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        let end = reference
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unterminated `{{{{` in `{input}`"))?;
+        substituted.push_str(resolve(reference[..end].trim(), outputs)?);
+        rest = &reference[end + 2..];
+    }
+    substituted.push_str(rest);
+    Ok(substituted)
+}
+
+/// Returns the output referred to as `steps.<id>.<output>`.
+fn resolve<'a>(reference: &str, outputs: &'a Outputs) -> Result<&'a str> {
+    let mut parts = reference.split('.');
+    let (Some("steps"), Some(id), Some(output), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!(
+            "Invalid reference `{{{{{reference}}}}}`, expected \
+             `{{{{steps.<id>.<output>}}}}`"
+        ))
+    };
+    let step = outputs.get(id).ok_or_else(|| {
+        anyhow!("`{{{{{reference}}}}}` refers to the step `{id}` which did not run before")
+    })?;
+    step.get(output).map(String::as_str).ok_or_else(|| {
+        let available = step.keys().cloned().collect::<Vec<_>>().join(", ");
+        anyhow!("The step `{id}` has no output `{output}`, it has: {available}")
+    })
+}
+
+/// The state of a failed run, written to continue it with `--resume`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct RunState {
+    /// The id of the step which failed.
+    failed_step: String,
+    /// The error the step failed with.
+    error: String,
+    /// The outputs of the steps before the failed step.
+    outputs: Outputs,
+}
+
+impl RunState {
+    fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!(
+                "Failed to read the state file {}, `--resume` requires a failed run",
+                path.display()
+            )
+        })?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid state file {}", path.display()))
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write the state file {}", path.display()))
+    }
+
+    /// Returns the index of the failed step in the `script`, checking that the outputs
+    /// of all steps before it are known.
+    fn resume_at(&self, script: &Script) -> Result<usize> {
+        let failed = script
+            .steps
+            .iter()
+            .position(|step| step.id == self.failed_step)
+            .ok_or_else(|| {
+                anyhow!(
+                    "The failed step `{}` of the state file is not in the script",
+                    self.failed_step
+                )
+            })?;
+        if let Some(step) = script.steps[..failed]
+            .iter()
+            .find(|step| !self.outputs.contains_key(&step.id))
+        {
+            return Err(anyhow!(
+                "The step `{}` before the failed step `{}` did not run, start the run \
+                 from the beginning without `--resume`",
+                step.id,
+                self.failed_step
+            ))
+        }
+        Ok(failed)
+    }
+}
+
+impl RunCommand {
+    pub async fn run(&self) -> Result<(), ErrorVariant> {
+        let script = Script::load(&self.script)?;
+        let state_file = self
+            .state_file
+            .clone()
+            .unwrap_or_else(|| self.script.with_extension("state.json"));
+        let (first, mut outputs) = if self.resume {
+            let state = RunState::load(&state_file)?;
+            (state.resume_at(&script)?, state.outputs)
+        } else {
+            (0, Outputs::new())
+        };
+
+        let connection = Connection::connect(self.url.as_str()).await?;
+        let runner = Runner {
+            token_metadata: TokenMetadata::query_with(connection.rpc()).await?,
+            connection,
+            signer: create_signer(&self.suri)?,
+            url: self.url.clone(),
+            script_dir: self
+                .script
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        };
+        for step in &script.steps[..first] {
+            println!("{} {}", "SKIP".yellow().bold(), step.describe());
+        }
+        // the steps are submitted one after another, each once the previous one is
+        // included, so that the nonce of the signer is taken from the chain
+        for step in &script.steps[first..] {
+            match runner.run_step(step, &outputs).await {
+                Ok(step_outputs) => {
+                    println!("{} {}", "DONE".green().bold(), step.describe());
+                    for (name, output) in &step_outputs {
+                        println!("     {name}: {output}");
+                    }
+                    outputs.insert(step.id.clone(), step_outputs);
+                }
+                Err(error) => {
+                    println!("{} {}", "FAIL".red().bold(), step.describe());
+                    for line in error.to_string().lines() {
+                        println!("     {line}");
+                    }
+                    RunState {
+                        failed_step: step.id.clone(),
+                        error: error.to_string(),
+                        outputs,
+                    }
+                    .write(&state_file)?;
+                    return Err(anyhow!(
+                        "The step `{}` failed. The outputs of the steps before it were \
+                         written to {}, continue from the failed step with `--resume`",
+                        step.id,
+                        state_file.display()
+                    )
+                    .into())
+                }
+            }
+        }
+        if state_file.exists() {
+            fs::remove_file(&state_file).with_context(|| {
+                format!("Failed to remove the state file {}", state_file.display())
+            })?;
+        }
+        println!("\n{} steps completed", script.steps.len());
+        Ok(())
+    }
+}
+
+/// The chain and the account the steps are executed with.
+struct Runner {
+    connection: Connection<DefaultConfig>,
+    signer: Keypair,
+    url: url::Url,
+    token_metadata: TokenMetadata,
+    /// The directory of the script, which the contract artifacts are relative to.
+    script_dir: PathBuf,
+}
+
+impl Runner {
+    fn extrinsic_opts(
+        &self,
+        step: &Step,
+    ) -> ExtrinsicOptsBuilder<DefaultConfig, DefaultEnvironment, Keypair> {
+        ExtrinsicOptsBuilder::new(self.signer.clone())
+            .file(step.file.as_ref().map(|file| self.script_dir.join(file)))
+            .url(self.url.clone())
+            .token_metadata(Some(self.token_metadata.clone()))
+    }
+
+    /// Executes the `step` with the `outputs` of the previous steps, returning its own
+    /// outputs.
+    async fn run_step(
+        &self,
+        step: &Step,
+        outputs: &Outputs,
+    ) -> Result<StepOutputs, ErrorVariant> {
+        let args = step
+            .args
+            .iter()
+            .map(|arg| substitute(arg, outputs))
+            .collect::<Result<Vec<_>>>()?;
+        let value = step
+            .value
+            .as_deref()
+            .map(|value| -> Result<u128> {
+                let value = BalanceVariant::from_str(&substitute(value, outputs)?)?;
+                denominate_balance(&value, &self.token_metadata)
+            })
+            .transpose()?
+            .unwrap_or_default();
+        match step.action() {
+            Action::Upload => self.upload(step).await,
+            Action::Instantiate => self.instantiate(step, outputs, args, value).await,
+            Action::Call => self.call(step, outputs, args, value).await,
+        }
+    }
+
+    /// Uploads the code of the contract, with the `code_hash` as output.
+    async fn upload(&self, step: &Step) -> Result<StepOutputs, ErrorVariant> {
+        let upload_exec =
+            UploadCommandBuilder::<_, DefaultEnvironment, _>::new(self.extrinsic_opts(step).done())
+                .connection(Some(self.connection.clone()))
+                .done()
+                .await?;
+        if let Err(err) = upload_exec.upload_code_rpc().await? {
+            let metadata = upload_exec.client().metadata();
+            return Err(ErrorVariant::from_dispatch_error(&err, &metadata)?)
+        }
+        if !step.dry_run {
+            upload_exec.upload_code().await?;
+        }
+        let code_hash = upload_exec.code().code_hash();
+        Ok(StepOutputs::from([(
+            "code_hash".into(),
+            format!("0x{}", hex::encode(code_hash)),
+        )]))
+    }
+
+    /// Instantiates the contract, with the address of the `contract` and its
+    /// `code_hash` as outputs.
+    async fn instantiate(
+        &self,
+        step: &Step,
+        outputs: &Outputs,
+        args: Vec<String>,
+        value: u128,
+    ) -> Result<StepOutputs, ErrorVariant> {
+        let salt = step
+            .salt
+            .as_deref()
+            .map(|salt| -> Result<Bytes> {
+                Ok(decode_hex(&substitute(salt, outputs)?)?.into())
+            })
+            .transpose()?;
+        let instantiate_exec = InstantiateCommandBuilder::new(self.extrinsic_opts(step).done())
+            .constructor(step.constructor.as_deref().unwrap_or("new"))
+            .args(args)
+            .value(value)
+            .gas_limit(step.gas)
+            .proof_size(step.proof_size)
+            .salt(salt)
+            .connection(Some(self.connection.clone()))
+            .done()
+            .await?;
+        let result = instantiate_exec.instantiate_dry_run().await?;
+        let dry_run = instantiate_exec
+            .decode_instantiate_dry_run(&result, Some(&self.token_metadata))
+            .await?;
+        if dry_run.reverted {
+            let error = dry_run.error.unwrap_or(dry_run.result);
+            return Err(anyhow!("The constructor reverted: {error}").into())
+        }
+        if step.dry_run {
+            return Ok(StepOutputs::from([(
+                "contract".into(),
+                dry_run.contract.to_string(),
+            )]))
+        }
+        let gas_limit = Weight::from_parts(
+            step.gas.unwrap_or(result.gas_required.ref_time()),
+            step.proof_size
+                .unwrap_or(result.gas_required.proof_size()),
+        );
+        let instantiated = instantiate_exec.instantiate(Some(gas_limit)).await?;
+        let mut step_outputs = StepOutputs::from([(
+            "contract".into(),
+            instantiated.contract_address.to_string(),
+        )]);
+        if let Some(code_hash) = instantiated.code_hash {
+            step_outputs.insert("code_hash".into(), format!("{code_hash:?}"));
+        }
+        Ok(step_outputs)
+    }
+
+    /// Dry-runs the message and checks the expectations, submitting the call unless
+    /// the step is a dry-run or the message doesn't mutate the contract. The decoded
+    /// `result` of the dry-run is the output.
+    async fn call(
+        &self,
+        step: &Step,
+        outputs: &Outputs,
+        args: Vec<String>,
+        value: u128,
+    ) -> Result<StepOutputs, ErrorVariant> {
+        let contract = step.contract.as_deref().unwrap_or_default();
+        let contract = ContractAddressArg::from_str(&substitute(contract, outputs)?)?;
+        let call_exec = CallCommandBuilder::<_, DefaultEnvironment, _>::new(
+            contract.address(),
+            step.message.as_deref().unwrap_or_default(),
+            self.extrinsic_opts(step).done(),
+        )
+        .args(args)
+        .gas_limit(step.gas)
+        .proof_size(step.proof_size)
+        .value(value)
+        .connection(Some(self.connection.clone()))
+        .done()
+        .await?;
+        let expect = step
+            .expect
+            .as_deref()
+            .map(|expect| substitute(expect, outputs))
+            .transpose()?;
+        let expect = ExpectOpts::new(expect, step.expect_reverted);
+        let expected = expect.expected_value(call_exec.transcoder(), call_exec.message())?;
+
+        let result = call_exec.call_dry_run().await?;
+        let ret_val = match &result.result {
+            Ok(ret_val) => ret_val,
+            Err(err) => {
+                let metadata = call_exec.client().metadata();
+                return Err(ErrorVariant::from_dispatch_error(err, &metadata)?)
+            }
+        };
+        let transcoder = call_exec
+            .transcoder()
+            .expect("the message is called with the contract metadata");
+        let returned = transcoder
+            .decode_message_return(call_exec.message(), &mut &ret_val.data[..])
+            .with_context(|| format!("Failed to decode return value {ret_val:?}"))?;
+        match expect.check(expected, &returned, ret_val.did_revert()) {
+            Some(assertion) => assertion.into_result()?,
+            None if ret_val.did_revert() => {
+                return Err(anyhow!("The message reverted: {returned}").into())
+            }
+            None => {}
+        }
+
+        let mutates = transcoder
+            .metadata()
+            .spec()
+            .messages()
+            .iter()
+            .find(|message| message.label() == call_exec.message())
+            .is_some_and(|message| message.mutates());
+        if !step.dry_run && mutates && !ret_val.did_revert() {
+            let gas_limit = Weight::from_parts(
+                step.gas.unwrap_or(result.gas_required.ref_time()),
+                step.proof_size
+                    .unwrap_or(result.gas_required.proof_size()),
+            );
+            call_exec.call(Some(gas_limit)).await?;
+        }
+        Ok(StepOutputs::from([("result".into(), returned.to_string())]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outputs() -> Outputs {
+        Outputs::from([(
+            "deploy_a".into(),
+            StepOutputs::from([
+                ("contract".into(), "5FKy7RwXBCCACCEPjM5WugkhUd787FjdgieTkdj7TPngJzxN".into()),
+                ("code_hash".into(), "0x01".into()),
+            ]),
+        )])
+    }
+
+    #[test]
+    fn script_is_read_and_checked() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("deploy.yaml");
+        fs::write(
+            &path,
+            r#"
+steps:
+  - id: upload_a
+    action: upload
+    file: a.contract
+  - id: deploy_a
+    action: instantiate
+    file: a.contract
+    args: ["42"]
+    value: 1UNIT
+  - id: deploy_b
+    action: instantiate
+    file: b.contract
+    args: ["{{steps.deploy_a.contract}}"]
+  - id: configure
+    action: call
+    file: b.contract
+    contract: "{{ steps.deploy_b.contract }}"
+    message: configure
+    args: ["true"]
+    gas: 500000000
+    expect: "Ok(())"
+"#,
+        )
+        .unwrap();
+        let script = Script::load(&path).unwrap();
+        assert_eq!(script.steps.len(), 4);
+        assert_eq!(
+            script.steps[1],
+            Step {
+                id: "deploy_a".into(),
+                action: Some(Action::Instantiate),
+                file: Some("a.contract".into()),
+                args: vec!["42".into()],
+                value: Some("1UNIT".into()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            script.steps[3].describe(),
+            "configure: call configure(true)"
+        );
+
+        fs::write(
+            &path,
+            "steps:\n  - id: a\n    action: upload\n  - id: a\n    action: upload\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", Script::load(&path).unwrap_err());
+        assert!(err.contains("used by more than one step"), "{err}");
+
+        fs::write(&path, "steps:\n  - id: a\n    action: call\n    message: get\n")
+            .unwrap();
+        let err = format!("{:#}", Script::load(&path).unwrap_err());
+        assert!(err.contains("requires `contract`"), "{err}");
+
+        fs::write(&path, "steps:\n  - id: a\n    action: upload\n    salt: \"0x01\"\n")
+            .unwrap();
+        let err = format!("{:#}", Script::load(&path).unwrap_err());
+        assert!(err.contains("does not support `salt`"), "{err}");
+
+        fs::write(&path, "steps:\n  - id: a\n    action: upload\n    gass: 1\n").unwrap();
+        let err = format!("{:#}", Script::load(&path).unwrap_err());
+        assert!(err.contains("Invalid script"), "{err}");
+    }
+
+    #[test]
+    fn references_are_substituted() {
+        let outputs = outputs();
+        assert_eq!(
+            substitute("{{steps.deploy_a.contract}}", &outputs).unwrap(),
+            "5FKy7RwXBCCACCEPjM5WugkhUd787FjdgieTkdj7TPngJzxN"
+        );
+        assert_eq!(
+            substitute("Some({{ steps.deploy_a.code_hash }})", &outputs).unwrap(),
+            "Some(0x01)"
+        );
+        assert_eq!(substitute("42", &outputs).unwrap(), "42");
+
+        let err = substitute("{{steps.deploy_b.contract}}", &outputs).unwrap_err();
+        assert!(err.to_string().contains("did not run before"), "{err}");
+        let err = substitute("{{steps.deploy_a.result}}", &outputs).unwrap_err();
+        assert!(
+            err.to_string().contains("it has: code_hash, contract"),
+            "{err}"
+        );
+        let err = substitute("{{deploy_a.contract}}", &outputs).unwrap_err();
+        assert!(err.to_string().contains("Invalid reference"), "{err}");
+        let err = substitute("{{steps.deploy_a.contract", &outputs).unwrap_err();
+        assert!(err.to_string().contains("Unterminated"), "{err}");
+    }
+
+    #[test]
+    fn resume_at_the_failed_step() {
+        let step = |id: &str| {
+            Step {
+                id: id.into(),
+                action: Some(Action::Upload),
+                ..Default::default()
+            }
+        };
+        let script = Script {
+            steps: vec![step("upload_a"), step("deploy_a"), step("configure")],
+        };
+        let mut state = RunState {
+            failed_step: "configure".into(),
+            error: "ContractTrapped".into(),
+            outputs: Outputs::from([
+                ("upload_a".into(), StepOutputs::new()),
+                ("deploy_a".into(), StepOutputs::new()),
+            ]),
+        };
+        assert_eq!(state.resume_at(&script).unwrap(), 2);
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join("deploy.state.json");
+        state.write(&path).unwrap();
+        assert_eq!(RunState::load(&path).unwrap(), state);
+
+        state.outputs.remove("upload_a");
+        let err = state.resume_at(&script).unwrap_err();
+        assert!(err.to_string().contains("`upload_a`"), "{err}");
+
+        state.failed_step = "removed".into();
+        let err = state.resume_at(&script).unwrap_err();
+        assert!(err.to_string().contains("not in the script"), "{err}");
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_extrinsics::{
+    sign_bundle,
+    SignatureScheme,
+};
+use contract_metadata::ContractMetadata;
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    path::PathBuf,
+};
+use subxt::utils::AccountId32;
+
+/// Signs a contract bundle, so that its recipients can verify who it was built by.
+#[derive(Debug, clap::Args)]
+#[clap(name = "sign-bundle")]
+pub struct SignBundleCommand {
+    /// The contract bundle (`*.contract`) to sign.
+    #[clap(long, value_parser)]
+    file: PathBuf,
+    /// Secret key URI of the signer.
+    ///
+    /// e.g.
+    /// - for a dev account "//Alice"
+    /// - with a password "//Alice///SECRET_PASSWORD"
+    #[clap(
+        name = "suri",
+        long,
+        short,
+        env = "CARGO_CONTRACT_SURI",
+        hide_env_values = true
+    )]
+    suri: String,
+    /// The signature scheme of the key.
+    #[clap(long, value_enum, default_value = "sr25519")]
+    scheme: SignatureScheme,
+}
+
+impl SignBundleCommand {
+    pub fn run(&self) -> Result<()> {
+        let path = &self.file;
+        let file = File::open(path)
+            .context(format!("Failed to open contract bundle {}", path.display()))?;
+        let mut bundle: ContractMetadata = serde_json::from_reader(file).context(
+            format!("Failed to deserialize contract bundle {}", path.display()),
+        )?;
+        anyhow::ensure!(
+            bundle.source.wasm.is_some(),
+            "{} contains no contract code, only `.contract` bundles can be signed",
+            path.display()
+        );
+
+        let signature = sign_bundle(&mut bundle, &self.suri, self.scheme)?;
+        fs::write(path, serde_json::to_string(&bundle)?).context(format!(
+            "Failed to write contract bundle {}",
+            path.display()
+        ))?;
+
+        let signer = AccountId32(signature.signer()?);
+        println!(
+            "{} {} by {signer} ({})",
+            "Signed contract bundle".bright_green().bold(),
+            format!("`{}`", path.display()).bold(),
+            signature.public_key
+        );
+        Ok(())
+    }
+}
@@ -14,8 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::DefaultConfig;
-use anyhow::Result;
+use super::{
+    parse_node_url,
+    resolve_ss58_prefix,
+    AccountIdArg,
+    DefaultConfig,
+    DisplayLimitOpts,
+};
+use anyhow::{
+    anyhow,
+    Result,
+};
 use colored::Colorize;
 use comfy_table::{
     ContentArrangement,
@@ -24,20 +33,28 @@ use comfy_table::{
 use contract_extrinsics::{
     ContractArtifacts,
     ContractStorage,
+    ContractStorageDiff,
     ContractStorageLayout,
     ContractStorageRpc,
     ErrorVariant,
+    Ss58Prefix,
 };
 use ink_env::DefaultEnvironment;
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    str::FromStr,
+};
 use subxt::Config;
 
+/// The number of latest blocks searched for the extrinsic given with `--extrinsic`.
+const MAX_EXTRINSIC_SEARCH_BLOCKS: u64 = 256;
+
 #[derive(Debug, clap::Args)]
 #[clap(name = "storage", about = "Inspect contract storage")]
 pub struct StorageCommand {
     /// The address of the contract to inspect storage of.
     #[clap(name = "contract", long, env = "CONTRACT")]
-    contract: <DefaultConfig as Config>::AccountId,
+    contract: AccountIdArg,
     /// Fetch the "raw" storage keys and values for the contract.
     #[clap(long)]
     raw: bool,
@@ -51,25 +68,55 @@ pub struct StorageCommand {
     /// Path to the `Cargo.toml` of the contract.
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
-    /// Websockets url of a substrate node.
+    /// Websockets or HTTP url of a substrate node.
     #[clap(
         name = "url",
         long,
-        value_parser,
+        value_parser = parse_node_url,
         default_value = "ws://localhost:9944"
     )]
     url: url::Url,
+    /// The SS58 address prefix used to display account ids. If not specified, the
+    /// prefix reported by the chain is used.
+    #[clap(long)]
+    ss58_prefix: Option<Ss58Prefix>,
+    /// Display account ids and hashes in their structural representation instead of as
+    /// SS58 addresses and hex.
+    #[clap(long, conflicts_with = "raw")]
+    raw_values: bool,
+    /// Show the storage entries which changed between two blocks, `--from` and `--to`.
+    #[clap(long, conflicts_with = "raw")]
+    diff: bool,
+    /// The hash of the block to compare the storage from.
+    #[clap(long, requires = "diff", value_parser = parse_block_hash)]
+    from: Option<<DefaultConfig as Config>::Hash>,
+    /// The hash of the block to compare the storage to, or `latest` for the best block.
+    /// Defaults to the block including the `--extrinsic` if given, else `latest`.
+    #[clap(long, requires = "diff")]
+    to: Option<BlockRef>,
+    /// The hash of an extrinsic in one of the latest 256 blocks. Compares the storage
+    /// before and after the block including it.
+    #[clap(long, requires = "diff", conflicts_with = "from", value_parser = parse_block_hash)]
+    extrinsic: Option<<DefaultConfig as Config>::Hash>,
+    #[clap(flatten)]
+    display_limit: DisplayLimitOpts,
 }
 
 impl StorageCommand {
     pub async fn run(&self) -> Result<(), ErrorVariant> {
         let rpc = ContractStorageRpc::<DefaultConfig>::new(&self.url).await?;
+        let ss58_prefix = resolve_ss58_prefix(self.ss58_prefix, &self.url).await?;
+        self.contract.note_ss58_prefix_mismatch(ss58_prefix);
+
+        if self.diff {
+            return self.run_diff(rpc, ss58_prefix.value()).await
+        }
         let storage_layout =
             ContractStorage::<DefaultConfig, DefaultEnvironment>::new(rpc);
 
         if self.raw {
             let storage_data = storage_layout
-                .load_contract_storage_data(&self.contract)
+                .load_contract_storage_data(self.contract.account_id())
                 .await?;
             println!(
                 "{json}",
@@ -85,9 +132,20 @@ impl StorageCommand {
 
         match contract_artifacts {
             Ok(contract_artifacts) => {
-                let transcoder = contract_artifacts.contract_transcoder()?;
+                if !self.output_json {
+                    for notice in contract_artifacts.notices() {
+                        eprintln!("{notice}");
+                    }
+                }
+                let transcoder = contract_artifacts
+                    .contract_transcoder()?
+                    .with_ss58_prefix(ss58_prefix.value())
+                    .with_raw_values(self.raw_values);
                 let contract_storage = storage_layout
-                    .load_contract_storage_with_layout(&self.contract, &transcoder)
+                    .load_contract_storage_with_layout(
+                        self.contract.account_id(),
+                        &transcoder,
+                    )
                     .await?;
                 if self.output_json {
                     println!(
@@ -95,7 +153,8 @@ impl StorageCommand {
                         json = serde_json::to_string_pretty(&contract_storage)?
                     );
                 } else {
-                    let table = StorageDisplayTable::new(&contract_storage);
+                    let table =
+                        StorageDisplayTable::new(&contract_storage, &self.display_limit);
                     table.display();
                 }
             }
@@ -105,7 +164,7 @@ impl StorageCommand {
                     "Info:".cyan().bold(),
                 );
                 let storage_data = storage_layout
-                    .load_contract_storage_data(&self.contract)
+                    .load_contract_storage_data(self.contract.account_id())
                     .await?;
                 println!(
                     "{json}",
@@ -117,6 +176,148 @@ impl StorageCommand {
 
         Ok(())
     }
+
+    /// Displays the changes of the contract storage between the `--from` and `--to`
+    /// blocks.
+    async fn run_diff(
+        &self,
+        rpc: ContractStorageRpc<DefaultConfig>,
+        ss58_prefix: u16,
+    ) -> Result<(), ErrorVariant> {
+        let (from, to) = match (self.from, self.extrinsic) {
+            (Some(from), _) => (from, self.to.and_then(BlockRef::hash)),
+            (None, Some(extrinsic)) => {
+                let (block, parent) = rpc
+                    .find_extrinsic_block(extrinsic, MAX_EXTRINSIC_SEARCH_BLOCKS)
+                    .await?;
+                let to = match self.to {
+                    Some(to) => to.hash(),
+                    None => Some(block),
+                };
+                (parent, to)
+            }
+            (None, None) => {
+                return Err(anyhow!(
+                    "`--diff` requires the block to compare from, pass `--from` or \
+                     `--extrinsic`"
+                )
+                .into())
+            }
+        };
+
+        let storage_layout =
+            ContractStorage::<DefaultConfig, DefaultEnvironment>::new(rpc);
+        let contract = self.contract.account_id();
+        let before = storage_layout
+            .load_contract_storage_data_at(contract, Some(from))
+            .await?;
+        let after = storage_layout
+            .load_contract_storage_data_at(contract, to)
+            .await?;
+
+        let transcoder = match ContractArtifacts::from_manifest_or_file(
+            self.manifest_path.as_ref(),
+            self.file.as_ref(),
+        )
+        .and_then(|artifacts| artifacts.contract_transcoder())
+        {
+            Ok(transcoder) => {
+                Some(
+                    transcoder
+                        .with_ss58_prefix(ss58_prefix)
+                        .with_raw_values(self.raw_values),
+                )
+            }
+            Err(_) => {
+                eprintln!(
+                    "{} Displaying raw storage changes: no valid contract metadata \
+                     artifacts found",
+                    "Info:".cyan().bold(),
+                );
+                None
+            }
+        };
+        let diff = ContractStorageDiff::new(before, after, transcoder.as_ref());
+
+        if self.output_json {
+            println!("{json}", json = serde_json::to_string_pretty(&diff)?);
+        } else if diff.is_empty() {
+            println!("No storage changes");
+        } else {
+            StorageDiffDisplayTable::new(&diff, &self.display_limit).display();
+        }
+        Ok(())
+    }
+}
+
+/// A block given on the command line, either by its hash or as `latest`.
+#[derive(Debug, Clone, Copy)]
+enum BlockRef {
+    Latest,
+    Hash(<DefaultConfig as Config>::Hash),
+}
+
+impl BlockRef {
+    /// The hash of the block, `None` for the best block.
+    fn hash(self) -> Option<<DefaultConfig as Config>::Hash> {
+        match self {
+            Self::Latest => None,
+            Self::Hash(hash) => Some(hash),
+        }
+    }
+}
+
+impl FromStr for BlockRef {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        if input == "latest" {
+            return Ok(Self::Latest)
+        }
+        parse_block_hash(input).map(Self::Hash)
+    }
+}
+
+/// Parse a hex encoded 32 byte block or extrinsic hash.
+fn parse_block_hash(input: &str) -> Result<<DefaultConfig as Config>::Hash> {
+    let bytes = contract_build::util::decode_hex(input)?;
+    let hash: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("Hash should be 32 bytes in length"))?;
+    Ok(hash.into())
+}
+
+struct StorageDiffDisplayTable(Table);
+
+impl StorageDiffDisplayTable {
+    fn new(diff: &ContractStorageDiff, display_limit: &DisplayLimitOpts) -> Self {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec!["Change", "Key", "Before", "After"]);
+        for entry in diff.iter() {
+            let key = entry
+                .path
+                .clone()
+                .unwrap_or_else(|| format!("0x{}", hex::encode(&entry.key.0)));
+            let value = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .map(|value| display_limit.display_str(value))
+                    .unwrap_or_default()
+            };
+            table.add_row(vec![
+                entry.change().to_string(),
+                key,
+                value(&entry.before),
+                value(&entry.after),
+            ]);
+        }
+        Self(table)
+    }
+
+    fn display(&self) {
+        println!("{}", self.0);
+    }
 }
 
 struct StorageDisplayTable(Table);
@@ -127,10 +328,13 @@ impl StorageDisplayTable {
     const PARENT_LABEL: &'static str = "Parent";
     const VALUE_LABEL: &'static str = "Value";
 
-    fn new(storage_layout: &ContractStorageLayout) -> Self {
+    fn new(
+        storage_layout: &ContractStorageLayout,
+        display_limit: &DisplayLimitOpts,
+    ) -> Self {
         let mut table = Table::new();
         Self::table_add_header(&mut table);
-        Self::table_add_rows(&mut table, storage_layout);
+        Self::table_add_rows(&mut table, storage_layout, display_limit);
         Self(table)
     }
 
@@ -146,16 +350,35 @@ impl StorageDisplayTable {
         table.set_header(header);
     }
 
-    fn table_add_rows(table: &mut Table, storage_layout: &ContractStorageLayout) {
+    fn table_add_rows(
+        table: &mut Table,
+        storage_layout: &ContractStorageLayout,
+        display_limit: &DisplayLimitOpts,
+    ) {
         for (index, cell) in storage_layout.iter().enumerate() {
             let formatted_cell = format!("{cell}");
-            let values = formatted_cell.split('\n');
-            for (i, v) in values.enumerate() {
+            let values: Vec<_> = formatted_cell.split('\n').collect();
+            let shown = display_limit
+                .max_items()
+                .unwrap_or(values.len())
+                .min(values.len());
+            for (i, v) in values[..shown].iter().enumerate() {
                 table.add_row(vec![
                     (index + i).to_string().as_str(),
                     cell.root_key().as_str(),
                     cell.parent().as_str(),
-                    v,
+                    display_limit.display_str(v).as_str(),
+                ]);
+            }
+            if shown < values.len() {
+                table.add_row(vec![
+                    "",
+                    cell.root_key().as_str(),
+                    cell.parent().as_str(),
+                    &format!(
+                        "… {} more items (use --full-output to show all)",
+                        values.len() - shown
+                    ),
                 ]);
             }
         }
@@ -0,0 +1,224 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::ErrorVariant;
+use std::fmt::Debug;
+
+use super::{
+    config::{
+        with_chain_config,
+        ExtrinsicBalance,
+        ExtrinsicConfig,
+    },
+    create_signer,
+    denominate_balance,
+    prompt_confirm_tx,
+    CLIExtrinsicOpts,
+    ContractAddressArg,
+};
+use anyhow::Result;
+use colored::Colorize;
+use contract_build::{
+    name_value_println,
+    util::DEFAULT_KEY_COL_WIDTH,
+};
+use contract_extrinsics::{
+    BalanceVariant,
+    Connection,
+    DisplayBalance,
+    DisplayEvents,
+    ExtrinsicOptsBuilder,
+    MortalityParams,
+    Progress,
+    TransferCommandBuilder,
+    TransferExec,
+};
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+};
+use subxt::config::ExtrinsicParams;
+use subxt_signer::sr25519::Keypair;
+
+#[derive(Debug, clap::Args)]
+#[clap(name = "transfer", about = "Transfer balance to a contract")]
+pub struct TransferCommand {
+    /// The address of the account or contract to transfer the balance to.
+    #[clap(long)]
+    recipient: ContractAddressArg,
+    /// The value to be transferred.
+    #[clap(long)]
+    value: BalanceVariant<<DefaultEnvironment as Environment>::Balance>,
+    /// Use `transfer_allow_death`, allowing the transfer to reap the sender's account if
+    /// its balance falls below the existential deposit.
+    #[clap(long)]
+    allow_death: bool,
+    #[clap(flatten)]
+    extrinsic_cli_opts: CLIExtrinsicOpts,
+    /// Export the transfer output as JSON.
+    #[clap(long, conflicts_with = "verbose")]
+    output_json: bool,
+}
+
+impl TransferCommand {
+    /// Returns whether to export the transfer output in JSON format.
+    pub fn output_json(&self) -> bool {
+        self.output_json
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts(&self) -> &CLIExtrinsicOpts {
+        &self.extrinsic_cli_opts
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts_mut(&mut self) -> &mut CLIExtrinsicOpts {
+        &mut self.extrinsic_cli_opts
+    }
+
+    pub async fn handle(&self) -> Result<(), ErrorVariant> {
+        if self.extrinsic_cli_opts.progress(self.output_json()) {
+            Progress::Connecting.report();
+        }
+        let connection = self.extrinsic_cli_opts.connect().await?;
+        let chain_config = self.extrinsic_cli_opts.chain_config(&connection);
+        with_chain_config!(chain_config, |C, E| {
+            self.handle_with_config::<C, E>(connection.with_config()?)
+                .await
+        })
+    }
+
+    async fn handle_with_config<C, E>(
+        &self,
+        connection: Connection<C>,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let token_metadata = self.extrinsic_cli_opts.token_metadata(&connection).await?;
+
+        let ss58_prefix = self.extrinsic_cli_opts.ss58_prefix(&connection).await?;
+        let address_book = self.extrinsic_cli_opts.address_book()?;
+        self.recipient.note_ss58_prefix_mismatch(ss58_prefix);
+
+        let signer: Keypair = create_signer(self.extrinsic_cli_opts.suri()?)?;
+        let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
+            .url(self.extrinsic_cli_opts.url.clone())
+            .ss58_prefix(ss58_prefix)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .submit_retries(self.extrinsic_cli_opts.submit_retries)
+            .timeout(self.extrinsic_cli_opts.timeout())
+            .timings(self.extrinsic_cli_opts.recorder().clone())
+            .max_fee(self.extrinsic_cli_opts.max_fee(&token_metadata)?)
+            .progress(self.extrinsic_cli_opts.progress(self.output_json()))
+            .tx_status(self.extrinsic_cli_opts.tx_status(self.output_json())?)
+            .verbosity(
+                self.extrinsic_cli_opts
+                    .extrinsic_verbosity(self.output_json())?,
+            )
+            .raw_values(self.extrinsic_cli_opts.raw_values)
+            .include_raw(self.extrinsic_cli_opts.include_raw)
+            .token_metadata(Some(token_metadata.clone()))
+            .done();
+        let transfer_exec: TransferExec<C, E, Keypair> = TransferCommandBuilder::new(
+            self.recipient.address(),
+            denominate_balance(&self.value, &token_metadata)?,
+            extrinsic_opts,
+        )
+        .allow_death(self.allow_death)
+        .connection(Some(connection))
+        .done()
+        .await?;
+
+        match transfer_exec.recipient_is_contract().await {
+            Ok(true) => (),
+            Ok(false) => {
+                eprintln!(
+                    "{} The recipient is not a contract on this chain",
+                    "Warning:".yellow().bold()
+                );
+            }
+            Err(err) => {
+                tracing::debug!(
+                    "Failed to check whether the recipient is a contract: {err}"
+                )
+            }
+        }
+
+        if !self.extrinsic_cli_opts.skip_confirm {
+            prompt_confirm_tx(|| {
+                name_value_println!(
+                    "Recipient",
+                    ss58_prefix.format_account(transfer_exec.recipient()),
+                    DEFAULT_KEY_COL_WIDTH
+                );
+                name_value_println!(
+                    "Value",
+                    DisplayBalance::new(*transfer_exec.value(), Some(&token_metadata))
+                        .to_string(),
+                    DEFAULT_KEY_COL_WIDTH
+                );
+                name_value_println!(
+                    "Allow death",
+                    self.allow_death.to_string(),
+                    DEFAULT_KEY_COL_WIDTH
+                );
+                name_value_println!(
+                    "Mortality",
+                    self.extrinsic_cli_opts.mortality.describe(),
+                    DEFAULT_KEY_COL_WIDTH
+                );
+            })?;
+        }
+
+        let events = transfer_exec.transfer().await?;
+        let display_events = DisplayEvents::from_events::<C, E>(
+            &events,
+            None,
+            &[],
+            &transfer_exec.client().metadata(),
+            ss58_prefix,
+            self.extrinsic_cli_opts.raw_values,
+            self.extrinsic_cli_opts.include_raw,
+            Some(&token_metadata),
+        )?
+        .with_aliases(&address_book);
+        let output = if self.output_json() {
+            match self.extrinsic_cli_opts.timings() {
+                Some(_) => {
+                    let events = serde_json::json!({ "events": display_events });
+                    self.extrinsic_cli_opts.to_json(&events)?
+                }
+                None => {
+                    self.extrinsic_cli_opts
+                        .to_json_without_timings(&display_events)?
+                }
+            }
+        } else {
+            display_events.display_events::<E>(
+                self.extrinsic_cli_opts.verbosity()?,
+                self.extrinsic_cli_opts.event_display(),
+                &token_metadata,
+            )?
+        };
+        println!("{output}");
+        Ok(())
+    }
+}
@@ -15,30 +15,39 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::ErrorVariant;
-use std::fmt::Debug;
 
 use super::{
+    config::{
+        with_chain_config,
+        ExtrinsicBalance,
+        ExtrinsicConfig,
+    },
     create_signer,
+    denominate_balance,
     display_dry_run_result_warning,
+    prompt_confirm_tx,
     CLIExtrinsicOpts,
 };
 use anyhow::Result;
-use contract_build::name_value_println;
+use colored::Colorize;
+use contract_build::{
+    name_value_println,
+    util::DEFAULT_KEY_COL_WIDTH,
+    WasmValidation,
+};
 use contract_extrinsics::{
+    Connection,
+    Determinism,
+    DisplayBalance,
     DisplayEvents,
     ExtrinsicOptsBuilder,
-    TokenMetadata,
+    MortalityParams,
+    Progress,
     UploadCommandBuilder,
     UploadExec,
 };
-use ink_env::{
-    DefaultEnvironment,
-    Environment,
-};
-use subxt::{
-    Config,
-    PolkadotConfig as DefaultConfig,
-};
+use ink_env::Environment;
+use subxt::config::ExtrinsicParams;
 use subxt_signer::sr25519::Keypair;
 
 #[derive(Debug, clap::Args)]
@@ -46,9 +55,19 @@ use subxt_signer::sr25519::Keypair;
 pub struct UploadCommand {
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
-    /// Export the call output in JSON format.
-    #[clap(long, conflicts_with = "verbose")]
+    /// Whether the code must be deterministic (`enforced`), or may contain
+    /// non-deterministic instructions like floating point arithmetic (`relaxed`). Code
+    /// uploaded with relaxed determinism can only be executed off-chain.
+    #[clap(long, default_value = "enforced")]
+    determinism: Determinism,
+    /// Export the upload output in JSON format.
+    #[clap(long, conflicts_with_all = ["verbose", "quiet"])]
     output_json: bool,
+    /// Wrap the submitted call in `Sudo::sudo` to dispatch it with the root origin, e.g.
+    /// on development chains which only allow root to upload code. The signer must be
+    /// the sudo key of the chain.
+    #[clap(long, requires = "execute")]
+    sudo: bool,
 }
 
 impl UploadCommand {
@@ -57,86 +76,203 @@ impl UploadCommand {
         self.output_json
     }
 
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts(&self) -> &CLIExtrinsicOpts {
+        &self.extrinsic_cli_opts
+    }
+
+    /// Returns the options for creating and sending the extrinsic.
+    pub fn extrinsic_cli_opts_mut(&mut self) -> &mut CLIExtrinsicOpts {
+        &mut self.extrinsic_cli_opts
+    }
+
     pub async fn handle(&self) -> Result<(), ErrorVariant> {
-        let token_metadata =
-            TokenMetadata::query::<DefaultConfig>(&self.extrinsic_cli_opts.url).await?;
+        if self.extrinsic_cli_opts.progress(self.output_json()) {
+            Progress::Connecting.report();
+        }
+        let connection = self.extrinsic_cli_opts.connect().await?;
+        let chain_config = self.extrinsic_cli_opts.chain_config(&connection);
+        with_chain_config!(chain_config, |C, E| {
+            self.handle_with_config::<C, E>(connection.with_config()?)
+                .await
+        })
+    }
+
+    async fn handle_with_config<C, E>(
+        &self,
+        connection: Connection<C>,
+    ) -> Result<(), ErrorVariant>
+    where
+        C: ExtrinsicConfig,
+        <C::ExtrinsicParams as ExtrinsicParams<C>>::OtherParams:
+            Default + MortalityParams<C>,
+        E: Environment,
+        E::Balance: ExtrinsicBalance,
+    {
+        let token_metadata = self.extrinsic_cli_opts.token_metadata(&connection).await?;
 
-        let signer = create_signer(&self.extrinsic_cli_opts.suri)?;
+        let ss58_prefix = self.extrinsic_cli_opts.ss58_prefix(&connection).await?;
+        let address_book = self.extrinsic_cli_opts.address_book()?;
+
+        let signer = create_signer(self.extrinsic_cli_opts.suri()?)?;
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
             .file(self.extrinsic_cli_opts.file.clone())
+            .expected_hash(self.extrinsic_cli_opts.expected_hash.map(|hash| hash.0))
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
+            .require_signed_artifacts(self.extrinsic_cli_opts.require_signed_artifacts)
             .url(self.extrinsic_cli_opts.url.clone())
+            .ss58_prefix(ss58_prefix)
             .storage_deposit_limit(
                 self.extrinsic_cli_opts
                     .storage_deposit_limit
-                    .clone()
-                    .map(|bv| bv.denominate_balance(&token_metadata))
+                    .as_ref()
+                    .map(|bv| denominate_balance(bv, &token_metadata))
                     .transpose()?,
             )
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .submit_retries(self.extrinsic_cli_opts.submit_retries)
+            .timeout(self.extrinsic_cli_opts.timeout())
+            .timings(self.extrinsic_cli_opts.recorder().clone())
+            .sudo(self.sudo)
+            .max_fee(self.extrinsic_cli_opts.max_fee(&token_metadata)?)
+            .progress(self.extrinsic_cli_opts.progress(self.output_json()))
+            .tx_status(self.extrinsic_cli_opts.tx_status(self.output_json())?)
+            .verbosity(
+                self.extrinsic_cli_opts
+                    .extrinsic_verbosity(self.output_json())?,
+            )
+            .raw_values(self.extrinsic_cli_opts.raw_values)
+            .include_raw(self.extrinsic_cli_opts.include_raw)
+            .token_metadata(Some(token_metadata.clone()))
+            .skip_compat_check(self.extrinsic_cli_opts.skip_compat_check)
+            .skip_wasm_validation(self.extrinsic_cli_opts.skip_wasm_validation)
             .done();
-        let upload_exec: UploadExec<DefaultConfig, DefaultEnvironment, Keypair> =
-            UploadCommandBuilder::new(extrinsic_opts).done().await?;
+        let mut upload_exec: UploadExec<C, E, Keypair> =
+            UploadCommandBuilder::new(extrinsic_opts)
+                .determinism(self.determinism)
+                .connection(Some(connection))
+                .done()
+                .await?;
+
+        if self.determinism == Determinism::Relaxed {
+            eprintln!(
+                "{} Code uploaded with relaxed determinism cannot be instantiated or \
+                called on-chain, it can only be executed off-chain via RPC",
+                "Warning:".yellow().bold()
+            );
+        }
 
         let code_hash = upload_exec.code().code_hash();
         let metadata = upload_exec.client().metadata();
 
         if !self.extrinsic_cli_opts.execute {
-            match upload_exec.upload_code_rpc().await? {
+            match upload_exec.upload_dry_run().await {
                 Ok(result) => {
                     let upload_result = UploadDryRunResult {
                         result: String::from("Success!"),
                         code_hash: format!("{:?}", result.code_hash),
-                        deposit: result.deposit,
+                        code_size: upload_exec.code().size(),
+                        deposit: DisplayBalance::new(
+                            result.deposit,
+                            Some(&token_metadata),
+                        ),
+                        determinism: self.determinism,
+                        wasm_validation: upload_exec.wasm_validation().cloned(),
                     };
                     if self.output_json() {
-                        println!("{}", upload_result.to_json()?);
+                        println!("{}", self.extrinsic_cli_opts.to_json(&upload_result)?);
                     } else {
                         upload_result.print();
                         display_dry_run_result_warning("upload");
                     }
                 }
-                Err(err) => {
-                    let err = ErrorVariant::from_dispatch_error(&err, &metadata)?;
-                    if self.output_json() {
-                        return Err(err)
-                    } else {
-                        name_value_println!("Result", err);
-                    }
+                Err(err @ ErrorVariant::Module(_)) if !self.output_json() => {
+                    name_value_println!("Result", err);
                 }
+                Err(err) => return Err(err),
             }
         } else {
-            let upload_result = upload_exec.upload_code().await?;
-            let display_events = DisplayEvents::from_events::<
-                DefaultConfig,
-                DefaultEnvironment,
-            >(&upload_result.events, None, &metadata)?;
-            let output_events = if self.output_json() {
-                display_events.to_json()?
+            // estimate the deposit, which also limits the deposit charged unless a
+            // limit was specified
+            let deposit = if self.extrinsic_cli_opts.skip_dry_run {
+                None
             } else {
-                display_events.display_events::<DefaultEnvironment>(
-                    self.extrinsic_cli_opts.verbosity()?,
-                    &token_metadata,
-                )?
-            };
-            if let Some(code_stored) = upload_result.code_stored {
-                let code_hash: <DefaultConfig as Config>::Hash = code_stored.code_hash;
-                if self.output_json() {
-                    // Create a JSON object with the events and the code hash.
-                    let json_object = serde_json::json!({
-                        "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
-                        "code_hash": code_hash,
-                    });
-                    println!("{}", serde_json::to_string_pretty(&json_object)?);
-                } else {
-                    println!("{}", output_events);
-                    name_value_println!("Code hash", format!("{:?}", code_hash));
+                let estimate = upload_exec.upload_dry_run().await?;
+                if upload_exec.opts().storage_deposit_limit().is_none() {
+                    upload_exec.set_storage_deposit_limit(Some(estimate.deposit));
                 }
+                Some(DisplayBalance::new(estimate.deposit, Some(&token_metadata)))
+            };
+            if !self.extrinsic_cli_opts.skip_confirm {
+                prompt_confirm_tx(|| {
+                    name_value_println!(
+                        "Code hash",
+                        format!("0x{}", hex::encode(code_hash)),
+                        DEFAULT_KEY_COL_WIDTH
+                    );
+                    name_value_println!(
+                        "Determinism",
+                        self.determinism.to_string(),
+                        DEFAULT_KEY_COL_WIDTH
+                    );
+                    if let Some(deposit) = &deposit {
+                        name_value_println!(
+                            "Deposit",
+                            deposit.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    name_value_println!(
+                        "Mortality",
+                        self.extrinsic_cli_opts.mortality.describe(),
+                        DEFAULT_KEY_COL_WIDTH
+                    );
+                })?;
+            }
+            let upload_result = upload_exec.upload_code().await?;
+            let display_events = DisplayEvents::from_events::<C, E>(
+                &upload_result.events,
+                None,
+                &[],
+                &metadata,
+                ss58_prefix,
+                self.extrinsic_cli_opts.raw_values,
+                self.extrinsic_cli_opts.include_raw,
+                Some(&token_metadata),
+            )?
+            .with_aliases(&address_book);
+            let explorer_url = self
+                .extrinsic_cli_opts
+                .explorer_url(upload_exec.client(), &upload_result.events, None)
+                .await?;
+            let upload_result = UploadResult {
+                code_hash: match &upload_result.code_stored {
+                    Some(code_stored) => format!("{:?}", code_stored.code_hash),
+                    None => format!("0x{}", hex::encode(code_hash)),
+                },
+                code_size: upload_exec.code().size(),
+                deposit: upload_result
+                    .deposit
+                    .map(|deposit| DisplayBalance::new(deposit, Some(&token_metadata))),
+                block_hash: format!("{:?}", upload_result.events.block_hash()),
+                newly_uploaded: upload_result.code_stored.is_some(),
+                determinism: self.determinism,
+                wasm_validation: upload_exec.wasm_validation().cloned(),
+                events: display_events,
+                explorer_url,
+            };
+            if self.output_json() {
+                println!("{}", self.extrinsic_cli_opts.to_json(&upload_result)?);
             } else {
-                let code_hash = hex::encode(code_hash);
-                return Err(anyhow::anyhow!(
-                    "This contract has already been uploaded with code hash: 0x{code_hash}"
-                )
-                .into())
+                println!(
+                    "{}",
+                    upload_result.events.display_events::<E>(
+                        self.extrinsic_cli_opts.verbosity()?,
+                        self.extrinsic_cli_opts.event_display(),
+                        &token_metadata,
+                    )?
+                );
+                upload_result.print();
             }
         }
         Ok(())
@@ -144,20 +280,71 @@ impl UploadCommand {
 }
 
 #[derive(serde::Serialize)]
-pub struct UploadDryRunResult {
+pub struct UploadDryRunResult<Balance> {
     pub result: String,
     pub code_hash: String,
-    pub deposit: <DefaultEnvironment as Environment>::Balance,
+    /// The size of the code in bytes
+    pub code_size: usize,
+    pub deposit: Balance,
+    pub determinism: Determinism,
+    /// The findings of the validation of the code, absent if skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_validation: Option<WasmValidation>,
 }
 
-impl UploadDryRunResult {
-    pub fn to_json(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
-    }
-
+impl UploadDryRunResult<DisplayBalance> {
     pub fn print(&self) {
         name_value_println!("Result", self.result);
         name_value_println!("Code hash", format!("{:?}", self.code_hash));
-        name_value_println!("Deposit", format!("{:?}", self.deposit));
+        name_value_println!("Code size", format!("{} bytes", self.code_size));
+        name_value_println!("Deposit", self.deposit.to_string());
+        name_value_println!("Determinism", self.determinism.to_string());
+    }
+}
+
+/// Result of a submitted code upload.
+#[derive(serde::Serialize)]
+pub struct UploadResult<Balance> {
+    pub code_hash: String,
+    /// The size of the code in bytes
+    pub code_size: usize,
+    /// The storage deposit held for the code, absent if the chain emitted no event for
+    /// it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit: Option<Balance>,
+    /// The hash of the block the upload was included in
+    pub block_hash: String,
+    /// Whether the code was stored by this upload, `false` if it was already present
+    pub newly_uploaded: bool,
+    pub determinism: Determinism,
+    /// The findings of the validation of the code, absent if skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_validation: Option<WasmValidation>,
+    /// The events emitted from the upload extrinsic invocation
+    pub events: DisplayEvents,
+    /// The url of the upload in a block explorer, if an explorer url template is
+    /// configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+}
+
+impl UploadResult<DisplayBalance> {
+    pub fn print(&self) {
+        if !self.newly_uploaded {
+            eprintln!(
+                "{} The code was already uploaded, no deposit was charged",
+                "Warning:".yellow().bold()
+            );
+        }
+        name_value_println!("Code hash", self.code_hash);
+        name_value_println!("Code size", format!("{} bytes", self.code_size));
+        if let Some(deposit) = &self.deposit {
+            name_value_println!("Deposit", deposit.to_string());
+        }
+        name_value_println!("Block hash", self.block_hash);
+        name_value_println!("Determinism", self.determinism.to_string());
+        if let Some(explorer_url) = &self.explorer_url {
+            name_value_println!("Explorer", explorer_url);
+        }
     }
 }
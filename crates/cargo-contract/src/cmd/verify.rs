@@ -26,12 +26,21 @@ use contract_build::{
     BuildInfo,
     BuildMode,
     ExecuteArgs,
+    GitInfo,
     ImageVariant,
     ManifestPath,
     Verbosity,
     VerbosityFlags,
 };
+use contract_extrinsics::{
+    resolve_artifact_file,
+    ArtifactFile,
+};
 use contract_metadata::ContractMetadata;
+use subxt::{
+    Config,
+    PolkadotConfig as DefaultConfig,
+};
 
 use std::{
     fs::File,
@@ -48,8 +57,13 @@ pub struct VerifyCommand {
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
     /// The reference Wasm contract (`*.contract`) that the workspace will be checked
-    /// against.
+    /// against. It can also be downloaded from an `https://` URL, or read from stdin
+    /// with `-`.
     contract: PathBuf,
+    /// The code hash the reference contract is expected to have, as hex. Aborts if the
+    /// code hash of the reference contract differs, e.g. for a downloaded bundle.
+    #[clap(long, value_parser = super::parse_code_hash)]
+    expected_hash: Option<<DefaultConfig as Config>::Hash>,
     /// Denotes if output should be printed to stdout.
     #[clap(flatten)]
     verbosity: VerbosityFlags,
@@ -64,13 +78,38 @@ impl VerifyCommand {
         let verbosity: Verbosity = TryFrom::<&VerbosityFlags>::try_from(&self.verbosity)?;
 
         // 1. Read the given metadata, and pull out the `BuildInfo`
-        let path = &self.contract;
-        let file = File::open(path)
-            .context(format!("Failed to open contract bundle {}", path.display()))?;
-
-        let metadata: ContractMetadata = serde_json::from_reader(&file).context(
-            format!("Failed to deserialize contract bundle {}", path.display()),
-        )?;
+        let (path, metadata) = match resolve_artifact_file(&self.contract)? {
+            ArtifactFile::Path(path) => {
+                let file = File::open(&path).context(format!(
+                    "Failed to open contract bundle {}",
+                    path.display()
+                ))?;
+                let metadata: ContractMetadata = serde_json::from_reader(&file).context(
+                    format!("Failed to deserialize contract bundle {}", path.display()),
+                )?;
+                (path, metadata)
+            }
+            ArtifactFile::Bundle {
+                source,
+                metadata,
+                notice,
+            } => {
+                if !self.output_json {
+                    eprintln!("{notice}");
+                }
+                (PathBuf::from(source), *metadata)
+            }
+        };
+        let path = &path;
+        if let Some(expected_hash) = &self.expected_hash {
+            anyhow::ensure!(
+                metadata.source.hash.0 == expected_hash.0,
+                "The code hash 0x{} of the reference contract does not match the \
+                 expected code hash 0x{}",
+                hex::encode(metadata.source.hash.0),
+                hex::encode(expected_hash)
+            );
+        }
         let build_info = if let Some(info) = metadata.source.build_info {
             info
         } else {
@@ -93,6 +132,8 @@ impl VerifyCommand {
             &build_info,
         );
 
+        let git = build_info.git.clone();
+
         let build_mode = if metadata.image.is_some() {
             BuildMode::Verifiable
         } else {
@@ -136,13 +177,26 @@ impl VerifyCommand {
         }
 
         // 3a. Call `cargo contract build` with the `BuildInfo` from the metadata.
+        // `wasm-opt` is only run for the Wasm target.
+        let wasm_opt_settings = build_info.wasm_opt_settings;
+        let (optimization_passes, wasm_opt_config) = if build_info.target.is_wasm() {
+            (
+                Some(wasm_opt_settings.optimization_passes),
+                Some(wasm_opt_settings.config),
+            )
+        } else {
+            (None, None)
+        };
         let args = ExecuteArgs {
             manifest_path: manifest_path.clone(),
             verbosity,
             build_mode,
             build_artifact: BuildArtifacts::All,
-            optimization_passes: Some(build_info.wasm_opt_settings.optimization_passes),
-            keep_debug_symbols: build_info.wasm_opt_settings.keep_debug_symbols,
+            optimization_passes,
+            keep_debug_symbols: wasm_opt_settings.keep_debug_symbols,
+            wasm_opt_config,
+            target: build_info.target,
+            features: build_info.features,
             image: ImageVariant::from(metadata.image.clone()),
             extra_lints: false,
             ..Default::default()
@@ -200,7 +254,8 @@ impl VerifyCommand {
             is_verified: true,
             image: metadata.image,
             contract: target_bundle.display().to_string(),
-            reference_contract: path.display().to_string(),
+            reference_contract: self.contract.display().to_string(),
+            git,
             output_json: self.output_json,
             verbosity,
         })
@@ -214,6 +269,9 @@ pub struct VerificationResult {
     pub image: Option<String>,
     pub contract: String,
     pub reference_contract: String,
+    /// The state of the git repository the reference contract was built from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
     #[serde(skip_serializing, skip_deserializing)]
     pub output_json: bool,
     #[serde(skip_serializing, skip_deserializing)]
@@ -223,12 +281,25 @@ pub struct VerificationResult {
 impl VerificationResult {
     /// Display the result in a fancy format
     pub fn display(&self) -> String {
-        format!(
+        let mut out = format!(
             "\n{} {} against reference contract {}",
             "Successfully verified contract".bright_green().bold(),
             format!("`{}`", &self.contract).bold(),
             format!("`{}`!", &self.reference_contract).bold()
-        )
+        );
+        if let Some(git) = &self.git {
+            out.push_str(&format!(
+                "\nThe reference contract was built from commit {}",
+                git.display().bold()
+            ));
+            if git.dirty {
+                out.push_str(&format!(
+                    "\n{} the working tree had uncommitted changes",
+                    "warning:".yellow().bold()
+                ));
+            }
+        }
+        out
     }
 
     /// Display the build results in a pretty formatted JSON string.
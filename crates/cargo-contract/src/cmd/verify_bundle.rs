@@ -0,0 +1,566 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::AccountIdArg;
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_build::{
+    code_hash,
+    Target,
+    Verbosity,
+    VerbosityFlags,
+    WasmValidation,
+};
+use contract_extrinsics::BundleSignature;
+use contract_metadata::{
+    CodeHash,
+    ContractMetadata,
+    Language,
+};
+use ink_metadata::InkProject;
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    io::{
+        self,
+        IsTerminal,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// The version of the ink! metadata supported by this version of `cargo-contract`.
+const METADATA_VERSION: u64 = 5;
+
+/// Checks that the code, the code hash and the metadata of a contract bundle are
+/// consistent with each other.
+#[derive(Debug, clap::Args)]
+#[clap(name = "verify-bundle")]
+pub struct VerifyBundleCommand {
+    /// The contract bundle (`*.contract`) to check.
+    contract: PathBuf,
+    /// Rewrite the code hash recorded in the bundle if it does not match the embedded
+    /// code, after asking for confirmation.
+    #[clap(long)]
+    fix_hash: bool,
+    /// Rewrite the code hash with `--fix-hash` without asking for confirmation.
+    #[clap(long, short('y'), requires = "fix_hash")]
+    skip_confirm: bool,
+    /// Require the bundle to be signed by this account, given as SS58 address or `0x`
+    /// hex public key. Otherwise a signature is only checked if the bundle has one.
+    #[clap(long, value_name = "ADDRESS")]
+    require_signer: Option<AccountIdArg>,
+    /// Denotes if output should be printed to stdout.
+    #[clap(flatten)]
+    verbosity: VerbosityFlags,
+    /// Output the result in JSON format
+    #[clap(long, conflicts_with = "verbose")]
+    output_json: bool,
+}
+
+impl VerifyBundleCommand {
+    pub fn run(&self) -> Result<BundleVerificationResult> {
+        let verbosity: Verbosity = TryFrom::<&VerbosityFlags>::try_from(&self.verbosity)?;
+
+        let path = &self.contract;
+        let file = File::open(path)
+            .context(format!("Failed to open contract bundle {}", path.display()))?;
+        let mut metadata: ContractMetadata = serde_json::from_reader(&file).context(
+            format!("Failed to deserialize contract bundle {}", path.display()),
+        )?;
+
+        let mut checks = vec![
+            check_code_hash(&metadata),
+            check_code(&metadata),
+            check_metadata(&metadata),
+        ];
+
+        if let Some(code) = &metadata.source.wasm {
+            let hash = CodeHash(code_hash(&code.0));
+            if self.fix_hash && hash != metadata.source.hash {
+                self.confirm_fix(path, &metadata.source.hash, &hash)?;
+                metadata.source.hash = hash;
+                fs::write(path, serde_json::to_string(&metadata)?).context(format!(
+                    "Failed to write contract bundle {}",
+                    path.display()
+                ))?;
+                checks[0] = BundleCheck::new(
+                    "code_hash",
+                    CheckStatus::Fixed,
+                    format!("Rewrote the code hash to {hash}"),
+                );
+            }
+        }
+
+        checks.push(check_signature(&metadata, self.require_signer.as_ref()));
+
+        Ok(BundleVerificationResult {
+            contract: path.display().to_string(),
+            is_consistent: checks.iter().all(|check| check.status.is_ok()),
+            checks,
+            output_json: self.output_json,
+            verbosity,
+        })
+    }
+
+    /// Asks the user to confirm rewriting the `recorded` code hash of the bundle at
+    /// `path` to the `actual` hash of its code.
+    ///
+    /// Without a terminal to ask on, or with `--output-json`, it fails unless the
+    /// confirmation is skipped.
+    fn confirm_fix(
+        &self,
+        path: &Path,
+        recorded: &CodeHash,
+        actual: &CodeHash,
+    ) -> Result<()> {
+        if self.skip_confirm {
+            return Ok(())
+        }
+        if self.output_json || !io::stdin().is_terminal() {
+            return Err(anyhow!(
+                "The code hash of the contract bundle {} is stale. Pass `--skip-confirm` \
+                 to rewrite it without confirmation.",
+                path.display()
+            ))
+        }
+        print!(
+            "{} {} of {} to {}? (y/{}): ",
+            "Rewrite the code hash".bright_white().bold(),
+            recorded,
+            path.display(),
+            actual,
+            "N".bright_white().bold()
+        );
+        let mut buf = String::new();
+        io::stdout().flush()?;
+        io::stdin().read_line(&mut buf)?;
+        match buf.trim().to_lowercase().as_str() {
+            "y" => Ok(()),
+            // default is 'n'
+            "n" | "" => Err(anyhow!("Code hash not rewritten")),
+            c => Err(anyhow!("Expected either 'y' or 'n', got '{}'", c)),
+        }
+    }
+}
+
+/// Checks that the code hash recorded in the bundle is the hash of the embedded code.
+fn check_code_hash(metadata: &ContractMetadata) -> BundleCheck {
+    let Some(code) = &metadata.source.wasm else {
+        return BundleCheck::new(
+            "code_hash",
+            CheckStatus::Failed,
+            "The bundle does not contain the contract code",
+        )
+    };
+    let hash = CodeHash(code_hash(&code.0));
+    if hash == metadata.source.hash {
+        BundleCheck::new("code_hash", CheckStatus::Passed, format!("{hash}"))
+    } else {
+        BundleCheck::new(
+            "code_hash",
+            CheckStatus::Failed,
+            format!(
+                "The recorded code hash {} does not match the hash {hash} of the code",
+                metadata.source.hash
+            ),
+        )
+    }
+}
+
+/// Checks that the embedded code is a valid Wasm module.
+///
+/// The check is skipped for code built for another target than Wasm, as recorded in
+/// the build info.
+fn check_code(metadata: &ContractMetadata) -> BundleCheck {
+    let Some(code) = &metadata.source.wasm else {
+        return BundleCheck::new(
+            "code",
+            CheckStatus::Skipped,
+            "The bundle does not contain the contract code",
+        )
+    };
+    let target: Target = metadata
+        .source
+        .build_info
+        .as_ref()
+        .and_then(|build_info| build_info.get("target"))
+        .and_then(|target| serde_json::from_value(target.clone()).ok())
+        .unwrap_or_default();
+    if !target.is_wasm() {
+        return BundleCheck::new(
+            "code",
+            CheckStatus::Skipped,
+            format!("The code is built for the {} target", target.llvm_target()),
+        )
+    }
+    match WasmValidation::new(&code.0) {
+        Ok(_) => {
+            BundleCheck::new(
+                "code",
+                CheckStatus::Passed,
+                format!("{} bytes of valid Wasm", code.0.len()),
+            )
+        }
+        Err(err) => {
+            BundleCheck::new(
+                "code",
+                CheckStatus::Failed,
+                format!("The code is not a valid Wasm module: {err:#}"),
+            )
+        }
+    }
+}
+
+/// Checks that the metadata of an ink! contract conforms to the supported ink! metadata
+/// version.
+fn check_metadata(metadata: &ContractMetadata) -> BundleCheck {
+    if !matches!(metadata.source.language.language, Language::Ink) {
+        return BundleCheck::new(
+            "metadata",
+            CheckStatus::Skipped,
+            format!(
+                "The contract is written in {}",
+                metadata.source.language.language
+            ),
+        )
+    }
+    let abi = serde_json::Value::Object(metadata.abi.clone());
+    match serde_json::from_value::<InkProject>(abi) {
+        Ok(project) if *project.version() == METADATA_VERSION => {
+            BundleCheck::new(
+                "metadata",
+                CheckStatus::Passed,
+                format!("ink! metadata version {METADATA_VERSION}"),
+            )
+        }
+        Ok(project) => {
+            BundleCheck::new(
+                "metadata",
+                CheckStatus::Failed,
+                format!(
+                    "Unsupported ink! metadata version {}, expected version \
+                     {METADATA_VERSION}",
+                    project.version()
+                ),
+            )
+        }
+        Err(err) => {
+            BundleCheck::new(
+                "metadata",
+                CheckStatus::Failed,
+                format!(
+                    "The metadata does not conform to ink! metadata version \
+                     {METADATA_VERSION}: {err}"
+                ),
+            )
+        }
+    }
+}
+
+/// Checks the signature embedded in the bundle with `cargo contract sign-bundle`, and
+/// that it was made by the `required` signer.
+fn check_signature(
+    metadata: &ContractMetadata,
+    required: Option<&AccountIdArg>,
+) -> BundleCheck {
+    let signature = match BundleSignature::of(metadata) {
+        Ok(Some(signature)) => signature,
+        Ok(None) => {
+            let status = if required.is_some() {
+                CheckStatus::Failed
+            } else {
+                CheckStatus::Skipped
+            };
+            return BundleCheck::new("signature", status, "The bundle is not signed")
+        }
+        Err(err) => {
+            return BundleCheck::new("signature", CheckStatus::Failed, format!("{err:#}"))
+        }
+    };
+    let signer = match signature.verify(metadata).and_then(|()| signature.signer()) {
+        Ok(signer) => subxt::utils::AccountId32(signer),
+        Err(err) => {
+            return BundleCheck::new("signature", CheckStatus::Failed, format!("{err:#}"))
+        }
+    };
+    match required {
+        Some(required) if required.account_id() != &signer => {
+            BundleCheck::new(
+                "signature",
+                CheckStatus::Failed,
+                format!(
+                    "The bundle is signed by {signer}, expected {}",
+                    required.account_id()
+                ),
+            )
+        }
+        _ => {
+            BundleCheck::new(
+                "signature",
+                CheckStatus::Passed,
+                format!("Signed by {signer} with {:?}", signature.scheme),
+            )
+        }
+    }
+}
+
+/// The outcome of a check of a contract bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// The check does not apply to the bundle.
+    Skipped,
+    /// The check failed, and the bundle was rewritten with `--fix-hash`.
+    Fixed,
+}
+
+impl CheckStatus {
+    /// Returns `false` if the check failed.
+    pub fn is_ok(&self) -> bool {
+        !matches!(self, Self::Failed)
+    }
+}
+
+/// A check of a contract bundle.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BundleCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl BundleCheck {
+    fn new(name: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// The result of the checks of a contract bundle.
+#[derive(serde::Serialize)]
+pub struct BundleVerificationResult {
+    pub contract: String,
+    pub is_consistent: bool,
+    pub checks: Vec<BundleCheck>,
+    #[serde(skip_serializing)]
+    pub output_json: bool,
+    #[serde(skip_serializing)]
+    pub verbosity: Verbosity,
+}
+
+impl BundleVerificationResult {
+    /// Display the result in a fancy format
+    pub fn display(&self) -> String {
+        let mut out = String::new();
+        for check in &self.checks {
+            let status = match check.status {
+                CheckStatus::Passed => "passed".bright_green().bold(),
+                CheckStatus::Failed => "failed".bright_red().bold(),
+                CheckStatus::Skipped => "skipped".yellow().bold(),
+                CheckStatus::Fixed => "fixed".cyan().bold(),
+            };
+            out.push_str(&format!(
+                "{:>12} {} {}\n",
+                check.name.bright_white().bold(),
+                status,
+                check.message
+            ));
+        }
+        if self.is_consistent {
+            out.push_str(&format!(
+                "\n{} {}",
+                "Successfully verified contract bundle"
+                    .bright_green()
+                    .bold(),
+                format!("`{}`", &self.contract).bold(),
+            ));
+        }
+        out
+    }
+
+    /// Display the results in a pretty formatted JSON string.
+    pub fn serialize_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Returns an error listing the failed checks, if any.
+    pub fn ensure_consistent(&self) -> Result<()> {
+        let failed: Vec<_> = self
+            .checks
+            .iter()
+            .filter(|check| !check.status.is_ok())
+            .map(|check| format!("{}: {}", check.name, check.message))
+            .collect();
+        anyhow::ensure!(
+            failed.is_empty(),
+            "The contract bundle `{}` is inconsistent:\n{}",
+            self.contract,
+            failed.join("\n")
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_metadata::{
+        Compiler,
+        Contract,
+        Source,
+        SourceCompiler,
+        SourceLanguage,
+        SourceWasm,
+    };
+    use semver::Version;
+
+    /// The smallest valid Wasm module.
+    const WASM: &[u8] = b"\0asm\x01\0\0\0";
+
+    fn bundle(code: &[u8], hash: [u8; 32]) -> ContractMetadata {
+        let source = Source::new(
+            Some(SourceWasm::new(code.to_vec())),
+            CodeHash(hash),
+            SourceLanguage::new(Language::Ink, Version::new(5, 0, 0)),
+            SourceCompiler::new(Compiler::RustC, Version::new(1, 76, 0)),
+            None,
+        );
+        let contract = Contract::builder()
+            .name("flipper")
+            .version(Version::new(0, 1, 0))
+            .authors(vec!["Parity Technologies <admin@parity.io>".to_string()])
+            .build()
+            .unwrap();
+        ContractMetadata::new(source, contract, None, None, Default::default())
+    }
+
+    #[test]
+    fn checks_code_hash() {
+        let check = check_code_hash(&bundle(WASM, code_hash(WASM)));
+        assert_eq!(check.status, CheckStatus::Passed);
+
+        let check = check_code_hash(&bundle(WASM, [0; 32]));
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(check.message.starts_with("The recorded code hash 0x"));
+
+        let mut metadata = bundle(WASM, code_hash(WASM));
+        metadata.remove_source_wasm_attribute();
+        assert_eq!(check_code_hash(&metadata).status, CheckStatus::Failed);
+        assert_eq!(check_code(&metadata).status, CheckStatus::Skipped);
+    }
+
+    #[test]
+    fn checks_code() {
+        assert_eq!(
+            check_code(&bundle(WASM, code_hash(WASM))).status,
+            CheckStatus::Passed
+        );
+        let check = check_code(&bundle(b"not wasm", [0; 32]));
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(check
+            .message
+            .starts_with("The code is not a valid Wasm module"));
+    }
+
+    #[test]
+    fn checks_metadata() {
+        let check = check_metadata(&bundle(WASM, code_hash(WASM)));
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(
+            check
+                .message
+                .starts_with("The metadata does not conform to ink! metadata version 5"),
+            "{}",
+            check.message
+        );
+    }
+
+    #[test]
+    fn fixes_stale_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flipper.contract");
+        fs::write(
+            &path,
+            serde_json::to_string(&bundle(WASM, [0; 32])).unwrap(),
+        )
+        .unwrap();
+
+        let cmd = VerifyBundleCommand {
+            contract: path.clone(),
+            fix_hash: true,
+            skip_confirm: true,
+            require_signer: None,
+            verbosity: Default::default(),
+            output_json: true,
+        };
+        let result = cmd.run().unwrap();
+        assert_eq!(result.checks[0].status, CheckStatus::Fixed);
+
+        let metadata = ContractMetadata::load(&path).unwrap();
+        assert_eq!(metadata.source.hash, CodeHash(code_hash(WASM)));
+    }
+
+    #[test]
+    fn checks_signature() {
+        let alice: AccountIdArg = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+            .parse()
+            .unwrap();
+        let bob: AccountIdArg = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
+            .parse()
+            .unwrap();
+        let mut metadata = bundle(WASM, code_hash(WASM));
+        assert_eq!(
+            check_signature(&metadata, None).status,
+            CheckStatus::Skipped
+        );
+        assert_eq!(
+            check_signature(&metadata, Some(&alice)).status,
+            CheckStatus::Failed
+        );
+
+        contract_extrinsics::sign_bundle(
+            &mut metadata,
+            "//Alice",
+            contract_extrinsics::SignatureScheme::Sr25519,
+        )
+        .unwrap();
+        assert_eq!(
+            check_signature(&metadata, Some(&alice)).status,
+            CheckStatus::Passed
+        );
+        let check = check_signature(&metadata, Some(&bob));
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(check.message.starts_with("The bundle is signed by"));
+
+        metadata.source.hash = CodeHash([0; 32]);
+        assert_eq!(check_signature(&metadata, None).status, CheckStatus::Failed);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    parse_node_url,
+    resolve_ss58_prefix,
+    AdditionalMetadataOpts,
+    ContractAddressArg,
+    DefaultConfig,
+};
+use anyhow::Result;
+use colored::Colorize;
+use contract_build::{
+    name_value_println,
+    DEFAULT_KEY_COL_WIDTH,
+};
+use contract_extrinsics::{
+    require_websocket,
+    url_to_string,
+    ContractArtifacts,
+    ContractEvent,
+    ContractEventWatcher,
+    ErrorVariant,
+    Ss58Prefix,
+};
+use contract_transcode::mark_indexed_fields;
+use std::path::PathBuf;
+
+/// The format the watched events are printed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum WatchOutput {
+    /// Human readable output.
+    #[default]
+    Text,
+    /// One JSON object per line and event.
+    Ndjson,
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(name = "watch", about = "Watch the events emitted by a contract")]
+pub struct WatchCommand {
+    /// The address of the contract to watch the events of.
+    #[clap(name = "contract", long, env = "CONTRACT")]
+    contract: ContractAddressArg,
+    /// Path to a contract build artifact file: a raw `.wasm` file, a `.contract` bundle,
+    /// or a `.json` metadata file.
+    #[clap(value_parser, conflicts_with = "manifest_path")]
+    file: Option<PathBuf>,
+    /// Path to the `Cargo.toml` of the contract.
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+    /// Websockets or HTTP url of a substrate node.
+    #[clap(
+        name = "url",
+        long,
+        value_parser = parse_node_url,
+        default_value = "ws://localhost:9944"
+    )]
+    url: url::Url,
+    /// Follow best blocks instead of finalized blocks.
+    #[clap(long)]
+    best: bool,
+    /// Backfill the events of all blocks starting at this block number before
+    /// following new blocks.
+    #[clap(long)]
+    from_block: Option<u64>,
+    /// The format the events are printed in.
+    #[clap(long, value_enum, default_value_t)]
+    output: WatchOutput,
+    /// The SS58 address prefix used to display account ids. If not specified, the
+    /// prefix reported by the chain is used.
+    #[clap(long)]
+    ss58_prefix: Option<Ss58Prefix>,
+    #[clap(flatten)]
+    additional_metadata: AdditionalMetadataOpts,
+}
+
+impl WatchCommand {
+    pub async fn run(&self) -> Result<(), ErrorVariant> {
+        require_websocket(&url_to_string(&self.url), "Watching contract events")?;
+        let ss58_prefix = resolve_ss58_prefix(self.ss58_prefix, &self.url).await?;
+        self.contract.note_ss58_prefix_mismatch(ss58_prefix);
+
+        let mut watcher = ContractEventWatcher::<DefaultConfig>::new(
+            self.url.clone(),
+            self.contract.address(),
+        )
+        .best(self.best)
+        .from_block(self.from_block)
+        .additional_metadata(self.additional_metadata.load(ss58_prefix, false)?);
+
+        match ContractArtifacts::from_manifest_or_file(
+            self.manifest_path.as_ref(),
+            self.file.as_ref(),
+        ) {
+            Ok(contract_artifacts) => {
+                for notice in contract_artifacts.notices() {
+                    eprintln!("{notice}");
+                }
+                let transcoder = contract_artifacts
+                    .contract_transcoder()?
+                    .with_ss58_prefix(ss58_prefix.value());
+                watcher = watcher.transcoder(transcoder);
+            }
+            Err(_) => {
+                eprintln!(
+                    "{} Displaying raw event data: no valid contract metadata artifacts found",
+                    "Info:".cyan().bold(),
+                );
+            }
+        }
+
+        let output = self.output;
+        watcher
+            .watch(|event| {
+                match output {
+                    WatchOutput::Text => print_event(&event),
+                    WatchOutput::Ndjson => println!("{}", serde_json::to_string(&event)?),
+                }
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+fn print_event(event: &ContractEvent) {
+    let location = match event.extrinsic_index {
+        Some(index) => format!("#{}, extrinsic {index}", event.block_number),
+        None => format!("#{}", event.block_number),
+    };
+    name_value_println!("Block", location, DEFAULT_KEY_COL_WIDTH);
+    name_value_println!(
+        "Event",
+        mark_indexed_fields(&event.event, &event.indexed),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    if let Some(metadata) = &event.metadata {
+        name_value_println!("Metadata", metadata, DEFAULT_KEY_COL_WIDTH);
+    }
+}
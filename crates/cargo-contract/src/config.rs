@@ -0,0 +1,177 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// The name of the config file of a contract, next to its `Cargo.toml`.
+pub const CONTRACT_CONFIG_FILE: &str = "contract.toml";
+
+/// A network of a `contract.toml`, defined as a `[network.<name>]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Network {
+    /// The url of a node of the network.
+    pub url: Option<url::Url>,
+    /// The environment variable containing the secret key URI of the signer.
+    pub suri_env: Option<String>,
+    /// The default storage deposit limit, e.g. `1SBY`.
+    pub storage_deposit_limit: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContractConfigFile {
+    #[serde(default)]
+    network: BTreeMap<String, Network>,
+}
+
+/// The named networks of a `contract.toml`, selected with `--network`.
+#[derive(Debug)]
+pub struct ContractConfig {
+    path: PathBuf,
+    networks: BTreeMap<String, Network>,
+}
+
+impl ContractConfig {
+    /// Loads the `config` file given with `--config`, or else the `contract.toml` next
+    /// to the nearest `Cargo.toml` in `dir` or one of its parents, if it exists.
+    pub fn resolve(config: Option<&Path>, dir: &Path) -> Result<Option<Self>> {
+        match config {
+            Some(path) => Self::load(path).map(Some),
+            None => {
+                find_contract_config(dir)
+                    .map(|path| Self::load(&path))
+                    .transpose()
+            }
+        }
+    }
+
+    /// Loads the networks of the config file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let file: ContractConfigFile = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            networks: file.network,
+        })
+    }
+
+    /// The path of the config file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the network `name`, if it is defined.
+    pub fn network(&self, name: &str) -> Option<&Network> {
+        self.networks.get(name)
+    }
+}
+
+/// Returns the `contract.toml` next to the nearest `Cargo.toml` found in `dir` or one of
+/// its ancestors, if it exists.
+fn find_contract_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(|dir| dir.join(CONTRACT_CONFIG_FILE))
+        .filter(|file| file.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_contract_config_next_to_cargo_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert!(ContractConfig::resolve(None, &nested).unwrap().is_none());
+
+        let file = dir.path().join(CONTRACT_CONFIG_FILE);
+        std::fs::write(
+            &file,
+            r#"
+                [network.shibuya]
+                url = "wss://rpc.shibuya.astar.network"
+                suri-env = "SHIBUYA_SURI"
+                storage-deposit-limit = "1SBY"
+            "#,
+        )
+        .unwrap();
+        let config = ContractConfig::resolve(None, &nested).unwrap().unwrap();
+        assert_eq!(config.path(), file);
+        assert_eq!(
+            config.network("shibuya"),
+            Some(&Network {
+                url: Some("wss://rpc.shibuya.astar.network".parse().unwrap()),
+                suri_env: Some("SHIBUYA_SURI".to_string()),
+                storage_deposit_limit: Some("1SBY".to_string()),
+            })
+        );
+        assert_eq!(config.network("rococo"), None);
+    }
+
+    #[test]
+    fn explicit_config_file_overrides_discovery() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        std::fs::write(
+            dir.path().join(CONTRACT_CONFIG_FILE),
+            "[network.local]\nurl = \"ws://localhost:9944\"\n",
+        )
+        .unwrap();
+        let explicit = dir.path().join("networks.toml");
+        std::fs::write(&explicit, "[network.rococo]\nsuri-env = \"ROCOCO_SURI\"\n")
+            .unwrap();
+
+        let config = ContractConfig::resolve(Some(&explicit), dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.path(), explicit);
+        assert!(config.network("local").is_none());
+        assert!(config.network("rococo").is_some());
+
+        let missing = dir.path().join("missing.toml");
+        let err = ContractConfig::resolve(Some(&missing), dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to read"), "{err:#}");
+    }
+
+    #[test]
+    fn rejects_unknown_network_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(CONTRACT_CONFIG_FILE);
+        std::fs::write(&file, "[network.local]\nsuri = \"//Alice\"\n").unwrap();
+        let err = ContractConfig::load(&file).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("unknown field `suri`"),
+            "{err:#}"
+        );
+    }
+}
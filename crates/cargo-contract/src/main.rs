@@ -17,38 +17,61 @@
 #![deny(unused_crate_dependencies)]
 
 mod cmd;
+mod config;
 
 use self::cmd::{
+    display_workspace_results,
     BuildCommand,
+    CLIExtrinsicOpts,
     CallCommand,
     CheckCommand,
+    CompletionsCommand,
+    ConfigCommand,
+    ConvertMetadataCommand,
     DecodeCommand,
     ErrorVariant,
     GenerateSchemaCommand,
     InfoCommand,
     InstantiateCommand,
+    LogFormat,
+    NodeCommand,
+    OutputSchemaCommand,
     RemoveCommand,
     RpcCommand,
+    RunCommand,
+    SignBundleCommand,
     StorageCommand,
+    TestCommand,
+    TransferCommand,
     UploadCommand,
+    VerifyBundleCommand,
     VerifyCommand,
     VerifySchemaCommand,
+    WatchCommand,
 };
 use anyhow::{
     anyhow,
-    Error,
     Result,
 };
 use clap::{
+    ArgMatches,
     Args,
+    CommandFactory,
+    FromArgMatches,
     Parser,
     Subcommand,
 };
-use cmd::encode::EncodeCommand;
+use cmd::{
+    encode::EncodeCommand,
+    profile::Profile,
+};
 use colored::Colorize;
+use config::ContractConfig;
 use contract_build::{
     util::DEFAULT_KEY_COL_WIDTH,
     OutputType,
+    Template,
+    TEMPLATES,
 };
 use contract_extrinsics::InstantiateExec;
 use sp_weights::Weight;
@@ -89,6 +112,52 @@ pub(crate) enum Opts {
 pub(crate) struct ContractArgs {
     #[clap(subcommand)]
     cmd: Command,
+    /// Print errors as a single JSON object on stderr, regardless of the output mode of
+    /// the command.
+    #[clap(long, global = true)]
+    error_json: bool,
+    /// The profile of the config files to take the defaults of the extrinsic commands
+    /// from, e.g. the url and signer of a network. The `default` profile is used if it
+    /// exists and no profile is selected.
+    #[clap(
+        long,
+        visible_alias = "network",
+        global = true,
+        env = "CARGO_CONTRACT_PROFILE"
+    )]
+    profile: Option<String>,
+    /// The `contract.toml` to take the `--network` from, instead of the one next to the
+    /// `Cargo.toml` of the contract in the current directory.
+    #[clap(long, global = true, value_parser)]
+    config: Option<PathBuf>,
+    /// The format of the log output. With `json`, the status lines of the commands are
+    /// logged as JSON objects on stderr and only the final result is printed to stdout.
+    #[clap(long, global = true, value_enum, default_value_t = LogFormat::Plain)]
+    log_format: LogFormat,
+}
+
+impl ContractArgs {
+    /// Applies the selected profile to the options of an extrinsic command, given the
+    /// `matches` of the parsed command line, and reports the url of the node in verbose
+    /// mode.
+    fn apply_profile(&mut self, matches: &ArgMatches) -> Result<()> {
+        let Some(opts) = self.cmd.extrinsic_cli_opts_mut() else {
+            return Ok(())
+        };
+        let (_, matches) = matches
+            .subcommand()
+            .and_then(|(_, contract)| contract.subcommand())
+            .expect("a subcommand is required by clap");
+        opts.set_url_source(matches);
+        let contract_config =
+            ContractConfig::resolve(self.config.as_deref(), &std::env::current_dir()?)?;
+        if let Some(profile) =
+            Profile::load(self.profile.as_deref(), contract_config.as_ref())?
+        {
+            opts.apply_profile(&profile, matches)?;
+        }
+        opts.report_url()
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -108,10 +177,22 @@ enum Command {
     #[clap(name = "new")]
     New {
         /// The name of the newly created smart contract
-        name: String,
+        #[clap(required_unless_present = "list_templates")]
+        name: Option<String>,
         /// The optional target directory for the contract project
         #[clap(short, long, value_parser)]
         target_dir: Option<PathBuf>,
+        /// The template of the contract project: the name of a built-in template, or
+        /// the URL of a git repository
+        #[clap(long, default_value = "flipper")]
+        template: Template,
+        /// List the built-in templates
+        #[clap(long, conflicts_with_all = ["name", "template"])]
+        list_templates: bool,
+        /// Do not add the contract to the members of an enclosing cargo workspace, but
+        /// create it as a standalone project
+        #[clap(long)]
+        no_workspace: bool,
     },
     /// Compiles the contract, generates metadata, bundles both together in a
     /// `<name>.contract` file
@@ -139,51 +220,175 @@ enum Command {
     /// Remove contract code
     #[clap(name = "remove")]
     Remove(RemoveCommand),
+    /// Transfer balance to a contract
+    #[clap(name = "transfer")]
+    Transfer(TransferCommand),
     /// Display information about a contract
     #[clap(name = "info")]
     Info(InfoCommand),
     /// Inspect the on-chain storage of a contract.
     #[clap(name = "storage")]
     Storage(StorageCommand),
+    /// Watch the events emitted by a contract.
+    #[clap(name = "watch")]
+    Watch(WatchCommand),
     /// Verifies that a given contract binary matches the build result of the specified
     /// workspace.
     #[clap(name = "verify")]
     Verify(VerifyCommand),
+    /// Checks that the code, the code hash and the metadata of a contract bundle are
+    /// consistent with each other.
+    #[clap(name = "verify-bundle")]
+    VerifyBundle(VerifyBundleCommand),
+    /// Signs a contract bundle, so that its recipients can verify who it was built by.
+    #[clap(name = "sign-bundle")]
+    SignBundle(SignBundleCommand),
     /// Generates schema from the current metadata specification.
     #[clap(name = "generate-schema")]
     GenerateSchema(GenerateSchemaCommand),
     /// Verify schema from the current metadata specification.
     #[clap(name = "verify-schema")]
     VerifySchema(VerifySchemaCommand),
+    /// Converts the metadata of a contract between ink! metadata versions.
+    #[clap(name = "convert-metadata")]
+    ConvertMetadata(ConvertMetadataCommand),
+    /// Generates the JSON Schema of the `--output-json` output of the extrinsic
+    /// commands.
+    #[clap(name = "schema")]
+    Schema(OutputSchemaCommand),
     /// Make a raw RPC call.
     #[clap(name = "rpc")]
     Rpc(RpcCommand),
+    /// Inspect the profiles of the config files.
+    #[clap(name = "config")]
+    Config(ConfigCommand),
+    /// Generate a shell completion script for `cargo contract`.
+    #[clap(name = "completions")]
+    Completions(CompletionsCommand),
+    /// Manage a local `substrate-contracts-node` for development.
+    #[clap(name = "node")]
+    Node(NodeCommand),
+    /// Test the contract on a dev node with `--e2e`.
+    #[clap(name = "test")]
+    Test(TestCommand),
+    /// Run a script of upload, instantiate and call steps.
+    #[clap(name = "run")]
+    Run(RunCommand),
 }
 
 fn main() {
-    tracing_subscriber::fmt::init();
-
-    let Opts::Contract(args) = Opts::parse();
+    let matches = Opts::command().get_matches();
+    let Opts::Contract(mut args) =
+        Opts::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    args.log_format.init();
 
-    match exec(args.cmd) {
+    let result = args
+        .apply_profile(&matches)
+        .and_then(|()| exec(&args))
+        .map_err(ErrorVariant::from);
+    if let Some(extrinsic_cli_opts) = args.cmd.extrinsic_cli_opts() {
+        extrinsic_cli_opts.print_timings(args.cmd.output_json());
+    }
+    match result {
         Ok(()) => {}
         Err(err) => {
-            eprintln!("{err:?}");
-            std::process::exit(1);
+            let exit_code = err.exit_code();
+            eprintln!(
+                "{}",
+                format_err(err, args.error_json, args.cmd.output_json())
+            );
+            std::process::exit(exit_code);
+        }
+    }
+}
+
+impl Command {
+    /// Returns whether the command exports its output in JSON format.
+    fn output_json(&self) -> bool {
+        match self {
+            Command::Upload(upload) => upload.output_json(),
+            Command::Instantiate(instantiate) => instantiate.output_json(),
+            Command::Call(call) => call.output_json(),
+            Command::Remove(remove) => remove.output_json(),
+            Command::Transfer(transfer) => transfer.output_json(),
+            _ => false,
+        }
+    }
+
+    /// Returns the options of the commands which submit an extrinsic.
+    fn extrinsic_cli_opts(&self) -> Option<&CLIExtrinsicOpts> {
+        match self {
+            Command::Upload(upload) => Some(upload.extrinsic_cli_opts()),
+            Command::Instantiate(instantiate) => Some(instantiate.extrinsic_cli_opts()),
+            Command::Call(call) => Some(call.extrinsic_cli_opts()),
+            Command::Remove(remove) => Some(remove.extrinsic_cli_opts()),
+            Command::Transfer(transfer) => Some(transfer.extrinsic_cli_opts()),
+            _ => None,
+        }
+    }
+
+    /// Returns the options of the commands which submit an extrinsic.
+    fn extrinsic_cli_opts_mut(&mut self) -> Option<&mut CLIExtrinsicOpts> {
+        match self {
+            Command::Upload(upload) => Some(upload.extrinsic_cli_opts_mut()),
+            Command::Instantiate(instantiate) => {
+                Some(instantiate.extrinsic_cli_opts_mut())
+            }
+            Command::Call(call) => Some(call.extrinsic_cli_opts_mut()),
+            Command::Remove(remove) => Some(remove.extrinsic_cli_opts_mut()),
+            Command::Transfer(transfer) => Some(transfer.extrinsic_cli_opts_mut()),
+            _ => None,
         }
     }
 }
 
-fn exec(cmd: Command) -> Result<()> {
+fn exec(args: &ContractArgs) -> Result<()> {
     let runtime = Runtime::new().expect("Failed to create Tokio runtime");
-    match &cmd {
-        Command::New { name, target_dir } => {
-            contract_build::new_contract_project(name, target_dir.as_ref())?;
+    match &args.cmd {
+        Command::New {
+            list_templates: true,
+            ..
+        } => {
+            for (name, description) in TEMPLATES {
+                println!("{name:<16} {description}");
+            }
+            Ok(())
+        }
+        Command::New {
+            name,
+            target_dir,
+            template,
+            no_workspace,
+            ..
+        } => {
+            let name = name.as_deref().expect("required by clap");
+            let workspace = contract_build::new_contract_project_from_template(
+                name,
+                target_dir.as_ref(),
+                template,
+                !no_workspace,
+            )?;
             println!("Created contract {name}");
+            if let Some(workspace) = workspace {
+                println!("Added {name} to the members of {}", workspace.display());
+            }
+            Ok(())
+        }
+        Command::Build(build) if build.is_workspace_build()? => {
+            let results = build.exec_workspace()?;
+            match results.first() {
+                Some(result) if matches!(result.output_type, OutputType::Json) => {
+                    println!("{}", serde_json::to_string_pretty(&results)?)
+                }
+                Some(result) if result.verbosity.is_verbose() => {
+                    println!("{}", display_workspace_results(&results)?)
+                }
+                _ => (),
+            }
             Ok(())
         }
         Command::Build(build) => {
-            let result = build.exec().map_err(format_err)?;
+            let result = build.exec()?;
 
             if matches!(result.output_type, OutputType::Json) {
                 println!("{}", result.serialize_json()?)
@@ -193,54 +398,29 @@ fn exec(cmd: Command) -> Result<()> {
             Ok(())
         }
         Command::Check(check) => {
-            let res = check.exec().map_err(format_err)?;
+            let res = check.exec()?;
             assert!(
                 res.dest_wasm.is_none(),
                 "no dest_wasm must be on the generation result"
             );
             Ok(())
         }
-        Command::Upload(upload) => {
-            runtime.block_on(async {
-                upload
-                    .handle()
-                    .await
-                    .map_err(|err| map_extrinsic_err(err, upload.output_json()))
-            })
-        }
+        Command::Upload(upload) => runtime.block_on(async { Ok(upload.handle().await?) }),
         Command::Instantiate(instantiate) => {
-            runtime.block_on(async {
-                instantiate
-                    .handle()
-                    .await
-                    .map_err(|err| map_extrinsic_err(err, instantiate.output_json()))
-            })
-        }
-        Command::Call(call) => {
-            runtime.block_on(async {
-                call.handle()
-                    .await
-                    .map_err(|err| map_extrinsic_err(err, call.output_json()))
-            })
+            runtime.block_on(async { Ok(instantiate.handle().await?) })
         }
-        Command::Encode(encode) => encode.run().map_err(format_err),
-        Command::Decode(decode) => decode.run().map_err(format_err),
-        Command::Remove(remove) => {
-            runtime.block_on(async {
-                remove
-                    .handle()
-                    .await
-                    .map_err(|err| map_extrinsic_err(err, remove.output_json()))
-            })
-        }
-        Command::Info(info) => {
-            runtime.block_on(async { info.run().await.map_err(format_err) })
-        }
-        Command::Storage(storage) => {
-            runtime.block_on(async { storage.run().await.map_err(format_err) })
+        Command::Call(call) => runtime.block_on(async { Ok(call.handle().await?) }),
+        Command::Encode(encode) => Ok(encode.run()?),
+        Command::Decode(decode) => Ok(decode.run()?),
+        Command::Remove(remove) => runtime.block_on(async { Ok(remove.handle().await?) }),
+        Command::Transfer(transfer) => {
+            runtime.block_on(async { Ok(transfer.handle().await?) })
         }
+        Command::Info(info) => runtime.block_on(async { Ok(info.run().await?) }),
+        Command::Storage(storage) => runtime.block_on(async { Ok(storage.run().await?) }),
+        Command::Watch(watch) => runtime.block_on(async { Ok(watch.run().await?) }),
         Command::Verify(verify) => {
-            let result = verify.run().map_err(format_err)?;
+            let result = verify.run()?;
 
             if result.output_json {
                 println!("{}", result.serialize_json()?)
@@ -249,13 +429,24 @@ fn exec(cmd: Command) -> Result<()> {
             }
             Ok(())
         }
+        Command::VerifyBundle(verify) => {
+            let result = verify.run()?;
+
+            if result.output_json {
+                println!("{}", result.serialize_json()?)
+            } else if result.verbosity.is_verbose() {
+                println!("{}", result.display())
+            }
+            Ok(result.ensure_consistent()?)
+        }
+        Command::SignBundle(sign) => Ok(sign.run()?),
         Command::GenerateSchema(generate) => {
-            let result = generate.run().map_err(format_err)?;
+            let result = generate.run()?;
             println!("{}", result);
             Ok(())
         }
         Command::VerifySchema(verify) => {
-            let result = verify.run().map_err(format_err)?;
+            let result = verify.run()?;
 
             if result.output_json {
                 println!("{}", result.serialize_json()?)
@@ -264,28 +455,37 @@ fn exec(cmd: Command) -> Result<()> {
             }
             Ok(())
         }
-        Command::Rpc(rpc) => {
-            runtime.block_on(async { rpc.run().await.map_err(format_err) })
+        Command::ConvertMetadata(convert) => Ok(convert.run()?),
+        Command::Schema(schema) => {
+            let result = schema.run()?;
+            println!("{}", result);
+            Ok(())
         }
+        Command::Rpc(rpc) => runtime.block_on(async { Ok(rpc.run().await?) }),
+        Command::Config(config) => {
+            Ok(config.run(args.profile.as_deref(), args.config.as_deref())?)
+        }
+        Command::Completions(completions) => Ok(completions.run(Opts::command())?),
+        Command::Node(node) => runtime.block_on(async { node.run().await }),
+        Command::Test(test) => runtime.block_on(async { Ok(test.run().await?) }),
+        Command::Run(run) => runtime.block_on(async { Ok(run.run().await?) }),
     }
 }
 
-fn map_extrinsic_err(err: ErrorVariant, is_json: bool) -> Error {
-    if is_json {
-        anyhow!(
-            "{}",
-            serde_json::to_string_pretty(&err)
-                .expect("error serialization is infallible; qed")
-        )
+/// Formats the error of a failed command: as a single line of JSON with `error_json`,
+/// as pretty printed JSON if the command exports its output in JSON format, otherwise
+/// in a human readable format.
+fn format_err(err: ErrorVariant, error_json: bool, output_json: bool) -> String {
+    if error_json {
+        serde_json::to_string(&err).expect("error serialization is infallible; qed")
+    } else if output_json {
+        serde_json::to_string_pretty(&err)
+            .expect("error serialization is infallible; qed")
     } else {
-        format_err(err)
+        format!(
+            "{} {}",
+            "ERROR:".bright_red().bold(),
+            format!("{err:?}").bright_red()
+        )
     }
 }
-
-fn format_err<E: Debug>(err: E) -> Error {
-    anyhow!(
-        "{} {}",
-        "ERROR:".bright_red().bold(),
-        format!("{err:?}").bright_red()
-    )
-}
@@ -0,0 +1,78 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+/// Create a `cargo contract` command
+fn cargo_contract<P: AsRef<Path>>(path: P) -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.current_dir(path).arg("contract");
+    cmd
+}
+
+/// The metadata of the `flipper` example contract.
+fn flipper_metadata() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("flipper.json")
+}
+
+#[test]
+fn completions_generates_script_for_every_shell() {
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        // when
+        let output = cargo_contract(env!("CARGO_MANIFEST_DIR"))
+            .args(["completions", shell])
+            .output()
+            .expect("failed to execute process");
+
+        // then
+        let stdout = std::str::from_utf8(&output.stdout).unwrap();
+        assert!(output.status.success(), "{shell}: {stdout}");
+        assert!(stdout.contains("instantiate"), "{shell}: {stdout}");
+    }
+}
+
+#[test]
+fn completions_lists_messages_and_constructors_from_metadata() {
+    // when
+    let messages = cargo_contract(env!("CARGO_MANIFEST_DIR"))
+        .args(["completions", "--list", "messages", "--file"])
+        .arg(flipper_metadata())
+        .output()
+        .expect("failed to execute process");
+    let constructors = cargo_contract(env!("CARGO_MANIFEST_DIR"))
+        .args(["completions", "--list", "constructors", "--file"])
+        .arg(flipper_metadata())
+        .output()
+        .expect("failed to execute process");
+
+    // then
+    assert!(messages.status.success());
+    assert_eq!(
+        std::str::from_utf8(&messages.stdout).unwrap(),
+        "flip\nget\n"
+    );
+    assert!(constructors.status.success());
+    assert_eq!(
+        std::str::from_utf8(&constructors.stdout).unwrap(),
+        "new\ndefault\n"
+    );
+}
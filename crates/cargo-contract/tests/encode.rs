@@ -16,6 +16,21 @@
 
 use std::path::Path;
 
+/// A `.contract` bundle of the `flipper` example contract with placeholder code.
+fn flipper_bundle() -> Vec<u8> {
+    let metadata_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("flipper.json");
+    let mut bundle: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(metadata_path).unwrap()).unwrap();
+    let code = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    bundle["source"]["wasm"] = format!("0x{}", hex::encode(code)).into();
+    bundle["source"]["hash"] =
+        format!("0x{}", hex::encode(contract_build::code_hash(&code))).into();
+    serde_json::to_vec(&bundle).unwrap()
+}
+
 /// Create a `cargo contract` command
 fn cargo_contract<P: AsRef<Path>>(path: P) -> assert_cmd::Command {
     let mut cmd = assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
@@ -107,3 +122,33 @@ fn encode_works() {
         .success()
         .stdout(predicates::str::contains(output));
 }
+
+#[test]
+fn encode_reads_bundle_from_stdin() {
+    cargo_contract(env!("CARGO_MANIFEST_DIR"))
+        .args(["encode", "-", "--message", "flip"])
+        .write_stdin(flipper_bundle())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Encoded data: 633AA551"))
+        .stderr(predicates::str::contains(
+            "Loaded the contract bundle from stdin, SHA-256 0x",
+        ));
+}
+
+#[test]
+fn stdin_requires_bundle_with_code() {
+    let metadata = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("flipper.json");
+
+    cargo_contract(env!("CARGO_MANIFEST_DIR"))
+        .args(["encode", "-", "--message", "flip"])
+        .write_stdin(std::fs::read(metadata).unwrap())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The content of stdin is contract metadata without code",
+        ));
+}
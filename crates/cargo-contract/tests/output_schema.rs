@@ -0,0 +1,58 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Create a `cargo contract` command
+fn cargo_contract() -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("contract");
+    cmd
+}
+
+#[test]
+fn schema_documents_field_order() {
+    // when
+    let output = cargo_contract()
+        .args(["schema", "--command", "instantiate"])
+        .output()
+        .expect("failed to execute process");
+
+    // then
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let order: Vec<_> = schema["definitions"]["InstantiateResult"]["x-field-order"]
+        .as_array()
+        .expect("the field order is documented")
+        .iter()
+        .map(|field| field.as_str().unwrap())
+        .collect();
+    assert_eq!(
+        order,
+        [
+            "contract",
+            "code_hash",
+            "storage_deposit_limit",
+            "events",
+            "existing",
+            "wasm_validation",
+            "gas_limit_submitted",
+            "weight_consumed",
+            "explorer_url",
+            "weight_used",
+            "deposit_refunded",
+            "timings",
+        ]
+    );
+}
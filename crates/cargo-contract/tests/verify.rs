@@ -232,3 +232,24 @@ fn verify_different_contracts() {
         .failure()
         .stderr(predicates::str::contains(output));
 }
+
+#[test]
+fn verify_aborts_on_expected_hash_mismatch() {
+    let metadata_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("flipper.json");
+    let mut bundle: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(metadata_path).unwrap()).unwrap();
+    bundle["source"]["wasm"] = "0x0061736d01000000".into();
+    let expected_hash = format!("0x{}", "11".repeat(32));
+
+    cargo_contract(env!("CARGO_MANIFEST_DIR"))
+        .args(["verify", "-", "--expected-hash", &expected_hash])
+        .write_stdin(serde_json::to_vec(&bundle).unwrap())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(format!(
+            "does not match the expected code hash {expected_hash}"
+        )));
+}
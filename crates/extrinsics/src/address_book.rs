@@ -0,0 +1,130 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+};
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_transcode::{
+    AccountId32,
+    Value,
+};
+
+/// Human readable aliases of known account ids, displayed next to the addresses in the
+/// output of the commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressBook {
+    aliases: BTreeMap<[u8; 32], String>,
+}
+
+impl AddressBook {
+    /// Adds the `alias` of an `address`, given either in the SS58 format of any chain or
+    /// as `0x` prefixed hex of the 32 bytes of the account id.
+    pub fn insert(&mut self, address: &str, alias: impl Into<String>) -> Result<()> {
+        let account = parse_account(address)
+            .ok_or_else(|| anyhow!("Invalid address `{address}`"))?;
+        self.aliases.insert(account, alias.into());
+        Ok(())
+    }
+
+    /// Returns `true` if no aliases are known.
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Returns the alias of an account id `value`, if it is a known address.
+    ///
+    /// Account ids are displayed as SS58 literals, or as hex with `--raw-values`.
+    /// Optional account ids and returned account ids, i.e. `Some(address)` and
+    /// `Ok(address)`, are resolved as well.
+    pub fn alias(&self, value: &Value) -> Option<&str> {
+        let account = match value {
+            Value::Literal(address) => parse_account(address)?,
+            Value::Hex(hex) => hex.bytes().try_into().ok()?,
+            Value::Tuple(tuple)
+                if matches!(tuple.ident().as_deref(), Some("Some" | "Ok")) =>
+            {
+                let mut values = tuple.values();
+                return match (values.next(), values.next()) {
+                    (Some(value), None) => self.alias(value),
+                    _ => None,
+                };
+            }
+            _ => return None,
+        };
+        self.aliases.get(&account).map(String::as_str)
+    }
+}
+
+/// Parses an address in the SS58 format of any chain, or as hex.
+fn parse_account(address: &str) -> Option<[u8; 32]> {
+    if let Some(hex) = address.strip_prefix("0x") {
+        return hex::decode(hex).ok()?.try_into().ok()
+    }
+    AccountId32::from_str(address)
+        .ok()
+        .map(|account| *account.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_transcode::{
+        Hex,
+        Tuple,
+    };
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_HEX: &str =
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn resolves_aliases_of_known_accounts() {
+        let mut book = AddressBook::default();
+        book.insert(ALICE, "alice-treasury").unwrap();
+
+        let literal = Value::Literal(ALICE.to_string());
+        assert_eq!(book.alias(&literal), Some("alice-treasury"));
+        let hex = Value::Hex(Hex::from_str(ALICE_HEX).unwrap());
+        assert_eq!(book.alias(&hex), Some("alice-treasury"));
+        let some = Value::Tuple(Tuple::new(Some("Some"), vec![literal]));
+        assert_eq!(book.alias(&some), Some("alice-treasury"));
+        let ok = Value::Tuple(Tuple::new(Some("Ok"), vec![some]));
+        assert_eq!(book.alias(&ok), Some("alice-treasury"));
+
+        let bob = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+        assert_eq!(book.alias(&Value::Literal(bob.to_string())), None);
+        assert_eq!(book.alias(&Value::UInt(1)), None);
+    }
+
+    #[test]
+    fn accepts_hex_addresses_and_rejects_invalid_ones() {
+        let mut book = AddressBook::default();
+        book.insert(ALICE_HEX, "alice").unwrap();
+        assert_eq!(
+            book.alias(&Value::Literal(ALICE.to_string())),
+            Some("alice")
+        );
+
+        assert!(book.insert("0x1234", "short").is_err());
+        assert!(book.insert("alice", "name").is_err());
+    }
+}
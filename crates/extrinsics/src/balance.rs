@@ -18,18 +18,23 @@ use std::{
     fmt::Display,
     result::Result::Ok,
     str::FromStr,
+    sync::Once,
 };
 
 use rust_decimal::{
     prelude::FromPrimitive,
     Decimal,
 };
-use serde_json::json;
+use serde::{
+    Serialize,
+    Serializer,
+};
+use serde_json::{
+    Map,
+    Value,
+};
 use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
-    },
+    backend::legacy::LegacyRpcMethods,
     Config,
 };
 
@@ -40,7 +45,10 @@ use anyhow::{
 };
 use url::Url;
 
-use crate::url_to_string;
+use crate::{
+    rpc_client,
+    url_to_string,
+};
 
 /// Represents different formats of a balance
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -51,12 +59,30 @@ pub enum BalanceVariant<Balance> {
     Denominated(DenominatedBalance),
 }
 
+/// A balance as shown in the output of commands: denominated, followed by the raw
+/// value, e.g. `1.2UNIT (1200000000000)`.
+///
+/// It is serialized as an object with the `raw` value as a string, so that consumers
+/// parsing JSON numbers as floats don't lose precision, and the `denominated` value if
+/// the balance can be denominated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DisplayBalance {
+    #[serde(serialize_with = "DisplayBalance::raw_as_string")]
+    raw: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    denominated: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenMetadata {
     /// Number of token_decimals used for denomination
     pub token_decimals: usize,
     /// Token symbol
     pub symbol: String,
+    /// Set if the chain does not define valid token decimals in its system properties,
+    /// in which case balances can only be given and are only displayed without
+    /// denomination
+    pub fallback: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -78,29 +104,103 @@ pub enum UnitPrefix {
 }
 
 impl TokenMetadata {
+    /// The token decimals assumed if the chain does not define them.
+    pub const FALLBACK_DECIMALS: usize = 0;
+    /// The token symbol assumed if the chain does not define it.
+    pub const FALLBACK_SYMBOL: &'static str = "UNIT";
+
     /// Query [TokenMetadata] through the node's RPC
     pub async fn query<C: Config>(url: &Url) -> Result<Self> {
-        let rpc_cli = RpcClient::from_url(url_to_string(url)).await?;
-        let rpc = LegacyRpcMethods::<C>::new(rpc_cli.clone());
+        let rpc_cli = rpc_client(&url_to_string(url)).await?;
+        Self::query_with(&LegacyRpcMethods::<C>::new(rpc_cli)).await
+    }
+
+    /// Query [TokenMetadata] through an existing connection to the node.
+    pub async fn query_with<C: Config>(rpc: &LegacyRpcMethods<C>) -> Result<Self> {
         let sys_props = rpc.system_properties().await?;
+        Ok(Self::from_system_properties(&sys_props))
+    }
 
-        let default_decimals = json!(12);
-        let default_units = json!("UNIT");
-        let token_decimals = sys_props
-            .get("tokenDecimals")
-            .unwrap_or(&default_decimals)
-            .as_u64()
-            .context("error converting decimal to u64")?
-            as usize;
-        let symbol = sys_props
-            .get("tokenSymbol")
-            .unwrap_or(&default_units)
-            .as_str()
-            .context("error converting symbol to string")?;
-        Ok(Self {
-            token_decimals,
-            symbol: symbol.to_string(),
+    /// Reads the token metadata from the system properties of a chain. Chains with
+    /// multiple tokens define arrays of decimals and symbols, of which the first is
+    /// the native token.
+    ///
+    /// Bare development runtimes may not define them at all, so missing or malformed
+    /// properties fall back to [`Self::FALLBACK_DECIMALS`] and
+    /// [`Self::FALLBACK_SYMBOL`] with a warning, shown once. Without the decimals the
+    /// metadata is marked as [`Self::fallback`], see [`BalanceVariant::from`] and
+    /// [`BalanceVariant::denominate_balance`].
+    pub fn from_system_properties(properties: &Map<String, Value>) -> Self {
+        let token_decimals = native_token_property(properties, "tokenDecimals")
+            .and_then(Value::as_u64)
+            .and_then(|decimals| usize::try_from(decimals).ok());
+        let symbol = native_token_property(properties, "tokenSymbol")
+            .and_then(Value::as_str)
+            .filter(|symbol| !symbol.is_empty());
+        if token_decimals.is_none() || symbol.is_none() {
+            static WARNING: Once = Once::new();
+            WARNING.call_once(|| {
+                if token_decimals.is_none() {
+                    tracing::warn!(
+                        "The chain does not define valid `tokenDecimals` in its system \
+                         properties, balances must be given without denomination"
+                    );
+                }
+                if symbol.is_none() {
+                    tracing::warn!(
+                        "The chain does not define a valid `tokenSymbol` in its system \
+                         properties, assuming `{}`",
+                        Self::FALLBACK_SYMBOL
+                    );
+                }
+            });
+        }
+        Self {
+            token_decimals: token_decimals.unwrap_or(Self::FALLBACK_DECIMALS),
+            symbol: symbol.unwrap_or(Self::FALLBACK_SYMBOL).to_string(),
+            fallback: token_decimals.is_none(),
+        }
+    }
+
+    /// Converts a denominated balance like `1.5UNIT` or `3mUNIT` into the raw balance.
+    ///
+    /// Unlike [`BalanceVariant::from_str`], the token symbol must match the symbol of
+    /// this chain exactly, so that a balance for a different chain is rejected.
+    pub fn denominate(&self, input: &str) -> Result<u128> {
+        let input = input.replace('_', "");
+        let suffix = input.trim_start_matches(|ch: char| ch.is_numeric() || ch == '.');
+        let number = &input[..input.len() - suffix.len()];
+        let unit = if suffix == self.symbol {
+            UnitPrefix::One
+        } else {
+            let mut chars = suffix.chars();
+            let unit = match chars.next() {
+                Some('G') => Some(UnitPrefix::Giga),
+                Some('M') => Some(UnitPrefix::Mega),
+                Some('k') => Some(UnitPrefix::Kilo),
+                Some('m') => Some(UnitPrefix::Milli),
+                Some('\u{3bc}') => Some(UnitPrefix::Micro),
+                Some('n') => Some(UnitPrefix::Nano),
+                _ => None,
+            };
+            unit.filter(|_| chars.as_str() == self.symbol)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Unknown token symbol `{}`, expected a denomination of `{}`",
+                        suffix,
+                        self.symbol
+                    )
+                })?
+        };
+        let value = Decimal::from_str_exact(number)
+            .context("Error while parsing the value of the balance")?
+            .normalize();
+        BalanceVariant::<u128>::Denominated(DenominatedBalance {
+            value,
+            unit,
+            symbol: self.symbol.clone(),
         })
+        .denominate_balance(self)
     }
 }
 
@@ -168,7 +268,7 @@ impl FromStr for DenominatedBalance {
 
 impl<Balance> BalanceVariant<Balance>
 where
-    Balance: From<u128> + Clone,
+    Balance: TryFrom<u128> + Clone,
 {
     /// Converts BalanceVariant into Balance.
     ///
@@ -185,6 +285,7 @@ where
     /// let tm = TokenMetadata {
     ///     token_decimals: decimals,
     ///     symbol: String::from("DOT"),
+    ///     fallback: false,
     /// };
     /// let sample_den_balance: BalanceVariant<u128> = "0.4\u{3bc}DOT".parse().unwrap();
     /// let result = sample_den_balance.denominate_balance(&tm);
@@ -201,6 +302,7 @@ where
     /// let tm = TokenMetadata {
     ///     token_decimals: decimals,
     ///     symbol: String::from("DOT"),
+    ///     fallback: false,
     /// };
     /// let sample_den_balance: BalanceVariant<u128> = "4123\u{3bc}DOT".parse().unwrap();
     /// let balance = 4123;
@@ -210,6 +312,13 @@ where
     pub fn denominate_balance(&self, token_metadata: &TokenMetadata) -> Result<Balance> {
         match self {
             BalanceVariant::Default(balance) => Ok(balance.clone()),
+            BalanceVariant::Denominated(den_balance) if token_metadata.fallback => {
+                Err(anyhow!(
+                    "Cannot interpret the denominated balance `{den_balance}`, the chain \
+                     does not define its token decimals. Give the balance without \
+                     denomination instead"
+                ))
+            }
             BalanceVariant::Denominated(den_balance) => {
                 let zeros: usize = (token_metadata.token_decimals as isize
                     + match den_balance.unit {
@@ -236,7 +345,9 @@ where
                     .checked_mul(multiple)
                     .context("error while converting balance to raw format. Overflow during multiplication!")?
                     .try_into()?;
-                Ok(balance.into())
+                Balance::try_from(balance).map_err(|_| {
+                    anyhow!("Balance {balance} is out of range for the balance type")
+                })
             }
         }
     }
@@ -251,7 +362,7 @@ where
     /// If the number is divisible by 10^(`token_decimals` + `unit_zeros`),
     /// It sets the `UnitPrefix` and divides the `value` into `Decimal`
     ///
-    /// If no [TokenMetadata] was present, than that means
+    /// If no [TokenMetadata] was present, or only its fallback, than that means
     /// that [Balance] is to be displayed in *normal* format
     /// and `BalanceVariant::Default` is returned
     ///
@@ -265,6 +376,7 @@ where
     /// let tm = TokenMetadata {
     ///     token_decimals: decimals,
     ///     symbol: String::from("DOT"),
+    ///     fallback: false,
     /// };
     /// let sample_den_balance: BalanceVariant<u128> = "500.5MDOT".parse().unwrap();
     /// let balance: u128 = 5_005_000_000_000_000_000;
@@ -277,7 +389,7 @@ where
     ) -> Result<Self> {
         let n: u128 = value.into();
 
-        if let Some(token_metadata) = token_metadata {
+        if let Some(token_metadata) = token_metadata.filter(|tm| !tm.fallback) {
             if n == 0 {
                 return Ok(BalanceVariant::Denominated(DenominatedBalance {
                     value: Decimal::ZERO,
@@ -356,11 +468,71 @@ where
 
             Ok(BalanceVariant::Denominated(den_balance))
         } else {
-            Ok(BalanceVariant::Default(n.into()))
+            let balance = Balance::try_from(n).map_err(|_| {
+                anyhow!("Balance {n} is out of range for the balance type")
+            })?;
+            Ok(BalanceVariant::Default(balance))
+        }
+    }
+}
+
+impl DisplayBalance {
+    /// Denominates the `value` with the `token_metadata`, if given.
+    ///
+    /// The balance is shown only raw without token metadata, if the chain does not
+    /// define its token decimals, see [`TokenMetadata::fallback`], or if it is too
+    /// large to be denominated.
+    pub fn new<T: Into<u128>>(value: T, token_metadata: Option<&TokenMetadata>) -> Self {
+        let raw = value.into();
+        let denominated = match BalanceVariant::<u128>::from(raw, token_metadata) {
+            Ok(BalanceVariant::Denominated(mut balance)) => {
+                balance.value = balance.value.normalize();
+                Some(balance.to_string())
+            }
+            _ => None,
+        };
+        Self { raw, denominated }
+    }
+
+    /// The raw value of the balance.
+    pub fn raw(&self) -> u128 {
+        self.raw
+    }
+
+    /// The denominated value of the balance, if it can be denominated.
+    pub fn denominated(&self) -> Option<&str> {
+        self.denominated.as_deref()
+    }
+
+    fn raw_as_string<S: Serializer>(
+        raw: &u128,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(raw)
+    }
+}
+
+impl Display for DisplayBalance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.denominated {
+            Some(denominated) => write!(f, "{} ({})", denominated, self.raw),
+            None => write!(f, "{}", self.raw),
         }
     }
 }
 
+/// Returns the system property `key` of the native token of the chain, the first
+/// element if it is an array.
+fn native_token_property<'a>(
+    properties: &'a Map<String, Value>,
+    key: &str,
+) -> Option<&'a Value> {
+    match properties.get(key)? {
+        Value::Array(values) => values.first(),
+        value => Some(value),
+    }
+}
+
 impl<Balance> Display for BalanceVariant<Balance>
 where
     Balance: Display + Clone,
@@ -429,6 +601,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn denominate_checks_token_symbol() {
+        let tm = TokenMetadata {
+            token_decimals: 12,
+            symbol: String::from("UNIT"),
+            fallback: false,
+        };
+        assert_eq!(tm.denominate("1.5UNIT").unwrap(), 1_500_000_000_000);
+        assert_eq!(tm.denominate("3mUNIT").unwrap(), 3_000_000_000);
+        assert_eq!(
+            tm.denominate("1_000kUNIT").unwrap(),
+            1_000_000_000_000_000_000
+        );
+
+        let err = tm.denominate("3DOT").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown token symbol `DOT`, expected a denomination of `UNIT`"
+        );
+        assert!(tm.denominate("3MDOT").is_err());
+        assert!(tm.denominate("0.0000000000001UNIT").is_err());
+    }
+
+    fn from_properties(properties: Value) -> TokenMetadata {
+        TokenMetadata::from_system_properties(properties.as_object().unwrap())
+    }
+
+    #[test]
+    fn token_metadata_from_system_properties() {
+        let tm = from_properties(serde_json::json!({
+            "ss58Format": 0,
+            "tokenDecimals": 10,
+            "tokenSymbol": "DOT",
+        }));
+        assert_eq!((tm.token_decimals, tm.symbol.as_str()), (10, "DOT"));
+        assert!(!tm.fallback);
+
+        // chains with multiple tokens list the native token first
+        let tm = from_properties(serde_json::json!({
+            "tokenDecimals": [12, 12],
+            "tokenSymbol": ["ACA", "AUSD"],
+        }));
+        assert_eq!((tm.token_decimals, tm.symbol.as_str()), (12, "ACA"));
+        assert!(!tm.fallback);
+    }
+
+    #[test]
+    fn token_metadata_falls_back_if_properties_are_missing() {
+        let tm = from_properties(serde_json::json!({}));
+        assert_eq!(tm.token_decimals, TokenMetadata::FALLBACK_DECIMALS);
+        assert_eq!(tm.symbol, TokenMetadata::FALLBACK_SYMBOL);
+        assert!(tm.fallback);
+
+        // plain balances are accepted and displayed without denomination
+        let balance: BalanceVariant<u128> = "1000".parse().unwrap();
+        assert_eq!(balance.denominate_balance(&tm).unwrap(), 1000);
+        let balance = BalanceVariant::<u128>::from(1_500_000u128, Some(&tm)).unwrap();
+        assert_eq!(balance.to_string(), "1500000");
+
+        // denominated balances can't be interpreted without the decimals
+        let balance: BalanceVariant<u128> = "1.5UNIT".parse().unwrap();
+        let err = balance.denominate_balance(&tm).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not define its token decimals"));
+        assert!(tm.denominate("1.5UNIT").is_err());
+    }
+
+    #[test]
+    fn token_metadata_with_partial_properties() {
+        let tm = from_properties(serde_json::json!({ "tokenDecimals": 18 }));
+        assert_eq!((tm.token_decimals, tm.symbol.as_str()), (18, "UNIT"));
+        assert!(!tm.fallback);
+        assert_eq!(tm.denominate("2UNIT").unwrap(), 2_000_000_000_000_000_000);
+
+        let tm = from_properties(serde_json::json!({ "tokenSymbol": "DEV" }));
+        assert_eq!((tm.token_decimals, tm.symbol.as_str()), (0, "DEV"));
+        assert!(tm.fallback);
+    }
+
+    #[test]
+    fn token_metadata_with_malformed_properties() {
+        let tm = from_properties(serde_json::json!({
+            "tokenDecimals": "12",
+            "tokenSymbol": 42,
+        }));
+        assert_eq!(tm.symbol, TokenMetadata::FALLBACK_SYMBOL);
+        assert!(tm.fallback);
+
+        let tm = from_properties(serde_json::json!({
+            "tokenDecimals": -1,
+            "tokenSymbol": [],
+        }));
+        assert_eq!(tm.symbol, TokenMetadata::FALLBACK_SYMBOL);
+        assert!(tm.fallback);
+
+        let tm = from_properties(serde_json::json!({
+            "tokenDecimals": [null],
+            "tokenSymbol": "",
+        }));
+        assert_eq!(tm.symbol, TokenMetadata::FALLBACK_SYMBOL);
+        assert!(tm.fallback);
+    }
+
+    #[test]
+    fn display_balance_shows_denominated_and_raw_values() {
+        let tm = TokenMetadata {
+            token_decimals: 12,
+            symbol: String::from("UNIT"),
+            fallback: false,
+        };
+        let balance = DisplayBalance::new(0u128, Some(&tm));
+        assert_eq!(balance.to_string(), "0UNIT (0)");
+        assert_eq!(
+            serde_json::to_value(&balance).unwrap(),
+            serde_json::json!({ "raw": "0", "denominated": "0UNIT" })
+        );
+
+        let balance = DisplayBalance::new(1_500_000_000u128, Some(&tm));
+        assert_eq!(balance.to_string(), "1.5mUNIT (1500000000)");
+        assert_eq!(
+            DisplayBalance::new(1_200_000_000_000u128, Some(&tm)).to_string(),
+            "1.2UNIT (1200000000000)"
+        );
+
+        // larger than `u64::MAX`, which JSON consumers can't represent as a number
+        let balance = DisplayBalance::new(20_000_000_000_000_000_000_000u128, Some(&tm));
+        assert_eq!(balance.to_string(), "20GUNIT (20000000000000000000000)");
+        assert_eq!(
+            serde_json::to_value(&balance).unwrap(),
+            serde_json::json!({
+                "raw": "20000000000000000000000",
+                "denominated": "20GUNIT",
+            })
+        );
+
+        // too large to be denominated
+        let balance = DisplayBalance::new(u128::MAX, Some(&tm));
+        assert_eq!(balance.to_string(), u128::MAX.to_string());
+        assert_eq!(balance.raw(), u128::MAX);
+    }
+
+    #[test]
+    fn display_balance_without_token_decimals() {
+        let tm = from_properties(serde_json::json!({}));
+        let balance = DisplayBalance::new(1_500_000u128, Some(&tm));
+        assert_eq!(balance.to_string(), "1500000");
+        assert_eq!(
+            serde_json::to_value(&balance).unwrap(),
+            serde_json::json!({ "raw": "1500000" })
+        );
+        assert_eq!(DisplayBalance::new(1_500_000u128, None), balance);
+    }
+
     #[test]
     fn incorrect_balances() {
         assert!(
@@ -445,6 +771,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: 10,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let bv =
             BalanceVariant::<<DefaultEnvironment as Environment>::Balance>::from_str(
@@ -463,6 +790,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance =
             500 * 1_000_000 * 10_000_000_000;
@@ -481,6 +809,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance =
             5_005_000_000_000_000_000;
@@ -495,6 +824,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005_000;
         let bv = BalanceVariant::from_str("500.5μDOT").expect("successful parsing. qed");
@@ -507,6 +837,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 1;
         let bv = BalanceVariant::from_str("0.1nDOT").expect("successful parsing. qed");
@@ -522,6 +853,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let bv =
             BalanceVariant::<<DefaultEnvironment as Environment>::Balance>::from_str(
@@ -538,6 +870,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance =
             5_005_000_000_000_000_000_000;
@@ -552,6 +885,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005_000_000_000_000;
         let bv = BalanceVariant::from_str("500.5kDOT").expect("successful parsing. qed");
@@ -565,6 +899,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005_000_000_000;
         let bv = BalanceVariant::from_str("500.5DOT").expect("successful parsing. qed");
@@ -578,6 +913,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005_000_000;
         let bv = BalanceVariant::from_str("500.5mDOT").expect("successful parsing. qed");
@@ -590,6 +926,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005_000;
         let bv = BalanceVariant::from_str("500.5μDOT").expect("successful parsing. qed");
@@ -602,6 +939,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_005;
         let bv = BalanceVariant::from_str("500.5nDOT").expect("successful parsing. qed");
@@ -615,6 +953,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 5_235_456_210_000_000;
         let bv =
@@ -629,6 +968,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance: <DefaultEnvironment as Environment>::Balance = 50_015_000_000_000;
         let bv = BalanceVariant::from_str("5001.5DOT").expect("successful parsing. qed");
@@ -642,6 +982,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let bv =
             BalanceVariant::<<DefaultEnvironment as Environment>::Balance>::from_str(
@@ -670,12 +1011,27 @@ mod tests {
         assert!(bv.is_ok())
     }
 
+    #[test]
+    fn denominate_u64_balance_out_of_range() {
+        let tm = TokenMetadata {
+            token_decimals: 12,
+            symbol: String::from("DOT"),
+            fallback: false,
+        };
+        let bv = BalanceVariant::<u64>::from_str("19MDOT").expect("successful parsing");
+        assert!(bv.denominate_balance(&tm).is_err());
+
+        let bv = BalanceVariant::<u64>::from_str("18kDOT").expect("successful parsing");
+        assert_eq!(bv.denominate_balance(&tm).unwrap(), 18_000_000_000_000_000);
+    }
+
     #[test]
     fn convert_from_u128() {
         let decimals = 6;
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance = 532_500_000_000_u128;
         let denominated_balance = BalanceVariant::<
@@ -696,6 +1052,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         let balance = 532_500_000_000_u128;
         let denominated_balance = BalanceVariant::<
@@ -716,6 +1073,7 @@ mod tests {
         let tm = TokenMetadata {
             token_decimals: decimals,
             symbol: String::from("DOT"),
+            fallback: false,
         };
         // 10_000_000_000 - One
         // 10_000_000 - Milli
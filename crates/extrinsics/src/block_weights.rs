@@ -0,0 +1,192 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use serde::Serialize;
+use sp_weights::Weight;
+use subxt::{
+    constants::Address,
+    ext::scale_decode,
+    Config,
+    OnlineClient,
+};
+
+/// The weight limits of a block and of a single extrinsic in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockWeights {
+    /// The maximum weight of a block.
+    pub max_block: Weight,
+    /// The maximum weight of a single normal extrinsic.
+    pub max_extrinsic: Weight,
+}
+
+impl BlockWeights {
+    /// Read the limits from the `System::BlockWeights` constant.
+    ///
+    /// Constants are part of the metadata cached by the client, so this does not
+    /// require a request to the node.
+    pub fn from_client<C: Config>(client: &OnlineClient<C>) -> Result<Self> {
+        let block_weights = client
+            .constants()
+            .at(&Address::<ChainBlockWeights>::new("System", "BlockWeights"))?;
+        let normal = block_weights.per_class.normal;
+        let max_extrinsic = normal
+            .max_extrinsic
+            .or(normal.max_total)
+            .unwrap_or(block_weights.max_block);
+        Ok(Self {
+            max_block: block_weights.max_block.into(),
+            max_extrinsic: max_extrinsic.into(),
+        })
+    }
+
+    /// Returns the share of the limits taken up by the given weight.
+    pub fn usage(&self, weight: Weight) -> BlockUsage {
+        BlockUsage {
+            ref_time_block_percent: percent(weight.ref_time(), self.max_block.ref_time()),
+            proof_size_block_percent: percent(
+                weight.proof_size(),
+                self.max_block.proof_size(),
+            ),
+            ref_time_extrinsic_percent: percent(
+                weight.ref_time(),
+                self.max_extrinsic.ref_time(),
+            ),
+            proof_size_extrinsic_percent: percent(
+                weight.proof_size(),
+                self.max_extrinsic.proof_size(),
+            ),
+            exceeds_extrinsic_limit: weight.any_gt(self.max_extrinsic),
+        }
+    }
+}
+
+/// Returns the share of the weight limits of the chain taken up by `weight`, or `None`
+/// if the chain does not expose its limits.
+pub fn block_usage<C: Config>(
+    client: &OnlineClient<C>,
+    weight: Weight,
+) -> Option<BlockUsage> {
+    match BlockWeights::from_client(client) {
+        Ok(block_weights) => Some(block_weights.usage(weight)),
+        Err(err) => {
+            tracing::debug!("Failed to read the block weight limits: {err}");
+            None
+        }
+    }
+}
+
+/// The share of the block and extrinsic weight limits taken up by a weight, in percent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BlockUsage {
+    pub ref_time_block_percent: f64,
+    pub proof_size_block_percent: f64,
+    pub ref_time_extrinsic_percent: f64,
+    pub proof_size_extrinsic_percent: f64,
+    /// The weight exceeds the limit of a single extrinsic, so submitting an extrinsic
+    /// with this weight is guaranteed to fail.
+    pub exceeds_extrinsic_limit: bool,
+}
+
+/// Returns `value` as a percentage of `max`, rounded to two decimal places.
+fn percent(value: u64, max: u64) -> f64 {
+    if max == 0 {
+        return 0.0
+    }
+    (value as f64 * 10_000.0 / max as f64).round() / 100.0
+}
+
+/// The `frame_system::limits::BlockWeights` type.
+#[derive(scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[allow(dead_code)]
+struct ChainBlockWeights {
+    base_block: ChainWeight,
+    max_block: ChainWeight,
+    per_class: PerDispatchClass,
+}
+
+#[derive(scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[allow(dead_code)]
+struct PerDispatchClass {
+    normal: WeightsPerClass,
+    operational: WeightsPerClass,
+    mandatory: WeightsPerClass,
+}
+
+#[derive(scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[allow(dead_code)]
+struct WeightsPerClass {
+    base_extrinsic: ChainWeight,
+    max_extrinsic: Option<ChainWeight>,
+    max_total: Option<ChainWeight>,
+    reserved: Option<ChainWeight>,
+}
+
+#[derive(Clone, Copy, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct ChainWeight {
+    ref_time: u64,
+    proof_size: u64,
+}
+
+impl From<ChainWeight> for Weight {
+    fn from(weight: ChainWeight) -> Self {
+        Weight::from_parts(weight.ref_time, weight.proof_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_weights() -> BlockWeights {
+        BlockWeights {
+            max_block: Weight::from_parts(2_000_000_000_000, 5 * 1024 * 1024),
+            max_extrinsic: Weight::from_parts(1_500_000_000_000, 4 * 1024 * 1024),
+        }
+    }
+
+    #[test]
+    fn usage_in_percent() {
+        let usage = block_weights().usage(Weight::from_parts(3_951_114_240, 629_760));
+        assert_eq!(
+            usage,
+            BlockUsage {
+                ref_time_block_percent: 0.2,
+                proof_size_block_percent: 12.01,
+                ref_time_extrinsic_percent: 0.26,
+                proof_size_extrinsic_percent: 15.01,
+                exceeds_extrinsic_limit: false,
+            }
+        );
+    }
+
+    #[test]
+    fn usage_exceeding_extrinsic_limit() {
+        let usage =
+            block_weights().usage(Weight::from_parts(1_000_000, 4 * 1024 * 1024 + 1));
+        assert!(usage.exceeds_extrinsic_limit);
+        assert_eq!(usage.proof_size_extrinsic_percent, 100.0);
+    }
+
+    #[test]
+    fn zero_limits() {
+        assert_eq!(percent(1, 0), 0.0);
+    }
+}
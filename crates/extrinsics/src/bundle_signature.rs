@@ -0,0 +1,229 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use contract_metadata::ContractMetadata;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use sp_core::{
+    ed25519,
+    sr25519,
+    Pair,
+};
+
+/// The key of the signature in the JSON of a `.contract` bundle.
+const SIGNATURE_KEY: &str = "signature";
+
+/// The signature scheme of a bundle signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    Sr25519,
+    Ed25519,
+}
+
+/// The signature of a `.contract` bundle, embedded in the bundle as `signature`.
+///
+/// The signature is made over the blake2-256 hash of the canonical bundle: the bundle
+/// without its signature, serialized as compact JSON with the keys of all objects sorted.
+/// The code and the code hash of the bundle are left untouched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleSignature {
+    /// The scheme of the signature.
+    pub scheme: SignatureScheme,
+    /// The `0x` hex encoded public key of the signer.
+    pub public_key: String,
+    /// The `0x` hex encoded signature.
+    pub signature: String,
+}
+
+impl BundleSignature {
+    /// Reads the signature embedded in the `bundle`, `None` if it is unsigned.
+    pub fn of(bundle: &ContractMetadata) -> Result<Option<Self>> {
+        bundle
+            .abi
+            .get(SIGNATURE_KEY)
+            .map(|signature| {
+                serde_json::from_value(signature.clone())
+                    .context("Failed to deserialize the signature of the contract bundle")
+            })
+            .transpose()
+    }
+
+    /// Returns the public key of the signer, which is also its account id.
+    pub fn signer(&self) -> Result<[u8; 32]> {
+        decode_hex(&self.public_key, "public key")
+    }
+
+    /// Checks that this is a valid signature of the `bundle` by its signer.
+    pub fn verify(&self, bundle: &ContractMetadata) -> Result<()> {
+        let message = canonical_bundle_hash(bundle)?;
+        let public_key = self.signer()?;
+        let signature: [u8; 64] = decode_hex(&self.signature, "signature")?;
+        let is_valid = match self.scheme {
+            SignatureScheme::Sr25519 => {
+                sr25519::Pair::verify(
+                    &sr25519::Signature::from_raw(signature),
+                    message,
+                    &sr25519::Public::from_raw(public_key),
+                )
+            }
+            SignatureScheme::Ed25519 => {
+                ed25519::Pair::verify(
+                    &ed25519::Signature::from_raw(signature),
+                    message,
+                    &ed25519::Public::from_raw(public_key),
+                )
+            }
+        };
+        anyhow::ensure!(
+            is_valid,
+            "The signature of the contract bundle by {} is invalid, the bundle was \
+             modified after signing",
+            self.public_key
+        );
+        Ok(())
+    }
+}
+
+/// Signs the `bundle` with the key of the secret URI `suri`, replacing any previous
+/// signature.
+pub fn sign_bundle(
+    bundle: &mut ContractMetadata,
+    suri: &str,
+    scheme: SignatureScheme,
+) -> Result<BundleSignature> {
+    let message = canonical_bundle_hash(bundle)?;
+    let (public_key, signature) = match scheme {
+        SignatureScheme::Sr25519 => {
+            let pair = sr25519::Pair::from_string(suri, None)
+                .map_err(|err| anyhow!("Invalid secret URI: {err:?}"))?;
+            (pair.public().0, pair.sign(&message).0)
+        }
+        SignatureScheme::Ed25519 => {
+            let pair = ed25519::Pair::from_string(suri, None)
+                .map_err(|err| anyhow!("Invalid secret URI: {err:?}"))?;
+            (pair.public().0, pair.sign(&message).0)
+        }
+    };
+    let signature = BundleSignature {
+        scheme,
+        public_key: format!("0x{}", hex::encode(public_key)),
+        signature: format!("0x{}", hex::encode(signature)),
+    };
+    bundle
+        .abi
+        .insert(SIGNATURE_KEY.to_string(), serde_json::to_value(&signature)?);
+    Ok(signature)
+}
+
+/// Returns the blake2-256 hash of the canonical `bundle`, which is signed.
+fn canonical_bundle_hash(bundle: &ContractMetadata) -> Result<[u8; 32]> {
+    let mut value = serde_json::to_value(bundle)?;
+    if let Some(bundle) = value.as_object_mut() {
+        bundle.remove(SIGNATURE_KEY);
+    }
+    let canonical = serde_json::to_vec(&sort_keys(value))?;
+    Ok(sp_core::blake2_256(&canonical))
+}
+
+/// Sorts the keys of all objects in `value`, which keep their insertion order if
+/// `serde_json` preserves the order.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(array) => Value::Array(array.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+fn decode_hex<const N: usize>(input: &str, name: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(input.trim_start_matches("0x"))
+        .with_context(|| format!("The {name} of the bundle signature is not hex"))?;
+    bytes.try_into().map_err(|_| {
+        anyhow!("The {name} of the bundle signature should be {N} bytes in length")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> ContractMetadata {
+        let code = b"\0asm\x01\0\0\0";
+        serde_json::from_value(serde_json::json!({
+            "source": {
+                "hash": format!("0x{}", hex::encode(contract_build::code_hash(code))),
+                "language": "ink! 5.0.0",
+                "compiler": "rustc 1.76.0",
+                "wasm": format!("0x{}", hex::encode(code)),
+            },
+            "contract": {
+                "name": "flipper",
+                "version": "0.1.0",
+                "authors": ["Parity Technologies <admin@parity.io>"],
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn signed_bundle_verifies_after_round_trip() {
+        for scheme in [SignatureScheme::Sr25519, SignatureScheme::Ed25519] {
+            let mut bundle = bundle();
+            let code_hash = bundle.source.hash;
+            let signature = sign_bundle(&mut bundle, "//Alice", scheme).unwrap();
+            assert_eq!(bundle.source.hash, code_hash);
+
+            let json = serde_json::to_string_pretty(&bundle).unwrap();
+            let bundle: ContractMetadata = serde_json::from_str(&json).unwrap();
+            let embedded = BundleSignature::of(&bundle).unwrap().unwrap();
+            assert_eq!(embedded, signature);
+            assert!(embedded.verify(&bundle).is_ok());
+        }
+    }
+
+    #[test]
+    fn modified_bundle_fails_verification() {
+        let mut bundle = bundle();
+        assert_eq!(BundleSignature::of(&bundle).unwrap(), None);
+        let signature =
+            sign_bundle(&mut bundle, "//Alice", SignatureScheme::Sr25519).unwrap();
+
+        bundle.contract.name = "not-flipper".to_string();
+        let err = signature.verify(&bundle).unwrap_err();
+        assert!(err.to_string().contains("is invalid"), "{err}");
+    }
+}
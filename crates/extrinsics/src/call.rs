@@ -15,16 +15,44 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    contract_info::get_account_balance,
+    dry_run_state_call,
+    estimate_fee,
     pallet_contracts_primitives::ContractExecResult,
-    state_call,
+    resolve_label,
     submit_extrinsic,
+    unsigned::{
+        create_unsigned,
+        submit_signed,
+        UnsignedExtrinsic,
+    },
+    xcm::{
+        query_call_weight,
+        send_xcm_transact,
+        XcmFee,
+        XcmSendResult,
+        XcmTransact,
+    },
+    Connection,
     ContractMessageTranscoder,
     ErrorVariant,
 };
 use crate::{
     check_env_types,
-    extrinsic_calls::Call,
-    extrinsic_opts::ExtrinsicOpts,
+    extrinsic_calls::{
+        Call,
+        EncodedCall,
+        ReviveCall,
+    },
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
+    },
+    ContractAddress,
+    ContractsPallet,
+    EntryPoint,
+    EventRecord,
+    Phase,
 };
 
 use anyhow::{
@@ -36,29 +64,36 @@ use scale::Encode;
 use sp_weights::Weight;
 
 use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
-    },
+    backend::legacy::LegacyRpcMethods,
     blocks::ExtrinsicEvents,
     ext::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
+    tx::{
+        self,
+        TxPayload,
+    },
     Config,
     OnlineClient,
 };
 
+/// How often the gas is estimated for a larger value by [`CallExec::set_max_value`].
+const MAX_VALUE_ESTIMATES: usize = 3;
+
 /// A builder for the call command.
 pub struct CallCommandBuilder<C: Config, E: Environment, Signer: Clone> {
-    contract: C::AccountId,
+    contract: ContractAddress<C::AccountId>,
     message: String,
+    selector: Option<[u8; 4]>,
     args: Vec<String>,
+    data: Option<Vec<u8>>,
     extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
     gas_limit: Option<u64>,
     proof_size: Option<u64>,
     value: E::Balance,
+    fuzzy_match: bool,
+    connection: Option<Connection<C>>,
 }
 
 impl<C: Config, E: Environment, Signer> CallCommandBuilder<C, E, Signer>
@@ -68,21 +103,35 @@ where
 {
     /// Returns a clean builder for [`CallExec`].
     pub fn new(
-        contract: C::AccountId,
+        contract: ContractAddress<C::AccountId>,
         message: &str,
         extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
     ) -> CallCommandBuilder<C, E, Signer> {
         CallCommandBuilder {
             contract,
             message: message.to_string(),
+            selector: None,
             args: Vec::new(),
+            data: None,
             extrinsic_opts,
             gas_limit: None,
             proof_size: None,
             value: Default::default(),
+            fuzzy_match: false,
+            connection: None,
         }
     }
 
+    /// Sets the selector of the contract message to call.
+    ///
+    /// The message is looked up by its selector in the metadata. If a message name is
+    /// given as well, it must be the name of that message.
+    pub fn selector(self, selector: Option<[u8; 4]>) -> Self {
+        let mut this = self;
+        this.selector = selector;
+        this
+    }
+
     /// Sets the arguments of the contract message to call.
     pub fn args<T: ToString>(self, args: Vec<T>) -> Self {
         let mut this = self;
@@ -90,6 +139,18 @@ where
         this
     }
 
+    /// Sets the SCALE encoded arguments of the message with the selector, which are
+    /// passed to the contract as they are instead of encoding the arguments with the
+    /// metadata of the contract.
+    ///
+    /// No contract artifacts are required to call a contract with raw data, hence the
+    /// data returned by the call can't be decoded.
+    pub fn data(self, data: Option<Vec<u8>>) -> Self {
+        let mut this = self;
+        this.data = data;
+        this
+    }
+
     /// Sets the maximum amount of gas to be used for this command.
     pub fn gas_limit(self, gas_limit: Option<u64>) -> Self {
         let mut this = self;
@@ -111,6 +172,22 @@ where
         this
     }
 
+    /// Accept a message whose name matches the given name case-insensitively, or starts
+    /// with it, if the match is unique.
+    pub fn fuzzy_match(self, fuzzy_match: bool) -> Self {
+        let mut this = self;
+        this.fuzzy_match = fuzzy_match;
+        this
+    }
+
+    /// Sets the connection to the node to reuse, otherwise a new connection is
+    /// established to the url of the extrinsic options.
+    pub fn connection(self, connection: Option<Connection<C>>) -> Self {
+        let mut this = self;
+        this.connection = connection;
+        this
+    }
+
     /// Preprocesses contract artifacts and options for subsequent contract calls.
     ///
     /// This function prepares the necessary data for making a contract call based on the
@@ -121,21 +198,71 @@ where
     /// Returns the `CallExec` containing the preprocessed data for the contract call,
     /// or an error in case of failure.
     pub async fn done(self) -> Result<CallExec<C, E, Signer>> {
-        let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
-
-        let call_data = transcoder.encode(&self.message, &self.args)?;
+        let (message, transcoder, call_data) = match self.data {
+            Some(data) => {
+                let selector = self.selector.ok_or_else(|| {
+                    anyhow!("A selector is required to call a message with raw data")
+                })?;
+                let call_data = selector.iter().chain(&data).copied().collect();
+                (format!("0x{}", hex::encode(selector)), None, call_data)
+            }
+            None => {
+                let artifacts = self.extrinsic_opts.contract_artifacts()?;
+                let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
+                let message = match self.selector {
+                    Some(selector) => {
+                        let label = transcoder.message_label(&selector)?;
+                        if !self.message.is_empty() && self.message != label {
+                            return Err(anyhow!(
+                                "The selector 0x{} is the selector of the message \
+                                 `{label}`, not of `{}`",
+                                hex::encode(selector),
+                                self.message
+                            ))
+                        }
+                        label.to_string()
+                    }
+                    None => {
+                        resolve_label(
+                            &transcoder,
+                            EntryPoint::Message,
+                            &self.message,
+                            self.fuzzy_match,
+                            self.extrinsic_opts.verbosity(),
+                        )?
+                    }
+                };
+                let call_data = self
+                    .extrinsic_opts
+                    .timings()
+                    .measure_sync(Phase::Transcode, || {
+                        transcoder.encode(&message, &self.args)
+                    })?;
+                (message, Some(transcoder), call_data)
+            }
+        };
         tracing::debug!("Message data: {:?}", hex::encode(&call_data));
 
-        let url = self.extrinsic_opts.url();
-        let rpc = RpcClient::from_url(&url).await?;
-        let client = OnlineClient::from_rpc_client(rpc.clone()).await?;
-        let rpc = LegacyRpcMethods::new(rpc);
-        check_env_types(&client, &transcoder, self.extrinsic_opts.verbosity())?;
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => Connection::connect(&self.extrinsic_opts.url()).await?,
+        };
+        let client = connection.client().clone();
+        let rpc = connection.rpc().clone();
+        if let Some(transcoder) = &transcoder {
+            check_env_types::<C, E>(
+                &client,
+                transcoder,
+                self.extrinsic_opts.verbosity(),
+            )?;
+        }
+        let pallet = ContractsPallet::detect(&client.metadata())?;
+        self.contract.check_pallet(pallet)?;
 
         Ok(CallExec {
             contract: self.contract,
-            message: self.message.clone(),
+            pallet,
+            message,
             args: self.args.clone(),
             opts: self.extrinsic_opts,
             gas_limit: self.gas_limit,
@@ -150,7 +277,8 @@ where
 }
 
 pub struct CallExec<C: Config, E: Environment, Signer: Clone> {
-    contract: C::AccountId,
+    contract: ContractAddress<C::AccountId>,
+    pallet: ContractsPallet,
     message: String,
     args: Vec<String>,
     opts: ExtrinsicOpts<C, E, Signer>,
@@ -159,13 +287,48 @@ pub struct CallExec<C: Config, E: Environment, Signer: Clone> {
     value: E::Balance,
     rpc: LegacyRpcMethods<C>,
     client: OnlineClient<C>,
-    transcoder: ContractMessageTranscoder,
+    transcoder: Option<ContractMessageTranscoder>,
     call_data: Vec<u8>,
 }
 
+/// The values which override those a [`CallExec`] was built with for a single call,
+/// see [`CallExec::call_with`].
+#[derive(Debug, Clone)]
+pub struct CallOverrides<Balance> {
+    value: Option<Balance>,
+    args: Option<Vec<String>>,
+}
+
+impl<Balance> Default for CallOverrides<Balance> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            args: None,
+        }
+    }
+}
+
+impl<Balance> CallOverrides<Balance> {
+    /// Transfer `value` with the call.
+    pub fn value(self, value: Balance) -> Self {
+        let mut this = self;
+        this.value = Some(value);
+        this
+    }
+
+    /// Call the message with `args`, encoded with the metadata of the contract.
+    pub fn args<T: ToString>(self, args: Vec<T>) -> Self {
+        let mut this = self;
+        this.args = Some(args.into_iter().map(|arg| arg.to_string()).collect());
+        this
+    }
+}
+
 impl<C: Config, E: Environment, Signer> CallExec<C, E, Signer>
 where
-    <C::ExtrinsicParams as subxt::config::ExtrinsicParams<C>>::OtherParams: Default,
+    <C::ExtrinsicParams as subxt::config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+    C::AccountId: serde::Serialize,
     C::AccountId: EncodeAsType + IntoVisitor,
     Signer: tx::Signer<C> + Clone,
 {
@@ -178,17 +341,31 @@ where
     ///
     /// Returns the dry run simulation result of type [`ContractExecResult`], which
     /// includes information about the simulated call, or an error in case of failure.
-    pub async fn call_dry_run(&self) -> Result<ContractExecResult<E::Balance, ()>> {
+    pub async fn call_dry_run(
+        &self,
+    ) -> Result<ContractExecResult<E::Balance, EventRecord<C::Hash>>> {
+        self.dry_run(self.value, self.call_data.clone()).await
+    }
+
+    /// Simulates the call with the given `value` and `call_data`.
+    async fn dry_run(
+        &self,
+        value: E::Balance,
+        call_data: Vec<u8>,
+    ) -> Result<ContractExecResult<E::Balance, EventRecord<C::Hash>>> {
         let storage_deposit_limit = self.opts.storage_deposit_limit();
         let call_request = CallRequest {
-            origin: self.opts.signer().account_id(),
+            origin: self.opts.origin(),
             dest: self.contract.clone(),
-            value: self.value,
+            value,
             gas_limit: None,
             storage_deposit_limit,
-            input_data: self.call_data.clone(),
+            input_data: call_data,
         };
-        state_call(&self.rpc, "ContractsApi_call", call_request).await
+        let func = self.pallet.runtime_api("call");
+        let metadata = self.client.metadata();
+        let dry_run = dry_run_state_call(&self.rpc, &metadata, &func, call_request);
+        self.opts.timings().measure(Phase::DryRun, dry_run).await
     }
 
     /// Calls a contract on the blockchain with a specified gas limit.
@@ -203,46 +380,209 @@ where
         &self,
         gas_limit: Option<Weight>,
     ) -> Result<ExtrinsicEvents<C>, ErrorVariant> {
-        if !self
-            .transcoder()
-            .metadata()
-            .spec()
-            .messages()
-            .iter()
-            .find(|msg| msg.label() == &self.message)
-            .expect("message exist after calling CallExec::done()")
-            .mutates()
-        {
-            let inner = anyhow!(
-                "Tried to execute a call on the immutable contract message '{}'. Please do a dry-run instead.",
-                &self.message
-            );
-            return Err(inner.into())
-        }
+        self.call_with(gas_limit, &CallOverrides::default()).await
+    }
+
+    /// Calls the contract like [`CallExec::call`], with the value and arguments of the
+    /// `overrides` instead of those the call was built with.
+    ///
+    /// This allows submitting the same call repeatedly without building it again. Set a
+    /// [`crate::NonceHandle`] in the extrinsic options to submit the calls back-to-back,
+    /// without waiting for each to be included in a block.
+    pub async fn call_with(
+        &self,
+        gas_limit: Option<Weight>,
+        overrides: &CallOverrides<E::Balance>,
+    ) -> Result<ExtrinsicEvents<C>, ErrorVariant> {
+        self.check_mutates()?;
+        let value = overrides.value.unwrap_or(self.value);
+        let call_data = match &overrides.args {
+            Some(args) => self.encode_args(args)?,
+            None => self.call_data.clone(),
+        };
 
         // use user specified values where provided, otherwise estimate
         let gas_limit = match gas_limit {
             Some(gas_limit) => gas_limit,
-            None => self.estimate_gas().await?,
+            None => self.estimate_gas_for(value, &call_data).await?,
         };
         tracing::debug!("calling contract {:?}", self.contract);
         let storage_deposit_limit = self.opts.storage_deposit_limit();
 
-        let call = Call::new(
-            self.contract.clone().into(),
-            self.value,
-            gas_limit,
-            storage_deposit_limit,
-            self.call_data.clone(),
-        )
-        .build();
-
-        let result =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        let result = match &self.contract {
+            ContractAddress::AccountId(contract) => {
+                let call = Call::new(
+                    contract.clone().into(),
+                    value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    call_data,
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+            ContractAddress::H160(contract) => {
+                // `pallet-revive` requires a storage deposit limit, use the deposit
+                // charged by a dry run if none was specified.
+                let storage_deposit_limit = match storage_deposit_limit {
+                    Some(limit) => limit,
+                    None => {
+                        self.dry_run(value, call_data.clone())
+                            .await?
+                            .storage_deposit
+                            .charge_or_zero()
+                    }
+                };
+                let call = ReviveCall::new(
+                    *contract,
+                    value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    call_data,
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+        };
 
         Ok(result)
     }
 
+    /// Calls the contract from another chain, the relay chain or a sibling parachain
+    /// of the chain of the contract, by sending an XCM `Transact` of the call to the
+    /// parachain `dest` with the XCM pallet of the `origin` chain.
+    ///
+    /// The weight required to dispatch the call is queried from the chain of the
+    /// contract, and the `fee` buys its execution there. The extrinsic is signed by the
+    /// signer of the extrinsic options, the call is dispatched on the chain of the
+    /// contract by the account derived from the signer and the origin chain, which must
+    /// hold the `fee`.
+    ///
+    /// The call is executed asynchronously once the message is delivered, so only the
+    /// events of sending the message are returned.
+    pub async fn call_via_xcm(
+        &self,
+        origin: &Connection<C>,
+        dest: u32,
+        fee: XcmFee,
+        gas_limit: Option<Weight>,
+    ) -> Result<XcmSendResult<C>, ErrorVariant> {
+        self.check_mutates()?;
+        let gas_limit = match gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => self.estimate_gas().await?,
+        };
+        let call = self.encoded_call(gas_limit).await?;
+        let require_weight_at_most = query_call_weight(&self.rpc, &call).await?;
+        tracing::debug!(
+            "sending the call to parachain {dest} via XCM, requiring {require_weight_at_most:?}"
+        );
+        let transact = XcmTransact {
+            dest,
+            fee,
+            require_weight_at_most,
+            call,
+        };
+        send_xcm_transact(origin, &transact, &self.opts).await
+    }
+
+    /// Creates the extrinsic calling the contract, to be signed by `account_id` outside
+    /// of this crate, e.g. by a wallet, instead of by the signer of the extrinsic
+    /// options.
+    ///
+    /// Sign its [`UnsignedExtrinsic::signer_payload`] and submit the signed extrinsic
+    /// with [`CallExec::submit_signed`].
+    pub async fn build_partial_extrinsic(
+        &self,
+        gas_limit: Option<Weight>,
+        account_id: &C::AccountId,
+    ) -> Result<UnsignedExtrinsic<C>, ErrorVariant> {
+        self.check_mutates()?;
+        let gas_limit = match gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => self.estimate_gas().await?,
+        };
+        let call = EncodedCall(self.encoded_call(gas_limit).await?);
+        create_unsigned(&self.client, &self.rpc, &call, account_id, &self.opts).await
+    }
+
+    /// Submits the `signed` extrinsic created by [`CallExec::build_partial_extrinsic`],
+    /// returning its events like [`CallExec::call`].
+    pub async fn submit_signed(
+        &self,
+        signed: Vec<u8>,
+    ) -> Result<ExtrinsicEvents<C>, ErrorVariant> {
+        submit_signed(&self.client, &self.rpc, signed, &self.opts).await
+    }
+
+    /// Returns the SCALE encoded call of the contract with the given gas limit.
+    async fn encoded_call(&self, gas_limit: Weight) -> Result<Vec<u8>> {
+        let storage_deposit_limit = self.opts.storage_deposit_limit();
+        let metadata = self.client.metadata();
+        let call = match &self.contract {
+            ContractAddress::AccountId(contract) => {
+                Call::new(
+                    contract.clone().into(),
+                    self.value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    self.call_data.clone(),
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+            ContractAddress::H160(contract) => {
+                let storage_deposit_limit = match storage_deposit_limit {
+                    Some(limit) => limit,
+                    None => self.call_dry_run().await?.storage_deposit.charge_or_zero(),
+                };
+                ReviveCall::new(
+                    *contract,
+                    self.value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    self.call_data.clone(),
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+        };
+        Ok(call)
+    }
+
+    /// Encodes the `args` for the message of the call.
+    fn encode_args(&self, args: &[String]) -> Result<Vec<u8>> {
+        let transcoder = self.transcoder.as_ref().ok_or_else(|| {
+            anyhow!("The arguments of a call with raw data can not be overridden")
+        })?;
+        self.opts
+            .timings()
+            .measure_sync(Phase::Transcode, || transcoder.encode(&self.message, args))
+    }
+
+    /// Returns an error if the message is known not to mutate the state of the
+    /// contract, so that calling it with an extrinsic is pointless.
+    fn check_mutates(&self) -> Result<()> {
+        let mutates = self.transcoder.as_ref().map(|transcoder| {
+            transcoder
+                .metadata()
+                .spec()
+                .messages()
+                .iter()
+                .find(|msg| msg.label() == &self.message)
+                .expect("message exist after calling CallExec::done()")
+                .mutates()
+        });
+        if mutates == Some(false) {
+            let inner = anyhow!(
+                "Tried to execute a call on the immutable contract message '{}'. Please do a dry-run instead.",
+                &self.message
+            );
+            return Err(inner)
+        }
+        Ok(())
+    }
+
     /// Estimates the gas required for a contract call without modifying the blockchain.
     ///
     /// This function provides a gas estimation for contract calls, considering the
@@ -252,12 +592,21 @@ where
     /// Returns the estimated gas weight of type [`Weight`] for contract calls, or an
     /// error.
     pub async fn estimate_gas(&self) -> Result<Weight> {
+        self.estimate_gas_for(self.value, &self.call_data).await
+    }
+
+    /// Estimates the gas required for the call with the given `value` and `call_data`.
+    async fn estimate_gas_for(
+        &self,
+        value: E::Balance,
+        call_data: &[u8],
+    ) -> Result<Weight> {
         match (self.gas_limit, self.proof_size) {
             (Some(ref_time), Some(proof_size)) => {
                 Ok(Weight::from_parts(ref_time, proof_size))
             }
             _ => {
-                let call_result = self.call_dry_run().await?;
+                let call_result = self.dry_run(value, call_data.to_vec()).await?;
                 match call_result.result {
                     Ok(_) => {
                         // use user specified values where provided, otherwise use the
@@ -282,16 +631,136 @@ where
         }
     }
 
+    /// Returns the largest value which can be transferred with the call: the free
+    /// balance of the origin minus the estimated fee of the call with the given gas
+    /// limit and the existential deposit.
+    pub async fn max_value(&self, gas_limit: Weight) -> Result<E::Balance>
+    where
+        C::AccountId: AsRef<[u8]>,
+        E::Balance: IntoVisitor + Into<u128> + TryFrom<u128>,
+    {
+        let fee = self.estimate_fee(gas_limit).await?;
+        let account_id = self.opts.origin();
+        let free: u128 =
+            get_account_balance::<C, E>(&account_id, &self.rpc, &self.client)
+                .await?
+                .free
+                .into();
+        let existential_deposit: u128 = self
+            .client
+            .constants()
+            .at(&subxt::dynamic::constant("Balances", "ExistentialDeposit"))?
+            .as_type::<E::Balance>()?
+            .into();
+        let max_value = free
+            .checked_sub(fee)
+            .and_then(|value| value.checked_sub(existential_deposit))
+            .filter(|value| *value > 0)
+            .ok_or_else(|| {
+                anyhow!(
+                    "The free balance of the origin ({free}) does not cover the estimated \
+                     fee ({fee}) and the existential deposit ({existential_deposit})"
+                )
+            })?;
+        E::Balance::try_from(max_value)
+            .map_err(|_| anyhow!("The value {max_value} exceeds the balance type"))
+    }
+
+    /// Sets the value of the call to the largest value which can be transferred, see
+    /// [`Self::max_value`], and returns the gas limit its fee was estimated with.
+    ///
+    /// The gas is first estimated with the current value. A message may require more gas
+    /// for a larger value, so the call is dry-run again with the resulting value. If it
+    /// requires more gas, the value is computed again with the larger gas limit. The gas
+    /// limit of the call is then fixed to the one the fee was estimated with.
+    pub async fn set_max_value(&mut self) -> Result<Weight>
+    where
+        C::AccountId: AsRef<[u8]>,
+        E::Balance: IntoVisitor + Into<u128> + TryFrom<u128>,
+    {
+        let mut gas_limit = self.estimate_gas().await?;
+        for _ in 0..MAX_VALUE_ESTIMATES {
+            let value = self.max_value(gas_limit).await?;
+            let gas_required = self.estimate_gas_for(value, &self.call_data).await?;
+            if gas_required.all_lte(gas_limit) {
+                self.value = value;
+                self.gas_limit = Some(gas_limit.ref_time());
+                self.proof_size = Some(gas_limit.proof_size());
+                return Ok(gas_limit)
+            }
+            gas_limit = gas_limit.max(gas_required);
+        }
+        Err(anyhow!(
+            "The gas required by the call keeps growing with the transferred value, \
+             specify the value to transfer instead"
+        ))
+    }
+
+    /// Estimates the fee of calling the contract with the given gas limit via the
+    /// transaction payment API.
+    pub async fn estimate_fee(&self, gas_limit: Weight) -> Result<u128> {
+        let storage_deposit_limit = self.opts.storage_deposit_limit();
+        match &self.contract {
+            ContractAddress::AccountId(contract) => {
+                let call = Call::new(
+                    contract.clone().into(),
+                    self.value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    self.call_data.clone(),
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+            ContractAddress::H160(contract) => {
+                let storage_deposit_limit = match storage_deposit_limit {
+                    Some(limit) => limit,
+                    None => self.call_dry_run().await?.storage_deposit.charge_or_zero(),
+                };
+                let call = ReviveCall::new(
+                    *contract,
+                    self.value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    self.call_data.clone(),
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+        }
+    }
+
+    /// Sets the value to be transferred as part of the call.
+    pub fn set_value(&mut self, value: E::Balance) {
+        self.value = value;
+    }
+
+    /// Sets the storage deposit limit of the call.
+    pub fn set_storage_deposit_limit(&mut self, limit: Option<E::Balance>) {
+        self.opts.set_storage_deposit_limit(limit);
+    }
+
     /// Returns the address of the the contract to call.
-    pub fn contract(&self) -> &C::AccountId {
+    pub fn contract(&self) -> &ContractAddress<C::AccountId> {
         &self.contract
     }
 
-    /// Returns the name of the contract message to call.
+    /// Returns the contracts pallet of the target chain.
+    pub fn pallet(&self) -> ContractsPallet {
+        self.pallet
+    }
+
+    /// Returns the name of the contract message to call, or its selector as hex if it
+    /// is called with raw data.
     pub fn message(&self) -> &str {
         &self.message
     }
 
+    /// Returns the selector of the contract message to call.
+    pub fn selector(&self) -> &[u8] {
+        &self.call_data[..4]
+    }
+
     /// Returns the arguments of the contract message to call.
     pub fn args(&self) -> &Vec<String> {
         &self.args
@@ -322,9 +791,10 @@ where
         &self.client
     }
 
-    /// Returns the contract message transcoder.
-    pub fn transcoder(&self) -> &ContractMessageTranscoder {
-        &self.transcoder
+    /// Returns the contract message transcoder, `None` if the message is called with
+    /// raw data.
+    pub fn transcoder(&self) -> Option<&ContractMessageTranscoder> {
+        self.transcoder.as_ref()
     }
 
     /// Returns the call data.
@@ -335,11 +805,12 @@ where
 
 /// A struct that encodes RPC parameters required for a call to a smart contract.
 ///
-/// Copied from `pallet-contracts-rpc-runtime-api`.
+/// Copied from `pallet-contracts-rpc-runtime-api`, the parameters of `pallet-revive`'s
+/// runtime API only differ in the type of `dest`.
 #[derive(Encode)]
 struct CallRequest<AccountId, Balance> {
     origin: AccountId,
-    dest: AccountId,
+    dest: ContractAddress<AccountId>,
     value: Balance,
     gas_limit: Option<Weight>,
     storage_deposit_limit: Option<Balance>,
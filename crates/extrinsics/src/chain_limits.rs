@@ -0,0 +1,117 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::pallet::ContractsPallet;
+use anyhow::{
+    anyhow,
+    Result,
+};
+use scale::Decode;
+use serde::Serialize;
+use subxt::Metadata;
+
+/// The limits the contracts pallet of a chain imposes on contracts, read from the
+/// constants of the pallet.
+///
+/// A limit is `None` if the pallet does not expose it as a constant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChainLimits {
+    /// The maximum size of the code of a contract, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_code_len: Option<u32>,
+    /// The maximum size of a storage key of a contract, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_storage_key_len: Option<u32>,
+    /// The maximum size of the debug buffer of a dry-run, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_debug_buffer_len: Option<u32>,
+}
+
+impl ChainLimits {
+    /// Reads the limits of the `pallet` from the chain `metadata`.
+    ///
+    /// Constants are part of the metadata cached by the client, so this does not
+    /// require a request to the node.
+    pub fn from_metadata(metadata: &Metadata, pallet: ContractsPallet) -> Self {
+        let constant = |name: &str| {
+            let value = metadata
+                .pallet_by_name(pallet.name())?
+                .constant_by_name(name)?
+                .value();
+            u32::decode(&mut &value[..]).ok()
+        };
+        Self {
+            max_code_len: constant("MaxCodeLen"),
+            max_storage_key_len: constant("MaxStorageKeyLen"),
+            max_debug_buffer_len: constant("MaxDebugBufferLen"),
+        }
+    }
+
+    /// Returns an error if code of `code_len` bytes exceeds the maximum code size of
+    /// the chain, before the code is submitted and rejected on-chain.
+    pub fn check_code_len(&self, code_len: usize) -> Result<()> {
+        match self.max_code_len {
+            Some(max_code_len) if code_len > max_code_len as usize => {
+                Err(anyhow!(
+                    "The contract code is {code_len} bytes, the chain limit is \
+                     {max_code_len} bytes (`MaxCodeLen`). Build the contract with \
+                     `--release`, and try `--optimization-passes z` to optimize it for \
+                     size"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode")
+    }
+
+    #[test]
+    fn reads_limits_from_metadata() {
+        let limits = ChainLimits::from_metadata(&metadata(), ContractsPallet::Contracts);
+        assert!(limits.max_code_len.is_some());
+        assert!(limits.max_storage_key_len.is_some());
+        assert!(limits.max_debug_buffer_len.is_some());
+
+        let limits = ChainLimits::from_metadata(&metadata(), ContractsPallet::Revive);
+        assert_eq!(limits, ChainLimits::default());
+    }
+
+    #[test]
+    fn rejects_oversized_code() {
+        let limits = ChainLimits {
+            max_code_len: Some(131072),
+            ..Default::default()
+        };
+        assert!(limits.check_code_len(131072).is_ok());
+        let err = limits.check_code_len(131400).unwrap_err().to_string();
+        assert!(
+            err.starts_with(
+                "The contract code is 131400 bytes, the chain limit is 131072 bytes"
+            ),
+            "{err}"
+        );
+        assert!(ChainLimits::default().check_code_len(usize::MAX).is_ok());
+    }
+}
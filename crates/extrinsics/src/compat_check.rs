@@ -0,0 +1,154 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::pallet::ContractsPallet;
+use anyhow::{
+    Context,
+    Result,
+};
+use contract_metadata::{
+    ContractMetadata,
+    Language,
+};
+use scale::Decode;
+use std::fmt::{
+    self,
+    Display,
+};
+use subxt::{
+    Config,
+    OnlineClient,
+};
+
+/// The storage versions of `pallet-contracts` which introduced the host functions used
+/// by the contracts of an ink! major version.
+const REQUIRED_PALLET_VERSIONS: [(u64, u16); 2] = [(4, 9), (5, 15)];
+
+/// The contract requires a newer `pallet-contracts` than the one of the connected chain.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct IncompatibleChain {
+    /// The ink! version the contract was built with.
+    pub ink_version: String,
+    /// The minimum storage version of `pallet-contracts` required by the contract.
+    pub required_pallet_version: u16,
+    /// The storage version of `pallet-contracts` of the connected chain.
+    pub chain_pallet_version: u16,
+}
+
+impl Display for IncompatibleChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "contract built with ink! {} requires pallet-contracts >= v{}; connected \
+            chain exposes v{}. Pass `--skip-compat-check` to submit it anyway",
+            self.ink_version, self.required_pallet_version, self.chain_pallet_version
+        )
+    }
+}
+
+impl std::error::Error for IncompatibleChain {}
+
+/// Checks that the contract of the `metadata` can be executed by the `pallet-contracts`
+/// of the connected chain, before its code is uploaded.
+///
+/// Chains using `pallet-revive` and contracts not written in ink! are not checked.
+pub async fn check_chain_compatibility<C: Config>(
+    client: &OnlineClient<C>,
+    pallet: ContractsPallet,
+    metadata: &ContractMetadata,
+) -> Result<()> {
+    let language = &metadata.source.language;
+    if pallet != ContractsPallet::Contracts || !matches!(language.language, Language::Ink)
+    {
+        return Ok(())
+    }
+    let chain_pallet_version = fetch_pallet_version(client, pallet).await?;
+    check_pallet_version(
+        language.version.major,
+        language.version.minor,
+        chain_pallet_version,
+    )?;
+    Ok(())
+}
+
+/// Returns an error if the contracts of the ink! version `major.minor` require a newer
+/// version of `pallet-contracts` than `chain_pallet_version`.
+fn check_pallet_version(
+    major: u64,
+    minor: u64,
+    chain_pallet_version: u16,
+) -> std::result::Result<(), IncompatibleChain> {
+    let required = REQUIRED_PALLET_VERSIONS
+        .iter()
+        .rev()
+        .find(|(ink_major, _)| major >= *ink_major)
+        .map(|(_, version)| *version);
+    match required {
+        Some(required) if chain_pallet_version < required => {
+            Err(IncompatibleChain {
+                ink_version: format!("{major}.{minor}"),
+                required_pallet_version: required,
+                chain_pallet_version,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fetches the storage version of the pallet, which is `0` if it was never set.
+async fn fetch_pallet_version<C: Config>(
+    client: &OnlineClient<C>,
+    pallet: ContractsPallet,
+) -> Result<u16> {
+    let mut key = sp_core::hashing::twox_128(pallet.name().as_bytes()).to_vec();
+    key.extend(sp_core::hashing::twox_128(b":__STORAGE_VERSION__:"));
+    let version = client.storage().at_latest().await?.fetch_raw(key).await?;
+    match version {
+        Some(version) => {
+            u16::decode(&mut &version[..])
+                .context("Failed to decode the storage version of the contracts pallet")
+        }
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pallet_version_must_support_ink_version() {
+        assert!(check_pallet_version(3, 0, 0).is_ok());
+        assert!(check_pallet_version(4, 3, 9).is_ok());
+        assert!(check_pallet_version(5, 0, 16).is_ok());
+        assert!(check_pallet_version(6, 0, 15).is_ok());
+
+        let err = check_pallet_version(5, 0, 9).unwrap_err();
+        assert_eq!(
+            err,
+            IncompatibleChain {
+                ink_version: "5.0".to_string(),
+                required_pallet_version: 15,
+                chain_pallet_version: 9,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "contract built with ink! 5.0 requires pallet-contracts >= v15; connected \
+             chain exposes v9. Pass `--skip-compat-check` to submit it anyway"
+        );
+    }
+}
@@ -15,22 +15,39 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    BundleSignature,
     ContractMessageTranscoder,
     ContractMetadata,
     CrateMetadata,
     WasmCode,
 };
 use anyhow::{
+    anyhow,
     Context,
     Result,
 };
 use colored::Colorize;
+use contract_build::{
+    BuildInfo,
+    Target,
+};
 use ink_metadata::InkProject;
-use std::path::{
-    Path,
-    PathBuf,
+use sha2::{
+    Digest,
+    Sha256,
+};
+use std::{
+    fmt,
+    io::Read,
+    path::{
+        Path,
+        PathBuf,
+    },
 };
 
+/// The artifact file argument to read a `.contract` bundle from stdin.
+const STDIN_ARTIFACT: &str = "-";
+
 /// Contract artifacts for use with extrinsic commands.
 #[derive(Debug)]
 pub struct ContractArtifacts {
@@ -42,6 +59,30 @@ pub struct ContractArtifacts {
     metadata: Option<ContractMetadata>,
     /// The Wasm code of the contract if available.
     pub code: Option<WasmCode>,
+    /// The target the contract code was built for.
+    target: Target,
+    /// Notices about loading the artifacts, to be displayed by the caller.
+    notices: Vec<ArtifactsNotice>,
+}
+
+/// A notice about loading contract artifacts, see [`ContractArtifacts::notices`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactsNotice {
+    /// Information for verifying the artifacts, e.g. the hash of a downloaded bundle.
+    Info(String),
+    /// The artifacts were loaded, but might not be what the user intended.
+    Warning(String),
+}
+
+impl fmt::Display for ArtifactsNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info(message) => write!(f, "{} {message}", "Info:".cyan().bold()),
+            Self::Warning(message) => {
+                write!(f, "{} {message}", "warning:".yellow().bold())
+            }
+        }
+    }
 }
 
 impl ContractArtifacts {
@@ -52,10 +93,8 @@ impl ContractArtifacts {
     ) -> Result<ContractArtifacts> {
         let artifact_path = match (manifest_path, file) {
             (manifest_path, None) => {
-                let crate_metadata = CrateMetadata::from_manifest_path(
-                    manifest_path,
-                    contract_build::Target::Wasm,
-                )?;
+                let crate_metadata =
+                    CrateMetadata::from_manifest_path(manifest_path, Target::Wasm)?;
 
                 if crate_metadata.contract_bundle_path().exists() {
                     crate_metadata.contract_bundle_path()
@@ -68,7 +107,20 @@ impl ContractArtifacts {
                     )
                 }
             }
-            (None, Some(artifact_file)) => artifact_file.clone(),
+            (None, Some(artifact_file)) => {
+                match resolve_artifact_file(artifact_file)? {
+                    ArtifactFile::Path(path) => path,
+                    ArtifactFile::Bundle {
+                        source,
+                        metadata,
+                        notice,
+                    } => {
+                        let mut artifacts = Self::from_bundle(&source, *metadata);
+                        artifacts.notices.insert(0, notice);
+                        return Ok(artifacts)
+                    }
+                }
+            }
             (Some(_), Some(_)) => {
                 anyhow::bail!("conflicting options: --manifest-path and --file")
             }
@@ -86,12 +138,14 @@ impl ContractArtifacts {
                     let code = metadata.clone().source.wasm.map(|wasm| WasmCode(wasm.0));
                     (PathBuf::from(path), Some(metadata), code)
                 }
-                Some("wasm") => {
+                Some("wasm") | Some("polkavm") => {
                     let file_name = path.file_stem()
-                        .context("WASM bundle file has unreadable name")?
+                        .context("Contract code file has unreadable name")?
                         .to_str()
                         .context("Error parsing filename string")?;
-                    let code = Some(WasmCode(std::fs::read(path)?));
+                    let code = std::fs::read(path)?;
+                    check_code_extension(path, &code)?;
+                    let code = Some(WasmCode(code));
                     let dir = path.parent().map_or_else(PathBuf::new, PathBuf::from);
                     let metadata_path = dir.join(format!("{file_name}.json"));
                     if !metadata_path.exists() {
@@ -102,33 +156,160 @@ impl ContractArtifacts {
                     }
                 }
                 Some(ext) => anyhow::bail!(
-                    "Invalid artifact extension {ext}, expected `.contract`, `.json` or `.wasm`"
+                    "Invalid artifact extension {ext}, expected `.contract`, `.json`, `.wasm` or `.polkavm`"
                 ),
                 None => {
                     anyhow::bail!(
-                        "Artifact path has no extension, expected `.contract`, `.json`, or `.wasm`"
+                        "Artifact path has no extension, expected `.contract`, `.json`, `.wasm` or `.polkavm`"
                     )
                 }
             };
 
+        // A code file is built for the target of its extension, regardless of the
+        // metadata next to it.
+        let target = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("polkavm") => Target::RiscV,
+            Some("wasm") => Target::Wasm,
+            _ => metadata.as_ref().map_or(Target::Wasm, metadata_target),
+        };
+
+        Ok(Self::new(
+            path.into(),
+            metadata_path,
+            metadata,
+            code,
+            target,
+        ))
+    }
+
+    /// Load the code and metadata of a `.contract` bundle downloaded or read from stdin,
+    /// which is kept in memory instead of a file.
+    fn from_bundle(source: &str, metadata: ContractMetadata) -> Self {
+        let code = metadata.source.wasm.clone().map(|wasm| WasmCode(wasm.0));
+        let target = metadata_target(&metadata);
+        Self::new(source.into(), source.into(), Some(metadata), code, target)
+    }
+
+    fn new(
+        artifacts_path: PathBuf,
+        metadata_path: PathBuf,
+        metadata: Option<ContractMetadata>,
+        code: Option<WasmCode>,
+        target: Target,
+    ) -> Self {
+        let mut notices = Vec::new();
         if let Some(contract_metadata) = metadata.as_ref() {
             if let Err(e) = contract_metadata.check_ink_compatibility() {
-                eprintln!("{} {}", "warning:".yellow().bold(), e.to_string().bold());
+                notices.push(ArtifactsNotice::Warning(e.to_string()));
             }
         }
-        Ok(Self {
-            artifacts_path: path.into(),
+        Self {
+            artifacts_path,
             metadata_path,
             metadata,
             code,
+            target,
+            notices,
+        }
+    }
+
+    /// Replace the metadata of a raw `.wasm` or `.polkavm` code file with the metadata
+    /// file at `path`, e.g. for code obtained without the metadata next to it.
+    ///
+    /// ## Errors
+    /// - The artifacts were not loaded from a raw code file.
+    /// - Invalid contract metadata.
+    pub fn with_metadata_file(mut self, path: &Path) -> Result<Self> {
+        anyhow::ensure!(
+            self.is_code_file(),
+            "A separate metadata file can only be used with a raw `.wasm` or `.polkavm` \
+             code file, but {} already contains the metadata",
+            self.artifacts_path.display()
+        );
+        let metadata = ContractMetadata::load(path)?;
+        if let Some(code) = &self.code {
+            if code.code_hash() != metadata.source.hash.0 {
+                self.notices.push(ArtifactsNotice::Warning(format!(
+                    "The metadata file {} was generated for different code than {}",
+                    path.display(),
+                    self.artifacts_path.display()
+                )));
+            }
+        }
+        if let Err(e) = metadata.check_ink_compatibility() {
+            self.notices.push(ArtifactsNotice::Warning(e.to_string()));
+        }
+        Ok(Self {
+            metadata_path: path.into(),
+            metadata: Some(metadata),
+            ..self
         })
     }
 
+    /// Verify the signature of the `.contract` bundle the artifacts were loaded from.
+    ///
+    /// Returns the public key of the signer, or `None` if the bundle is unsigned or the
+    /// code was loaded from a raw code file, which can not be signed.
+    pub fn bundle_signer(&self) -> Result<Option<[u8; 32]>> {
+        let Some(metadata) = self.metadata.as_ref().filter(|_| !self.is_code_file())
+        else {
+            return Ok(None)
+        };
+        match BundleSignature::of(metadata)? {
+            Some(signature) => {
+                signature.verify(metadata)?;
+                Ok(Some(signature.signer()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the artifacts were loaded from a raw `.wasm` or `.polkavm`
+    /// code file.
+    fn is_code_file(&self) -> bool {
+        matches!(
+            self.artifacts_path.extension().and_then(|ext| ext.to_str()),
+            Some("wasm" | "polkavm")
+        )
+    }
+
+    /// Returns the notices about loading the artifacts, e.g. where a downloaded bundle
+    /// came from, which are not printed but left to the caller to display.
+    pub fn notices(&self) -> &[ArtifactsNotice] {
+        &self.notices
+    }
+
+    /// Adds a notice about the artifacts.
+    pub(crate) fn push_notice(&mut self, notice: ArtifactsNotice) {
+        self.notices.push(notice);
+    }
+
+    /// Get the target the contract code was built for.
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
     /// Get the path of the artifact file used to load the artifacts.
     pub fn artifact_path(&self) -> &Path {
         self.artifacts_path.as_path()
     }
 
+    /// Check that the hash of the contract code matches the `expected` code hash.
+    pub fn check_code_hash(&self, expected: &[u8; 32]) -> Result<()> {
+        let code_hash = match &self.code {
+            Some(code) => code.code_hash(),
+            None => self.code_hash()?,
+        };
+        anyhow::ensure!(
+            &code_hash == expected,
+            "The code hash 0x{} of the contract artifact does not match the expected \
+             code hash 0x{}",
+            hex::encode(code_hash),
+            hex::encode(expected)
+        );
+        Ok(())
+    }
+
     /// Get contract metadata, if available.
     ///
     /// ## Errors
@@ -170,3 +351,278 @@ impl ContractArtifacts {
             .context("Failed to deserialize ink project metadata from contract metadata")
     }
 }
+
+/// Returns the target recorded in the build info of the metadata, defaulting to Wasm for
+/// metadata without build info.
+fn metadata_target(metadata: &ContractMetadata) -> Target {
+    metadata
+        .source
+        .build_info
+        .clone()
+        .and_then(|info| serde_json::from_value::<BuildInfo>(info.into()).ok())
+        .map_or(Target::Wasm, |info| info.target)
+}
+
+/// Checks that the magic bytes of the raw `code` read from `path` match the target of its
+/// extension, e.g. to refuse a PolkaVM program saved as a `.wasm` file.
+fn check_code_extension(path: &Path, code: &[u8]) -> Result<()> {
+    let expected = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("polkavm") => Target::RiscV,
+        _ => Target::Wasm,
+    };
+    let code_name = |target: Target| {
+        match target {
+            Target::Wasm => "a Wasm module",
+            Target::RiscV => "a PolkaVM program",
+        }
+    };
+    match Target::from_code(code) {
+        Some(target) if target == expected => Ok(()),
+        Some(target) => {
+            anyhow::bail!(
+                "The code file {} contains {}, but its `.{}` extension expects {}",
+                path.display(),
+                code_name(target),
+                expected.dest_extension(),
+                code_name(expected)
+            )
+        }
+        None => {
+            anyhow::bail!(
+                "The code file {} is neither a Wasm module nor a PolkaVM program",
+                path.display()
+            )
+        }
+    }
+}
+
+/// A contract artifact given on the command line, see [`resolve_artifact_file`].
+#[derive(Debug)]
+pub enum ArtifactFile {
+    /// The path of a local artifact file.
+    Path(PathBuf),
+    /// A `.contract` bundle downloaded or read from stdin, which is kept in memory.
+    Bundle {
+        /// The URL the bundle was downloaded from, or `stdin`.
+        source: String,
+        /// The metadata of the bundle, including its code.
+        metadata: Box<ContractMetadata>,
+        /// A notice with the SHA-256 of the bundle, for the caller to display for
+        /// verification.
+        notice: ArtifactsNotice,
+    },
+}
+
+/// Resolves the contract artifact `file`.
+///
+/// A `.contract` bundle given as an `https://` URL is downloaded, and `-` reads it from
+/// stdin, without writing it to a file. Any other `file` is a local path.
+pub fn resolve_artifact_file(file: &Path) -> Result<ArtifactFile> {
+    let Some(file_str) = file.to_str() else {
+        return Ok(ArtifactFile::Path(file.to_path_buf()))
+    };
+    let (source, bundle) = if file_str == STDIN_ARTIFACT {
+        let mut bundle = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut bundle)
+            .context("Failed to read the contract bundle from stdin")?;
+        ("stdin".to_string(), bundle)
+    } else if file_str.starts_with("https://") {
+        (file_str.to_string(), download_bundle(file_str)?)
+    } else if file_str.starts_with("http://") {
+        anyhow::bail!(
+            "Refusing to download the contract artifact {file_str} over plain HTTP, use an \
+             `https://` URL"
+        )
+    } else {
+        return Ok(ArtifactFile::Path(file.to_path_buf()))
+    };
+
+    let metadata: ContractMetadata =
+        serde_json::from_slice(&bundle).with_context(|| {
+            format!("The content of {source} is not a `.contract` bundle")
+        })?;
+    anyhow::ensure!(
+        metadata.source.wasm.is_some(),
+        "The content of {source} is contract metadata without code, expected a \
+         `.contract` bundle"
+    );
+    let sha256 = hex::encode(Sha256::digest(&bundle));
+    let notice = ArtifactsNotice::Info(format!(
+        "Loaded the contract bundle from {source}, SHA-256 0x{sha256}"
+    ));
+    Ok(ArtifactFile::Bundle {
+        source,
+        metadata: Box::new(metadata),
+        notice,
+    })
+}
+
+/// Downloads the `.contract` bundle at the `https://` `url`.
+fn download_bundle(url: &str) -> Result<Vec<u8>> {
+    // the blocking client must not run on the thread of an async runtime
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let response = reqwest::blocking::get(url)
+                    .and_then(|response| response.error_for_status())
+                    .with_context(|| {
+                        format!("Failed to download the contract artifact from {url}")
+                    })?;
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|content_type| content_type.to_str().ok())
+                    .map(|content_type| {
+                        content_type
+                            .split(';')
+                            .next()
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string()
+                    });
+                if let Some(content_type) = content_type {
+                    anyhow::ensure!(
+                        ["application/json", "application/octet-stream"]
+                            .contains(&content_type.as_str()),
+                        "{url} returned content of type `{content_type}`, expected a \
+                         `.contract` bundle as `application/json` or \
+                         `application/octet-stream`"
+                    );
+                }
+                let bundle = response.bytes().with_context(|| {
+                    format!("Failed to download the contract artifact from {url}")
+                })?;
+                Ok(bundle.to_vec())
+            })
+            .join()
+            .map_err(|_| {
+                anyhow!("Downloading the contract artifact from {url} panicked")
+            })?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_artifact_file_keeps_local_paths() {
+        let path = PathBuf::from("target/ink/flipper.contract");
+        assert!(matches!(
+            resolve_artifact_file(&path).unwrap(),
+            ArtifactFile::Path(resolved) if resolved == path
+        ));
+
+        let err = resolve_artifact_file(Path::new("http://example.com/flipper.contract"))
+            .unwrap_err();
+        assert!(err.to_string().contains("use an `https://` URL"), "{err}");
+    }
+
+    #[test]
+    fn check_code_hash_compares_hash_of_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flipper.wasm");
+        let code = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+        std::fs::write(&path, code).unwrap();
+        let artifacts =
+            ContractArtifacts::from_manifest_or_file(None, Some(&path)).unwrap();
+
+        assert!(artifacts
+            .check_code_hash(&contract_build::code_hash(&code))
+            .is_ok());
+        let err = artifacts.check_code_hash(&[0; 32]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("does not match the expected code hash"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn metadata_of_different_code_is_a_notice() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flipper.wasm");
+        std::fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+        let metadata = dir.path().join("other.json");
+        let json = serde_json::json!({
+            "source": {
+                "hash": format!("0x{}", hex::encode([0u8; 32])),
+                "language": "ink! 5.0.0",
+                "compiler": "rustc 1.76.0",
+            },
+            "contract": {
+                "name": "other",
+                "version": "0.1.0",
+                "authors": ["Parity Technologies <admin@parity.io>"],
+            },
+        });
+        std::fs::write(&metadata, json.to_string()).unwrap();
+
+        let artifacts = ContractArtifacts::from_manifest_or_file(None, Some(&path))
+            .unwrap()
+            .with_metadata_file(&metadata)
+            .unwrap();
+        assert!(matches!(
+            artifacts.notices(),
+            [ArtifactsNotice::Warning(message)]
+                if message.contains("was generated for different code")
+        ));
+    }
+
+    #[test]
+    fn incompatible_ink_version_is_a_notice() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flipper.json");
+        let json = serde_json::json!({
+            "source": {
+                "hash": format!("0x{}", hex::encode([0u8; 32])),
+                "language": "ink! 1.0.0",
+                "compiler": "rustc 1.76.0",
+            },
+            "contract": {
+                "name": "flipper",
+                "version": "0.1.0",
+                "authors": ["Parity Technologies <admin@parity.io>"],
+            },
+        });
+        std::fs::write(&path, json.to_string()).unwrap();
+
+        let artifacts =
+            ContractArtifacts::from_manifest_or_file(None, Some(&path)).unwrap();
+        assert!(
+            matches!(artifacts.notices(), [ArtifactsNotice::Warning(_)]),
+            "{:?}",
+            artifacts.notices()
+        );
+    }
+
+    #[test]
+    fn code_file_extension_must_match_magic_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let polkavm = dir.path().join("flipper.polkavm");
+        std::fs::write(&polkavm, b"PVM\0\x01").unwrap();
+        let artifacts =
+            ContractArtifacts::from_manifest_or_file(None, Some(&polkavm)).unwrap();
+        assert_eq!(artifacts.target(), Target::RiscV);
+
+        let wasm = dir.path().join("flipper.wasm");
+        std::fs::write(&wasm, b"PVM\0\x01").unwrap();
+        let err =
+            ContractArtifacts::from_manifest_or_file(None, Some(&wasm)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("contains a PolkaVM program, but its `.wasm` extension"),
+            "{err}"
+        );
+
+        std::fs::write(&wasm, b"not code").unwrap();
+        let err =
+            ContractArtifacts::from_manifest_or_file(None, Some(&wasm)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("is neither a Wasm module nor a PolkaVM program"),
+            "{err}"
+        );
+    }
+}
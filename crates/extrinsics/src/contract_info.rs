@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::get_best_block;
+use super::{
+    get_best_block,
+    ContractAddress,
+    ContractsPallet,
+};
 use anyhow::{
     anyhow,
     Result,
@@ -26,7 +30,10 @@ use std::fmt::{
 };
 
 use ink_env::Environment;
-use scale::Decode;
+use scale::{
+    Decode,
+    Encode,
+};
 use std::option::Option;
 use subxt::{
     backend::legacy::LegacyRpcMethods,
@@ -41,12 +48,13 @@ use subxt::{
         scale_value::Value,
     },
     storage::dynamic,
+    utils::H160,
     Config,
     OnlineClient,
 };
 
 /// Return the account data for an account ID.
-async fn get_account_balance<C: Config, E: Environment>(
+pub(crate) async fn get_account_balance<C: Config, E: Environment>(
     account: &C::AccountId,
     rpc: &LegacyRpcMethods<C>,
     client: &OnlineClient<C>,
@@ -71,23 +79,28 @@ where
 }
 
 /// Fetch the contract info from the storage using the provided client.
+///
+/// The contract info is read from `pallet-contracts` or `pallet-revive`, depending on the
+/// pallet exposed by the chain.
 pub async fn fetch_contract_info<C: Config, E: Environment>(
-    contract: &C::AccountId,
+    contract: &ContractAddress<C::AccountId>,
     rpc: &LegacyRpcMethods<C>,
     client: &OnlineClient<C>,
 ) -> Result<ContractInfo<C::Hash, E::Balance>>
 where
-    C::AccountId: AsRef<[u8]> + Display + IntoVisitor,
+    C::AccountId: AsRef<[u8]> + Display + IntoVisitor + Encode + Decode,
     C::Hash: IntoVisitor,
     DecodeError: From<<<C::AccountId as IntoVisitor>::Visitor as Visitor>::Error>,
     E::Balance: IntoVisitor,
 {
+    let pallet = ContractsPallet::detect(&client.metadata())?;
+    contract.check_pallet(pallet)?;
     let best_block = get_best_block(rpc).await?;
 
     let contract_info_address = dynamic(
-        "Contracts",
+        pallet.name(),
         "ContractInfoOf",
-        vec![Value::from_bytes(contract)],
+        vec![Value::from_bytes(contract.encode())],
     );
     let contract_info_value = client
         .storage()
@@ -101,8 +114,9 @@ where
             )
         })?;
 
+    let contract_account = contract_account::<C>(contract)?;
     let contract_info_raw =
-        ContractInfoRaw::<C, E>::new(contract.clone(), contract_info_value)?;
+        ContractInfoRaw::<C, E>::new(contract_account, contract_info_value)?;
     let deposit_account = contract_info_raw.get_deposit_account();
 
     let deposit_account_data =
@@ -110,6 +124,79 @@ where
     Ok(contract_info_raw.into_contract_info(deposit_account_data))
 }
 
+/// Returns `true` if contract information is stored for the given address.
+pub(crate) async fn contract_exists<C: Config>(
+    contract: &ContractAddress<C::AccountId>,
+    rpc: &LegacyRpcMethods<C>,
+    client: &OnlineClient<C>,
+) -> Result<bool>
+where
+    C::AccountId: Encode,
+{
+    let pallet = ContractsPallet::detect(&client.metadata())?;
+    contract.check_pallet(pallet)?;
+    let best_block = get_best_block(rpc).await?;
+
+    let contract_info_address = dynamic(
+        pallet.name(),
+        "ContractInfoOf",
+        vec![Value::from_bytes(contract.encode())],
+    );
+    let contract_info = client
+        .storage()
+        .at(best_block)
+        .fetch(&contract_info_address)
+        .await?;
+    Ok(contract_info.is_some())
+}
+
+/// Returns `true` if code with the given hash is stored on chain.
+pub(crate) async fn code_exists<C: Config>(
+    code_hash: &C::Hash,
+    rpc: &LegacyRpcMethods<C>,
+    client: &OnlineClient<C>,
+) -> Result<bool> {
+    let pallet = ContractsPallet::detect(&client.metadata())?;
+    let best_block = get_best_block(rpc).await?;
+
+    let pristine_code_address = dynamic(
+        pallet.name(),
+        "PristineCode",
+        vec![Value::from_bytes(code_hash.encode())],
+    );
+    let pristine_code = client
+        .storage()
+        .at(best_block)
+        .fetch(&pristine_code_address)
+        .await?;
+    Ok(pristine_code.is_some())
+}
+
+/// Returns the account which holds the balance of a contract.
+pub(crate) fn contract_account<C: Config>(
+    contract: &ContractAddress<C::AccountId>,
+) -> Result<C::AccountId>
+where
+    C::AccountId: Clone + Decode,
+{
+    match contract {
+        ContractAddress::AccountId(account_id) => Ok(account_id.clone()),
+        ContractAddress::H160(address) => revive_contract_account::<C>(address),
+    }
+}
+
+/// Returns the account which holds the balance and deposits of a `pallet-revive`
+/// contract: its address padded with `0xEE` bytes.
+fn revive_contract_account<C: Config>(address: &H160) -> Result<C::AccountId>
+where
+    C::AccountId: Decode,
+{
+    let mut account = [0xEE; 32];
+    account[..20].copy_from_slice(address.as_bytes());
+    Decode::decode(&mut &account[..])
+        .map_err(|err| anyhow!("AccountId deserialization error: {}", err))
+}
+
 /// Struct representing contract info, supporting deposit on either the main or secondary
 /// account.
 struct ContractInfoRaw<C: Config, E: Environment> {
@@ -270,10 +357,11 @@ pub async fn fetch_wasm_code<C: Config>(
 where
     C::Hash: AsRef<[u8]> + Display + IntoVisitor,
 {
+    let pallet = ContractsPallet::detect(&client.metadata())?;
     let best_block = get_best_block(rpc).await?;
 
     let pristine_code_address =
-        dynamic("Contracts", "PristineCode", vec![Value::from_bytes(hash)]);
+        dynamic(pallet.name(), "PristineCode", vec![Value::from_bytes(hash)]);
     let pristine_code = client
         .storage()
         .at(best_block)
@@ -291,30 +379,45 @@ where
 fn parse_contract_account_address<C: Config>(
     storage_contract_account_key: &[u8],
     storage_contract_root_key_len: usize,
-) -> Result<C::AccountId>
+    pallet: ContractsPallet,
+) -> Result<ContractAddress<C::AccountId>>
 where
     C::AccountId: Decode,
 {
     // storage_contract_account_key is a concatenation of contract_info_of root key and
-    // Twox64Concat(AccountId)
-    let mut account = storage_contract_account_key
-        .get(storage_contract_root_key_len + 8..)
-        .ok_or(anyhow!("Unexpected storage key size"))?;
-    Decode::decode(&mut account)
-        .map_err(|err| anyhow!("AccountId deserialization error: {}", err))
+    // Twox64Concat(AccountId) for `pallet-contracts`, or Identity(H160) for
+    // `pallet-revive`
+    match pallet {
+        ContractsPallet::Contracts => {
+            let mut account = storage_contract_account_key
+                .get(storage_contract_root_key_len + 8..)
+                .ok_or(anyhow!("Unexpected storage key size"))?;
+            Decode::decode(&mut account)
+                .map(ContractAddress::AccountId)
+                .map_err(|err| anyhow!("AccountId deserialization error: {}", err))
+        }
+        ContractsPallet::Revive => {
+            let address = storage_contract_account_key
+                .get(storage_contract_root_key_len..)
+                .filter(|address| address.len() == H160::len_bytes())
+                .ok_or(anyhow!("Unexpected storage key size"))?;
+            Ok(ContractAddress::H160(H160::from_slice(address)))
+        }
+    }
 }
 
 /// Fetch all contract addresses from the storage using the provided client.
 pub async fn fetch_all_contracts<C: Config>(
     client: &OnlineClient<C>,
     rpc: &LegacyRpcMethods<C>,
-) -> Result<Vec<C::AccountId>>
+) -> Result<Vec<ContractAddress<C::AccountId>>>
 where
     C::AccountId: Decode,
 {
+    let pallet = ContractsPallet::detect(&client.metadata())?;
     let best_block = get_best_block(rpc).await?;
     let root_key =
-        subxt::dynamic::storage("Contracts", "ContractInfoOf", Vec::<()>::new())
+        subxt::dynamic::storage(pallet.name(), "ContractInfoOf", Vec::<()>::new())
             .to_root_bytes();
     let mut keys = client
         .storage()
@@ -325,13 +428,107 @@ where
     let mut contract_accounts = Vec::new();
     while let Some(result) = keys.next().await {
         let key = result?;
-        let contract_account = parse_contract_account_address::<C>(&key, root_key.len())?;
+        let contract_account =
+            parse_contract_account_address::<C>(&key, root_key.len(), pallet)?;
         contract_accounts.push(contract_account);
     }
 
     Ok(contract_accounts)
 }
 
+/// Fetch the information about all code uploaded to the chain from the storage using
+/// the provided client.
+pub async fn fetch_all_code_info<C: Config, E: Environment>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+) -> Result<Vec<CodeInfo<C::Hash, C::AccountId, E::Balance>>>
+where
+    C::Hash: Decode,
+    C::AccountId: IntoVisitor,
+    E::Balance: IntoVisitor,
+{
+    let pallet = ContractsPallet::detect(&client.metadata())?;
+    let best_block = get_best_block(rpc).await?;
+    let address = dynamic(pallet.name(), "CodeInfoOf", Vec::<Value>::new());
+    let mut entries = client.storage().at(best_block).iter(address).await?;
+
+    let mut code_infos = Vec::new();
+    while let Some(result) = entries.next().await {
+        let (key, value) = result?;
+        code_infos.push(CodeInfo::from_storage(&key, value)?);
+    }
+    Ok(code_infos)
+}
+
+/// Information about code uploaded to the chain.
+#[derive(Debug, Eq, PartialEq, serde::Serialize)]
+pub struct CodeInfo<Hash, AccountId, Balance> {
+    code_hash: Hash,
+    owner: AccountId,
+    deposit: Balance,
+    refcount: u64,
+    code_len: u32,
+}
+
+impl<Hash, AccountId, Balance> CodeInfo<Hash, AccountId, Balance> {
+    /// Decode the code info from a `CodeInfoOf` storage entry.
+    fn from_storage(key: &[u8], value: DecodedValueThunk) -> Result<Self>
+    where
+        Hash: Decode,
+        AccountId: IntoVisitor,
+        Balance: IntoVisitor,
+    {
+        // the key is a concatenation of the `CodeInfoOf` root key and Identity(Hash)
+        let mut code_hash = key
+            .len()
+            .checked_sub(32)
+            .map(|start| &key[start..])
+            .ok_or(anyhow!("Unexpected storage key size"))?;
+        let code_hash = Decode::decode(&mut code_hash)
+            .map_err(|err| anyhow!("Code hash deserialization error: {}", err))?;
+        let code_info = value
+            .as_type::<CodeInfoOf<AccountId, Balance>>()
+            .map_err(|err| anyhow!("Code info could not be parsed: {err}"))?;
+        Ok(Self {
+            code_hash,
+            owner: code_info.owner,
+            deposit: code_info.deposit,
+            refcount: code_info.refcount,
+            code_len: code_info.code_len,
+        })
+    }
+
+    /// Return the hash of the code.
+    pub fn code_hash(&self) -> &Hash {
+        &self.code_hash
+    }
+
+    /// Return the account which uploaded the code and holds the deposit.
+    pub fn owner(&self) -> &AccountId {
+        &self.owner
+    }
+
+    /// Return the storage deposit held for the code.
+    pub fn deposit(&self) -> &Balance {
+        &self.deposit
+    }
+
+    /// Return the number of contracts using the code.
+    pub fn refcount(&self) -> u64 {
+        self.refcount
+    }
+
+    /// Return the length of the code in bytes.
+    pub fn code_len(&self) -> u32 {
+        self.code_len
+    }
+
+    /// Return `true` if no contract uses the code, so the owner can remove it.
+    pub fn is_removable(&self) -> bool {
+        self.refcount == 0
+    }
+}
+
 /// A struct used in the storage reads to access account info.
 #[derive(DecodeAsType, Debug)]
 #[decode_as_type(crate_path = "subxt::ext::scale_decode")]
@@ -342,8 +539,8 @@ struct AccountInfo<Balance> {
 /// A struct used in the storage reads to access account data.
 #[derive(Clone, Debug, DecodeAsType)]
 #[decode_as_type(crate_path = "subxt::ext::scale_decode")]
-struct AccountData<Balance> {
-    free: Balance,
+pub(crate) struct AccountData<Balance> {
+    pub free: Balance,
     reserved: Balance,
 }
 
@@ -362,6 +559,16 @@ struct ContractInfoOf<Hash, Balance> {
     storage_item_deposit: Balance,
 }
 
+/// A struct used in the storage reads to access code info.
+#[derive(Debug, DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct CodeInfoOf<AccountId, Balance> {
+    owner: AccountId,
+    deposit: Balance,
+    refcount: u64,
+    code_len: u32,
+}
+
 /// A struct used in storage reads to access the deposit account from contract info.
 #[derive(Debug, DecodeAsType)]
 #[decode_as_type(crate_path = "subxt::ext::scale_decode")]
@@ -383,7 +590,10 @@ mod tests {
             types::Metadata,
             DecodeWithMetadata,
         },
-        utils::AccountId32,
+        utils::{
+            AccountId32,
+            H256,
+        },
         PolkadotConfig as DefaultConfig,
     };
 
@@ -547,4 +757,121 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn code_info_decode_works() {
+        #[subxt::subxt(runtime_metadata_path = "src/test_runtime_api/metadata_v15.scale")]
+        mod api_v15 {}
+
+        use api_v15::runtime_types::pallet_contracts::wasm::{
+            CodeInfo as CodeInfoV15,
+            Determinism,
+        };
+
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        let metadata =
+            Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode");
+        let code_info_type_id =
+            get_metadata_type_index("CodeInfo", "pallet_contracts::wasm", &metadata)
+                .expect("the code info type must be present in the metadata");
+
+        let code_info_v15 = CodeInfoV15 {
+            owner: AccountId32([7u8; 32]),
+            deposit: 1_000,
+            refcount: 2,
+            determinism: Determinism::Enforced,
+            code_len: 300,
+        };
+
+        let code_info_thunk = DecodedValueThunk::decode_with_metadata(
+            &mut &*code_info_v15.encode(),
+            code_info_type_id as u32,
+            &metadata.into(),
+        )
+        .expect("the code info must be decoded");
+
+        let code_hash = [3u8; 32];
+        let key = [&[0u8; 32][..], &code_hash].concat();
+        let code_info =
+            CodeInfo::<H256, AccountId32, u128>::from_storage(&key, code_info_thunk)
+                .expect("the code info must be created");
+        assert_eq!(
+            code_info,
+            CodeInfo {
+                code_hash: code_hash.into(),
+                owner: AccountId32([7u8; 32]),
+                deposit: 1_000,
+                refcount: 2,
+                code_len: 300,
+            }
+        );
+        assert!(!code_info.is_removable());
+    }
+
+    #[test]
+    fn parse_contract_account_address_works() {
+        let root_key = [1u8; 32];
+        let account = AccountId32([7u8; 32]);
+
+        let mut key = root_key.to_vec();
+        key.extend_from_slice(&[0u8; 8]);
+        key.extend_from_slice(&account.0);
+        assert_eq!(
+            parse_contract_account_address::<DefaultConfig>(
+                &key,
+                root_key.len(),
+                ContractsPallet::Contracts
+            )
+            .unwrap(),
+            ContractAddress::AccountId(account)
+        );
+
+        let address = H160::repeat_byte(7);
+        let mut key = root_key.to_vec();
+        key.extend_from_slice(address.as_bytes());
+        assert_eq!(
+            parse_contract_account_address::<DefaultConfig>(
+                &key,
+                root_key.len(),
+                ContractsPallet::Revive
+            )
+            .unwrap(),
+            ContractAddress::H160(address)
+        );
+        assert!(parse_contract_account_address::<DefaultConfig>(
+            &key[..key.len() - 1],
+            root_key.len(),
+            ContractsPallet::Revive
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn revive_contract_account_works() {
+        let account =
+            revive_contract_account::<DefaultConfig>(&H160::repeat_byte(7)).unwrap();
+        let mut expected = [0xEE; 32];
+        expected[..20].copy_from_slice(&[7u8; 20]);
+        assert_eq!(account, AccountId32(expected));
+    }
+
+    #[test]
+    fn contract_account_works() {
+        let account_id = AccountId32([7; 32]);
+        assert_eq!(
+            contract_account::<DefaultConfig>(&ContractAddress::AccountId(
+                account_id.clone()
+            ))
+            .unwrap(),
+            account_id
+        );
+        assert_eq!(
+            contract_account::<DefaultConfig>(&ContractAddress::H160(H160::repeat_byte(
+                7
+            )))
+            .unwrap(),
+            revive_contract_account::<DefaultConfig>(&H160::repeat_byte(7)).unwrap()
+        );
+    }
 }
@@ -75,7 +75,9 @@ use subxt::{
 
 use super::{
     fetch_contract_info,
+    rpc_client,
     url_to_string,
+    ContractAddress,
     ContractInfo,
     TrieId,
 };
@@ -87,7 +89,7 @@ pub struct ContractStorage<C: Config, E: Environment> {
 
 impl<C: Config, E: Environment> ContractStorage<C, E>
 where
-    C::AccountId: AsRef<[u8]> + Display + IntoVisitor,
+    C::AccountId: AsRef<[u8]> + Display + IntoVisitor + Encode + Decode,
     C::Hash: IntoVisitor,
     DecodeError: From<<<C::AccountId as IntoVisitor>::Visitor as Visitor>::Error>,
     E::Balance: IntoVisitor + Serialize,
@@ -103,6 +105,17 @@ where
     pub async fn load_contract_storage_data(
         &self,
         contract_account: &C::AccountId,
+    ) -> Result<ContractStorageData> {
+        self.load_contract_storage_data_at(contract_account, None)
+            .await
+    }
+
+    /// Load the raw key/value storage for a given contract at the block with the
+    /// `block_hash`, or at the best block if `None`.
+    pub async fn load_contract_storage_data_at(
+        &self,
+        contract_account: &C::AccountId,
+        block_hash: Option<C::Hash>,
     ) -> Result<ContractStorageData> {
         let contract_info = self.rpc.fetch_contract_info::<E>(contract_account).await?;
         let trie_id = contract_info.trie_id();
@@ -118,11 +131,14 @@ where
                     None,
                     KEYS_COUNT,
                     storage_keys.last().map(|k: &Bytes| k.as_bytes_ref()),
-                    None,
+                    block_hash,
                 )
                 .await?;
             let keys_count = keys.len();
-            let mut values = self.rpc.fetch_storage_entries(trie_id, &keys, None).await?;
+            let mut values = self
+                .rpc
+                .fetch_storage_entries(trie_id, &keys, block_hash)
+                .await?;
             assert_eq!(
                 keys_count,
                 values.len(),
@@ -387,11 +403,8 @@ impl ContractStorageLayout {
         data: ContractStorageData,
         decoder: &ContractMessageTranscoder,
     ) -> Result<Self> {
-        let layout = decoder.metadata().layout();
         let registry = decoder.metadata().registry();
-        let mut path_stack = vec!["root".to_string()];
-        let mut root_key_entries: Vec<RootKeyEntry> = Vec::new();
-        Self::collect_root_key_entries(layout, &mut path_stack, &mut root_key_entries);
+        let root_key_entries = Self::root_key_entries(decoder);
 
         let mut cells = data
             .0
@@ -480,6 +493,18 @@ impl ContractStorageLayout {
         self.cells.iter()
     }
 
+    /// Returns the entries of the root keys of the storage layout of the contract.
+    fn root_key_entries(decoder: &ContractMessageTranscoder) -> Vec<RootKeyEntry> {
+        let mut path_stack = vec!["root".to_string()];
+        let mut root_key_entries: Vec<RootKeyEntry> = Vec::new();
+        Self::collect_root_key_entries(
+            decoder.metadata().layout(),
+            &mut path_stack,
+            &mut root_key_entries,
+        );
+        root_key_entries
+    }
+
     fn decode_to_mapping(
         data: Vec<(Option<Bytes>, Bytes)>,
         key_type_id: u32,
@@ -593,6 +618,176 @@ impl ContractStorageLayout {
     }
 }
 
+/// A storage entry of a contract which differs between two blocks.
+#[derive(Serialize, Debug)]
+pub struct StorageDiffEntry {
+    /// The raw key of the entry.
+    pub key: Bytes,
+    /// The path of the entry in the storage layout, if it could be decoded.
+    pub path: Option<String>,
+    /// The value before, `None` if the entry was added. Values which can't be decoded
+    /// are given as `0x` hex.
+    pub before: Option<String>,
+    /// The value after, `None` if the entry was removed.
+    pub after: Option<String>,
+}
+
+impl StorageDiffEntry {
+    /// Returns whether the entry was `added`, `removed` or `changed`.
+    pub fn change(&self) -> &'static str {
+        match (&self.before, &self.after) {
+            (None, _) => "added",
+            (_, None) => "removed",
+            _ => "changed",
+        }
+    }
+}
+
+/// The entries of the storage of a contract which differ between two blocks.
+#[derive(Serialize, Debug)]
+#[serde(transparent)]
+pub struct ContractStorageDiff(Vec<StorageDiffEntry>);
+
+impl ContractStorageDiff {
+    /// Compare the raw storage of a contract `before` and `after`, decoding the keys and
+    /// values with the storage layout of the `decoder` where possible.
+    pub fn new(
+        before: ContractStorageData,
+        after: ContractStorageData,
+        decoder: Option<&ContractMessageTranscoder>,
+    ) -> Self {
+        let root_key_entries = decoder
+            .map(ContractStorageLayout::root_key_entries)
+            .unwrap_or_default();
+        let decode = |key: &Bytes, value: Option<Bytes>| {
+            let (path, value_type) = decoder
+                .and_then(|decoder| {
+                    Self::decode_key(&root_key_entries, decoder, key).map(
+                        |(path, value_type)| (Some(path), Some((decoder, value_type))),
+                    )
+                })
+                .unwrap_or_default();
+            let value = value.map(|value| {
+                value_type
+                    .and_then(|(decoder, value_type)| {
+                        Self::decode_value(decoder, value_type, &value)
+                    })
+                    .unwrap_or_else(|| format!("0x{}", hex::encode(&value.0)))
+            });
+            (path, value)
+        };
+
+        let mut before = before.0;
+        let mut entries = Vec::new();
+        for (key, after) in after.0 {
+            let before = before.remove(&key);
+            if before.as_ref() == Some(&after) {
+                continue
+            }
+            let (path, before) = decode(&key, before);
+            let (_, after) = decode(&key, Some(after));
+            entries.push(StorageDiffEntry {
+                key,
+                path,
+                before,
+                after,
+            });
+        }
+        for (key, before) in before {
+            let (path, before) = decode(&key, Some(before));
+            entries.push(StorageDiffEntry {
+                key,
+                path,
+                before,
+                after: None,
+            });
+        }
+        entries.sort_by(|a, b| (&a.path, &a.key).cmp(&(&b.path, &b.key)));
+        Self(entries)
+    }
+
+    /// Return the iterator over the changed entries.
+    pub fn iter(&self) -> impl Iterator<Item = &StorageDiffEntry> {
+        self.0.iter()
+    }
+
+    /// Returns `true` if the storage did not change.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the path of the storage entry with the `key` and the type of its value,
+    /// or `None` if the key is not part of the storage layout.
+    fn decode_key(
+        root_key_entries: &[RootKeyEntry],
+        decoder: &ContractMessageTranscoder,
+        key: &Bytes,
+    ) -> Option<(String, DiffValueType)> {
+        if key.0.len() < 20 {
+            return None
+        }
+        let (root_key, mapping_key) = ContractStorageLayout::key_parts(key);
+        let entry = root_key_entries.iter().find(|e| e.root_key == root_key)?;
+        let type_def = decoder.metadata().registry().resolve(entry.type_id)?;
+        let path = entry.path.join("::");
+        let param = |name| ContractStorageLayout::param_type_id(type_def, name);
+        match (type_def.path.to_string().as_str(), mapping_key) {
+            ("ink_storage::lazy::mapping::Mapping", Some(mapping_key)) => {
+                let key = param("K")
+                    .and_then(|key_type| {
+                        decoder
+                            .decode(key_type, &mut mapping_key.as_bytes_ref())
+                            .ok()
+                    })
+                    .map_or_else(
+                        || format!("0x{}", hex::encode(&mapping_key.0)),
+                        |key| key.to_string(),
+                    );
+                Some((format!("{path}[{key}]"), DiffValueType::Type(param("V")?)))
+            }
+            ("ink_storage::lazy::vec::StorageVec", None) => {
+                Some((format!("{path}.len"), DiffValueType::Len))
+            }
+            ("ink_storage::lazy::vec::StorageVec", Some(index)) => {
+                let index = u32::decode(&mut index.as_bytes_ref()).ok()?;
+                Some((format!("{path}[{index}]"), DiffValueType::Type(param("V")?)))
+            }
+            ("ink_storage::lazy::Lazy", None) => {
+                Some((path, DiffValueType::Type(param("V")?)))
+            }
+            (_, None) => Some((path, DiffValueType::Type(entry.type_id))),
+            (_, Some(_)) => None,
+        }
+    }
+
+    /// Decodes a `value` of the `value_type`, `None` if it can't be decoded.
+    fn decode_value(
+        decoder: &ContractMessageTranscoder,
+        value_type: DiffValueType,
+        value: &Bytes,
+    ) -> Option<String> {
+        let mut bytes = value.as_bytes_ref();
+        match value_type {
+            DiffValueType::Len => u32::decode(&mut bytes).ok().map(|len| len.to_string()),
+            DiffValueType::Type(type_id) => {
+                decoder
+                    .decode(type_id, &mut bytes)
+                    .ok()
+                    .map(|value| value.to_string())
+            }
+        }
+    }
+}
+
+/// The type of the value of a storage entry in a [`ContractStorageDiff`].
+#[derive(Clone, Copy)]
+enum DiffValueType {
+    /// The length of a `StorageVec`.
+    Len,
+    /// A value of the type with the id.
+    Type(u32),
+}
+
 /// Methods for querying contracts over RPC.
 pub struct ContractStorageRpc<C: Config> {
     rpc_client: RpcClient,
@@ -608,7 +803,7 @@ where
 {
     /// Create a new instance of the ContractsRpc.
     pub async fn new(url: &url::Url) -> Result<Self> {
-        let rpc_client = RpcClient::from_url(url_to_string(url)).await?;
+        let rpc_client = rpc_client(&url_to_string(url)).await?;
         let client = OnlineClient::from_rpc_client(rpc_client.clone()).await?;
         let rpc_methods = LegacyRpcMethods::new(rpc_client.clone());
 
@@ -625,9 +820,55 @@ where
         contract: &C::AccountId,
     ) -> Result<ContractInfo<C::Hash, E::Balance>>
     where
+        C::AccountId: Encode + Decode,
         E::Balance: IntoVisitor,
     {
-        fetch_contract_info::<C, E>(contract, &self.rpc_methods, &self.client).await
+        let contract = ContractAddress::AccountId(contract.clone());
+        fetch_contract_info::<C, E>(&contract, &self.rpc_methods, &self.client).await
+    }
+
+    /// Search the latest `max_blocks` blocks, from the best block backwards, for the
+    /// extrinsic with the `extrinsic_hash`. Returns the hash of the block including it
+    /// and the hash of its parent.
+    pub async fn find_extrinsic_block(
+        &self,
+        extrinsic_hash: C::Hash,
+        max_blocks: u64,
+    ) -> Result<(C::Hash, C::Hash)> {
+        use subxt::config::Hasher as _;
+
+        let best_block = self
+            .rpc_methods
+            .chain_get_block_hash(None)
+            .await?
+            .ok_or(anyhow!("Best block not found"))?;
+        let best_block_number: u64 =
+            self.client.blocks().at(best_block).await?.number().into();
+        let lowest_block_number = best_block_number.saturating_sub(max_blocks - 1).max(1);
+        for number in (lowest_block_number..=best_block_number).rev() {
+            let Some(hash) = self
+                .rpc_methods
+                .chain_get_block_hash(Some(number.into()))
+                .await?
+            else {
+                continue
+            };
+            let extrinsics = self.client.blocks().at(hash).await?.extrinsics().await?;
+            for extrinsic in extrinsics.iter() {
+                if C::Hasher::hash_of(&extrinsic?.bytes()) == extrinsic_hash {
+                    let parent = self
+                        .rpc_methods
+                        .chain_get_block_hash(Some((number - 1).into()))
+                        .await?
+                        .ok_or(anyhow!("Parent of block {hash:?} not found"))?;
+                    return Ok((hash, parent))
+                }
+            }
+        }
+        Err(anyhow!(
+            "Extrinsic {extrinsic_hash:?} not found in the latest {max_blocks} blocks, \
+             pass the blocks to compare with `--from` and `--to` instead"
+        ))
     }
 
     /// Fetch the contract storage at the given key.
@@ -16,6 +16,7 @@
 
 use crate::contract_storage::{
     ContractStorageData,
+    ContractStorageDiff,
     ContractStorageLayout,
 };
 use contract_transcode::ContractMessageTranscoder;
@@ -236,3 +237,87 @@ fn storage_decode_mapping_type_works() {
     );
     assert_eq!(cell.root_key(), hex::encode(lazy_type_root_encoded));
 }
+
+#[test]
+fn storage_diff_decodes_changed_entries() {
+    let root_key_encoded = Encode::encode(&ROOT_KEY);
+    let lazy_type_root_encoded = Encode::encode(&LAZY_TYPE_ROOT_KEY);
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: Mapping<u8, u8, ManualKey<LAZY_TYPE_ROOT_KEY>>,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let key = Bytes::from([BASE_KEY_RAW.to_vec(), root_key_encoded].concat());
+    let mapping_key = |key: u8| {
+        Bytes::from(
+            [
+                BASE_KEY_RAW.to_vec(),
+                lazy_type_root_encoded.clone(),
+                Encode::encode(&key),
+            ]
+            .concat(),
+        )
+    };
+    let root = encode_storage_value(&Data { a: Mapping::new() });
+
+    let before = ContractStorageData::new(BTreeMap::from([
+        (key.clone(), root.clone()),
+        (mapping_key(1), encode_storage_value(&10u8)),
+        (mapping_key(2), encode_storage_value(&20u8)),
+    ]));
+    let after = ContractStorageData::new(BTreeMap::from([
+        (key, root),
+        (mapping_key(2), encode_storage_value(&21u8)),
+        (mapping_key(3), Bytes::from(Vec::new())),
+    ]));
+
+    let diff = ContractStorageDiff::new(before, after, Some(&decoder));
+    let entries: Vec<_> = diff
+        .iter()
+        .map(|entry| {
+            (
+                entry.change(),
+                entry.path.clone().unwrap(),
+                entry.before.clone(),
+                entry.after.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "removed",
+                "root::Data::a[1]".to_string(),
+                Some("10".to_string()),
+                None
+            ),
+            (
+                "changed",
+                "root::Data::a[2]".to_string(),
+                Some("20".to_string()),
+                Some("21".to_string())
+            ),
+            // the value can't be decoded, so falls back to hex
+            (
+                "added",
+                "root::Data::a[3]".to_string(),
+                None,
+                Some("0x".to_string())
+            ),
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::Serialize;
+
+/// The debug buffer of a dry-run, filled by `ink::env::debug_println!`.
+///
+/// Serialized as the `debug_message` and `debug_message_hex` fields of the dry-run
+/// result it is flattened into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DebugMessage {
+    /// The debug buffer decoded as UTF-8, with invalid sequences replaced.
+    #[serde(rename = "debug_message")]
+    pub message: String,
+    /// The raw debug buffer, only present if it is not valid UTF-8.
+    #[serde(rename = "debug_message_hex", skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+}
+
+impl DebugMessage {
+    /// Returns the debug message of the given buffer, or `None` if it is empty.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() {
+            return None
+        }
+        let (message, hex) = match std::str::from_utf8(bytes) {
+            Ok(message) => (message.to_string(), None),
+            Err(_) => {
+                (
+                    String::from_utf8_lossy(bytes).into_owned(),
+                    Some(format!("0x{}", hex::encode(bytes))),
+                )
+            }
+        };
+        Some(Self { message, hex })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct DryRunResult {
+        reverted: bool,
+        #[serde(flatten)]
+        debug_message: Option<DebugMessage>,
+    }
+
+    fn to_json(bytes: &[u8]) -> String {
+        serde_json::to_string(&DryRunResult {
+            reverted: false,
+            debug_message: DebugMessage::from_bytes(bytes),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn empty_debug_message_is_absent() {
+        assert_eq!(to_json(b""), r#"{"reverted":false}"#);
+    }
+
+    #[test]
+    fn utf8_debug_message() {
+        assert_eq!(
+            to_json(b"hello\n"),
+            r#"{"reverted":false,"debug_message":"hello\n"}"#
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_debug_message_includes_hex() {
+        assert_eq!(
+            to_json(&[0x68, 0x69, 0xff]),
+            r#"{"reverted":false,"debug_message":"hi�","debug_message_hex":"0x6869ff"}"#
+        );
+    }
+}
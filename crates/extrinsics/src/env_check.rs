@@ -9,6 +9,11 @@ use scale_info::{
     Field,
     PortableRegistry,
     TypeDef,
+    TypeDefPrimitive,
+};
+use std::fmt::{
+    Display,
+    Formatter,
 };
 
 use anyhow::{
@@ -77,6 +82,77 @@ pub(crate) fn resolve_type_definition(
     }
 }
 
+/// The width of the `Balance` type of a contract or chain environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceWidth {
+    U64,
+    U128,
+}
+
+impl BalanceWidth {
+    /// Returns the width of the `Balance` type declared in the environment of the
+    /// contract metadata.
+    pub fn from_contract(contract_metadata: &InkProject) -> Result<Self> {
+        let balance_ty = contract_metadata.spec().environment().balance().ty().id;
+        let type_def = resolve_type_definition(contract_metadata.registry(), balance_ty)?;
+        Self::from_type_def(&type_def)
+    }
+
+    /// Returns the width of the given `Balance` type.
+    pub fn of<Balance>() -> Result<Self> {
+        match std::mem::size_of::<Balance>() {
+            8 => Ok(Self::U64),
+            16 => Ok(Self::U128),
+            size => anyhow::bail!("Unsupported {}-bit Balance type", size * 8),
+        }
+    }
+
+    fn from_type_def(type_def: &TypeDef<PortableForm>) -> Result<Self> {
+        match type_def {
+            TypeDef::Primitive(TypeDefPrimitive::U64) => Ok(Self::U64),
+            TypeDef::Primitive(TypeDefPrimitive::U128) => Ok(Self::U128),
+            _ => anyhow::bail!("Unsupported Balance type {:?}", type_def),
+        }
+    }
+
+    /// Returns the number of bits of the `Balance` type.
+    pub fn bits(&self) -> u32 {
+        match self {
+            Self::U64 => 64,
+            Self::U128 => 128,
+        }
+    }
+
+    /// Returns an error if the `Balance` width expected by the contract differs from
+    /// the one of the chain.
+    pub fn check_chain(&self, chain: BalanceWidth) -> Result<()> {
+        if *self != chain {
+            anyhow::bail!("contract expects {self} Balance, chain reports {chain}")
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the `Balance` width declared in the environment of the
+    /// contract metadata differs from the `chain` one. Widths which can't be told are
+    /// not checked, a `Balance` type of another kind is reported when comparing the
+    /// environment types.
+    pub fn check_contract(
+        contract_metadata: &InkProject,
+        chain: Result<BalanceWidth>,
+    ) -> Result<()> {
+        match (Self::from_contract(contract_metadata), chain) {
+            (Ok(contract), Ok(chain)) => contract.check_chain(chain),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for BalanceWidth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-bit", self.bits())
+    }
+}
+
 /// Compares the environment type of the targeted chain against the current contract.
 ///
 /// It is achieved by iterating over the type specifications of `Environment` trait
@@ -96,6 +172,12 @@ pub fn compare_node_env_with_contract(
             continue
         }
         let field_def = resolve_type_definition(node_registry, field.ty.id)?;
+        if &field_name == "balance" {
+            BalanceWidth::check_contract(
+                contract_metadata,
+                BalanceWidth::from_type_def(&field_def),
+            )?;
+        }
         let checked =
             compare_type(&field_name, field_def, contract_metadata, node_registry)?;
         if !checked {
@@ -179,6 +261,7 @@ mod tests {
     use crate::{
         compare_node_env_with_contract,
         env_check::resolve_type_definition,
+        BalanceWidth,
     };
 
     #[derive(Encode, Decode, TypeInfo, serde::Serialize, serde::Deserialize)]
@@ -395,4 +478,86 @@ mod tests {
             "Failed to validate the field: timestamp"
         )
     }
+
+    #[test]
+    fn balance_width_from_contract() {
+        let u64_balance_project = generate_contract_ink_project::<
+            AccountId,
+            u64,
+            BlockNumber,
+            Hash,
+            Timestamp,
+        >();
+        let u128_balance_project = generate_contract_ink_project::<
+            AccountId,
+            Balance,
+            BlockNumber,
+            Hash,
+            Timestamp,
+        >();
+
+        assert_eq!(
+            BalanceWidth::from_contract(&u64_balance_project).unwrap(),
+            BalanceWidth::U64
+        );
+        assert_eq!(
+            BalanceWidth::from_contract(&u128_balance_project).unwrap(),
+            BalanceWidth::U128
+        );
+        assert_eq!(BalanceWidth::of::<u64>().unwrap(), BalanceWidth::U64);
+        assert_eq!(BalanceWidth::of::<u128>().unwrap(), BalanceWidth::U128);
+        assert!(BalanceWidth::of::<u32>().is_err());
+    }
+
+    #[test]
+    fn u64_balance_contract_on_u128_balance_chain_fails() {
+        let ink_project = generate_contract_ink_project::<
+            AccountId,
+            u64,
+            BlockNumber,
+            Hash,
+            Timestamp,
+        >();
+        let contract = BalanceWidth::from_contract(&ink_project).unwrap();
+
+        assert!(contract.check_chain(BalanceWidth::U64).is_ok());
+        assert_eq!(
+            contract
+                .check_chain(BalanceWidth::of::<u128>().unwrap())
+                .unwrap_err()
+                .to_string(),
+            "contract expects 64-bit Balance, chain reports 128-bit"
+        );
+        assert!(
+            BalanceWidth::check_contract(&ink_project, BalanceWidth::of::<u128>())
+                .is_err()
+        );
+        // a chain width which can't be told is not checked
+        assert!(
+            BalanceWidth::check_contract(&ink_project, BalanceWidth::of::<u32>()).is_ok()
+        );
+    }
+
+    #[test]
+    fn contract_and_node_balance_width_mismatch() {
+        let mut registry = Registry::new();
+        registry.register_type(&MetaType::new::<Environment>());
+
+        let portable: PortableRegistry = registry.into();
+
+        let ink_project = generate_contract_ink_project::<
+            AccountId,
+            u64,
+            BlockNumber,
+            Hash,
+            Timestamp,
+        >();
+
+        let result =
+            compare_node_env_with_contract(&portable, &ink_project, &Verbosity::Default);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "contract expects 64-bit Balance, chain reports 128-bit"
+        )
+    }
 }
@@ -14,6 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::{
+    Address,
+    DisplayBalance,
+    IncompatibleChain,
+};
 use sp_runtime::DispatchError;
 use std::fmt::{
     self,
@@ -27,6 +32,14 @@ pub enum ErrorVariant {
     Module(ModuleError),
     #[serde(rename = "generic_error")]
     Generic(GenericError),
+    #[serde(rename = "incompatible_chain_error")]
+    IncompatibleChain(IncompatibleChain),
+    #[serde(rename = "max_fee_exceeded_error")]
+    MaxFeeExceeded(MaxFeeExceeded),
+    #[serde(rename = "assertion_failed_error")]
+    AssertionFailed(AssertionFailed),
+    #[serde(rename = "timeout_error")]
+    Timeout(TransactionTimeout),
 }
 
 impl From<subxt::Error> for ErrorVariant {
@@ -40,6 +53,8 @@ impl From<subxt::Error> for ErrorVariant {
                             pallet: details.pallet.name().to_string(),
                             error: details.variant.name.to_string(),
                             docs: details.variant.docs.clone(),
+                            hint: None,
+                            existing_contract: None,
                         })
                     })
                     .unwrap_or_else(|err| {
@@ -56,7 +71,20 @@ impl From<subxt::Error> for ErrorVariant {
 
 impl From<anyhow::Error> for ErrorVariant {
     fn from(error: anyhow::Error) -> Self {
-        Self::Generic(GenericError::from_message(format!("{error:?}")))
+        let error = match error.downcast::<ErrorVariant>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+        match error.downcast::<IncompatibleChain>() {
+            Ok(error) => Self::IncompatibleChain(error),
+            Err(error) => Self::Generic(GenericError::from_message(format!("{error:?}"))),
+        }
+    }
+}
+
+impl From<AssertionFailed> for ErrorVariant {
+    fn from(error: AssertionFailed) -> Self {
+        Self::AssertionFailed(error)
     }
 }
 
@@ -83,6 +111,89 @@ pub struct ModuleError {
     pub pallet: String,
     pub error: String,
     pub docs: Vec<String>,
+    /// What the user might have done wrong, if it can be told from the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// The address of the contract which caused a `DuplicateContract` error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub existing_contract: Option<Address>,
+}
+
+/// The estimated fee of an extrinsic exceeds the maximum fee the user is willing to
+/// pay, so it was not submitted.
+#[derive(Debug, serde::Serialize)]
+pub struct MaxFeeExceeded {
+    /// The fee estimated by the transaction payment API.
+    pub estimated_fee: DisplayBalance,
+    /// The maximum fee passed by the user.
+    pub max_fee: DisplayBalance,
+}
+
+impl Display for MaxFeeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The estimated fee of {} exceeds the maximum fee of {}, the transaction was \
+             not submitted",
+            self.estimated_fee, self.max_fee
+        )
+    }
+}
+
+/// The result of a dry-run does not match the expectation passed with `--expect` or
+/// `--expect-reverted`.
+#[derive(Debug, serde::Serialize)]
+pub struct AssertionFailed {
+    /// What was expected and what the dry-run returned instead.
+    pub error: String,
+}
+
+/// A submitted extrinsic was not included in a block within the `--timeout`.
+///
+/// The extrinsic may still be included later, so this is not a failure of the
+/// transaction: its `status` is `timeout` and it can be looked up by its hash.
+#[derive(Debug, serde::Serialize)]
+pub struct TransactionTimeout {
+    /// Always `timeout`, the outcome of the transaction is unknown.
+    pub status: &'static str,
+    /// The hash of the submitted extrinsic.
+    pub tx_hash: String,
+    /// The last status of the extrinsic reported by the node, e.g. `broadcasted`.
+    pub last_status: String,
+    /// The number of the first block the extrinsic may be included in.
+    pub from_block: u64,
+    /// How long was waited for the extrinsic to be included, in seconds.
+    pub timeout_secs: u64,
+}
+
+impl TransactionTimeout {
+    pub fn new(
+        tx_hash: String,
+        last_status: String,
+        from_block: u64,
+        timeout: u64,
+    ) -> Self {
+        Self {
+            status: "timeout",
+            tx_hash,
+            last_status,
+            from_block,
+            timeout_secs: timeout,
+        }
+    }
+}
+
+impl Display for TransactionTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Stopped waiting for the transaction {} after {}s, its last status was \
+             `{}`.\nThe transaction may still be included in a block: search the blocks \
+             from #{} on for its hash, e.g. in a block explorer, before submitting it \
+             again.",
+            self.tx_hash, self.timeout_secs, self.last_status, self.from_block
+        )
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -112,6 +223,8 @@ impl ErrorVariant {
                     pallet: pallet.name().to_string(),
                     error: variant.name.to_owned(),
                     docs: variant.docs.to_owned(),
+                    hint: None,
+                    existing_contract: None,
                 }))
             }
             err => {
@@ -123,12 +236,67 @@ impl ErrorVariant {
     }
 }
 
+impl ErrorVariant {
+    /// Attaches a `hint` to a module error, other errors are returned unchanged.
+    pub fn with_hint(self, hint: String) -> Self {
+        match self {
+            ErrorVariant::Module(err) => {
+                ErrorVariant::Module(ModuleError {
+                    hint: Some(hint),
+                    ..err
+                })
+            }
+            err => err,
+        }
+    }
+
+    /// Attaches the address of the `existing_contract` to a module error.
+    pub fn with_existing_contract(self, existing_contract: Address) -> Self {
+        match self {
+            ErrorVariant::Module(err) => {
+                ErrorVariant::Module(ModuleError {
+                    existing_contract: Some(existing_contract),
+                    ..err
+                })
+            }
+            err => err,
+        }
+    }
+
+    /// Returns `true` if this is the module error `error` of the `pallet`.
+    pub fn is_module_error(&self, pallet: &str, error: &str) -> bool {
+        matches!(self, ErrorVariant::Module(err) if err.pallet == pallet && err.error == error)
+    }
+
+    /// Returns the exit code of a command failing with this error: 2 if an assertion
+    /// failed, to tell failed checks apart from failures to run them, 3 if a submitted
+    /// transaction timed out and may still be included, otherwise 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorVariant::AssertionFailed(_) => 2,
+            ErrorVariant::Timeout(_) => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the address of the contract which caused a `DuplicateContract` error, if
+    /// it was looked up.
+    pub fn existing_contract(&self) -> Option<&Address> {
+        match self {
+            ErrorVariant::Module(err) => err.existing_contract.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 impl Debug for ErrorVariant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <Self as Display>::fmt(self, f)
     }
 }
 
+impl std::error::Error for ErrorVariant {}
+
 impl Display for ErrorVariant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -136,9 +304,20 @@ impl Display for ErrorVariant {
                 f.write_fmt(format_args!(
                     "ModuleError: {}::{}: {:?}",
                     err.pallet, err.error, err.docs
-                ))
+                ))?;
+                if let Some(existing_contract) = &err.existing_contract {
+                    write!(f, "\nExisting contract: {existing_contract}")?;
+                }
+                match &err.hint {
+                    Some(hint) => write!(f, "\nHint: {hint}"),
+                    None => Ok(()),
+                }
             }
             ErrorVariant::Generic(err) => write!(f, "{}", err.error),
+            ErrorVariant::IncompatibleChain(err) => write!(f, "{err}"),
+            ErrorVariant::MaxFeeExceeded(err) => write!(f, "{err}"),
+            ErrorVariant::AssertionFailed(err) => write!(f, "{}", err.error),
+            ErrorVariant::Timeout(err) => write!(f, "{err}"),
         }
     }
 }
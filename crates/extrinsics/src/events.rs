@@ -15,33 +15,59 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
-    BalanceVariant,
+    AddressBook,
+    ContractAddress,
+    DisplayBalance,
+    Ss58Prefix,
     TokenMetadata,
 };
 use crate::DEFAULT_KEY_COL_WIDTH;
 use colored::Colorize as _;
 use contract_build::Verbosity;
 use contract_transcode::{
+    mark_indexed_fields,
     ContractMessageTranscoder,
+    DecodedEvent,
     Hex,
+    Transcoder,
     TranscoderBuilder,
     Value,
 };
 
 use anyhow::Result;
 use ink_env::Environment;
-use scale_info::form::PortableForm;
+use scale::{
+    Compact,
+    Decode,
+    Encode,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
+    Variant,
+};
+use sp_weights::Weight;
 use std::{
     fmt::{
         Display,
         Write,
     },
+    path::{
+        Path,
+        PathBuf,
+    },
     str::FromStr,
+    sync::Arc,
 };
 use subxt::{
     self,
     blocks::ExtrinsicEvents,
-    events::StaticEvent,
+    events::{
+        Phase,
+        StaticEvent,
+    },
     ext::{
         scale_decode::{
             self,
@@ -49,6 +75,7 @@ use subxt::{
         },
         scale_encode,
     },
+    utils::H160,
     Config,
 };
 
@@ -123,6 +150,152 @@ where
     const EVENT: &'static str = "CodeStored";
 }
 
+/// An event triggered when `pallet-balances` holds funds of an account, e.g. the
+/// storage deposit of uploaded code.
+///
+/// The `reason` of the hold is not decoded, it is a type of the runtime.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+pub struct BalancesHeld<AccountId, Balance> {
+    pub who: AccountId,
+    pub amount: Balance,
+}
+
+impl<AccountId, Balance> StaticEvent for BalancesHeld<AccountId, Balance>
+where
+    AccountId: IntoVisitor,
+    Balance: IntoVisitor,
+{
+    const PALLET: &'static str = "Balances";
+    const EVENT: &'static str = "Held";
+}
+
+/// An event triggered when `pallet-balances` reserves funds of an account, which
+/// runtimes without holds emit for storage deposits.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+pub struct BalancesReserved<AccountId, Balance> {
+    pub who: AccountId,
+    pub amount: Balance,
+}
+
+impl<AccountId, Balance> StaticEvent for BalancesReserved<AccountId, Balance>
+where
+    AccountId: IntoVisitor,
+    Balance: IntoVisitor,
+{
+    const PALLET: &'static str = "Balances";
+    const EVENT: &'static str = "Reserved";
+}
+
+/// An event triggered when `pallet-contracts` releases a storage deposit, e.g. to
+/// refund the caller for storage a contract freed.
+///
+/// Only the recipient and the amount are decoded, the account the deposit is released
+/// from is the contract.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+pub struct StorageDepositTransferredAndReleased<AccountId, Balance> {
+    pub to: AccountId,
+    pub amount: Balance,
+}
+
+impl<AccountId, Balance> StaticEvent
+    for StorageDepositTransferredAndReleased<AccountId, Balance>
+where
+    AccountId: IntoVisitor,
+    Balance: IntoVisitor,
+{
+    const PALLET: &'static str = "Contracts";
+    const EVENT: &'static str = "StorageDepositTransferredAndReleased";
+}
+
+/// An event triggered by `frame-system` for every successful extrinsic.
+///
+/// Only the weight of the `dispatch_info` is decoded, which is the weight the extrinsic
+/// actually consumed.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct ExtrinsicSuccess {
+    dispatch_info: DispatchInfo,
+}
+
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct DispatchInfo {
+    weight: DispatchWeight,
+}
+
+/// A two-dimensional weight, `sp_weights::Weight` doesn't implement `DecodeAsType`.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct DispatchWeight {
+    ref_time: u64,
+    proof_size: u64,
+}
+
+impl StaticEvent for ExtrinsicSuccess {
+    const PALLET: &'static str = "System";
+    const EVENT: &'static str = "ExtrinsicSuccess";
+}
+
+/// An event triggered by `frame-system` for every failed extrinsic, which reports the
+/// consumed weight as well.
+#[derive(Debug, scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct ExtrinsicFailed {
+    dispatch_info: DispatchInfo,
+}
+
+impl StaticEvent for ExtrinsicFailed {
+    const PALLET: &'static str = "System";
+    const EVENT: &'static str = "ExtrinsicFailed";
+}
+
+/// Returns the weight the extrinsic actually consumed, including its base weight, from
+/// its `System::ExtrinsicSuccess` or `System::ExtrinsicFailed` event.
+///
+/// `None` if the extrinsic emitted neither event, or if the chain doesn't report the
+/// weight as a two-dimensional weight.
+pub fn weight_used<C: Config>(events: &ExtrinsicEvents<C>) -> Option<Weight> {
+    let dispatch_info = match events.find_first::<ExtrinsicSuccess>().ok().flatten() {
+        Some(success) => success.dispatch_info,
+        None => {
+            events
+                .find_first::<ExtrinsicFailed>()
+                .ok()
+                .flatten()?
+                .dispatch_info
+        }
+    };
+    let weight = dispatch_info.weight;
+    Some(Weight::from_parts(weight.ref_time, weight.proof_size))
+}
+
+/// Returns the sum of the storage deposits `pallet-contracts` released to the account
+/// `who` in the `events`, `None` if it released none.
+pub fn deposit_refunded<C: Config, E: Environment>(
+    events: &ExtrinsicEvents<C>,
+    who: &C::AccountId,
+) -> Result<Option<E::Balance>>
+where
+    C::AccountId: IntoVisitor + PartialEq,
+    E::Balance: IntoVisitor,
+{
+    let mut refunded = None;
+    for released in
+        events.find::<StorageDepositTransferredAndReleased<C::AccountId, E::Balance>>()
+    {
+        let released = released?;
+        if &released.to == who {
+            refunded = Some(
+                refunded.map_or(released.amount, |refunded| refunded + released.amount),
+            );
+        }
+    }
+    Ok(refunded)
+}
+
 /// An event triggered by the `remove_code` call.
 #[derive(
     Debug,
@@ -149,6 +322,84 @@ where
     const EVENT: &'static str = "CodeRemoved";
 }
 
+/// A custom event emitted by a `pallet-revive` contract.
+#[derive(
+    scale::Decode,
+    scale::Encode,
+    scale_decode::DecodeAsType,
+    scale_encode::EncodeAsType,
+    Debug,
+)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub struct ReviveContractEmitted<Hash> {
+    pub contract: H160,
+    pub data: Vec<u8>,
+    pub topics: Vec<Hash>,
+}
+
+impl<Hash> StaticEvent for ReviveContractEmitted<Hash>
+where
+    Hash: IntoVisitor,
+{
+    const PALLET: &'static str = "Revive";
+    const EVENT: &'static str = "ContractEmitted";
+}
+
+/// A `pallet-revive` contract was successfully instantiated.
+#[derive(
+    Debug,
+    scale::Decode,
+    scale::Encode,
+    scale_decode::DecodeAsType,
+    scale_encode::EncodeAsType,
+)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub struct ReviveContractInstantiated {
+    /// Address of the deployer.
+    pub deployer: H160,
+    /// Address where the contract was instantiated to.
+    pub contract: H160,
+}
+
+impl StaticEvent for ReviveContractInstantiated {
+    const PALLET: &'static str = "Revive";
+    const EVENT: &'static str = "Instantiated";
+}
+
+/// An event triggered by either the `instantiate_with_code` or the `upload_code` call
+/// of `pallet-revive`.
+#[derive(
+    Debug,
+    scale::Decode,
+    scale::Encode,
+    scale_decode::DecodeAsType,
+    scale_encode::EncodeAsType,
+)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub struct ReviveCodeStored<Hash> {
+    /// Hash under which the contract code was stored.
+    pub code_hash: Hash,
+}
+
+impl<Hash> StaticEvent for ReviveCodeStored<Hash>
+where
+    Hash: IntoVisitor,
+{
+    const PALLET: &'static str = "Revive";
+    const EVENT: &'static str = "CodeStored";
+}
+
+impl<Hash> From<ReviveCodeStored<Hash>> for CodeStored<Hash> {
+    fn from(code_stored: ReviveCodeStored<Hash>) -> Self {
+        Self {
+            code_hash: code_stored.code_hash,
+        }
+    }
+}
+
 /// Field that represent data of an event from invoking a contract extrinsic.
 #[derive(serde::Serialize)]
 pub struct Field {
@@ -156,9 +407,22 @@ pub struct Field {
     pub name: String,
     /// value of a field
     pub value: Value,
+    /// The value of a balance field denominated with the token of the chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denominated: Option<String>,
     /// The name of a type as defined in the pallet Source Code
     #[serde(skip_serializing)]
     pub type_name: Option<String>,
+    /// Whether the field is a balance of the chain
+    #[serde(skip_serializing)]
+    pub is_balance: bool,
+    /// The labels of the fields of a decoded contract event which are indexed in the
+    /// topics of the event
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub indexed: Vec<String>,
+    /// The alias of the account id of the field in the address book
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
 }
 
 impl Field {
@@ -166,7 +430,22 @@ impl Field {
         Field {
             name,
             value,
+            denominated: None,
             type_name,
+            is_balance: false,
+            indexed: Vec::new(),
+            alias: None,
+        }
+    }
+
+    /// Returns the value denominated with the token of the chain if the field is a
+    /// balance, otherwise `None`.
+    fn denominate(&self, token_metadata: &TokenMetadata) -> Option<DisplayBalance> {
+        match self.value {
+            Value::UInt(balance) if self.is_balance => {
+                Some(DisplayBalance::new(balance, Some(token_metadata)))
+            }
+            _ => None,
         }
     }
 }
@@ -180,104 +459,288 @@ pub struct Event {
     pub name: String,
     /// data associated with the event
     pub fields: Vec<Field>,
+    /// The topics of a contract event as hex, by which its indexed fields can be
+    /// verified
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    /// The SCALE encoded fields of the event as hex, only included with
+    /// [`DisplayEvents::include_raw`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    /// The undecoded data of a contract event as hex, only included with
+    /// [`DisplayEvents::include_raw`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_data: Option<String>,
+    /// Why the fields of the event could not be decoded, in which case `fields` is
+    /// empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
+    /// The path of the additional metadata the data of a contract event was decoded
+    /// with, if not with the metadata of the called contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
 }
 
 /// Events produced from invoking a contract extrinsic.
 #[derive(serde::Serialize)]
 pub struct Events(Vec<Event>);
 
+/// Metadata of another contract than the called one, to decode the events of contracts
+/// called by it.
+#[derive(Clone)]
+pub struct AdditionalMetadata {
+    /// The path of the metadata file.
+    path: PathBuf,
+    /// The SCALE encoded address of the contract the metadata is bound to.
+    address: Option<Vec<u8>>,
+    transcoder: Arc<ContractMessageTranscoder>,
+}
+
+impl AdditionalMetadata {
+    /// Creates the additional metadata loaded from `path`, which is only used for the
+    /// events emitted by the contract at `address` if given.
+    pub fn new<AccountId: Encode>(
+        path: PathBuf,
+        transcoder: ContractMessageTranscoder,
+        address: Option<&ContractAddress<AccountId>>,
+    ) -> Self {
+        let address = address.map(|address| {
+            match address {
+                ContractAddress::AccountId(account_id) => account_id.encode(),
+                ContractAddress::H160(address) => address.encode(),
+            }
+        });
+        Self {
+            path,
+            address,
+            transcoder: Arc::new(transcoder),
+        }
+    }
+
+    /// Returns the path of the metadata file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Decodes the data of a contract event matched by its `topics`, emitted by the
+/// contract with the SCALE encoded address `emitter`.
+///
+/// The additional metadata bound to the emitter is tried first, so that it is preferred
+/// over other metadata which decodes the event as well. Then the `transcoder` of the
+/// called contract is tried, and finally the additional metadata which isn't bound to a
+/// contract. Returns the decoded event with the path of the additional metadata which
+/// decoded it, or `None` if the event can't be decoded.
+pub(crate) fn decode_contract_event<'m, Hash: AsRef<[u8]>>(
+    transcoder: Option<&ContractMessageTranscoder>,
+    additional_metadata: &'m [AdditionalMetadata],
+    emitter: Option<&[u8]>,
+    topics: &[Hash],
+    data: &mut &[u8],
+) -> Option<(DecodedEvent, Option<&'m Path>)> {
+    let bound = additional_metadata.iter().filter(|metadata| {
+        metadata.address.is_some() && metadata.address.as_deref() == emitter
+    });
+    let unbound = additional_metadata
+        .iter()
+        .filter(|metadata| metadata.address.is_none());
+    let candidates = bound
+        .map(|metadata| (&*metadata.transcoder, Some(metadata.path())))
+        .chain(transcoder.map(|transcoder| (transcoder, None)))
+        .chain(unbound.map(|metadata| (&*metadata.transcoder, Some(metadata.path()))));
+    for (transcoder, path) in candidates {
+        let mut input = *data;
+        match transcoder.decode_contract_event_with_topics(topics, &mut input) {
+            Ok(event) => {
+                *data = input;
+                return Some((event, path))
+            }
+            Err(err) => tracing::debug!("Decoding contract event failed: {err:?}"),
+        }
+    }
+    None
+}
+
+/// The number of characters of a long value displayed in compact event output.
+const COMPACT_VALUE_LEN: usize = 8;
+
+/// How events are displayed in human readable output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventDisplay {
+    /// One line per event, with long values such as account ids and hashes elided.
+    Compact,
+    /// One line per field of each event, expanding nested structures of the fields up
+    /// to the given depth, or fully if `None`.
+    Full(Option<usize>),
+}
+
+impl Default for EventDisplay {
+    fn default() -> Self {
+        EventDisplay::Full(None)
+    }
+}
+
 /// Displays events produced from invoking a contract extrinsic.
 #[derive(serde::Serialize)]
 pub struct DisplayEvents(Vec<Event>);
 
+impl From<Vec<Event>> for DisplayEvents {
+    fn from(events: Vec<Event>) -> Self {
+        DisplayEvents(events)
+    }
+}
+
 impl DisplayEvents {
     /// Parses events and returns an object which can be serialised
+    ///
+    /// Account ids contained in the event fields are displayed with the given
+    /// `ss58_prefix`, unless `raw_values` is set in which case account ids, hashes and
+    /// weights are displayed in their structural representation.
+    ///
+    /// Contract events which can't be decoded with the `transcoder` of the called
+    /// contract are decoded with the `additional_metadata`, e.g. the events of contracts
+    /// called by it.
+    ///
+    /// With `include_raw` the SCALE encoded fields and contract event data are included
+    /// as hex, and events whose fields can't be decoded are included with the error
+    /// instead of failing.
+    ///
+    /// Balance fields of runtime events are additionally denominated with the
+    /// `token_metadata`, if given.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_events<C: Config, E: Environment>(
         result: &ExtrinsicEvents<C>,
         transcoder: Option<&ContractMessageTranscoder>,
+        additional_metadata: &[AdditionalMetadata],
         subxt_metadata: &subxt::Metadata,
+        ss58_prefix: Ss58Prefix,
+        raw_values: bool,
+        include_raw: bool,
+        token_metadata: Option<&TokenMetadata>,
     ) -> Result<DisplayEvents>
     where
         C::AccountId: IntoVisitor,
+        C::Hash: IntoVisitor,
     {
+        let decoder = EventDecoder::new(
+            transcoder,
+            additional_metadata,
+            subxt_metadata,
+            ss58_prefix,
+            raw_values,
+            token_metadata,
+        );
         let mut events: Vec<Event> = vec![];
-
-        let events_transcoder = TranscoderBuilder::new(subxt_metadata.types())
-            .with_default_custom_type_transcoders()
-            .done();
-
         for event in result.iter() {
             let event = event?;
-            tracing::debug!(
-                "displaying event {}:{}",
+            let event_metadata = event.event_metadata();
+            events.push(decoder.decode::<C>(
                 event.pallet_name(),
-                event.variant_name()
-            );
+                event_metadata.variant,
+                event.field_bytes(),
+                event.topics(),
+            ));
+        }
+        DisplayEvents(events).include_raw(include_raw)
+    }
 
-            let event_metadata = event.event_metadata();
-            let event_fields = &event_metadata.variant.fields;
-
-            let mut event_entry = Event {
-                pallet: event.pallet_name().to_string(),
-                name: event.variant_name().to_string(),
-                fields: vec![],
-            };
-
-            let event_data = &mut event.field_bytes();
-            let event_sig_topic = event.topics().iter().next();
-            let mut unnamed_field_name = 0;
-            for field_metadata in event_fields {
-                if <ContractEmitted<C::AccountId> as StaticEvent>::is_event(
-                    event.pallet_name(),
-                    event.variant_name(),
-                ) && field_metadata.name == Some("data".to_string())
-                {
-                    tracing::debug!("event data: {:?}", hex::encode(&event_data));
-                    let field = contract_event_data_field::<C>(
-                        transcoder,
-                        field_metadata,
-                        event_sig_topic,
-                        event_data,
-                    )?;
-                    event_entry.fields.push(field);
-                } else {
-                    let field_name = field_metadata
-                        .name
-                        .as_ref()
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| {
-                            let name = unnamed_field_name.to_string();
-                            unnamed_field_name += 1;
-                            name
-                        });
-
-                    let decoded_field = events_transcoder.decode(
-                        subxt_metadata.types(),
-                        field_metadata.ty.id,
-                        event_data,
-                    )?;
-                    let field = Field::new(
-                        field_name,
-                        decoded_field,
-                        field_metadata.type_name.as_ref().map(|s| s.to_string()),
-                    );
-                    event_entry.fields.push(field);
-                }
-            }
-            events.push(event_entry);
+    /// Parses the event records emitted during a dry-run and returns an object which
+    /// can be serialised.
+    ///
+    /// See [`Self::from_events`] for the meaning of the other arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_event_records<C: Config, E: Environment>(
+        records: &[EventRecord<C::Hash>],
+        transcoder: Option<&ContractMessageTranscoder>,
+        additional_metadata: &[AdditionalMetadata],
+        subxt_metadata: &subxt::Metadata,
+        ss58_prefix: Ss58Prefix,
+        raw_values: bool,
+        include_raw: bool,
+        token_metadata: Option<&TokenMetadata>,
+    ) -> Result<DisplayEvents>
+    where
+        C::AccountId: IntoVisitor,
+        C::Hash: IntoVisitor,
+    {
+        let decoder = EventDecoder::new(
+            transcoder,
+            additional_metadata,
+            subxt_metadata,
+            ss58_prefix,
+            raw_values,
+            token_metadata,
+        );
+        let mut events: Vec<Event> = vec![];
+        for record in records {
+            let variant = subxt_metadata
+                .pallet_by_name_err(&record.pallet)?
+                .event_variant_by_index(record.variant_index)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Event variant {} of pallet {} not found in the metadata",
+                        record.variant_index,
+                        record.pallet
+                    )
+                })?;
+            events.push(decoder.decode::<C>(
+                &record.pallet,
+                variant,
+                &record.field_bytes,
+                &record.topics,
+            ));
         }
+        DisplayEvents(events).include_raw(include_raw)
+    }
 
+    /// Keeps the raw SCALE bytes of the events if `include_raw` is set, otherwise
+    /// removes them and fails with the first event which could not be decoded.
+    fn include_raw(self, include_raw: bool) -> Result<Self> {
+        if include_raw {
+            return Ok(self)
+        }
+        let mut events = self.0;
+        for event in &mut events {
+            if let Some(error) = &event.decode_error {
+                anyhow::bail!(
+                    "Failed to decode the event {}::{}: {error}",
+                    event.pallet,
+                    event.name
+                )
+            }
+            event.raw = None;
+            event.raw_data = None;
+        }
         Ok(DisplayEvents(events))
     }
 
-    /// Displays events in a human readable format
+    /// Resolves the account ids of the event fields to their aliases in the
+    /// `address_book`.
+    pub fn with_aliases(mut self, address_book: &AddressBook) -> Self {
+        if address_book.is_empty() {
+            return self;
+        }
+        for field in self.0.iter_mut().flat_map(|event| event.fields.iter_mut()) {
+            field.alias = address_book.alias(&field.value).map(ToString::to_string);
+        }
+        self
+    }
+
+    /// Returns `true` if there are no events.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Displays events in a human readable format, the fields of the events are only
+    /// displayed if the `verbosity` is verbose.
     pub fn display_events<E: Environment>(
         &self,
         verbosity: Verbosity,
+        event_display: EventDisplay,
         token_metadata: &TokenMetadata,
     ) -> Result<String>
     where
-        E::Balance: Display + From<u128>,
+        E::Balance: Display + TryFrom<u128>,
     {
         let event_field_indent: usize = DEFAULT_KEY_COL_WIDTH - 3;
         let mut out = format!(
@@ -285,6 +748,21 @@ impl DisplayEvents {
             "Events".bright_purple().bold(),
             width = DEFAULT_KEY_COL_WIDTH
         );
+        let depth = match event_display {
+            EventDisplay::Compact => {
+                for event in &self.0 {
+                    let _ = writeln!(
+                        out,
+                        "{:>width$} {}",
+                        "Event".bright_green().bold(),
+                        display_compact(event, verbosity, token_metadata),
+                        width = DEFAULT_KEY_COL_WIDTH
+                    );
+                }
+                return Ok(out)
+            }
+            EventDisplay::Full(depth) => depth,
+        };
         for event in &self.0 {
             let _ = writeln!(
                 out,
@@ -295,19 +773,40 @@ impl DisplayEvents {
                 width = DEFAULT_KEY_COL_WIDTH
             );
 
+            if let Some(metadata) = &event.metadata {
+                let _ = writeln!(
+                    out,
+                    "{:width$}{} {}",
+                    "",
+                    "Decoded with metadata".bright_white(),
+                    metadata,
+                    width = event_field_indent,
+                );
+            }
+            if let Some(error) = &event.decode_error {
+                let _ = writeln!(
+                    out,
+                    "{:width$}{} {}",
+                    "",
+                    "Failed to decode the event:".yellow(),
+                    error,
+                    width = event_field_indent,
+                );
+            }
             for field in &event.fields {
                 if verbosity.is_verbose() {
-                    let mut value: String = field.value.to_string();
-                    if field.type_name == Some("T::Balance".to_string())
-                        || field.type_name == Some("BalanceOf<T>".to_string())
-                    {
-                        if let Value::UInt(balance) = field.value {
-                            value = BalanceVariant::<E::Balance>::from(
-                                balance,
-                                Some(token_metadata),
-                            )?
-                            .to_string();
+                    let mut value = match field.denominate(token_metadata) {
+                        Some(balance) => balance.to_string(),
+                        None => {
+                            let value = mark_indexed_fields(&field.value, &field.indexed);
+                            match depth {
+                                Some(depth) => value.display_depth(depth).to_string(),
+                                None => value.to_string(),
+                            }
                         }
+                    };
+                    if let Some(alias) = &field.alias {
+                        let _ = write!(value, " ({alias})");
                     }
                     let _ = writeln!(
                         out,
@@ -329,36 +828,819 @@ impl DisplayEvents {
     }
 }
 
+/// Displays the `event` on a single line: `Pallet::Event → { field: value, .. }`, or
+/// the decoded contract event in place of the fields of a contract event.
+///
+/// Long values are elided, balances are denominated and account ids with an alias in
+/// the address book are displayed as the alias.
+fn display_compact(
+    event: &Event,
+    verbosity: Verbosity,
+    token_metadata: &TokenMetadata,
+) -> String {
+    let mut out = format!(
+        "{}::{}",
+        event.pallet.bright_white(),
+        event.name.bright_white().bold()
+    );
+    if let Some(error) = &event.decode_error {
+        let _ = write!(out, " → {} {error}", "Failed to decode:".yellow());
+        return out
+    }
+    if !verbosity.is_verbose() || event.fields.is_empty() {
+        return out
+    }
+    let compact_value = |field: &Field| {
+        match (field.denominate(token_metadata), &field.alias) {
+            (Some(balance), _) => {
+                balance
+                    .denominated()
+                    .map_or_else(|| balance.raw().to_string(), str::to_string)
+            }
+            (None, Some(alias)) => alias.clone(),
+            (None, None) => field.value.display_elided(COMPACT_VALUE_LEN).to_string(),
+        }
+    };
+    let contract_event = event.fields.iter().find(|field| {
+        field.name == "data"
+            && matches!(&field.value, Value::Map(map) if map.ident().is_some())
+    });
+    let fields = match contract_event {
+        Some(data) => compact_value(data),
+        None => {
+            let fields = event
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, compact_value(field)))
+                .collect::<Vec<_>>();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    };
+    let _ = write!(out, " → {fields}");
+    out
+}
+
+/// A runtime event record emitted during a dry-run.
+///
+/// Runtimes which collect the events of a dry-run return them SCALE encoded as part of
+/// the dry-run result. They can only be decoded with the runtime metadata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventRecord<Hash> {
+    /// The phase of the block in which the event was emitted.
+    pub phase: Phase,
+    /// The name of the pallet which emitted the event.
+    pub pallet: String,
+    /// The index of the event variant within the events of the pallet.
+    pub variant_index: u8,
+    /// The name of the event.
+    pub variant: String,
+    /// The SCALE encoded fields of the event.
+    pub field_bytes: Vec<u8>,
+    /// The topics of the event.
+    pub topics: Vec<Hash>,
+}
+
+impl<Hash: Decode> EventRecord<Hash> {
+    /// Decodes a SCALE encoded `Vec<EventRecord>` with the runtime metadata.
+    pub fn decode_all(
+        input: &mut &[u8],
+        metadata: &subxt::Metadata,
+    ) -> Result<Vec<Self>> {
+        let Compact(len) = Compact::<u32>::decode(input)?;
+        (0..len)
+            .map(|_| Self::decode_with_metadata(input, metadata))
+            .collect()
+    }
+
+    /// Decodes a SCALE encoded `frame_system::EventRecord` with the runtime metadata.
+    pub fn decode_with_metadata(
+        input: &mut &[u8],
+        metadata: &subxt::Metadata,
+    ) -> Result<Self> {
+        let phase = Phase::decode(input)?;
+        let pallet_index = u8::decode(input)?;
+        let variant_index = u8::decode(input)?;
+        let pallet = metadata.pallet_by_index_err(pallet_index)?;
+        let variant = pallet
+            .event_variant_by_index(variant_index)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Event variant {variant_index} of pallet {} not found in the metadata",
+                    pallet.name()
+                )
+            })?;
+
+        // The length of the fields is only known after skipping over them.
+        let fields_start = *input;
+        for field in &variant.fields {
+            scale_decode::visitor::decode_with_visitor(
+                input,
+                field.ty.id,
+                metadata.types(),
+                scale_decode::visitor::IgnoreVisitor,
+            )
+            .map_err(scale_decode::Error::from)?;
+        }
+        let field_bytes = fields_start[..fields_start.len() - input.len()].to_vec();
+        let topics = Vec::<Hash>::decode(input)?;
+
+        Ok(Self {
+            phase,
+            pallet: pallet.name().to_string(),
+            variant_index,
+            variant: variant.name.clone(),
+            field_bytes,
+            topics,
+        })
+    }
+}
+
+/// Decodes the fields of runtime events for display.
+struct EventDecoder<'a> {
+    transcoder: Option<&'a ContractMessageTranscoder>,
+    additional_metadata: &'a [AdditionalMetadata],
+    events_transcoder: Transcoder,
+    subxt_metadata: &'a subxt::Metadata,
+    token_metadata: Option<&'a TokenMetadata>,
+}
+
+impl<'a> EventDecoder<'a> {
+    fn new(
+        transcoder: Option<&'a ContractMessageTranscoder>,
+        additional_metadata: &'a [AdditionalMetadata],
+        subxt_metadata: &'a subxt::Metadata,
+        ss58_prefix: Ss58Prefix,
+        raw_values: bool,
+        token_metadata: Option<&'a TokenMetadata>,
+    ) -> Self {
+        let mut events_transcoder = TranscoderBuilder::new(subxt_metadata.types());
+        if !raw_values {
+            events_transcoder = events_transcoder
+                .with_ss58_prefix_custom_type_transcoders(ss58_prefix.value());
+        }
+        Self {
+            transcoder,
+            additional_metadata,
+            events_transcoder: events_transcoder.done(),
+            subxt_metadata,
+            token_metadata,
+        }
+    }
+
+    /// Decodes an event of `pallet`, keeping its raw SCALE bytes. An event whose fields
+    /// can't be decoded is returned with the error instead of the fields.
+    fn decode<C: Config>(
+        &self,
+        pallet: &str,
+        variant: &Variant<PortableForm>,
+        field_bytes: &[u8],
+        topics: &[C::Hash],
+    ) -> Event
+    where
+        C::AccountId: IntoVisitor,
+        C::Hash: IntoVisitor,
+    {
+        let mut event = self
+            .decode_fields::<C>(pallet, variant, field_bytes, topics)
+            .unwrap_or_else(|err| {
+                Event {
+                    pallet: pallet.to_string(),
+                    name: variant.name.clone(),
+                    fields: Vec::new(),
+                    topics: Vec::new(),
+                    raw: None,
+                    raw_data: None,
+                    decode_error: Some(format!("{err:#}")),
+                    metadata: None,
+                }
+            });
+        event.raw = Some(format!("0x{}", hex::encode(field_bytes)));
+        if is_contract_event::<C>(pallet, &variant.name) {
+            event.raw_data =
+                contract_event_data(self.subxt_metadata, variant, field_bytes)
+                    .map(|data| format!("0x{}", hex::encode(data)));
+            if event.topics.is_empty() {
+                event.topics = topics
+                    .iter()
+                    .map(|topic| format!("0x{}", hex::encode(topic)))
+                    .collect();
+            }
+        }
+        event
+    }
+
+    /// Decodes the fields of an event of `pallet`, decoding the data of contract
+    /// events with the contract transcoder if available.
+    fn decode_fields<C: Config>(
+        &self,
+        pallet: &str,
+        variant: &Variant<PortableForm>,
+        field_bytes: &[u8],
+        topics: &[C::Hash],
+    ) -> Result<Event>
+    where
+        C::AccountId: IntoVisitor,
+        C::Hash: IntoVisitor,
+    {
+        tracing::debug!("displaying event {}:{}", pallet, variant.name);
+
+        let mut event_entry = Event {
+            pallet: pallet.to_string(),
+            name: variant.name.clone(),
+            fields: vec![],
+            topics: vec![],
+            raw: None,
+            raw_data: None,
+            decode_error: None,
+            metadata: None,
+        };
+
+        let event_data = &mut &field_bytes[..];
+        // `pallet-revive` does not index contract events, their topics are part of
+        // the event fields instead.
+        let revive_event = if <ReviveContractEmitted<C::Hash> as StaticEvent>::is_event(
+            pallet,
+            &variant.name,
+        ) {
+            Some(ReviveContractEmitted::<C::Hash>::decode(
+                &mut &field_bytes[..],
+            )?)
+        } else {
+            None
+        };
+        let is_contract_event = revive_event.is_some()
+            || <ContractEmitted<C::AccountId> as StaticEvent>::is_event(
+                pallet,
+                &variant.name,
+            );
+        let revive_topics = revive_event.map(|e| e.topics).unwrap_or_default();
+        let contract_topics = if topics.is_empty() {
+            &revive_topics[..]
+        } else {
+            topics
+        };
+        if is_contract_event {
+            event_entry.topics = topics
+                .iter()
+                .map(|topic| format!("0x{}", hex::encode(topic)))
+                .collect();
+        }
+        let emitter = is_contract_event
+            .then(|| {
+                event_field_bytes(self.subxt_metadata, variant, field_bytes, "contract")
+            })
+            .flatten();
+        let mut unnamed_field_name = 0;
+        for field_metadata in &variant.fields {
+            if is_contract_event && field_metadata.name == Some("data".to_string()) {
+                tracing::debug!("event data: {:?}", hex::encode(&event_data));
+                let (field, metadata) = contract_event_data_field::<C>(
+                    self.transcoder,
+                    self.additional_metadata,
+                    emitter,
+                    field_metadata,
+                    contract_topics,
+                    event_data,
+                )?;
+                event_entry.fields.push(field);
+                event_entry.metadata = metadata.map(|path| path.display().to_string());
+            } else {
+                let field_name = field_metadata
+                    .name
+                    .as_ref()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| {
+                        let name = unnamed_field_name.to_string();
+                        unnamed_field_name += 1;
+                        name
+                    });
+
+                let decoded_field = self.events_transcoder.decode(
+                    self.subxt_metadata.types(),
+                    field_metadata.ty.id,
+                    event_data,
+                )?;
+                let mut field = Field::new(
+                    field_name,
+                    decoded_field,
+                    field_metadata.type_name.as_ref().map(|s| s.to_string()),
+                );
+                field.is_balance =
+                    is_balance_field(field_metadata, self.subxt_metadata.types());
+                if let Some(token_metadata) = self.token_metadata {
+                    field.denominated = field
+                        .denominate(token_metadata)
+                        .and_then(|balance| balance.denominated().map(str::to_string));
+                }
+                event_entry.fields.push(field);
+            }
+        }
+        Ok(event_entry)
+    }
+}
+
+/// Returns whether the event is emitted by a contract of `pallet-contracts` or
+/// `pallet-revive`.
+fn is_contract_event<C: Config>(pallet: &str, variant: &str) -> bool
+where
+    C::AccountId: IntoVisitor,
+    C::Hash: IntoVisitor,
+{
+    <ContractEmitted<C::AccountId> as StaticEvent>::is_event(pallet, variant)
+        || <ReviveContractEmitted<C::Hash> as StaticEvent>::is_event(pallet, variant)
+}
+
+/// Returns the undecoded `data` of a contract event.
+fn contract_event_data(
+    metadata: &subxt::Metadata,
+    variant: &Variant<PortableForm>,
+    field_bytes: &[u8],
+) -> Option<Vec<u8>> {
+    let data = event_field_bytes(metadata, variant, field_bytes, "data")?;
+    Vec::<u8>::decode(&mut &data[..]).ok()
+}
+
+/// Returns the SCALE encoded field `name` of an event, skipping over the fields before
+/// it with the type information of the metadata.
+fn event_field_bytes<'b>(
+    metadata: &subxt::Metadata,
+    variant: &Variant<PortableForm>,
+    field_bytes: &'b [u8],
+    name: &str,
+) -> Option<&'b [u8]> {
+    let input = &mut &field_bytes[..];
+    for field in &variant.fields {
+        let field_start = *input;
+        scale_decode::visitor::decode_with_visitor(
+            input,
+            field.ty.id,
+            metadata.types(),
+            scale_decode::visitor::IgnoreVisitor,
+        )
+        .ok()?;
+        if field.name.as_deref() == Some(name) {
+            return Some(&field_start[..field_start.len() - input.len()])
+        }
+    }
+    None
+}
+
+/// Returns whether the field of a runtime event is a balance of the chain.
+///
+/// The balance type of a chain is a type alias of an unsigned integer, which is not
+/// preserved in the type information of the metadata. Hence balances are detected by
+/// the type name of the field, e.g. `T::Balance` or `BalanceOf<T, I>`, which must
+/// resolve to an unsigned integer or its compact encoding.
+fn is_balance_field(
+    field_metadata: &scale_info::Field<PortableForm>,
+    types: &PortableRegistry,
+) -> bool {
+    let Some(type_name) = field_metadata.type_name.as_deref() else {
+        return false
+    };
+    let name = type_name
+        .split('<')
+        .next()
+        .and_then(|path| path.rsplit("::").next())
+        .unwrap_or_default();
+    let name = if name.is_empty() {
+        // a qualified path like `<T as Config>::Balance`
+        type_name.rsplit("::").next().unwrap_or_default()
+    } else {
+        name
+    };
+    matches!(name, "Balance" | "BalanceOf")
+        && is_unsigned_integer(field_metadata.ty.id, types)
+}
+
+/// Returns whether the type is an unsigned integer which can hold a balance, or its
+/// compact encoding.
+fn is_unsigned_integer(type_id: u32, types: &PortableRegistry) -> bool {
+    match types.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Primitive(
+            TypeDefPrimitive::U32 | TypeDefPrimitive::U64 | TypeDefPrimitive::U128,
+        )) => true,
+        Some(TypeDef::Compact(compact)) => {
+            is_unsigned_integer(compact.type_param.id, types)
+        }
+        _ => false,
+    }
+}
+
 /// Construct the contract event data field, attempting to decode the event using the
-/// [`ContractMessageTranscoder`] if available.
-fn contract_event_data_field<C: Config>(
+/// [`ContractMessageTranscoder`] or the additional metadata if available, see
+/// [`decode_contract_event`]. Returns the path of the additional metadata which decoded
+/// the event along with the field.
+///
+/// The event is matched by its `topics`, which are also used to match anonymous
+/// events.
+fn contract_event_data_field<'m, C: Config>(
     transcoder: Option<&ContractMessageTranscoder>,
+    additional_metadata: &'m [AdditionalMetadata],
+    emitter: Option<&[u8]>,
     field_metadata: &scale_info::Field<PortableForm>,
-    event_sig_topic: Option<&C::Hash>,
+    topics: &[C::Hash],
     event_data: &mut &[u8],
-) -> Result<Field> {
-    let event_value = if let Some(transcoder) = transcoder {
-        if let Some(event_sig_topic) = event_sig_topic {
-            match transcoder.decode_contract_event(event_sig_topic, event_data) {
-                Ok(contract_event) => contract_event,
-                Err(err) => {
-                    tracing::warn!(
-                        "Decoding contract event failed: {:?}. It might have come from another contract.",
-                        err
-                    );
-                    Value::Hex(Hex::from_str(&hex::encode(&event_data))?)
-                }
+) -> Result<(Field, Option<&'m Path>)> {
+    let mut indexed = Vec::new();
+    let mut metadata = None;
+    let event_value = match decode_contract_event(
+        transcoder,
+        additional_metadata,
+        emitter,
+        topics,
+        event_data,
+    ) {
+        Some((contract_event, path)) => {
+            indexed = contract_event.indexed;
+            metadata = path;
+            contract_event.value
+        }
+        None => {
+            if transcoder.is_some() || !additional_metadata.is_empty() {
+                tracing::warn!(
+                    "Decoding contract event failed. It might have come from another contract."
+                );
             }
-        } else {
-            tracing::info!("Anonymous event not decoded. Data displayed as raw hex.");
-            Value::Hex(Hex::from_str(&hex::encode(event_data))?)
+            Value::Hex(Hex::from_str(&hex::encode(&event_data))?)
         }
-    } else {
-        Value::Hex(Hex::from_str(&hex::encode(event_data))?)
     };
-    Ok(Field::new(
+    let mut field = Field::new(
         String::from("data"),
         event_value,
         field_metadata.type_name.as_ref().map(|s| s.to_string()),
-    ))
+    );
+    field.indexed = indexed;
+    Ok((field, metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_info::{
+        MetaType,
+        Registry,
+    };
+    use subxt::utils::H256;
+
+    fn field(ty: u32, type_name: &str) -> scale_info::Field<PortableForm> {
+        scale_info::Field {
+            name: Some("amount".into()),
+            ty: ty.into(),
+            type_name: Some(type_name.into()),
+            docs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn detects_balance_fields() {
+        let mut registry = Registry::new();
+        let balance = registry.register_type(&MetaType::new::<u128>()).id;
+        let compact = registry
+            .register_type(&MetaType::new::<scale::Compact<u64>>())
+            .id;
+        let account = registry.register_type(&MetaType::new::<[u8; 32]>()).id;
+        let types: PortableRegistry = registry.into();
+
+        assert!(is_balance_field(&field(balance, "T::Balance"), &types));
+        assert!(is_balance_field(&field(balance, "BalanceOf<T>"), &types));
+        assert!(is_balance_field(&field(balance, "BalanceOf<T, I>"), &types));
+        assert!(is_balance_field(
+            &field(balance, "<T as Config>::Balance"),
+            &types
+        ));
+        assert!(is_balance_field(&field(compact, "BalanceOf<T>"), &types));
+        assert!(!is_balance_field(&field(balance, "u128"), &types));
+        assert!(!is_balance_field(&field(balance, "T::Imbalance"), &types));
+        assert!(!is_balance_field(&field(account, "BalanceOf<T>"), &types));
+    }
+
+    #[test]
+    fn denominates_balance_fields() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".to_string(),
+            fallback: false,
+        };
+        let mut field = Field::new(
+            "amount".to_string(),
+            Value::UInt(1_000_000_000_000),
+            Some("T::Balance".to_string()),
+        );
+        assert_eq!(field.denominate(&token_metadata), None);
+
+        field.is_balance = true;
+        let balance = field.denominate(&token_metadata).unwrap();
+        assert_eq!(balance.to_string(), "1UNIT (1000000000000)");
+        field.denominated = balance.denominated().map(str::to_string);
+        assert_eq!(field.denominated.as_deref(), Some("1UNIT"));
+        assert_eq!(
+            serde_json::to_value(&field).unwrap(),
+            serde_json::json!({
+                "name": "amount",
+                "value": { "UInt": 1_000_000_000_000u128 },
+                "denominated": "1UNIT",
+            })
+        );
+    }
+
+    fn event(pallet: &str, name: &str, fields: Vec<Field>) -> Event {
+        Event {
+            pallet: pallet.to_string(),
+            name: name.to_string(),
+            fields,
+            topics: Vec::new(),
+            raw: None,
+            raw_data: None,
+            decode_error: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn displays_events_compactly() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".to_string(),
+            fallback: false,
+        };
+        let account = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let mut amount = Field::new(
+            "amount".to_string(),
+            Value::UInt(1_500_000_000_000),
+            Some("T::Balance".to_string()),
+        );
+        amount.is_balance = true;
+        let mut to = Field::new("to".to_string(), Value::Literal(account.into()), None);
+        to.alias = Some("alice".to_string());
+        let transfer = event(
+            "Balances",
+            "Transfer",
+            vec![
+                Field::new("from".to_string(), Value::Literal(account.into()), None),
+                to,
+                amount,
+            ],
+        );
+        let contract_event = Value::Map(contract_transcode::Map::new(
+            Some("Transfer"),
+            vec![(Value::String("from".into()), Value::Literal(account.into()))]
+                .into_iter()
+                .collect(),
+        ));
+        let emitted = event(
+            "Contracts",
+            "ContractEmitted",
+            vec![
+                Field::new("contract".to_string(), Value::Literal(account.into()), None),
+                Field::new("data".to_string(), contract_event, None),
+            ],
+        );
+        let events = DisplayEvents::from(vec![transfer, emitted]);
+
+        let out = events
+            .display_events::<ink_env::DefaultEnvironment>(
+                Verbosity::Default,
+                EventDisplay::Compact,
+                &token_metadata,
+            )
+            .unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3, "{out}");
+        assert!(
+            lines[1].ends_with(" → { from: 5GrwvaEF…, to: alice, amount: 1.5UNIT }"),
+            "{out}"
+        );
+        assert!(
+            lines[2].ends_with(" → Transfer { from: 5GrwvaEF… }"),
+            "{out}"
+        );
+
+        let out = events
+            .display_events::<ink_env::DefaultEnvironment>(
+                Verbosity::Quiet,
+                EventDisplay::Compact,
+                &token_metadata,
+            )
+            .unwrap();
+        assert!(!out.contains('→'), "{out}");
+    }
+
+    #[test]
+    fn limits_depth_of_event_fields() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".to_string(),
+            fallback: false,
+        };
+        let nested = Value::Seq(vec![Value::Seq(vec![Value::UInt(1)].into())].into());
+        let events = DisplayEvents::from(vec![event(
+            "Test",
+            "Nested",
+            vec![Field::new("values".to_string(), nested, None)],
+        )]);
+        let display = |event_display| {
+            events
+                .display_events::<ink_env::DefaultEnvironment>(
+                    Verbosity::Default,
+                    event_display,
+                    &token_metadata,
+                )
+                .unwrap()
+        };
+        assert!(display(EventDisplay::Full(None)).contains(": [[1]]"));
+        assert!(display(EventDisplay::Full(Some(1))).contains(": […]"));
+    }
+
+    #[test]
+    fn displays_event_records() {
+        let metadata = metadata();
+        let mut record = contract_emitted(
+            &metadata,
+            scale::Encode::encode(&([0u8; 32], vec![0xdeu8, 0xad])),
+        );
+        record.topics = vec![];
+
+        let events = display_event_records(&[record], &metadata, false).unwrap();
+        assert_eq!(events.0.len(), 1);
+        let event = &events.0[0];
+        assert_eq!(event.pallet, "Contracts");
+        assert_eq!(event.name, "ContractEmitted");
+        assert_eq!(event.fields[0].name, "contract");
+        assert_eq!(event.fields[1].name, "data");
+        assert_eq!(event.fields[1].value.to_string(), "0x08dead");
+        assert_eq!(event.raw, None);
+        assert_eq!(event.raw_data, None);
+    }
+
+    fn metadata() -> subxt::Metadata {
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        subxt::Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode")
+    }
+
+    fn contract_emitted(
+        metadata: &subxt::Metadata,
+        field_bytes: Vec<u8>,
+    ) -> EventRecord<H256> {
+        let variant = metadata
+            .pallet_by_name("Contracts")
+            .unwrap()
+            .event_variants()
+            .unwrap()
+            .iter()
+            .find(|variant| variant.name == "ContractEmitted")
+            .unwrap();
+        EventRecord {
+            phase: Phase::ApplyExtrinsic(1),
+            pallet: "Contracts".to_string(),
+            variant_index: variant.index,
+            variant: "ContractEmitted".to_string(),
+            field_bytes,
+            topics: vec![H256::repeat_byte(0x01)],
+        }
+    }
+
+    fn display_event_records(
+        records: &[EventRecord<H256>],
+        metadata: &subxt::Metadata,
+        include_raw: bool,
+    ) -> Result<DisplayEvents> {
+        DisplayEvents::from_event_records::<
+            subxt::PolkadotConfig,
+            ink_env::DefaultEnvironment,
+        >(
+            records,
+            None,
+            &[],
+            metadata,
+            Ss58Prefix::default(),
+            false,
+            include_raw,
+            None,
+        )
+    }
+
+    #[test]
+    fn includes_raw_event_bytes() {
+        let metadata = metadata();
+        let field_bytes = scale::Encode::encode(&([0u8; 32], vec![0xdeu8, 0xad]));
+        let record = contract_emitted(&metadata, field_bytes.clone());
+
+        let events = display_event_records(&[record], &metadata, true).unwrap();
+        let event = &events.0[0];
+        assert_eq!(event.raw, Some(format!("0x{}", hex::encode(field_bytes))));
+        assert_eq!(event.raw_data.as_deref(), Some("0xdead"));
+        assert_eq!(event.topics, vec![format!("0x{}", "01".repeat(32))]);
+        assert_eq!(event.decode_error, None);
+        assert_eq!(event.fields.len(), 2);
+    }
+
+    #[test]
+    fn keeps_undecodable_events_with_raw_bytes() {
+        let metadata = metadata();
+        let record = contract_emitted(&metadata, vec![0xff; 8]);
+
+        let events =
+            display_event_records(std::slice::from_ref(&record), &metadata, true)
+                .unwrap();
+        let event = &events.0[0];
+        assert!(event.fields.is_empty());
+        assert!(event.decode_error.is_some());
+        assert_eq!(event.raw.as_deref(), Some("0xffffffffffffffff"));
+        assert_eq!(event.raw_data, None);
+
+        let err = display_event_records(&[record], &metadata, false)
+            .err()
+            .expect("undecodable events fail without raw bytes");
+        assert!(
+            err.to_string()
+                .starts_with("Failed to decode the event Contracts::ContractEmitted"),
+            "{err}"
+        );
+    }
+
+    /// A transcoder of the flipper contract with the event `label`, which has the
+    /// signature topic `0x01..01` and a `bool` field.
+    fn event_transcoder(label: &str) -> ContractMessageTranscoder {
+        let mut metadata: serde_json::Value = serde_json::from_slice(
+            &std::fs::read("../cargo-contract/tests/fixtures/flipper.json").unwrap(),
+        )
+        .unwrap();
+        metadata["spec"]["events"] = serde_json::json!([{
+            "label": label,
+            "module_path": "flipper",
+            "signature_topic": format!("0x{}", "01".repeat(32)),
+            "args": [{
+                "label": "value",
+                "indexed": false,
+                "type": { "type": 0, "displayName": ["bool"] },
+                "docs": [],
+            }],
+            "docs": [],
+        }]);
+        ContractMessageTranscoder::try_from(
+            serde_json::from_value::<crate::ContractMetadata>(metadata).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decodes_contract_events_with_additional_metadata() {
+        let callee = ContractAddress::<[u8; 32]>::AccountId([1; 32]);
+        let other = ContractAddress::<[u8; 32]>::AccountId([2; 32]);
+        let additional = |label, address| {
+            AdditionalMetadata::new(
+                PathBuf::from(format!("{label}.json")),
+                event_transcoder(label),
+                address,
+            )
+        };
+        let decode = |transcoder: Option<&ContractMessageTranscoder>,
+                      additional_metadata: &[AdditionalMetadata]| {
+            let topics = [H256::repeat_byte(0x01)];
+            let data = scale::Encode::encode(&vec![1u8]);
+            let (event, path) = decode_contract_event(
+                transcoder,
+                additional_metadata,
+                Some(&[1; 32]),
+                &topics,
+                &mut &data[..],
+            )
+            .expect("the event is decoded");
+            (
+                event.value.to_string(),
+                path.map(|path| path.display().to_string()),
+            )
+        };
+        let caller = event_transcoder("Caller");
+
+        // the metadata of the called contract is tried first
+        let (event, path) = decode(Some(&caller), &[additional("Unbound", None)]);
+        assert!(event.starts_with("Caller"), "{event}");
+        assert_eq!(path, None);
+
+        // unless additional metadata is bound to the contract which emitted the event
+        let (event, path) = decode(
+            Some(&caller),
+            &[
+                additional("Unbound", None),
+                additional("Other", Some(&other)),
+                additional("Callee", Some(&callee)),
+            ],
+        );
+        assert!(event.starts_with("Callee"), "{event}");
+        assert_eq!(path.as_deref(), Some("Callee.json"));
+
+        // metadata bound to another contract is not used
+        let (event, path) = decode(
+            None,
+            &[
+                additional("Other", Some(&other)),
+                additional("Unbound", None),
+            ],
+        );
+        assert!(event.starts_with("Unbound"), "{event}");
+        assert_eq!(path.as_deref(), Some("Unbound.json"));
+    }
 }
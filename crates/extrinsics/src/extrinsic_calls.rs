@@ -19,11 +19,20 @@ use crate::{
     WasmCode,
 };
 use subxt::{
+    error::MetadataError,
     ext::{
-        codec::Compact,
+        codec::{
+            Compact,
+            Encode,
+        },
         scale_encode::EncodeAsType,
     },
-    utils::MultiAddress,
+    tx::TxPayload,
+    utils::{
+        MultiAddress,
+        H160,
+    },
+    Metadata,
 };
 
 /// Copied from `sp_weight` to additionally implement `scale_encode::EncodeAsType`.
@@ -214,3 +223,212 @@ impl<AccountId, Balance> Call<AccountId, Balance> {
         subxt::tx::Payload::new("Contracts", "call", self)
     }
 }
+
+/// A raw call to `pallet-balances`'s `transfer_keep_alive` or `transfer_allow_death`.
+#[derive(EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct Transfer<AccountId, Balance> {
+    dest: MultiAddress<AccountId, ()>,
+    #[codec(compact)]
+    value: Balance,
+}
+
+impl<AccountId, Balance> Transfer<AccountId, Balance> {
+    pub fn new(dest: MultiAddress<AccountId, ()>, value: Balance) -> Self {
+        Self { dest, value }
+    }
+
+    pub fn build(self, allow_death: bool) -> subxt::tx::Payload<Self> {
+        let call = if allow_death {
+            "transfer_allow_death"
+        } else {
+            "transfer_keep_alive"
+        };
+        subxt::tx::Payload::new("Balances", call, self)
+    }
+}
+
+/// A raw call to `pallet-revive`'s `upload_code`.
+#[derive(Debug, EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct ReviveUploadCode<Balance> {
+    code: Vec<u8>,
+    #[codec(compact)]
+    storage_deposit_limit: Balance,
+}
+
+impl<Balance> ReviveUploadCode<Balance> {
+    pub fn new(code: WasmCode, storage_deposit_limit: Balance) -> Self {
+        Self {
+            code: code.0,
+            storage_deposit_limit,
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Revive", "upload_code", self)
+    }
+}
+
+/// A raw call to `pallet-revive`'s `instantiate_with_code`.
+#[derive(Debug, EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct ReviveInstantiateWithCode<Balance> {
+    #[codec(compact)]
+    value: Balance,
+    gas_limit: Weight,
+    #[codec(compact)]
+    storage_deposit_limit: Balance,
+    code: Vec<u8>,
+    data: Vec<u8>,
+    salt: Option<[u8; 32]>,
+}
+
+impl<Balance> ReviveInstantiateWithCode<Balance> {
+    pub fn new(
+        value: Balance,
+        gas_limit: sp_weights::Weight,
+        storage_deposit_limit: Balance,
+        code: Vec<u8>,
+        data: Vec<u8>,
+        salt: Option<[u8; 32]>,
+    ) -> Self {
+        Self {
+            value,
+            gas_limit: gas_limit.into(),
+            storage_deposit_limit,
+            code,
+            data,
+            salt,
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Revive", "instantiate_with_code", self)
+    }
+}
+
+/// A raw call to `pallet-revive`'s `instantiate`.
+#[derive(Debug, EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct ReviveInstantiate<Hash, Balance>
+where
+    Hash: EncodeAsType,
+{
+    #[codec(compact)]
+    value: Balance,
+    gas_limit: Weight,
+    #[codec(compact)]
+    storage_deposit_limit: Balance,
+    code_hash: Hash,
+    data: Vec<u8>,
+    salt: Option<[u8; 32]>,
+}
+
+impl<Hash, Balance> ReviveInstantiate<Hash, Balance>
+where
+    Hash: EncodeAsType,
+{
+    pub fn new(
+        value: Balance,
+        gas_limit: sp_weights::Weight,
+        storage_deposit_limit: Balance,
+        code_hash: Hash,
+        data: Vec<u8>,
+        salt: Option<[u8; 32]>,
+    ) -> Self {
+        Self {
+            value,
+            gas_limit: gas_limit.into(),
+            storage_deposit_limit,
+            code_hash,
+            data,
+            salt,
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Revive", "instantiate", self)
+    }
+}
+
+/// A raw call to `pallet-revive`'s `call`.
+#[derive(EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct ReviveCall<Balance> {
+    dest: H160,
+    #[codec(compact)]
+    value: Balance,
+    gas_limit: Weight,
+    #[codec(compact)]
+    storage_deposit_limit: Balance,
+    data: Vec<u8>,
+}
+
+impl<Balance> ReviveCall<Balance> {
+    pub fn new(
+        dest: H160,
+        value: Balance,
+        gas_limit: sp_weights::Weight,
+        storage_deposit_limit: Balance,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            dest,
+            value,
+            gas_limit: gas_limit.into(),
+            storage_deposit_limit,
+            data,
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Revive", "call", self)
+    }
+}
+
+/// A call which is already SCALE encoded, e.g. to be signed outside of this crate.
+pub(crate) struct EncodedCall(pub Vec<u8>);
+
+impl TxPayload for EncodedCall {
+    fn encode_call_data_to(
+        &self,
+        _metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        out.extend_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+/// A call wrapped in `pallet-sudo`'s `sudo` if `sudo` is set, to dispatch it with the
+/// root origin. Otherwise the call is dispatched as is.
+pub(crate) struct MaybeSudo<'a, Call> {
+    call: &'a Call,
+    sudo: bool,
+}
+
+impl<'a, Call> MaybeSudo<'a, Call> {
+    pub fn new(call: &'a Call, sudo: bool) -> Self {
+        Self { call, sudo }
+    }
+}
+
+impl<Call: TxPayload> TxPayload for MaybeSudo<'_, Call> {
+    fn encode_call_data_to(
+        &self,
+        metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        if self.sudo {
+            let pallet = metadata.pallet_by_name_err("Sudo")?;
+            let sudo = pallet
+                .call_variant_by_name("sudo")
+                .ok_or_else(|| MetadataError::CallNameNotFound("sudo".into()))?;
+            pallet.index().encode_to(out);
+            sudo.index.encode_to(out);
+        }
+        // the boxed `RuntimeCall` argument of `sudo` is encoded as the call itself
+        self.call.encode_call_data_to(metadata, out)
+    }
+}
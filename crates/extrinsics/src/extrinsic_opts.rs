@@ -14,11 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use anyhow::Result;
-use contract_build::Verbosity;
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_build::{
+    verbose_eprintln,
+    Verbosity,
+};
 use derivative::Derivative;
 use ink_env::Environment;
 use subxt::{
+    config::signed_extensions::CheckMortalityParams,
     tx,
     Config,
 };
@@ -26,24 +33,59 @@ use url::Url;
 
 use crate::{
     url_to_string,
+    AdditionalMetadata,
+    ArtifactsNotice,
     ContractArtifacts,
+    ContractMessageTranscoder,
+    NonceHandle,
+    Progress,
+    Ss58Prefix,
+    Timings,
+    TokenMetadata,
 };
 use std::{
+    fmt::Display,
     marker::PhantomData,
     option::Option,
     path::PathBuf,
+    str::FromStr,
+    time::Duration,
 };
 
+/// How often an extrinsic is resubmitted with a refreshed nonce by default.
+pub const DEFAULT_SUBMIT_RETRIES: u32 = 3;
+
 /// Arguments required for creating and sending an extrinsic to a substrate node.
 #[derive(Derivative)]
 #[derivative(Clone(bound = "E::Balance: Clone"))]
 pub struct ExtrinsicOpts<C: Config, E: Environment, Signer: Clone> {
     file: Option<PathBuf>,
+    metadata: Option<PathBuf>,
+    expected_hash: Option<[u8; 32]>,
     manifest_path: Option<PathBuf>,
     url: url::Url,
     signer: Signer,
+    origin: Option<C::AccountId>,
     storage_deposit_limit: Option<E::Balance>,
     verbosity: Verbosity,
+    ss58_prefix: Ss58Prefix,
+    mortality: Mortality,
+    submit_retries: u32,
+    timeout: Option<Duration>,
+    sudo: bool,
+    max_fee: Option<u128>,
+    progress: bool,
+    tx_status: bool,
+    token_metadata: Option<TokenMetadata>,
+    raw_values: bool,
+    args_hex_files: bool,
+    include_raw: bool,
+    additional_metadata: Vec<AdditionalMetadata>,
+    skip_compat_check: bool,
+    require_signed_artifacts: bool,
+    skip_wasm_validation: bool,
+    timings: Timings,
+    nonce: Option<NonceHandle>,
     _marker: PhantomData<C>,
 }
 
@@ -61,11 +103,32 @@ where
         ExtrinsicOptsBuilder {
             opts: ExtrinsicOpts {
                 file: None,
+                metadata: None,
+                expected_hash: None,
                 manifest_path: None,
                 url: url::Url::parse("ws://localhost:9944").unwrap(),
                 signer,
+                origin: None,
                 storage_deposit_limit: None,
                 verbosity: Verbosity::Default,
+                ss58_prefix: Ss58Prefix::default(),
+                mortality: Mortality::default(),
+                submit_retries: DEFAULT_SUBMIT_RETRIES,
+                timeout: None,
+                sudo: false,
+                max_fee: None,
+                progress: false,
+                tx_status: false,
+                token_metadata: None,
+                raw_values: false,
+                args_hex_files: false,
+                include_raw: false,
+                additional_metadata: Vec::new(),
+                skip_compat_check: false,
+                require_signed_artifacts: false,
+                skip_wasm_validation: false,
+                timings: Timings::default(),
+                nonce: None,
                 _marker: PhantomData,
             },
         }
@@ -78,6 +141,22 @@ where
         this
     }
 
+    /// Sets the path to the metadata file of a raw `.wasm` or `.polkavm` code file,
+    /// used instead of the metadata next to the code file.
+    pub fn metadata<T: Into<PathBuf>>(self, metadata: Option<T>) -> Self {
+        let mut this = self;
+        this.opts.metadata = metadata.map(|f| f.into());
+        this
+    }
+
+    /// Sets the code hash the contract artifact is expected to have, loading the
+    /// artifacts fails if the hash of its code differs.
+    pub fn expected_hash(self, expected_hash: Option<[u8; 32]>) -> Self {
+        let mut this = self;
+        this.opts.expected_hash = expected_hash;
+        this
+    }
+
     /// Sets the path to the Cargo.toml of the contract.
     pub fn manifest_path<T: Into<PathBuf>>(self, manifest_path: Option<T>) -> Self {
         let mut this = self;
@@ -92,6 +171,14 @@ where
         this
     }
 
+    /// Sets the account used as the origin of dry-runs instead of the signer. Extrinsics
+    /// can not be submitted with such an origin, since they are signed by the signer.
+    pub fn origin(self, origin: Option<C::AccountId>) -> Self {
+        let mut this = self;
+        this.opts.origin = origin;
+        this
+    }
+
     /// Sets the maximum amount of balance that can be charged from the caller to pay for
     /// storage.
     pub fn storage_deposit_limit(
@@ -110,6 +197,152 @@ where
         this
     }
 
+    /// Set the SS58 address prefix used to display account ids.
+    pub fn ss58_prefix(self, ss58_prefix: Ss58Prefix) -> Self {
+        let mut this = self;
+        this.opts.ss58_prefix = ss58_prefix;
+        this
+    }
+
+    /// Set the mortality of the submitted extrinsics.
+    pub fn mortality(self, mortality: Mortality) -> Self {
+        let mut this = self;
+        this.opts.mortality = mortality;
+        this
+    }
+
+    /// Set how often an extrinsic is resubmitted with a refreshed nonce if the
+    /// transaction pool rejects it because of a stale nonce.
+    pub fn submit_retries(self, submit_retries: u32) -> Self {
+        let mut this = self;
+        this.opts.submit_retries = submit_retries;
+        this
+    }
+
+    /// Set how long to wait for a submitted extrinsic to be included in a block, at
+    /// most 5 minutes for nodes connected to via HTTP by default.
+    pub fn timeout(self, timeout: Option<Duration>) -> Self {
+        let mut this = self;
+        this.opts.timeout = timeout;
+        this
+    }
+
+    /// Wrap the submitted call in `Sudo::sudo` to dispatch it with the root origin,
+    /// signed by the sudo key of the chain.
+    pub fn sudo(self, sudo: bool) -> Self {
+        let mut this = self;
+        this.opts.sudo = sudo;
+        this
+    }
+
+    /// Set the maximum fee of a submitted extrinsic. An extrinsic whose estimated fee
+    /// exceeds it is not submitted.
+    pub fn max_fee(self, max_fee: Option<u128>) -> Self {
+        let mut this = self;
+        this.opts.max_fee = max_fee;
+        this
+    }
+
+    /// Report the progress of dry-runs and submissions as JSON lines on stderr.
+    pub fn progress(self, progress: bool) -> Self {
+        let mut this = self;
+        this.opts.progress = progress;
+        this
+    }
+
+    /// Print the status of submitted extrinsics as single lines on stderr, from the
+    /// submission until the extrinsic is included in a block. Ignored if the progress is
+    /// reported as JSON lines.
+    pub fn tx_status(self, tx_status: bool) -> Self {
+        let mut this = self;
+        this.opts.tx_status = tx_status;
+        this
+    }
+
+    /// Set the token metadata of the chain, used to accept denominated balances such
+    /// as `1.5UNIT` for `Balance` arguments of contract messages.
+    pub fn token_metadata(self, token_metadata: Option<TokenMetadata>) -> Self {
+        let mut this = self;
+        this.opts.token_metadata = token_metadata;
+        this
+    }
+
+    /// Decode account ids, hashes and weights into their structural representation
+    /// instead of displaying them as SS58 addresses, hex and `ref_time/proof_size`.
+    pub fn raw_values(self, raw_values: bool) -> Self {
+        let mut this = self;
+        this.opts.raw_values = raw_values;
+        this
+    }
+
+    /// Read `@path` argument files of byte array arguments as hex if they start with
+    /// `0x`, instead of as raw bytes.
+    pub fn args_hex_files(self, args_hex_files: bool) -> Self {
+        let mut this = self;
+        this.opts.args_hex_files = args_hex_files;
+        this
+    }
+
+    /// Include the raw SCALE bytes of events alongside their decoded fields, and keep
+    /// the events which can't be decoded instead of failing.
+    pub fn include_raw(self, include_raw: bool) -> Self {
+        let mut this = self;
+        this.opts.include_raw = include_raw;
+        this
+    }
+
+    /// Sets the metadata of other contracts to decode the events of contracts called by
+    /// the contract with.
+    pub fn additional_metadata(
+        self,
+        additional_metadata: Vec<AdditionalMetadata>,
+    ) -> Self {
+        let mut this = self;
+        this.opts.additional_metadata = additional_metadata;
+        this
+    }
+
+    /// Do not check whether the contract's ink! version is supported by the contracts
+    /// pallet of the chain before uploading its code.
+    pub fn skip_compat_check(self, skip_compat_check: bool) -> Self {
+        let mut this = self;
+        this.opts.skip_compat_check = skip_compat_check;
+        this
+    }
+
+    /// Refuse contract artifacts which are not a `.contract` bundle with a valid
+    /// signature. Otherwise only an invalid signature is reported as a warning.
+    pub fn require_signed_artifacts(self, require_signed_artifacts: bool) -> Self {
+        let mut this = self;
+        this.opts.require_signed_artifacts = require_signed_artifacts;
+        this
+    }
+
+    /// Do not validate the contract Wasm against the host functions of the chain before
+    /// uploading it.
+    pub fn skip_wasm_validation(self, skip_wasm_validation: bool) -> Self {
+        let mut this = self;
+        this.opts.skip_wasm_validation = skip_wasm_validation;
+        this
+    }
+
+    /// Set the recorder of the durations of the transcoding, dry-runs, signing and
+    /// waiting for the submitted extrinsic, shared with the caller to report them.
+    pub fn timings(self, timings: Timings) -> Self {
+        let mut this = self;
+        this.opts.timings = timings;
+        this
+    }
+
+    /// Set the nonces of the signer, shared with other commands to submit extrinsics
+    /// back-to-back. By default the nonce is fetched from the chain for every
+    /// extrinsic.
+    pub fn nonce(self, nonce: NonceHandle) -> Self {
+        let mut this = self;
+        this.opts.nonce = Some(nonce);
+        this
+    }
+
     pub fn done(self) -> ExtrinsicOpts<C, E, Signer> {
         self.opts
     }
@@ -121,10 +354,56 @@ where
 {
     /// Load contract artifacts.
     pub fn contract_artifacts(&self) -> Result<ContractArtifacts> {
-        ContractArtifacts::from_manifest_or_file(
+        let mut artifacts = ContractArtifacts::from_manifest_or_file(
             self.manifest_path.as_ref(),
             self.file.as_ref(),
-        )
+        )?;
+        if let Some(metadata) = &self.metadata {
+            artifacts = artifacts.with_metadata_file(metadata)?;
+        }
+        if let Some(expected_hash) = &self.expected_hash {
+            artifacts.check_code_hash(expected_hash)?;
+        }
+        match artifacts.bundle_signer() {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                anyhow::ensure!(
+                    !self.require_signed_artifacts,
+                    "The contract artifact {} is not a signed `.contract` bundle",
+                    artifacts.artifact_path().display()
+                )
+            }
+            Err(err) if self.require_signed_artifacts => return Err(err),
+            Err(err) => {
+                artifacts.push_notice(ArtifactsNotice::Warning(format!("{err:#}")))
+            }
+        }
+        for notice in artifacts.notices() {
+            verbose_eprintln!(self.verbosity, "{notice}");
+        }
+        Ok(artifacts)
+    }
+
+    /// Construct a [`ContractMessageTranscoder`] from the contract artifacts, displaying
+    /// account ids with the configured SS58 address prefix. If the token metadata is
+    /// set, denominated balances are accepted for `Balance` arguments.
+    pub fn contract_transcoder(
+        &self,
+        artifacts: &ContractArtifacts,
+    ) -> Result<ContractMessageTranscoder> {
+        let transcoder = artifacts
+            .contract_transcoder()?
+            .with_ss58_prefix(self.ss58_prefix.value())
+            .with_raw_values(self.raw_values)
+            .with_hex_file_args(self.args_hex_files);
+        Ok(match self.token_metadata.clone() {
+            Some(token_metadata) => {
+                transcoder.with_balance_denomination(move |balance| {
+                    token_metadata.denominate(balance)
+                })
+            }
+            None => transcoder,
+        })
     }
 
     /// Return the file path of the contract artifact.
@@ -132,6 +411,16 @@ where
         self.file.as_ref()
     }
 
+    /// Return the path to the metadata file of a raw code file.
+    pub fn metadata(&self) -> Option<&PathBuf> {
+        self.metadata.as_ref()
+    }
+
+    /// Return the code hash the contract artifact is expected to have.
+    pub fn expected_hash(&self) -> Option<&[u8; 32]> {
+        self.expected_hash.as_ref()
+    }
+
     /// Return the path to the `Cargo.toml` of the contract.
     pub fn manifest_path(&self) -> Option<&PathBuf> {
         self.manifest_path.as_ref()
@@ -147,13 +436,258 @@ where
         &self.signer
     }
 
+    /// Return the origin of dry-runs: the account set with
+    /// [`ExtrinsicOptsBuilder::origin`], or else the account of the signer.
+    pub fn origin(&self) -> C::AccountId {
+        self.origin
+            .clone()
+            .unwrap_or_else(|| self.signer.account_id())
+    }
+
+    /// Return whether the origin of dry-runs differs from the account of the signer.
+    pub fn has_custom_origin(&self) -> bool {
+        self.origin.is_some()
+    }
+
     /// Return the storage deposit limit.
     pub fn storage_deposit_limit(&self) -> Option<E::Balance> {
         self.storage_deposit_limit
     }
 
+    /// Set the storage deposit limit.
+    pub(crate) fn set_storage_deposit_limit(
+        &mut self,
+        storage_deposit_limit: Option<E::Balance>,
+    ) {
+        self.storage_deposit_limit = storage_deposit_limit;
+    }
+
     /// Verbosity for message reporting.
     pub fn verbosity(&self) -> &Verbosity {
         &self.verbosity
     }
+
+    /// Return the SS58 address prefix used to display account ids.
+    pub fn ss58_prefix(&self) -> Ss58Prefix {
+        self.ss58_prefix
+    }
+
+    /// Return the mortality of the submitted extrinsics.
+    pub fn mortality(&self) -> Mortality {
+        self.mortality
+    }
+
+    /// Return how often an extrinsic is resubmitted if its nonce is stale.
+    pub fn submit_retries(&self) -> u32 {
+        self.submit_retries
+    }
+
+    /// Return how long to wait for a submitted extrinsic to be included in a block.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Return whether the submitted call is wrapped in `Sudo::sudo`.
+    pub fn sudo(&self) -> bool {
+        self.sudo
+    }
+
+    /// Return the maximum fee of a submitted extrinsic.
+    pub fn max_fee(&self) -> Option<u128> {
+        self.max_fee
+    }
+
+    /// Return the token metadata of the chain, if set.
+    pub fn token_metadata(&self) -> Option<&TokenMetadata> {
+        self.token_metadata.as_ref()
+    }
+
+    /// Return whether to report progress as JSON lines on stderr.
+    pub fn progress(&self) -> bool {
+        self.progress
+    }
+
+    /// Return whether to print the status of submitted extrinsics on stderr.
+    pub fn tx_status(&self) -> bool {
+        self.tx_status
+    }
+
+    /// Report the `progress` of a submitted extrinsic, as a JSON line or as a status
+    /// line, if either is enabled.
+    pub(crate) fn report_tx_progress(&self, progress: Progress) {
+        if self.progress {
+            progress.report()
+        } else if self.tx_status {
+            progress.report_status()
+        }
+    }
+
+    /// Return whether decoded values are displayed in their structural representation.
+    pub fn raw_values(&self) -> bool {
+        self.raw_values
+    }
+
+    /// Return whether the raw SCALE bytes of events are included.
+    pub fn include_raw(&self) -> bool {
+        self.include_raw
+    }
+
+    /// Return the metadata of other contracts to decode events with.
+    pub fn additional_metadata(&self) -> &[AdditionalMetadata] {
+        &self.additional_metadata
+    }
+
+    /// Return whether the compatibility check with the chain is skipped.
+    pub fn skip_compat_check(&self) -> bool {
+        self.skip_compat_check
+    }
+
+    /// Return whether contract artifacts must be signed `.contract` bundles.
+    pub fn require_signed_artifacts(&self) -> bool {
+        self.require_signed_artifacts
+    }
+
+    /// Return whether the validation of the contract Wasm is skipped.
+    pub fn skip_wasm_validation(&self) -> bool {
+        self.skip_wasm_validation
+    }
+
+    /// Return the recorder of the durations of the phases of the extrinsic.
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Return the nonces of the signer, if they are managed locally.
+    pub fn nonce(&self) -> Option<&NonceHandle> {
+        self.nonce.as_ref()
+    }
+}
+
+/// The mortality of an extrinsic: the number of blocks after which it can no longer be
+/// included in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mortality {
+    /// The extrinsic is valid forever.
+    Immortal,
+    /// The extrinsic is valid for (roughly, rounded to a power of two) the given number
+    /// of blocks, starting at the best block at the time of submission.
+    Mortal(u64),
+}
+
+impl Mortality {
+    /// The number of blocks an extrinsic is valid for by default.
+    pub const DEFAULT_PERIOD: u64 = 64;
+
+    /// Describes the mortality with its unit, e.g. `64 blocks`, unlike [`Display`] which
+    /// writes the value as it is parsed.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Immortal => "immortal".to_string(),
+            Self::Mortal(1) => "1 block".to_string(),
+            Self::Mortal(period) => format!("{period} blocks"),
+        }
+    }
+}
+
+impl Default for Mortality {
+    fn default() -> Self {
+        Self::Mortal(Self::DEFAULT_PERIOD)
+    }
+}
+
+impl FromStr for Mortality {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "immortal" {
+            return Ok(Self::Immortal)
+        }
+        match s.parse::<u64>() {
+            Ok(0) => Err(anyhow!("Mortality must be at least 1 block")),
+            Ok(period) => Ok(Self::Mortal(period)),
+            Err(_) => {
+                Err(anyhow!(
+                    "Invalid mortality '{s}', expected a number of blocks or 'immortal'"
+                ))
+            }
+        }
+    }
+}
+
+impl Display for Mortality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immortal => write!(f, "immortal"),
+            Self::Mortal(period) => write!(f, "{period}"),
+        }
+    }
+}
+
+/// The parameters of the signed extensions of a chain which include `CheckMortality`,
+/// allowing to submit mortal extrinsics.
+pub trait MortalityParams<C: Config> {
+    /// Make the extrinsic mortal for `period` blocks, starting at the given block.
+    fn mortal(self, period: u64, block_number: u64, block_hash: C::Hash) -> Self;
+}
+
+/// The parameters of the default set of signed extensions.
+impl<C: Config, A, B, D, F, G, H> MortalityParams<C>
+    for (A, B, D, F, CheckMortalityParams<C>, G, H)
+{
+    fn mortal(self, period: u64, block_number: u64, block_hash: C::Hash) -> Self {
+        let (a, b, d, f, _, g, h) = self;
+        let mortality = CheckMortalityParams::mortal(period, block_number, block_hash);
+        (a, b, d, f, mortality, g, h)
+    }
+}
+
+/// The parameters of the default set of signed extensions, extended by one more signed
+/// extension.
+impl<C: Config, A, B, D, F, G, H, I> MortalityParams<C>
+    for (A, B, D, F, CheckMortalityParams<C>, G, H, I)
+{
+    fn mortal(self, period: u64, block_number: u64, block_hash: C::Hash) -> Self {
+        let (a, b, d, f, _, g, h, i) = self;
+        let mortality = CheckMortalityParams::mortal(period, block_number, block_hash);
+        (a, b, d, f, mortality, g, h, i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mortality() {
+        assert_eq!("64".parse::<Mortality>().unwrap(), Mortality::Mortal(64));
+        assert_eq!(
+            "immortal".parse::<Mortality>().unwrap(),
+            Mortality::Immortal
+        );
+        assert!("0".parse::<Mortality>().is_err());
+        assert!("forever".parse::<Mortality>().is_err());
+    }
+
+    #[test]
+    fn display_mortality() {
+        assert_eq!(Mortality::default().to_string(), "64");
+        assert_eq!(Mortality::Immortal.to_string(), "immortal");
+    }
+
+    #[test]
+    fn describe_mortality() {
+        assert_eq!(Mortality::default().describe(), "64 blocks");
+        assert_eq!(Mortality::Mortal(1).describe(), "1 block");
+        assert_eq!(Mortality::Immortal.describe(), "immortal");
+    }
+
+    #[test]
+    fn mortality_display_roundtrips() {
+        for mortality in [Mortality::default(), Mortality::Immortal] {
+            assert_eq!(
+                mortality.to_string().parse::<Mortality>().unwrap(),
+                mortality
+            );
+        }
+    }
 }
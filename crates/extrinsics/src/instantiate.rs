@@ -15,33 +15,74 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    block_usage,
+    dry_run_state_call,
+    estimate_fee,
     events::{
         CodeStored,
         ContractInstantiated,
+        ReviveCodeStored,
+        ReviveContractInstantiated,
     },
     pallet_contracts_primitives::{
         ContractInstantiateResult,
+        ContractResult,
+        InstantiateReturnValue,
         StorageDeposit,
     },
-    state_call,
+    resolve_label,
     submit_extrinsic,
+    unsigned::{
+        create_unsigned,
+        submit_signed,
+        UnsignedExtrinsic,
+    },
+    Address,
+    BlockUsage,
+    Connection,
     ContractMessageTranscoder,
+    DebugMessage,
+    DisplayBalance,
+    DisplayEvents,
     ErrorVariant,
+    EventRecord,
+    TokenMetadata,
 };
 use crate::{
+    chain_limits::ChainLimits,
     check_env_types,
+    compat_check::check_chain_compatibility,
+    contract_info::{
+        code_exists,
+        contract_exists,
+    },
     extrinsic_calls::{
+        EncodedCall,
         Instantiate,
         InstantiateWithCode,
+        ReviveInstantiate,
+        ReviveInstantiateWithCode,
+    },
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
     },
-    extrinsic_opts::ExtrinsicOpts,
+    wasm_check::validate_wasm_code,
+    ContractAddress,
+    ContractsPallet,
+    Determinism,
+    EntryPoint,
+    Phase,
 };
 use anyhow::{
     anyhow,
-    Context,
     Result,
 };
-use contract_transcode::Value;
+use contract_build::WasmValidation;
+use contract_transcode::{
+    Hex,
+    Value,
+};
 use ink_env::Environment;
 use serde::Serialize;
 
@@ -49,21 +90,31 @@ use scale::{
     Decode,
     Encode,
 };
-use sp_core::Bytes;
+use sp_core::{
+    hashing::{
+        blake2_256,
+        keccak_256,
+    },
+    Bytes,
+};
 use sp_weights::Weight;
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+};
 use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
-    },
+    backend::legacy::LegacyRpcMethods,
     blocks::ExtrinsicEvents,
     config,
     ext::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
+    tx::{
+        self,
+        TxPayload,
+    },
+    utils::H160,
     Config,
     OnlineClient,
 };
@@ -77,6 +128,9 @@ pub struct InstantiateCommandBuilder<C: Config, E: Environment, Signer: Clone> {
     gas_limit: Option<u64>,
     proof_size: Option<u64>,
     salt: Option<Bytes>,
+    code_hash: Option<C::Hash>,
+    fuzzy_match: bool,
+    connection: Option<Connection<C>>,
 }
 
 impl<C: Config, E: Environment, Signer> InstantiateCommandBuilder<C, E, Signer>
@@ -97,6 +151,9 @@ where
             gas_limit: None,
             proof_size: None,
             salt: None,
+            code_hash: None,
+            fuzzy_match: false,
+            connection: None,
         }
     }
 
@@ -142,6 +199,30 @@ where
         this
     }
 
+    /// Sets the hash of code already stored on chain to instantiate, instead of the
+    /// code of the contract artifacts, which then only provide the metadata.
+    pub fn code_hash(self, code_hash: Option<C::Hash>) -> Self {
+        let mut this = self;
+        this.code_hash = code_hash;
+        this
+    }
+
+    /// Accept a constructor whose name matches the given name case-insensitively, or
+    /// starts with it, if the match is unique.
+    pub fn fuzzy_match(self, fuzzy_match: bool) -> Self {
+        let mut this = self;
+        this.fuzzy_match = fuzzy_match;
+        this
+    }
+
+    /// Sets the connection to the node to reuse, otherwise a new connection is
+    /// established to the url of the extrinsic options.
+    pub fn connection(self, connection: Option<Connection<C>>) -> Self {
+        let mut this = self;
+        this.connection = connection;
+        this
+    }
+
     /// Preprocesses contract artifacts and options for instantiation.
     ///
     /// This function prepares the required data for instantiating a contract based on the
@@ -153,24 +234,72 @@ where
     /// instantiation, or an error in case of failure.
     pub async fn done(self) -> Result<InstantiateExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
-        let data = transcoder.encode(&self.constructor, &self.args)?;
-        let url = self.extrinsic_opts.url();
-        let code = if let Some(code) = artifacts.code {
-            Code::Upload(code.0)
-        } else {
-            let code_hash = artifacts.code_hash()?;
-            Code::Existing(code_hash.into())
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
+        let constructor = resolve_label(
+            &transcoder,
+            EntryPoint::Constructor,
+            &self.constructor,
+            self.fuzzy_match,
+            self.extrinsic_opts.verbosity(),
+        )?;
+        let data = self
+            .extrinsic_opts
+            .timings()
+            .measure_sync(Phase::Transcode, || {
+                transcoder.encode(&constructor, &self.args)
+            })?;
+        let metadata = artifacts.metadata()?;
+        let target = artifacts.target();
+        let code = match (self.code_hash, &artifacts.code) {
+            (Some(code_hash), _) => Code::Existing(code_hash),
+            (None, Some(code)) => Code::Upload(code.0.clone()),
+            (None, None) => Code::Existing(artifacts.code_hash()?.into()),
         };
         let salt = self.salt.clone().map(|s| s.0).unwrap_or_default();
 
-        let rpc_cli = RpcClient::from_url(&url).await?;
-        let client = OnlineClient::from_rpc_client(rpc_cli.clone()).await?;
-        check_env_types(&client, &transcoder, self.extrinsic_opts.verbosity())?;
-        let rpc = LegacyRpcMethods::new(rpc_cli);
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => Connection::connect(&self.extrinsic_opts.url()).await?,
+        };
+        let client = connection.client().clone();
+        check_env_types::<C, E>(&client, &transcoder, self.extrinsic_opts.verbosity())?;
+        let rpc = connection.rpc().clone();
+        let pallet = ContractsPallet::detect(&client.metadata())?;
+        pallet.check_target(target)?;
+        if let Code::Upload(code) = &code {
+            ChainLimits::from_metadata(&client.metadata(), pallet)
+                .check_code_len(code.len())?;
+        }
+        if let Some(code_hash) = &self.code_hash {
+            if !code_exists(code_hash, &rpc, &client).await? {
+                return Err(anyhow!(
+                    "No code with the hash {code_hash:?} is stored on chain. Upload it \
+                     first with `cargo contract upload`"
+                ))
+            }
+        }
+        if !self.extrinsic_opts.skip_compat_check() {
+            check_chain_compatibility(&client, pallet, &metadata).await?;
+        }
+        if pallet == ContractsPallet::Revive {
+            revive_salt(&salt)?;
+        }
+        let wasm_validation = match &code {
+            Code::Upload(code)
+                if pallet == ContractsPallet::Contracts
+                    && !self.extrinsic_opts.skip_wasm_validation() =>
+            {
+                Some(validate_wasm_code(
+                    &client.metadata(),
+                    code,
+                    Determinism::Enforced,
+                )?)
+            }
+            _ => None,
+        };
 
         let args = InstantiateArgs {
-            constructor: self.constructor.clone(),
+            constructor,
             raw_args: self.args.clone(),
             value: self.value,
             gas_limit: self.gas_limit,
@@ -183,11 +312,13 @@ where
 
         Ok(InstantiateExec {
             args,
+            url: self.extrinsic_opts.url(),
             opts: self.extrinsic_opts,
-            url,
+            pallet,
             rpc,
             client,
             transcoder,
+            wasm_validation,
         })
     }
 }
@@ -230,6 +361,11 @@ impl<C: Config, E: Environment> InstantiateArgs<C, E> {
         self.proof_size
     }
 
+    /// Returns the storage deposit limit for this instantiation.
+    pub fn storage_deposit_limit(&self) -> Option<E::Balance> {
+        self.storage_deposit_limit
+    }
+
     /// Returns the storage deposit limit for this instantiation.
     pub fn storage_deposit_limit_compact(&self) -> Option<scale::Compact<E::Balance>> {
         self.storage_deposit_limit.map(Into::into)
@@ -253,16 +389,20 @@ impl<C: Config, E: Environment> InstantiateArgs<C, E> {
 pub struct InstantiateExec<C: Config, E: Environment, Signer: Clone> {
     opts: ExtrinsicOpts<C, E, Signer>,
     args: InstantiateArgs<C, E>,
+    pallet: ContractsPallet,
     url: String,
     rpc: LegacyRpcMethods<C>,
     client: OnlineClient<C>,
     transcoder: ContractMessageTranscoder,
+    wasm_validation: Option<WasmValidation>,
 }
 
 impl<C: Config, E: Environment, Signer> InstantiateExec<C, E, Signer>
 where
     C::AccountId: Decode,
-    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams: Default,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+    C::AccountId: Serialize,
     C::Hash: IntoVisitor + EncodeAsType,
     C::AccountId: IntoVisitor + Display,
     E::Balance: Serialize,
@@ -272,37 +412,77 @@ where
     ///
     /// This function decodes the result of a simulated contract instantiation dry run.
     /// It processes the returned data, including the constructor's return value, contract
-    /// address, gas consumption, and storage deposit, and packages them into an
-    /// [`InstantiateDryRunResult`].
+    /// address, gas consumption, and storage deposit, denominated with the
+    /// `token_metadata` if given, and packages them into an [`InstantiateDryRunResult`].
     ///
     /// Returns the decoded dry run result, or an error in case of failure.
     pub async fn decode_instantiate_dry_run(
         &self,
-        result: &ContractInstantiateResult<C::AccountId, E::Balance, ()>,
-    ) -> Result<InstantiateDryRunResult<E::Balance>, ErrorVariant> {
+        result: &ContractInstantiateResult<
+            ContractAddress<C::AccountId>,
+            E::Balance,
+            EventRecord<C::Hash>,
+        >,
+        token_metadata: Option<&TokenMetadata>,
+    ) -> Result<InstantiateDryRunResult<DisplayBalance>, ErrorVariant>
+    where
+        E::Balance: Into<u128>,
+    {
         tracing::debug!("instantiate data {:?}", self.args.data);
         match result.result {
             Ok(ref ret_val) => {
-                let value = self
-                    .transcoder
-                    .decode_constructor_return(
-                        &self.args.constructor,
-                        &mut &ret_val.result.data[..],
-                    )
-                    .context(format!("Failed to decode return value {:?}", &ret_val))?;
+                let (value, decode_error) = decode_constructor_return(
+                    &self.transcoder,
+                    &self.args.constructor,
+                    &ret_val.result.data,
+                );
+                let error = if ret_val.result.did_revert() {
+                    constructor_error(&value)
+                } else {
+                    None
+                };
+                let events = result
+                    .events
+                    .as_ref()
+                    .map(|records| {
+                        DisplayEvents::from_event_records::<C, E>(
+                            records,
+                            Some(&self.transcoder),
+                            self.opts.additional_metadata(),
+                            &self.client.metadata(),
+                            self.opts.ss58_prefix(),
+                            self.opts.raw_values(),
+                            self.opts.include_raw(),
+                            token_metadata,
+                        )
+                    })
+                    .transpose()?;
                 let dry_run_result = InstantiateDryRunResult {
+                    origin: self
+                        .opts
+                        .has_custom_origin()
+                        .then(|| self.opts.ss58_prefix().address(&self.opts.origin())),
                     result: value,
-                    contract: ret_val.account_id.to_string(),
+                    decode_error,
+                    error,
+                    contract: self.opts.ss58_prefix().address(&ret_val.account_id),
+                    data: format!("0x{}", hex::encode(&self.args.data)),
+                    salt: format!("0x{}", hex::encode(&self.args.salt)),
                     reverted: ret_val.result.did_revert(),
                     gas_consumed: result.gas_consumed,
                     gas_required: result.gas_required,
-                    storage_deposit: result.storage_deposit.clone(),
+                    storage_deposit: result.storage_deposit.display(token_metadata),
+                    debug_message: DebugMessage::from_bytes(&result.debug_message),
+                    debug_message_file: None,
+                    block_usage: block_usage(&self.client, result.gas_required),
+                    events,
                 };
                 Ok(dry_run_result)
             }
             Err(ref err) => {
                 let metadata = self.client.metadata();
-                Err(ErrorVariant::from_dispatch_error(err, &metadata)?)
+                let error = ErrorVariant::from_dispatch_error(err, &metadata)?;
+                Err(self.explain_duplicate(error).await)
             }
         }
     }
@@ -317,18 +497,117 @@ where
     /// Returns the dry run simulation result, or an error in case of failure.
     pub async fn instantiate_dry_run(
         &self,
-    ) -> Result<ContractInstantiateResult<C::AccountId, E::Balance, ()>> {
+    ) -> Result<
+        ContractInstantiateResult<
+            ContractAddress<C::AccountId>,
+            E::Balance,
+            EventRecord<C::Hash>,
+        >,
+    > {
         let storage_deposit_limit = self.args.storage_deposit_limit;
-        let call_request = InstantiateRequest::<C, E> {
-            origin: self.opts.signer().account_id(),
-            value: self.args.value,
-            gas_limit: None,
-            storage_deposit_limit,
-            code: self.args.code.clone(),
-            data: self.args.data.clone(),
-            salt: self.args.salt.clone(),
-        };
-        state_call(&self.rpc, "ContractsApi_instantiate", &call_request).await
+        let func = self.pallet.runtime_api("instantiate");
+        let metadata = self.client.metadata();
+        match self.pallet {
+            ContractsPallet::Contracts => {
+                let call_request = InstantiateRequest::<C, E, _> {
+                    origin: self.opts.origin(),
+                    value: self.args.value,
+                    gas_limit: None,
+                    storage_deposit_limit,
+                    code: self.args.code.clone(),
+                    data: self.args.data.clone(),
+                    salt: self.args.salt.clone(),
+                };
+                let dry_run =
+                    dry_run_state_call(&self.rpc, &metadata, &func, &call_request);
+                let result: ContractInstantiateResult<C::AccountId, E::Balance, _> =
+                    self.opts.timings().measure(Phase::DryRun, dry_run).await?;
+                Ok(map_contract_address(result, ContractAddress::AccountId))
+            }
+            ContractsPallet::Revive => {
+                let call_request = InstantiateRequest::<C, E, _> {
+                    origin: self.opts.origin(),
+                    value: self.args.value,
+                    gas_limit: None,
+                    storage_deposit_limit,
+                    code: self.args.code.clone(),
+                    data: self.args.data.clone(),
+                    salt: revive_salt(&self.args.salt)?,
+                };
+                let dry_run =
+                    dry_run_state_call(&self.rpc, &metadata, &func, &call_request);
+                let result: ContractInstantiateResult<H160, E::Balance, _> =
+                    self.opts.timings().measure(Phase::DryRun, dry_run).await?;
+                Ok(map_contract_address(result, ContractAddress::H160))
+            }
+        }
+    }
+
+    /// Returns the storage deposit limit for `pallet-revive`, which requires one to be
+    /// set: the deposit charged by a dry run is used if none was specified.
+    async fn revive_storage_deposit_limit(&self) -> Result<E::Balance> {
+        match self.args.storage_deposit_limit {
+            Some(limit) => Ok(limit),
+            None => {
+                let result = self.instantiate_dry_run().await?;
+                Ok(result.storage_deposit.charge_or_zero())
+            }
+        }
+    }
+
+    /// Estimates the fee of instantiating the contract with the given gas limit via the
+    /// transaction payment API.
+    pub async fn estimate_fee(&self, gas_limit: Weight) -> Result<u128> {
+        match (self.pallet, self.args.code.clone()) {
+            (ContractsPallet::Contracts, Code::Upload(code)) => {
+                let call = InstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+            (ContractsPallet::Contracts, Code::Existing(code_hash)) => {
+                let call = Instantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code_hash,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+            (ContractsPallet::Revive, Code::Upload(code)) => {
+                let call = ReviveInstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+            (ContractsPallet::Revive, Code::Existing(code_hash)) => {
+                let call = ReviveInstantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code_hash,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build();
+                estimate_fee(&self.client, &call, &self.opts).await
+            }
+        }
     }
 
     async fn instantiate_with_code(
@@ -336,34 +615,33 @@ where
         code: Vec<u8>,
         gas_limit: Weight,
     ) -> Result<InstantiateExecResult<C>, ErrorVariant> {
-        let call = InstantiateWithCode::new(
-            self.args.value,
-            gas_limit,
-            self.args.storage_deposit_limit,
-            code,
-            self.args.data.clone(),
-            self.args.salt.clone(),
-        )
-        .build();
-
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
-
-        // The CodeStored event is only raised if the contract has not already been
-        // uploaded.
-        let code_hash = events
-            .find_first::<CodeStored<C::Hash>>()?
-            .map(|code_stored| code_stored.code_hash);
-
-        let instantiated = events
-            .find_last::<ContractInstantiated<C::AccountId>>()?
-            .ok_or_else(|| anyhow!("Failed to find Instantiated event"))?;
-
-        Ok(InstantiateExecResult {
-            events,
-            code_hash,
-            contract_address: instantiated.contract,
-        })
+        let events = match self.pallet {
+            ContractsPallet::Contracts => {
+                let call = InstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+            ContractsPallet::Revive => {
+                let call = ReviveInstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+        };
+        self.exec_result(events).map_err(Into::into)
     }
 
     async fn instantiate_with_code_hash(
@@ -371,30 +649,154 @@ where
         code_hash: C::Hash,
         gas_limit: Weight,
     ) -> Result<InstantiateExecResult<C>, ErrorVariant> {
-        let call = Instantiate::<C::Hash, E::Balance>::new(
-            self.args.value,
-            gas_limit,
-            self.args.storage_deposit_limit,
-            code_hash,
-            self.args.data.clone(),
-            self.args.salt.clone(),
-        )
-        .build();
+        let events = match self.pallet {
+            ContractsPallet::Contracts => {
+                let call = Instantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code_hash,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+            ContractsPallet::Revive => {
+                let call = ReviveInstantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code_hash,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build();
+                submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?
+            }
+        };
+        self.exec_result(events).map_err(Into::into)
+    }
 
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+    /// Returns the result of the instantiation from the `events` of its extrinsic.
+    fn exec_result(
+        &self,
+        events: ExtrinsicEvents<C>,
+    ) -> Result<InstantiateExecResult<C>> {
+        let uploaded = matches!(self.args.code, Code::Upload(_));
+        // The CodeStored event is only raised if the contract has not already been
+        // uploaded.
+        let code_hash = match (self.pallet, uploaded) {
+            (_, false) => None,
+            (ContractsPallet::Contracts, true) => {
+                events.find_first::<CodeStored<C::Hash>>()?
+            }
+            (ContractsPallet::Revive, true) => {
+                events
+                    .find_first::<ReviveCodeStored<C::Hash>>()?
+                    .map(Into::into)
+            }
+        }
+        .map(|code_stored| code_stored.code_hash);
 
-        let instantiated = events
-            .find_first::<ContractInstantiated<C::AccountId>>()?
-            .ok_or_else(|| anyhow!("Failed to find Instantiated event"))?;
+        let contract_address = self.find_instantiated_contract(&events, uploaded)?;
 
         Ok(InstantiateExecResult {
             events,
-            code_hash: None,
-            contract_address: instantiated.contract,
+            code_hash,
+            contract_address,
         })
     }
 
+    /// Returns the SCALE encoded instantiation call with the given gas limit.
+    async fn encoded_call(&self, gas_limit: Weight) -> Result<Vec<u8>> {
+        let metadata = self.client.metadata();
+        let call = match (self.pallet, self.args.code.clone()) {
+            (ContractsPallet::Contracts, Code::Upload(code)) => {
+                InstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+            (ContractsPallet::Contracts, Code::Existing(code_hash)) => {
+                Instantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.args.storage_deposit_limit,
+                    code_hash,
+                    self.args.data.clone(),
+                    self.args.salt.clone(),
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+            (ContractsPallet::Revive, Code::Upload(code)) => {
+                ReviveInstantiateWithCode::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+            (ContractsPallet::Revive, Code::Existing(code_hash)) => {
+                ReviveInstantiate::<C::Hash, E::Balance>::new(
+                    self.args.value,
+                    gas_limit,
+                    self.revive_storage_deposit_limit().await?,
+                    code_hash,
+                    self.args.data.clone(),
+                    revive_salt(&self.args.salt)?,
+                )
+                .build()
+                .encode_call_data(&metadata)?
+            }
+        };
+        Ok(call)
+    }
+
+    /// Returns the address of the instantiated contract from the `Instantiated` event.
+    ///
+    /// If `last` is set the last such event is used, otherwise the first.
+    fn find_instantiated_contract(
+        &self,
+        events: &ExtrinsicEvents<C>,
+        last: bool,
+    ) -> Result<ContractAddress<C::AccountId>> {
+        let contract_address = match (self.pallet, last) {
+            (ContractsPallet::Contracts, true) => {
+                events
+                    .find_last::<ContractInstantiated<C::AccountId>>()?
+                    .map(|instantiated| ContractAddress::AccountId(instantiated.contract))
+            }
+            (ContractsPallet::Contracts, false) => {
+                events
+                    .find_first::<ContractInstantiated<C::AccountId>>()?
+                    .map(|instantiated| ContractAddress::AccountId(instantiated.contract))
+            }
+            (ContractsPallet::Revive, true) => {
+                events
+                    .find_last::<ReviveContractInstantiated>()?
+                    .map(|instantiated| ContractAddress::H160(instantiated.contract))
+            }
+            (ContractsPallet::Revive, false) => {
+                events
+                    .find_first::<ReviveContractInstantiated>()?
+                    .map(|instantiated| ContractAddress::H160(instantiated.contract))
+            }
+        };
+        contract_address.ok_or_else(|| anyhow!("Failed to find Instantiated event"))
+    }
+
     /// Initiates the deployment of a smart contract on the blockchain.
     ///
     /// This function can be used to deploy a contract using either its source code or an
@@ -414,11 +816,113 @@ where
             Some(gas_limit) => gas_limit,
             None => self.estimate_gas().await?,
         };
-        match self.args.code.clone() {
+        let result = match self.args.code.clone() {
             Code::Upload(code) => self.instantiate_with_code(code, gas_limit).await,
             Code::Existing(code_hash) => {
                 self.instantiate_with_code_hash(code_hash, gas_limit).await
             }
+        };
+        match result {
+            Ok(result) => Ok(result),
+            Err(error) => Err(self.explain_duplicate(error).await),
+        }
+    }
+
+    /// Creates the extrinsic instantiating the contract, to be signed by `account_id`
+    /// outside of this crate, e.g. by a wallet, instead of by the signer of the
+    /// extrinsic options.
+    ///
+    /// Sign its [`UnsignedExtrinsic::signer_payload`] and submit the signed extrinsic
+    /// with [`InstantiateExec::submit_signed`].
+    pub async fn build_partial_extrinsic(
+        &self,
+        gas_limit: Option<Weight>,
+        account_id: &C::AccountId,
+    ) -> Result<UnsignedExtrinsic<C>, ErrorVariant> {
+        let gas_limit = match gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => self.estimate_gas().await?,
+        };
+        let call = EncodedCall(self.encoded_call(gas_limit).await?);
+        create_unsigned(&self.client, &self.rpc, &call, account_id, &self.opts).await
+    }
+
+    /// Submits the `signed` extrinsic created by
+    /// [`InstantiateExec::build_partial_extrinsic`], returning the result like
+    /// [`InstantiateExec::instantiate`].
+    pub async fn submit_signed(
+        &self,
+        signed: Vec<u8>,
+    ) -> Result<InstantiateExecResult<C>, ErrorVariant> {
+        let result =
+            match submit_signed(&self.client, &self.rpc, signed, &self.opts).await {
+                Ok(events) => self.exec_result(events).map_err(Into::into),
+                Err(error) => Err(error),
+            };
+        match result {
+            Ok(result) => Ok(result),
+            Err(error) => Err(self.explain_duplicate(error).await),
+        }
+    }
+
+    /// Returns the address of the contract the origin instantiated before with the same
+    /// code, constructor input and salt, if it exists on chain.
+    ///
+    /// Instantiating such a contract again fails with `DuplicateContract`. On chains with
+    /// `pallet-revive` the address is only derived if a salt is given and the code is
+    /// uploaded along with the instantiation.
+    pub async fn existing_contract(
+        &self,
+    ) -> Result<Option<ContractAddress<C::AccountId>>> {
+        let deployer = self.opts.origin().encode();
+        let address = match (self.pallet, &self.args.code) {
+            (ContractsPallet::Contracts, code) => {
+                let code_hash = match code {
+                    Code::Upload(code) => blake2_256(code),
+                    Code::Existing(code_hash) => {
+                        Decode::decode(&mut &code_hash.encode()[..])?
+                    }
+                };
+                let address = contracts_address(
+                    &deployer,
+                    &code_hash,
+                    &self.args.data,
+                    &self.args.salt,
+                );
+                ContractAddress::AccountId(Decode::decode(&mut &address[..])?)
+            }
+            (ContractsPallet::Revive, Code::Upload(code)) => {
+                let Some(salt) = revive_salt(&self.args.salt)? else {
+                    return Ok(None)
+                };
+                let deployer = H160::from_slice(&deployer[..20]);
+                ContractAddress::H160(revive_address(
+                    &deployer,
+                    code,
+                    &self.args.data,
+                    &salt,
+                ))
+            }
+            (ContractsPallet::Revive, Code::Existing(_)) => return Ok(None),
+        };
+        let exists = contract_exists(&address, &self.rpc, &self.client).await?;
+        Ok(exists.then_some(address))
+    }
+
+    /// Adds the address of the existing contract to a `DuplicateContract` error.
+    pub async fn explain_duplicate(&self, error: ErrorVariant) -> ErrorVariant {
+        if !error.is_module_error(self.pallet.name(), "DuplicateContract") {
+            return error
+        }
+        match self.existing_contract().await {
+            Ok(Some(contract)) => {
+                error.with_existing_contract(self.opts.ss58_prefix().address(&contract))
+            }
+            Ok(None) => error,
+            Err(err) => {
+                tracing::debug!("Failed to look up the existing contract: {err:?}");
+                error
+            }
         }
     }
 
@@ -461,6 +965,11 @@ where
         }
     }
 
+    /// Sets the storage deposit limit of the instantiation.
+    pub fn set_storage_deposit_limit(&mut self, limit: Option<E::Balance>) {
+        self.args.storage_deposit_limit = limit;
+    }
+
     /// Returns the extrinsic options.
     pub fn opts(&self) -> &ExtrinsicOpts<C, E, Signer> {
         &self.opts
@@ -471,6 +980,11 @@ where
         &self.args
     }
 
+    /// Returns the contracts pallet of the target chain.
+    pub fn pallet(&self) -> ContractsPallet {
+        self.pallet
+    }
+
     /// Returns the url.
     pub fn url(&self) -> &String {
         &self.url
@@ -485,28 +999,61 @@ where
     pub fn transcoder(&self) -> &ContractMessageTranscoder {
         &self.transcoder
     }
+
+    /// Returns the findings of the validation of the uploaded code, unless skipped or
+    /// instantiating existing code.
+    pub fn wasm_validation(&self) -> Option<&WasmValidation> {
+        self.wasm_validation.as_ref()
+    }
 }
 
 /// A struct representing the result of an instantiate command execution.
 pub struct InstantiateExecResult<C: Config> {
     pub events: ExtrinsicEvents<C>,
     pub code_hash: Option<C::Hash>,
-    pub contract_address: C::AccountId,
+    pub contract_address: ContractAddress<C::AccountId>,
 }
 
 /// Result of the contract call
 #[derive(serde::Serialize)]
 pub struct InstantiateDryRunResult<Balance: Serialize> {
+    /// The account the dry-run was performed with, if it is not the signer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Address>,
     /// The decoded result returned from the constructor
     pub result: Value,
+    /// Why the returned data could not be decoded, in which case `result` is the raw
+    /// data as hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
+    /// The error returned by a reverted constructor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
     /// contract address
-    pub contract: String,
+    pub contract: Address,
+    /// The hex encoded input data passed to the constructor: the selector followed by
+    /// the encoded arguments
+    pub data: String,
+    /// The hex encoded salt used in the address derivation, `0x` if empty
+    pub salt: String,
     /// Was the operation reverted
     pub reverted: bool,
     pub gas_consumed: Weight,
     pub gas_required: Weight,
     /// Storage deposit after the operation
     pub storage_deposit: StorageDeposit<Balance>,
+    /// The debug message of the dry-run, absent if empty
+    #[serde(flatten)]
+    pub debug_message: Option<DebugMessage>,
+    /// The file the debug buffer was written to, instead of the debug message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_message_file: Option<PathBuf>,
+    /// The share of the block weight limits taken up by the required gas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_usage: Option<BlockUsage>,
+    /// The events emitted during the dry-run, absent if the runtime doesn't return them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<DisplayEvents>,
 }
 
 impl<Balance: Serialize> InstantiateDryRunResult<Balance> {
@@ -517,15 +1064,136 @@ impl<Balance: Serialize> InstantiateDryRunResult<Balance> {
 }
 
 /// A struct that encodes RPC parameters required to instantiate a new smart contract.
+///
+/// The salt is a `Vec<u8>` for `pallet-contracts` and an `Option<[u8; 32]>` for
+/// `pallet-revive`.
 #[derive(Encode)]
-struct InstantiateRequest<C: Config, E: Environment> {
+struct InstantiateRequest<C: Config, E: Environment, Salt> {
     origin: C::AccountId,
     value: E::Balance,
     gas_limit: Option<Weight>,
     storage_deposit_limit: Option<E::Balance>,
     code: Code<C::Hash>,
     data: Vec<u8>,
-    salt: Vec<u8>,
+    salt: Salt,
+}
+
+/// Decodes the value returned by the constructor, falling back to the raw bytes as hex
+/// together with the decoding error if the data does not match the metadata.
+fn decode_constructor_return(
+    transcoder: &ContractMessageTranscoder,
+    constructor: &str,
+    data: &[u8],
+) -> (Value, Option<String>) {
+    match transcoder.decode_constructor_return(constructor, &mut &data[..]) {
+        Ok(value) => (value, None),
+        Err(err) => {
+            tracing::warn!("Failed to decode return value {:?}: {:?}", data, err);
+            (
+                Value::Hex(Hex::from(data.to_vec())),
+                Some(format!("{err:#}")),
+            )
+        }
+    }
+}
+
+/// Returns the error of a reverted constructor from its decoded return value.
+///
+/// ink! constructors return `Result<Self, LangError>`, or `Result<Result<Self, E>,
+/// LangError>` if they are fallible. The error is either the `LangError` or the error
+/// `E` returned by the constructor.
+fn constructor_error(value: &Value) -> Option<Value> {
+    match result_variant(value)? {
+        ("Err", error) => Some(error.clone()),
+        ("Ok", inner) => {
+            match result_variant(inner)? {
+                ("Err", error) => Some(error.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the variant name and the value of a decoded `Result`.
+fn result_variant(value: &Value) -> Option<(&str, &Value)> {
+    let Value::Tuple(tuple) = value else {
+        return None
+    };
+    let variant = match tuple.ident().as_deref() {
+        Some("Ok") => "Ok",
+        Some("Err") => "Err",
+        _ => return None,
+    };
+    let mut values = tuple.values();
+    match (values.next(), values.next()) {
+        (Some(value), None) => Some((variant, value)),
+        _ => None,
+    }
+}
+
+/// Converts the salt to the optional 32 byte salt expected by `pallet-revive`.
+fn revive_salt(salt: &[u8]) -> Result<Option<[u8; 32]>> {
+    if salt.is_empty() {
+        return Ok(None)
+    }
+    let salt = salt.try_into().map_err(|_| {
+        anyhow!(
+            "pallet-revive requires the salt to be 32 bytes in length, got {} bytes",
+            salt.len()
+        )
+    })?;
+    Ok(Some(salt))
+}
+
+/// Returns the address `pallet-contracts` derives for a contract instantiated by the
+/// `deployer` from the code with the `code_hash`, with the constructor `input` and
+/// `salt`.
+fn contracts_address(
+    deployer: &[u8],
+    code_hash: &[u8; 32],
+    input: &[u8],
+    salt: &[u8],
+) -> [u8; 32] {
+    let mut entropy = b"contract_addr_v1".to_vec();
+    entropy.extend(deployer);
+    entropy.extend(code_hash);
+    input.encode_to(&mut entropy);
+    salt.encode_to(&mut entropy);
+    blake2_256(&entropy)
+}
+
+/// Returns the address `pallet-revive` derives for a contract instantiated with a salt,
+/// as the `CREATE2` opcode does: from the `deployer`, `salt` and the hash of the `code`
+/// followed by the constructor `input`.
+fn revive_address(deployer: &H160, code: &[u8], input: &[u8], salt: &[u8; 32]) -> H160 {
+    let init_code_hash = keccak_256(&[code, input].concat());
+    let mut bytes = [0u8; 85];
+    bytes[0] = 0xff;
+    bytes[1..21].copy_from_slice(deployer.as_bytes());
+    bytes[21..53].copy_from_slice(salt);
+    bytes[53..85].copy_from_slice(&init_code_hash);
+    H160::from_slice(&keccak_256(&bytes)[12..])
+}
+
+/// Maps the address of the instantiated contract of a dry run result.
+fn map_contract_address<A, B, Balance, EventRecord>(
+    result: ContractInstantiateResult<A, Balance, EventRecord>,
+    f: impl FnOnce(A) -> B,
+) -> ContractInstantiateResult<B, Balance, EventRecord> {
+    ContractResult {
+        gas_consumed: result.gas_consumed,
+        gas_required: result.gas_required,
+        storage_deposit: result.storage_deposit,
+        debug_message: result.debug_message,
+        result: result.result.map(|ret_val| {
+            InstantiateReturnValue {
+                result: ret_val.result,
+                account_id: f(ret_val.account_id),
+            }
+        }),
+        events: result.events,
+    }
 }
 
 /// Reference to an existing code hash or a new Wasm module.
@@ -539,3 +1207,94 @@ where
     /// The code hash of an on-chain Wasm blob.
     Existing(Hash),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_transcode::Tuple;
+    use std::str::FromStr;
+
+    fn variant(name: &str, value: Value) -> Value {
+        Value::Tuple(Tuple::new(Some(name), vec![value]))
+    }
+
+    #[test]
+    fn revive_address_follows_create2() {
+        // examples from EIP-1014, with the init code split into code and input
+        let salt = [0u8; 32];
+        assert_eq!(
+            revive_address(&H160::zero(), &[0x00], &[], &salt),
+            H160::from_str("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38").unwrap()
+        );
+        assert_eq!(
+            revive_address(
+                &H160::from_str("0xdeadbeef00000000000000000000000000000000").unwrap(),
+                &[],
+                &[0x00],
+                &salt
+            ),
+            H160::from_str("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3").unwrap()
+        );
+    }
+
+    #[test]
+    fn contracts_address_depends_on_input_and_salt() {
+        let deployer = [1u8; 32];
+        let code_hash = [2u8; 32];
+        let address = contracts_address(&deployer, &code_hash, &[3], &[]);
+        assert_eq!(address, contracts_address(&deployer, &code_hash, &[3], &[]));
+        assert_ne!(
+            address,
+            contracts_address(&deployer, &code_hash, &[3], &[4])
+        );
+        // the input and salt are length prefixed, so they can't be confused
+        assert_ne!(address, contracts_address(&deployer, &code_hash, &[], &[3]));
+    }
+
+    #[test]
+    fn constructor_error_of_fallible_constructor() {
+        let error = Value::Tuple(Tuple::new(Some("InsufficientBalance"), vec![]));
+        let value = variant("Ok", variant("Err", error.clone()));
+        assert_eq!(constructor_error(&value), Some(error));
+    }
+
+    #[test]
+    fn constructor_error_of_lang_error() {
+        let error = Value::Tuple(Tuple::new(Some("CouldNotReadInput"), vec![]));
+        let value = variant("Err", error.clone());
+        assert_eq!(constructor_error(&value), Some(error));
+    }
+
+    #[test]
+    fn malformed_return_value_falls_back_to_hex() {
+        let transcoder = ContractMessageTranscoder::try_from(
+            serde_json::from_slice::<crate::ContractMetadata>(
+                &std::fs::read("../cargo-contract/tests/fixtures/flipper.json").unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let (value, decode_error) =
+            decode_constructor_return(&transcoder, "new", &[0x00, 0x00]);
+        assert_eq!(decode_error, None);
+        assert_eq!(constructor_error(&value), None);
+
+        // `0x07` is not a variant of the returned `Result`
+        let (value, decode_error) =
+            decode_constructor_return(&transcoder, "new", &[0x07, 0x2a]);
+        assert_eq!(value, Value::Hex(Hex::from_str("0x072a").unwrap()));
+        assert!(decode_error.is_some());
+        assert_eq!(constructor_error(&value), None);
+    }
+
+    #[test]
+    fn no_constructor_error_on_success() {
+        assert_eq!(constructor_error(&variant("Ok", Value::Unit)), None);
+        assert_eq!(
+            constructor_error(&variant("Ok", variant("Ok", Value::Unit))),
+            None
+        );
+        assert_eq!(constructor_error(&Value::Unit), None);
+    }
+}
@@ -17,10 +17,12 @@
 use crate::{
     CallCommandBuilder,
     CallExec,
+    CallOverrides,
     DisplayEvents,
     ExtrinsicOptsBuilder,
     InstantiateCommandBuilder,
     InstantiateExecResult,
+    NonceHandle,
     RemoveCommandBuilder,
     RemoveExec,
     UploadCommandBuilder,
@@ -82,6 +84,14 @@ impl Drop for ContractsNodeProcess {
 
 impl ContractsNodeProcess {
     async fn spawn<S>(program: S) -> Result<Self>
+    where
+        S: AsRef<OsStr>,
+    {
+        Self::spawn_with_args(program, &[]).await
+    }
+
+    /// Spawn the node with additional command line `args`.
+    async fn spawn_with_args<S>(program: S, args: &[&str]) -> Result<Self>
     where
         S: AsRef<OsStr>,
     {
@@ -93,6 +103,7 @@ impl ContractsNodeProcess {
             .env("RUST_LOG", "error")
             .arg("--dev")
             .arg(format!("--base-path={}", tmp_dir.path().to_string_lossy()))
+            .args(args)
             .spawn()?;
         // wait for rpc to be initialized
         const MAX_ATTEMPTS: u32 = 10;
@@ -517,6 +528,7 @@ async fn api_build_upload_instantiate_call() {
     let ret_val = result.result.unwrap();
     let value = call
         .transcoder()
+        .unwrap()
         .decode_message_return(call.message(), &mut &ret_val.data[..])
         .unwrap()
         .to_string();
@@ -546,7 +558,12 @@ async fn api_build_upload_instantiate_call() {
     let output = DisplayEvents::from_events::<DefaultConfig, DefaultEnvironment>(
         &call_result,
         None,
+        &[],
         &call.client().metadata(),
+        call.opts().ss58_prefix(),
+        call.opts().raw_values(),
+        call.opts().include_raw(),
+        None,
     )
     .unwrap()
     .to_json()
@@ -570,6 +587,7 @@ async fn api_build_upload_instantiate_call() {
     let ret_val = result.result.unwrap();
     let value = call
         .transcoder()
+        .unwrap()
         .decode_message_return(call.message(), &mut &ret_val.data[..])
         .unwrap()
         .to_string();
@@ -579,6 +597,117 @@ async fn api_build_upload_instantiate_call() {
     let _ = node_process;
 }
 
+/// Submits the same prepared call three times back-to-back, with the nonces of the
+/// signer managed by a [`NonceHandle`], so that the calls are included in one block.
+///
+/// # Note
+///
+/// The node seals a block every few seconds instead of a block per extrinsic, so that
+/// the calls can be included in the same block.
+#[tokio::test]
+async fn api_call_back_to_back_with_nonce_handle() {
+    init_tracing_subscriber();
+
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("cargo-contract.cli.test.")
+        .tempdir()
+        .expect("temporary directory creation failed");
+
+    let node_process = ContractsNodeProcess::spawn_with_args(
+        CONTRACTS_NODE,
+        &["--consensus=manual-seal-3000"],
+    )
+    .await
+    .expect("Error spawning contracts node");
+
+    cargo_contract(tmp_dir.path())
+        .arg("new")
+        .arg("flipper")
+        .assert()
+        .success();
+
+    let mut project_path = tmp_dir.path().to_path_buf();
+    project_path.push("flipper");
+
+    cargo_contract(project_path.as_path())
+        .arg("build")
+        .assert()
+        .success();
+
+    let contract_file = project_path.join("target/ink/flipper.contract");
+    let uri = <SecretUri as std::str::FromStr>::from_str("//Alice").unwrap();
+    let signer = Keypair::from_uri(&uri).unwrap();
+    let nonce = NonceHandle::default();
+    let opts = ExtrinsicOptsBuilder::new(signer)
+        .file(Some(contract_file))
+        .nonce(nonce.clone())
+        .done();
+
+    let instantiate = InstantiateCommandBuilder::new(opts.clone())
+        .constructor("new")
+        .args(["true"].to_vec())
+        .done()
+        .await
+        .unwrap();
+    let instantiate_result: InstantiateExecResult<DefaultConfig> =
+        instantiate.instantiate(None).await.unwrap();
+
+    let call: CallExec<DefaultConfig, DefaultEnvironment, Keypair> =
+        CallCommandBuilder::new(
+            instantiate_result.contract_address.clone(),
+            "flip",
+            opts,
+        )
+        .done()
+        .await
+        .unwrap();
+    // estimate the gas once, so that the calls are submitted without dry-runs
+    let gas_limit = call.estimate_gas().await.unwrap();
+    let first_nonce = nonce
+        .peek()
+        .await
+        .expect("the nonce is known after instantiating");
+
+    let overrides = CallOverrides::default();
+    let zero_value = overrides.clone().value(0);
+    let (first, second, third) = tokio::join!(
+        call.call(Some(gas_limit)),
+        call.call_with(Some(gas_limit), &overrides),
+        call.call_with(Some(gas_limit), &zero_value),
+    );
+    let block_hashes = [first, second, third]
+        .into_iter()
+        .map(|result| result.expect("call failed").block_hash())
+        .collect::<Vec<_>>();
+    assert!(
+        block_hashes.iter().all(|hash| *hash == block_hashes[0]),
+        "{block_hashes:?}"
+    );
+    assert_eq!(nonce.peek().await, Some(first_nonce + 3));
+
+    // the value was flipped three times
+    let get: CallExec<DefaultConfig, DefaultEnvironment, Keypair> =
+        CallCommandBuilder::new(
+            instantiate_result.contract_address,
+            "get",
+            call.opts().clone(),
+        )
+        .done()
+        .await
+        .unwrap();
+    let ret_val = get.call_dry_run().await.unwrap().result.unwrap();
+    let value = get
+        .transcoder()
+        .unwrap()
+        .decode_message_return(get.message(), &mut &ret_val.data[..])
+        .unwrap()
+        .to_string();
+    assert!(value.contains("false"), "{:#?}", value);
+
+    // prevent the node_process from being dropped and killed
+    let _ = node_process;
+}
+
 /// Sanity test the whole lifecycle of:
 /// build -> upload -> remove
 #[tokio::test]
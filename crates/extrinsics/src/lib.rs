@@ -14,21 +14,38 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+mod address_book;
 mod balance;
+mod block_weights;
+mod bundle_signature;
 mod call;
+mod chain_limits;
+mod compat_check;
 mod contract_artifacts;
 mod contract_info;
 mod contract_storage;
+mod debug_message;
 mod env_check;
 mod error;
 mod events;
 mod extrinsic_calls;
 mod extrinsic_opts;
 mod instantiate;
+mod nonce;
+mod pallet;
 pub mod pallet_contracts_primitives;
+mod progress;
 mod remove;
 mod rpc;
+mod ss58;
+mod timings;
+mod transfer;
+mod transport;
+mod unsigned;
 mod upload;
+mod wasm_check;
+mod watch;
+mod xcm;
 
 #[cfg(test)]
 mod contract_storage_tests;
@@ -38,8 +55,15 @@ mod contract_storage_tests;
 mod integration_tests;
 
 use env_check::compare_node_env_with_contract;
+use extrinsic_calls::MaybeSudo;
+use extrinsic_opts::ExtrinsicOpts;
+use ink_env::Environment;
+use pallet_contracts_primitives::ContractResult;
 
-use anyhow::Result;
+use anyhow::{
+    anyhow,
+    Result,
+};
 use contract_build::{
     CrateMetadata,
     Verbosity,
@@ -49,6 +73,11 @@ use scale::{
     Decode,
     Encode,
 };
+use serde::Serialize;
+use std::time::{
+    Duration,
+    Instant,
+};
 use subxt::{
     backend::legacy::LegacyRpcMethods,
     blocks,
@@ -58,19 +87,41 @@ use subxt::{
     OnlineClient,
 };
 
+pub use address_book::AddressBook;
 pub use balance::{
     BalanceVariant,
+    DisplayBalance,
     TokenMetadata,
 };
+pub use block_weights::{
+    block_usage,
+    BlockUsage,
+    BlockWeights,
+};
+pub use bundle_signature::{
+    sign_bundle,
+    BundleSignature,
+    SignatureScheme,
+};
 pub use call::{
     CallCommandBuilder,
     CallExec,
+    CallOverrides,
+};
+pub use chain_limits::ChainLimits;
+pub use compat_check::IncompatibleChain;
+pub use contract_artifacts::{
+    resolve_artifact_file,
+    ArtifactFile,
+    ArtifactsNotice,
+    ContractArtifacts,
 };
-pub use contract_artifacts::ContractArtifacts;
 pub use contract_info::{
+    fetch_all_code_info,
     fetch_all_contracts,
     fetch_contract_info,
     fetch_wasm_code,
+    CodeInfo,
     ContractInfo,
     TrieId,
 };
@@ -78,16 +129,41 @@ use contract_metadata::ContractMetadata;
 pub use contract_storage::{
     ContractStorage,
     ContractStorageCell,
+    ContractStorageDiff,
     ContractStorageLayout,
     ContractStorageRpc,
+    StorageDiffEntry,
+};
+pub use contract_transcode::{
+    ContractMessageTranscoder,
+    EntryPoint,
 };
-pub use contract_transcode::ContractMessageTranscoder;
+pub use debug_message::DebugMessage;
+pub use env_check::BalanceWidth;
 pub use error::{
+    AssertionFailed,
     ErrorVariant,
     GenericError,
+    MaxFeeExceeded,
+    ModuleError,
+    TransactionTimeout,
+};
+pub use events::{
+    deposit_refunded,
+    weight_used,
+    AdditionalMetadata,
+    DisplayEvents,
+    Event,
+    EventDisplay,
+    EventRecord,
+    Field,
+};
+pub use extrinsic_opts::{
+    ExtrinsicOptsBuilder,
+    Mortality,
+    MortalityParams,
+    DEFAULT_SUBMIT_RETRIES,
 };
-pub use events::DisplayEvents;
-pub use extrinsic_opts::ExtrinsicOptsBuilder;
 pub use instantiate::{
     Code,
     InstantiateArgs,
@@ -96,13 +172,32 @@ pub use instantiate::{
     InstantiateExec,
     InstantiateExecResult,
 };
+pub use nonce::NonceHandle;
+pub use progress::Progress;
 pub use remove::{
     RemoveCommandBuilder,
     RemoveExec,
     RemoveResult,
 };
 
+pub use timings::{
+    Phase,
+    Timings,
+    TimingsReport,
+};
+pub use transfer::{
+    TransferCommandBuilder,
+    TransferExec,
+};
+pub use transport::{
+    is_http_url,
+    require_websocket,
+    rpc_client,
+    Connection,
+};
+pub use unsigned::UnsignedExtrinsic;
 pub use upload::{
+    Determinism,
     UploadCommandBuilder,
     UploadExec,
     UploadResult,
@@ -113,6 +208,25 @@ pub use rpc::{
     RpcRequest,
 };
 
+pub use pallet::{
+    ContractAddress,
+    ContractsPallet,
+};
+pub use ss58::{
+    Address,
+    Ss58Prefix,
+};
+pub use watch::{
+    ContractEvent,
+    ContractEventWatcher,
+};
+pub use xcm::{
+    XcmFee,
+    XcmFeeAsset,
+    XcmPallet,
+    XcmSendResult,
+};
+
 /// The Wasm code of a contract.
 #[derive(Debug, Clone)]
 pub struct WasmCode(Vec<u8>);
@@ -122,6 +236,11 @@ impl WasmCode {
     pub fn code_hash(&self) -> [u8; 32] {
         contract_build::code_hash(&self.0)
     }
+
+    /// The size of the contract code in bytes.
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
 }
 
 /// Wait for the transaction to be included successfully into a block.
@@ -136,26 +255,430 @@ impl WasmCode {
 ///
 /// Currently this will report success once the transaction is included in a block. In the
 /// future there could be a flag to wait for finality before reporting success.
-async fn submit_extrinsic<C, Call, Signer>(
+///
+/// # Mortality
+///
+/// The extrinsic is mortal from the current best block for the number of blocks
+/// configured in the [`Mortality`] of the `opts`.
+///
+/// # Retries
+///
+/// If the transaction pool rejects the extrinsic because its nonce was taken from a
+/// stale view of the chain, the nonce is refreshed and the extrinsic resubmitted up to
+/// the number of retries configured in the `opts`. If a previously submitted attempt
+/// turns out to be included in a block after all, it is treated as success.
+///
+/// # Maximum fee
+///
+/// If a maximum fee is configured in the `opts`, the extrinsic is not submitted if its
+/// fee estimated by the transaction payment API exceeds it.
+///
+/// # HTTP endpoints
+///
+/// Nodes connected to via HTTP don't support watching the status of the extrinsic,
+/// instead the best blocks are polled until it is included in one.
+///
+/// # Timeout
+///
+/// If a timeout is configured in the `opts`, or the node is connected to via HTTP, it
+/// is given up waiting for the extrinsic to be included in a block after the timeout,
+/// returning an [`ErrorVariant::Timeout`] with its hash and last known status.
+///
+/// # Timings
+///
+/// The durations of signing the extrinsic and of waiting for its inclusion in a block
+/// are recorded in the [`Timings`] of the `opts`. The time until it is finalized is only
+/// recorded if the node reports it in a finalized block first, since finality is not
+/// waited for.
+///
+/// # Sudo
+///
+/// If `sudo` is set in the `opts`, the call is wrapped in `Sudo::sudo` to dispatch it
+/// with the root origin, which requires the signer to be the sudo key of the chain. The
+/// extrinsic succeeds even if the wrapped call fails, so its result is taken from the
+/// `Sudo::Sudid` event instead.
+///
+/// # Nonce
+///
+/// If a [`NonceHandle`] is set in the `opts`, the nonce is taken from it instead of the
+/// chain, so that extrinsics can be submitted back-to-back. It is resynced with the
+/// chain if the extrinsic fails, since its nonce may not have been used.
+async fn submit_extrinsic<C, E, Call, Signer>(
     client: &OnlineClient<C>,
     rpc: &LegacyRpcMethods<C>,
     call: &Call,
-    signer: &Signer,
-) -> core::result::Result<blocks::ExtrinsicEvents<C>, subxt::Error>
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> core::result::Result<blocks::ExtrinsicEvents<C>, ErrorVariant>
 where
     C: Config,
+    C::AccountId: Serialize,
+    E: Environment,
     Call: tx::TxPayload,
-    Signer: tx::Signer<C>,
-    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams: Default,
+    Signer: tx::Signer<C> + Clone,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
 {
+    if opts.has_custom_origin() {
+        return Err(anyhow!(
+            "An extrinsic can not be submitted with a custom origin, it is always \
+             signed by the signer"
+        )
+        .into())
+    }
+    if opts.sudo() {
+        check_sudo_key(client, rpc, &Signer::account_id(opts.signer())).await?;
+    }
+    let call = MaybeSudo::new(call, opts.sudo());
+    let events = match submit_and_wait(client, rpc, &call, opts).await {
+        Ok(events) => events,
+        Err(err) => {
+            if let Some(nonce) = opts.nonce() {
+                nonce.resync().await;
+            }
+            return Err(err)
+        }
+    };
+    if opts.sudo() {
+        check_sudo_result(&events, &client.metadata()).map_err(|err| *err)?;
+    }
+    Ok(events)
+}
+
+/// Submits the extrinsic, see [`submit_extrinsic`], and waits for it to be included.
+async fn submit_and_wait<C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    call: &Call,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> core::result::Result<blocks::ExtrinsicEvents<C>, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: Serialize,
+    E: Environment,
+    Call: tx::TxPayload,
+    Signer: tx::Signer<C> + Clone,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+{
+    let signer = opts.signer();
     let account_id = Signer::account_id(signer);
-    let account_nonce = get_account_nonce(client, rpc, &account_id).await?;
+    let best_block = client.blocks().at(get_best_block(rpc).await?).await?;
+    let first_block_number: u64 = best_block.number().into();
+    let mut account_nonce = match opts.nonce() {
+        Some(nonce) => {
+            nonce
+                .next(next_account_nonce(client, rpc, &account_id))
+                .await?
+        }
+        None => best_block.account_nonce(&account_id).await?,
+    };
+
+    let mortality = opts.mortality();
+    let deadline = submit_deadline(opts);
+    let mut submitted = Vec::new();
+    let mut attempt = 0;
+    loop {
+        let params =
+            extrinsic_params::<C>(mortality, first_block_number, best_block.hash());
+        let tx = opts
+            .timings()
+            .measure(Phase::Sign, async {
+                let tx = client.tx().create_signed_with_nonce(
+                    call,
+                    signer,
+                    account_nonce,
+                    params,
+                )?;
+                if attempt == 0 {
+                    check_max_fee(&tx, opts).await?;
+                }
+                Ok::<_, ErrorVariant>(tx)
+            })
+            .await?;
+        submitted.push(tx.hash());
 
-    let mut tx = client
+        let result = submit_until_deadline(
+            client,
+            rpc,
+            &tx,
+            first_block_number,
+            &submitted,
+            deadline,
+            opts,
+        )
+        .await?;
+        let err = match result {
+            Ok(events) => return Ok(events),
+            Err(err) if is_stale_nonce(&err) => err,
+            Err(err) => return Err(explain_expired(err, mortality).into()),
+        };
+        if let Some(events) =
+            find_included_extrinsic(client, rpc, first_block_number, &submitted).await?
+        {
+            tracing::info!("A previously submitted transaction was included in a block");
+            return Ok(events)
+        }
+        if attempt >= opts.submit_retries() {
+            return Err(err.into())
+        }
+        attempt += 1;
+        account_nonce = match opts.nonce() {
+            Some(nonce) => {
+                nonce.resync().await;
+                nonce
+                    .next(next_account_nonce(client, rpc, &account_id))
+                    .await?
+            }
+            None => next_account_nonce(client, rpc, &account_id).await?,
+        };
+        tracing::warn!(
+            "Transaction rejected: {err}. Resubmitting with nonce {account_nonce} \
+             (attempt {attempt} of {})",
+            opts.submit_retries()
+        );
+    }
+}
+
+/// Returns the parameters of the signed extensions for an extrinsic with the given
+/// `mortality`, starting at the given block.
+fn extrinsic_params<C>(
+    mortality: Mortality,
+    block_number: u64,
+    block_hash: C::Hash,
+) -> <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams
+where
+    C: Config,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+{
+    match mortality {
+        Mortality::Immortal => Default::default(),
+        Mortality::Mortal(period) => {
+            <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams::default()
+                .mortal(period, block_number, block_hash)
+        }
+    }
+}
+
+/// Returns when to give up waiting for a submitted extrinsic to be included, along with
+/// the timeout it was derived from, or `None` to wait indefinitely.
+fn submit_deadline<C, E, Signer>(
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> Option<(tokio::time::Instant, Duration)>
+where
+    C: Config,
+    E: Environment,
+    Signer: tx::Signer<C> + Clone,
+{
+    let timeout = opts
+        .timeout()
+        .or(is_http_url(&opts.url()).then_some(POLL_TIMEOUT));
+    timeout.map(|timeout| (tokio::time::Instant::now() + timeout, timeout))
+}
+
+/// Submits the signed `tx` and waits until it is included in a block, failing with a
+/// timeout once the `deadline` passed and none of the `submitted` extrinsics was
+/// included.
+///
+/// An error of the node, e.g. if it rejected the extrinsic, is returned as the inner
+/// result so that the extrinsic can be resubmitted.
+async fn submit_until_deadline<C, E, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    tx: &tx::SubmittableExtrinsic<C, OnlineClient<C>>,
+    first_block_number: u64,
+    submitted: &[C::Hash],
+    deadline: Option<(tokio::time::Instant, Duration)>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> core::result::Result<
+    core::result::Result<blocks::ExtrinsicEvents<C>, subxt::Error>,
+    ErrorVariant,
+>
+where
+    C: Config,
+    E: Environment,
+    Signer: tx::Signer<C> + Clone,
+{
+    let mut last_status = "signed";
+    let report = |progress| opts.report_tx_progress(progress);
+    let wait = async {
+        if is_http_url(&opts.url()) {
+            submit_and_poll(
+                tx,
+                client,
+                rpc,
+                first_block_number,
+                report,
+                opts.timings(),
+                &mut last_status,
+            )
+            .await
+        } else {
+            watch_extrinsic(tx, report, opts.timings(), &mut last_status).await
+        }
+    };
+    let result = match deadline {
+        Some((deadline, _)) => tokio::time::timeout_at(deadline, wait).await.ok(),
+        None => Some(wait.await),
+    };
+    match result {
+        Some(result) => Ok(result),
+        None => {
+            if let Some(events) =
+                find_included_extrinsic(client, rpc, first_block_number, submitted)
+                    .await?
+            {
+                return Ok(Ok(events))
+            }
+            Err(ErrorVariant::Timeout(TransactionTimeout::new(
+                format!("{:?}", tx.hash()),
+                last_status.to_string(),
+                first_block_number,
+                deadline
+                    .map(|(_, timeout)| timeout)
+                    .unwrap_or_default()
+                    .as_secs(),
+            )))
+        }
+    }
+}
+
+/// Estimates the fee of submitting the extrinsic `call` signed by the signer of the
+/// `opts`, via the transaction payment API.
+async fn estimate_fee<C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    call: &Call,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> Result<u128>
+where
+    C: Config,
+    E: Environment,
+    Call: tx::TxPayload,
+    Signer: tx::Signer<C> + Clone,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams: Default,
+{
+    let call = MaybeSudo::new(call, opts.sudo());
+    let fee = client
         .tx()
-        .create_signed_with_nonce(call, signer, account_nonce, Default::default())?
-        .submit_and_watch()
+        .create_signed(&call, opts.signer(), Default::default())
+        .await?
+        .partial_fee_estimate()
+        .await?;
+    Ok(fee)
+}
+
+/// Returns an error if the chain has no `Sudo` pallet, or if the `account_id` is not its
+/// sudo key.
+async fn check_sudo_key<C>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    account_id: &C::AccountId,
+) -> Result<()>
+where
+    C: Config,
+{
+    if client.metadata().pallet_by_name("Sudo").is_none() {
+        return Err(anyhow!(
+            "The chain has no `Sudo` pallet, `--sudo` can only be used on chains with \
+             one, e.g. development chains"
+        ))
+    }
+    let key = client
+        .storage()
+        .at(get_best_block(rpc).await?)
+        .fetch(&subxt::dynamic::storage("Sudo", "Key", Vec::<()>::new()))
         .await?;
+    match key {
+        Some(key) if key.encoded() == account_id.encode() => Ok(()),
+        Some(_) => {
+            Err(anyhow!(
+                "The signer is not the sudo key of the chain, `--sudo` requires the \
+                 `--suri` of the sudo key"
+            ))
+        }
+        None => Err(anyhow!("The chain has no sudo key")),
+    }
+}
+
+/// Returns the error of the call dispatched by `Sudo::sudo`, taken from the
+/// `Sudo::Sudid` event since the extrinsic itself succeeds either way.
+fn check_sudo_result<C>(
+    events: &blocks::ExtrinsicEvents<C>,
+    metadata: &subxt::Metadata,
+) -> core::result::Result<(), Box<ErrorVariant>>
+where
+    C: Config,
+{
+    for event in events.iter() {
+        let event = event.map_err(|err| Box::new(err.into()))?;
+        if event.pallet_name() == "Sudo" && event.variant_name() == "Sudid" {
+            let sudo_result =
+                <core::result::Result<(), sp_runtime::DispatchError>>::decode(
+                    &mut event.field_bytes(),
+                )
+                .map_err(|err| {
+                    Box::new(
+                        anyhow!("Failed to decode the `Sudo::Sudid` event: {err}").into(),
+                    )
+                })?;
+            return match sudo_result {
+                Ok(()) => Ok(()),
+                Err(err) => {
+                    let error = ErrorVariant::from_dispatch_error(&err, metadata)
+                        .unwrap_or_else(Into::into);
+                    Err(Box::new(error))
+                }
+            }
+        }
+    }
+    Err(Box::new(
+        anyhow!("The extrinsic emitted no `Sudo::Sudid` event").into(),
+    ))
+}
+
+/// Returns an error if the estimated fee of the extrinsic exceeds the maximum fee
+/// configured in the `opts`.
+async fn check_max_fee<C, E, Signer>(
+    tx: &tx::SubmittableExtrinsic<C, OnlineClient<C>>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> core::result::Result<(), ErrorVariant>
+where
+    C: Config,
+    E: Environment,
+    Signer: tx::Signer<C> + Clone,
+{
+    let Some(max_fee) = opts.max_fee() else {
+        return Ok(())
+    };
+    let estimated_fee = tx.partial_fee_estimate().await?;
+    tracing::debug!("Estimated fee {estimated_fee}, maximum fee {max_fee}");
+    if estimated_fee > max_fee {
+        return Err(ErrorVariant::MaxFeeExceeded(MaxFeeExceeded {
+            estimated_fee: DisplayBalance::new(estimated_fee, opts.token_metadata()),
+            max_fee: DisplayBalance::new(max_fee, opts.token_metadata()),
+        }))
+    }
+    Ok(())
+}
+
+/// Submit the extrinsic and wait until it is included in a block, keeping the last
+/// status reported by the node in `last_status` and reporting each status as it
+/// arrives.
+async fn watch_extrinsic<C>(
+    tx: &tx::SubmittableExtrinsic<C, OnlineClient<C>>,
+    report: impl Fn(Progress),
+    timings: &Timings,
+    last_status: &mut &'static str,
+) -> core::result::Result<blocks::ExtrinsicEvents<C>, subxt::Error>
+where
+    C: Config,
+{
+    let submitted_at = Instant::now();
+    let mut tx = tx.submit_and_watch().await?;
+    *last_status = "submitted";
+    report(Progress::Submitted {
+        tx_hash: format!("{:?}", tx.extrinsic_hash()),
+    });
 
     // Below we use the low level API to replicate the `wait_for_in_block` behaviour which
     // was removed in subxt 0.33.0. See https://github.com/paritytech/subxt/pull/1237.
@@ -170,9 +693,23 @@ where
     use tx::TxStatus;
 
     while let Some(status) = tx.next().await {
-        match status? {
+        let status = status?;
+        let finalized = matches!(status, TxStatus::InFinalizedBlock(_));
+        match status {
             TxStatus::InBestBlock(tx_in_block)
             | TxStatus::InFinalizedBlock(tx_in_block) => {
+                *last_status = "in_block";
+                timings.record(Phase::InBlock, submitted_at.elapsed());
+                if finalized {
+                    timings.record(Phase::Finalized, submitted_at.elapsed());
+                }
+                let block = format!("{:?}", tx_in_block.block_hash());
+                report(Progress::InBlock {
+                    block: block.clone(),
+                });
+                if finalized {
+                    report(Progress::Finalized { block });
+                }
                 let events = tx_in_block.wait_for_success().await?;
                 return Ok(events)
             }
@@ -185,32 +722,163 @@ where
             TxStatus::Dropped { message } => {
                 return Err(TransactionError::Dropped(message).into())
             }
-            _ => continue,
+            TxStatus::Validated => {
+                *last_status = "validated";
+                report(Progress::Validated);
+            }
+            TxStatus::Broadcasted { num_peers } => {
+                *last_status = "broadcasted";
+                report(Progress::Broadcast { num_peers });
+            }
+            TxStatus::NoLongerInBestBlock => *last_status = "no_longer_in_best_block",
         }
     }
     Err(RpcError::SubscriptionDropped.into())
 }
 
-/// Return the account nonce at the *best* block for an account ID.
-async fn get_account_nonce<C>(
+/// How long to wait for an extrinsic submitted via HTTP to be included in a block, if
+/// no timeout is configured.
+const POLL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How often to check for new blocks while waiting for an extrinsic submitted via HTTP.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Submits the extrinsic without watching its status, which requires a subscription,
+/// and polls the best blocks until it is included in one.
+///
+/// This is used for nodes connected to via HTTP, which poll until the caller gives up.
+async fn submit_and_poll<C>(
+    tx: &tx::SubmittableExtrinsic<C, OnlineClient<C>>,
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    from_block: u64,
+    report: impl Fn(Progress),
+    timings: &Timings,
+    last_status: &mut &'static str,
+) -> core::result::Result<blocks::ExtrinsicEvents<C>, subxt::Error>
+where
+    C: Config,
+{
+    let submitted_at = Instant::now();
+    let tx_hash = tx.submit().await?;
+    *last_status = "submitted";
+    report(Progress::Submitted {
+        tx_hash: format!("{tx_hash:?}"),
+    });
+    let mut next_block = from_block;
+    loop {
+        let best_block = client.blocks().at(get_best_block(rpc).await?).await?;
+        let best_block_number: u64 = best_block.number().into();
+        if best_block_number >= next_block {
+            if let Some(events) =
+                find_included_extrinsic(client, rpc, next_block, &[tx_hash]).await?
+            {
+                timings.record(Phase::InBlock, submitted_at.elapsed());
+                report(Progress::InBlock {
+                    block: format!("{:?}", events.block_hash()),
+                });
+                return Ok(events)
+            }
+            next_block = best_block_number + 1;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Returns `true` if the transaction pool rejected the extrinsic because its nonce was
+/// already used by another transaction.
+fn is_stale_nonce(err: &subxt::Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("priority is too low") || message.contains("transaction is outdated")
+}
+
+/// Return the next account nonce, including the transactions in the transaction pool.
+///
+/// Falls back to the account nonce at the *best* block if the node does not support the
+/// `system_accountNextIndex` RPC.
+async fn next_account_nonce<C>(
     client: &OnlineClient<C>,
     rpc: &LegacyRpcMethods<C>,
     account_id: &C::AccountId,
 ) -> core::result::Result<u64, subxt::Error>
 where
     C: Config,
+    C::AccountId: Serialize,
 {
-    let best_block = rpc
-        .chain_get_block_hash(None)
-        .await?
-        .ok_or(subxt::Error::Other("Best block not found".into()))?;
-    let account_nonce = client
-        .blocks()
-        .at(best_block)
-        .await?
-        .account_nonce(account_id)
-        .await?;
-    Ok(account_nonce)
+    match rpc.system_account_next_index(account_id).await {
+        Ok(nonce) => Ok(nonce),
+        Err(err) => {
+            tracing::debug!("Failed to query the next account index: {err}");
+            client
+                .blocks()
+                .at(get_best_block(rpc).await?)
+                .await?
+                .account_nonce(account_id)
+                .await
+        }
+    }
+}
+
+/// Search the blocks since `from_block` up to the *best* block for one of the
+/// `submitted` extrinsics, returning its events if it was included successfully.
+async fn find_included_extrinsic<C>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    from_block: u64,
+    submitted: &[C::Hash],
+) -> core::result::Result<Option<blocks::ExtrinsicEvents<C>>, subxt::Error>
+where
+    C: Config,
+{
+    use subxt::config::Hasher as _;
+
+    let best_block = client.blocks().at(get_best_block(rpc).await?).await?;
+    let best_block_number: u64 = best_block.number().into();
+    for number in from_block..=best_block_number {
+        let Some(hash) = rpc.chain_get_block_hash(Some(number.into())).await? else {
+            continue
+        };
+        let extrinsics = client.blocks().at(hash).await?.extrinsics().await?;
+        for extrinsic in extrinsics.iter() {
+            let extrinsic = extrinsic?;
+            if !submitted.contains(&C::Hasher::hash_of(&extrinsic.bytes())) {
+                continue
+            }
+            let events = extrinsic.events().await?;
+            for event in events.iter() {
+                let event = event?;
+                if event.pallet_name() == "System"
+                    && event.variant_name() == "ExtrinsicFailed"
+                {
+                    let dispatch_error = subxt::error::DispatchError::decode_from(
+                        event.field_bytes(),
+                        client.metadata(),
+                    )?;
+                    return Err(dispatch_error.into())
+                }
+            }
+            return Ok(Some(events))
+        }
+    }
+    Ok(None)
+}
+
+/// Replace the error returned by the transaction pool for an expired mortal extrinsic
+/// with a human readable explanation.
+fn explain_expired(err: subxt::Error, mortality: Mortality) -> subxt::Error {
+    if err
+        .to_string()
+        .to_lowercase()
+        .contains("ancient birth block")
+    {
+        subxt::Error::Other(format!(
+            "The transaction expired before it was included in a block, it was only valid \
+             for {mortality} blocks. Resubmit the transaction, or increase its validity \
+             with `--mortality`."
+        ))
+    } else {
+        err
+    }
 }
 
 async fn state_call<C, A: Encode, R: Decode>(
@@ -226,6 +894,54 @@ where
     Ok(R::decode(&mut bytes.as_ref())?)
 }
 
+/// Calls the `call` or `instantiate` runtime API of the contracts pallet.
+///
+/// Runtimes which collect the events of a dry-run append them to the result, they are
+/// decoded with the runtime `metadata`.
+async fn dry_run_state_call<C, A: Encode, R: Decode, Balance: Decode>(
+    rpc: &LegacyRpcMethods<C>,
+    metadata: &subxt::Metadata,
+    func: &str,
+    args: A,
+) -> Result<ContractResult<R, Balance, EventRecord<C::Hash>>>
+where
+    C: Config,
+{
+    let params = args.encode();
+    let bytes = rpc.state_call(func, Some(&params), None).await?;
+    decode_dry_run_result(&mut bytes.as_ref(), metadata)
+}
+
+/// Decodes the result of a dry-run, including the events emitted during the dry-run.
+///
+/// Older runtimes don't append the `events` to the result, in which case they are
+/// `None`.
+fn decode_dry_run_result<R: Decode, Balance: Decode, Hash: Decode>(
+    input: &mut &[u8],
+    metadata: &subxt::Metadata,
+) -> Result<ContractResult<R, Balance, EventRecord<Hash>>> {
+    let gas_consumed = Decode::decode(input)?;
+    let gas_required = Decode::decode(input)?;
+    let storage_deposit = Decode::decode(input)?;
+    let debug_message = Decode::decode(input)?;
+    let result = Decode::decode(input)?;
+    let events = if input.is_empty() {
+        None
+    } else {
+        Option::<()>::decode(input)?
+            .map(|()| EventRecord::decode_all(input, metadata))
+            .transpose()?
+    };
+    Ok(ContractResult {
+        gas_consumed,
+        gas_required,
+        storage_deposit,
+        debug_message,
+        result,
+        events,
+    })
+}
+
 /// Fetch the hash of the *best* block (included but not guaranteed to be finalized).
 async fn get_best_block<C>(
     rpc: &LegacyRpcMethods<C>,
@@ -238,14 +954,21 @@ where
         .ok_or(subxt::Error::Other("Best block not found".into()))
 }
 
-fn check_env_types<C>(
+/// Checks that the environment types of the contract match the ones of the chain and of
+/// the `Environment` the extrinsics are instantiated with.
+fn check_env_types<C, E>(
     client: &OnlineClient<C>,
     transcoder: &ContractMessageTranscoder,
     verbosity: &Verbosity,
 ) -> Result<()>
 where
     C: Config,
+    E: Environment,
 {
+    BalanceWidth::check_contract(
+        transcoder.metadata(),
+        BalanceWidth::of::<E::Balance>(),
+    )?;
     compare_node_env_with_contract(
         client.metadata().types(),
         transcoder.metadata(),
@@ -253,6 +976,26 @@ where
     )
 }
 
+/// Returns the label of the constructor or message `name` of the contract, see
+/// [`ContractMessageTranscoder::resolve_label`]. All labels are listed in the error with
+/// verbose output. If `name` was matched fuzzily the label differs from it, which is up
+/// to the caller to point out.
+fn resolve_label(
+    transcoder: &ContractMessageTranscoder,
+    entry_point: EntryPoint,
+    name: &str,
+    fuzzy_match: bool,
+    verbosity: &Verbosity,
+) -> Result<String> {
+    let label = transcoder.resolve_label(
+        entry_point,
+        name,
+        fuzzy_match,
+        *verbosity == Verbosity::Verbose,
+    )?;
+    Ok(label.to_string())
+}
+
 // Converts a Url into a String representation without excluding the default port.
 pub fn url_to_string(url: &url::Url) -> String {
     match (url.port(), url.port_or_known_default()) {
@@ -272,6 +1015,142 @@ pub fn url_to_string(url: &url::Url) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn explain_expired_extrinsic() {
+        let err = subxt::Error::Other(
+            "Invalid Transaction: Transaction has an ancient birth block".into(),
+        );
+        let explained = explain_expired(err, Mortality::Mortal(64)).to_string();
+        assert!(explained.contains("expired"), "{explained}");
+        assert!(explained.contains("64 blocks"), "{explained}");
+
+        let err = subxt::Error::Other("Priority is too low".into());
+        assert_eq!(
+            explain_expired(err, Mortality::Mortal(64)).to_string(),
+            "Other error: Priority is too low"
+        );
+    }
+
+    #[test]
+    fn max_fee_exceeded_error() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".into(),
+            fallback: false,
+        };
+        let err = ErrorVariant::MaxFeeExceeded(MaxFeeExceeded {
+            estimated_fee: DisplayBalance::new(
+                1_500_000_000_000u128,
+                Some(&token_metadata),
+            ),
+            max_fee: DisplayBalance::new(1_000_000_000_000u128, Some(&token_metadata)),
+        });
+        assert_eq!(
+            err.to_string(),
+            "The estimated fee of 1.5UNIT (1500000000000) exceeds the maximum fee of \
+             1UNIT (1000000000000), the transaction was not submitted"
+        );
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "max_fee_exceeded_error": {
+                    "estimated_fee": { "raw": "1500000000000", "denominated": "1.5UNIT" },
+                    "max_fee": { "raw": "1000000000000", "denominated": "1UNIT" },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn stale_nonce_errors() {
+        let err = subxt::Error::Other("Priority is too low: (1 vs 1)".into());
+        assert!(is_stale_nonce(&err));
+        let err =
+            subxt::Error::Other("Invalid Transaction: Transaction is outdated".into());
+        assert!(is_stale_nonce(&err));
+        let err = subxt::Error::Other("Inability to pay some fees".into());
+        assert!(!is_stale_nonce(&err));
+    }
+
+    fn test_metadata() -> subxt::Metadata {
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        subxt::Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode")
+    }
+
+    fn dry_run_result(
+        events: Option<Vec<()>>,
+    ) -> ContractResult<Result<u32, ()>, u128, ()> {
+        ContractResult {
+            gas_consumed: sp_weights::Weight::from_parts(1_000, 10),
+            gas_required: sp_weights::Weight::from_parts(2_000, 20),
+            storage_deposit: pallet_contracts_primitives::StorageDeposit::Charge(100),
+            debug_message: Vec::new(),
+            result: Ok(42),
+            events,
+        }
+    }
+
+    #[test]
+    fn decode_dry_run_result_with_events() {
+        let metadata = test_metadata();
+        let pallet = metadata.pallet_by_name("Contracts").unwrap();
+        let variant = pallet
+            .event_variants()
+            .unwrap()
+            .iter()
+            .find(|variant| variant.name == "ContractEmitted")
+            .unwrap();
+        let fields = ([1u8; 32], vec![1u8, 2, 3]).encode();
+        let topics = vec![sp_core::H256::repeat_byte(2)];
+
+        let mut bytes = dry_run_result(Some(vec![()])).encode();
+        bytes.extend(subxt::events::Phase::ApplyExtrinsic(1).encode());
+        bytes.extend([pallet.index(), variant.index]);
+        bytes.extend(&fields);
+        bytes.extend(topics.encode());
+
+        let result = decode_dry_run_result::<Result<u32, ()>, u128, sp_core::H256>(
+            &mut &bytes[..],
+            &metadata,
+        )
+        .unwrap();
+        assert_eq!(result.result, Ok(42));
+        assert_eq!(
+            result.events,
+            Some(vec![EventRecord {
+                phase: subxt::events::Phase::ApplyExtrinsic(1),
+                pallet: "Contracts".to_string(),
+                variant_index: variant.index,
+                variant: "ContractEmitted".to_string(),
+                field_bytes: fields,
+                topics,
+            }])
+        );
+    }
+
+    #[test]
+    fn decode_dry_run_result_without_events() {
+        let metadata = test_metadata();
+
+        let bytes = dry_run_result(None).encode();
+        let result = decode_dry_run_result::<Result<u32, ()>, u128, sp_core::H256>(
+            &mut &bytes[..],
+            &metadata,
+        )
+        .unwrap();
+        assert_eq!(result.events, None);
+
+        // runtimes which don't collect events omit the field entirely
+        let result = decode_dry_run_result::<Result<u32, ()>, u128, sp_core::H256>(
+            &mut &bytes[..bytes.len() - 1],
+            &metadata,
+        )
+        .unwrap();
+        assert_eq!(result.result, Ok(42));
+        assert_eq!(result.events, None);
+    }
+
     #[test]
     fn url_to_string_works() {
         // with custom port
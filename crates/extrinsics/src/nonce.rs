@@ -0,0 +1,97 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    future::Future,
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+/// The nonces of the account submitting extrinsics, shared by the commands whose
+/// [`crate::ExtrinsicOpts`] it is set in.
+///
+/// The next nonce is fetched from the chain once and then incremented locally for
+/// every extrinsic, so that extrinsics can be submitted back-to-back without waiting
+/// for the previous one to be included in a block.
+#[derive(Debug, Clone, Default)]
+pub struct NonceHandle {
+    next: Arc<Mutex<Option<u64>>>,
+}
+
+impl NonceHandle {
+    /// Creates a handle which uses `nonce` for the next extrinsic, instead of fetching
+    /// it from the chain.
+    pub fn new(nonce: u64) -> Self {
+        Self {
+            next: Arc::new(Mutex::new(Some(nonce))),
+        }
+    }
+
+    /// Returns the nonce of the next extrinsic, or `None` if it is fetched from the
+    /// chain by the next extrinsic.
+    pub async fn peek(&self) -> Option<u64> {
+        *self.next.lock().await
+    }
+
+    /// Forgets the nonce of the next extrinsic, so that it is fetched from the chain
+    /// again, e.g. after the account submitted an extrinsic with another tool or an
+    /// extrinsic was rejected.
+    pub async fn resync(&self) {
+        *self.next.lock().await = None;
+    }
+
+    /// Returns the nonce of the next extrinsic, fetching it with `fetch` if it is not
+    /// known, and increments it.
+    pub(crate) async fn next<F, Err>(&self, fetch: F) -> Result<u64, Err>
+    where
+        F: Future<Output = Result<u64, Err>>,
+    {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => fetch.await?,
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn increments_locally_until_resynced() {
+        let nonce = NonceHandle::default();
+        let fetched = |value| async move { Ok::<_, ()>(value) };
+        assert_eq!(nonce.next(fetched(5)).await, Ok(5));
+        assert_eq!(nonce.next(fetched(5)).await, Ok(6));
+        assert_eq!(nonce.clone().next(fetched(5)).await, Ok(7));
+        assert_eq!(nonce.peek().await, Some(8));
+
+        nonce.resync().await;
+        assert_eq!(nonce.peek().await, None);
+        assert_eq!(nonce.next(fetched(9)).await, Ok(9));
+        assert_eq!(NonceHandle::new(3).next(fetched(9)).await, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn keeps_the_nonce_if_fetching_fails() {
+        let nonce = NonceHandle::default();
+        assert_eq!(nonce.next(async { Err("offline") }).await, Err("offline"));
+        assert_eq!(nonce.peek().await, None);
+    }
+}
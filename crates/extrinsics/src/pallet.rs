@@ -0,0 +1,279 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_build::Target;
+use scale::Encode;
+use subxt::utils::H160;
+
+/// The pallet which executes contracts on the target chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractsPallet {
+    /// `pallet-contracts`, with contract addresses being account ids.
+    Contracts,
+    /// `pallet-revive`, with contract addresses being 20 byte [`H160`] values.
+    Revive,
+}
+
+impl ContractsPallet {
+    /// Detect the contracts pallet exposed by the chain from its metadata.
+    ///
+    /// If the chain exposes both pallets, `pallet-contracts` is preferred.
+    pub fn detect(metadata: &subxt::Metadata) -> Result<Self> {
+        Self::find(|name| metadata.pallet_by_name(name).is_some())
+    }
+
+    /// Returns the first pallet for which `has_pallet` returns `true`.
+    fn find(has_pallet: impl Fn(&str) -> bool) -> Result<Self> {
+        [Self::Contracts, Self::Revive]
+            .into_iter()
+            .find(|pallet| has_pallet(pallet.name()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No contracts pallet found: the chain exposes neither the `Contracts` \
+                     nor the `Revive` pallet"
+                )
+            })
+    }
+
+    /// Returns the name of the pallet in the chain metadata.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Contracts => "Contracts",
+            Self::Revive => "Revive",
+        }
+    }
+
+    /// Returns the target the contract code executed by the pallet is built for.
+    pub fn target(&self) -> Target {
+        match self {
+            Self::Contracts => Target::Wasm,
+            Self::Revive => Target::RiscV,
+        }
+    }
+
+    /// Returns an error if the contract code built for `target` cannot be executed by
+    /// the pallet.
+    pub fn check_target(&self, target: Target) -> Result<()> {
+        let code = |target| {
+            match target {
+                Target::Wasm => "Wasm",
+                Target::RiscV => "PolkaVM (RISC-V)",
+            }
+        };
+        if target != self.target() {
+            return Err(anyhow!(
+                "The chain uses {self}, which executes {} contracts, but the contract \
+                 artifact contains {} code. Rebuild the contract with \
+                 `cargo contract build --target {}`",
+                code(self.target()),
+                code(target),
+                match self.target() {
+                    Target::Wasm => "wasm",
+                    Target::RiscV => "riscv",
+                }
+            ))
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the runtime API function `method` of the pallet.
+    pub(crate) fn runtime_api(&self, method: &str) -> String {
+        let api = match self {
+            Self::Contracts => "ContractsApi",
+            Self::Revive => "ReviveApi",
+        };
+        format!("{api}_{method}")
+    }
+}
+
+impl Display for ContractsPallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Contracts => write!(f, "pallet-contracts"),
+            Self::Revive => write!(f, "pallet-revive"),
+        }
+    }
+}
+
+/// The address of a contract, depending on the [`ContractsPallet`] of the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractAddress<AccountId> {
+    /// The account id of a `pallet-contracts` contract.
+    AccountId(AccountId),
+    /// The address of a `pallet-revive` contract.
+    H160(H160),
+}
+
+impl<AccountId> ContractAddress<AccountId> {
+    /// Returns the account id of a `pallet-contracts` contract.
+    pub fn account_id(&self) -> Option<&AccountId> {
+        match self {
+            Self::AccountId(account_id) => Some(account_id),
+            Self::H160(_) => None,
+        }
+    }
+
+    /// Returns the address of a `pallet-revive` contract.
+    pub fn h160(&self) -> Option<&H160> {
+        match self {
+            Self::AccountId(_) => None,
+            Self::H160(address) => Some(address),
+        }
+    }
+
+    /// Returns an error if the address is not of the kind used by the given `pallet`.
+    pub fn check_pallet(&self, pallet: ContractsPallet) -> Result<()> {
+        match (self, pallet) {
+            (Self::AccountId(_), ContractsPallet::Contracts)
+            | (Self::H160(_), ContractsPallet::Revive) => Ok(()),
+            (Self::AccountId(_), ContractsPallet::Revive) => {
+                Err(anyhow!(
+                    "The chain uses {pallet}, which expects a 20 byte H160 contract \
+                     address, but an account id was provided"
+                ))
+            }
+            (Self::H160(_), ContractsPallet::Contracts) => {
+                Err(anyhow!(
+                    "The chain uses {pallet}, which expects an account id as contract \
+                     address, but a 20 byte H160 address was provided"
+                ))
+            }
+        }
+    }
+}
+
+impl<AccountId: Encode> Encode for ContractAddress<AccountId> {
+    fn size_hint(&self) -> usize {
+        match self {
+            Self::AccountId(account_id) => account_id.size_hint(),
+            Self::H160(address) => address.size_hint(),
+        }
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        match self {
+            Self::AccountId(account_id) => account_id.encode_to(dest),
+            Self::H160(address) => address.encode_to(dest),
+        }
+    }
+}
+
+impl<AccountId: Display> Display for ContractAddress<AccountId> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccountId(account_id) => write!(f, "{account_id}"),
+            Self::H160(address) => write!(f, "0x{}", hex::encode(address)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ss58Prefix;
+    use subxt::utils::AccountId32;
+
+    fn find_in(pallets: &[&str]) -> Result<ContractsPallet> {
+        ContractsPallet::find(|name| pallets.contains(&name))
+    }
+
+    #[test]
+    fn detect_pallet() {
+        assert_eq!(
+            find_in(&["System", "Contracts"]).unwrap(),
+            ContractsPallet::Contracts
+        );
+        assert_eq!(
+            find_in(&["System", "Revive"]).unwrap(),
+            ContractsPallet::Revive
+        );
+        assert_eq!(
+            find_in(&["Revive", "Contracts"]).unwrap(),
+            ContractsPallet::Contracts
+        );
+    }
+
+    #[test]
+    fn detect_pallet_without_contracts_pallet_fails() {
+        let err = find_in(&["System", "Balances"]).unwrap_err().to_string();
+        assert!(err.starts_with("No contracts pallet found"), "{err}");
+    }
+
+    #[test]
+    fn runtime_api_names() {
+        assert_eq!(
+            ContractsPallet::Contracts.runtime_api("call"),
+            "ContractsApi_call"
+        );
+        assert_eq!(
+            ContractsPallet::Revive.runtime_api("instantiate"),
+            "ReviveApi_instantiate"
+        );
+    }
+
+    #[test]
+    fn contract_address_encoding_and_display() {
+        let h160 = ContractAddress::<AccountId32>::H160(H160::repeat_byte(0xab));
+        assert_eq!(h160.encode(), vec![0xab; 20]);
+        assert_eq!(h160.to_string(), format!("0x{}", "ab".repeat(20)));
+        assert_eq!(
+            Ss58Prefix::default().format_account(&h160),
+            format!("0x{}", "ab".repeat(20))
+        );
+
+        let account = ContractAddress::AccountId(AccountId32([1u8; 32]));
+        assert_eq!(account.encode(), vec![1u8; 32]);
+    }
+
+    #[test]
+    fn contract_address_must_match_pallet() {
+        let h160 = ContractAddress::<AccountId32>::H160(H160::zero());
+        let account = ContractAddress::AccountId(AccountId32([0u8; 32]));
+
+        assert!(h160.check_pallet(ContractsPallet::Revive).is_ok());
+        assert!(h160.check_pallet(ContractsPallet::Contracts).is_err());
+        assert!(account.check_pallet(ContractsPallet::Contracts).is_ok());
+        assert!(account.check_pallet(ContractsPallet::Revive).is_err());
+    }
+
+    #[test]
+    fn code_target_must_match_pallet() {
+        assert!(ContractsPallet::Contracts
+            .check_target(Target::Wasm)
+            .is_ok());
+        assert!(ContractsPallet::Revive.check_target(Target::RiscV).is_ok());
+
+        let err = ContractsPallet::Revive
+            .check_target(Target::Wasm)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "The chain uses pallet-revive, which executes PolkaVM (RISC-V) contracts, but \
+             the contract artifact contains Wasm code. Rebuild the contract with \
+             `cargo contract build --target riscv`"
+        );
+        assert!(ContractsPallet::Contracts
+            .check_target(Target::RiscV)
+            .is_err());
+    }
+}
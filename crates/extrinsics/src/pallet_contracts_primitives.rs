@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::{
+    DisplayBalance,
+    TokenMetadata,
+};
 use pallet_contracts_uapi::ReturnFlags;
 use scale::{
     Decode,
@@ -26,6 +30,7 @@ use sp_runtime::{
     RuntimeDebug,
 };
 use sp_weights::Weight;
+use std::fmt;
 
 // A copy of primitive types defined within `pallet_contracts`, required for RPC calls.
 
@@ -184,3 +189,119 @@ pub enum StorageDeposit<Balance> {
     /// to the involved deposit accounts.
     Charge(Balance),
 }
+
+impl<Balance: Copy + From<u8>> StorageDeposit<Balance> {
+    /// Returns the charged amount, or zero if the deposit was refunded.
+    pub fn charge_or_zero(&self) -> Balance {
+        match self {
+            Self::Charge(amount) => *amount,
+            Self::Refund(_) => 0u8.into(),
+        }
+    }
+}
+
+impl<Balance> StorageDeposit<Balance>
+where
+    Balance: Copy + From<u8> + Into<u128> + TryFrom<u128>,
+{
+    /// Returns the charged amount increased by `buffer_percent` percent, to be used as
+    /// the storage deposit limit of a transaction. A refund results in a zero limit.
+    pub fn limit_with_buffer(&self, buffer_percent: u64) -> anyhow::Result<Balance> {
+        let charge: u128 = self.charge_or_zero().into();
+        charge
+            .checked_mul(100 + u128::from(buffer_percent))
+            .and_then(|limit| limit.checked_add(99))
+            .map(|limit| limit / 100)
+            .and_then(|limit| Balance::try_from(limit).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The storage deposit {charge} increased by {buffer_percent}% exceeds \
+                    the balance type"
+                )
+            })
+    }
+}
+
+impl<Balance: Copy + Into<u128>> StorageDeposit<Balance> {
+    /// Denominates the charged or refunded amount with the `token_metadata`, if given.
+    pub fn display(
+        &self,
+        token_metadata: Option<&TokenMetadata>,
+    ) -> StorageDeposit<DisplayBalance> {
+        match self {
+            Self::Refund(amount) => {
+                StorageDeposit::Refund(DisplayBalance::new(*amount, token_metadata))
+            }
+            Self::Charge(amount) => {
+                StorageDeposit::Charge(DisplayBalance::new(*amount, token_metadata))
+            }
+        }
+    }
+}
+
+impl fmt::Display for StorageDeposit<DisplayBalance> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Refund(amount) => write!(f, "{amount} refunded"),
+            Self::Charge(amount) => write!(f, "{amount} charged"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_deposit_limit_with_buffer() {
+        assert_eq!(
+            StorageDeposit::Charge(1000u128)
+                .limit_with_buffer(0)
+                .unwrap(),
+            1000
+        );
+        assert_eq!(
+            StorageDeposit::Charge(1000u128)
+                .limit_with_buffer(15)
+                .unwrap(),
+            1150
+        );
+        assert_eq!(
+            StorageDeposit::Charge(7u64).limit_with_buffer(10).unwrap(),
+            8
+        );
+        assert_eq!(
+            StorageDeposit::Refund(1000u128)
+                .limit_with_buffer(20)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            StorageDeposit::Charge(0u128).limit_with_buffer(20).unwrap(),
+            0
+        );
+        assert!(StorageDeposit::Charge(u64::MAX)
+            .limit_with_buffer(1)
+            .is_err());
+    }
+
+    #[test]
+    fn display_storage_deposit() {
+        let token_metadata = TokenMetadata {
+            token_decimals: 12,
+            symbol: "UNIT".into(),
+            fallback: false,
+        };
+        let deposit =
+            StorageDeposit::Charge(1_500_000_000u128).display(Some(&token_metadata));
+        assert_eq!(deposit.to_string(), "1.5mUNIT (1500000000) charged");
+        assert_eq!(
+            serde_json::to_value(&deposit).unwrap(),
+            serde_json::json!({ "Charge": { "raw": "1500000000", "denominated": "1.5mUNIT" } })
+        );
+        assert_eq!(
+            StorageDeposit::Refund(7u64).display(None).to_string(),
+            "7 refunded"
+        );
+    }
+}
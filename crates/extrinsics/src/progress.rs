@@ -0,0 +1,108 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use colored::Colorize;
+use serde::Serialize;
+use sp_weights::Weight;
+
+/// A step in the progress of a command, reported as a JSON line on stderr so that tools
+/// consuming the JSON output on stdout can display it.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Progress {
+    /// Connecting to the node.
+    Connecting,
+    /// Dry-running the given message or constructor.
+    DryRunning { entity: String },
+    /// The dry-run succeeded and estimated the gas required.
+    GasEstimated { gas_required: Weight },
+    /// The extrinsic was submitted to the transaction pool.
+    Submitted { tx_hash: String },
+    /// The extrinsic was validated by the transaction pool.
+    Validated,
+    /// The extrinsic was broadcast to the given number of peers.
+    Broadcast { num_peers: u32 },
+    /// The extrinsic was included in a block.
+    InBlock { block: String },
+    /// The block including the extrinsic was finalized.
+    Finalized { block: String },
+}
+
+impl Progress {
+    /// Print the progress as a single line of JSON to stderr.
+    pub fn report(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => eprintln!("{json}"),
+            Err(err) => tracing::debug!("Failed to serialize progress: {err}"),
+        }
+    }
+
+    /// Print the status of a submitted extrinsic as a single line to stderr, for humans.
+    /// The steps before the submission are not printed.
+    pub fn report_status(&self) {
+        let (status, detail) = match self {
+            Self::Connecting | Self::DryRunning { .. } | Self::GasEstimated { .. } => {
+                return
+            }
+            Self::Submitted { tx_hash } => ("Submitted", tx_hash.clone()),
+            Self::Validated => ("Validated", "by the transaction pool".to_string()),
+            Self::Broadcast { num_peers } => {
+                ("Broadcast", format!("to {num_peers} peers"))
+            }
+            Self::InBlock { block } => ("In block", block.clone()),
+            Self::Finalized { block } => ("Finalized", block.clone()),
+        };
+        eprintln!("{:>12} {detail}", status.bright_cyan().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_progress() {
+        assert_eq!(
+            serde_json::to_string(&Progress::Connecting).unwrap(),
+            r#"{"status":"connecting"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Progress::DryRunning {
+                entity: "new".into()
+            })
+            .unwrap(),
+            r#"{"status":"dry_running","entity":"new"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Progress::GasEstimated {
+                gas_required: Weight::from_parts(1, 2)
+            })
+            .unwrap(),
+            r#"{"status":"gas_estimated","gas_required":{"ref_time":1,"proof_size":2}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Progress::InBlock {
+                block: "0x01".into()
+            })
+            .unwrap(),
+            r#"{"status":"in_block","block":"0x01"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Progress::Broadcast { num_peers: 3 }).unwrap(),
+            r#"{"status":"broadcast","num_peers":3}"#
+        );
+    }
+}
@@ -17,21 +17,22 @@
 use super::{
     events::CodeRemoved,
     submit_extrinsic,
+    Connection,
     ContractMessageTranscoder,
     ErrorVariant,
 };
 use crate::{
     extrinsic_calls::RemoveCode,
-    extrinsic_opts::ExtrinsicOpts,
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
+    },
 };
 
 use anyhow::Result;
 use ink_env::Environment;
 use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
-    },
+    backend::legacy::LegacyRpcMethods,
     blocks::ExtrinsicEvents,
     config,
     ext::{
@@ -47,6 +48,7 @@ use subxt::{
 pub struct RemoveCommandBuilder<C: Config, E: Environment, Signer: Clone> {
     code_hash: Option<C::Hash>,
     extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
+    connection: Option<Connection<C>>,
 }
 
 impl<C: Config, E: Environment, Signer> RemoveCommandBuilder<C, E, Signer>
@@ -60,6 +62,7 @@ where
         RemoveCommandBuilder {
             code_hash: None,
             extrinsic_opts,
+            connection: None,
         }
     }
 
@@ -69,6 +72,14 @@ where
         this.code_hash = code_hash;
         this
     }
+
+    /// Sets the connection to the node to reuse, otherwise a new connection is
+    /// established to the url of the extrinsic options.
+    pub fn connection(self, connection: Option<Connection<C>>) -> Self {
+        let mut this = self;
+        this.connection = connection;
+        this
+    }
 }
 
 impl<C: Config, E: Environment, Signer> RemoveCommandBuilder<C, E, Signer>
@@ -88,7 +99,7 @@ where
     /// removal, or an error in case of failure.
     pub async fn done(self) -> Result<RemoveExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
 
         let artifacts_path = artifacts.artifact_path().to_path_buf();
 
@@ -103,10 +114,12 @@ where
             )),
         }?;
 
-        let url = self.extrinsic_opts.url();
-        let rpc_cli = RpcClient::from_url(&url).await?;
-        let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
-        let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => Connection::connect(&self.extrinsic_opts.url()).await?,
+        };
+        let client = connection.client().clone();
+        let rpc = connection.rpc().clone();
 
         Ok(RemoveExec {
             final_code_hash,
@@ -130,7 +143,9 @@ impl<C: Config, E: Environment, Signer> RemoveExec<C, E, Signer>
 where
     C::Hash: IntoVisitor + EncodeAsType,
     C::AccountId: IntoVisitor,
-    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams: Default,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+    C::AccountId: serde::Serialize,
     Signer: tx::Signer<C> + Clone,
 {
     /// Removes a contract code from the blockchain.
@@ -150,8 +165,7 @@ where
 
         let call = RemoveCode::new(code_hash).build();
 
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        let events = submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?;
 
         let code_removed =
             events.find_first::<CodeRemoved<C::Hash, C::AccountId, E::Balance>>()?;
@@ -32,7 +32,10 @@ use subxt::{
     },
 };
 
-use crate::url_to_string;
+use crate::{
+    rpc_client,
+    url_to_string,
+};
 use anyhow::{
     anyhow,
     bail,
@@ -80,7 +83,7 @@ pub struct RpcRequest(RpcClient);
 impl RpcRequest {
     /// Creates a new `RpcRequest` instance.
     pub async fn new(url: &url::Url) -> Result<Self> {
-        let rpc = RpcClient::from_url(url_to_string(url)).await?;
+        let rpc = rpc_client(&url_to_string(url)).await?;
         Ok(Self(rpc))
     }
 
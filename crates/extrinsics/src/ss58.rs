@@ -0,0 +1,247 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    fmt::{
+        self,
+        Display,
+    },
+    str::FromStr,
+};
+
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use contract_transcode::{
+    AccountId32,
+    SUBSTRATE_SS58_PREFIX,
+};
+use scale::Encode;
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    Config,
+};
+use url::Url;
+
+use crate::{
+    rpc_client,
+    url_to_string,
+};
+
+/// The SS58 address format prefix of a chain, used to display account ids the same way
+/// as block explorers and wallets for that chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ss58Prefix(u16);
+
+impl Ss58Prefix {
+    /// The largest prefix which can be represented by the SS58 format.
+    const MAX: u16 = 16_383;
+
+    /// Query the [Ss58Prefix] through the node's RPC.
+    ///
+    /// Falls back to the generic Substrate prefix if the chain does not report its
+    /// `ss58Format` in the system properties.
+    pub async fn query<C: Config>(url: &Url) -> Result<Self> {
+        let rpc_cli = rpc_client(&url_to_string(url)).await?;
+        Self::query_with(&LegacyRpcMethods::<C>::new(rpc_cli)).await
+    }
+
+    /// Query the [Ss58Prefix] through an existing connection to the node, see
+    /// [`Self::query`].
+    pub async fn query_with<C: Config>(rpc: &LegacyRpcMethods<C>) -> Result<Self> {
+        let sys_props = rpc.system_properties().await?;
+
+        match sys_props.get("ss58Format") {
+            Some(ss58_format) => {
+                let prefix = ss58_format
+                    .as_u64()
+                    .context("error converting ss58Format to u64")?;
+                u16::try_from(prefix)
+                    .map_err(|_| anyhow!("ss58Format {prefix} is out of range"))
+                    .and_then(Self::try_from)
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Returns the numeric value of the prefix.
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    /// Encode the given account id as an SS58 address with this prefix.
+    ///
+    /// Account ids which are not 32 bytes long cannot be SS58 encoded by this tool, they
+    /// are displayed as hex instead.
+    pub fn format_account<AccountId: Encode>(&self, account: &AccountId) -> String {
+        let bytes = account.encode();
+        match AccountId32::try_from(bytes.as_slice()) {
+            Ok(account) => account.to_ss58check_with_prefix(self.0),
+            Err(()) => format!("0x{}", hex::encode(bytes)),
+        }
+    }
+
+    /// Returns the `account` in both the SS58 format and as hex, for JSON outputs.
+    pub fn address<AccountId: Encode>(&self, account: &AccountId) -> Address {
+        let bytes = account.encode();
+        Address {
+            ss58: AccountId32::try_from(bytes.as_slice())
+                .ok()
+                .map(|account| account.to_ss58check_with_prefix(self.0)),
+            hex: format!("0x{}", hex::encode(bytes)),
+        }
+    }
+}
+
+/// An account id in the JSON output, with both its SS58 address for wallets and its
+/// raw public key for indexers.
+///
+/// Displayed in the SS58 format, or as hex if the account id is not 32 bytes long.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Address {
+    /// The SS58 address with the prefix of the chain, absent for account ids which are
+    /// not 32 bytes long, e.g. `pallet-revive` contract addresses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ss58: Option<String>,
+    /// The `0x` prefixed hex encoded account id
+    pub hex: String,
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.ss58.as_deref().unwrap_or(&self.hex))
+    }
+}
+
+impl Default for Ss58Prefix {
+    fn default() -> Self {
+        Self(SUBSTRATE_SS58_PREFIX)
+    }
+}
+
+impl TryFrom<u16> for Ss58Prefix {
+    type Error = anyhow::Error;
+
+    fn try_from(prefix: u16) -> Result<Self> {
+        if prefix > Self::MAX {
+            anyhow::bail!(
+                "Invalid SS58 prefix {prefix}, must not be greater than {}",
+                Self::MAX
+            )
+        }
+        Ok(Self(prefix))
+    }
+}
+
+impl FromStr for Ss58Prefix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let prefix = s
+            .parse::<u16>()
+            .map_err(|e| anyhow!("Invalid SS58 prefix '{s}': {e}"))?;
+        Self::try_from(prefix)
+    }
+}
+
+impl Display for Ss58Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn alice() -> subxt::utils::AccountId32 {
+        ALICE.parse().unwrap()
+    }
+
+    #[test]
+    fn format_account_with_default_prefix() {
+        assert_eq!(Ss58Prefix::default().format_account(&alice()), ALICE);
+    }
+
+    #[test]
+    fn format_account_with_chain_prefix() {
+        let polkadot = Ss58Prefix::try_from(0).unwrap();
+        let kusama = Ss58Prefix::try_from(2).unwrap();
+
+        assert_eq!(
+            polkadot.format_account(&alice()),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            kusama.format_account(&alice()),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+    }
+
+    #[test]
+    fn format_account_not_32_bytes_as_hex() {
+        let prefix = Ss58Prefix::default();
+        assert_eq!(
+            prefix.format_account(&[1u8; 20]),
+            format!("0x{}", "01".repeat(20))
+        );
+    }
+
+    #[test]
+    fn address_encodings_refer_to_the_same_key() {
+        let kusama = Ss58Prefix::try_from(2).unwrap();
+        let address = kusama.address(&alice());
+        assert_eq!(address.to_string(), kusama.format_account(&alice()));
+
+        let (account, prefix) =
+            AccountId32::from_ss58check_with_prefix(address.ss58.as_deref().unwrap())
+                .unwrap();
+        assert_eq!(prefix, 2);
+        assert_eq!(address.hex, format!("0x{}", hex::encode(account.0)));
+        assert_eq!(account.0, alice().0);
+
+        let json = serde_json::to_value(&address).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ss58": "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F",
+                "hex": address.hex,
+            })
+        );
+    }
+
+    #[test]
+    fn address_not_32_bytes_has_no_ss58() {
+        let address = Ss58Prefix::default().address(&[1u8; 20]);
+        assert_eq!(address.ss58, None);
+        assert_eq!(address.to_string(), format!("0x{}", "01".repeat(20)));
+        assert_eq!(
+            serde_json::to_value(&address).unwrap(),
+            serde_json::json!({ "hex": format!("0x{}", "01".repeat(20)) })
+        );
+    }
+
+    #[test]
+    fn parse_prefix() {
+        assert_eq!("5".parse::<Ss58Prefix>().unwrap().value(), 5);
+        assert!("16384".parse::<Ss58Prefix>().is_err());
+        assert!("astar".parse::<Ss58Prefix>().is_err());
+    }
+}
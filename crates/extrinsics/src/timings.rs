@@ -0,0 +1,201 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{
+    ser::SerializeMap,
+    Serialize,
+    Serializer,
+};
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// A phase of an extrinsic command whose duration is measured by [`Timings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// Connecting to the node and fetching its metadata.
+    Connect,
+    /// Querying the token metadata of the chain.
+    TokenMetadata,
+    /// Encoding the arguments of the message or constructor.
+    Transcode,
+    /// Dry-running the extrinsic, possibly more than once.
+    DryRun,
+    /// Signing the extrinsic and estimating its fee.
+    Sign,
+    /// From submitting the extrinsic until it is included in a block.
+    InBlock,
+    /// From submitting the extrinsic until it is included in a finalized block, only
+    /// measured if the node reports the finalized block before returning.
+    Finalized,
+}
+
+impl Phase {
+    /// Returns the label of the phase in the human readable output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Connect => "Connect",
+            Self::TokenMetadata => "Token",
+            Self::Transcode => "Transcode",
+            Self::DryRun => "Dry-run",
+            Self::Sign => "Sign",
+            Self::InBlock => "In block",
+            Self::Finalized => "Finalized",
+        }
+    }
+}
+
+/// Measures the durations of the phases of an extrinsic command, shared by the command
+/// and the [`crate::ExtrinsicOpts`] it builds.
+///
+/// Phases measured more than once, e.g. repeated dry-runs, add up.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    started: Instant,
+    phases: Arc<Mutex<Vec<(Phase, Duration)>>>,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Default::default(),
+        }
+    }
+}
+
+impl Timings {
+    /// Adds the `duration` to the `phase`.
+    pub fn record(&self, phase: Phase, duration: Duration) {
+        if let Ok(mut phases) = self.phases.lock() {
+            match phases.iter_mut().find(|(measured, _)| *measured == phase) {
+                Some((_, total)) => *total += duration,
+                None => phases.push((phase, duration)),
+            }
+        }
+    }
+
+    /// Awaits the `future`, adding its duration to the `phase`.
+    pub async fn measure<F: Future>(&self, phase: Phase, future: F) -> F::Output {
+        let started = Instant::now();
+        let output = future.await;
+        self.record(phase, started.elapsed());
+        output
+    }
+
+    /// Runs `f`, adding its duration to the `phase`.
+    pub fn measure_sync<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let output = f();
+        self.record(phase, started.elapsed());
+        output
+    }
+
+    /// Returns the durations measured so far, and the total duration of the command.
+    pub fn report(&self) -> TimingsReport {
+        let phases = self
+            .phases
+            .lock()
+            .map(|phases| {
+                phases
+                    .iter()
+                    .map(|(phase, duration)| (*phase, millis(*duration)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        TimingsReport {
+            phases,
+            total: millis(self.started.elapsed()),
+        }
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+/// The durations of the phases of a command in milliseconds, in the order they were
+/// first measured. Serialized as an object mapping the phases to their durations.
+#[derive(Debug)]
+pub struct TimingsReport {
+    pub phases: Vec<(Phase, u64)>,
+    /// The duration of the whole command.
+    pub total: u64,
+}
+
+impl Serialize for TimingsReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.phases.len() + 1))?;
+        for (phase, duration) in &self.phases {
+            map.serialize_entry(phase, duration)?;
+        }
+        map.serialize_entry("total", &self.total)?;
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phases_add_up_in_order() {
+        let timings = Timings::default();
+        timings.record(Phase::Connect, Duration::from_millis(400));
+        timings.record(Phase::DryRun, Duration::from_millis(120));
+        timings.measure_sync(Phase::Transcode, || ());
+        timings.record(Phase::DryRun, Duration::from_millis(80));
+
+        assert_eq!(
+            timings.report().phases,
+            vec![
+                (Phase::Connect, 400),
+                (Phase::DryRun, 200),
+                (Phase::Transcode, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_report() {
+        let report = TimingsReport {
+            phases: vec![(Phase::TokenMetadata, 12), (Phase::InBlock, 6000)],
+            total: 6100,
+        };
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"token_metadata":12,"in_block":6000,"total":6100}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn clones_share_the_measurements() {
+        let timings = Timings::default();
+        let opts_timings = timings.clone();
+        opts_timings.measure(Phase::Sign, async {}).await;
+        let report = timings.report();
+        assert!(report.phases.iter().any(|(phase, _)| *phase == Phase::Sign));
+    }
+}
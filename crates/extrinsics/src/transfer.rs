@@ -0,0 +1,174 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    contract_info::{
+        contract_account,
+        contract_exists,
+    },
+    submit_extrinsic,
+    Connection,
+    ContractAddress,
+    ErrorVariant,
+};
+use crate::{
+    extrinsic_calls::Transfer,
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
+    },
+};
+
+use anyhow::Result;
+use ink_env::Environment;
+use scale::{
+    Decode,
+    Encode,
+};
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    blocks::ExtrinsicEvents,
+    config,
+    ext::scale_encode::EncodeAsType,
+    tx,
+    Config,
+    OnlineClient,
+};
+
+/// A builder for the transfer command.
+pub struct TransferCommandBuilder<C: Config, E: Environment, Signer: Clone> {
+    recipient: ContractAddress<C::AccountId>,
+    value: E::Balance,
+    allow_death: bool,
+    extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
+    connection: Option<Connection<C>>,
+}
+
+impl<C: Config, E: Environment, Signer> TransferCommandBuilder<C, E, Signer>
+where
+    Signer: tx::Signer<C> + Clone,
+{
+    /// Returns a clean builder for [`TransferExec`], transferring `value` to the
+    /// `recipient`.
+    pub fn new(
+        recipient: ContractAddress<C::AccountId>,
+        value: E::Balance,
+        extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
+    ) -> TransferCommandBuilder<C, E, Signer> {
+        TransferCommandBuilder {
+            recipient,
+            value,
+            allow_death: false,
+            extrinsic_opts,
+            connection: None,
+        }
+    }
+
+    /// Allow the transfer to reap the sender's account, using `transfer_allow_death`
+    /// instead of `transfer_keep_alive`.
+    pub fn allow_death(self, allow_death: bool) -> Self {
+        let mut this = self;
+        this.allow_death = allow_death;
+        this
+    }
+
+    /// Sets the connection to the node to reuse, otherwise a new connection is
+    /// established to the url of the extrinsic options.
+    pub fn connection(self, connection: Option<Connection<C>>) -> Self {
+        let mut this = self;
+        this.connection = connection;
+        this
+    }
+
+    /// Sets up the client for the subsequent transfer.
+    pub async fn done(self) -> Result<TransferExec<C, E, Signer>> {
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => Connection::connect(&self.extrinsic_opts.url()).await?,
+        };
+        let client = connection.client().clone();
+        let rpc = connection.rpc().clone();
+
+        Ok(TransferExec {
+            recipient: self.recipient,
+            value: self.value,
+            allow_death: self.allow_death,
+            opts: self.extrinsic_opts,
+            rpc,
+            client,
+        })
+    }
+}
+
+pub struct TransferExec<C: Config, E: Environment, Signer: Clone> {
+    recipient: ContractAddress<C::AccountId>,
+    value: E::Balance,
+    allow_death: bool,
+    opts: ExtrinsicOpts<C, E, Signer>,
+    rpc: LegacyRpcMethods<C>,
+    client: OnlineClient<C>,
+}
+
+impl<C: Config, E: Environment, Signer> TransferExec<C, E, Signer>
+where
+    C::AccountId: Clone + Encode + Decode + EncodeAsType + serde::Serialize,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+    E::Balance: EncodeAsType,
+    Signer: tx::Signer<C> + Clone,
+{
+    /// Returns `true` if the recipient is a contract according to the `ContractInfoOf`
+    /// storage of the contracts pallet.
+    pub async fn recipient_is_contract(&self) -> Result<bool> {
+        contract_exists(&self.recipient, &self.rpc, &self.client).await
+    }
+
+    /// Transfers the balance to the recipient, returning the events of the extrinsic.
+    ///
+    /// The balance of a `pallet-revive` contract is held by the account derived from
+    /// its address.
+    pub async fn transfer(&self) -> Result<ExtrinsicEvents<C>, ErrorVariant> {
+        let dest = contract_account::<C>(&self.recipient)?;
+        let call = Transfer::new(dest.into(), self.value).build(self.allow_death);
+        let events = submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?;
+        Ok(events)
+    }
+
+    /// Returns the recipient of the transfer.
+    pub fn recipient(&self) -> &ContractAddress<C::AccountId> {
+        &self.recipient
+    }
+
+    /// Returns the transferred value.
+    pub fn value(&self) -> &E::Balance {
+        &self.value
+    }
+
+    /// Returns whether the transfer may reap the sender's account.
+    pub fn allow_death(&self) -> bool {
+        self.allow_death
+    }
+
+    /// Returns the extrinsic options.
+    pub fn opts(&self) -> &ExtrinsicOpts<C, E, Signer> {
+        &self.opts
+    }
+
+    /// Returns the client.
+    pub fn client(&self) -> &OnlineClient<C> {
+        &self.client
+    }
+}
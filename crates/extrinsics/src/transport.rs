@@ -0,0 +1,363 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use derivative::Derivative;
+use jsonrpsee::{
+    core::{
+        client::ClientT,
+        traits::ToRpcParams,
+    },
+    http_client::{
+        HttpClient,
+        HttpClientBuilder,
+    },
+};
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::{
+            RawRpcFuture,
+            RawRpcSubscription,
+            RawValue,
+            RpcClient,
+            RpcClientT,
+        },
+    },
+    error::RpcError,
+    Config,
+    OnlineClient,
+};
+
+/// Connects to the node at `url`, via HTTP for `http://` and `https://` urls and via
+/// websockets otherwise.
+///
+/// Subscriptions are not supported over HTTP, they fail with an error stating that a
+/// websocket endpoint is required.
+pub async fn rpc_client(url: &str) -> Result<RpcClient> {
+    if is_http_url(url) {
+        let client = HttpClientBuilder::default()
+            .build(url)
+            .map_err(|err| anyhow!("Failed to connect to {url}: {err}"))?;
+        Ok(RpcClient::new(HttpRpcClient(client)))
+    } else {
+        Ok(RpcClient::from_url(url).await?)
+    }
+}
+
+/// Returns whether the node at `url` is connected to via HTTP, which does not support
+/// subscriptions.
+pub fn is_http_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+/// Returns an error if the node at `url` is connected to via HTTP, since the `feature`
+/// requires a subscription.
+pub fn require_websocket(url: &str, feature: &str) -> Result<()> {
+    if is_http_url(url) {
+        return Err(anyhow!(
+            "{feature} requires a websocket endpoint (`ws://` or `wss://`), {url} is an \
+             HTTP endpoint"
+        ))
+    }
+    Ok(())
+}
+
+/// A connection to a node, shared by the queries, dry-runs and submissions of a
+/// command so that it connects to the node only once.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct Connection<C: Config> {
+    rpc_client: RpcClient,
+    client: OnlineClient<C>,
+    rpc: LegacyRpcMethods<C>,
+}
+
+impl<C: Config> Connection<C> {
+    /// Connects to the node at `url`.
+    pub async fn connect(url: &str) -> Result<Self> {
+        Self::from_rpc_client(rpc_client(url).await?).await
+    }
+
+    /// Creates a client on top of an established RPC connection, fetching the metadata
+    /// of the chain.
+    pub async fn from_rpc_client(rpc_client: RpcClient) -> Result<Self> {
+        let client = OnlineClient::from_rpc_client(rpc_client.clone()).await?;
+        let rpc = LegacyRpcMethods::new(rpc_client.clone());
+        Ok(Self {
+            rpc_client,
+            client,
+            rpc,
+        })
+    }
+
+    /// Returns the connection for the configuration `T` of the same chain, reusing the
+    /// connection and the metadata fetched for `C`.
+    pub fn with_config<T: Config<Hash = C::Hash>>(&self) -> Result<Connection<T>> {
+        let client = OnlineClient::from_rpc_client_with(
+            self.client.genesis_hash(),
+            self.client.runtime_version(),
+            self.client.metadata(),
+            self.rpc_client.clone(),
+        )?;
+        Ok(Connection {
+            rpc_client: self.rpc_client.clone(),
+            client,
+            rpc: LegacyRpcMethods::new(self.rpc_client.clone()),
+        })
+    }
+
+    /// Returns the client of the chain.
+    pub fn client(&self) -> &OnlineClient<C> {
+        &self.client
+    }
+
+    /// Returns the legacy RPC methods of the node.
+    pub fn rpc(&self) -> &LegacyRpcMethods<C> {
+        &self.rpc
+    }
+}
+
+/// A JSON-RPC client connected to a node via HTTP.
+struct HttpRpcClient(HttpClient);
+
+/// Already serialized JSON-RPC parameters.
+struct Params(Option<Box<RawValue>>);
+
+impl ToRpcParams for Params {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+impl RpcClientT for HttpRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            ClientT::request(&self.0, method, Params(params))
+                .await
+                .map_err(|err| RpcError::ClientError(Box::new(err)))
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        _params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async move {
+            Err(RpcError::request_rejected(format!(
+                "`{sub}` requires a websocket endpoint (`ws://` or `wss://`), \
+                 subscriptions are not supported over HTTP"
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pallet_contracts_primitives::{
+            ContractExecResult,
+            ExecReturnValue,
+            StorageDeposit,
+        },
+        CallCommandBuilder,
+        ContractAddress,
+        ExtrinsicOptsBuilder,
+        Ss58Prefix,
+        TokenMetadata,
+    };
+    use ink_env::DefaultEnvironment;
+    use pallet_contracts_uapi::ReturnFlags;
+    use scale::Encode;
+    use serde_json::{
+        json,
+        Value,
+    };
+    use std::{
+        path::PathBuf,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+    use subxt::{
+        backend::rpc::rpc_params,
+        PolkadotConfig,
+    };
+    use subxt_signer::sr25519::dev;
+
+    /// A transport answering the requests of a command against a contracts node,
+    /// recording the methods requested.
+    #[derive(Clone, Default)]
+    struct CountingRpcClient {
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CountingRpcClient {
+        fn count(&self, method: &str) -> usize {
+            let requests = self.requests.lock().unwrap();
+            requests.iter().filter(|request| *request == method).count()
+        }
+
+        fn respond(method: &str, params: Value) -> Value {
+            let hash = format!("0x{}", "00".repeat(32));
+            match method {
+                "chain_getFinalizedHead" | "chain_getBlockHash" => json!(hash),
+                "state_getRuntimeVersion" => {
+                    json!({ "specVersion": 1, "transactionVersion": 1 })
+                }
+                "system_properties" => {
+                    json!({ "ss58Format": 42, "tokenDecimals": 12, "tokenSymbol": "UNIT" })
+                }
+                "state_call" => {
+                    let bytes = match params[0].as_str().unwrap() {
+                        "Metadata_metadata_at_version" => {
+                            let metadata =
+                                std::fs::read("src/test_runtime_api/metadata_v15.scale")
+                                    .expect("the metadata must be present");
+                            Some(metadata).encode()
+                        }
+                        "ContractsApi_call" => {
+                            ContractExecResult::<u128, ()> {
+                                gas_consumed: Default::default(),
+                                gas_required: Default::default(),
+                                storage_deposit: StorageDeposit::Charge(0),
+                                debug_message: Vec::new(),
+                                result: Ok(ExecReturnValue {
+                                    flags: ReturnFlags::empty(),
+                                    data: Ok::<bool, ()>(true).encode(),
+                                }),
+                                events: None,
+                            }
+                            .encode()
+                        }
+                        call => panic!("unexpected runtime call {call}"),
+                    };
+                    json!(format!("0x{}", hex::encode(bytes)))
+                }
+                method => panic!("unexpected request {method}"),
+            }
+        }
+    }
+
+    impl RpcClientT for CountingRpcClient {
+        fn request_raw<'a>(
+            &'a self,
+            method: &'a str,
+            params: Option<Box<RawValue>>,
+        ) -> RawRpcFuture<'a, Box<RawValue>> {
+            self.requests.lock().unwrap().push(method.to_string());
+            let params = params
+                .map(|params| serde_json::from_str(params.get()).unwrap())
+                .unwrap_or(Value::Null);
+            let response = Self::respond(method, params);
+            let response = RawValue::from_string(response.to_string()).unwrap();
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn subscribe_raw<'a>(
+            &'a self,
+            sub: &'a str,
+            _params: Option<Box<RawValue>>,
+            _unsub: &'a str,
+        ) -> RawRpcFuture<'a, RawRpcSubscription> {
+            panic!("unexpected subscription {sub}")
+        }
+    }
+
+    #[test]
+    fn http_urls_are_detected() {
+        assert!(is_http_url("http://localhost:9933/"));
+        assert!(is_http_url("https://rpc.example.com:443/"));
+        assert!(!is_http_url("ws://localhost:9944/"));
+        assert!(!is_http_url("wss://rpc.example.com:443/"));
+
+        let err = require_websocket("https://rpc.example.com:443/", "Watching events")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Watching events requires a websocket endpoint (`ws://` or `wss://`), \
+             https://rpc.example.com:443/ is an HTTP endpoint"
+        );
+        assert!(require_websocket("ws://localhost:9944/", "Watching events").is_ok());
+    }
+
+    #[tokio::test]
+    async fn http_client_rejects_subscriptions() {
+        let rpc = rpc_client("http://localhost:9933/").await.unwrap();
+        let err = rpc
+            .subscribe::<u32>("chain_subscribeNewHeads", rpc_params![], "unsub")
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("requires a websocket endpoint"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn command_connects_to_the_node_once() {
+        let transport = CountingRpcClient::default();
+        let connection = Connection::<PolkadotConfig>::from_rpc_client(RpcClient::new(
+            transport.clone(),
+        ))
+        .await
+        .unwrap();
+
+        let token_metadata = TokenMetadata::query_with(connection.rpc()).await.unwrap();
+        let ss58_prefix = Ss58Prefix::query_with(connection.rpc()).await.unwrap();
+        assert_eq!(token_metadata.symbol, "UNIT");
+        assert_eq!(ss58_prefix, Ss58Prefix::default());
+
+        // no node is listening at the default url, any new connection would fail
+        let opts = ExtrinsicOptsBuilder::new(dev::alice())
+            .file(Some(PathBuf::from(
+                "../cargo-contract/tests/fixtures/flipper.json",
+            )))
+            .ss58_prefix(ss58_prefix)
+            .token_metadata(Some(token_metadata))
+            .done();
+        let call = CallCommandBuilder::<PolkadotConfig, DefaultEnvironment, _>::new(
+            ContractAddress::AccountId(dev::bob().public_key().to_account_id()),
+            "get",
+            opts,
+        )
+        .connection(Some(connection.with_config().unwrap()))
+        .done()
+        .await
+        .unwrap();
+        let result = call.call_dry_run().await.unwrap();
+        assert!(result.result.is_ok());
+
+        assert_eq!(transport.count("chain_getBlockHash"), 1);
+        assert_eq!(transport.count("state_getRuntimeVersion"), 1);
+        assert_eq!(transport.count("system_properties"), 2);
+        assert_eq!(transport.count("state_call"), 2);
+    }
+}
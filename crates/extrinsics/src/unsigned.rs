@@ -0,0 +1,167 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    check_sudo_key,
+    check_sudo_result,
+    explain_expired,
+    extrinsic_calls::MaybeSudo,
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
+    },
+    extrinsic_params,
+    get_best_block,
+    next_account_nonce,
+    submit_deadline,
+    submit_until_deadline,
+    ErrorVariant,
+};
+use ink_env::Environment;
+use serde::Serialize;
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    blocks::ExtrinsicEvents,
+    config,
+    tx,
+    Config,
+    OnlineClient,
+};
+
+/// An extrinsic which is ready to be signed outside of this crate, e.g. by a hardware
+/// or browser wallet which does not hand out its keys.
+///
+/// The nonce and the mortality of the extrinsic are fixed when it is created, so it must
+/// be signed and submitted before the signer submits another extrinsic.
+pub struct UnsignedExtrinsic<C: Config> {
+    partial: tx::PartialExtrinsic<C, OnlineClient<C>>,
+    account_id: C::AccountId,
+}
+
+impl<C: Config> UnsignedExtrinsic<C> {
+    /// The account which must sign the extrinsic.
+    pub fn account_id(&self) -> &C::AccountId {
+        &self.account_id
+    }
+
+    /// The SCALE encoded call of the extrinsic.
+    pub fn call_data(&self) -> &[u8] {
+        self.partial.call_data()
+    }
+
+    /// The bytes to sign: the call data followed by the parameters of the signed
+    /// extensions, hashed with `blake2_256` if they are longer than 256 bytes.
+    pub fn signer_payload(&self) -> Vec<u8> {
+        self.partial.signer_payload()
+    }
+
+    /// Returns the encoded signed extrinsic, given the `signature` of the
+    /// [`Self::signer_payload`] by the [`Self::account_id`], to be submitted with
+    /// `submit_signed`.
+    pub fn signed(&self, signature: &C::Signature) -> Vec<u8>
+    where
+        C::Address: From<C::AccountId>,
+    {
+        self.partial
+            .sign_with_address_and_signature(&self.account_id.clone().into(), signature)
+            .into_encoded()
+    }
+}
+
+/// Creates the extrinsic `call` to be signed by `account_id` outside of this crate, with
+/// the mortality of the `opts`.
+///
+/// If `sudo` is set in the `opts`, the call is wrapped in `Sudo::sudo`, and if a
+/// [`crate::NonceHandle`] is set, the nonce is taken from it, see `submit_extrinsic`.
+pub(crate) async fn create_unsigned<C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    call: &Call,
+    account_id: &C::AccountId,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> Result<UnsignedExtrinsic<C>, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: Serialize,
+    E: Environment,
+    Call: tx::TxPayload,
+    Signer: tx::Signer<C> + Clone,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+{
+    if opts.sudo() {
+        check_sudo_key(client, rpc, account_id).await?;
+    }
+    let call = MaybeSudo::new(call, opts.sudo());
+    let best_block = client.blocks().at(get_best_block(rpc).await?).await?;
+    let params = extrinsic_params::<C>(
+        opts.mortality(),
+        best_block.number().into(),
+        best_block.hash(),
+    );
+    let account_nonce = match opts.nonce() {
+        Some(nonce) => {
+            nonce
+                .next(next_account_nonce(client, rpc, account_id))
+                .await?
+        }
+        None => next_account_nonce(client, rpc, account_id).await?,
+    };
+    let partial =
+        client
+            .tx()
+            .create_partial_signed_with_nonce(&call, account_nonce, params)?;
+    Ok(UnsignedExtrinsic {
+        partial,
+        account_id: account_id.clone(),
+    })
+}
+
+/// Submits an extrinsic which was signed outside of this crate, e.g. an
+/// [`UnsignedExtrinsic`] signed by a wallet, and waits until it is included in a block.
+///
+/// Unlike extrinsics signed with the signer of the `opts`, it cannot be resubmitted with
+/// a new nonce if it is rejected.
+pub(crate) async fn submit_signed<C, E, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    signed: Vec<u8>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> Result<ExtrinsicEvents<C>, ErrorVariant>
+where
+    C: Config,
+    E: Environment,
+    Signer: tx::Signer<C> + Clone,
+{
+    let best_block = client.blocks().at(get_best_block(rpc).await?).await?;
+    let first_block_number: u64 = best_block.number().into();
+    let tx = tx::SubmittableExtrinsic::from_bytes(client.clone(), signed);
+    let events = submit_until_deadline(
+        client,
+        rpc,
+        &tx,
+        first_block_number,
+        &[tx.hash()],
+        submit_deadline(opts),
+        opts,
+    )
+    .await?
+    .map_err(|err| explain_expired(err, opts.mortality()))?;
+    if opts.sudo() {
+        check_sudo_result(&events, &client.metadata()).map_err(|err| *err)?;
+    }
+    Ok(events)
+}
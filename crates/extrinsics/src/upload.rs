@@ -15,27 +15,52 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
-    events::CodeStored,
-    pallet_contracts_primitives::CodeUploadResult,
+    events::{
+        BalancesHeld,
+        BalancesReserved,
+        CodeStored,
+        ReviveCodeStored,
+    },
+    pallet_contracts_primitives::{
+        CodeUploadResult,
+        CodeUploadReturnValue,
+    },
     state_call,
     submit_extrinsic,
+    Connection,
     ErrorVariant,
     WasmCode,
 };
 use crate::{
+    chain_limits::ChainLimits,
     check_env_types,
-    extrinsic_calls::UploadCode,
-    extrinsic_opts::ExtrinsicOpts,
+    compat_check::check_chain_compatibility,
+    extrinsic_calls::{
+        ReviveUploadCode,
+        UploadCode,
+    },
+    extrinsic_opts::{
+        ExtrinsicOpts,
+        MortalityParams,
+    },
+    wasm_check::validate_wasm_code,
+    ContractsPallet,
+    Phase,
 };
 use anyhow::Result;
+use contract_build::WasmValidation;
 use contract_transcode::ContractMessageTranscoder;
 use ink_env::Environment;
 use scale::Encode;
-use subxt::{
-    backend::{
-        legacy::LegacyRpcMethods,
-        rpc::RpcClient,
+use std::{
+    fmt::{
+        self,
+        Display,
     },
+    str::FromStr,
+};
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
     blocks::ExtrinsicEvents,
     config,
     ext::{
@@ -50,6 +75,8 @@ use subxt::{
 /// A builder for the upload command.
 pub struct UploadCommandBuilder<C: Config, E: Environment, Signer: Clone> {
     extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
+    determinism: Determinism,
+    connection: Option<Connection<C>>,
 }
 
 impl<C: Config, E: Environment, Signer> UploadCommandBuilder<C, E, Signer>
@@ -60,7 +87,26 @@ where
     pub fn new(
         extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
     ) -> UploadCommandBuilder<C, E, Signer> {
-        UploadCommandBuilder { extrinsic_opts }
+        UploadCommandBuilder {
+            extrinsic_opts,
+            determinism: Determinism::Enforced,
+            connection: None,
+        }
+    }
+
+    /// Sets the determinism of the uploaded code.
+    pub fn determinism(self, determinism: Determinism) -> Self {
+        let mut this = self;
+        this.determinism = determinism;
+        this
+    }
+
+    /// Sets the connection to the node to reuse, otherwise a new connection is
+    /// established to the url of the extrinsic options.
+    pub fn connection(self, connection: Option<Connection<C>>) -> Self {
+        let mut this = self;
+        this.connection = connection;
+        this
     }
 
     /// Preprocesses contract artifacts and options for subsequent upload.
@@ -74,8 +120,15 @@ where
     /// execution.
     pub async fn done(self) -> Result<UploadExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
+        // Raw code without metadata is uploaded as is, only the checks of the
+        // environment types and the ink! version against the chain are skipped.
+        let metadata = artifacts.metadata().ok();
+        let transcoder = metadata
+            .is_some()
+            .then(|| self.extrinsic_opts.contract_transcoder(&artifacts))
+            .transpose()?;
 
+        let target = artifacts.target();
         let artifacts_path = artifacts.artifact_path().to_path_buf();
         let code = artifacts.code.ok_or_else(|| {
             anyhow::anyhow!(
@@ -84,35 +137,80 @@ where
             )
         })?;
 
-        let url = self.extrinsic_opts.url();
-        let rpc_cli = RpcClient::from_url(&url).await?;
-        let client = OnlineClient::from_rpc_client(rpc_cli.clone()).await?;
-        check_env_types(&client, &transcoder, self.extrinsic_opts.verbosity())?;
-        let rpc = LegacyRpcMethods::new(rpc_cli);
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => Connection::connect(&self.extrinsic_opts.url()).await?,
+        };
+        let client = connection.client().clone();
+        if let Some(transcoder) = &transcoder {
+            check_env_types::<C, E>(
+                &client,
+                transcoder,
+                self.extrinsic_opts.verbosity(),
+            )?;
+        }
+        let rpc = connection.rpc().clone();
+        let pallet = ContractsPallet::detect(&client.metadata())?;
+        pallet.check_target(target)?;
+        ChainLimits::from_metadata(&client.metadata(), pallet)
+            .check_code_len(code.0.len())?;
+        if self.determinism == Determinism::Relaxed
+            && pallet != ContractsPallet::Contracts
+        {
+            anyhow::bail!(
+                "Uploading code with relaxed determinism is only supported by \
+                pallet-contracts"
+            )
+        }
+        if let Some(metadata) =
+            metadata.filter(|_| !self.extrinsic_opts.skip_compat_check())
+        {
+            check_chain_compatibility(&client, pallet, &metadata).await?;
+        }
+        let wasm_validation = if pallet == ContractsPallet::Contracts
+            && !self.extrinsic_opts.skip_wasm_validation()
+        {
+            Some(validate_wasm_code(
+                &client.metadata(),
+                &code.0,
+                self.determinism,
+            )?)
+        } else {
+            None
+        };
 
         Ok(UploadExec {
             opts: self.extrinsic_opts,
+            pallet,
             rpc,
             client,
             code,
             transcoder,
+            determinism: self.determinism,
+            wasm_validation,
         })
     }
 }
 
 pub struct UploadExec<C: Config, E: Environment, Signer: Clone> {
     opts: ExtrinsicOpts<C, E, Signer>,
+    pallet: ContractsPallet,
+    determinism: Determinism,
     rpc: LegacyRpcMethods<C>,
     client: OnlineClient<C>,
     code: WasmCode,
-    transcoder: ContractMessageTranscoder,
+    transcoder: Option<ContractMessageTranscoder>,
+    wasm_validation: Option<WasmValidation>,
 }
 
 impl<C: Config, E: Environment, Signer> UploadExec<C, E, Signer>
 where
     C::Hash: IntoVisitor,
     C::AccountId: IntoVisitor,
-    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams: Default,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+    C::AccountId: serde::Serialize + PartialEq,
+    E::Balance: IntoVisitor,
     Signer: tx::Signer<C> + Clone,
 {
     /// Uploads contract code to a specified URL using a JSON-RPC call.
@@ -123,13 +221,49 @@ where
     /// the state of the blockchain.
     pub async fn upload_code_rpc(&self) -> Result<CodeUploadResult<C::Hash, E::Balance>> {
         let storage_deposit_limit = self.opts.storage_deposit_limit();
-        let call_request = CodeUploadRequest {
-            origin: self.opts.signer().account_id(),
-            code: self.code.0.clone(),
-            storage_deposit_limit,
-            determinism: Determinism::Enforced,
+        let func = self.pallet.runtime_api("upload_code");
+        let dry_run = async {
+            match self.pallet {
+                ContractsPallet::Contracts => {
+                    let call_request = CodeUploadRequest {
+                        origin: self.opts.signer().account_id(),
+                        code: self.code.0.clone(),
+                        storage_deposit_limit,
+                        determinism: self.determinism,
+                    };
+                    state_call(&self.rpc, &func, call_request).await
+                }
+                ContractsPallet::Revive => {
+                    let call_request = ReviveCodeUploadRequest {
+                        origin: self.opts.signer().account_id(),
+                        code: self.code.0.clone(),
+                        storage_deposit_limit,
+                    };
+                    state_call(&self.rpc, &func, call_request).await
+                }
+            }
         };
-        state_call(&self.rpc, "ContractsApi_upload_code", call_request).await
+        self.opts.timings().measure(Phase::DryRun, dry_run).await
+    }
+
+    /// Dry-runs the upload of the code, returning the hash the code is stored under and
+    /// the storage deposit the upload requires, which is zero if the code was already
+    /// uploaded.
+    ///
+    /// Unlike [`UploadExec::upload_code_rpc`], a failing dry-run is returned as an
+    /// error.
+    pub async fn upload_dry_run(
+        &self,
+    ) -> Result<CodeUploadReturnValue<C::Hash, E::Balance>, ErrorVariant> {
+        match self.upload_code_rpc().await? {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                Err(ErrorVariant::from_dispatch_error(
+                    &err,
+                    &self.client.metadata(),
+                )?)
+            }
+        }
     }
 
     /// Uploads contract code to the blockchain with specified options.
@@ -138,44 +272,87 @@ where
     /// blockchain, utilizing the provided options.
     /// The function handles the necessary interactions with the blockchain's runtime
     /// API to ensure the successful upload of the code.
-    pub async fn upload_code(&self) -> Result<UploadResult<C>, ErrorVariant> {
+    pub async fn upload_code(&self) -> Result<UploadResult<C, E>, ErrorVariant> {
         let storage_deposit_limit = self.opts.storage_deposit_limit();
 
-        let call = UploadCode::new(
-            self.code.clone(),
-            storage_deposit_limit,
-            Determinism::Enforced,
-        )
-        .build();
-
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
-
-        let code_stored = events.find_first::<CodeStored<C::Hash>>()?;
+        let (events, code_stored) = match self.pallet {
+            ContractsPallet::Contracts => {
+                let call = UploadCode::new(
+                    self.code.clone(),
+                    storage_deposit_limit,
+                    self.determinism,
+                )
+                .build();
+                let events =
+                    submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?;
+                let code_stored = events.find_first::<CodeStored<C::Hash>>()?;
+                (events, code_stored)
+            }
+            ContractsPallet::Revive => {
+                // `pallet-revive` requires a storage deposit limit, use the deposit
+                // reserved in a dry run if none was specified.
+                let storage_deposit_limit = match storage_deposit_limit {
+                    Some(limit) => limit,
+                    None => self.upload_dry_run().await?.deposit,
+                };
+                let call =
+                    ReviveUploadCode::new(self.code.clone(), storage_deposit_limit)
+                        .build();
+                let events =
+                    submit_extrinsic(&self.client, &self.rpc, &call, &self.opts).await?;
+                let code_stored = events
+                    .find_first::<ReviveCodeStored<C::Hash>>()?
+                    .map(Into::into);
+                (events, code_stored)
+            }
+        };
+        let deposit = deposit_held::<C, E>(&events, &self.opts.signer().account_id())?;
         Ok(UploadResult {
             code_stored,
+            deposit,
             events,
         })
     }
 
+    /// Sets the storage deposit limit of the upload, e.g. to the deposit estimated by
+    /// [`UploadExec::upload_dry_run`].
+    pub fn set_storage_deposit_limit(&mut self, limit: Option<E::Balance>) {
+        self.opts.set_storage_deposit_limit(limit);
+    }
+
     /// Returns the extrinsic options.
     pub fn opts(&self) -> &ExtrinsicOpts<C, E, Signer> {
         &self.opts
     }
 
+    /// Returns the contracts pallet of the target chain.
+    pub fn pallet(&self) -> ContractsPallet {
+        self.pallet
+    }
+
     /// Returns the client.
     pub fn client(&self) -> &OnlineClient<C> {
         &self.client
     }
 
+    /// Returns the determinism of the uploaded code.
+    pub fn determinism(&self) -> Determinism {
+        self.determinism
+    }
+
     /// Returns the code.
     pub fn code(&self) -> &WasmCode {
         &self.code
     }
 
-    /// Returns the contract message transcoder.
-    pub fn transcoder(&self) -> &ContractMessageTranscoder {
-        &self.transcoder
+    /// Returns the contract message transcoder, if the code was uploaded with metadata.
+    pub fn transcoder(&self) -> Option<&ContractMessageTranscoder> {
+        self.transcoder.as_ref()
+    }
+
+    /// Returns the findings of the validation of the code, unless skipped.
+    pub fn wasm_validation(&self) -> Option<&WasmValidation> {
+        self.wasm_validation.as_ref()
     }
 }
 
@@ -188,17 +365,58 @@ struct CodeUploadRequest<AccountId, Balance> {
     determinism: Determinism,
 }
 
+/// A struct that encodes RPC parameters required for a call to upload a new code to
+/// `pallet-revive`.
+#[derive(Encode)]
+struct ReviveCodeUploadRequest<AccountId, Balance> {
+    origin: AccountId,
+    code: Vec<u8>,
+    storage_deposit_limit: Option<Balance>,
+}
+
 /// A struct representing the result of an upload command execution.
-pub struct UploadResult<C: Config> {
+pub struct UploadResult<C: Config, E: Environment> {
     pub code_stored: Option<CodeStored<C::Hash>>,
+    /// The storage deposit held from the signer, `None` if the runtime emitted no
+    /// event for it, e.g. because the code was already uploaded.
+    pub deposit: Option<E::Balance>,
     pub events: ExtrinsicEvents<C>,
 }
 
+/// Returns the sum of the funds held or reserved from the account `who` in the `events`.
+fn deposit_held<C: Config, E: Environment>(
+    events: &ExtrinsicEvents<C>,
+    who: &C::AccountId,
+) -> Result<Option<E::Balance>>
+where
+    C::AccountId: IntoVisitor + PartialEq,
+    E::Balance: IntoVisitor,
+{
+    let mut deposit = None;
+    for event in events.iter() {
+        let event = event?;
+        let amount = match event.as_event::<BalancesHeld<C::AccountId, E::Balance>>()? {
+            Some(held) => (&held.who == who).then_some(held.amount),
+            None => {
+                event
+                    .as_event::<BalancesReserved<C::AccountId, E::Balance>>()?
+                    .and_then(|reserved| {
+                        (&reserved.who == who).then_some(reserved.amount)
+                    })
+            }
+        };
+        if let Some(amount) = amount {
+            deposit = Some(deposit.map_or(amount, |deposit| deposit + amount));
+        }
+    }
+    Ok(deposit)
+}
+
 /// Copied from `pallet-contracts` to additionally implement `scale_encode::EncodeAsType`.
-#[allow(dead_code)]
-#[derive(Debug, Encode, EncodeAsType)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, EncodeAsType, serde::Serialize)]
 #[encode_as_type(crate_path = "subxt::ext::scale_encode")]
-pub(crate) enum Determinism {
+#[serde(rename_all = "lowercase")]
+pub enum Determinism {
     /// The execution should be deterministic and hence no indeterministic instructions
     /// are allowed.
     ///
@@ -208,10 +426,55 @@ pub(crate) enum Determinism {
     /// Allow calling or uploading an indeterministic code.
     ///
     /// This is only possible when calling into `pallet-contracts` directly via
-    /// [`crate::Pallet::bare_call`].
+    /// `pallet_contracts::Pallet::bare_call`.
     ///
     /// # Note
     ///
     /// **Never** use this mode for on-chain execution.
     Relaxed,
 }
+
+impl FromStr for Determinism {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "enforced" => Ok(Self::Enforced),
+            "relaxed" => Ok(Self::Relaxed),
+            _ => {
+                Err(anyhow::anyhow!(
+                    "Invalid determinism '{s}', expected 'enforced' or 'relaxed'"
+                ))
+            }
+        }
+    }
+}
+
+impl Display for Determinism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enforced => write!(f, "enforced"),
+            Self::Relaxed => write!(f, "relaxed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_determinism() {
+        for determinism in [Determinism::Enforced, Determinism::Relaxed] {
+            assert_eq!(
+                determinism.to_string().parse::<Determinism>().unwrap(),
+                determinism
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&Determinism::Relaxed).unwrap(),
+            "\"relaxed\""
+        );
+        assert!("floats".parse::<Determinism>().is_err());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::upload::Determinism;
+use anyhow::Result;
+use contract_build::WasmValidation;
+use scale_info::TypeDef;
+use subxt::Metadata;
+
+/// The host functions without weights in the schedule, since their weight is charged
+/// by the dispatched call or by the chain extension.
+const UNWEIGHED_HOST_FUNCTIONS: [&str; 4] = [
+    "call_chain_extension",
+    "call_runtime",
+    "xcm_execute",
+    "xcm_send",
+];
+
+/// Validates the Wasm `code` of a contract before it is uploaded to a chain using
+/// `pallet-contracts`, so that it is not rejected on-chain with an opaque
+/// `CodeRejected` error.
+///
+/// The imported host functions are compared against the host functions advertised by
+/// the chain `metadata`, and floating point instructions are only accepted with relaxed
+/// `determinism`.
+pub fn validate_wasm_code(
+    metadata: &Metadata,
+    code: &[u8],
+    determinism: Determinism,
+) -> Result<WasmValidation> {
+    let mut validation = WasmValidation::new(code)?;
+    if let Some(host_functions) = host_functions(metadata) {
+        validation.check_host_functions(&host_functions);
+    }
+    validation.ensure_valid(determinism == Determinism::Relaxed)?;
+    Ok(validation)
+}
+
+/// Returns the names of the host functions provided by `pallet-contracts`, as
+/// advertised by the host function weights of its `Schedule` constant.
+///
+/// Returns `None` if the chain does not advertise them, e.g. since newer versions of
+/// the pallet no longer include the weights in the schedule.
+fn host_functions(metadata: &Metadata) -> Option<Vec<String>> {
+    let schedule = metadata
+        .pallet_by_name("Contracts")?
+        .constant_by_name("Schedule")?
+        .ty();
+    let registry = metadata.types();
+    let TypeDef::Composite(schedule) = &registry.resolve(schedule)?.type_def else {
+        return None
+    };
+    let weights = schedule
+        .fields
+        .iter()
+        .find(|field| field.name.as_deref() == Some("host_fn_weights"))?;
+    let TypeDef::Composite(weights) = &registry.resolve(weights.ty.id)?.type_def else {
+        return None
+    };
+    let host_functions = weights
+        .fields
+        .iter()
+        .filter_map(|field| field.name.as_deref())
+        .map(|name| name.trim_start_matches("r#"))
+        .chain(UNWEIGHED_HOST_FUNCTIONS)
+        .map(ToString::to_string)
+        .collect();
+    Some(host_functions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Decode;
+
+    fn metadata() -> Metadata {
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode")
+    }
+
+    /// A module importing `seal0::foo` and `seal0::seal_input`, with a function
+    /// executing `f32.const 0; drop`.
+    const CODE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: `fn()`
+        0x02, 0x20, 0x02, // import section
+        0x05, b's', b'e', b'a', b'l', b'0', 0x03, b'f', b'o', b'o', 0x00, 0x00, 0x05,
+        b's', b'e', b'a', b'l', b'0', 0x0a, b's', b'e', b'a', b'l', b'_', b'i', b'n',
+        b'p', b'u', b't', 0x00, 0x00, //
+        0x03, 0x02, 0x01, 0x00, // function section
+        0x0a, 0x0a, 0x01, 0x08, 0x00, 0x43, 0x00, 0x00, 0x00, 0x00, 0x1a,
+        0x0b, // code section
+    ];
+
+    #[test]
+    fn rejects_floats_and_unknown_host_functions() {
+        let err = validate_wasm_code(&metadata(), CODE, Determinism::Enforced)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("func[2]: F32Const"), "{err}");
+        assert!(err.contains("seal0::foo"), "{err}");
+        assert!(!err.contains("seal_input"), "{err}");
+
+        let err = validate_wasm_code(&metadata(), CODE, Determinism::Relaxed)
+            .unwrap_err()
+            .to_string();
+        assert!(!err.contains("floating point"), "{err}");
+        assert!(err.contains("seal0::foo"), "{err}");
+    }
+
+    #[test]
+    fn host_functions_are_read_from_the_schedule() {
+        let metadata = metadata();
+        let host_functions = host_functions(&metadata).expect("schedule has weights");
+
+        for host_function in ["call", "input", "return", "call_chain_extension"] {
+            assert!(
+                host_functions.iter().any(|f| f == host_function),
+                "{host_function} missing in {host_functions:?}"
+            );
+        }
+        assert!(!host_functions.iter().any(|f| f.starts_with("r#")));
+    }
+}
@@ -0,0 +1,338 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    events::{
+        decode_contract_event,
+        AdditionalMetadata,
+        ContractEmitted,
+        ReviveContractEmitted,
+    },
+    require_websocket,
+    rpc_client,
+    url_to_string,
+    ContractAddress,
+};
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_transcode::{
+    ContractMessageTranscoder,
+    DecodedEvent,
+    Hex,
+    Value,
+};
+use scale::Encode;
+use std::{
+    str::FromStr,
+    time::Duration,
+};
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    blocks::Block,
+    events::Phase,
+    ext::scale_decode::IntoVisitor,
+    Config,
+    OnlineClient,
+};
+
+/// How long to wait before reconnecting after the connection to the node was lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// An event emitted by the watched contract.
+#[derive(Debug, serde::Serialize)]
+pub struct ContractEvent {
+    /// The number of the block the event was emitted in.
+    pub block_number: u64,
+    /// The index of the extrinsic which emitted the event.
+    pub extrinsic_index: Option<u32>,
+    /// The event, decoded with the contract's transcoder if available, otherwise the
+    /// raw event data.
+    pub event: Value,
+    /// The labels of the fields of the decoded event which are indexed in its topics.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub indexed: Vec<String>,
+    /// The topics of the event as hex.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    /// The path of the additional metadata the event was decoded with, if not with the
+    /// contract's transcoder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+/// Subscribes to the events emitted by a contract.
+pub struct ContractEventWatcher<C: Config> {
+    url: url::Url,
+    contract: ContractAddress<C::AccountId>,
+    transcoder: Option<ContractMessageTranscoder>,
+    additional_metadata: Vec<AdditionalMetadata>,
+    best: bool,
+    next_block: Option<u64>,
+}
+
+impl<C: Config> ContractEventWatcher<C>
+where
+    C::AccountId: IntoVisitor + Encode,
+    C::Hash: IntoVisitor,
+{
+    /// Creates a watcher for the events of `contract`, following finalized blocks.
+    pub fn new(url: url::Url, contract: ContractAddress<C::AccountId>) -> Self {
+        Self {
+            url,
+            contract,
+            transcoder: None,
+            additional_metadata: Vec::new(),
+            best: false,
+            next_block: None,
+        }
+    }
+
+    /// Decode the events with the given transcoder, otherwise they are displayed as raw
+    /// hex data.
+    pub fn transcoder(mut self, transcoder: ContractMessageTranscoder) -> Self {
+        self.transcoder = Some(transcoder);
+        self
+    }
+
+    /// Decode the events the transcoder can't decode with the given metadata, e.g. of
+    /// an earlier version of the contract or of ink! events defined in a dependency.
+    pub fn additional_metadata(
+        mut self,
+        additional_metadata: Vec<AdditionalMetadata>,
+    ) -> Self {
+        self.additional_metadata = additional_metadata;
+        self
+    }
+
+    /// Follow best blocks instead of finalized blocks.
+    pub fn best(mut self, best: bool) -> Self {
+        self.best = best;
+        self
+    }
+
+    /// Backfill the events of all blocks starting at `block_number` before following
+    /// new blocks.
+    pub fn from_block(mut self, block_number: Option<u64>) -> Self {
+        self.next_block = block_number;
+        self
+    }
+
+    /// Watch the contract's events, invoking `on_event` for each event until it returns
+    /// an error.
+    ///
+    /// The connection to the node is re-established if it is lost, events of blocks
+    /// produced in the meantime are backfilled. Watching requires a websocket endpoint,
+    /// since blocks are followed via a subscription.
+    pub async fn watch<F>(mut self, mut on_event: F) -> Result<()>
+    where
+        F: FnMut(ContractEvent) -> Result<()>,
+    {
+        require_websocket(&url_to_string(&self.url), "Watching contract events")?;
+        loop {
+            match self.watch_connection(&mut on_event).await {
+                Ok(()) => tracing::warn!("Block subscription ended, reconnecting"),
+                Err(err) if is_connection_error(&err) => {
+                    tracing::warn!("Connection to the node lost: {err}, reconnecting")
+                }
+                Err(err) => return Err(err),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn watch_connection<F>(&mut self, on_event: &mut F) -> Result<()>
+    where
+        F: FnMut(ContractEvent) -> Result<()>,
+    {
+        let rpc_cli = rpc_client(&url_to_string(&self.url)).await?;
+        let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
+        let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+
+        let mut blocks = if self.best {
+            client.blocks().subscribe_best().await?
+        } else {
+            client.blocks().subscribe_finalized().await?
+        };
+
+        while let Some(block) = blocks.next().await {
+            let block = block?;
+            let block_number: u64 = block.number().into();
+            // Backfill the blocks requested with `--from-block` or missed while the
+            // connection was lost.
+            if let Some(next_block) = self.next_block {
+                if block_number < next_block {
+                    continue
+                }
+                for number in next_block..block_number {
+                    let hash = rpc
+                        .chain_get_block_hash(Some(number.into()))
+                        .await?
+                        .ok_or_else(|| anyhow!("Block #{number} not found"))?;
+                    let block = client.blocks().at(hash).await?;
+                    self.process_block(&block, on_event).await?;
+                }
+            }
+            self.process_block(&block, on_event).await?;
+        }
+        Ok(())
+    }
+
+    async fn process_block<F>(
+        &mut self,
+        block: &Block<C, OnlineClient<C>>,
+        on_event: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(ContractEvent) -> Result<()>,
+    {
+        let block_number: u64 = block.number().into();
+        let events = block.events().await?;
+        for event in events.iter() {
+            let event = event?;
+            let emitted = match &self.contract {
+                ContractAddress::AccountId(account_id) => {
+                    event
+                        .as_event::<ContractEmitted<C::AccountId>>()?
+                        .filter(|e| e.contract.encode() == account_id.encode())
+                        .map(|e| (e.data, event.topics().to_vec()))
+                }
+                ContractAddress::H160(address) => {
+                    event
+                        .as_event::<ReviveContractEmitted<C::Hash>>()?
+                        .filter(|e| &e.contract == address)
+                        .map(|e| (e.data, e.topics))
+                }
+            };
+            let Some((data, topics)) = emitted else {
+                continue
+            };
+            let extrinsic_index = match event.phase() {
+                Phase::ApplyExtrinsic(index) => Some(index),
+                Phase::Initialization | Phase::Finalization => None,
+            };
+            let emitter = match &self.contract {
+                ContractAddress::AccountId(account_id) => account_id.encode(),
+                ContractAddress::H160(address) => address.encode(),
+            };
+            let (decoded, metadata) = decode_event(
+                self.transcoder.as_ref(),
+                &self.additional_metadata,
+                &emitter,
+                &topics,
+                &data,
+            )?;
+            on_event(ContractEvent {
+                block_number,
+                extrinsic_index,
+                event: decoded.value,
+                indexed: decoded.indexed,
+                metadata,
+                topics: topics
+                    .iter()
+                    .map(|topic| format!("0x{}", hex::encode(topic)))
+                    .collect(),
+            })?;
+        }
+        self.next_block = Some(block_number + 1);
+        Ok(())
+    }
+}
+
+/// Decodes the data of a contract event emitted by the SCALE encoded `emitter` and
+/// matched by its `topics`, falling back to the raw hex data if the event can not be
+/// decoded. Returns the path of the additional metadata the event was decoded with.
+fn decode_event<Hash: AsRef<[u8]>>(
+    transcoder: Option<&ContractMessageTranscoder>,
+    additional_metadata: &[AdditionalMetadata],
+    emitter: &[u8],
+    topics: &[Hash],
+    data: &[u8],
+) -> Result<(DecodedEvent, Option<String>)> {
+    // The transcoder expects the data prefixed with its length, as it is encoded in
+    // the event fields.
+    match decode_contract_event(
+        transcoder,
+        additional_metadata,
+        Some(emitter),
+        topics,
+        &mut &data.encode()[..],
+    ) {
+        Some((event, metadata)) => {
+            Ok((event, metadata.map(|path| path.display().to_string())))
+        }
+        None => {
+            if transcoder.is_some() || !additional_metadata.is_empty() {
+                tracing::warn!("Decoding contract event failed");
+            }
+            let event = DecodedEvent {
+                value: Value::Hex(Hex::from_str(&hex::encode(data))?),
+                indexed: Vec::new(),
+            };
+            Ok((event, None))
+        }
+    }
+}
+
+/// Returns `true` if the error was caused by the connection to the node.
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<subxt::Error>(),
+        Some(subxt::Error::Rpc(_) | subxt::Error::Io(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subxt::{
+        error::RpcError,
+        utils::H256,
+    };
+
+    #[test]
+    fn undecodable_event_as_hex() {
+        let (event, metadata) =
+            decode_event::<H256>(None, &[], &[], &[], &[0xde, 0xad]).unwrap();
+        assert!(metadata.is_none());
+        assert_eq!(event.value.to_string(), "0xdead");
+        assert!(event.indexed.is_empty());
+    }
+
+    #[test]
+    fn connection_errors_are_recoverable() {
+        let err = anyhow::Error::from(subxt::Error::from(RpcError::SubscriptionDropped));
+        assert!(is_connection_error(&err));
+        assert!(!is_connection_error(&anyhow!("Block #1 not found")));
+    }
+
+    #[test]
+    fn serialize_contract_event() {
+        let event = ContractEvent {
+            block_number: 42,
+            extrinsic_index: Some(1),
+            event: Value::Bool(true),
+            indexed: Vec::new(),
+            topics: Vec::new(),
+            metadata: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"block_number":42,"extrinsic_index":1,"event":{"Bool":true}}"#
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Contract calls sent from another chain, the relay chain or a sibling parachain of the
+//! chain of the contract, as an XCM `Transact` with the `send` extrinsic of its XCM
+//! pallet.
+//!
+//! The message is only sent by the extrinsic, it is executed on the destination chain
+//! asynchronously once delivered.
+
+use super::{
+    submit_extrinsic,
+    Connection,
+    ErrorVariant,
+    ExtrinsicOpts,
+    MortalityParams,
+};
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use ink_env::Environment;
+use scale::{
+    Decode,
+    Encode,
+};
+use serde::Serialize;
+use sp_weights::Weight;
+use std::{
+    fmt::Display,
+    str::FromStr,
+};
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    blocks::ExtrinsicEvents,
+    config,
+    dynamic::Value,
+    tx,
+    Config,
+    Metadata,
+};
+
+/// The asset paying for the execution of the XCM message on the destination chain,
+/// given relative to the destination chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum XcmFeeAsset {
+    /// The native token of the relay chain, the parent of the destination chain.
+    Parent,
+    /// The native token of the destination chain.
+    Here,
+}
+
+/// The fee withdrawn from the sovereign account of the sender on the destination chain
+/// to buy the execution of the XCM message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct XcmFee {
+    pub asset: XcmFeeAsset,
+    /// The amount in the smallest unit of the asset.
+    pub amount: u128,
+}
+
+impl FromStr for XcmFee {
+    type Err = anyhow::Error;
+
+    /// Parses `<asset>:<amount>` with an asset of `parent` or `here`, or an amount of
+    /// the `parent` asset.
+    fn from_str(input: &str) -> Result<Self> {
+        let (asset, amount) = match input.split_once(':') {
+            Some(("parent", amount)) => (XcmFeeAsset::Parent, amount),
+            Some(("here", amount)) => (XcmFeeAsset::Here, amount),
+            Some((asset, _)) => {
+                return Err(anyhow!(
+                    "Unknown fee asset `{asset}`, expected `parent` or `here`"
+                ))
+            }
+            None => (XcmFeeAsset::Parent, input),
+        };
+        let amount = amount.replace('_', "").parse().with_context(|| {
+            format!(
+                "Invalid fee amount `{amount}`, expected an integer in the smallest unit"
+            )
+        })?;
+        Ok(Self { asset, amount })
+    }
+}
+
+impl Display for XcmFee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let asset = match self.asset {
+            XcmFeeAsset::Parent => "parent",
+            XcmFeeAsset::Here => "here",
+        };
+        write!(f, "{asset}:{}", self.amount)
+    }
+}
+
+/// The XCM pallet of the origin chain the message is sent with, which also tells how
+/// the destination parachain is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XcmPallet {
+    /// `XcmPallet` of a relay chain, the destination is one of its parachains.
+    Relay,
+    /// `PolkadotXcm` of a parachain, the destination is a sibling parachain.
+    Parachain,
+}
+
+impl XcmPallet {
+    /// Detects the XCM pallet of the origin chain from its `metadata`.
+    pub fn detect(metadata: &Metadata) -> Result<Self> {
+        if metadata.pallet_by_name(Self::Relay.name()).is_some() {
+            Ok(Self::Relay)
+        } else if metadata.pallet_by_name(Self::Parachain.name()).is_some() {
+            Ok(Self::Parachain)
+        } else {
+            Err(anyhow!(
+                "The origin chain has no XCM pallet, expected `XcmPallet` or `PolkadotXcm`"
+            ))
+        }
+    }
+
+    /// The name of the pallet in the metadata.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Relay => "XcmPallet",
+            Self::Parachain => "PolkadotXcm",
+        }
+    }
+
+    /// The number of parents of the destination parachain relative to the origin.
+    fn dest_parents(&self) -> u8 {
+        match self {
+            Self::Relay => 0,
+            Self::Parachain => 1,
+        }
+    }
+}
+
+/// An XCM `Transact` of an encoded call on the destination parachain.
+#[derive(Debug, Clone)]
+pub(crate) struct XcmTransact {
+    /// The id of the destination parachain.
+    pub dest: u32,
+    /// The fee paying for the execution of the message.
+    pub fee: XcmFee,
+    /// The weight the call requires to be dispatched on the destination chain.
+    pub require_weight_at_most: Weight,
+    /// The call encoded for the destination chain.
+    pub call: Vec<u8>,
+}
+
+impl XcmTransact {
+    /// Returns the arguments of the `send` extrinsic of the XCM pallet: the versioned
+    /// location of the destination and the versioned message, in XCM version 3.
+    ///
+    /// The message withdraws the fee from the account of the sender on the destination
+    /// chain, buys execution with it, and dispatches the call with the sender's origin.
+    fn send_args(&self, pallet: XcmPallet) -> Vec<Value> {
+        let dest = location(
+            pallet.dest_parents(),
+            Value::unnamed_variant(
+                "X1",
+                [Value::unnamed_variant(
+                    "Parachain",
+                    [Value::u128(self.dest.into())],
+                )],
+            ),
+        );
+        let fee_parents = match self.fee.asset {
+            XcmFeeAsset::Parent => 1,
+            XcmFeeAsset::Here => 0,
+        };
+        let fee = Value::named_composite([
+            (
+                "id",
+                Value::unnamed_variant(
+                    "Concrete",
+                    [location(fee_parents, Value::unnamed_variant("Here", []))],
+                ),
+            ),
+            (
+                "fun",
+                Value::unnamed_variant("Fungible", [Value::u128(self.fee.amount)]),
+            ),
+        ]);
+        let instructions = [
+            Value::unnamed_variant(
+                "WithdrawAsset",
+                [Value::unnamed_composite([Value::unnamed_composite([
+                    fee.clone()
+                ])])],
+            ),
+            Value::named_variant(
+                "BuyExecution",
+                [
+                    ("fees", fee),
+                    ("weight_limit", Value::unnamed_variant("Unlimited", [])),
+                ],
+            ),
+            Value::named_variant(
+                "Transact",
+                [
+                    (
+                        "origin_kind",
+                        Value::unnamed_variant("SovereignAccount", []),
+                    ),
+                    (
+                        "require_weight_at_most",
+                        Value::named_composite([
+                            (
+                                "ref_time",
+                                Value::u128(
+                                    self.require_weight_at_most.ref_time().into(),
+                                ),
+                            ),
+                            (
+                                "proof_size",
+                                Value::u128(
+                                    self.require_weight_at_most.proof_size().into(),
+                                ),
+                            ),
+                        ]),
+                    ),
+                    (
+                        "call",
+                        Value::named_composite([(
+                            "encoded",
+                            Value::from_bytes(&self.call),
+                        )]),
+                    ),
+                ],
+            ),
+        ];
+        let message = Value::unnamed_composite([Value::unnamed_composite(instructions)]);
+        vec![
+            Value::unnamed_variant("V3", [dest]),
+            Value::unnamed_variant("V3", [message]),
+        ]
+    }
+}
+
+/// A `MultiLocation` of XCM version 3.
+fn location(parents: u8, interior: Value) -> Value {
+    Value::named_composite([
+        ("parents", Value::u128(parents.into())),
+        ("interior", interior),
+    ])
+}
+
+/// Queries the weight required to dispatch the encoded `call` via the transaction
+/// payment API of the chain of the contract.
+pub(crate) async fn query_call_weight<C: Config>(
+    rpc: &LegacyRpcMethods<C>,
+    call: &[u8],
+) -> Result<Weight> {
+    // The call is passed as it is, not as a byte vector, followed by its length.
+    let mut params = call.to_vec();
+    (call.len() as u32).encode_to(&mut params);
+    let bytes = rpc
+        .state_call(
+            "TransactionPaymentCallApi_query_call_info",
+            Some(&params),
+            None,
+        )
+        .await?;
+    // The dispatch info starts with the weight of the call, the rest is ignored.
+    Ok(Weight::decode(&mut bytes.as_ref())?)
+}
+
+/// The result of sending an XCM message.
+pub struct XcmSendResult<C: Config> {
+    /// The XCM pallet the message was sent with.
+    pub pallet: XcmPallet,
+    /// The events of the `send` extrinsic on the origin chain.
+    pub events: ExtrinsicEvents<C>,
+    /// The id of the message to trace its delivery with, taken from the `Sent` event.
+    /// Older XCM pallets don't report it.
+    pub message_id: Option<[u8; 32]>,
+}
+
+/// Sends the `transact` from the `origin` chain with its XCM pallet, signed by the
+/// signer of the `opts`, and waits for the `send` extrinsic to be included in a block.
+///
+/// The call is executed on the destination chain asynchronously once the message is
+/// delivered, so the success of the extrinsic doesn't tell whether the call succeeded.
+pub(crate) async fn send_xcm_transact<C, E, Signer>(
+    origin: &Connection<C>,
+    transact: &XcmTransact,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+) -> Result<XcmSendResult<C>, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: Serialize,
+    E: Environment,
+    Signer: tx::Signer<C> + Clone,
+    <C::ExtrinsicParams as config::ExtrinsicParams<C>>::OtherParams:
+        Default + MortalityParams<C>,
+{
+    let pallet = XcmPallet::detect(&origin.client().metadata())?;
+    let call = subxt::dynamic::tx(pallet.name(), "send", transact.send_args(pallet));
+    let events = submit_extrinsic(origin.client(), origin.rpc(), &call, opts).await?;
+    let message_id = sent_message_id(&events, pallet)?;
+    Ok(XcmSendResult {
+        pallet,
+        events,
+        message_id,
+    })
+}
+
+/// Returns the `message_id` of the `Sent` event of the XCM pallet, its last field.
+fn sent_message_id<C: Config>(
+    events: &ExtrinsicEvents<C>,
+    pallet: XcmPallet,
+) -> Result<Option<[u8; 32]>> {
+    for event in events.iter() {
+        let event = event?;
+        if event.pallet_name() != pallet.name() || event.variant_name() != "Sent" {
+            continue
+        }
+        let has_message_id = event
+            .event_metadata()
+            .variant
+            .fields
+            .last()
+            .is_some_and(|field| field.name.as_deref() == Some("message_id"));
+        let fields = event.field_bytes();
+        if !has_message_id || fields.len() < 32 {
+            return Ok(None)
+        }
+        let mut message_id = [0u8; 32];
+        message_id.copy_from_slice(&fields[fields.len() - 32..]);
+        return Ok(Some(message_id))
+    }
+    Err(anyhow!(
+        "The extrinsic emitted no `{}::Sent` event",
+        pallet.name()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_xcm_fee() {
+        assert_eq!(
+            "parent:1_000_000_000".parse::<XcmFee>().unwrap(),
+            XcmFee {
+                asset: XcmFeeAsset::Parent,
+                amount: 1_000_000_000
+            }
+        );
+        assert_eq!(
+            "here:500".parse::<XcmFee>().unwrap(),
+            XcmFee {
+                asset: XcmFeeAsset::Here,
+                amount: 500
+            }
+        );
+        assert_eq!("42".parse::<XcmFee>().unwrap().asset, XcmFeeAsset::Parent);
+        assert!("sibling:42".parse::<XcmFee>().is_err());
+        assert!("parent:1.5DOT".parse::<XcmFee>().is_err());
+    }
+
+    #[test]
+    fn xcm_fee_display_roundtrips() {
+        for fee in ["parent:100", "here:7"] {
+            assert_eq!(fee.parse::<XcmFee>().unwrap().to_string(), fee);
+        }
+    }
+
+    #[test]
+    fn send_args_reach_the_destination() {
+        let transact = XcmTransact {
+            dest: 1000,
+            fee: "parent:100".parse().unwrap(),
+            require_weight_at_most: Weight::from_parts(1_000_000, 4096),
+            call: vec![0x28, 0x06],
+        };
+        let args = transact.send_args(XcmPallet::Relay);
+        assert_eq!(args.len(), 2);
+        let dest = args[0].to_string();
+        assert!(dest.starts_with("V3"), "{dest}");
+        assert!(dest.contains("parents: 0"), "{dest}");
+        assert!(dest.contains("Parachain (1000)"), "{dest}");
+
+        let dest = transact.send_args(XcmPallet::Parachain)[0].to_string();
+        assert!(dest.contains("parents: 1"), "{dest}");
+    }
+}
@@ -23,6 +23,10 @@ use serde::{
     Serialize,
 };
 
+/// The default address prefix of Substrate based chains, used when the prefix of the
+/// target chain is not known.
+pub const SUBSTRATE_SS58_PREFIX: u16 = 42;
+
 /// A 32-byte cryptographic identifier. This is a simplified version of Substrate's
 /// `sp_core::crypto::AccountId32`.
 ///
@@ -99,9 +103,30 @@ impl AccountId32 {
         // substrate prefix (since we have no way to otherwise pick one). It
         // doesn't really matter, since when it's deserialized back in
         // system_accountNextIndex, we ignore this (so long as it's valid).
-        const SUBSTRATE_SS58_PREFIX: u8 = 42;
-        // prefix <= 63 just take up one byte at the start:
-        let mut v = vec![SUBSTRATE_SS58_PREFIX];
+        self.to_ss58check_with_prefix(SUBSTRATE_SS58_PREFIX)
+    }
+
+    /// Return the ss58-check string for this key, using the given address `prefix` of the
+    /// target chain. Adapted from
+    /// `sp_core::crypto::Ss58Codec::to_ss58check_with_version`.
+    pub fn to_ss58check_with_prefix(&self, prefix: u16) -> String {
+        // We mask out the upper two bits of the ident - SS58 Prefix currently only
+        // supports 14-bits
+        let ident: u16 = prefix & 0b0011_1111_1111_1111;
+        let mut v = match ident {
+            // prefix <= 63 just take up one byte at the start:
+            0..=63 => vec![ident as u8],
+            // upper six bits of the lower byte(!)
+            64..=16_383 => {
+                let first = ((ident & 0b0000_0000_1111_1100) as u8) >> 2;
+                // lower two bits of the lower byte in the high pos,
+                // lower bits of the upper byte in the low pos
+                let second =
+                    ((ident >> 8) as u8) | ((ident & 0b0000_0000_0000_0011) as u8) << 6;
+                vec![first | 0b01000000, second]
+            }
+            _ => unreachable!("masked out the upper two bits; qed"),
+        };
         // then push the account ID bytes.
         v.extend(self.0);
         // then push a 2 byte checksum of what we have so far.
@@ -116,6 +141,13 @@ impl AccountId32 {
     // we also implement the logic needed to decode an AccountId32 from an SS58
     // encoded string. This is exposed via a `FromStr` impl.
     fn from_ss58check(s: &str) -> Result<Self, FromSs58Error> {
+        Self::from_ss58check_with_prefix(s).map(|(account_id, _)| account_id)
+    }
+
+    /// Decode an SS58 encoded string with any valid address prefix, returning the
+    /// account together with the prefix it was encoded with. Adapted from
+    /// `sp_core::crypto::Ss58Codec::from_ss58check_with_version`.
+    pub fn from_ss58check_with_prefix(s: &str) -> Result<(Self, u16), FromSs58Error> {
         const CHECKSUM_LEN: usize = 2;
         let body_len = 32;
 
@@ -124,9 +156,19 @@ impl AccountId32 {
         if data.len() < 2 {
             return Err(FromSs58Error::BadLength)
         }
-        let prefix_len = match data[0] {
-            0..=63 => 1,
-            64..=127 => 2,
+        let (prefix_len, prefix) = match data[0] {
+            0..=63 => (1, data[0] as u16),
+            64..=127 => {
+                // weird bit manipulation owing to the combination of LE encoding and
+                // missing two bits from the left.
+                // d[0] d[1] are: 01aaaaaa bbcccccc
+                // they make the LE-encoded 16-bit value: aaaaaabb 00cccccc
+                // so the lower byte is formed of aaaaaabb and the higher byte is
+                // 00cccccc
+                let lower = (data[0] << 2) | (data[1] >> 6);
+                let upper = data[1] & 0b0011_1111;
+                (2, (lower as u16) | ((upper as u16) << 8))
+            }
             _ => return Err(FromSs58Error::InvalidPrefix),
         };
         if data.len() != prefix_len + body_len + CHECKSUM_LEN {
@@ -143,7 +185,7 @@ impl AccountId32 {
         let result = data[prefix_len..body_len + prefix_len]
             .try_into()
             .map_err(|_| FromSs58Error::BadLength)?;
-        Ok(AccountId32(result))
+        Ok((AccountId32(result), prefix))
     }
 }
 
@@ -243,4 +285,47 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn ss58_with_prefix_is_compatible_with_substrate_impl() {
+        use sp_core::crypto::Ss58AddressFormat;
+
+        let substrate_account = AccountKeyring::Alice.to_account_id();
+        let local_account = AccountId32(substrate_account.clone().into());
+
+        // prefixes encoded as a single byte (Polkadot, Astar, Substrate) and as two bytes
+        for prefix in [0u16, 5, 42, 1284, 16_383] {
+            let substrate_ss58 = substrate_account
+                .to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            let local_ss58 = local_account.to_ss58check_with_prefix(prefix);
+            assert_eq!(substrate_ss58, local_ss58);
+            assert_eq!(
+                AccountId32::from_ss58check(&local_ss58).unwrap(),
+                local_account
+            );
+        }
+
+        // the default prefix is unchanged
+        assert_ne!(
+            local_account.to_ss58check_with_prefix(5),
+            local_account.to_ss58check()
+        );
+    }
+
+    #[test]
+    fn ss58_prefix_is_decoded() {
+        use sp_core::crypto::Ss58AddressFormat;
+
+        let substrate_account = AccountKeyring::Bob.to_account_id();
+        let local_account = AccountId32(substrate_account.clone().into());
+
+        for prefix in [0u16, 5, 42, 64, 1284, 16_383] {
+            let ss58 = substrate_account
+                .to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            assert_eq!(
+                AccountId32::from_ss58check_with_prefix(&ss58),
+                Ok((local_account.clone(), prefix))
+            );
+        }
+    }
 }
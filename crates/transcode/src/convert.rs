@@ -0,0 +1,475 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Best-effort conversion of contract metadata between the ink! 4 and ink! 5 metadata
+//! formats, for tools which only support one of them.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use itertools::Itertools;
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+/// The size of the static buffer of ink! 5 contracts which do not configure it.
+const DEFAULT_STATIC_BUFFER_SIZE: u64 = 16384;
+
+/// A version of the ink! metadata format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataVersion {
+    /// The metadata of ink! 4 contracts, with the version `"4"`.
+    V4,
+    /// The metadata of ink! 5 contracts, with the version `5`.
+    V5,
+}
+
+impl MetadataVersion {
+    /// Reads the version of the ink! `metadata`.
+    pub fn of(metadata: &Map<String, Value>) -> Result<Self> {
+        match metadata.get("version") {
+            Some(Value::String(version)) if version == "4" => Ok(Self::V4),
+            Some(Value::Number(version)) if version.as_u64() == Some(5) => Ok(Self::V5),
+            Some(version) => Err(anyhow!("Unsupported ink! metadata version {version}")),
+            None => {
+                Err(anyhow!(
+                    "The metadata does not contain an ink! metadata version"
+                ))
+            }
+        }
+    }
+}
+
+impl FromStr for MetadataVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(version: &str) -> Result<Self> {
+        match version {
+            "v4" | "4" => Ok(Self::V4),
+            "v5" | "5" => Ok(Self::V5),
+            _ => {
+                Err(anyhow!(
+                    "Unknown ink! metadata version `{version}`, expected `v4` or `v5`"
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for MetadataVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V4 => f.write_str("v4"),
+            Self::V5 => f.write_str("v5"),
+        }
+    }
+}
+
+/// A field of the metadata which has no counterpart in the other metadata version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedField {
+    /// The path to the field, e.g. `spec.events.Transfer.signature_topic`.
+    pub path: String,
+    /// Whether the field was dropped, losing its information, rather than filled with
+    /// a default value.
+    pub lost: bool,
+    /// How the field was converted.
+    pub note: String,
+}
+
+/// The result of a conversion of contract metadata to another ink! metadata version.
+#[derive(Debug)]
+pub struct Conversion {
+    /// The converted metadata.
+    pub metadata: Map<String, Value>,
+    /// The fields which could not be mapped to the other metadata version.
+    pub unmapped: Vec<UnmappedField>,
+}
+
+impl Conversion {
+    /// Returns `true` if the information of any field was lost by the conversion.
+    pub fn is_lossy(&self) -> bool {
+        self.unmapped.iter().any(|field| field.lost)
+    }
+}
+
+/// Converts the ink! `metadata`, either the metadata file or the `.contract` bundle of a
+/// contract, to the metadata version `to`.
+///
+/// The fields outside of the ink! metadata, e.g. `source` and `contract`, are kept as
+/// they are.
+pub fn convert_metadata(
+    mut metadata: Map<String, Value>,
+    to: MetadataVersion,
+) -> Result<Conversion> {
+    let mut unmapped = Vec::new();
+    match (MetadataVersion::of(&metadata)?, to) {
+        (MetadataVersion::V4, MetadataVersion::V5) => {
+            upgrade(&mut metadata, &mut unmapped)?
+        }
+        (MetadataVersion::V5, MetadataVersion::V4) => {
+            downgrade(&mut metadata, &mut unmapped)?
+        }
+        _ => (),
+    }
+    Ok(Conversion { metadata, unmapped })
+}
+
+/// Converts ink! 4 metadata to ink! 5 metadata.
+fn upgrade(
+    metadata: &mut Map<String, Value>,
+    unmapped: &mut Vec<UnmappedField>,
+) -> Result<()> {
+    let storage = metadata.get("storage").cloned().unwrap_or_default();
+    let mut untyped_roots = Vec::new();
+    untyped_root_layouts(&storage, "storage", &mut untyped_roots);
+    if !untyped_roots.is_empty() {
+        return Err(anyhow!(
+            "The type of the root storage layouts {} is required by ink! metadata v5, \
+             but missing from the metadata",
+            untyped_roots
+                .iter()
+                .map(|path| format!("`{path}`"))
+                .join(", ")
+        ))
+    }
+    let storage_name = storage
+        .pointer("/root/layout/struct/name")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+    // The module of an event is not recorded in ink! 4 metadata, the module of the
+    // contract is the best guess.
+    let contract_name = metadata
+        .get("contract")
+        .and_then(|contract| contract.get("name"))
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+
+    let spec = spec_mut(metadata)?;
+    let environment = spec
+        .get_mut("environment")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| {
+            anyhow!(
+                "The environment types required by ink! metadata v5 are missing from \
+                 the metadata"
+            )
+        })?;
+    if !environment.contains_key("staticBufferSize") {
+        environment.insert(
+            "staticBufferSize".to_string(),
+            DEFAULT_STATIC_BUFFER_SIZE.into(),
+        );
+        unmapped.push(UnmappedField {
+            path: "spec.environment.staticBufferSize".to_string(),
+            lost: false,
+            note: format!("set to the default of {DEFAULT_STATIC_BUFFER_SIZE} bytes"),
+        });
+    }
+
+    for kind in ["constructors", "messages"] {
+        for (label, entry) in entries_mut(spec, kind) {
+            if !entry.contains_key("default") {
+                entry.insert("default".to_string(), false.into());
+                unmapped.push(UnmappedField {
+                    path: format!("spec.{kind}.{label}.default"),
+                    lost: false,
+                    note: "set to `false`".to_string(),
+                });
+            }
+        }
+    }
+
+    for (label, event) in entries_mut(spec, "events") {
+        if !event.contains_key("module_path") {
+            let module_path = contract_name.clone().unwrap_or_else(|| label.clone());
+            unmapped.push(UnmappedField {
+                path: format!("spec.events.{label}.module_path"),
+                lost: false,
+                note: format!("set to `{module_path}`"),
+            });
+            event.insert("module_path".to_string(), module_path.into());
+        }
+        if !event.contains_key("signature_topic") {
+            let topic = match &storage_name {
+                Some(storage_name) => {
+                    let topic = ink_v4_event_topic(storage_name, &label);
+                    format!("0x{}", hex::encode(topic)).into()
+                }
+                None => {
+                    unmapped.push(UnmappedField {
+                        path: format!("spec.events.{label}.signature_topic"),
+                        lost: false,
+                        note: "set to anonymous, the name of the storage struct the \
+                               topic is derived from is missing"
+                            .to_string(),
+                    });
+                    Value::Null
+                }
+            };
+            event.insert("signature_topic".to_string(), topic);
+        }
+    }
+
+    metadata.insert("version".to_string(), 5.into());
+    Ok(())
+}
+
+/// Converts ink! 5 metadata to ink! 4 metadata.
+fn downgrade(
+    metadata: &mut Map<String, Value>,
+    unmapped: &mut Vec<UnmappedField>,
+) -> Result<()> {
+    let spec = spec_mut(metadata)?;
+    if let Some(environment) = spec.get_mut("environment").and_then(Value::as_object_mut)
+    {
+        if environment.remove("staticBufferSize").is_some() {
+            unmapped.push(UnmappedField {
+                path: "spec.environment.staticBufferSize".to_string(),
+                lost: true,
+                note: "dropped".to_string(),
+            });
+        }
+    }
+
+    for (label, event) in entries_mut(spec, "events") {
+        if event.remove("module_path").is_some() {
+            unmapped.push(UnmappedField {
+                path: format!("spec.events.{label}.module_path"),
+                lost: true,
+                note: "dropped".to_string(),
+            });
+        }
+        match event.remove("signature_topic") {
+            Some(Value::Null) => {
+                unmapped.push(UnmappedField {
+                    path: format!("spec.events.{label}.signature_topic"),
+                    lost: true,
+                    note: "dropped, anonymous events are not supported by ink! 4"
+                        .to_string(),
+                })
+            }
+            Some(_) => {
+                unmapped.push(UnmappedField {
+                    path: format!("spec.events.{label}.signature_topic"),
+                    lost: true,
+                    note: "dropped, ink! 4 derives the topic from the event name"
+                        .to_string(),
+                })
+            }
+            None => (),
+        }
+    }
+
+    metadata.insert("version".to_string(), "4".into());
+    Ok(())
+}
+
+/// Returns the contract spec of the `metadata`.
+fn spec_mut(metadata: &mut Map<String, Value>) -> Result<&mut Map<String, Value>> {
+    metadata
+        .get_mut("spec")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| anyhow!("The metadata does not contain a contract spec"))
+}
+
+/// Returns the labels and the specs of the constructors, messages or events of the
+/// `spec`.
+fn entries_mut<'a>(
+    spec: &'a mut Map<String, Value>,
+    kind: &str,
+) -> impl Iterator<Item = (String, &'a mut Map<String, Value>)> {
+    spec.get_mut(kind)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+        .map(|entry| {
+            let label = entry
+                .get("label")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            (label, entry)
+        })
+}
+
+/// Collects the paths of the root storage layouts in the `layout` at `path` which do
+/// not record their type.
+fn untyped_root_layouts(layout: &Value, path: &str, paths: &mut Vec<String>) {
+    match layout {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = format!("{path}.{key}");
+                if key == "root"
+                    && value
+                        .as_object()
+                        .is_some_and(|root| !root.contains_key("ty"))
+                {
+                    paths.push(path.clone())
+                }
+                untyped_root_layouts(value, &path, paths)
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                untyped_root_layouts(value, &format!("{path}[{i}]"), paths)
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Returns the first topic ink! 4 contracts emit for the `event` of the contract with
+/// the storage struct `storage`.
+///
+/// It is the SCALE encoded empty prefix followed by `<storage>::<event>`, hashed with
+/// `blake2_256` if it is longer than a topic.
+fn ink_v4_event_topic(storage: &str, event: &str) -> [u8; 32] {
+    use blake2::{
+        digest::consts::U32,
+        Blake2b,
+        Digest,
+    };
+    let mut encoded = vec![0u8];
+    encoded.extend_from_slice(format!("{storage}::{event}").as_bytes());
+    let mut topic = [0u8; 32];
+    if encoded.len() <= topic.len() {
+        topic[..encoded.len()].copy_from_slice(&encoded);
+    } else {
+        topic.copy_from_slice(&Blake2b::<U32>::digest(&encoded));
+    }
+    topic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::generate_metadata,
+        ContractMessageTranscoder,
+    };
+    use ink_metadata::InkProject;
+
+    fn v5_metadata() -> Map<String, Value> {
+        match serde_json::to_value(generate_metadata()).unwrap() {
+            Value::Object(metadata) => metadata,
+            _ => panic!("the metadata must be an object"),
+        }
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(
+            MetadataVersion::from_str("v4").unwrap(),
+            MetadataVersion::V4
+        );
+        assert_eq!(MetadataVersion::from_str("5").unwrap(), MetadataVersion::V5);
+        assert!(MetadataVersion::from_str("v3").is_err());
+        assert_eq!(
+            MetadataVersion::of(&v5_metadata()).unwrap(),
+            MetadataVersion::V5
+        );
+    }
+
+    #[test]
+    fn downgrade_drops_v5_fields() {
+        let conversion = convert_metadata(v5_metadata(), MetadataVersion::V4).unwrap();
+        assert!(conversion.is_lossy());
+        assert_eq!(
+            MetadataVersion::of(&conversion.metadata).unwrap(),
+            MetadataVersion::V4
+        );
+        assert!(conversion
+            .unmapped
+            .iter()
+            .any(|field| field.path == "spec.events.Event1.signature_topic"));
+        assert!(conversion
+            .unmapped
+            .iter()
+            .any(|field| field.path == "spec.environment.staticBufferSize"));
+        let event = &conversion.metadata["spec"]["events"][0];
+        assert!(event.get("signature_topic").is_none());
+        assert!(event.get("module_path").is_none());
+    }
+
+    #[test]
+    fn upgraded_metadata_loads_in_transcoder() {
+        let mut v4 = convert_metadata(v5_metadata(), MetadataVersion::V4)
+            .unwrap()
+            .metadata;
+        let spec = v4["spec"].as_object_mut().unwrap();
+        for entry in spec["messages"].as_array_mut().unwrap() {
+            entry.as_object_mut().unwrap().remove("default");
+        }
+
+        let conversion = convert_metadata(v4, MetadataVersion::V5).unwrap();
+        assert!(!conversion.is_lossy());
+        assert!(conversion
+            .unmapped
+            .iter()
+            .any(|field| field.path == "spec.messages.flip.default"));
+        assert_eq!(
+            conversion.metadata["spec"]["events"][0]["signature_topic"],
+            format!(
+                "0x{}",
+                hex::encode(ink_v4_event_topic("Transcode", "Event1"))
+            )
+        );
+
+        let project: InkProject =
+            serde_json::from_value(Value::Object(conversion.metadata)).unwrap();
+        let transcoder = ContractMessageTranscoder::new(project);
+        let original = ContractMessageTranscoder::new(generate_metadata());
+        assert_eq!(
+            transcoder
+                .encode("uint_array_args", ["[1, 2, 3, 4]"])
+                .unwrap(),
+            original
+                .encode("uint_array_args", ["[1, 2, 3, 4]"])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_untyped_root_layouts() {
+        let mut v4 = convert_metadata(v5_metadata(), MetadataVersion::V4)
+            .unwrap()
+            .metadata;
+        v4["storage"]["root"].as_object_mut().unwrap().remove("ty");
+        let err = convert_metadata(v4, MetadataVersion::V5)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`storage.root`"), "{err}");
+    }
+
+    #[test]
+    fn derives_ink_v4_event_topics() {
+        let mut expected = [0u8; 32];
+        expected[1..11].copy_from_slice(b"Erc20::Foo");
+        assert_eq!(ink_v4_event_topic("Erc20", "Foo"), expected);
+        assert_ne!(
+            ink_v4_event_topic("Erc20", "TransferWithAVeryLongName"),
+            ink_v4_event_topic("Erc20", "ApprovalWithAVeryLongName")
+        );
+    }
+}
@@ -17,10 +17,12 @@
 use super::{
     env_types::EnvTypesTranscoder,
     scon::{
+        Hex,
         Map,
         Tuple,
         Value,
     },
+    wide_int::WideInt,
     CompositeTypeFields,
 };
 use anyhow::{
@@ -45,6 +47,7 @@ use scale_info::{
     TypeDefPrimitive,
     TypeDefVariant,
 };
+use std::str::FromStr;
 
 pub struct Decoder<'a> {
     registry: &'a PortableRegistry,
@@ -91,6 +94,13 @@ impl<'a> Decoder<'a> {
             anyhow::anyhow!("Failed to find type with id '{}'", type_id)
         })?;
 
+        // byte arrays are displayed as hex
+        if let TypeDef::Primitive(TypeDefPrimitive::U8) = ty.type_def {
+            let mut bytes = vec![0u8; len];
+            input.read(&mut bytes)?;
+            return Ok(Value::Hex(Hex::from_str(&hex::encode(bytes))?))
+        }
+
         let mut elems = Vec::new();
         while elems.len() < len {
             let elem = self.decode_type(type_id, ty, input)?;
@@ -105,6 +115,11 @@ impl<'a> Decoder<'a> {
         ty: &Type<PortableForm>,
         input: &mut &[u8],
     ) -> Result<Value> {
+        if let Some(wide_int) = WideInt::from_type(self.registry, ty) {
+            let mut bytes = vec![0u8; wide_int.bytes()];
+            input.read(&mut bytes)?;
+            return Ok(wide_int.decode(&bytes))
+        }
         match &ty.type_def {
             TypeDef::Composite(composite) => {
                 let ident = ty.path.segments.last().map(|s| s.as_str());
@@ -226,17 +241,14 @@ impl<'a> Decoder<'a> {
             TypeDefPrimitive::U32 => decode_uint::<u32>(input),
             TypeDefPrimitive::U64 => decode_uint::<u64>(input),
             TypeDefPrimitive::U128 => decode_uint::<u128>(input),
-            TypeDefPrimitive::U256 => {
-                Err(anyhow::anyhow!("U256 currently not supported"))
+            TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+                unreachable!("256-bit integers are decoded as `WideInt`")
             }
             TypeDefPrimitive::I8 => decode_int::<i8>(input),
             TypeDefPrimitive::I16 => decode_int::<i16>(input),
             TypeDefPrimitive::I32 => decode_int::<i32>(input),
             TypeDefPrimitive::I64 => decode_int::<i64>(input),
             TypeDefPrimitive::I128 => decode_int::<i128>(input),
-            TypeDefPrimitive::I256 => {
-                Err(anyhow::anyhow!("I256 currently not supported"))
-            }
         }
     }
 
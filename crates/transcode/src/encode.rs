@@ -16,7 +16,12 @@
 
 use super::{
     env_types::EnvTypesTranscoder,
+    field_path::{
+        FieldPathError,
+        PathSegment,
+    },
     scon::Value,
+    wide_int::WideInt,
     CompositeTypeFields,
 };
 use anyhow::Result;
@@ -66,6 +71,21 @@ impl<'a> Encoder<'a> {
     }
 
     pub fn encode<O>(&self, type_id: u32, value: &Value, output: &mut O) -> Result<()>
+    where
+        O: Output + Debug,
+    {
+        self.encode_with_type_name(type_id, None, value, output)
+    }
+
+    /// Encodes a `value` of the type with the `type_id`, declared with the `type_name`
+    /// of an argument or field, e.g. `Balance`.
+    pub fn encode_with_type_name<O>(
+        &self,
+        type_id: u32,
+        type_name: Option<&str>,
+        value: &Value,
+        output: &mut O,
+    ) -> Result<()>
     where
         O: Output + Debug,
     {
@@ -79,7 +99,14 @@ impl<'a> Encoder<'a> {
             type_id,
             ty.type_def,
         );
-        if !self.env_types.try_encode(type_id, value, output)? {
+        if !self
+            .env_types
+            .try_encode(type_id, type_name, value, output)?
+        {
+            if let Some(wide_int) = WideInt::from_type(self.registry, ty) {
+                output.write(&wide_int.encode(value)?);
+                return Ok(())
+            }
             match &ty.type_def {
                 TypeDef::Composite(composite) => {
                     self.encode_composite(&composite.fields, value, output)
@@ -88,11 +115,11 @@ impl<'a> Encoder<'a> {
                     self.encode_variant_type(variant, value, output)
                 }
                 TypeDef::Array(array) => {
-                    self.encode_seq(&array.type_param, value, false, output)
+                    self.encode_seq(&array.type_param, value, Some(array.len), output)
                 }
                 TypeDef::Tuple(tuple) => self.encode_tuple(tuple, value, output),
                 TypeDef::Sequence(sequence) => {
-                    self.encode_seq(&sequence.type_param, value, true, output)
+                    self.encode_seq(&sequence.type_param, value, None, output)
                 }
                 TypeDef::Primitive(primitive) => {
                     self.encode_primitive(primitive, value, output)
@@ -118,8 +145,10 @@ impl<'a> Encoder<'a> {
             Value::Map(map) => {
                 match struct_type {
                     CompositeTypeFields::Unnamed(fields) => {
-                        for (field, value) in fields.iter().zip(map.values()) {
-                            self.encode(field.ty.id, value, output)?;
+                        for (i, (field, value)) in
+                            fields.iter().zip(map.values()).enumerate()
+                        {
+                            self.encode_field(field, value, i, output)?;
                         }
                         Ok(())
                     }
@@ -130,14 +159,19 @@ impl<'a> Encoder<'a> {
                             let value = map.get_by_str(field_name).ok_or_else(|| {
                                 anyhow::anyhow!("Missing a field named `{}`", field_name)
                             })?;
-                            self.encode(named_field.field().ty.id, value, output)
-                                .map_err(|e| {
-                                    anyhow::anyhow!(
-                                        "Error encoding field `{}`: {}",
-                                        field_name,
-                                        e
-                                    )
-                                })?;
+                            let field = named_field.field();
+                            self.encode_with_type_name(
+                                field.ty.id,
+                                field.type_name.as_deref(),
+                                value,
+                                output,
+                            )
+                            .map_err(|e| {
+                                FieldPathError::prepend(
+                                    e,
+                                    PathSegment::Field(field_name.to_string()),
+                                )
+                            })?;
                         }
                         Ok(())
                     }
@@ -146,8 +180,10 @@ impl<'a> Encoder<'a> {
             Value::Tuple(tuple) => {
                 match struct_type {
                     CompositeTypeFields::Unnamed(fields) => {
-                        for (field, value) in fields.iter().zip(tuple.values()) {
-                            self.encode(field.ty.id, value, output)?;
+                        for (i, (field, value)) in
+                            fields.iter().zip(tuple.values()).enumerate()
+                        {
+                            self.encode_field(field, value, i, output)?;
                         }
                         Ok(())
                     }
@@ -159,7 +195,12 @@ impl<'a> Encoder<'a> {
             }
             v => {
                 if let Ok(single_field) = fields.iter().exactly_one() {
-                    self.encode(single_field.ty.id, value, output)
+                    self.encode_with_type_name(
+                        single_field.ty.id,
+                        single_field.type_name.as_deref(),
+                        value,
+                        output,
+                    )
                 } else {
                     Err(anyhow::anyhow!(
                         "Expected a Map or a Tuple or a single Value for a composite data type, found {:?}",
@@ -170,6 +211,33 @@ impl<'a> Encoder<'a> {
         }
     }
 
+    /// Encode the unnamed `field` at `index` of a tuple struct.
+    fn encode_field<O: Output + Debug>(
+        &self,
+        field: &Field<PortableForm>,
+        value: &Value,
+        index: usize,
+        output: &mut O,
+    ) -> Result<()> {
+        self.encode_with_type_name(field.ty.id, field.type_name.as_deref(), value, output)
+            .map_err(|e| {
+                FieldPathError::prepend(e, PathSegment::Field(index.to_string()))
+            })
+    }
+
+    /// Encode the element at `index` of a tuple.
+    fn encode_element<O: Output + Debug>(
+        &self,
+        type_id: u32,
+        value: &Value,
+        index: usize,
+        output: &mut O,
+    ) -> Result<()> {
+        self.encode(type_id, value, output).map_err(|e| {
+            FieldPathError::prepend(e, PathSegment::Field(index.to_string()))
+        })
+    }
+
     fn encode_tuple<O: Output + Debug>(
         &self,
         tuple: &TypeDefTuple<PortableForm>,
@@ -178,8 +246,10 @@ impl<'a> Encoder<'a> {
     ) -> Result<()> {
         match value {
             Value::Tuple(tuple_val) => {
-                for (field_type, value) in tuple.fields.iter().zip(tuple_val.values()) {
-                    self.encode(field_type.id, value, output)?;
+                for (i, (field_type, value)) in
+                    tuple.fields.iter().zip(tuple_val.values()).enumerate()
+                {
+                    self.encode_element(field_type.id, value, i, output)?;
                 }
                 Ok(())
             }
@@ -216,16 +286,19 @@ impl<'a> Encoder<'a> {
             v => Err(anyhow::anyhow!("Invalid enum variant value '{:?}'", v)),
         }?;
 
-        let (index, variant) = variant_def
+        let variant = variant_def
             .variants
             .iter()
-            .find_position(|v| v.name == variant_ident)
-            .ok_or_else(|| anyhow::anyhow!("No variant '{}' found", variant_ident))?;
+            .find(|v| v.name == variant_ident)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No variant '{}' found, expected one of: {}",
+                    variant_ident,
+                    variant_def.variants.iter().map(|v| &v.name).join(", ")
+                )
+            })?;
 
-        let index: u8 = index
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("Variant index > 255"))?;
-        output.push_byte(index);
+        output.push_byte(variant.index);
 
         self.encode_composite(&variant.fields, value, output)
     }
@@ -234,21 +307,40 @@ impl<'a> Encoder<'a> {
         &self,
         ty: &<PortableForm as Form>::Type,
         value: &Value,
-        encode_len: bool,
+        array_len: Option<u32>,
         output: &mut O,
     ) -> Result<()> {
         match value {
             Value::Seq(values) => {
-                if encode_len {
+                if array_len.is_none() {
                     Compact(values.len() as u32).encode_to(output);
                 }
-                for value in values.elems() {
-                    self.encode(ty.id, value, output)?;
+                for (i, value) in values.elems().iter().enumerate() {
+                    self.encode(ty.id, value, output)
+                        .map_err(|e| FieldPathError::prepend(e, PathSegment::Index(i)))?;
                 }
             }
             Value::Hex(hex) => {
-                if encode_len {
-                    Compact(hex.bytes().len() as u32).encode_to(output);
+                let elem_ty = self.registry.resolve(ty.id).ok_or_else(|| {
+                    anyhow::anyhow!("Failed to resolve type with id '{:?}'", ty.id)
+                })?;
+                if !matches!(elem_ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)) {
+                    return Err(anyhow::anyhow!(
+                        "Hex literals can only be encoded as byte arrays, found {:?}",
+                        value
+                    ))
+                }
+                match array_len {
+                    Some(len) if hex.bytes().len() != len as usize => {
+                        return Err(anyhow::anyhow!(
+                            "Expected {} bytes for `[u8; {}]`, provided {} bytes",
+                            len,
+                            len,
+                            hex.bytes().len()
+                        ))
+                    }
+                    Some(_) => (),
+                    None => Compact(hex.bytes().len() as u32).encode_to(output),
                 }
                 for byte in hex.bytes() {
                     output.push_byte(*byte);
@@ -294,17 +386,14 @@ impl<'a> Encoder<'a> {
             TypeDefPrimitive::U32 => encode_uint::<u32, O>(value, "u32", output),
             TypeDefPrimitive::U64 => encode_uint::<u64, O>(value, "u64", output),
             TypeDefPrimitive::U128 => encode_uint::<u128, O>(value, "u128", output),
-            TypeDefPrimitive::U256 => {
-                Err(anyhow::anyhow!("U256 currently not supported"))
+            TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+                unreachable!("256-bit integers are encoded as `WideInt`")
             }
             TypeDefPrimitive::I8 => encode_int::<i8, O>(value, "i8", output),
             TypeDefPrimitive::I16 => encode_int::<i16, O>(value, "i16", output),
             TypeDefPrimitive::I32 => encode_int::<i32, O>(value, "i32", output),
             TypeDefPrimitive::I64 => encode_int::<i64, O>(value, "i64", output),
             TypeDefPrimitive::I128 => encode_int::<i128, O>(value, "i128", output),
-            TypeDefPrimitive::I256 => {
-                Err(anyhow::anyhow!("I256 currently not supported"))
-            }
         }
     }
 
@@ -419,7 +508,7 @@ where
             let uint = (*i).try_into()?;
             Ok(uint)
         }
-        Value::String(s) => {
+        Value::String(s) | Value::Literal(s) => {
             let sanitized = s.replace(&['_', ','][..], "");
             let uint = T::from_str(&sanitized)?;
             Ok(uint)
@@ -438,7 +527,11 @@ where
     }
 }
 
-fn encode_uint<T, O>(value: &Value, expected: &str, output: &mut O) -> Result<()>
+pub(crate) fn encode_uint<T, O>(
+    value: &Value,
+    expected: &str,
+    output: &mut O,
+) -> Result<()>
 where
     T: TryFrom<u128> + TryFromHex + FromStr + Encode,
     <T as TryFrom<u128>>::Error: Error + Send + Sync + 'static,
@@ -467,7 +560,7 @@ where
             let i: T = (*u).try_into()?;
             Ok(i)
         }
-        Value::String(s) => {
+        Value::String(s) | Value::Literal(s) => {
             let sanitized = s.replace(&['_', ','][..], "");
             let i = T::from_str(&sanitized)?;
             Ok(i)
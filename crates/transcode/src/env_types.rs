@@ -15,15 +15,18 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
+    encode::encode_uint,
     AccountId32,
     Hex,
     Value,
+    SUBSTRATE_SS58_PREFIX,
 };
 use anyhow::{
     Context,
     Result,
 };
 use scale::{
+    Compact,
     Decode,
     Encode,
     Output,
@@ -32,6 +35,7 @@ use scale_info::{
     form::PortableForm,
     IntoPortable,
     Path,
+    TypeDefPrimitive,
     TypeInfo,
 };
 use std::{
@@ -39,6 +43,7 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     str::FromStr,
+    sync::Arc,
 };
 
 /// Provides custom encoding and decoding for predefined environment types.
@@ -46,6 +51,7 @@ use std::{
 pub struct EnvTypesTranscoder {
     encoders: HashMap<u32, Box<dyn CustomTypeEncoder>>,
     decoders: HashMap<u32, Box<dyn CustomTypeDecoder>>,
+    balance: Option<Balance>,
 }
 
 impl EnvTypesTranscoder {
@@ -53,25 +59,58 @@ impl EnvTypesTranscoder {
     pub fn new(
         encoders: HashMap<u32, Box<dyn CustomTypeEncoder>>,
         decoders: HashMap<u32, Box<dyn CustomTypeDecoder>>,
+        balance: Option<Balance>,
     ) -> Self {
-        Self { encoders, decoders }
+        Self {
+            encoders,
+            decoders,
+            balance,
+        }
+    }
+
+    /// Returns `true` if the given type id is for a type with custom encoding.
+    pub fn has_encoder(&self, type_id: u32) -> bool {
+        self.encoders.contains_key(&type_id)
+            || self
+                .balance
+                .as_ref()
+                .is_some_and(|balance| balance.type_id == type_id)
     }
 
     /// If the given type id is for a type with custom encoding, encodes the given value
     /// with the custom encoder and returns `true`. Otherwise returns `false`.
     ///
+    /// The `Balance` encoder only applies to values declared with the `type_name` of
+    /// the `Balance` type, since other integers share its type id.
+    ///
     /// # Errors
     ///
     /// - If the custom encoding fails.
+    /// - If a denominated balance is given for an integer which is not a `Balance`.
     pub fn try_encode<O>(
         &self,
         type_id: u32,
+        type_name: Option<&str>,
         value: &Value,
         output: &mut O,
     ) -> Result<bool>
     where
         O: Output,
     {
+        if let Some(balance) = self
+            .balance
+            .as_ref()
+            .filter(|balance| balance.type_id == type_id)
+        {
+            if balance.is_declared_as(type_name) {
+                let encoded = balance
+                    .encode_value(value)
+                    .context("Error encoding custom type")?;
+                output.write(&encoded);
+                return Ok(true)
+            }
+            balance.ensure_not_denominated(value)?;
+        }
         match self.encoders.get(&type_id) {
             Some(encoder) => {
                 tracing::debug!("Encoding type {:?} with custom encoder", type_id);
@@ -111,6 +150,10 @@ impl EnvTypesTranscoder {
 pub struct PathKey(Vec<String>);
 
 impl PathKey {
+    pub fn new(segments: &[&str]) -> Self {
+        PathKey(segments.iter().map(ToString::to_string).collect())
+    }
+
     pub fn from_type<T>() -> Self
     where
         T: TypeInfo,
@@ -145,8 +188,27 @@ pub trait CustomTypeDecoder: Send + Sync {
 ///
 /// Enables an `AccountId` to be input/ouput as an SS58 Encoded literal e.g.
 /// 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+///
+/// Decoded accounts are displayed with the configured SS58 address prefix, which
+/// defaults to the generic Substrate prefix `42`.
 #[derive(Clone)]
-pub struct AccountId;
+pub struct AccountId {
+    ss58_prefix: u16,
+}
+
+impl AccountId {
+    /// Create an `AccountId` transcoder which displays decoded accounts using the given
+    /// SS58 address prefix.
+    pub fn with_ss58_prefix(ss58_prefix: u16) -> Self {
+        Self { ss58_prefix }
+    }
+}
+
+impl Default for AccountId {
+    fn default() -> Self {
+        Self::with_ss58_prefix(SUBSTRATE_SS58_PREFIX)
+    }
+}
 
 impl CustomTypeEncoder for AccountId {
     fn encode_value(&self, value: &Value) -> Result<Vec<u8>> {
@@ -177,6 +239,10 @@ impl CustomTypeEncoder for AccountId {
                     )
                 })?
             }
+            // the structural representation decoded with raw values, `AccountId(0x..)`
+            Value::Tuple(tuple) if tuple.values().count() == 1 => {
+                return self.encode_value(tuple.values().next().expect("one value"))
+            }
             _ => {
                 return Err(anyhow::anyhow!(
                     "Expected a string or a literal for an AccountId"
@@ -190,7 +256,109 @@ impl CustomTypeEncoder for AccountId {
 impl CustomTypeDecoder for AccountId {
     fn decode_value(&self, input: &mut &[u8]) -> Result<Value> {
         let account_id = AccountId32::decode(input)?;
-        Ok(Value::Literal(account_id.to_ss58check()))
+        Ok(Value::Literal(
+            account_id.to_ss58check_with_prefix(self.ss58_prefix),
+        ))
+    }
+}
+
+/// Converts a denominated balance literal such as `1.5UNIT` into the smallest unit of
+/// the chain's token.
+pub type Denominate = Arc<dyn Fn(&str) -> Result<u128> + Send + Sync>;
+
+/// Custom encoding for the contract's `Balance` type.
+///
+/// Besides plain integers, which are the balance in the smallest unit of the chain's
+/// token, enables a `Balance` to be input as a denominated literal e.g. `1.5UNIT`.
+///
+/// All integers of the same width share the type id of the `Balance` primitive, so the
+/// encoder only applies to arguments and fields declared with the `type_name` of the
+/// `Balance`, e.g. `amount: Balance`. Denominated literals are rejected for any other
+/// integer.
+#[derive(Clone)]
+pub struct Balance {
+    type_id: u32,
+    type_name: String,
+    primitive: TypeDefPrimitive,
+    denominate: Denominate,
+}
+
+impl Balance {
+    /// Create a `Balance` encoder for a balance of the given primitive type with the
+    /// `type_id` and `type_name`, converting denominated literals with `denominate`.
+    pub fn new(
+        type_id: u32,
+        type_name: &str,
+        primitive: TypeDefPrimitive,
+        denominate: Denominate,
+    ) -> Self {
+        Self {
+            type_id,
+            type_name: type_name.to_string(),
+            primitive,
+            denominate,
+        }
+    }
+
+    /// Returns `true` if the last segment of the declared `type_name`, e.g.
+    /// `Balance` of `ink::primitives::Balance`, is the name of the `Balance` type.
+    fn is_declared_as(&self, type_name: Option<&str>) -> bool {
+        type_name
+            .and_then(|type_name| type_name.rsplit("::").next())
+            .is_some_and(|name| name.trim() == self.type_name)
+    }
+
+    /// Returns an error if the `value` is a denominated literal, given for an integer
+    /// which is not declared as a `Balance`.
+    fn ensure_not_denominated(&self, value: &Value) -> Result<()> {
+        match value {
+            Value::Literal(literal) | Value::String(literal)
+                if literal.replace('_', "").parse::<u128>().is_err() =>
+            {
+                Err(anyhow::anyhow!(
+                    "Denominated balance `{literal}` is only accepted for values of the \
+                     `{}` type",
+                    self.type_name
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl CustomTypeEncoder for Balance {
+    fn encode_value(&self, value: &Value) -> Result<Vec<u8>> {
+        let denominated;
+        let value = match value {
+            Value::Literal(literal) | Value::String(literal) => {
+                let balance = match literal.replace('_', "").parse::<u128>() {
+                    Ok(balance) => balance,
+                    Err(_) => {
+                        (self.denominate)(literal).with_context(|| {
+                            format!("Error parsing denominated balance `{literal}`")
+                        })?
+                    }
+                };
+                denominated = Value::UInt(balance);
+                &denominated
+            }
+            value => value,
+        };
+        let mut output = Vec::new();
+        match &self.primitive {
+            TypeDefPrimitive::U8 => encode_uint::<u8, _>(value, "u8", &mut output),
+            TypeDefPrimitive::U16 => encode_uint::<u16, _>(value, "u16", &mut output),
+            TypeDefPrimitive::U32 => encode_uint::<u32, _>(value, "u32", &mut output),
+            TypeDefPrimitive::U64 => encode_uint::<u64, _>(value, "u64", &mut output),
+            TypeDefPrimitive::U128 => encode_uint::<u128, _>(value, "u128", &mut output),
+            primitive => {
+                Err(anyhow::anyhow!(
+                    "Unsupported primitive type {:?} for a Balance",
+                    primitive
+                ))
+            }
+        }?;
+        Ok(output)
     }
 }
 
@@ -204,3 +372,18 @@ impl CustomTypeDecoder for Hash {
         Ok(Value::Hex(Hex::from_str(&format!("{hash:?}"))?))
     }
 }
+
+/// The path of `sp_weights::Weight` in the type registry of a chain.
+pub const WEIGHT_PATH: [&str; 3] = ["sp_weights", "weight_v2", "Weight"];
+
+/// Custom decoding for the `sp_weights::Weight` type so that it is displayed as
+/// `ref_time/proof_size`.
+pub struct Weight;
+
+impl CustomTypeDecoder for Weight {
+    fn decode_value(&self, input: &mut &[u8]) -> Result<Value> {
+        let ref_time = Compact::<u64>::decode(input)?.0;
+        let proof_size = Compact::<u64>::decode(input)?.0;
+        Ok(Value::Literal(format!("{ref_time}/{proof_size}")))
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::{
+    Display,
+    Formatter,
+    Result as FmtResult,
+};
+
+/// A segment of the path to a value nested in a composite value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A named field of a struct or an unnamed field of a tuple, e.g. `.rate` or `.0`.
+    Field(String),
+    /// An element of a sequence or an array, e.g. `[2]`.
+    Index(usize),
+}
+
+/// An error transcoding a value nested in a composite value, with the path to the
+/// failing field e.g. `fees[2].rate`.
+#[derive(Debug)]
+pub struct FieldPathError {
+    path: Vec<PathSegment>,
+    source: anyhow::Error,
+}
+
+impl FieldPathError {
+    /// Prepends `segment` to the path of the given error, wrapping it in a
+    /// `FieldPathError` if it isn't one already.
+    pub fn prepend(err: anyhow::Error, segment: PathSegment) -> anyhow::Error {
+        match err.downcast::<FieldPathError>() {
+            Ok(mut err) => {
+                err.path.insert(0, segment);
+                err.into()
+            }
+            Err(source) => {
+                FieldPathError {
+                    path: vec![segment],
+                    source,
+                }
+                .into()
+            }
+        }
+    }
+
+    /// The path to the failing field, relative to the outermost value.
+    pub fn path(&self) -> String {
+        self.path_from("").trim_start_matches('.').to_string()
+    }
+
+    /// The path to the failing field, starting at the outermost value named `root`.
+    pub fn path_from(&self, root: &str) -> String {
+        let segments = self.path.iter().map(|segment| {
+            match segment {
+                PathSegment::Field(name) => format!(".{name}"),
+                PathSegment::Index(index) => format!("[{index}]"),
+            }
+        });
+        std::iter::once(root.to_string()).chain(segments).collect()
+    }
+
+    /// The error encountered transcoding the field.
+    pub fn source_error(&self) -> &anyhow::Error {
+        &self.source
+    }
+}
+
+impl Display for FieldPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}: {:#}", self.path(), self.source)
+    }
+}
+
+impl std::error::Error for FieldPathError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_path() {
+        let err = anyhow::anyhow!("invalid digit found in string");
+        let err = FieldPathError::prepend(err, PathSegment::Field("rate".into()));
+        let err = FieldPathError::prepend(err, PathSegment::Index(2));
+        let err = FieldPathError::prepend(err, PathSegment::Field("fees".into()));
+
+        let err = err.downcast::<FieldPathError>().unwrap();
+        assert_eq!(err.path(), "fees[2].rate");
+        assert_eq!(err.path_from("config"), "config.fees[2].rate");
+        assert_eq!(
+            err.to_string(),
+            "fees[2].rate: invalid digit found in string"
+        );
+    }
+}
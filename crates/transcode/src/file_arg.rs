@@ -0,0 +1,145 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Argument values read from a file with `@path`, or from stdin with `@-`, for values
+//! which are too large to pass on the command line.
+
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use scale_info::{
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::io::Read;
+
+/// An argument given on the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Arg<'a> {
+    /// The value itself, with an escaped leading `@@` replaced by `@`.
+    Inline(&'a str),
+    /// The path of the file to read the value from, `-` for stdin.
+    File(&'a str),
+}
+
+impl<'a> Arg<'a> {
+    pub fn parse(arg: &'a str) -> Self {
+        match arg.strip_prefix('@') {
+            Some(rest) if rest.starts_with('@') => Self::Inline(rest),
+            Some(path) => Self::File(path),
+            None => Self::Inline(arg),
+        }
+    }
+}
+
+/// How the contents of an argument file are read, depending on the argument type.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgKind {
+    /// `Vec<u8>` or `[u8; N]`: the raw bytes of the file.
+    Bytes,
+    /// `String`: the contents of the file as UTF-8.
+    String,
+    /// Any other type: the contents of the file in the syntax of the arguments.
+    Value,
+}
+
+impl ArgKind {
+    pub fn of(registry: &PortableRegistry, type_id: u32) -> Self {
+        let Some(ty) = registry.resolve(type_id) else {
+            return Self::Value
+        };
+        let elem_id = match &ty.type_def {
+            TypeDef::Primitive(TypeDefPrimitive::Str) => return Self::String,
+            TypeDef::Sequence(seq) => seq.type_param.id,
+            TypeDef::Array(arr) => arr.type_param.id,
+            _ => return Self::Value,
+        };
+        match registry.resolve(elem_id).map(|elem| &elem.type_def) {
+            Some(TypeDef::Primitive(TypeDefPrimitive::U8)) => Self::Bytes,
+            _ => Self::Value,
+        }
+    }
+}
+
+/// Reads the contents of the argument file at `path`, or of stdin if it is `-`.
+pub fn read(path: &str) -> Result<Vec<u8>> {
+    if path.is_empty() {
+        return Err(anyhow!(
+            "Missing file path after `@`, use `@@` for a value starting with `@`"
+        ))
+    }
+    if path == "-" {
+        let mut contents = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut contents)
+            .context("Failed to read the argument from stdin")?;
+        return Ok(contents)
+    }
+    std::fs::read(path)
+        .with_context(|| format!("Failed to read the argument file `{path}`"))
+}
+
+/// Returns the contents of the argument file at `path` as UTF-8.
+pub fn utf8(path: &str, contents: Vec<u8>) -> Result<String> {
+    String::from_utf8(contents)
+        .map_err(|err| anyhow!("The argument file `{path}` is not valid UTF-8: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_info::{
+        MetaType,
+        Registry,
+    };
+
+    fn kind<T: scale_info::TypeInfo + 'static>() -> ArgKind {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&MetaType::new::<T>()).id;
+        ArgKind::of(&registry.into(), type_id)
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(Arg::parse("42"), Arg::Inline("42"));
+        assert_eq!(Arg::parse("@proof.bin"), Arg::File("proof.bin"));
+        assert_eq!(Arg::parse("@-"), Arg::File("-"));
+        assert_eq!(Arg::parse("@@handle"), Arg::Inline("@handle"));
+        assert_eq!(Arg::parse("@"), Arg::File(""));
+    }
+
+    #[test]
+    fn arg_kinds() {
+        assert_eq!(kind::<Vec<u8>>(), ArgKind::Bytes);
+        assert_eq!(kind::<[u8; 32]>(), ArgKind::Bytes);
+        assert_eq!(kind::<String>(), ArgKind::String);
+        assert_eq!(kind::<Vec<u32>>(), ArgKind::Value);
+        assert_eq!(kind::<Option<Vec<u8>>>(), ArgKind::Value);
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let err = read("does/not/exist.bin").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to read the argument file `does/not/exist.bin`"
+        );
+        assert!(read("").is_err());
+    }
+}
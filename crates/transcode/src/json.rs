@@ -0,0 +1,362 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    env_types::EnvTypesTranscoder,
+    field_path::{
+        FieldPathError,
+        PathSegment,
+    },
+    scon::{
+        Hex,
+        Map,
+        Seq,
+        Tuple,
+        Value,
+    },
+    wide_int::WideInt,
+    CompositeTypeFields,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+    TypeDefVariant,
+};
+use serde_json::Value as Json;
+use std::str::FromStr;
+
+/// Returns the JSON value of an argument if it is a JSON document containing an object,
+/// i.e. an argument for a composite type given as a JSON literal.
+///
+/// Any other argument is left to the SCON parser, so that the existing syntax keeps
+/// working unchanged.
+pub fn parse_json_arg(arg: &str) -> Option<Json> {
+    let json = serde_json::from_str(arg.trim()).ok()?;
+    contains_object(&json).then_some(json)
+}
+
+fn contains_object(json: &Json) -> bool {
+    match json {
+        Json::Object(_) => true,
+        Json::Array(elems) => elems.iter().any(contains_object),
+        _ => false,
+    }
+}
+
+/// Converts JSON values into [`Value`]s, guided by the expected type from the registry.
+pub struct JsonConverter<'a> {
+    registry: &'a PortableRegistry,
+    env_types: &'a EnvTypesTranscoder,
+}
+
+impl<'a> JsonConverter<'a> {
+    pub fn new(
+        registry: &'a PortableRegistry,
+        env_types: &'a EnvTypesTranscoder,
+    ) -> Self {
+        Self {
+            registry,
+            env_types,
+        }
+    }
+
+    pub fn convert(&self, type_id: u32, json: &Json) -> Result<Value> {
+        let ty = self.registry.resolve(type_id).ok_or_else(|| {
+            anyhow::anyhow!("Failed to resolve type with id '{:?}'", type_id)
+        })?;
+
+        if self.env_types.has_encoder(type_id) {
+            return scalar_value(json)
+        }
+        if WideInt::from_type(self.registry, ty).is_some() {
+            return wide_int_value(json)
+        }
+        match &ty.type_def {
+            TypeDef::Composite(composite) => {
+                self.convert_composite(
+                    ty.path.ident().as_deref(),
+                    &composite.fields,
+                    json,
+                )
+            }
+            TypeDef::Variant(variant) => self.convert_variant(ty, variant, json),
+            TypeDef::Array(array) => self.convert_seq(array.type_param.id, json),
+            TypeDef::Sequence(sequence) => self.convert_seq(sequence.type_param.id, json),
+            TypeDef::Tuple(tuple) => {
+                match json {
+                    Json::Null if tuple.fields.is_empty() => {
+                        Ok(Value::Tuple(Tuple::new(None, Vec::new())))
+                    }
+                    Json::Array(elems) if elems.len() == tuple.fields.len() => {
+                        let values = tuple
+                            .fields
+                            .iter()
+                            .zip(elems)
+                            .map(|(field, elem)| self.convert(field.id, elem))
+                            .collect::<Result<_>>()?;
+                        Ok(Value::Tuple(Tuple::new(None, values)))
+                    }
+                    _ => {
+                        Err(anyhow::anyhow!(
+                            "Expected an array of {} elements for a tuple, found {}",
+                            tuple.fields.len(),
+                            json
+                        ))
+                    }
+                }
+            }
+            TypeDef::Primitive(primitive) => primitive_value(primitive, json),
+            TypeDef::Compact(compact) => self.convert(compact.type_param.id, json),
+            TypeDef::BitSequence(_) => {
+                Err(anyhow::anyhow!("bitvec encoding not yet supported"))
+            }
+        }
+    }
+
+    fn convert_composite(
+        &self,
+        ident: Option<&str>,
+        fields: &[Field<PortableForm>],
+        json: &Json,
+    ) -> Result<Value> {
+        let name = ident.unwrap_or("struct");
+        match CompositeTypeFields::from_fields(fields)? {
+            CompositeTypeFields::Named(named_fields) => {
+                let Json::Object(object) = json else {
+                    anyhow::bail!("Expected a JSON object for `{}`, found {}", name, json)
+                };
+                if let Some(unknown) = object
+                    .keys()
+                    .find(|key| !named_fields.iter().any(|f| f.name() == *key))
+                {
+                    anyhow::bail!(
+                        "Unknown field `{}` for `{}`, expected one of: {}",
+                        unknown,
+                        name,
+                        named_fields.iter().map(|f| f.name()).join(", ")
+                    )
+                }
+                let mut map = IndexMap::new();
+                for named_field in named_fields {
+                    let field_name = named_field.name();
+                    let field_json = object.get(field_name).ok_or_else(|| {
+                        anyhow::anyhow!("Missing field `{}` for `{}`", field_name, name)
+                    })?;
+                    let value = self
+                        .convert(named_field.field().ty.id, field_json)
+                        .map_err(|e| {
+                            FieldPathError::prepend(
+                                e,
+                                PathSegment::Field(field_name.to_string()),
+                            )
+                        })?;
+                    map.insert(Value::String(field_name.to_string()), value);
+                }
+                Ok(Value::Map(Map::new(ident, map)))
+            }
+            CompositeTypeFields::Unnamed(fields) => {
+                let values = match (json, &fields[..]) {
+                    // a single field wrapper type is given as its inner value
+                    (_, [field]) => vec![self.convert(field.ty.id, json)?],
+                    (Json::Array(elems), _) if elems.len() == fields.len() => {
+                        fields
+                            .iter()
+                            .zip(elems)
+                            .map(|(field, elem)| self.convert(field.ty.id, elem))
+                            .collect::<Result<_>>()?
+                    }
+                    _ => {
+                        anyhow::bail!(
+                            "Expected an array of {} elements for `{}`, found {}",
+                            fields.len(),
+                            name,
+                            json
+                        )
+                    }
+                };
+                Ok(Value::Tuple(Tuple::new(ident, values)))
+            }
+            CompositeTypeFields::NoFields => {
+                Ok(Value::Tuple(Tuple::new(ident, Vec::new())))
+            }
+        }
+    }
+
+    fn convert_variant(
+        &self,
+        ty: &Type<PortableForm>,
+        variant_def: &TypeDefVariant<PortableForm>,
+        json: &Json,
+    ) -> Result<Value> {
+        let variant_names = || variant_def.variants.iter().map(|v| &v.name).join(", ");
+        let find_variant = |name: &str| {
+            variant_def
+                .variants
+                .iter()
+                .find(|v| v.name == name)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No variant `{}` found, expected one of: {}",
+                        name,
+                        variant_names()
+                    )
+                })
+        };
+
+        // `Option` is given as `null` or the inner value.
+        if ty.path.segments == ["Option"] {
+            return match json {
+                Json::Null => Ok(Value::Tuple(Tuple::new(Some("None"), Vec::new()))),
+                json => {
+                    let some = find_variant("Some")?;
+                    let inner = some.fields.first().ok_or_else(|| {
+                        anyhow::anyhow!("Invalid metadata: `Some` without a field")
+                    })?;
+                    let value = self.convert(inner.ty.id, json)?;
+                    Ok(Value::Tuple(Tuple::new(Some("Some"), vec![value])))
+                }
+            }
+        }
+
+        let (variant, payload) = match json {
+            Json::String(name) => (find_variant(name)?, None),
+            Json::Object(object) if object.len() == 1 => {
+                let (name, payload) = object.iter().next().expect("len is 1; qed");
+                (find_variant(name)?, Some(payload))
+            }
+            _ => {
+                anyhow::bail!(
+                    "Expected a variant name or an object with a single variant key, \
+                     found {}",
+                    json
+                )
+            }
+        };
+        let name = variant.name.as_str();
+        match (payload, CompositeTypeFields::from_fields(&variant.fields)?) {
+            (None | Some(Json::Null), CompositeTypeFields::NoFields) => {
+                Ok(Value::Tuple(Tuple::new(Some(name), Vec::new())))
+            }
+            (None, _) => anyhow::bail!("Missing the fields of variant `{}`", name),
+            (Some(payload), _) => {
+                self.convert_composite(Some(name), &variant.fields, payload)
+                    .with_context(|| format!("Invalid variant `{name}`"))
+            }
+        }
+    }
+
+    fn convert_seq(&self, elem_type_id: u32, json: &Json) -> Result<Value> {
+        match json {
+            Json::Array(elems) => {
+                let values = elems
+                    .iter()
+                    .enumerate()
+                    .map(|(i, elem)| {
+                        self.convert(elem_type_id, elem).map_err(|e| {
+                            FieldPathError::prepend(e, PathSegment::Index(i))
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Value::Seq(Seq::new(values)))
+            }
+            Json::String(s) if s.starts_with("0x") => Ok(Value::Hex(Hex::from_str(s)?)),
+            _ => Err(anyhow::anyhow!("Expected a JSON array, found {}", json)),
+        }
+    }
+}
+
+/// Converts the JSON value for a type with a custom encoder, e.g. an `AccountId`.
+fn scalar_value(json: &Json) -> Result<Value> {
+    match json {
+        Json::String(s) if s.starts_with("0x") => Ok(Value::Hex(Hex::from_str(s)?)),
+        Json::String(s) => Ok(Value::String(s.clone())),
+        Json::Number(n) => {
+            n.as_u64().map(|n| Value::UInt(n.into())).ok_or_else(|| {
+                anyhow::anyhow!("Expected an unsigned integer, found {}", n)
+            })
+        }
+        json => Err(anyhow::anyhow!("Expected a string, found {}", json)),
+    }
+}
+
+/// Converts the JSON value for an integer wider than 128 bits, which exceeds the JSON
+/// number range unless given as a decimal or hex string.
+fn wide_int_value(json: &Json) -> Result<Value> {
+    match json {
+        Json::String(s) => Ok(Value::String(s.clone())),
+        Json::Number(n) if n.is_u64() => Ok(Value::UInt(n.as_u64().unwrap().into())),
+        Json::Number(n) if n.is_i64() => Ok(Value::Int(n.as_i64().unwrap().into())),
+        json => Err(anyhow::anyhow!("Expected an integer, found {}", json)),
+    }
+}
+
+fn primitive_value(primitive: &TypeDefPrimitive, json: &Json) -> Result<Value> {
+    match (primitive, json) {
+        (TypeDefPrimitive::Bool, Json::Bool(b)) => Ok(Value::Bool(*b)),
+        (TypeDefPrimitive::Str, Json::String(s)) => Ok(Value::String(s.clone())),
+        (
+            TypeDefPrimitive::U8
+            | TypeDefPrimitive::U16
+            | TypeDefPrimitive::U32
+            | TypeDefPrimitive::U64
+            | TypeDefPrimitive::U128,
+            json,
+        ) => {
+            let uint = match json {
+                Json::Number(n) => n.as_u64().map(Into::into),
+                // integers exceeding the JSON number range can be given as strings
+                Json::String(s) => s.parse::<u128>().ok(),
+                _ => None,
+            };
+            uint.map(Value::UInt).ok_or_else(|| {
+                anyhow::anyhow!("Expected an unsigned integer, found {}", json)
+            })
+        }
+        (
+            TypeDefPrimitive::I8
+            | TypeDefPrimitive::I16
+            | TypeDefPrimitive::I32
+            | TypeDefPrimitive::I64
+            | TypeDefPrimitive::I128,
+            json,
+        ) => {
+            let int = match json {
+                Json::Number(n) => n.as_i64().map(Into::into),
+                Json::String(s) => s.parse::<i128>().ok(),
+                _ => None,
+            };
+            int.map(Value::Int)
+                .ok_or_else(|| anyhow::anyhow!("Expected an integer, found {}", json))
+        }
+        (primitive, json) => {
+            Err(anyhow::anyhow!(
+                "Expected a {:?} value, found {}",
+                primitive,
+                json
+            ))
+        }
+    }
+}
@@ -83,6 +83,15 @@
 //! As with the example for the primitive `bool` above, this works in the other direction
 //! for decoding SCALE encoded bytes and converting them into a human readable string.
 //!
+//! # JSON arguments
+//!
+//! Arguments of composite types can alternatively be given as JSON objects, whose keys
+//! are matched with the field names of the expected type. e.g.
+//!
+//! `{"fee": 3, "admin": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"}`
+//!
+//! `Option` values are given as `null` or the inner value, and sequences as JSON arrays.
+//!
 //! # Example
 //! ```no_run
 //! # use contract_metadata::ContractMetadata;
@@ -99,15 +108,23 @@
 //! ```
 
 mod account_id;
+pub mod convert;
 mod decode;
 mod encode;
 pub mod env_types;
+mod field_path;
+mod file_arg;
+mod json;
 mod scon;
 mod transcoder;
 mod util;
+mod wide_int;
 
 pub use self::{
-    account_id::AccountId32,
+    account_id::{
+        AccountId32,
+        SUBSTRATE_SS58_PREFIX,
+    },
     scon::{
         Hex,
         Map,
@@ -120,14 +137,17 @@ pub use self::{
     },
 };
 
+use crate::field_path::FieldPathError;
 use anyhow::{
     Context,
     Result,
 };
 use ink_metadata::{
     ConstructorSpec,
+    EventSpec,
     InkProject,
     MessageSpec,
+    TypeSpec,
 };
 use itertools::Itertools;
 use scale::{
@@ -141,18 +161,76 @@ use scale_info::{
         PortableForm,
     },
     Field,
+    PortableRegistry,
+    TypeDef,
+    TypeDefPrimitive,
 };
 use std::{
     cmp::Ordering,
-    fmt::Debug,
+    fmt::{
+        self,
+        Debug,
+        Display,
+        Formatter,
+    },
     path::Path,
+    sync::Arc,
 };
 
+/// An entry point of a contract which is called by its label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPoint {
+    Constructor,
+    Message,
+}
+
+impl Display for EntryPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constructor => write!(f, "constructor"),
+            Self::Message => write!(f, "message"),
+        }
+    }
+}
+
 /// Encode strings to SCALE encoded smart contract calls.
 /// Decode SCALE encoded smart contract events and return values into `Value` objects.
 pub struct ContractMessageTranscoder {
     metadata: InkProject,
     transcoder: Transcoder,
+    ss58_prefix: u16,
+    denominate: Option<env_types::Denominate>,
+    raw_values: bool,
+    hex_file_args: bool,
+}
+
+/// A contract event decoded with the metadata of the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    /// The fields of the event.
+    pub value: Value,
+    /// The labels of the fields which are indexed in the topics of the event.
+    pub indexed: Vec<String>,
+}
+
+/// Returns the decoded `event` with the labels of its `indexed` fields marked as
+/// `(indexed)`, for display.
+pub fn mark_indexed_fields(event: &Value, indexed: &[String]) -> Value {
+    match event {
+        Value::Map(map) if !indexed.is_empty() => {
+            let fields = map.iter().map(|(label, value)| {
+                let label = match label {
+                    Value::String(label) if indexed.contains(label) => {
+                        Value::String(format!("{label} (indexed)"))
+                    }
+                    label => label.clone(),
+                };
+                (label, value.clone())
+            });
+            Value::Map(Map::new(map.ident().as_deref(), fields.collect()))
+        }
+        event => event.clone(),
+    }
 }
 
 /// Find strings from an iterable of `possible_values` similar to a given value `v`
@@ -173,16 +251,209 @@ where
     candidates.into_iter().map(|(_, pv)| pv).collect()
 }
 
+/// Returns up to three of the `possible_values` most similar to `v`, the most similar
+/// first.
+fn suggestions<T, I>(v: &str, possible_values: I) -> Vec<String>
+where
+    T: AsRef<str>,
+    I: IntoIterator<Item = T>,
+{
+    did_you_mean(v, possible_values)
+        .into_iter()
+        .rev()
+        .take(3)
+        .collect()
+}
+
+/// Returns the label matching `name` case-insensitively, or otherwise the label starting
+/// with `name`, if the match is unique.
+fn fuzzy_match<'a>(name: &str, labels: &[&'a str]) -> Option<&'a str> {
+    let unique = |matches: Vec<&'a str>| {
+        match matches.as_slice() {
+            [label] => Some(*label),
+            _ => None,
+        }
+    };
+    if name.is_empty() {
+        return None
+    }
+    let name = name.to_lowercase();
+    unique(
+        labels
+            .iter()
+            .copied()
+            .filter(|label| label.to_lowercase() == name)
+            .collect(),
+    )
+    .or_else(|| {
+        unique(
+            labels
+                .iter()
+                .copied()
+                .filter(|label| label.to_lowercase().starts_with(&name))
+                .collect(),
+        )
+    })
+}
+
+/// Returns `true` if the type with the given id is a 32 byte array, possibly wrapped in
+/// single field composites like the `AccountId` types of the `ink!` environments.
+fn is_32_byte_array(registry: &PortableRegistry, type_id: u32) -> bool {
+    match registry.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Composite(composite)) if composite.fields.len() == 1 => {
+            is_32_byte_array(registry, composite.fields[0].ty.id)
+        }
+        Some(TypeDef::Array(array)) => {
+            array.len == 32
+                && matches!(
+                    registry.resolve(array.type_param.id).map(|ty| &ty.type_def),
+                    Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Returns the name a type is declared with in the contract, e.g. `Balance`.
+fn display_name(spec: &TypeSpec<PortableForm>) -> String {
+    spec.display_name().segments.join("::")
+}
+
 impl ContractMessageTranscoder {
     pub fn new(metadata: InkProject) -> Self {
-        let transcoder = TranscoderBuilder::new(metadata.registry())
-            .register_custom_type_transcoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::AccountId, _>(env_types::AccountId)
-            .register_custom_type_decoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::Hash, _>(env_types::Hash)
-            .done();
+        let transcoder =
+            Self::build_transcoder(&metadata, SUBSTRATE_SS58_PREFIX, None, false);
         Self {
             metadata,
             transcoder,
+            ss58_prefix: SUBSTRATE_SS58_PREFIX,
+            denominate: None,
+            raw_values: false,
+            hex_file_args: false,
+        }
+    }
+
+    /// Display decoded accounts using the given SS58 address prefix instead of the
+    /// default Substrate prefix.
+    pub fn with_ss58_prefix(self, ss58_prefix: u16) -> Self {
+        let transcoder = Self::build_transcoder(
+            &self.metadata,
+            ss58_prefix,
+            self.denominate.as_ref(),
+            self.raw_values,
+        );
+        Self {
+            transcoder,
+            ss58_prefix,
+            ..self
+        }
+    }
+
+    /// Accept denominated literals such as `1.5UNIT` for arguments of the contract's
+    /// `Balance` type, converted into the smallest unit of the chain's token with
+    /// `denominate`. Plain integers remain the balance in the smallest unit.
+    ///
+    /// Only the arguments and fields declared with the name of the environment's
+    /// `Balance` type accept denominated literals; other integers of the same width,
+    /// e.g. a `u128` argument, reject them with an error.
+    pub fn with_balance_denomination<F>(self, denominate: F) -> Self
+    where
+        F: Fn(&str) -> Result<u128> + Send + Sync + 'static,
+    {
+        let denominate: env_types::Denominate = Arc::new(denominate);
+        let transcoder = Self::build_transcoder(
+            &self.metadata,
+            self.ss58_prefix,
+            Some(&denominate),
+            self.raw_values,
+        );
+        Self {
+            transcoder,
+            denominate: Some(denominate),
+            ..self
+        }
+    }
+
+    /// Decode account ids, hashes and weights into their structural representation, e.g.
+    /// a byte array, instead of displaying them as SS58 addresses, hex and
+    /// `ref_time/proof_size` respectively.
+    pub fn with_raw_values(self, raw_values: bool) -> Self {
+        let transcoder = Self::build_transcoder(
+            &self.metadata,
+            self.ss58_prefix,
+            self.denominate.as_ref(),
+            raw_values,
+        );
+        Self {
+            transcoder,
+            raw_values,
+            ..self
+        }
+    }
+
+    /// Read the contents of `@path` argument files for byte array arguments as hex if
+    /// they start with `0x`, instead of as raw bytes.
+    pub fn with_hex_file_args(self, hex_file_args: bool) -> Self {
+        Self {
+            hex_file_args,
+            ..self
+        }
+    }
+
+    fn build_transcoder(
+        metadata: &InkProject,
+        ss58_prefix: u16,
+        denominate: Option<&env_types::Denominate>,
+        raw_values: bool,
+    ) -> Transcoder {
+        type AccountId = <ink_env::DefaultEnvironment as ink_env::Environment>::AccountId;
+        type Hash = <ink_env::DefaultEnvironment as ink_env::Environment>::Hash;
+        let account_id = env_types::AccountId::with_ss58_prefix(ss58_prefix);
+        let mut builder = TranscoderBuilder::new(metadata.registry());
+        builder = if raw_values {
+            builder.register_custom_type_encoder::<AccountId, _>(account_id.clone())
+        } else {
+            builder
+                .register_custom_type_transcoder::<AccountId, _>(account_id.clone())
+                .register_custom_type_decoder::<Hash, _>(env_types::Hash)
+                .with_ss58_prefix_custom_type_transcoders(ss58_prefix)
+        };
+        // the environment of the contract may declare an `AccountId` type of its own
+        let account_id_type_id = metadata.spec().environment().account_id().ty().id;
+        if !builder.has_custom_type_encoder(account_id_type_id)
+            && is_32_byte_array(metadata.registry(), account_id_type_id)
+        {
+            builder = builder.register_custom_type_encoder_for_id(
+                account_id_type_id,
+                account_id.clone(),
+            );
+            if !raw_values {
+                builder = builder
+                    .register_custom_type_decoder_for_id(account_id_type_id, account_id);
+            }
+        }
+        if let Some(denominate) = denominate {
+            let balance_type_id = metadata.spec().environment().balance().ty().id;
+            if let Some(TypeDef::Primitive(primitive)) = metadata
+                .registry()
+                .resolve(balance_type_id)
+                .map(|ty| &ty.type_def)
+            {
+                let balance = metadata.spec().environment().balance();
+                let type_name = balance
+                    .display_name()
+                    .segments
+                    .last()
+                    .map_or("Balance", String::as_str);
+                builder = builder.register_balance_encoder(env_types::Balance::new(
+                    balance_type_id,
+                    type_name,
+                    primitive.clone(),
+                    denominate.clone(),
+                ));
+            }
         }
+        builder.done()
     }
 
     /// Attempt to create a [`ContractMessageTranscoder`] from the metadata file at the
@@ -226,12 +497,18 @@ impl ContractMessageTranscoder {
                 let constructors = self.constructors().map(|c| c.label());
                 let messages = self.messages().map(|c| c.label());
                 let possible_values: Vec<_> = constructors.chain(messages).collect();
-                let help_txt = did_you_mean(name, possible_values.clone())
-                    .first()
-                    .map(|suggestion| format!("Did you mean '{}'?", suggestion))
-                    .unwrap_or_else(|| {
-                        format!("Should be one of: {}", possible_values.iter().join(", "))
-                    });
+                let suggestions = suggestions(name, possible_values.clone());
+                let help_txt = if suggestions.is_empty() {
+                    format!("Should be one of: {}", possible_values.iter().join(", "))
+                } else {
+                    format!(
+                        "Did you mean {}?",
+                        suggestions
+                            .iter()
+                            .map(|label| format!("'{label}'"))
+                            .join(", ")
+                    )
+                };
 
                 return Err(anyhow::anyhow!(
                     "No constructor or message with the name '{name}' found.\n{help_txt}",
@@ -247,25 +524,142 @@ impl ContractMessageTranscoder {
                 args.len()
             )
         }
+        let stdin_args = args
+            .iter()
+            .filter(|arg| file_arg::Arg::parse(arg.as_ref()) == file_arg::Arg::File("-"))
+            .count();
+        if stdin_args > 1 {
+            anyhow::bail!("Only one argument can be read from stdin with `@-`")
+        }
 
         let mut encoded = selector.to_bytes().to_vec();
-        for (spec, arg) in spec_args.iter().zip(args) {
-            let value = scon::parse_value(arg.as_ref())?;
-            self.transcoder.encode(
-                self.metadata.registry(),
-                spec.ty().ty().id,
-                &value,
-                &mut encoded,
-            )?;
+        for (i, (spec, arg)) in spec_args.iter().zip(args).enumerate() {
+            let type_id = spec.ty().ty().id;
+            let type_name = display_name(spec.ty());
+            self.encode_arg(type_id, Some(&type_name), arg.as_ref(), &mut encoded)
+                .map_err(|err| {
+                    let context = format!(
+                        "argument {} (\"{}\", expected {})",
+                        i + 1,
+                        spec.label(),
+                        util::type_name(self.metadata.registry(), type_id)
+                    );
+                    match err.downcast::<FieldPathError>() {
+                        Ok(err) => {
+                            anyhow::anyhow!(
+                                "{}: {}: {:#}",
+                                context,
+                                err.path_from(spec.label()),
+                                err.source_error()
+                            )
+                        }
+                        Err(err) => anyhow::anyhow!("{}: {:#}", context, err),
+                    }
+                })?;
         }
         Ok(encoded)
     }
 
+    /// Encodes an argument given on the command line, which is read from a file with
+    /// `@path` or from stdin with `@-`. A value starting with `@` is escaped as `@@`.
+    ///
+    /// Files are read as raw bytes for byte array arguments (or as hex with
+    /// [`Self::with_hex_file_args`]), as UTF-8 for `String` arguments, and in the syntax
+    /// of the arguments for any other type.
+    ///
+    /// The `type_name` is the name the argument is declared with, which decides whether
+    /// it accepts denominated balances.
+    fn encode_arg(
+        &self,
+        type_id: u32,
+        type_name: Option<&str>,
+        arg: &str,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        let path = match file_arg::Arg::parse(arg) {
+            file_arg::Arg::Inline(arg) => {
+                return self.encode_inline_arg(type_id, type_name, arg, output)
+            }
+            file_arg::Arg::File(path) => path,
+        };
+        let contents = file_arg::read(path)?;
+        let value = match file_arg::ArgKind::of(self.metadata.registry(), type_id) {
+            file_arg::ArgKind::Bytes => {
+                match std::str::from_utf8(&contents).map(str::trim) {
+                    Ok(hex) if self.hex_file_args && hex.starts_with("0x") => {
+                        Value::Hex(hex.parse().with_context(|| {
+                            format!("Invalid hex in the argument file `{path}`")
+                        })?)
+                    }
+                    _ => Value::Hex(contents.into()),
+                }
+            }
+            file_arg::ArgKind::String => Value::String(file_arg::utf8(path, contents)?),
+            file_arg::ArgKind::Value => {
+                let arg = file_arg::utf8(path, contents)?;
+                return self
+                    .encode_inline_arg(type_id, type_name, arg.trim(), output)
+                    .map_err(|err| {
+                        anyhow::anyhow!("in the argument file `{path}`: {err:#}")
+                    })
+            }
+        };
+        self.transcoder.encode_with_type_name(
+            self.metadata.registry(),
+            type_id,
+            type_name,
+            &value,
+            output,
+        )
+    }
+
+    fn encode_inline_arg(
+        &self,
+        type_id: u32,
+        type_name: Option<&str>,
+        arg: &str,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        let value = match json::parse_json_arg(arg) {
+            Some(json) => {
+                self.transcoder.value_from_json(
+                    self.metadata.registry(),
+                    type_id,
+                    &json,
+                )?
+            }
+            None => scon::parse_value(arg)?,
+        };
+        self.transcoder.encode_with_type_name(
+            self.metadata.registry(),
+            type_id,
+            type_name,
+            &value,
+            output,
+        )
+    }
+
     pub fn decode(&self, type_id: u32, input: &mut &[u8]) -> Result<Value> {
         self.transcoder
             .decode(self.metadata.registry(), type_id, input)
     }
 
+    /// Encodes a `value` of the type with the `type_id` in the contract metadata, the
+    /// inverse of [`Self::decode`], so that decoded values can be modified and encoded
+    /// again.
+    ///
+    /// Errors in nested values name the path of the field which failed to encode, e.g.
+    /// `tiers[2].bonus: Expected a u8 or a String value, got -1`.
+    pub fn encode_value(
+        &self,
+        type_id: u32,
+        value: &Value,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        self.transcoder
+            .encode(self.metadata.registry(), type_id, value, output)
+    }
+
     pub fn metadata(&self) -> &InkProject {
         &self.metadata
     }
@@ -278,6 +672,80 @@ impl ContractMessageTranscoder {
         self.metadata.spec().messages().iter()
     }
 
+    /// Returns whether the constructor or message with the given name accepts a
+    /// transferred value, or `None` if neither is found.
+    pub fn is_payable(&self, name: &str) -> Option<bool> {
+        match (
+            self.find_constructor_spec(name),
+            self.find_message_spec(name),
+        ) {
+            (Some(constructor), _) => Some(*constructor.payable()),
+            (None, Some(message)) => Some(message.payable()),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the label of the message with the given `selector`.
+    pub fn message_label(&self, selector: &[u8]) -> Result<&str> {
+        self.messages()
+            .find(|msg| msg.selector().to_bytes() == selector)
+            .map(|msg| msg.label().as_str())
+            .ok_or_else(|| {
+                let known_selectors = self
+                    .messages()
+                    .map(|msg| {
+                        format!("0x{} ({})", hex::encode(msg.selector().to_bytes()), msg.label())
+                    })
+                    .join(", ");
+                anyhow::anyhow!(
+                    "No message with the selector 0x{} found.\nKnown selectors: {known_selectors}",
+                    hex::encode(selector)
+                )
+            })
+    }
+
+    /// Returns the label of the constructor or message `name`.
+    ///
+    /// Without an exact match, a unique case-insensitive match or otherwise the only
+    /// label starting with `name` is returned if `fuzzy` is set. Otherwise the error
+    /// suggests up to three similar labels, and lists all labels of the `entry_point`
+    /// if there are none or `list_all` is set.
+    pub fn resolve_label(
+        &self,
+        entry_point: EntryPoint,
+        name: &str,
+        fuzzy: bool,
+        list_all: bool,
+    ) -> Result<&str> {
+        let labels: Vec<&str> = match entry_point {
+            EntryPoint::Constructor => {
+                self.constructors().map(|c| c.label().as_str()).collect()
+            }
+            EntryPoint::Message => self.messages().map(|m| m.label().as_str()).collect(),
+        };
+        if let Some(label) = labels.iter().find(|label| **label == name) {
+            return Ok(label)
+        }
+        if fuzzy {
+            if let Some(label) = fuzzy_match(name, &labels) {
+                return Ok(label)
+            }
+        }
+        let suggestions = suggestions(name, &labels);
+        let mut err = format!("Unknown {entry_point} `{name}`");
+        if !suggestions.is_empty() {
+            let suggestions = suggestions.iter().map(|label| format!("`{label}`"));
+            err.push_str(&format!("; did you mean {}?", suggestions.format(", ")));
+        }
+        if suggestions.is_empty() || list_all {
+            err.push_str(&format!(
+                "\nAvailable {entry_point}s: {}",
+                labels.iter().join(", ")
+            ));
+        }
+        Err(anyhow::anyhow!(err))
+    }
+
     fn find_message_spec(&self, name: &str) -> Option<&MessageSpec<PortableForm>> {
         self.messages().find(|msg| msg.label() == &name.to_string())
     }
@@ -303,23 +771,90 @@ impl ContractMessageTranscoder {
         // decoding.
         let _len = <Compact<u32>>::decode(data)?;
         let event_spec = self
-            .metadata
-            .spec()
-            .events()
-            .iter()
-            .find(|event| {
-                if let Some(sig_topic) = event.signature_topic() {
-                    sig_topic.as_bytes() == event_sig_topic.as_ref()
-                } else {
-                    false
-                }
-            })
+            .find_event_spec_by_signature(event_sig_topic)
             .ok_or_else(|| {
                 anyhow::anyhow!(
                     "Event with signature topic {} not found in contract metadata",
                     hex::encode(event_sig_topic)
                 )
             })?;
+        Ok(self.decode_event_data(event_spec, data)?.value)
+    }
+
+    /// Decodes the data of a contract event, matching the event by its `topics`.
+    ///
+    /// The first topic is the signature topic of the event, unless the event is
+    /// anonymous. Anonymous events are matched by the number of their indexed fields,
+    /// each of which is a topic, and the first one whose fields decode from the data
+    /// is returned.
+    pub fn decode_contract_event_with_topics<Hash>(
+        &self,
+        topics: &[Hash],
+        data: &mut &[u8],
+    ) -> Result<DecodedEvent>
+    where
+        Hash: AsRef<[u8]>,
+    {
+        // data is an encoded `Vec<u8>` so is prepended with its length `Compact<u32>`,
+        // which we ignore because the structure of the event data is known for
+        // decoding.
+        let _len = <Compact<u32>>::decode(data)?;
+        if let Some(event_spec) = topics
+            .first()
+            .and_then(|topic| self.find_event_spec_by_signature(topic))
+        {
+            return self.decode_event_data(event_spec, data)
+        }
+
+        let anonymous_events = self.metadata.spec().events().iter().filter(|event| {
+            event.signature_topic().is_none()
+                && event.args().iter().filter(|arg| arg.indexed()).count() == topics.len()
+        });
+        for event_spec in anonymous_events {
+            let mut input = *data;
+            match self.decode_event_data(event_spec, &mut input) {
+                Ok(event) => {
+                    *data = input;
+                    return Ok(event)
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        "Anonymous event '{}' does not match: {err}",
+                        event_spec.label()
+                    )
+                }
+            }
+        }
+        Err(anyhow::anyhow!(
+            "No event with the topics [{}] found in contract metadata",
+            topics
+                .iter()
+                .map(|topic| format!("0x{}", hex::encode(topic)))
+                .join(", ")
+        ))
+    }
+
+    fn find_event_spec_by_signature<Hash>(
+        &self,
+        event_sig_topic: &Hash,
+    ) -> Option<&EventSpec<PortableForm>>
+    where
+        Hash: AsRef<[u8]>,
+    {
+        self.metadata.spec().events().iter().find(|event| {
+            if let Some(sig_topic) = event.signature_topic() {
+                sig_topic.as_bytes() == event_sig_topic.as_ref()
+            } else {
+                false
+            }
+        })
+    }
+
+    fn decode_event_data(
+        &self,
+        event_spec: &EventSpec<PortableForm>,
+        data: &mut &[u8],
+    ) -> Result<DecodedEvent> {
         tracing::debug!("Decoding contract event '{}'", event_spec.label());
 
         let mut args = Vec::new();
@@ -333,8 +868,17 @@ impl ContractMessageTranscoder {
 
         let name = event_spec.label().to_string();
         let map = Map::new(Some(&name), args.into_iter().collect());
-
-        Ok(Value::Map(map))
+        let indexed = event_spec
+            .args()
+            .iter()
+            .filter(|arg| arg.indexed())
+            .map(|arg| arg.label().to_string())
+            .collect();
+
+        Ok(DecodedEvent {
+            value: Value::Map(map),
+            indexed,
+        })
     }
 
     pub fn decode_contract_message(&self, data: &mut &[u8]) -> Result<Value> {
@@ -415,6 +959,32 @@ impl ContractMessageTranscoder {
         self.decode(return_ty.ty().id, data)
     }
 
+    /// Parses a return value of the message with the given name, in the syntax of the
+    /// message arguments, into the [`Value`] it is decoded as, e.g. to compare it with
+    /// the value returned by a dry-run. The `Ok` wrapping the return value of ink!
+    /// messages may be omitted.
+    pub fn parse_message_return(&self, name: &str, input: &str) -> Result<Value> {
+        let msg_spec = self.find_message_spec(name).ok_or_else(|| {
+            anyhow::anyhow!("Failed to find message spec with name '{}'", name)
+        })?;
+        let return_type = msg_spec.return_type().ret_type();
+        let type_id = return_type.ty().id;
+        let type_name = display_name(return_type);
+        let mut encoded = Vec::new();
+        if let Err(err) = self.encode_arg(type_id, Some(&type_name), input, &mut encoded)
+        {
+            encoded.clear();
+            self.encode_arg(type_id, None, &format!("Ok({input})"), &mut encoded)
+                .map_err(|_| {
+                    err.context(format!(
+                        "Invalid return value of `{name}`, expected {}",
+                        util::type_name(self.metadata.registry(), type_id)
+                    ))
+                })?;
+        }
+        self.decode(type_id, &mut &encoded[..])
+    }
+
     /// Checks if buffer empty, otherwise returns am error
     fn validate_length(data: &[u8], label: &str, args: &[(Value, Value)]) -> Result<()> {
         if !data.is_empty() {
@@ -528,6 +1098,51 @@ mod tests {
             from: AccountId,
         }
 
+        #[ink(event, anonymous)]
+        pub struct Deposited {
+            #[ink(topic)]
+            amount: Balance,
+            memo: u8,
+        }
+
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub struct Config {
+            fee: u32,
+            admin: AccountId,
+            limit: Option<u128>,
+            rate: Rate,
+            tiers: Vec<Tier>,
+        }
+
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub struct Rate {
+            numerator: u32,
+            denominator: u32,
+        }
+
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub struct Tier {
+            threshold: u128,
+            bonus: u8,
+        }
+
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub struct Fee {
+            amount: Balance,
+            memo: u32,
+        }
+
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub enum Instruction {
+            Pause,
+            Limit {
+                max: u32,
+                strict: bool,
+            },
+            #[codec(index = 5)]
+            Transfer(u128, AccountId),
+        }
+
         impl Transcode {
             #[ink(constructor)]
             pub fn new(init_value: bool) -> Self {
@@ -549,6 +1164,9 @@ mod tests {
                 self.value
             }
 
+            #[ink(message, payable)]
+            pub fn deposit(&mut self) {}
+
             #[ink(message)]
             pub fn set_account_id(&self, account_id: AccountId) {
                 let _ = account_id;
@@ -579,10 +1197,25 @@ mod tests {
             pub fn uint_array_args(&self, arr: [u8; 4]) {
                 let _ = arr;
             }
+
+            #[ink(message)]
+            pub fn set_config(&self, config: Config) {
+                let _ = config;
+            }
+
+            #[ink(message)]
+            pub fn execute(&self, instruction: Instruction) {
+                let _ = instruction;
+            }
+
+            #[ink(message)]
+            pub fn set_fee(&self, fee: Fee) {
+                let _ = fee;
+            }
         }
     }
 
-    fn generate_metadata() -> InkProject {
+    pub(crate) fn generate_metadata() -> InkProject {
         extern "Rust" {
             fn __ink_generate_metadata() -> InkProject;
         }
@@ -590,6 +1223,59 @@ mod tests {
         unsafe { __ink_generate_metadata() }
     }
 
+    #[test]
+    fn message_label_by_selector() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let flip = transcoder.encode("flip", Vec::<&str>::new()).unwrap();
+
+        assert_eq!(transcoder.message_label(&flip[..4]).unwrap(), "flip");
+        let err = transcoder
+            .message_label(&[0, 0, 0, 0])
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("No message with the selector 0x00000000 found."));
+        assert!(
+            err.contains(&format!("0x{} (flip)", hex::encode(&flip[..4]))),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn is_payable() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        assert_eq!(transcoder.is_payable("deposit"), Some(true));
+        assert_eq!(transcoder.is_payable("flip"), Some(false));
+        assert_eq!(transcoder.is_payable("new"), Some(false));
+        assert_eq!(transcoder.is_payable("unknown"), None);
+    }
+
+    #[test]
+    fn parse_message_return_value() -> Result<()> {
+        let transcoder = ContractMessageTranscoder::new(generate_metadata());
+
+        let expected = transcoder.parse_message_return("get", "true")?;
+        assert_eq!(expected.to_string(), "Ok(true)");
+        let returned = transcoder.decode_message_return(
+            "get",
+            &mut &Result::<bool, ink::primitives::LangError>::Ok(true).encode()[..],
+        )?;
+        assert_eq!(expected, returned);
+        assert_eq!(
+            transcoder.parse_message_return("get", "Ok(true)")?,
+            returned
+        );
+
+        let err = transcoder.parse_message_return("get", "42").unwrap_err();
+        assert!(
+            format!("{err:#}").starts_with("Invalid return value of `get`"),
+            "{err:#}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn encode_single_primitive_arg() -> Result<()> {
         let metadata = generate_metadata();
@@ -613,6 +1299,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_label_suggests_similar_labels() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        assert_eq!(
+            transcoder
+                .resolve_label(EntryPoint::Message, "flip", false, false)
+                .unwrap(),
+            "flip"
+        );
+        assert_eq!(
+            transcoder
+                .resolve_label(EntryPoint::Message, "set_acount_id", false, false)
+                .unwrap_err()
+                .to_string(),
+            "Unknown message `set_acount_id`; did you mean `set_account_id`, \
+             `set_account_ids_vec`, `set_config`?"
+        );
+        let err = transcoder
+            .resolve_label(EntryPoint::Constructor, "flip", false, true)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.ends_with("\nAvailable constructors: new, default"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn resolve_label_fuzzy_matches() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let resolve =
+            |name| transcoder.resolve_label(EntryPoint::Message, name, true, false);
+        assert_eq!(resolve("FLIP").unwrap(), "flip");
+        assert_eq!(resolve("set_c").unwrap(), "set_config");
+        // ambiguous prefix
+        assert!(resolve("set_").is_err());
+        assert!(transcoder
+            .resolve_label(EntryPoint::Message, "FLIP", false, false)
+            .is_err());
+    }
+
     #[test]
     fn encode_mismatching_args_length() {
         let metadata = generate_metadata();
@@ -737,6 +1466,293 @@ mod tests {
         Ok(())
     }
 
+    fn arg_file(name: &str, contents: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("transcode-arg-file-{name}"));
+        std::fs::write(&path, contents).unwrap();
+        format!("@{}", path.display())
+    }
+
+    #[test]
+    fn encode_byte_array_arg_from_file() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let expected: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+
+        let raw = arg_file("raw.bin", &expected);
+        let encoded = transcoder.encode("uint_array_args", [raw])?;
+        assert_eq!(expected.encode(), &encoded[4..]);
+
+        // `0xdeadbeef` is read as raw bytes, unless hex files are enabled
+        let hex = arg_file("hex.txt", b"0xdeadbeef\n");
+        assert!(transcoder.encode("uint_array_args", [&hex]).is_err());
+        let transcoder = transcoder.with_hex_file_args(true);
+        let encoded = transcoder.encode("uint_array_args", [&hex])?;
+        assert_eq!(expected.encode(), &encoded[4..]);
+        Ok(())
+    }
+
+    #[test]
+    fn encode_value_arg_from_file() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let file = arg_file("vec.txt", b"[1, 2]\n");
+        let encoded = transcoder.encode("primitive_vec_args", [file])?;
+        assert_eq!(vec![1u32, 2].encode(), &encoded[4..]);
+        Ok(())
+    }
+
+    #[test]
+    fn arg_file_errors_name_the_argument_and_the_file() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let err = transcoder
+            .encode("primitive_vec_args", ["@does-not-exist.txt"])
+            .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "argument 1 (\"args\", expected Vec<u32>): Failed to read the argument \
+             file `does-not-exist.txt`"
+        ));
+
+        let file = arg_file("invalid.txt", b"[1, x]");
+        let err = transcoder
+            .encode("primitive_vec_args", [&file])
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with(
+            "argument 1 (\"args\", expected Vec<u32>): in the argument file"
+        ));
+        assert!(err.contains(&file[1..]));
+    }
+
+    #[test]
+    fn escaped_at_is_not_a_file() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        // the escaped value is parsed as is, and `@1` is no valid `Vec<u32>`
+        let err = transcoder
+            .encode("primitive_vec_args", ["@@1"])
+            .unwrap_err()
+            .to_string();
+        assert!(!err.contains("argument file"), "{err}");
+    }
+
+    #[test]
+    fn encode_json_struct_args() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let json = r#"{
+            "fee": 3,
+            "admin": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            "limit": null,
+            "rate": { "numerator": 1, "denominator": 100 },
+            "tiers": [{ "threshold": "340282366920938463463374607431768211455", "bonus": 5 }]
+        }"#;
+        let scon = r#"Config {
+            fee: 3,
+            admin: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY,
+            limit: None,
+            rate: Rate { numerator: 1, denominator: 100 },
+            tiers: [Tier { threshold: 340282366920938463463374607431768211455, bonus: 5 }]
+        }"#;
+        assert_eq!(
+            transcoder.encode("set_config", [json])?,
+            transcoder.encode("set_config", [scon])?
+        );
+
+        let json = json.replace("null", "1000");
+        let scon = scon.replace("None", "Some(1000)");
+        assert_eq!(
+            transcoder.encode("set_config", [json])?,
+            transcoder.encode("set_config", [scon])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn encode_json_struct_args_missing_or_unknown_field() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let err = transcoder
+            .encode(
+                "set_config",
+                [r#"{"fee": 3, "admin": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"}"#],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 1 (\"config\", expected contract_transcode::tests::transcode::Config): \
+             Missing field `limit` for `Config`"
+        );
+
+        let err = transcoder
+            .encode(
+                "set_config",
+                [r#"{
+                    "fee": 3,
+                    "admin": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                    "limit": null,
+                    "rate": { "numerator": 1, "denominator": 100, "scale": 2 },
+                    "tiers": []
+                }"#],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 1 (\"config\", expected contract_transcode::tests::transcode::Config): \
+             config.rate: Unknown field `scale` for `Rate`, expected one of: numerator, denominator"
+        );
+    }
+
+    #[test]
+    fn encode_enum_variant_args() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let encoded = transcoder.encode("execute", ["MyEnum::Pause"])?;
+        assert_eq!(&encoded[4..], [0]);
+
+        let encoded = transcoder
+            .encode("execute", ["Instruction::Limit { max: 10, strict: true }"])?;
+        assert_eq!(&encoded[4..], (1u8, 10u32, true).encode());
+
+        let account_id =
+            AccountId32::from_str("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap();
+        let expected = (5u8, 7u128, account_id).encode();
+        let encoded = transcoder.encode(
+            "execute",
+            ["Transfer(7, 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)"],
+        )?;
+        assert_eq!(&encoded[4..], expected);
+        let encoded = transcoder.encode(
+            "execute",
+            [r#"{"Transfer": [7, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]}"#],
+        )?;
+        assert_eq!(&encoded[4..], expected);
+        let encoded = transcoder
+            .encode("execute", [r#"{"Limit": {"max": 10, "strict": true}}"#])?;
+        assert_eq!(&encoded[4..], (1u8, 10u32, true).encode());
+        Ok(())
+    }
+
+    #[test]
+    fn encode_unknown_enum_variant() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let err = transcoder.encode("execute", ["Stop"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 1 (\"instruction\", expected contract_transcode::tests::transcode::Instruction): \
+             No variant 'Stop' found, expected one of: Pause, Limit, Transfer"
+        );
+    }
+
+    #[test]
+    fn encode_invalid_arg_error_names_the_argument() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let err = transcoder
+            .encode("uint_args", ["1", "2", "3", "4", "10_00x"])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 5 (\"_u128\", expected u128): Unexpected `x` at position 5 in \"10_00x\""
+        );
+    }
+
+    #[test]
+    fn encode_invalid_nested_field_error_has_field_path() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let err = transcoder
+            .encode(
+                "set_config",
+                [r#"Config {
+                    fee: 3,
+                    admin: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY,
+                    limit: None,
+                    rate: Rate { numerator: 1, denominator: 100 },
+                    tiers: [Tier { threshold: 1, bonus: 2 }, Tier { threshold: 2, bonus: 300 }]
+                }"#],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument 1 (\"config\", expected contract_transcode::tests::transcode::Config): \
+             config.tiers[1].bonus: out of range integral type conversion attempted"
+        );
+    }
+
+    #[test]
+    fn encode_denominated_balance_args() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata)
+            .with_balance_denomination(|input| {
+                match input {
+                    "1.5UNIT" => Ok(1_500),
+                    _ => Err(anyhow::anyhow!("Unknown token symbol")),
+                }
+            });
+
+        let expected = transcoder.encode("set_fee", ["Fee { amount: 1500, memo: 1 }"])?;
+        assert_eq!(
+            transcoder.encode("set_fee", ["Fee { amount: 1.5UNIT, memo: 1 }"])?,
+            expected
+        );
+        assert_eq!(
+            transcoder.encode("set_fee", [r#"{"amount": "1.5UNIT", "memo": 1}"#])?,
+            expected
+        );
+        assert_eq!(&expected[4..], (1_500u128, 1u32).encode());
+
+        let err = transcoder
+            .encode("set_fee", ["Fee { amount: 3DOT, memo: 1 }"])
+            .unwrap_err();
+        assert!(
+            err.to_string().ends_with(
+                "fee.amount: Error encoding custom type: \
+                 Error parsing denominated balance `3DOT`: Unknown token symbol"
+            ),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_denominated_literal_for_integers_not_declared_as_balance() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata)
+            .with_balance_denomination(|input| {
+                match input {
+                    "1.5UNIT" => Ok(1_500),
+                    _ => Err(anyhow::anyhow!("Unknown token symbol")),
+                }
+            });
+
+        let err = transcoder
+            .encode("uint_args", ["1", "2", "3", "4", "1.5UNIT"])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Denominated balance `1.5UNIT` is only accepted"),
+            "{err}"
+        );
+        assert_eq!(
+            transcoder.encode("uint_args", ["1", "2", "3", "4", "1500"])?,
+            ContractMessageTranscoder::new(generate_metadata())
+                .encode("uint_args", ["1", "2", "3", "4", "1500"])?
+        );
+        Ok(())
+    }
+
     #[test]
     fn decode_primitive_return() {
         let metadata = generate_metadata();
@@ -797,6 +1813,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn decode_contract_event_with_topics() -> Result<()> {
+        let transcoder = ContractMessageTranscoder::new(generate_metadata());
+
+        let signature_topic: H256 =
+            <transcode::Event1 as ink::env::Event>::SIGNATURE_TOPIC
+                .unwrap()
+                .into();
+        let topics = [signature_topic, H256::zero(), H256::repeat_byte(1)];
+        let encoded_bytes = ([0u32; 8], [1u32; 8]).encode().encode();
+        let decoded = transcoder
+            .decode_contract_event_with_topics(&topics, &mut &encoded_bytes[..])?;
+
+        assert_eq!(
+            decoded.value,
+            transcoder
+                .decode_contract_event(&signature_topic, &mut &encoded_bytes[..])?
+        );
+        assert_eq!(
+            decoded.indexed,
+            vec!["name".to_string(), "from".to_string()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decode_anonymous_contract_event() -> Result<()> {
+        let transcoder = ContractMessageTranscoder::new(generate_metadata());
+
+        // the only topic is the one of the indexed `amount`
+        let topics = [H256::repeat_byte(7)];
+        let encoded_bytes = (1_000u128, 3u8).encode().encode();
+        let decoded = transcoder
+            .decode_contract_event_with_topics(&topics, &mut &encoded_bytes[..])?;
+
+        assert_eq!(
+            decoded.value.to_string(),
+            "Deposited { amount: 1000, memo: 3 }"
+        );
+        assert_eq!(decoded.indexed, vec!["amount".to_string()]);
+        assert_eq!(
+            mark_indexed_fields(&decoded.value, &decoded.indexed).to_string(),
+            "Deposited { amount (indexed): 1000, memo: 3 }"
+        );
+
+        // no anonymous event has two indexed fields
+        let topics = [H256::repeat_byte(7), H256::repeat_byte(8)];
+        let err = transcoder
+            .decode_contract_event_with_topics(&topics, &mut &encoded_bytes[..])
+            .unwrap_err();
+        assert!(err.to_string().starts_with("No event with the topics"));
+        Ok(())
+    }
+
     #[test]
     fn decode_hash_as_hex_encoded_string() -> Result<()> {
         let metadata = generate_metadata();
@@ -835,6 +1905,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_account_id_with_ss58_prefix() -> Result<()> {
+        let signature_topic: H256 =
+            <transcode::Event1 as ink::env::Event>::SIGNATURE_TOPIC
+                .unwrap()
+                .into();
+        let account = AccountId32([7u8; 32]);
+        // raw encoded event, encoded again as a Vec<u8> which has a len prefix.
+        let encoded_bytes = ([0u32; 8], account.clone()).encode().encode();
+
+        for prefix in [0u16, 5] {
+            let transcoder = ContractMessageTranscoder::new(generate_metadata())
+                .with_ss58_prefix(prefix);
+            let decoded = transcoder
+                .decode_contract_event(&signature_topic, &mut &encoded_bytes[..])?;
+            let Value::Map(ref map) = decoded else {
+                anyhow::bail!("Expected a Value::Map for the decoded event")
+            };
+            assert_eq!(
+                map[&Value::String("from".into())],
+                Value::Literal(account.to_ss58check_with_prefix(prefix))
+            );
+            assert_ne!(
+                map[&Value::String("from".into())],
+                Value::Literal(account.to_ss58check())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn transcode_account_id_type_of_the_contract_environment() -> Result<()> {
+        let mut metadata: serde_json::Value = serde_json::from_slice(&std::fs::read(
+            "../cargo-contract/tests/fixtures/flipper.json",
+        )?)?;
+        // an `AccountId` type declared by a custom environment, taken by the constructor
+        metadata["types"][6]["type"]["path"] = serde_json::json!(["my_env", "AccountId"]);
+        metadata["spec"]["constructors"][0]["args"][0]["type"]["type"] =
+            serde_json::json!(6);
+        let transcoder = ContractMessageTranscoder::try_from(serde_json::from_value::<
+            contract_metadata::ContractMetadata,
+        >(metadata)?)?;
+
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let encoded = transcoder.encode("new", [alice])?;
+        assert_eq!(encoded[4..], AccountId32::from_str(alice).unwrap().0);
+
+        let decoded = transcoder.decode_contract_constructor(&mut &encoded[..])?;
+        assert!(decoded.to_string().contains(alice), "{decoded}");
+        Ok(())
+    }
+
+    #[test]
+    fn decode_account_id_raw_values() -> Result<()> {
+        let signature_topic: H256 =
+            <transcode::Event1 as ink::env::Event>::SIGNATURE_TOPIC
+                .unwrap()
+                .into();
+        let account = AccountId32([7u8; 32]);
+        let encoded_bytes = ([0u32; 8], account).encode().encode();
+
+        let transcoder =
+            ContractMessageTranscoder::new(generate_metadata()).with_raw_values(true);
+        let decoded = transcoder
+            .decode_contract_event(&signature_topic, &mut &encoded_bytes[..])?;
+        let Value::Map(ref map) = decoded else {
+            anyhow::bail!("Expected a Value::Map for the decoded event")
+        };
+        assert_eq!(
+            map[&Value::String("from".into())],
+            Value::Tuple(Tuple::new(
+                Some("AccountId"),
+                vec![Value::Hex(Hex::from_str(&hex::encode([7u8; 32]))?)]
+            ))
+        );
+        Ok(())
+    }
+
     #[test]
     fn decode_contract_message() -> Result<()> {
         let metadata = generate_metadata();
@@ -846,6 +1994,172 @@ mod tests {
         Ok(())
     }
 
+    /// A xorshift generator, to derive arbitrary but reproducible encodings.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn bytes(&mut self, len: usize) -> Vec<u8> {
+            (0..len).map(|_| self.next() as u8).collect()
+        }
+    }
+
+    /// Appends an arbitrary valid SCALE encoding of the type with the `type_id` to
+    /// `output`, or returns `None` if the type can't be transcoded.
+    fn arbitrary_encoding(
+        registry: &scale_info::PortableRegistry,
+        type_id: u32,
+        rng: &mut Rng,
+        output: &mut Vec<u8>,
+    ) -> Option<()> {
+        use scale_info::TypeDefPrimitive as P;
+
+        let ty = registry.resolve(type_id)?;
+        match &ty.type_def {
+            TypeDef::Composite(composite) => {
+                for field in &composite.fields {
+                    arbitrary_encoding(registry, field.ty.id, rng, output)?;
+                }
+            }
+            TypeDef::Variant(variant) => {
+                if variant.variants.is_empty() {
+                    return None
+                }
+                let variant = &variant.variants[rng.below(variant.variants.len())];
+                output.push(variant.index);
+                for field in &variant.fields {
+                    arbitrary_encoding(registry, field.ty.id, rng, output)?;
+                }
+            }
+            TypeDef::Sequence(sequence) => {
+                let len = rng.below(4);
+                Compact(len as u32).encode_to(output);
+                for _ in 0..len {
+                    arbitrary_encoding(registry, sequence.type_param.id, rng, output)?;
+                }
+            }
+            TypeDef::Array(array) => {
+                for _ in 0..array.len {
+                    arbitrary_encoding(registry, array.type_param.id, rng, output)?;
+                }
+            }
+            TypeDef::Tuple(tuple) => {
+                for field in &tuple.fields {
+                    arbitrary_encoding(registry, field.id, rng, output)?;
+                }
+            }
+            TypeDef::Primitive(primitive) => {
+                match primitive {
+                    P::Bool => (rng.below(2) == 1).encode_to(output),
+                    P::Char => return None,
+                    P::Str => {
+                        let len = rng.below(8);
+                        let s: String = (0..len)
+                            .map(|_| (b'a' + rng.below(26) as u8) as char)
+                            .collect();
+                        s.encode_to(output)
+                    }
+                    P::U8 | P::I8 => output.extend(rng.bytes(1)),
+                    P::U16 | P::I16 => output.extend(rng.bytes(2)),
+                    P::U32 | P::I32 => output.extend(rng.bytes(4)),
+                    P::U64 | P::I64 => output.extend(rng.bytes(8)),
+                    P::U128 | P::I128 => output.extend(rng.bytes(16)),
+                    P::U256 | P::I256 => output.extend(rng.bytes(32)),
+                }
+            }
+            TypeDef::Compact(compact) => {
+                let mut inner = registry.resolve(compact.type_param.id)?;
+                if let TypeDef::Composite(composite) = &inner.type_def {
+                    inner = registry.resolve(composite.fields.first()?.ty.id)?;
+                }
+                let value = rng.next() as u128 * rng.next() as u128;
+                match &inner.type_def {
+                    TypeDef::Primitive(P::U8) => Compact(value as u8).encode_to(output),
+                    TypeDef::Primitive(P::U16) => Compact(value as u16).encode_to(output),
+                    TypeDef::Primitive(P::U32) => Compact(value as u32).encode_to(output),
+                    TypeDef::Primitive(P::U64) => Compact(value as u64).encode_to(output),
+                    TypeDef::Primitive(P::U128) => Compact(value).encode_to(output),
+                    _ => return None,
+                }
+            }
+            TypeDef::BitSequence(_) => return None,
+        }
+        Some(())
+    }
+
+    #[test]
+    fn encode_value_round_trips_decoded_values_of_all_types() {
+        for raw_values in [false, true] {
+            let transcoder = ContractMessageTranscoder::new(generate_metadata())
+                .with_raw_values(raw_values);
+            assert_round_trips(&transcoder);
+        }
+    }
+
+    fn assert_round_trips(transcoder: &ContractMessageTranscoder) {
+        let registry = transcoder.metadata().registry();
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for ty in &registry.types {
+            for _ in 0..16 {
+                let mut encoded = Vec::new();
+                if arbitrary_encoding(registry, ty.id, &mut rng, &mut encoded).is_none() {
+                    break
+                }
+                let value = transcoder
+                    .decode(ty.id, &mut &encoded[..])
+                    .unwrap_or_else(|err| panic!("{}: {err:#}", ty.ty.path));
+
+                let mut reencoded = Vec::new();
+                transcoder
+                    .encode_value(ty.id, &value, &mut reencoded)
+                    .unwrap_or_else(|err| panic!("{}: {value}: {err:#}", ty.ty.path));
+                assert_eq!(reencoded, encoded, "{}: {value}", ty.ty.path);
+                assert_eq!(
+                    transcoder.decode(ty.id, &mut &reencoded[..]).unwrap(),
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encode_value_names_the_path_of_the_failing_field() -> Result<()> {
+        let transcoder = ContractMessageTranscoder::new(generate_metadata());
+        let config = transcoder
+            .metadata()
+            .spec()
+            .messages()
+            .iter()
+            .find(|message| message.label() == "set_config")
+            .unwrap()
+            .args()[0]
+            .ty()
+            .ty()
+            .id;
+        let value = scon::parse_value(
+            r#"Config { fee: 1, admin: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY,
+                limit: None, rate: Rate { numerator: 1, denominator: 100 },
+                tiers: [Tier { threshold: 10, bonus: 1 }, Tier { threshold: 20, bonus: -1 }] }"#,
+        )?;
+
+        let err = transcoder
+            .encode_value(config, &value, &mut Vec::new())
+            .unwrap_err();
+        assert!(err.to_string().starts_with("tiers[1].bonus: "), "{err:#}");
+        Ok(())
+    }
+
     #[test]
     #[should_panic(
         expected = "input length was longer than expected by 1 byte(s).\nManaged to decode `flip` but `00` bytes were left unread"
@@ -29,37 +29,165 @@ use std::fmt::{
     Result,
 };
 
-/// Wraps Value for custom Debug impl to provide pretty-printed Display
-struct DisplayValue<'a>(&'a Value);
+/// Limits how much of a value is displayed, unlimited by default.
+#[derive(Clone, Copy, Default)]
+struct Limits {
+    /// The maximum number of elements of sequences and unnamed maps.
+    max_items: Option<usize>,
+    /// The number of levels of nested maps, tuples and sequences to expand.
+    max_depth: Option<usize>,
+    /// The maximum number of characters of strings, literals and hex values.
+    max_len: Option<usize>,
+}
+
+impl Limits {
+    /// The limits of the values nested one level deeper.
+    fn nested(self) -> Self {
+        Self {
+            max_depth: self.max_depth.map(|depth| depth.saturating_sub(1)),
+            ..self
+        }
+    }
+}
+
+/// Wraps Value for custom Debug impl to provide pretty-printed Display, within the
+/// given limits.
+struct DisplayValue<'a>(&'a Value, Limits);
 
 impl<'a> Debug for DisplayValue<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let limits = self.1;
+        if limits.max_depth == Some(0)
+            && matches!(self.0, Value::Map(_) | Value::Tuple(_) | Value::Seq(_))
+        {
+            return <Omitted as Debug>::fmt(&Omitted, f)
+        }
+        let nested = limits.nested();
         match &self.0 {
             Value::Bool(boolean) => <bool as Debug>::fmt(boolean, f),
             Value::Char(character) => <char as Debug>::fmt(character, f),
             Value::UInt(uint) => <u128 as Display>::fmt(uint, f),
             Value::Int(integer) => <i128 as Display>::fmt(integer, f),
-            Value::Map(map) => <DisplayMap as Debug>::fmt(&DisplayMap(map), f),
-            Value::Tuple(tuple) => <DisplayTuple as Debug>::fmt(&DisplayTuple(tuple), f),
-            Value::String(string) => <String as Display>::fmt(string, f),
-            Value::Seq(seq) => <DisplaySeq as Debug>::fmt(&DisplaySeq(seq), f),
-            Value::Hex(hex) => <Hex as Debug>::fmt(hex, f),
-            Value::Literal(literal) => <String as Display>::fmt(literal, f),
+            Value::Map(map) => <DisplayMap as Debug>::fmt(&DisplayMap(map, nested), f),
+            Value::Tuple(tuple) => {
+                <DisplayTuple as Debug>::fmt(&DisplayTuple(tuple, nested), f)
+            }
+            Value::String(string) => elide(string, limits.max_len, f),
+            Value::Seq(seq) => <DisplaySeq as Debug>::fmt(&DisplaySeq(seq, nested), f),
+            Value::Hex(hex) => {
+                match limits.max_len {
+                    Some(_) => elide(&format!("{hex:#x}"), limits.max_len, f),
+                    None => <Hex as Debug>::fmt(hex, f),
+                }
+            }
+            Value::Literal(literal) => elide(literal, limits.max_len, f),
             Value::Unit => write!(f, "()"),
         }
     }
 }
 
+/// Writes the string, shortened to its first `max_len` characters followed by `…` if
+/// it is longer.
+fn elide(string: &str, max_len: Option<usize>, f: &mut Formatter<'_>) -> Result {
+    match max_len {
+        Some(max_len) if string.chars().count() > max_len => {
+            let shown: String = string.chars().take(max_len).collect();
+            write!(f, "{shown}…")
+        }
+        _ => <str as Display>::fmt(string, f),
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        <DisplayTruncated as Display>::fmt(&DisplayTruncated(self, Limits::default()), f)
+    }
+}
+
+impl Value {
+    /// Display the value showing at most `max_items` elements of each sequence and
+    /// unnamed map, followed by the number of omitted elements.
+    pub fn display_truncated(&self, max_items: usize) -> impl Display + '_ {
+        let limits = Limits {
+            max_items: Some(max_items),
+            ..Limits::default()
+        };
+        DisplayTruncated(self, limits)
+    }
+
+    /// Display the value expanding at most `max_depth` levels of nested maps, tuples
+    /// and sequences, the structures below are displayed as `…`.
+    pub fn display_depth(&self, max_depth: usize) -> impl Display + '_ {
+        let limits = Limits {
+            max_depth: Some(max_depth),
+            ..Limits::default()
+        };
+        DisplayTruncated(self, limits)
+    }
+
+    /// Display the value on a single line, showing only the first `max_len` characters
+    /// of longer strings, literals and hex values, e.g. of account ids and hashes.
+    pub fn display_elided(&self, max_len: usize) -> impl Display + '_ {
+        let limits = Limits {
+            max_len: Some(max_len),
+            ..Limits::default()
+        };
+        DisplayTruncated(self, limits)
+    }
+
+    /// Returns `true` if any sequence or unnamed map within the value has more than
+    /// `max_items` elements, i.e. if [`Value::display_truncated`] omits elements.
+    pub fn exceeds_items(&self, max_items: usize) -> bool {
         match self {
-            Value::String(string) => <String as Display>::fmt(string, f),
-            value => <DisplayValue as Debug>::fmt(&DisplayValue(value), f),
+            Value::Map(map) => {
+                (map.ident.is_none() && map.map.len() > max_items)
+                    || map.map.values().any(|value| value.exceeds_items(max_items))
+            }
+            Value::Tuple(tuple) => {
+                tuple
+                    .values
+                    .iter()
+                    .any(|value| value.exceeds_items(max_items))
+            }
+            Value::Seq(seq) => {
+                seq.elems.len() > max_items
+                    || seq.elems.iter().any(|value| value.exceeds_items(max_items))
+            }
+            _ => false,
+        }
+    }
+}
+
+struct DisplayTruncated<'a>(&'a Value, Limits);
+
+impl<'a> Display for DisplayTruncated<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            Value::String(string) => elide(string, self.1.max_len, f),
+            value => <DisplayValue as Debug>::fmt(&DisplayValue(value, self.1), f),
         }
     }
 }
 
-struct DisplayMap<'a>(&'a Map);
+/// Displayed in place of the elements omitted from a truncated collection.
+struct MoreItems(usize);
+
+impl Debug for MoreItems {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "… {} more items", self.0)
+    }
+}
+
+/// Displayed in place of the values of the entries omitted from a truncated map.
+struct Omitted;
+
+impl Debug for Omitted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "…")
+    }
+}
+
+struct DisplayMap<'a>(&'a Map, Limits);
 
 impl<'a> Debug for DisplayMap<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -67,14 +195,19 @@ impl<'a> Debug for DisplayMap<'a> {
             Some(ref name) => {
                 let mut builder = f.debug_struct(name);
                 for (name, value) in self.0.map.iter() {
-                    builder.field(&format!("{name}"), &DisplayValue(value));
+                    builder.field(&format!("{name}"), &DisplayValue(value, self.1));
                 }
                 builder.finish()
             }
             None => {
+                let len = self.0.map.len();
+                let shown = self.1.max_items.unwrap_or(len).min(len);
                 let mut builder = f.debug_map();
-                for (name, value) in self.0.map.iter() {
-                    builder.entry(name, &DisplayValue(value));
+                for (name, value) in self.0.map.iter().take(shown) {
+                    builder.entry(name, &DisplayValue(value, self.1));
+                }
+                if shown < len {
+                    builder.entry(&MoreItems(len - shown), &Omitted);
                 }
                 builder.finish()
             }
@@ -82,26 +215,31 @@ impl<'a> Debug for DisplayMap<'a> {
     }
 }
 
-struct DisplayTuple<'a>(&'a Tuple);
+struct DisplayTuple<'a>(&'a Tuple, Limits);
 
 impl<'a> Debug for DisplayTuple<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let name = self.0.ident.as_ref().map_or("", |s| s.as_str());
         let mut builder = f.debug_tuple(name);
         for value in self.0.values.iter() {
-            builder.field(&DisplayValue(value));
+            builder.field(&DisplayValue(value, self.1));
         }
         builder.finish()
     }
 }
 
-struct DisplaySeq<'a>(&'a Seq);
+struct DisplaySeq<'a>(&'a Seq, Limits);
 
 impl<'a> Debug for DisplaySeq<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let len = self.0.elems.len();
+        let shown = self.1.max_items.unwrap_or(len).min(len);
         let mut builder = f.debug_list();
-        for elem in &self.0.elems {
-            builder.entry(&DisplayValue(elem));
+        for elem in &self.0.elems[..shown] {
+            builder.entry(&DisplayValue(elem, self.1));
+        }
+        if shown < len {
+            builder.entry(&MoreItems(len - shown));
         }
         builder.finish()
     }
@@ -126,6 +264,7 @@ impl LowerHex for Hex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
 
     #[test]
     fn display_map() {
@@ -142,4 +281,80 @@ mod tests {
             "alternate indented (pretty)"
         );
     }
+
+    #[test]
+    fn display_truncated_seq() {
+        let seq = Value::Seq((0..5).map(Value::UInt).collect::<Vec<_>>().into());
+        assert_eq!(
+            "[0, 1, … 3 more items]",
+            seq.display_truncated(2).to_string()
+        );
+        assert_eq!("[0, 1, 2, 3, 4]", seq.display_truncated(5).to_string());
+        assert_eq!("[0, 1, 2, 3, 4]", seq.to_string());
+        assert!(seq.exceeds_items(2));
+        assert!(!seq.exceeds_items(5));
+    }
+
+    #[test]
+    fn display_truncated_nested_map() {
+        let map = Value::Map(Map::new(
+            None,
+            (0..3)
+                .map(|i| (Value::UInt(i), Value::Seq(vec![Value::UInt(i); 3].into())))
+                .collect(),
+        ));
+        assert_eq!(
+            "{UInt(0): [0, … 2 more items], … 2 more items: …}",
+            map.display_truncated(1).to_string()
+        );
+        assert!(map.exceeds_items(2));
+        assert!(!map.exceeds_items(3));
+    }
+
+    #[test]
+    fn display_depth_collapses_nested_structures() {
+        let map = Value::Map(Map::new(
+            Some("M"),
+            vec![
+                (Value::String("a".into()), Value::UInt(1)),
+                (
+                    Value::String("b".into()),
+                    Value::Seq(vec![Value::Seq(vec![Value::UInt(2)].into())].into()),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        assert_eq!("…", map.display_depth(0).to_string());
+        assert_eq!("M { a: 1, b: … }", map.display_depth(1).to_string());
+        assert_eq!("M { a: 1, b: […] }", map.display_depth(2).to_string());
+        assert_eq!("M { a: 1, b: [[2]] }", map.display_depth(3).to_string());
+    }
+
+    #[test]
+    fn display_elided_shortens_long_values() {
+        let map = Value::Map(Map::new(
+            Some("Transfer"),
+            vec![
+                (
+                    Value::String("from".into()),
+                    Value::Literal(
+                        "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+                    ),
+                ),
+                (
+                    Value::String("hash".into()),
+                    Value::Hex(Hex::from_str("0x0102030405").unwrap()),
+                ),
+                (Value::String("memo".into()), Value::String("ok".into())),
+                (Value::String("value".into()), Value::UInt(1_000_000)),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        assert_eq!(
+            "Transfer { from: 5Grw…, hash: 0x01…, memo: ok, value: 1000000 }",
+            map.display_elided(4).to_string()
+        );
+    }
 }
@@ -38,6 +38,7 @@ use nom::{
     multi::{
         many0,
         separated_list0,
+        separated_list1,
     },
     sequence::{
         delimited,
@@ -57,8 +58,16 @@ use std::str::FromStr as _;
 
 /// Attempt to parse a SCON value
 pub fn parse_value(input: &str) -> anyhow::Result<Value> {
-    let (_, value) = scon_value(input)
+    let (rest, value) = scon_value(input)
         .map_err(|err| anyhow::anyhow!("Error parsing Value: {}", err))?;
+    if !rest.is_empty() {
+        anyhow::bail!(
+            "Unexpected `{}` at position {} in \"{}\"",
+            rest,
+            input.len() - rest.len(),
+            input
+        )
+    }
     Ok(value)
 }
 
@@ -71,6 +80,7 @@ fn scon_value(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
         scon_map,
         scon_string,
         scon_literal,
+        scon_denominated,
         scon_integer,
         scon_bool,
         scon_char,
@@ -142,19 +152,45 @@ fn rust_ident(input: &str) -> IResult<&str, &str, ErrorTree<&str>> {
         .parse(input)
 }
 
+/// Parse a rust ident, optionally qualified with a path e.g. `MyEnum::Limit`, returning
+/// only the last segment.
+fn rust_path_ident(input: &str) -> IResult<&str, &str, ErrorTree<&str>> {
+    separated_list1(tag("::"), rust_ident)
+        .map(|segments| *segments.last().expect("at least one segment; qed"))
+        .parse(input)
+}
+
+/// Parse a denominated balance literal e.g. `1.5UNIT` or `3mDOT`, which is converted by
+/// the custom encoder of the `Balance` type. The token symbol must contain an uppercase
+/// letter.
+fn scon_denominated(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
+    let number = separated_list1(char('_'), digit1);
+    let fraction = pair(char('.'), digit1);
+    let symbol = take_while1(|c: char| c.is_alphabetic())
+        .verify(|symbol: &&str| symbol.chars().any(char::is_uppercase));
+    tuple((number, fraction.opt(), symbol))
+        .recognize()
+        .map(|literal: &str| Value::Literal(literal.to_string()))
+        .parse(input)
+}
+
 /// Parse a signed or unsigned integer literal, supports optional Rust style underscore
 /// separators.
+///
+/// Integers exceeding the range of `i128` or `u128` are parsed as a [`Value::Literal`],
+/// for types wider than 128 bits such as `U256`.
 fn scon_integer(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
     let sign = alt((char('+'), char('-')));
-    pair(sign.opt(), separated_list0(char('_'), digit1))
-        .map_res(|(sign, parts)| {
+    pair(sign.opt(), separated_list1(char('_'), digit1))
+        .map(|(sign, parts)| {
             let digits = parts.join("");
-            if let Some(sign) = sign {
+            let int = if let Some(sign) = sign {
                 let s = format!("{sign}{digits}");
-                s.parse::<i128>().map(Value::Int)
+                s.parse::<i128>().map(Value::Int).map_err(|_| s)
             } else {
-                digits.parse::<u128>().map(Value::UInt)
-            }
+                digits.parse::<u128>().map(Value::UInt).map_err(|_| digits)
+            };
+            int.unwrap_or_else(Value::Literal)
         })
         .parse(input)
 }
@@ -192,7 +228,7 @@ fn scon_tuple(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
         .preceded_by(ws(char('(')))
         .terminated(pair(ws(char(',')).opt(), ws(char(')'))));
 
-    tuple((ws(rust_ident).opt(), tuple_body))
+    tuple((ws(rust_path_ident).opt(), tuple_body))
         .map(|(ident, v)| Value::Tuple(Tuple::new(ident, v.into_iter().collect())))
         .parse(input)
 }
@@ -200,7 +236,7 @@ fn scon_tuple(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
 /// Parse a rust ident on its own which could represent a struct with no fields or a enum
 /// unit variant e.g. "None"
 fn scon_unit_tuple(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
-    rust_ident
+    rust_path_ident
         .map(|ident| Value::Tuple(Tuple::new(Some(ident), Vec::new())))
         .parse(input)
 }
@@ -219,7 +255,7 @@ fn scon_map(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
     .preceded_by(ws(opening))
     .terminated(pair(ws(char(',')).opt(), ws(closing)));
 
-    tuple((ws(rust_ident).opt(), map_body))
+    tuple((ws(rust_path_ident).opt(), map_body))
         .map(|(ident, v)| Value::Map(Map::new(ident, v.into_iter().collect())))
         .parse(input)
 }
@@ -313,12 +349,23 @@ mod tests {
             ("", Value::UInt(340282366920938463463374607431768211455))
         );
 
-        // too many digits
-        assert_matches!(
-            scon_integer("3402823669209384634633746074317682114550"),
-            Err(nom::Err::Error(_))
-        );
         assert_matches!(scon_integer("abc123"), Err(nom::Err::Error(_)));
+
+        // integers exceeding 128 bits, for wider integer types
+        assert_eq!(
+            scon_integer("340282366920938463463374607431768211456").unwrap(),
+            (
+                "",
+                Value::Literal("340282366920938463463374607431768211456".into())
+            )
+        );
+        assert_eq!(
+            scon_integer("-170_141_183_460_469_231_731_687_303_715_884_105_729").unwrap(),
+            (
+                "",
+                Value::Literal("-170141183460469231731687303715884105729".into())
+            )
+        );
     }
 
     #[test]
@@ -418,6 +465,21 @@ mod tests {
         assert!(rust_ident("1notok").is_err());
     }
 
+    #[test]
+    fn test_denominated() {
+        assert_scon_value("1.5UNIT", Value::Literal("1.5UNIT".into()));
+        assert_scon_value("3DOT", Value::Literal("3DOT".into()));
+        assert_scon_value("1_000mDOT", Value::Literal("1_000mDOT".into()));
+        assert_scon_value("1000", Value::UInt(1000));
+    }
+
+    #[test]
+    fn test_rust_path_ident() {
+        assert_eq!(rust_path_ident("Limit").unwrap(), ("", "Limit"));
+        assert_eq!(rust_path_ident("MyEnum::Limit").unwrap(), ("", "Limit"));
+        assert_eq!(rust_path_ident("a::b::C {").unwrap(), (" {", "C"));
+    }
+
     #[test]
     fn test_literal() {
         assert_eq!(
@@ -588,6 +650,33 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_path_qualified_variant() {
+        assert_scon_value(
+            "MyEnum::Limit { max: 10, strict: true }",
+            Value::Map(Map::new(
+                Some("Limit"),
+                vec![
+                    (Value::String("max".into()), Value::UInt(10)),
+                    (Value::String("strict".into()), Value::Bool(true)),
+                ]
+                .into_iter()
+                .collect(),
+            )),
+        );
+        assert_scon_value(
+            "Instruction::Transfer(5, true)",
+            Value::Tuple(Tuple::new(
+                Some("Transfer"),
+                vec![Value::UInt(5), Value::Bool(true)],
+            )),
+        );
+        assert_scon_value(
+            "MyEnum::Pause",
+            Value::Tuple(Tuple::new(Some("Pause"), Vec::new())),
+        );
+    }
+
     #[test]
     fn test_option() {
         assert_scon_value(
@@ -25,8 +25,10 @@ use super::{
         PathKey,
         TypesByPath,
     },
+    json::JsonConverter,
     scon::Value,
     AccountId32,
+    SUBSTRATE_SS58_PREFIX,
 };
 
 use anyhow::Result;
@@ -58,11 +60,39 @@ impl Transcoder {
         value: &Value,
         output: &mut O,
     ) -> Result<()>
+    where
+        O: Output + Debug,
+    {
+        self.encode_with_type_name(registry, type_id, None, value, output)
+    }
+
+    /// Encodes a `value` of the type with the `type_id`, declared with the `type_name`
+    /// in the metadata, e.g. the display name of an argument such as `Balance`.
+    pub fn encode_with_type_name<O>(
+        &self,
+        registry: &PortableRegistry,
+        type_id: u32,
+        type_name: Option<&str>,
+        value: &Value,
+        output: &mut O,
+    ) -> Result<()>
     where
         O: Output + Debug,
     {
         let encoder = Encoder::new(registry, &self.env_types);
-        encoder.encode(type_id, value, output)
+        encoder.encode_with_type_name(type_id, type_name, value, output)
+    }
+
+    /// Convert a JSON value into a [`Value`] of the given type, which can then be
+    /// encoded.
+    pub fn value_from_json(
+        &self,
+        registry: &PortableRegistry,
+        type_id: u32,
+        json: &serde_json::Value,
+    ) -> Result<Value> {
+        let converter = JsonConverter::new(registry, &self.env_types);
+        converter.convert(type_id, json)
     }
 
     pub fn decode(
@@ -81,6 +111,7 @@ pub struct TranscoderBuilder {
     types_by_path: TypesByPath,
     encoders: HashMap<u32, Box<dyn CustomTypeEncoder>>,
     decoders: HashMap<u32, Box<dyn CustomTypeDecoder>>,
+    balance: Option<env_types::Balance>,
 }
 
 impl TranscoderBuilder {
@@ -94,12 +125,25 @@ impl TranscoderBuilder {
             types_by_path,
             encoders: HashMap::new(),
             decoders: HashMap::new(),
+            balance: None,
         }
     }
 
     pub fn with_default_custom_type_transcoders(self) -> Self {
-        self.register_custom_type_transcoder::<AccountId32, _>(env_types::AccountId)
-            .register_custom_type_decoder::<primitive_types::H256, _>(env_types::Hash)
+        self.with_ss58_prefix_custom_type_transcoders(SUBSTRATE_SS58_PREFIX)
+    }
+
+    /// Register the default custom type transcoders, displaying decoded accounts with
+    /// the given SS58 address prefix.
+    pub fn with_ss58_prefix_custom_type_transcoders(self, ss58_prefix: u16) -> Self {
+        self.register_custom_type_transcoder::<AccountId32, _>(
+            env_types::AccountId::with_ss58_prefix(ss58_prefix),
+        )
+        .register_custom_type_decoder::<primitive_types::H256, _>(env_types::Hash)
+        .register_custom_type_decoder_for_path(
+            PathKey::new(&env_types::WEIGHT_PATH),
+            env_types::Weight,
+        )
     }
 
     pub fn register_custom_type_transcoder<T, U>(self, transcoder: U) -> Self
@@ -140,19 +184,75 @@ impl TranscoderBuilder {
         this
     }
 
-    pub fn register_custom_type_decoder<T, U>(self, encoder: U) -> Self
+    /// Register a custom encoder for the type with the given id, e.g. for an environment
+    /// type without a path such as a primitive `Balance`.
+    pub fn register_custom_type_encoder_for_id<U>(self, type_id: u32, encoder: U) -> Self
+    where
+        U: CustomTypeEncoder + 'static,
+    {
+        let mut this = self;
+        let existing = this.encoders.insert(type_id, Box::new(encoder));
+        tracing::debug!("Registered custom encoder for type `{:?}`", type_id);
+        if existing.is_some() {
+            panic!("Attempted to register encoder with existing type id {type_id:?}");
+        }
+        this
+    }
+
+    /// Register a custom decoder for the type with the given id, e.g. for an environment
+    /// type declared by the contract under a path of its own.
+    pub fn register_custom_type_decoder_for_id<U>(self, type_id: u32, decoder: U) -> Self
+    where
+        U: CustomTypeDecoder + 'static,
+    {
+        let mut this = self;
+        let existing = this.decoders.insert(type_id, Box::new(decoder));
+        tracing::debug!("Registered custom decoder for type `{:?}`", type_id);
+        if existing.is_some() {
+            panic!("Attempted to register decoder with existing type id {type_id:?}");
+        }
+        this
+    }
+
+    /// Register the encoder of the contract's `Balance` type, which applies to the
+    /// arguments and fields declared as a `Balance` only.
+    pub fn register_balance_encoder(self, balance: env_types::Balance) -> Self {
+        Self {
+            balance: Some(balance),
+            ..self
+        }
+    }
+
+    /// Returns `true` if a custom encoder is registered for the type with the given id.
+    pub fn has_custom_type_encoder(&self, type_id: u32) -> bool {
+        self.encoders.contains_key(&type_id)
+    }
+
+    pub fn register_custom_type_decoder<T, U>(self, decoder: U) -> Self
     where
         T: TypeInfo + 'static,
         U: CustomTypeDecoder + 'static,
+    {
+        self.register_custom_type_decoder_for_path(PathKey::from_type::<T>(), decoder)
+    }
+
+    /// Register a custom decoder for the type with the given path, e.g. for a type of a
+    /// crate which is not a dependency.
+    pub fn register_custom_type_decoder_for_path<U>(
+        self,
+        path_key: PathKey,
+        decoder: U,
+    ) -> Self
+    where
+        U: CustomTypeDecoder + 'static,
     {
         let mut this = self;
 
-        let path_key = PathKey::from_type::<T>();
         let type_id = this.types_by_path.get(&path_key);
 
         match type_id {
             Some(type_id) => {
-                let existing = this.decoders.insert(*type_id, Box::new(encoder));
+                let existing = this.decoders.insert(*type_id, Box::new(decoder));
                 tracing::debug!("Registered custom decoder for type `{:?}`", type_id);
                 if existing.is_some() {
                     panic!(
@@ -170,7 +270,8 @@ impl TranscoderBuilder {
     }
 
     pub fn done(self) -> Transcoder {
-        let env_types_transcoder = EnvTypesTranscoder::new(self.encoders, self.decoders);
+        let env_types_transcoder =
+            EnvTypesTranscoder::new(self.encoders, self.decoders, self.balance);
         Transcoder::new(env_types_transcoder)
     }
 }
@@ -307,34 +408,58 @@ mod tests {
     fn transcode_byte_array() -> Result<()> {
         transcode_roundtrip::<[u8; 2]>(
             r#"0x0000"#,
-            Value::Seq(vec![Value::UInt(0x00), Value::UInt(0x00)].into()),
+            Value::Hex(Hex::from_str("0x0000")?),
         )?;
         transcode_roundtrip::<[u8; 4]>(
             r#"0xDEADBEEF"#,
-            Value::Seq(
-                vec![
-                    Value::UInt(0xDE),
-                    Value::UInt(0xAD),
-                    Value::UInt(0xBE),
-                    Value::UInt(0xEF),
-                ]
-                .into(),
-            ),
+            Value::Hex(Hex::from_str("0xdeadbeef")?),
         )?;
         transcode_roundtrip::<[u8; 4]>(
             r#"0xdeadbeef"#,
-            Value::Seq(
-                vec![
-                    Value::UInt(0xDE),
-                    Value::UInt(0xAD),
-                    Value::UInt(0xBE),
-                    Value::UInt(0xEF),
-                ]
-                .into(),
-            ),
+            Value::Hex(Hex::from_str("0xdeadbeef")?),
         )
     }
 
+    #[test]
+    fn transcode_byte_vec() -> Result<()> {
+        transcode_roundtrip::<Vec<u8>>(
+            r#"0xdeadbeef"#,
+            Value::Hex(Hex::from_str("0xdeadbeef")?),
+        )?;
+        transcode_roundtrip::<Vec<u8>>(
+            "[222, 173, 190, 239]",
+            Value::Hex(Hex::from_str("0xdeadbeef")?),
+        )
+    }
+
+    #[test]
+    fn encode_byte_array_hex_length_mismatch() -> Result<()> {
+        let (registry, ty) = registry_with_type::<[u8; 32]>()?;
+        let transcoder = TranscoderBuilder::new(&registry).done();
+        let value = scon::parse_value("0xdeadbeef")?;
+
+        let err = transcoder
+            .encode(&registry, ty, &value, &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected 32 bytes for `[u8; 32]`, provided 4 bytes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn encode_hex_for_non_byte_seq_fails() -> Result<()> {
+        let (registry, ty) = registry_with_type::<Vec<u32>>()?;
+        let transcoder = TranscoderBuilder::new(&registry).done();
+        let value = scon::parse_value("0xdeadbeef")?;
+
+        assert!(transcoder
+            .encode(&registry, ty, &value, &mut Vec::new())
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn transcode_array() -> Result<()> {
         transcode_roundtrip::<[u32; 3]>(
@@ -380,15 +505,7 @@ mod tests {
                 vec![
                     Value::UInt(1),
                     Value::String("ink!".to_string()),
-                    Value::Seq(
-                        vec![
-                            Value::UInt(0xDE),
-                            Value::UInt(0xAD),
-                            Value::UInt(0xBE),
-                            Value::UInt(0xEF),
-                        ]
-                        .into(),
-                    ),
+                    Value::Hex(Hex::from_str("0xdeadbeef")?),
                 ],
             )),
         )
@@ -417,15 +534,7 @@ mod tests {
                     ),
                     (
                         Value::String("c".to_string()),
-                        Value::Seq(
-                            vec![
-                                Value::UInt(0xDE),
-                                Value::UInt(0xAD),
-                                Value::UInt(0xBE),
-                                Value::UInt(0xEF),
-                            ]
-                            .into(),
-                        ),
+                        Value::Hex(Hex::from_str("0xdeadbeef")?),
                     ),
                     (
                         Value::String("d".to_string()),
@@ -439,15 +548,7 @@ mod tests {
                                     ),
                                     (
                                         Value::String("c".to_string()),
-                                        Value::Seq(
-                                            vec![
-                                                Value::UInt(0xDE),
-                                                Value::UInt(0xAD),
-                                                Value::UInt(0xBE),
-                                                Value::UInt(0xEF),
-                                            ]
-                                            .into(),
-                                        ),
+                                        Value::Hex(Hex::from_str("0xdeadbeef")?),
                                     ),
                                     (
                                         Value::String("d".to_string()),
@@ -522,15 +623,7 @@ mod tests {
                     ),
                     (
                         Value::String("c".to_string()),
-                        Value::Seq(
-                            vec![
-                                Value::UInt(0xDE),
-                                Value::UInt(0xAD),
-                                Value::UInt(0xBE),
-                                Value::UInt(0xEF),
-                            ]
-                            .into(),
-                        ),
+                        Value::Hex(Hex::from_str("0xdeadbeef")?),
                     ),
                 ]
                 .into_iter()
@@ -552,15 +645,7 @@ mod tests {
                 vec![
                     Value::UInt(1),
                     Value::String("ink!".to_string()),
-                    Value::Seq(
-                        vec![
-                            Value::UInt(0xDE),
-                            Value::UInt(0xAD),
-                            Value::UInt(0xBE),
-                            Value::UInt(0xEF),
-                        ]
-                        .into(),
-                    ),
+                    Value::Hex(Hex::from_str("0xdeadbeef")?),
                 ],
             )),
         )
@@ -576,15 +661,7 @@ mod tests {
             r#"0xDEADBEEF"#,
             Value::Tuple(Tuple::new(
                 Some("S"),
-                vec![Value::Seq(
-                    vec![
-                        Value::UInt(0xDE),
-                        Value::UInt(0xAD),
-                        Value::UInt(0xBE),
-                        Value::UInt(0xEF),
-                    ]
-                    .into(),
-                )],
+                vec![Value::Hex(Hex::from_str("0xdeadbeef")?)],
             )),
         )
     }
@@ -595,15 +672,7 @@ mod tests {
             r#"0xDEADBEEF"#,
             Value::Tuple(Tuple::new(
                 None,
-                vec![Value::Seq(
-                    vec![
-                        Value::UInt(0xDE),
-                        Value::UInt(0xAD),
-                        Value::UInt(0xBE),
-                        Value::UInt(0xEF),
-                    ]
-                    .into(),
-                )],
+                vec![Value::Hex(Hex::from_str("0xdeadbeef")?)],
             )),
         )
     }
@@ -724,11 +793,8 @@ mod tests {
 
     #[test]
     fn transcode_account_id_custom_ss58_encoding_seq() -> Result<()> {
-        let hex_to_bytes = |hex: &str| -> Result<Value> {
-            let hex = Hex::from_str(hex)?;
-            let values = hex.bytes().iter().map(|b| Value::UInt((*b).into()));
-            Ok(Value::Seq(Seq::new(values.collect())))
-        };
+        let hex_value =
+            |hex: &str| -> Result<Value> { Ok(Value::Hex(Hex::from_str(hex)?)) };
 
         transcode_roundtrip::<Vec<AccountId32>>(
             r#"[
@@ -740,13 +806,13 @@ mod tests {
                     Value::Tuple(
                         Tuple::new(
                             Some("AccountId32"),
-                            vec![hex_to_bytes("0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")?]
+                            vec![hex_value("0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")?]
                         )
                     ),
                     Value::Tuple(
                         Tuple::new(
                             Some("AccountId32"),
-                            vec![hex_to_bytes("0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")?]
+                            vec![hex_value("0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")?]
                         )
                     )
                 ]
@@ -784,6 +850,63 @@ mod tests {
         )
     }
 
+    #[test]
+    fn transcode_u256_as_decimal() -> Result<()> {
+        #[allow(dead_code)]
+        #[derive(TypeInfo)]
+        struct S {
+            amount: primitive_types::U256,
+        }
+
+        let max = primitive_types::U256::MAX.to_string();
+        transcode_roundtrip::<S>(
+            &format!("S(amount: {max})"),
+            Value::Map(Map::new(
+                Some("S"),
+                vec![(Value::String("amount".into()), Value::Literal(max.clone()))]
+                    .into_iter()
+                    .collect(),
+            )),
+        )?;
+        transcode_roundtrip::<primitive_types::U256>(
+            "0x0100",
+            Value::Literal("256".into()),
+        )?;
+        transcode_roundtrip::<primitive_types::U256>(
+            "\"1_000\"",
+            Value::Literal("1000".into()),
+        )?;
+
+        let (registry, ty) = registry_with_type::<primitive_types::U256>()?;
+        let transcoder = TranscoderBuilder::new(&registry).done();
+        let value = scon::parse_value(&format!("{max}0"))?;
+        let err = transcoder
+            .encode(&registry, ty, &value, &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "number too large to fit in u256");
+        Ok(())
+    }
+
+    #[test]
+    fn decode_weight_as_ref_time_and_proof_size() -> Result<()> {
+        let (registry, ty) = registry_with_type::<sp_weights::Weight>()?;
+        let encoded = sp_weights::Weight::from_parts(1_000, 64).encode();
+
+        let transcoder = TranscoderBuilder::new(&registry)
+            .with_default_custom_type_transcoders()
+            .done();
+        let decoded = transcoder.decode(&registry, ty, &mut &encoded[..])?;
+        assert_eq!(decoded, Value::Literal("1000/64".into()));
+
+        let raw = TranscoderBuilder::new(&registry).done();
+        let decoded = raw.decode(&registry, ty, &mut &encoded[..])?;
+        assert_eq!(
+            decoded.to_string(),
+            "Weight { ref_time: 1000, proof_size: 64 }"
+        );
+        Ok(())
+    }
+
     #[test]
     fn transcode_compact_primitives() -> Result<()> {
         transcode_roundtrip::<scale::Compact<u8>>(r#"33"#, Value::UInt(33))?;
@@ -14,7 +14,48 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+use itertools::Itertools;
+use scale_info::{
+    PortableRegistry,
+    TypeDef,
+};
+
 /// Decode hex string with or without 0x prefix
 pub fn decode_hex(input: &str) -> Result<Vec<u8>, hex::FromHexError> {
     hex::decode(input.trim_start_matches("0x"))
 }
+
+/// Returns a human readable name of the type with the given id, e.g. `Vec<u128>`.
+pub fn type_name(registry: &PortableRegistry, type_id: u32) -> String {
+    let Some(ty) = registry.resolve(type_id) else {
+        return format!("type {type_id}")
+    };
+    let name = |id| type_name(registry, id);
+    match &ty.type_def {
+        TypeDef::Composite(_) | TypeDef::Variant(_) => {
+            let params = ty
+                .type_params
+                .iter()
+                .filter_map(|param| param.ty.map(|ty| name(ty.id)))
+                .collect::<Vec<_>>();
+            if params.is_empty() {
+                ty.path.to_string()
+            } else {
+                format!("{}<{}>", ty.path, params.join(", "))
+            }
+        }
+        TypeDef::Sequence(sequence) => format!("Vec<{}>", name(sequence.type_param.id)),
+        TypeDef::Array(array) => {
+            format!("[{}; {}]", name(array.type_param.id), array.len)
+        }
+        TypeDef::Tuple(tuple) => {
+            format!(
+                "({})",
+                tuple.fields.iter().map(|field| name(field.id)).join(", ")
+            )
+        }
+        TypeDef::Primitive(primitive) => format!("{primitive:?}").to_lowercase(),
+        TypeDef::Compact(compact) => format!("Compact<{}>", name(compact.type_param.id)),
+        TypeDef::BitSequence(_) => "BitVec".to_string(),
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    scon::Value,
+    util::decode_hex,
+};
+use anyhow::Result;
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+};
+
+/// A fixed width integer wider than 128 bits, e.g. `U256` or `I256`.
+///
+/// Besides the `u256` and `i256` primitives, these are newtypes named like `U256` or
+/// `I512` wrapping a little endian `[u64; N]` or `[u8; N]`, e.g.
+/// `primitive_types::U256`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WideInt {
+    /// The width of the integer in bytes.
+    bytes: usize,
+    /// Whether the integer is signed, i.e. two's complement.
+    signed: bool,
+}
+
+impl WideInt {
+    /// Returns the [`WideInt`] representation of the type, if it is one.
+    pub fn from_type(
+        registry: &PortableRegistry,
+        ty: &Type<PortableForm>,
+    ) -> Option<Self> {
+        match &ty.type_def {
+            TypeDef::Primitive(TypeDefPrimitive::U256) => {
+                Some(Self {
+                    bytes: 32,
+                    signed: false,
+                })
+            }
+            TypeDef::Primitive(TypeDefPrimitive::I256) => {
+                Some(Self {
+                    bytes: 32,
+                    signed: true,
+                })
+            }
+            TypeDef::Composite(composite) => {
+                let ident = ty.path.ident()?;
+                let signed = match ident.chars().next()? {
+                    'U' => false,
+                    'I' => true,
+                    _ => return None,
+                };
+                let bits = ident[1..].parse::<usize>().ok()?;
+                let [field] = &composite.fields[..] else {
+                    return None
+                };
+                let TypeDef::Array(array) = &registry.resolve(field.ty.id)?.type_def
+                else {
+                    return None
+                };
+                let elem_bytes = match registry.resolve(array.type_param.id)?.type_def {
+                    TypeDef::Primitive(TypeDefPrimitive::U8) => 1,
+                    TypeDef::Primitive(TypeDefPrimitive::U64) => 8,
+                    _ => return None,
+                };
+                let bytes = array.len as usize * elem_bytes;
+                (bits > 128 && bits == bytes * 8).then_some(Self { bytes, signed })
+            }
+            _ => None,
+        }
+    }
+
+    /// The width of the integer in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// The name of the integer type, e.g. `u256`.
+    fn name(&self) -> String {
+        let sign = if self.signed { 'i' } else { 'u' };
+        format!("{}{}", sign, self.bytes * 8)
+    }
+
+    /// Returns the little endian bytes of the integer given as a decimal or a `0x`
+    /// prefixed hex number.
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>> {
+        let (negative, magnitude) = match value {
+            Value::UInt(uint) => (false, self.parse_le(&uint.to_le_bytes())?),
+            Value::Int(int) => {
+                (*int < 0, self.parse_le(&int.unsigned_abs().to_le_bytes())?)
+            }
+            Value::Hex(hex) => (false, self.parse_be(hex.bytes())?),
+            Value::String(s) | Value::Literal(s) => {
+                let s = s.replace(&['_', ','][..], "");
+                if s.starts_with("0x") {
+                    (false, self.parse_be(&decode_hex(&s)?)?)
+                } else {
+                    let (negative, digits) = match s.strip_prefix('-') {
+                        Some(digits) => (true, digits),
+                        None => (false, s.strip_prefix('+').unwrap_or(&s)),
+                    };
+                    (negative, self.parse_decimal(digits)?)
+                }
+            }
+            _ => {
+                anyhow::bail!(
+                    "Expected a {} or a String value, got {}",
+                    self.name(),
+                    value
+                )
+            }
+        };
+        self.apply_sign(negative, magnitude)
+    }
+
+    /// Returns the decimal representation of the little endian bytes of the integer.
+    pub fn decode(&self, bytes: &[u8]) -> Value {
+        let negative = self.signed && bytes.last().is_some_and(|b| b & 0x80 != 0);
+        let mut magnitude = bytes.to_vec();
+        if negative {
+            negate(&mut magnitude);
+        }
+        let mut digits = Vec::new();
+        while magnitude.iter().any(|b| *b != 0) {
+            let mut remainder = 0u16;
+            for byte in magnitude.iter_mut().rev() {
+                let acc = (remainder << 8) | *byte as u16;
+                *byte = (acc / 10) as u8;
+                remainder = acc % 10;
+            }
+            digits.push(char::from(b'0' + remainder as u8));
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        if negative {
+            digits.push('-');
+        }
+        Value::Literal(digits.into_iter().rev().collect())
+    }
+
+    fn out_of_range(&self) -> anyhow::Error {
+        anyhow::anyhow!("number too large to fit in {}", self.name())
+    }
+
+    fn parse_le(&self, le: &[u8]) -> Result<Vec<u8>> {
+        let mut bytes = vec![0u8; self.bytes];
+        for (i, byte) in le.iter().enumerate() {
+            match bytes.get_mut(i) {
+                Some(b) => *b = *byte,
+                None if *byte == 0 => (),
+                None => return Err(self.out_of_range()),
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn parse_be(&self, be: &[u8]) -> Result<Vec<u8>> {
+        self.parse_le(&be.iter().rev().copied().collect::<Vec<_>>())
+    }
+
+    fn parse_decimal(&self, digits: &str) -> Result<Vec<u8>> {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            anyhow::bail!("invalid digit found in string")
+        }
+        let mut bytes = vec![0u8; self.bytes];
+        for digit in digits.bytes() {
+            let mut carry = (digit - b'0') as u16;
+            for byte in bytes.iter_mut() {
+                let acc = *byte as u16 * 10 + carry;
+                *byte = acc as u8;
+                carry = acc >> 8;
+            }
+            if carry != 0 {
+                return Err(self.out_of_range())
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Converts the magnitude to two's complement if negative, checking that it fits the
+    /// range of the integer.
+    fn apply_sign(&self, negative: bool, mut magnitude: Vec<u8>) -> Result<Vec<u8>> {
+        let is_zero = magnitude.iter().all(|b| *b == 0);
+        if !self.signed {
+            if negative && !is_zero {
+                anyhow::bail!("negative number cannot be encoded as {}", self.name())
+            }
+            return Ok(magnitude)
+        }
+        let high_bit = magnitude.last().is_some_and(|b| b & 0x80 != 0);
+        // the magnitude of the minimum value is the only one with the high bit set
+        let is_min = high_bit
+            && magnitude.last() == Some(&0x80)
+            && magnitude[..magnitude.len() - 1].iter().all(|b| *b == 0);
+        if high_bit && !(negative && is_min) {
+            return Err(self.out_of_range())
+        }
+        if negative {
+            negate(&mut magnitude);
+        }
+        Ok(magnitude)
+    }
+}
+
+/// Negates the little endian two's complement integer in place.
+fn negate(bytes: &mut [u8]) {
+    let mut carry = true;
+    for byte in bytes.iter_mut() {
+        let (negated, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = negated;
+        carry = overflow;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const U256: WideInt = WideInt {
+        bytes: 32,
+        signed: false,
+    };
+    const I256: WideInt = WideInt {
+        bytes: 32,
+        signed: true,
+    };
+
+    fn roundtrip(int: WideInt, input: &str) -> String {
+        let bytes = int.encode(&Value::String(input.to_string())).unwrap();
+        int.decode(&bytes).to_string()
+    }
+
+    #[test]
+    fn u256_roundtrip() {
+        let max = primitive_types::U256::MAX.to_string();
+        assert_eq!(roundtrip(U256, &max), max);
+        assert_eq!(roundtrip(U256, "0"), "0");
+        assert_eq!(roundtrip(U256, "1_000"), "1000");
+        assert_eq!(roundtrip(U256, &format!("0x{}", "ff".repeat(32))), max);
+
+        let bytes = U256.encode(&Value::UInt(u128::MAX)).unwrap();
+        let expected = primitive_types::U256::from(u128::MAX);
+        assert_eq!(bytes, scale::Encode::encode(&expected));
+    }
+
+    #[test]
+    fn i256_roundtrip() {
+        let min = format!("-{}", primitive_types::U256::one() << 255);
+        let max = ((primitive_types::U256::one() << 255) - 1).to_string();
+        assert_eq!(roundtrip(I256, &min), min);
+        assert_eq!(roundtrip(I256, &max), max);
+        assert_eq!(roundtrip(I256, "-1"), "-1");
+
+        let bytes = I256.encode(&Value::Int(-1)).unwrap();
+        assert_eq!(bytes, vec![0xff; 32]);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let overflow =
+            (primitive_types::U512::from(primitive_types::U256::MAX) + 1).to_string();
+        let err = U256.encode(&Value::String(overflow)).unwrap_err();
+        assert_eq!(err.to_string(), "number too large to fit in u256");
+
+        let err = U256
+            .encode(&Value::String(format!("0x01{}", "00".repeat(32))))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "number too large to fit in u256");
+
+        let overflow = (primitive_types::U256::one() << 255).to_string();
+        assert!(I256.encode(&Value::String(overflow)).is_err());
+        assert!(U256.encode(&Value::Int(-1)).is_err());
+        assert!(U256.encode(&Value::String("12a".into())).is_err());
+    }
+}